@@ -7,10 +7,10 @@ use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criteri
 use std::collections::HashMap;
 use std::time::Duration;
 
-use cliptions_core::commitment::{CommitmentGenerator, CommitmentVerifier};
+use cliptions_core::commitment::{CommitmentGenerator, CommitmentVerifier, HashAlgo};
 use cliptions_core::embedder::{EmbedderTrait, MockEmbedder};
 use cliptions_core::scoring::{
-    calculate_payouts, calculate_rankings, ClipBatchStrategy, ScoreValidator, ScoringStrategy,
+    calculate_payouts, rank_items, ClipBatchStrategy, ScoreValidator, ScoringStrategy,
 };
 
 fn benchmark_commitment_generation(c: &mut Criterion) {
@@ -75,6 +75,36 @@ fn benchmark_batch_commitment_verification(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_hash_algorithms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_verification_by_hash_algo");
+
+    for algo in [HashAlgo::Sha256, HashAlgo::Blake3] {
+        let generator = CommitmentGenerator::new().with_hash_algo(algo);
+        let verifier = CommitmentVerifier::new().with_hash_algo(algo);
+
+        let commitments: Vec<(String, String, String)> = (0..1000)
+            .map(|i| {
+                let message = format!("Message {}", i);
+                let salt = format!("salt_{}", i);
+                let commitment = generator.generate(&message, &salt).unwrap();
+                (message, salt, commitment)
+            })
+            .collect();
+        let commitment_refs: Vec<(&str, &str, &str)> = commitments
+            .iter()
+            .map(|(m, s, c)| (m.as_str(), s.as_str(), c.as_str()))
+            .collect();
+
+        group.bench_with_input(
+            BenchmarkId::new(algo.to_string(), commitment_refs.len()),
+            &commitment_refs,
+            |b, refs| b.iter(|| verifier.verify_batch(black_box(refs))),
+        );
+    }
+
+    group.finish();
+}
+
 fn benchmark_embedding_generation(c: &mut Criterion) {
     let embedder = MockEmbedder::clip_like();
 
@@ -125,11 +155,11 @@ fn benchmark_ranking_calculation(c: &mut Criterion) {
             .collect();
 
         group.bench_with_input(
-            BenchmarkId::new("calculate_rankings", num_guesses),
+            BenchmarkId::new("rank_items", num_guesses),
             &guesses,
             |b, guesses| {
                 b.iter(|| {
-                    calculate_rankings(
+                    rank_items(
                         black_box("test_image.jpg"),
                         black_box(guesses),
                         black_box(&validator),
@@ -191,12 +221,15 @@ fn benchmark_complete_scoring_pipeline(c: &mut Criterion) {
             |b, guesses| {
                 b.iter(|| {
                     // Complete pipeline: ranking + payout calculation
-                    let rankings = calculate_rankings(
+                    let rankings: Vec<(String, f64)> = rank_items(
                         black_box("test_image.jpg"),
                         black_box(guesses),
                         black_box(&validator),
                     )
-                    .unwrap();
+                    .unwrap()
+                    .into_iter()
+                    .map(|(guess, score)| (guess.clone(), score))
+                    .collect();
 
                     let _payouts =
                         calculate_payouts(black_box(&rankings), black_box(1000.0)).unwrap();
@@ -269,6 +302,7 @@ criterion_group!(
     benchmark_commitment_generation,
     benchmark_commitment_verification,
     benchmark_batch_commitment_verification,
+    benchmark_hash_algorithms,
     benchmark_embedding_generation,
     benchmark_scoring_strategies,
     benchmark_ranking_calculation,
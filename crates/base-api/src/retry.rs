@@ -0,0 +1,37 @@
+//! Backoff between retried requests
+
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Delay after failed attempt `attempt` (starting at 1)
+///
+/// `base_delay` doubles with each attempt up to `max_delay`, then is scaled
+/// by a random factor between 0.5 and 1 so parallel clients don't retry in
+/// lockstep.
+pub fn backoff(base_delay: Duration, max_delay: Duration, attempt: u32) -> Duration {
+    let exponential = base_delay
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(max_delay);
+    exponential.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_with_jitter() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_millis(300);
+        for _ in 0..20 {
+            let first = backoff(base, max, 1);
+            assert!(first >= Duration::from_millis(50) && first <= base);
+            let second = backoff(base, max, 2);
+            assert!(second >= base && second <= Duration::from_millis(200));
+            // Capped at max_delay, even where doubling would overflow
+            assert!(backoff(base, max, 10) <= max);
+            assert!(backoff(base, max, u32::MAX) <= max);
+        }
+    }
+}
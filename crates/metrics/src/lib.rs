@@ -0,0 +1,287 @@
+//! Lightweight metrics registry exported in the Prometheus text format
+//!
+//! Instrumented code records into one process-wide registry through
+//! [`increment_counter`], [`add_gauge`], [`set_gauge`], [`observe_seconds`]
+//! and [`Timer`]; [`export_prometheus`] renders everything recorded so far.
+//!
+//! Recording only happens with the `enabled` feature. Without it the
+//! recording functions are empty and [`Timer`] doesn't read the clock, so
+//! instrumentation costs nothing.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Whether recording is compiled in
+pub const ENABLED: bool = cfg!(feature = "enabled");
+
+/// Label name/value pairs identifying one series of a metric
+pub type Labels<'a> = &'a [(&'static str, &'a str)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Counter(u64),
+    Gauge(f64),
+    /// Total and count of observations, e.g. seconds spent
+    Summary {
+        sum: f64,
+        count: u64,
+    },
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Counter(_) => "counter",
+            Value::Gauge(_) => "gauge",
+            Value::Summary { .. } => "summary",
+        }
+    }
+}
+
+/// Metrics by name, then by rendered label set
+#[derive(Debug, Default)]
+pub struct Registry {
+    metrics: Mutex<BTreeMap<&'static str, BTreeMap<String, Value>>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply `update` to the series, starting from `initial` if it is new
+    ///
+    /// A name keeps the type it was first recorded with; recording it as
+    /// another type is ignored rather than corrupting the export.
+    fn update(
+        &self,
+        name: &'static str,
+        labels: Labels,
+        initial: Value,
+        update: impl FnOnce(&mut Value),
+    ) {
+        let mut metrics = self.metrics.lock().unwrap();
+        let series = metrics.entry(name).or_default();
+        if let Some(existing) = series.values().next() {
+            if existing.type_name() != initial.type_name() {
+                return;
+            }
+        }
+        update(series.entry(render_labels(labels)).or_insert(initial));
+    }
+
+    pub fn increment_counter(&self, name: &'static str, labels: Labels) {
+        self.update(name, labels, Value::Counter(0), |value| {
+            if let Value::Counter(count) = value {
+                *count += 1;
+            }
+        });
+    }
+
+    pub fn add_gauge(&self, name: &'static str, labels: Labels, delta: f64) {
+        self.update(name, labels, Value::Gauge(0.0), |value| {
+            if let Value::Gauge(current) = value {
+                *current += delta;
+            }
+        });
+    }
+
+    pub fn set_gauge(&self, name: &'static str, labels: Labels, to: f64) {
+        self.update(name, labels, Value::Gauge(0.0), |value| {
+            if let Value::Gauge(current) = value {
+                *current = to;
+            }
+        });
+    }
+
+    /// Record one observation of a duration in seconds
+    pub fn observe_seconds(&self, name: &'static str, labels: Labels, seconds: f64) {
+        self.update(
+            name,
+            labels,
+            Value::Summary { sum: 0.0, count: 0 },
+            |value| {
+                if let Value::Summary { sum, count } = value {
+                    *sum += seconds;
+                    *count += 1;
+                }
+            },
+        );
+    }
+
+    /// Everything recorded, in the Prometheus text exposition format
+    ///
+    /// Summaries are exported as `<name>_sum` and `<name>_count` series.
+    pub fn export_prometheus(&self) -> String {
+        let metrics = self.metrics.lock().unwrap();
+        let mut out = String::new();
+        for (name, series) in metrics.iter() {
+            let Some(first) = series.values().next() else {
+                continue;
+            };
+            let _ = writeln!(out, "# TYPE {} {}", name, first.type_name());
+            for (labels, value) in series {
+                match value {
+                    Value::Counter(count) => {
+                        let _ = writeln!(out, "{}{} {}", name, labels, count);
+                    }
+                    Value::Gauge(current) => {
+                        let _ = writeln!(out, "{}{} {}", name, labels, current);
+                    }
+                    Value::Summary { sum, count } => {
+                        let _ = writeln!(out, "{}_sum{} {}", name, labels, sum);
+                        let _ = writeln!(out, "{}_count{} {}", name, labels, count);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// `{name="value",...}`, or nothing for an unlabelled series
+fn render_labels(labels: Labels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", name, escaped)
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+/// The process-wide registry the recording functions write to
+#[cfg(feature = "enabled")]
+pub fn global() -> &'static Registry {
+    static GLOBAL: std::sync::OnceLock<Registry> = std::sync::OnceLock::new();
+    GLOBAL.get_or_init(Registry::new)
+}
+
+#[inline]
+pub fn increment_counter(name: &'static str, labels: Labels) {
+    #[cfg(feature = "enabled")]
+    global().increment_counter(name, labels);
+    #[cfg(not(feature = "enabled"))]
+    let _ = (name, labels);
+}
+
+#[inline]
+pub fn add_gauge(name: &'static str, labels: Labels, delta: f64) {
+    #[cfg(feature = "enabled")]
+    global().add_gauge(name, labels, delta);
+    #[cfg(not(feature = "enabled"))]
+    let _ = (name, labels, delta);
+}
+
+#[inline]
+pub fn set_gauge(name: &'static str, labels: Labels, to: f64) {
+    #[cfg(feature = "enabled")]
+    global().set_gauge(name, labels, to);
+    #[cfg(not(feature = "enabled"))]
+    let _ = (name, labels, to);
+}
+
+#[inline]
+pub fn observe_seconds(name: &'static str, labels: Labels, seconds: f64) {
+    #[cfg(feature = "enabled")]
+    global().observe_seconds(name, labels, seconds);
+    #[cfg(not(feature = "enabled"))]
+    let _ = (name, labels, seconds);
+}
+
+/// Everything recorded in this process, in the Prometheus text format
+///
+/// Empty when recording isn't compiled in.
+pub fn export_prometheus() -> String {
+    #[cfg(feature = "enabled")]
+    return global().export_prometheus();
+    #[cfg(not(feature = "enabled"))]
+    String::new()
+}
+
+/// Records the seconds from `start` until it is dropped as a summary observation
+#[must_use = "the duration is recorded when the timer is dropped"]
+pub struct Timer {
+    #[cfg(feature = "enabled")]
+    name: &'static str,
+    #[cfg(feature = "enabled")]
+    started: std::time::Instant,
+}
+
+impl Timer {
+    #[inline]
+    pub fn start(name: &'static str) -> Self {
+        #[cfg(feature = "enabled")]
+        return Self {
+            name,
+            started: std::time::Instant::now(),
+        };
+        #[cfg(not(feature = "enabled"))]
+        {
+            let _ = name;
+            Self {}
+        }
+    }
+}
+
+#[cfg(feature = "enabled")]
+impl Drop for Timer {
+    fn drop(&mut self) {
+        observe_seconds(self.name, &[], self.started.elapsed().as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_prometheus_text() {
+        let registry = Registry::new();
+        registry.increment_counter("api_errors_total", &[("status", "429")]);
+        registry.increment_counter("api_errors_total", &[("status", "429")]);
+        registry.increment_counter("api_errors_total", &[("status", "500")]);
+        registry.add_gauge("blocks", &[("state", "RevealsOpen")], 1.0);
+        registry.add_gauge("blocks", &[("state", "RevealsOpen")], 1.0);
+        registry.add_gauge("blocks", &[("state", "RevealsOpen")], -1.0);
+        registry.set_gauge("queue_depth", &[], 3.0);
+        registry.observe_seconds("scoring_seconds", &[], 0.5);
+        registry.observe_seconds("scoring_seconds", &[], 0.25);
+
+        assert_eq!(
+            registry.export_prometheus(),
+            "# TYPE api_errors_total counter\n\
+             api_errors_total{status=\"429\"} 2\n\
+             api_errors_total{status=\"500\"} 1\n\
+             # TYPE blocks gauge\n\
+             blocks{state=\"RevealsOpen\"} 1\n\
+             # TYPE queue_depth gauge\n\
+             queue_depth 3\n\
+             # TYPE scoring_seconds summary\n\
+             scoring_seconds_sum 0.75\n\
+             scoring_seconds_count 2\n"
+        );
+    }
+
+    #[test]
+    fn test_labels_are_escaped_and_types_kept() {
+        let registry = Registry::new();
+        registry.increment_counter("requests_total", &[("path", "a\"b\\c\nd")]);
+        // Recording the name as another type is ignored
+        registry.set_gauge("requests_total", &[], 5.0);
+
+        assert_eq!(
+            registry.export_prometheus(),
+            "# TYPE requests_total counter\n\
+             requests_total{path=\"a\\\"b\\\\c\\nd\"} 1\n"
+        );
+    }
+}
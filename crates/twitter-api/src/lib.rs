@@ -5,7 +5,8 @@
 
 use std::collections::HashMap;
 use std::path::Path;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use base64::Engine;
@@ -18,8 +19,26 @@ use sha1::Sha1;
 use thiserror::Error;
 use urlencoding;
 
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 type HmacSha1 = Hmac<Sha1>;
 
+/// Maximum number of characters allowed in a tweet
+pub const MAX_TWEET_LENGTH: usize = 280;
+
+/// Default time allowed to establish a connection to the API
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time allowed for a whole request, including reading the response
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// User-Agent sent with every request unless overridden
+pub const DEFAULT_USER_AGENT: &str = concat!("cliptions-twitter/", env!("CARGO_PKG_VERSION"));
+
+/// How long a looked-up user ID is reused before asking the API again
+pub const DEFAULT_USER_ID_TTL: Duration = Duration::from_secs(60 * 60);
+
 #[derive(Error, Debug)]
 pub enum TwitterError {
     #[error("HTTP request failed: {0}")]
@@ -28,6 +47,16 @@ pub enum TwitterError {
     #[error("Twitter API error ({status}): {message}")]
     ApiError { status: u16, message: String },
 
+    /// An error response whose body was Twitter's JSON error envelope
+    #[error("Twitter API error ({status}): {title}: {detail}")]
+    ApiStructured {
+        status: u16,
+        /// Problem type URI, e.g. `https://api.twitter.com/2/problems/duplicate-rules`
+        type_url: Option<String>,
+        title: String,
+        detail: String,
+    },
+
     #[error("Authentication failed: {0}")]
     AuthError(String),
 
@@ -46,20 +75,104 @@ pub enum TwitterError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    /// Twitter rejected a post because the same text was already posted
+    #[error("Duplicate content: {0}")]
+    DuplicateContent(String),
+
+    /// The client has no way to perform the request
+    #[error("Not supported by this client: {0}")]
+    Unsupported(String),
+
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 }
 
+impl TwitterError {
+    /// HTTP status of an error response from the API
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            TwitterError::ApiError { status, .. } | TwitterError::ApiStructured { status, .. } => {
+                Some(*status)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether Twitter rejected a post because the same text was already posted
+    pub fn is_duplicate_content(&self) -> bool {
+        match self {
+            TwitterError::DuplicateContent(_) => true,
+            TwitterError::ApiStructured { detail, .. } => {
+                detail.to_lowercase().contains("duplicate")
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Result type alias for TwitterError
 pub type Result<T> = std::result::Result<T, TwitterError>;
 
 /// Configuration for Twitter API authentication
-#[derive(Debug, Clone)]
+///
+/// `Debug` output redacts every credential (see [`redact_secret`]).
+#[derive(Clone)]
 pub struct TwitterConfig {
     pub api_key: String,
     pub api_secret: String,
     pub access_token: String,
     pub access_token_secret: String,
+    /// Optional app-only (OAuth 2.0) bearer token used for read-only requests
+    pub bearer_token: Option<String>,
+}
+
+impl std::fmt::Debug for TwitterConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwitterConfig")
+            .field("api_key", &redact_secret(&self.api_key))
+            .field("api_secret", &redact_secret(&self.api_secret))
+            .field("access_token", &redact_secret(&self.access_token))
+            .field(
+                "access_token_secret",
+                &redact_secret(&self.access_token_secret),
+            )
+            .field(
+                "bearer_token",
+                &self.bearer_token.as_deref().map(redact_secret),
+            )
+            .finish()
+    }
+}
+
+/// Shortest secret whose last four characters are shown by [`redact_secret`]
+const MIN_PARTLY_SHOWN_SECRET_LEN: usize = 12;
+
+/// A secret as it may appear in logs and error messages: `****` and its last
+/// four characters, so credentials can be told apart without revealing them
+///
+/// Secrets too short for that to be safe are shown as `****` alone, and an
+/// empty secret stays empty so a missing credential is still visible.
+pub fn redact_secret(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.is_empty() {
+        String::new()
+    } else if chars.len() < MIN_PARTLY_SHOWN_SECRET_LEN {
+        "****".to_string()
+    } else {
+        format!(
+            "****{}",
+            chars[chars.len() - 4..].iter().collect::<String>()
+        )
+    }
+}
+
+/// Authorization scheme used for a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMode {
+    /// OAuth 1.0a user context (HMAC-SHA1 signed), required for writes
+    OAuth1,
+    /// OAuth 2.0 app-only bearer token, usable for read-only endpoints
+    Bearer,
 }
 
 impl TwitterConfig {
@@ -84,8 +197,26 @@ impl TwitterConfig {
                     "TWITTER_ACCESS_TOKEN_SECRET environment variable not set".to_string(),
                 )
             })?,
+            bearer_token: std::env::var("TWITTER_BEARER_TOKEN").ok(),
         })
     }
+
+    /// Use an app-only bearer token for read-only (GET) requests
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Pick the authorization scheme for a request with the given HTTP method
+    ///
+    /// Reads use the bearer token when one is configured; writes always need
+    /// OAuth 1.0a user context.
+    pub fn auth_mode_for(&self, method: &str) -> AuthMode {
+        match (method, &self.bearer_token) {
+            ("GET", Some(_)) => AuthMode::Bearer,
+            _ => AuthMode::OAuth1,
+        }
+    }
 }
 
 /// Represents a Twitter user
@@ -107,6 +238,22 @@ pub struct Tweet {
     pub conversation_id: Option<String>,
     pub public_metrics: Option<PublicMetrics>,
     pub url: String,
+    /// Author of the tweet this one replies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to_user_id: Option<String>,
+    /// Tweets this one replies to, quotes or retweets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub referenced_tweets: Vec<ReferencedTweet>,
+}
+
+impl Tweet {
+    /// ID of the tweet this one directly replies to, if it is a reply
+    pub fn replied_to_id(&self) -> Option<&str> {
+        self.referenced_tweets
+            .iter()
+            .find(|r| r.kind == "replied_to")
+            .map(|r| r.id.as_str())
+    }
 }
 
 impl Default for Tweet {
@@ -119,10 +266,21 @@ impl Default for Tweet {
             conversation_id: None,
             public_metrics: None,
             url: String::new(),
+            in_reply_to_user_id: None,
+            referenced_tweets: Vec::new(),
         }
     }
 }
 
+/// A tweet referenced by another, from the API's `referenced_tweets` field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferencedTweet {
+    /// `replied_to`, `quoted` or `retweeted`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublicMetrics {
     pub retweet_count: u32,
@@ -149,6 +307,43 @@ pub struct PostTweetResult {
     pub success: bool,
 }
 
+/// Result from searching for replies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub tweets: Vec<Tweet>,
+    /// `false` when pagination stopped at `max_pages` with more results available,
+    /// or when a page after the first failed
+    pub complete: bool,
+    /// Highest tweet ID seen, for polling only newer replies next time
+    ///
+    /// `None` after a failed page, since the older unfetched pages would be skipped
+    pub newest_id: Option<String>,
+    /// Why pagination stopped early, if a page after the first failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Paging controls for finding a user's latest tweet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatestTweetOptions {
+    /// Tweets requested per timeline page (the API accepts 5..=100)
+    pub page_size: u32,
+    /// Pages to fetch before giving up on finding a qualifying tweet
+    pub max_pages: u32,
+    /// Skip retweets and replies
+    pub exclude_retweets_replies: bool,
+}
+
+impl Default for LatestTweetOptions {
+    fn default() -> Self {
+        Self {
+            page_size: 10,
+            max_pages: 3,
+            exclude_retweets_replies: false,
+        }
+    }
+}
+
 /// Result from uploading media
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaUploadResult {
@@ -157,6 +352,26 @@ pub struct MediaUploadResult {
     pub media_type: String,
 }
 
+/// Which search endpoint to query when collecting replies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// `/2/tweets/search/recent`, covering roughly the last 7 days
+    #[default]
+    Recent,
+    /// `/2/tweets/search/all`, covering the full archive (requires elevated access)
+    FullArchive,
+}
+
+impl SearchScope {
+    /// API path for this scope's search endpoint
+    pub fn endpoint_path(&self) -> &'static str {
+        match self {
+            SearchScope::Recent => "/2/tweets/search/recent",
+            SearchScope::FullArchive => "/2/tweets/search/all",
+        }
+    }
+}
+
 /// Trait for a Twitter API client, enabling mocking for tests.
 #[async_trait]
 pub trait TwitterApi {
@@ -178,14 +393,275 @@ pub trait TwitterApi {
         username: &str,
         exclude_retweets_replies: bool,
     ) -> Result<Option<Tweet>>;
-    async fn search_replies(&self, tweet_id: &str, max_results: u32) -> Result<Vec<Tweet>>;
+    /// Fetch up to `max_results` of a user's most recent original tweets, newest first
+    async fn get_user_tweets(&self, username: &str, max_results: u32) -> Result<Vec<Tweet>>;
+    /// Fetch every page of replies, failing if any page fails
+    async fn search_replies(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+    ) -> Result<Vec<Tweet>>;
+    /// Fetch up to `max_pages` pages of replies
+    ///
+    /// A failure after the first page returns the replies gathered so far,
+    /// marked incomplete with the failure in `SearchResult::error`.
+    async fn search_replies_paged(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+        max_pages: Option<u32>,
+    ) -> Result<SearchResult>;
+    async fn quote_tweet(&self, text: &str, quoted_tweet_url: &str) -> Result<PostTweetResult>;
+    async fn delete_tweet(&self, tweet_id: &str) -> Result<bool>;
+    async fn get_authenticated_user(&self) -> Result<TwitterUser>;
+    /// Look up a single user by username, `None` if there is no such user
+    ///
+    /// Clients that can't look users up fail with [`TwitterError::Unsupported`].
+    async fn get_user_by_username(&self, username: &str) -> Result<Option<TwitterUser>> {
+        Err(TwitterError::Unsupported(format!("looking up user {}", username)))
+    }
+    /// Look up users by username, [`MAX_USERNAMES_PER_LOOKUP`] per request
+    ///
+    /// Usernames that don't exist are left out rather than failing the
+    /// lookup; [`missing_usernames`] lists them. The default looks each
+    /// username up with [`TwitterApi::get_user_by_username`].
+    async fn get_users_by_username(&self, usernames: &[String]) -> Result<Vec<TwitterUser>> {
+        let mut users = Vec::with_capacity(usernames.len());
+        for username in usernames {
+            users.extend(self.get_user_by_username(username).await?);
+        }
+        Ok(users)
+    }
+}
+
+/// Base URLs the client sends requests to
+///
+/// Defaults to the public Twitter API hosts; tests can point these at a local
+/// mock server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwitterEndpoints {
+    /// Base for v2 API calls, e.g. `https://api.twitter.com`
+    pub api_base: String,
+    /// Base for v1.1 media uploads, e.g. `https://upload.twitter.com`
+    pub upload_base: String,
+}
+
+impl Default for TwitterEndpoints {
+    fn default() -> Self {
+        Self {
+            api_base: "https://api.twitter.com".to_string(),
+            upload_base: "https://upload.twitter.com".to_string(),
+        }
+    }
+}
+
+/// Rate-limit window last reported by the API for one endpoint family
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RateLimitWindow {
+    remaining: u32,
+    /// Unix time (seconds) at which the window resets
+    reset: u64,
+}
+
+/// Tracks the `x-rate-limit-*` headers returned for each endpoint family
+///
+/// The app rate limit is shared by every process using the same credentials,
+/// so clients that share one tracker (see
+/// [`TwitterClient::with_rate_limit_tracker`]) wait for the window to reset
+/// once it is exhausted instead of sending requests that would get a 429.
+#[derive(Debug, Default)]
+pub struct RateLimitTracker {
+    windows: Mutex<HashMap<String, RateLimitWindow>>,
+}
+
+impl RateLimitTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests left in the current window for `endpoint` (a URL or family),
+    /// or `None` if no response for it has reported a limit yet
+    pub fn remaining(&self, endpoint: &str) -> Option<u32> {
+        self.windows
+            .lock()
+            .unwrap()
+            .get(&endpoint_family(endpoint))
+            .map(|window| window.remaining)
+    }
+
+    /// Record the rate-limit headers from a response to `endpoint`
+    ///
+    /// Responses without both headers are ignored.
+    pub fn record(&self, endpoint: &str, headers: &reqwest::header::HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        let (Some(remaining), Some(reset)) = (
+            header("x-rate-limit-remaining"),
+            header("x-rate-limit-reset"),
+        ) else {
+            return;
+        };
+
+        self.windows.lock().unwrap().insert(
+            endpoint_family(endpoint),
+            RateLimitWindow {
+                remaining: u32::try_from(remaining).unwrap_or(u32::MAX),
+                reset,
+            },
+        );
+    }
+
+    /// How long a request to `endpoint` must wait, if its window is exhausted
+    /// and has not reset yet
+    pub fn delay(&self, endpoint: &str) -> Option<Duration> {
+        let window = *self
+            .windows
+            .lock()
+            .unwrap()
+            .get(&endpoint_family(endpoint))?;
+        if window.remaining > 0 {
+            return None;
+        }
+
+        let reset = UNIX_EPOCH + Duration::from_secs(window.reset);
+        reset
+            .duration_since(SystemTime::now())
+            .ok()
+            .filter(|delay| !delay.is_zero())
+    }
+
+    /// Sleep until a request to `endpoint` is allowed
+    pub async fn wait(&self, endpoint: &str) {
+        if let Some(delay) = self.delay(endpoint) {
+            let family = endpoint_family(endpoint);
+            let labels = [("endpoint", family.as_str())];
+            cliptions_metrics::increment_counter("cliptions_rate_limit_waits_total", &labels);
+            cliptions_metrics::observe_seconds(
+                "cliptions_rate_limit_wait_seconds",
+                &labels,
+                delay.as_secs_f64(),
+            );
+            if std::env::var("CLIPTIONS_DEBUG").is_ok() {
+                println!(
+                    "[DEBUG] rate limit exhausted for {}, waiting {:?}",
+                    endpoint_family(endpoint),
+                    delay
+                );
+            }
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Endpoint family a URL is rate limited under: its path with numeric ID
+/// segments replaced by `:id`, e.g. `/2/tweets/:id` or `/2/tweets/search/recent`
+pub fn endpoint_family(url: &str) -> String {
+    let without_query = url.split(['?', '#']).next().unwrap_or(url);
+    let path = match without_query.find("://") {
+        Some(scheme_end) => {
+            let rest = &without_query[scheme_end + 3..];
+            rest.find('/').map_or("/", |path_start| &rest[path_start..])
+        }
+        None => without_query,
+    };
+
+    // The first segment is the API version (`2`, `1.1`) and is kept as-is
+    path.split('/')
+        .enumerate()
+        .map(|(i, segment)| {
+            if i > 1 && !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// How requests rejected with a 429 are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total requests, including the first
+    pub attempts: u32,
+    /// Delay before the first retry; doubles on each further retry
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after failed attempt `attempt` (starting at 1)
+    ///
+    /// See `base_api::retry::backoff`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        base_api::retry::backoff(self.base_delay, self.max_delay, attempt)
+    }
+}
+
+/// User IDs by lowercased username, each with the time it was looked up
+///
+/// User IDs never change and usernames rarely do, so the TTL only bounds how
+/// long a renamed account keeps resolving to its old ID.
+#[derive(Debug)]
+struct UserIdCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl UserIdCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, username: &str) -> Option<String> {
+        let entries = self.entries.lock().unwrap();
+        let (id, looked_up) = entries.get(&username.to_lowercase())?;
+        (looked_up.elapsed() < self.ttl).then(|| id.clone())
+    }
+
+    fn insert(&self, username: &str, id: String) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(username.to_lowercase(), (id, Instant::now()));
+    }
 }
 
 /// High-level Twitter API client
+///
+/// Clones share the rate-limit tracker and the username to user ID cache.
 #[derive(Debug, Clone)]
 pub struct TwitterClient {
     config: TwitterConfig,
     client: reqwest::Client,
+    endpoints: TwitterEndpoints,
+    request_timeout: Duration,
+    rate_limits: Arc<RateLimitTracker>,
+    user_agent: String,
+    user_ids: Arc<UserIdCache>,
+    retry: RetryPolicy,
 }
 
 #[async_trait]
@@ -252,187 +728,672 @@ impl TwitterApi for TwitterClient {
         username: &str,
         exclude_retweets_replies: bool,
     ) -> Result<Option<Tweet>> {
-        // Step 1: Get user ID from username
-        let user_id = self.get_user_id(username).await?;
-
-        // Step 2: Get the user's latest tweets
-        let mut tweets_url = format!(
-            "https://api.twitter.com/2/users/{}/tweets?max_results=5&tweet.fields=created_at,author_id,public_metrics,conversation_id&user.fields=username,name,verified&expansions=author_id",
-            user_id
-        );
-
-        if exclude_retweets_replies {
-            tweets_url.push_str("&exclude=retweets,replies");
-        }
-
-        let response = self
-            .make_authenticated_request("GET", &tweets_url, None)
-            .await?;
-        let json: serde_json::Value = response.json().await?;
-
-        // Extract the first tweet if available
-        if let Some(tweets) = json["data"].as_array() {
-            if let Some(tweet_data) = tweets.first() {
-                let tweet = self.parse_tweet(tweet_data)?;
-                return Ok(Some(tweet));
-            }
-        }
-
-        Ok(None)
+        let options = LatestTweetOptions {
+            exclude_retweets_replies,
+            ..LatestTweetOptions::default()
+        };
+        self.get_latest_tweet_with_options(username, &options).await
     }
 
-    async fn search_replies(&self, tweet_id: &str, max_results: u32) -> Result<Vec<Tweet>> {
-        let query = format!("conversation_id:{} is:reply", tweet_id);
-        let url = format!(
-            "https://api.twitter.com/2/tweets/search/recent?query={}&max_results={}&tweet.fields=created_at,author_id,conversation_id,in_reply_to_user_id,referenced_tweets&user.fields=username,name&expansions=author_id",
-            urlencoding::encode(&query),
-            max_results
-        );
+    async fn get_user_tweets(&self, username: &str, max_results: u32) -> Result<Vec<Tweet>> {
+        let user_id = self.get_user_id(username).await?;
 
-        let mut all_replies = Vec::new();
+        let mut tweets = Vec::new();
         let mut next_token: Option<String> = None;
 
-        loop {
-            let mut current_url = url.clone();
+        while (tweets.len() as u32) < max_results {
+            // The timeline endpoint accepts 5..=100 results per page
+            let page_size = (max_results - tweets.len() as u32).clamp(5, 100);
+            let mut url = format!(
+                "{}/2/users/{}/tweets?max_results={}&exclude=retweets,replies&tweet.fields=created_at,author_id,public_metrics,conversation_id",
+                self.endpoints.api_base, user_id, page_size
+            );
             if let Some(token) = &next_token {
-                current_url.push_str(&format!("&pagination_token={}", token));
+                url.push_str(&format!("&pagination_token={}", token));
             }
 
-            let response = self
-                .make_authenticated_request("GET", &current_url, None)
-                .await?;
+            let response = self.make_authenticated_request("GET", &url, None).await?;
             let json: serde_json::Value = response.json().await?;
 
-            // Get tweets from this page
-            if let Some(data) = json["data"].as_array() {
-                for tweet_data in data {
-                    let tweet = self.parse_tweet(tweet_data)?;
-                    all_replies.push(tweet);
+            if let Some(page) = json["data"].as_array() {
+                for tweet_data in page {
+                    tweets.push(self.parse_tweet(tweet_data)?);
                 }
             }
 
-            // Check for next page
-            if let Some(meta) = json["meta"].as_object() {
-                if let Some(token) = meta.get("next_token").and_then(|t| t.as_str()) {
-                    next_token = Some(token.to_string());
-                } else {
-                    break; // No more pages
-                }
-            } else {
+            next_token = json["meta"]["next_token"].as_str().map(|s| s.to_string());
+            if next_token.is_none() {
                 break;
             }
         }
 
-        Ok(all_replies)
+        tweets.truncate(max_results as usize);
+        Ok(tweets)
     }
-}
 
-impl TwitterClient {
-    /// Create a new Twitter client with the given configuration
-    pub fn new(config: TwitterConfig) -> Self {
-        Self {
-            config,
-            client: reqwest::Client::new(),
+    async fn search_replies(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+    ) -> Result<Vec<Tweet>> {
+        let (result, error) = self
+            .fetch_reply_pages(tweet_id, max_results, scope, since_id, None)
+            .await?;
+        match error {
+            Some(e) => Err(e),
+            None => Ok(result.tweets),
         }
     }
 
-    /// Create a new Twitter client from environment variables
-    pub fn from_env() -> Result<Self> {
-        let config = TwitterConfig::from_env()?;
-        Ok(Self::new(config))
+    async fn search_replies_paged(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+        max_pages: Option<u32>,
+    ) -> Result<SearchResult> {
+        let (mut result, error) = self
+            .fetch_reply_pages(tweet_id, max_results, scope, since_id, max_pages)
+            .await?;
+        result.error = error.map(|e| e.to_string());
+        Ok(result)
     }
 
-    /// Upload media file to Twitter and return media ID
-    async fn upload_media<P: AsRef<Path>>(&self, image_path: P) -> Result<MediaUploadResult> {
-        let path = image_path.as_ref();
-
-        // Read the image file
-        let image_data = std::fs::read(path).map_err(|e| {
-            TwitterError::FileError(format!(
-                "Failed to read image file {}: {}",
-                path.display(),
-                e
-            ))
-        })?;
+    async fn quote_tweet(&self, text: &str, quoted_tweet_url: &str) -> Result<PostTweetResult> {
+        let tweet_data = build_quote_tweet_body(text, quoted_tweet_url)?;
 
-        // Validate file size (5MB limit for images)
-        if image_data.len() > 5 * 1024 * 1024 {
-            return Err(TwitterError::FileError(
-                "Image file too large (max 5MB)".to_string(),
-            ));
-        }
+        self.post_tweet_internal(tweet_data).await
+    }
 
-        // Detect media type from file extension
-        let media_type = match path.extension().and_then(|ext| ext.to_str()) {
-            Some("jpg") | Some("jpeg") => "image/jpeg",
-            Some("png") => "image/png",
-            Some("gif") => "image/gif",
-            Some("webp") => "image/webp",
-            _ => {
-                return Err(TwitterError::FileError(
-                    "Unsupported image format. Supported: jpg, png, gif, webp".to_string(),
-                ))
-            }
-        };
+    async fn delete_tweet(&self, tweet_id: &str) -> Result<bool> {
+        let url = format!("{}/2/tweets/{}", self.endpoints.api_base, tweet_id);
 
-        // Twitter media upload endpoint
-        let upload_url = "https://upload.twitter.com/1.1/media/upload.json";
+        let response = self
+            .make_authenticated_request("DELETE", &url, None)
+            .await
+            .map_err(|e| map_tweet_not_found(tweet_id, e))?;
+        let json: serde_json::Value = response.json().await?;
 
-        // Create OAuth header for upload request
-        let auth_header = self.create_oauth_header("POST", upload_url, None)?;
+        parse_delete_response(&json)
+    }
 
-        // Create multipart form
-        let form = multipart::Form::new().part(
-            "media",
-            multipart::Part::bytes(image_data)
-                .file_name(path.file_name().unwrap().to_string_lossy().to_string())
-                .mime_str(media_type)
-                .map_err(|e| TwitterError::FileError(format!("Invalid MIME type: {}", e)))?,
+    async fn get_authenticated_user(&self) -> Result<TwitterUser> {
+        let url = format!(
+            "{}/2/users/me?user.fields=verified",
+            self.endpoints.api_base
         );
 
-        // Make upload request
-        let response = self
-            .client
-            .post(upload_url)
-            .header("Authorization", auth_header)
-            .multipart(form)
-            .send()
-            .await?;
+        // An app-only bearer token has no user, so this always needs OAuth 1.0a
+        let auth_header = self.oauth_header_for("GET", &url)?;
+        let response = self.send_request("GET", &url, None, auth_header).await?;
+        let json: serde_json::Value = response.json().await?;
 
-        let status = response.status();
-        if status.is_success() {
-            let json: serde_json::Value = response.json().await?;
+        parse_user_response(&json)
+    }
 
-            let media_id = json["media_id_string"]
-                .as_str()
-                .ok_or_else(|| TwitterError::ApiError {
-                    status: status.as_u16(),
-                    message: "No media_id_string in response".to_string(),
-                })?
-                .to_string();
+    async fn get_user_by_username(&self, username: &str) -> Result<Option<TwitterUser>> {
+        let users = self.get_users_by_username(&[username.to_string()]).await?;
+        Ok(users.into_iter().next())
+    }
 
-            let size = json["size"].as_u64().unwrap_or(0);
+    async fn get_users_by_username(&self, usernames: &[String]) -> Result<Vec<TwitterUser>> {
+        let mut users = Vec::with_capacity(usernames.len());
+        for chunk in usernames.chunks(MAX_USERNAMES_PER_LOOKUP) {
+            let names: Vec<String> = chunk
+                .iter()
+                .map(|name| urlencoding::encode(name).into_owned())
+                .collect();
+            let url = format!(
+                "{}/2/users/by?usernames={}&user.fields=verified",
+                self.endpoints.api_base,
+                names.join(",")
+            );
 
-            Ok(MediaUploadResult {
-                media_id,
-                size,
-                media_type: media_type.to_string(),
-            })
-        } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(TwitterError::ApiError {
-                status: status.as_u16(),
-                message: error_text,
-            })
+            let response = self.make_authenticated_request("GET", &url, None).await?;
+            let json: serde_json::Value = response.json().await?;
+            users.extend(parse_users_response(&json)?);
+        }
+        Ok(users)
+    }
+}
+
+/// Extract the user from a `GET /2/users/me` response
+pub fn parse_user_response(json: &serde_json::Value) -> Result<TwitterUser> {
+    serde_json::from_value(json["data"].clone()).map_err(|e| {
+        TwitterError::ParseError(format!("Invalid user in response: {} ({})", json, e))
+    })
+}
+
+/// Most usernames `GET /2/users/by` accepts in one request
+pub const MAX_USERNAMES_PER_LOOKUP: usize = 100;
+
+/// Extract the users from a `GET /2/users/by` response
+///
+/// When none of the usernames exist the response has only `errors`, which
+/// parses as no users.
+pub fn parse_users_response(json: &serde_json::Value) -> Result<Vec<TwitterUser>> {
+    if json["data"].is_null() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_value(json["data"].clone()).map_err(|e| {
+        TwitterError::ParseError(format!("Invalid users in response: {} ({})", json, e))
+    })
+}
+
+/// Requested usernames with no matching user, compared case-insensitively like Twitter does
+pub fn missing_usernames<'a>(requested: &'a [String], found: &[TwitterUser]) -> Vec<&'a str> {
+    requested
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !found.iter().any(|u| u.username.eq_ignore_ascii_case(name)))
+        .collect()
+}
+
+/// Whether a timeline entry is a retweet or a reply, from its `referenced_tweets`
+fn is_retweet_or_reply(tweet_data: &serde_json::Value) -> bool {
+    tweet_data["referenced_tweets"]
+        .as_array()
+        .is_some_and(|refs| {
+            refs.iter()
+                .any(|r| matches!(r["type"].as_str(), Some("retweeted") | Some("replied_to")))
+        })
+}
+
+/// Highest tweet ID in `tweets`, comparing IDs numerically
+pub fn newest_tweet_id(tweets: &[Tweet]) -> Option<String> {
+    tweets
+        .iter()
+        .max_by(|a, b| a.id.len().cmp(&b.id.len()).then_with(|| a.id.cmp(&b.id)))
+        .map(|tweet| tweet.id.clone())
+}
+
+/// Extract the `data.deleted` flag from a `DELETE /2/tweets/:id` response
+pub fn parse_delete_response(json: &serde_json::Value) -> Result<bool> {
+    json["data"]["deleted"].as_bool().ok_or_else(|| {
+        TwitterError::ParseError(format!("Missing data.deleted in response: {}", json))
+    })
+}
+
+/// Build the error for a failed response from its status and body
+///
+/// Bodies in Twitter's JSON error envelope (a v2 problem with `type`, `title`
+/// and `detail`, or an `errors[]` array) become `ApiStructured`; anything else
+/// is kept as text in `ApiError`.
+pub fn parse_api_error(status: u16, body: &str) -> TwitterError {
+    let fallback = || TwitterError::ApiError {
+        status,
+        message: body.to_string(),
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return fallback();
+    };
+
+    let first = &json["errors"][0];
+    let field = |name: &str| {
+        json[name]
+            .as_str()
+            .or_else(|| first[name].as_str())
+            .map(|value| value.to_string())
+    };
+    let title = field("title");
+    let detail = field("detail").or_else(|| field("message"));
+    if title.is_none() && detail.is_none() {
+        return fallback();
+    }
+
+    TwitterError::ApiStructured {
+        status,
+        type_url: field("type"),
+        title: title.unwrap_or_else(|| {
+            reqwest::StatusCode::from_u16(status)
+                .ok()
+                .and_then(|code| code.canonical_reason())
+                .unwrap_or("Error")
+                .to_string()
+        }),
+        detail: detail.unwrap_or_default(),
+    }
+}
+
+/// Report a post rejected for duplicating an earlier tweet as `DuplicateContent`
+fn map_duplicate_content(error: TwitterError) -> TwitterError {
+    match error {
+        TwitterError::ApiStructured { detail, .. } if error.is_duplicate_content() => {
+            TwitterError::DuplicateContent(detail)
+        }
+        other => other,
+    }
+}
+
+/// Give a 404 from a tweet endpoint a message naming the missing tweet
+fn map_tweet_not_found(tweet_id: &str, error: TwitterError) -> TwitterError {
+    match error {
+        TwitterError::ApiError {
+            status: 404,
+            message,
+        } => TwitterError::ApiError {
+            status: 404,
+            message: format!("Tweet {} not found: {}", tweet_id, message),
+        },
+        TwitterError::ApiStructured {
+            status: 404,
+            type_url,
+            title,
+            detail,
+        } => TwitterError::ApiStructured {
+            status: 404,
+            type_url,
+            title,
+            detail: format!("Tweet {} not found: {}", tweet_id, detail),
+        },
+        other => other,
+    }
+}
+
+/// Build the search URL used to find replies to `tweet_id` within `scope`
+pub fn build_search_replies_url(
+    api_base: &str,
+    tweet_id: &str,
+    max_results: u32,
+    scope: SearchScope,
+) -> String {
+    let query = format!("conversation_id:{} is:reply", tweet_id);
+    format!(
+        "{}{}?query={}&max_results={}&tweet.fields=created_at,author_id,conversation_id,in_reply_to_user_id,referenced_tweets&user.fields=username,name&expansions=author_id",
+        api_base,
+        scope.endpoint_path(),
+        urlencoding::encode(&query),
+        max_results
+    )
+}
+
+/// Build the underlying HTTP client with the given timeouts
+fn build_http_client(connect_timeout: Duration, request_timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+        .build()
+        .expect("Failed to build HTTP client")
+}
+
+/// Percent-encode a value as required by OAuth 1.0a (RFC 5849 section 3.6)
+///
+/// Only the RFC 3986 unreserved characters `A-Za-z0-9-._~` are left as-is; every
+/// other byte of the UTF-8 encoding becomes `%XX` with uppercase hex digits.
+pub fn percent_encode_oauth(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Build the OAuth 1.0a signature base string (RFC 5849 section 3.4.1)
+///
+/// `params` holds every decoded OAuth and query parameter. They are encoded,
+/// sorted by name, joined with `&`, and combined with the uppercased method and
+/// base URL as `METHOD&encoded(base_url)&encoded(param_string)`.
+fn build_signature_base_string(
+    method: &str,
+    base_url: &str,
+    params: &[(String, String)],
+) -> String {
+    let mut encoded_params: Vec<(String, String)> = params
+        .iter()
+        .map(|(k, v)| (percent_encode_oauth(k), percent_encode_oauth(v)))
+        .collect();
+    encoded_params.sort();
+
+    let param_string = encoded_params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!(
+        "{}&{}&{}",
+        method.to_uppercase(),
+        percent_encode_oauth(base_url),
+        percent_encode_oauth(&param_string)
+    )
+}
+
+/// Sign a base string with HMAC-SHA1 (RFC 5849 section 3.4.2), returning base64
+fn sign_hmac_sha1(base_string: &str, consumer_secret: &str, token_secret: &str) -> Result<String> {
+    let signing_key = format!(
+        "{}&{}",
+        percent_encode_oauth(consumer_secret),
+        percent_encode_oauth(token_secret)
+    );
+
+    let mut mac = HmacSha1::new_from_slice(signing_key.as_bytes())
+        .map_err(|e| TwitterError::AuthError(format!("HMAC key error: {}", e)))?;
+    mac.update(base_string.as_bytes());
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes()))
+}
+
+/// Length of every URL once Twitter wraps it in a t.co link
+const TCO_URL_LENGTH: usize = 23;
+
+/// Length of a tweet as Twitter counts it against `MAX_TWEET_LENGTH`
+///
+/// URLs count as a fixed 23 characters, and characters outside the Latin and
+/// common punctuation ranges (CJK, emoji, ...) count double.
+pub fn weighted_tweet_length(text: &str) -> usize {
+    let mut length = 0;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if rest.starts_with("http://") || rest.starts_with("https://") {
+            let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            length += TCO_URL_LENGTH;
+            rest = &rest[url_end..];
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap();
+        length += match c as u32 {
+            0x0000..=0x10FF | 0x2000..=0x200D | 0x2010..=0x201F | 0x2032..=0x2037 => 1,
+            _ => 2,
+        };
+        rest = &rest[c.len_utf8()..];
+    }
+
+    length
+}
+
+/// Extract a tweet ID from a Twitter/X status URL, or accept a bare numeric ID
+///
+/// Accepts the same URL shapes as `UrlParser::extract_tweet_id` in the core crate
+/// (`https://twitter.com/<user>/status/<id>` and `https://x.com/<user>/status/<id>`).
+pub fn extract_tweet_id(url_or_id: &str) -> Result<String> {
+    let candidate = url_or_id.trim();
+
+    if !candidate.is_empty() && candidate.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(candidate.to_string());
+    }
+
+    let is_twitter_url = ["https://", "http://"].iter().any(|scheme| {
+        ["twitter.com/", "www.twitter.com/", "x.com/", "www.x.com/"]
+            .iter()
+            .any(|host| candidate.starts_with(&format!("{}{}", scheme, host)))
+    });
+
+    if is_twitter_url {
+        if let Some(pos) = candidate.find("/status/") {
+            let id: String = candidate[pos + "/status/".len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            if !id.is_empty() {
+                return Ok(id);
+            }
+        }
+    }
+
+    Err(TwitterError::InvalidInput(format!(
+        "Invalid Twitter URL: {}",
+        url_or_id
+    )))
+}
+
+/// Build the JSON body for a quote tweet
+///
+/// The v2 API quotes a tweet via `quote_tweet_id` rather than by embedding the
+/// quoted URL in the text, so the URL does not count towards the text length.
+pub fn build_quote_tweet_body(text: &str, quoted_tweet_url: &str) -> Result<serde_json::Value> {
+    let text_length = weighted_tweet_length(text);
+    if text_length > MAX_TWEET_LENGTH {
+        return Err(TwitterError::InvalidInput(format!(
+            "Tweet text too long: {} characters (max {})",
+            text_length, MAX_TWEET_LENGTH
+        )));
+    }
+
+    let quote_tweet_id = extract_tweet_id(quoted_tweet_url)?;
+
+    Ok(serde_json::json!({
+        "text": text,
+        "quote_tweet_id": quote_tweet_id
+    }))
+}
+
+impl TwitterClient {
+    /// Create a new Twitter client with the given configuration
+    pub fn new(config: TwitterConfig) -> Self {
+        Self {
+            config,
+            client: build_http_client(DEFAULT_CONNECT_TIMEOUT, DEFAULT_REQUEST_TIMEOUT),
+            endpoints: TwitterEndpoints::default(),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            rate_limits: Arc::new(RateLimitTracker::new()),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            user_ids: Arc::new(UserIdCache::new(DEFAULT_USER_ID_TTL)),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Override the connect and overall request timeouts
+    pub fn with_timeouts(mut self, connect: Duration, request: Duration) -> Self {
+        self.client = build_http_client(connect, request);
+        self.request_timeout = request;
+        self
+    }
+
+    /// Send requests through the given HTTP client
+    ///
+    /// Replaces the client built from the configured timeouts, so tests can
+    /// inject one with their own transport settings or default headers.
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Identify requests with the given User-Agent instead of [`DEFAULT_USER_AGENT`]
+    ///
+    /// Set on each request, so it also applies to an injected HTTP client.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Send requests to the given base URLs instead of the public Twitter hosts
+    pub fn with_endpoints(mut self, endpoints: TwitterEndpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    /// Share a rate-limit tracker with other clients using the same credentials
+    pub fn with_rate_limit_tracker(mut self, tracker: Arc<RateLimitTracker>) -> Self {
+        self.rate_limits = tracker;
+        self
+    }
+
+    /// The rate-limit tracker this client records into and waits on
+    pub fn rate_limits(&self) -> &Arc<RateLimitTracker> {
+        &self.rate_limits
+    }
+
+    /// Reuse looked-up user IDs for `ttl` instead of [`DEFAULT_USER_ID_TTL`]
+    ///
+    /// Starts an empty cache, no longer shared with earlier clones.
+    pub fn with_user_id_ttl(mut self, ttl: Duration) -> Self {
+        self.user_ids = Arc::new(UserIdCache::new(ttl));
+        self
+    }
+
+    /// Retry requests rejected with a 429 according to `retry`
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Create a new Twitter client from environment variables
+    pub fn from_env() -> Result<Self> {
+        let config = TwitterConfig::from_env()?;
+        Ok(Self::new(config))
+    }
+
+    /// Get a user's latest tweet, paging back through their timeline
+    ///
+    /// Stops at the first tweet that passes the exclusion filter, or returns
+    /// `None` once `max_pages` pages have been read without finding one.
+    pub async fn get_latest_tweet_with_options(
+        &self,
+        username: &str,
+        options: &LatestTweetOptions,
+    ) -> Result<Option<Tweet>> {
+        // Step 1: Get user ID from username
+        let user_id = self.get_user_id(username).await?;
+
+        // Step 2: Page through the user's latest tweets
+        let mut tweets_url = format!(
+            "{}/2/users/{}/tweets?max_results={}&tweet.fields=created_at,author_id,public_metrics,conversation_id,referenced_tweets&user.fields=username,name,verified&expansions=author_id",
+            self.endpoints.api_base,
+            user_id,
+            options.page_size.clamp(5, 100)
+        );
+
+        if options.exclude_retweets_replies {
+            tweets_url.push_str("&exclude=retweets,replies");
+        }
+
+        let mut next_token: Option<String> = None;
+        for _ in 0..options.max_pages {
+            let mut current_url = tweets_url.clone();
+            if let Some(token) = &next_token {
+                current_url.push_str(&format!("&pagination_token={}", token));
+            }
+
+            let response = self
+                .make_authenticated_request("GET", &current_url, None)
+                .await?;
+            let json: serde_json::Value = response.json().await?;
+
+            if let Some(tweets) = json["data"].as_array() {
+                // The exclude parameter isn't always honoured, so filter here too
+                let qualifying = tweets.iter().find(|tweet_data| {
+                    !(options.exclude_retweets_replies && is_retweet_or_reply(tweet_data))
+                });
+                if let Some(tweet_data) = qualifying {
+                    return Ok(Some(self.parse_tweet(tweet_data)?));
+                }
+            }
+
+            next_token = json["meta"]["next_token"].as_str().map(|s| s.to_string());
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Upload media file to Twitter and return media ID
+    async fn upload_media<P: AsRef<Path>>(&self, image_path: P) -> Result<MediaUploadResult> {
+        let path = image_path.as_ref();
+
+        // Read the image file
+        let image_data = std::fs::read(path).map_err(|e| {
+            TwitterError::FileError(format!(
+                "Failed to read image file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        // Validate file size (5MB limit for images)
+        if image_data.len() > 5 * 1024 * 1024 {
+            return Err(TwitterError::FileError(
+                "Image file too large (max 5MB)".to_string(),
+            ));
+        }
+
+        // Detect media type from file extension
+        let media_type = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("jpg") | Some("jpeg") => "image/jpeg",
+            Some("png") => "image/png",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            _ => {
+                return Err(TwitterError::FileError(
+                    "Unsupported image format. Supported: jpg, png, gif, webp".to_string(),
+                ))
+            }
+        };
+
+        // Twitter media upload endpoint
+        let upload_url = format!("{}/1.1/media/upload.json", self.endpoints.upload_base);
+
+        // Create OAuth header for upload request
+        let auth_header = self.create_oauth_header("POST", &upload_url, None)?;
+
+        // Create multipart form
+        let form = multipart::Form::new().part(
+            "media",
+            multipart::Part::bytes(image_data)
+                .file_name(path.file_name().unwrap().to_string_lossy().to_string())
+                .mime_str(media_type)
+                .map_err(|e| TwitterError::FileError(format!("Invalid MIME type: {}", e)))?,
+        );
+
+        // Make upload request
+        self.rate_limits.wait(&upload_url).await;
+        let response = self
+            .client
+            .post(&upload_url)
+            .header("Authorization", auth_header)
+            .header(reqwest::header::USER_AGENT, &self.user_agent)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| self.map_send_error(e))?;
+        self.rate_limits.record(&upload_url, response.headers());
+
+        let status = response.status();
+        if status.is_success() {
+            let json: serde_json::Value = response.json().await?;
+
+            let media_id = json["media_id_string"]
+                .as_str()
+                .ok_or_else(|| TwitterError::ApiError {
+                    status: status.as_u16(),
+                    message: "No media_id_string in response".to_string(),
+                })?
+                .to_string();
+
+            let size = json["size"].as_u64().unwrap_or(0);
+
+            Ok(MediaUploadResult {
+                media_id,
+                size,
+                media_type: media_type.to_string(),
+            })
+        } else {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            cliptions_metrics::increment_counter(
+                "cliptions_api_errors_total",
+                &[("status", status.as_str())],
+            );
+            Err(parse_api_error(status.as_u16(), &error_text))
         }
     }
 
     /// Internal method to handle all tweet posting logic
     async fn post_tweet_internal(&self, tweet_data: serde_json::Value) -> Result<PostTweetResult> {
-        let url = "https://api.twitter.com/2/tweets";
+        let url = format!("{}/2/tweets", self.endpoints.api_base);
 
         if std::env::var("CLIPTIONS_DEBUG").is_ok() {
             println!("[DEBUG] post_tweet_internal: url = {}", url);
@@ -440,8 +1401,9 @@ impl TwitterClient {
         }
 
         let response = self
-            .make_authenticated_request("POST", url, Some(tweet_data))
-            .await?;
+            .make_authenticated_request("POST", &url, Some(tweet_data))
+            .await
+            .map_err(map_duplicate_content)?;
         let json: serde_json::Value = response.json().await?;
 
         // Parse the tweet from the response
@@ -475,8 +1437,11 @@ impl TwitterClient {
                     .map(|s| s.to_string()),
                 public_metrics: None, // Not available immediately after posting
                 url,
+                in_reply_to_user_id: None,
+                referenced_tweets: Vec::new(),
             };
 
+            cliptions_metrics::increment_counter("cliptions_tweets_posted_total", &[]);
             Ok(PostTweetResult {
                 tweet,
                 success: true,
@@ -489,22 +1454,148 @@ impl TwitterClient {
         }
     }
 
+    /// Page through replies to `tweet_id`, keeping the pages fetched before any failure
+    ///
+    /// An error on the first page is returned as is. An error on a later page,
+    /// including a tweet that fails to parse, ends pagination and is returned alongside the partial result, which is
+    /// marked incomplete and has no `newest_id`.
+    async fn fetch_reply_pages(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+        max_pages: Option<u32>,
+    ) -> Result<(SearchResult, Option<TwitterError>)> {
+        let mut url =
+            build_search_replies_url(&self.endpoints.api_base, tweet_id, max_results, scope);
+        if let Some(since_id) = &since_id {
+            url.push_str(&format!("&since_id={}", since_id));
+        }
+
+        let mut all_replies = Vec::new();
+        let mut next_token: Option<String> = None;
+        let mut pages_fetched = 0;
+        let mut complete = true;
+        let mut error = None;
+
+        loop {
+            if max_pages.is_some_and(|max| pages_fetched >= max) {
+                complete = false;
+                break;
+            }
+
+            let mut current_url = url.clone();
+            if let Some(token) = &next_token {
+                current_url.push_str(&format!("&pagination_token={}", token));
+            }
+
+            let json = match self.fetch_reply_page(&current_url, scope).await {
+                Ok(json) => json,
+                Err(e) if pages_fetched > 0 => {
+                    complete = false;
+                    error = Some(e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            pages_fetched += 1;
+
+            // Get tweets from this page; a malformed one fails the whole page
+            let page: Result<Vec<Tweet>> = json["data"]
+                .as_array()
+                .map(|data| data.iter().map(|t| self.parse_tweet(t)).collect())
+                .unwrap_or_else(|| Ok(Vec::new()));
+            match page {
+                Ok(tweets) => all_replies.extend(tweets),
+                Err(e) if pages_fetched > 1 => {
+                    complete = false;
+                    error = Some(e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+
+            // Check for next page
+            if let Some(meta) = json["meta"].as_object() {
+                if let Some(token) = meta.get("next_token").and_then(|t| t.as_str()) {
+                    next_token = Some(token.to_string());
+                } else {
+                    break; // No more pages
+                }
+            } else {
+                break;
+            }
+        }
+
+        // Older pages were never fetched, so resuming from the newest ID would skip them
+        let newest_id = if error.is_none() {
+            newest_tweet_id(&all_replies)
+        } else {
+            None
+        };
+        let result = SearchResult {
+            tweets: all_replies,
+            complete,
+            newest_id,
+            error: None,
+        };
+        Ok((result, error))
+    }
+
+    /// Fetch one page of a reply search
+    async fn fetch_reply_page(&self, url: &str, scope: SearchScope) -> Result<serde_json::Value> {
+        let response = match self.make_authenticated_request("GET", url, None).await {
+            Err(e) if scope == SearchScope::FullArchive && e.status() == Some(403) => {
+                return Err(TwitterError::AuthError(format!(
+                    "Full-archive search requires Pro or Academic Research API access; \
+                     use SearchScope::Recent for tweets from the last 7 days ({})",
+                    e
+                )));
+            }
+            other => other?,
+        };
+        Ok(response.json().await?)
+    }
+
     /// Get user ID from username
+    ///
+    /// IDs are cached for the client's TTL, and lookups rejected with a 429
+    /// are retried with backoff.
     async fn get_user_id(&self, username: &str) -> Result<String> {
-        let user_lookup_url = format!("https://api.twitter.com/2/users/by/username/{}", username);
+        if let Some(id) = self.user_ids.get(username) {
+            return Ok(id);
+        }
 
-        let response = self
-            .make_authenticated_request("GET", &user_lookup_url, None)
-            .await?;
+        let user_lookup_url = format!(
+            "{}/2/users/by/username/{}",
+            self.endpoints.api_base, username
+        );
+
+        let mut attempt = 1;
+        let response = loop {
+            match self
+                .make_authenticated_request("GET", &user_lookup_url, None)
+                .await
+            {
+                Err(e) if e.status() == Some(429) && attempt < self.retry.attempts => {
+                    tokio::time::sleep(self.retry.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                other => break other?,
+            }
+        };
         let json: serde_json::Value = response.json().await?;
 
-        json["data"]["id"]
+        let id = json["data"]["id"]
             .as_str()
             .ok_or_else(|| TwitterError::ApiError {
                 status: 404,
                 message: format!("User not found: {}", username),
-            })
-            .map(|s| s.to_string())
+            })?
+            .to_string();
+        self.user_ids.insert(username, id.clone());
+        Ok(id)
     }
 
     /// Parse tweet data from JSON response
@@ -543,6 +1634,19 @@ impl TwitterClient {
                     quote_count: metrics["quote_count"].as_u64().unwrap_or(0) as u32,
                 });
 
+        let in_reply_to_user_id = tweet_data["in_reply_to_user_id"]
+            .as_str()
+            .map(|s| s.to_string());
+
+        let referenced_tweets = tweet_data["referenced_tweets"]
+            .as_array()
+            .map(|refs| {
+                refs.iter()
+                    .filter_map(|r| serde_json::from_value(r.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let url = format!("https://twitter.com/i/status/{}", id);
 
         Ok(Tweet {
@@ -553,6 +1657,8 @@ impl TwitterClient {
             conversation_id,
             public_metrics,
             url,
+            in_reply_to_user_id,
+            referenced_tweets,
         })
     }
 
@@ -563,22 +1669,25 @@ impl TwitterClient {
         url: &str,
         body: Option<serde_json::Value>,
     ) -> Result<reqwest::Response> {
-        // Parse URL to separate base URL from query parameters for OAuth signature
-        let (base_url, query_params) = if let Some(pos) = url.find('?') {
-            let base = &url[..pos];
-            let params = &url[pos + 1..];
-            (base, Some(params))
-        } else {
-            (url, None)
-        };
+        let auth_header = self.authorization_header(method, url)?;
+        self.send_request(method, url, body, auth_header).await
+    }
 
-        let auth_header = self.create_oauth_header(method, base_url, query_params)?;
+    /// Send a request with an already-built Authorization header
+    async fn send_request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<serde_json::Value>,
+        auth_header: String,
+    ) -> Result<reqwest::Response> {
         if std::env::var("CLIPTIONS_DEBUG").is_ok() {
             println!("[DEBUG] make_authenticated_request: method = {}", method);
             println!("[DEBUG] make_authenticated_request: url = {}", url);
+            // The header carries the credentials, so only its scheme is logged
             println!(
-                "[DEBUG] make_authenticated_request: Authorization = {}",
-                auth_header
+                "[DEBUG] make_authenticated_request: Authorization = {} ****",
+                auth_header.split(' ').next().unwrap_or_default()
             );
             if let Some(ref json_body) = body {
                 println!("[DEBUG] make_authenticated_request: body = {}", json_body);
@@ -588,6 +1697,7 @@ impl TwitterClient {
         let mut request_builder = match method {
             "GET" => self.client.get(url),
             "POST" => self.client.post(url),
+            "DELETE" => self.client.delete(url),
             _ => {
                 return Err(TwitterError::AuthError(format!(
                     "Unsupported HTTP method: {}",
@@ -596,7 +1706,9 @@ impl TwitterClient {
             }
         };
 
-        request_builder = request_builder.header("Authorization", auth_header);
+        request_builder = request_builder
+            .header("Authorization", auth_header)
+            .header(reqwest::header::USER_AGENT, &self.user_agent);
 
         if let Some(json_body) = body {
             request_builder = request_builder
@@ -604,7 +1716,12 @@ impl TwitterClient {
                 .json(&json_body);
         }
 
-        let response = request_builder.send().await?;
+        self.rate_limits.wait(url).await;
+        let response = request_builder
+            .send()
+            .await
+            .map_err(|e| self.map_send_error(e))?;
+        self.rate_limits.record(url, response.headers());
         let status = response.status();
 
         if status.is_success() {
@@ -614,11 +1731,49 @@ impl TwitterClient {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            Err(TwitterError::ApiError {
-                status: status.as_u16(),
-                message: error_text,
-            })
+            cliptions_metrics::increment_counter(
+                "cliptions_api_errors_total",
+                &[("status", status.as_str())],
+            );
+            Err(parse_api_error(status.as_u16(), &error_text))
+        }
+    }
+
+    /// Report timeouts as network errors; other transport failures stay HTTP errors
+    fn map_send_error(&self, error: reqwest::Error) -> TwitterError {
+        if error.is_timeout() {
+            TwitterError::NetworkError(format!(
+                "Request timed out (limit {:?}): {}",
+                self.request_timeout, error
+            ))
+        } else {
+            TwitterError::HttpError(error)
+        }
+    }
+
+    /// Build the Authorization header value for a request
+    fn authorization_header(&self, method: &str, url: &str) -> Result<String> {
+        if let (AuthMode::Bearer, Some(token)) =
+            (self.config.auth_mode_for(method), &self.config.bearer_token)
+        {
+            return Ok(format!("Bearer {}", token));
         }
+
+        self.oauth_header_for(method, url)
+    }
+
+    /// Build an OAuth 1.0a Authorization header for a request URL
+    fn oauth_header_for(&self, method: &str, url: &str) -> Result<String> {
+        // Parse URL to separate base URL from query parameters for OAuth signature
+        let (base_url, query_params) = if let Some(pos) = url.find('?') {
+            let base = &url[..pos];
+            let params = &url[pos + 1..];
+            (base, Some(params))
+        } else {
+            (url, None)
+        };
+
+        self.create_oauth_header(method, base_url, query_params)
     }
 
     /// Create OAuth 1.0a authorization header
@@ -641,13 +1796,25 @@ impl TwitterClient {
             .map(char::from)
             .collect();
 
+        self.build_oauth_header(method, base_url, query_params, &timestamp, &nonce)
+    }
+
+    /// Create OAuth 1.0a authorization header with a fixed timestamp and nonce
+    fn build_oauth_header(
+        &self,
+        method: &str,
+        base_url: &str,
+        query_params: Option<&str>,
+        timestamp: &str,
+        nonce: &str,
+    ) -> Result<String> {
         // OAuth parameters
         let mut oauth_params = HashMap::new();
         oauth_params.insert("oauth_consumer_key", self.config.api_key.as_str());
         oauth_params.insert("oauth_token", self.config.access_token.as_str());
         oauth_params.insert("oauth_signature_method", "HMAC-SHA1");
-        oauth_params.insert("oauth_timestamp", &timestamp);
-        oauth_params.insert("oauth_nonce", &nonce);
+        oauth_params.insert("oauth_timestamp", timestamp);
+        oauth_params.insert("oauth_nonce", nonce);
         oauth_params.insert("oauth_version", "1.0");
 
         // Combine OAuth parameters with query parameters for signature
@@ -672,37 +1839,13 @@ impl TwitterClient {
             }
         }
 
-        // Create parameter string for signature (all parameters must be included)
-        let mut sorted_params: Vec<_> = all_params.iter().collect();
-        sorted_params.sort_by_key(|(k, _)| k.as_str());
-
-        let param_string = sorted_params
-            .iter()
-            .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
-            .collect::<Vec<_>>()
-            .join("&");
-
-        // Create signature base string
-        let base_string = format!(
-            "{}&{}&{}",
-            method.to_uppercase(),
-            urlencoding::encode(base_url),
-            urlencoding::encode(&param_string)
-        );
-
-        // Create signing key
-        let signing_key = format!(
-            "{}&{}",
-            urlencoding::encode(&self.config.api_secret),
-            urlencoding::encode(&self.config.access_token_secret)
-        );
-
-        // Generate HMAC-SHA1 signature
-        let mut mac = HmacSha1::new_from_slice(signing_key.as_bytes())
-            .map_err(|e| TwitterError::AuthError(format!("HMAC key error: {}", e)))?;
-        mac.update(base_string.as_bytes());
-        let signature =
-            base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+        let all_params: Vec<(String, String)> = all_params.into_iter().collect();
+        let base_string = build_signature_base_string(method, base_url, &all_params);
+        let signature = sign_hmac_sha1(
+            &base_string,
+            &self.config.api_secret,
+            &self.config.access_token_secret,
+        )?;
 
         // Add signature to OAuth parameters
         oauth_params.insert("oauth_signature", &signature);
@@ -711,7 +1854,13 @@ impl TwitterClient {
         let auth_params: Vec<String> = oauth_params
             .iter()
             .filter(|(k, _)| k.starts_with("oauth_"))
-            .map(|(k, v)| format!("{}=\"{}\"", urlencoding::encode(k), urlencoding::encode(v)))
+            .map(|(k, v)| {
+                format!(
+                    "{}=\"{}\"",
+                    percent_encode_oauth(k),
+                    percent_encode_oauth(v)
+                )
+            })
             .collect();
 
         Ok(format!("OAuth {}", auth_params.join(", ")))
@@ -748,7 +1897,26 @@ mod tests {
                 username: &str,
                 exclude_retweets_replies: bool,
             ) -> Result<Option<Tweet>>;
-            async fn search_replies(&self, tweet_id: &str, max_results: u32) -> Result<Vec<Tweet>>;
+            async fn get_user_tweets(&self, username: &str, max_results: u32) -> Result<Vec<Tweet>>;
+            async fn search_replies(
+                &self,
+                tweet_id: &str,
+                max_results: u32,
+                scope: SearchScope,
+                since_id: Option<String>,
+            ) -> Result<Vec<Tweet>>;
+            async fn search_replies_paged(
+                &self,
+                tweet_id: &str,
+                max_results: u32,
+                scope: SearchScope,
+                since_id: Option<String>,
+                max_pages: Option<u32>,
+            ) -> Result<SearchResult>;
+            async fn quote_tweet(&self, text: &str, quoted_tweet_url: &str) -> Result<PostTweetResult>;
+            async fn delete_tweet(&self, tweet_id: &str) -> Result<bool>;
+            async fn get_authenticated_user(&self) -> Result<TwitterUser>;
+            async fn get_user_by_username(&self, username: &str) -> Result<Option<TwitterUser>>;
         }
     }
 
@@ -765,6 +1933,8 @@ mod tests {
             conversation_id: None,
             public_metrics: None,
             url: "https://twitter.com/i/status/12345".to_string(),
+            in_reply_to_user_id: None,
+            referenced_tweets: Vec::new(),
         };
 
         let expected_result = PostTweetResult {
@@ -783,4 +1953,1483 @@ mod tests {
         assert!(result.success);
         assert_eq!(result.tweet.id, "12345");
     }
+
+    #[test]
+    fn test_weighted_tweet_length() {
+        assert_eq!(weighted_tweet_length(""), 0);
+        assert_eq!(weighted_tweet_length("hello world"), 11);
+        // Emoji and CJK count double
+        assert_eq!(weighted_tweet_length("🎯 go"), 5);
+        assert_eq!(weighted_tweet_length("日本"), 4);
+        // URLs count as a t.co link regardless of their length
+        assert_eq!(
+            weighted_tweet_length("watch https://www.youtube.com/watch?v=abcdefghijklmnop now"),
+            6 + 23 + 4
+        );
+    }
+
+    #[test]
+    fn test_build_quote_tweet_body() {
+        let body = build_quote_tweet_body(
+            "Payouts are in!",
+            "https://x.com/cliptions_test/status/1234567890",
+        )
+        .unwrap();
+        assert_eq!(body["text"], "Payouts are in!");
+        assert_eq!(body["quote_tweet_id"], "1234567890");
+        assert!(body.get("reply").is_none());
+
+        // Bare IDs and twitter.com URLs are accepted too
+        let body = build_quote_tweet_body("Hi", "9876543210").unwrap();
+        assert_eq!(body["quote_tweet_id"], "9876543210");
+        let body = build_quote_tweet_body("Hi", "https://twitter.com/user/status/42?s=20").unwrap();
+        assert_eq!(body["quote_tweet_id"], "42");
+
+        // Invalid URLs and overlong text are rejected
+        assert!(matches!(
+            build_quote_tweet_body("Hi", "https://example.com/not-a-tweet"),
+            Err(TwitterError::InvalidInput(_))
+        ));
+        let long_text = "x".repeat(MAX_TWEET_LENGTH + 1);
+        assert!(matches!(
+            build_quote_tweet_body(&long_text, "1234567890"),
+            Err(TwitterError::InvalidInput(_))
+        ));
+        // CJK counts double, so 141 characters weigh 282
+        let cjk_text = "日".repeat(141);
+        assert!(matches!(
+            build_quote_tweet_body(&cjk_text, "1234567890"),
+            Err(TwitterError::InvalidInput(_))
+        ));
+        assert!(build_quote_tweet_body(&"日".repeat(140), "1234567890").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_mock_quote_tweet() {
+        let mut mock_client = MockTwitterApiClient::new();
+
+        mock_client
+            .expect_quote_tweet()
+            .withf(|text, url| text == "Winners!" && url.ends_with("/status/12345"))
+            .times(1)
+            .returning(|text, _| {
+                Ok(PostTweetResult {
+                    tweet: Tweet {
+                        id: "67890".to_string(),
+                        text: text.to_string(),
+                        ..Tweet::default()
+                    },
+                    success: true,
+                })
+            });
+
+        let result = mock_client
+            .quote_tweet("Winners!", "https://x.com/cliptions/status/12345")
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.tweet.id, "67890");
+    }
+
+    #[test]
+    fn test_build_search_replies_url_recent() {
+        let url =
+            build_search_replies_url("https://api.twitter.com", "12345", 100, SearchScope::Recent);
+        assert!(url.starts_with("https://api.twitter.com/2/tweets/search/recent?"));
+        assert!(url.contains("query=conversation_id%3A12345%20is%3Areply"));
+        assert!(url.contains("&max_results=100&"));
+        assert_eq!(SearchScope::default(), SearchScope::Recent);
+    }
+
+    #[test]
+    fn test_build_search_replies_url_full_archive() {
+        let url = build_search_replies_url(
+            "https://api.twitter.com",
+            "12345",
+            50,
+            SearchScope::FullArchive,
+        );
+        assert!(url.starts_with("https://api.twitter.com/2/tweets/search/all?"));
+        assert!(url.contains("query=conversation_id%3A12345%20is%3Areply"));
+        assert!(url.contains("&max_results=50&"));
+    }
+
+    fn test_config() -> TwitterConfig {
+        TwitterConfig {
+            api_key: "consumer_key".to_string(),
+            api_secret: "consumer_secret".to_string(),
+            access_token: "access_token".to_string(),
+            access_token_secret: "access_token_secret".to_string(),
+            bearer_token: None,
+        }
+    }
+
+    #[test]
+    fn test_auth_mode_for() {
+        let config = test_config();
+        assert_eq!(config.auth_mode_for("GET"), AuthMode::OAuth1);
+        assert_eq!(config.auth_mode_for("POST"), AuthMode::OAuth1);
+
+        let config = config.with_bearer_token("app_token");
+        assert_eq!(config.auth_mode_for("GET"), AuthMode::Bearer);
+        assert_eq!(config.auth_mode_for("POST"), AuthMode::OAuth1);
+    }
+
+    #[test]
+    fn test_get_requests_use_bearer_token_when_configured() {
+        let url =
+            build_search_replies_url("https://api.twitter.com", "12345", 100, SearchScope::Recent);
+
+        let client = TwitterClient::new(test_config().with_bearer_token("app_token"));
+        assert_eq!(
+            client.authorization_header("GET", &url).unwrap(),
+            "Bearer app_token"
+        );
+        assert!(client
+            .authorization_header("POST", "https://api.twitter.com/2/tweets")
+            .unwrap()
+            .starts_with("OAuth "));
+
+        // Without a bearer token, reads fall back to OAuth 1.0a signing
+        let client = TwitterClient::new(test_config());
+        assert!(client
+            .authorization_header("GET", &url)
+            .unwrap()
+            .starts_with("OAuth "));
+    }
+
+    // Example request from RFC 5849 section 1.2 (photos.example.net)
+    fn rfc5849_params() -> Vec<(String, String)> {
+        [
+            ("file", "vacation.jpg"),
+            ("size", "original"),
+            ("oauth_consumer_key", "dpf43f3p2l4k3l03"),
+            ("oauth_token", "nnch734d00sl2jdk"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "137131202"),
+            ("oauth_nonce", "chapoH"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn test_signature_base_string_rfc5849() {
+        let base_string = build_signature_base_string(
+            "get",
+            "http://photos.example.net/photos",
+            &rfc5849_params(),
+        );
+        assert_eq!(
+            base_string,
+            "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacation.jpg%26\
+             oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3DchapoH%26\
+             oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D137131202%26\
+             oauth_token%3Dnnch734d00sl2jdk%26size%3Doriginal"
+        );
+    }
+
+    #[test]
+    fn test_sign_hmac_sha1_rfc5849() {
+        let base_string = build_signature_base_string(
+            "GET",
+            "http://photos.example.net/photos",
+            &rfc5849_params(),
+        );
+        let signature =
+            sign_hmac_sha1(&base_string, "kd94hf93k423kf44", "pfkkdhi9sl3r4s00").unwrap();
+        assert_eq!(signature, "MdpQcU8iPSUjWoN/UDMsK2sui9I=");
+    }
+
+    #[test]
+    fn test_oauth_header_merges_query_params() {
+        // Same request through the client, including oauth_version as sent in practice
+        // (OAuth Core 1.0 appendix A.5 vector)
+        let client = TwitterClient::new(TwitterConfig {
+            api_key: "dpf43f3p2l4k3l03".to_string(),
+            api_secret: "kd94hf93k423kf44".to_string(),
+            access_token: "nnch734d00sl2jdk".to_string(),
+            access_token_secret: "pfkkdhi9sl3r4s00".to_string(),
+            bearer_token: None,
+        });
+
+        let header = client
+            .build_oauth_header(
+                "GET",
+                "http://photos.example.net/photos",
+                Some("file=vacation.jpg&size=original"),
+                "1191242096",
+                "kllo9940pd9333jh",
+            )
+            .unwrap();
+
+        assert!(header.starts_with("OAuth "));
+        assert!(header.contains("oauth_consumer_key=\"dpf43f3p2l4k3l03\""));
+        assert!(header.contains("oauth_timestamp=\"1191242096\""));
+        assert!(header.contains("oauth_nonce=\"kllo9940pd9333jh\""));
+        assert!(header.contains("oauth_version=\"1.0\""));
+        assert!(header.contains("oauth_signature=\"tR3%2BTy81lMeYAr%2FFid0kMTYa%2FWM%3D\""));
+        // Query parameters are signed but never sent in the header
+        assert!(!header.contains("file="));
+        assert!(!header.contains("size="));
+    }
+
+    #[test]
+    fn test_percent_encode_oauth() {
+        assert_eq!(percent_encode_oauth("AZaz09-._~"), "AZaz09-._~");
+        assert_eq!(percent_encode_oauth("a b"), "a%20b");
+        assert_eq!(percent_encode_oauth("1+1=2"), "1%2B1%3D2");
+        assert_eq!(percent_encode_oauth("#cliptions!"), "%23cliptions%21");
+        assert_eq!(percent_encode_oauth("/?&*"), "%2F%3F%26%2A");
+        assert_eq!(percent_encode_oauth("é"), "%C3%A9");
+        assert_eq!(percent_encode_oauth("🎯"), "%F0%9F%8E%AF");
+        assert_eq!(percent_encode_oauth(""), "");
+    }
+
+    #[test]
+    fn test_oauth_signature_with_plus_in_text() {
+        // Twitter's documented signing example, whose status text contains a `+`
+        let client = TwitterClient::new(TwitterConfig {
+            api_key: "xvz1evFS4wEEPTGEFPHBog".to_string(),
+            api_secret: "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw".to_string(),
+            access_token: "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb".to_string(),
+            access_token_secret: "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE".to_string(),
+            bearer_token: None,
+        });
+        let query = format!(
+            "include_entities=true&status={}",
+            percent_encode_oauth("Hello Ladies + Gentlemen, a signed OAuth request!")
+        );
+
+        let header = client
+            .build_oauth_header(
+                "POST",
+                "https://api.twitter.com/1.1/statuses/update.json",
+                Some(&query),
+                "1318622958",
+                "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg",
+            )
+            .unwrap();
+
+        assert!(header.contains("oauth_signature=\"hCtSmYh%2BiHYCEqBWrE7C7hYmtUk%3D\""));
+    }
+
+    #[tokio::test]
+    async fn test_mock_delete_tweet() {
+        let mut mock_client = MockTwitterApiClient::new();
+
+        mock_client
+            .expect_delete_tweet()
+            .with(mockall::predicate::eq("12345"))
+            .times(1)
+            .returning(|_| Ok(true));
+
+        assert!(mock_client.delete_tweet("12345").await.unwrap());
+    }
+
+    #[test]
+    fn test_parse_delete_response() {
+        let json = serde_json::json!({ "data": { "deleted": true } });
+        assert!(parse_delete_response(&json).unwrap());
+
+        let json = serde_json::json!({ "data": { "deleted": false } });
+        assert!(!parse_delete_response(&json).unwrap());
+
+        let json = serde_json::json!({ "errors": [] });
+        assert!(matches!(
+            parse_delete_response(&json),
+            Err(TwitterError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_tweet_reply_fields() {
+        let client = TwitterClient::new(test_config());
+        let json = serde_json::json!({
+            "id": "1002",
+            "text": "Guess: a red fox\nSalt: abc",
+            "author_id": "42",
+            "conversation_id": "1000",
+            "in_reply_to_user_id": "7",
+            "referenced_tweets": [{ "type": "replied_to", "id": "1001" }]
+        });
+
+        let tweet = client.parse_tweet(&json).unwrap();
+        assert_eq!(tweet.in_reply_to_user_id.as_deref(), Some("7"));
+        assert_eq!(
+            tweet.referenced_tweets,
+            vec![ReferencedTweet {
+                kind: "replied_to".to_string(),
+                id: "1001".to_string(),
+            }]
+        );
+        assert_eq!(tweet.replied_to_id(), Some("1001"));
+
+        // Tweets serialized before the fields existed still load
+        let old: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "1", "text": "gm", "author_id": "42", "created_at": null,
+            "conversation_id": null, "public_metrics": null, "url": ""
+        }))
+        .unwrap();
+        assert_eq!(old.in_reply_to_user_id, None);
+        assert!(old.referenced_tweets.is_empty());
+        assert_eq!(old.replied_to_id(), None);
+    }
+
+    #[test]
+    fn test_delete_not_found_error_mapping() {
+        let error = map_tweet_not_found(
+            "12345",
+            TwitterError::ApiError {
+                status: 404,
+                message: "Not Found".to_string(),
+            },
+        );
+        match error {
+            TwitterError::ApiError { status, message } => {
+                assert_eq!(status, 404);
+                assert!(message.contains("Tweet 12345 not found"));
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+
+        // Other errors pass through unchanged
+        let error = map_tweet_not_found(
+            "12345",
+            TwitterError::ApiError {
+                status: 403,
+                message: "Forbidden".to_string(),
+            },
+        );
+        assert!(matches!(error, TwitterError::ApiError { status: 403, .. }));
+    }
+
+    #[test]
+    fn test_parse_api_error_v2_problem() {
+        let body = r#"{
+            "detail": "You are not allowed to create a Tweet with duplicate content.",
+            "type": "about:blank",
+            "title": "Forbidden",
+            "status": 403
+        }"#;
+        let error = parse_api_error(403, body);
+        match &error {
+            TwitterError::ApiStructured {
+                status,
+                type_url,
+                title,
+                detail,
+            } => {
+                assert_eq!(*status, 403);
+                assert_eq!(type_url.as_deref(), Some("about:blank"));
+                assert_eq!(title, "Forbidden");
+                assert!(detail.contains("duplicate content"));
+            }
+            other => panic!("Expected ApiStructured, got {:?}", other),
+        }
+        assert!(error.is_duplicate_content());
+        assert_eq!(error.status(), Some(403));
+
+        // An errors[] envelope takes its fields from the first error
+        let body = r#"{"errors": [{
+            "message": "The `query` query parameter can not be empty",
+            "title": "Invalid Request",
+            "type": "https://api.twitter.com/2/problems/invalid-request"
+        }]}"#;
+        let error = parse_api_error(400, body);
+        assert!(matches!(
+            &error,
+            TwitterError::ApiStructured { title, detail, type_url: Some(type_url), .. }
+                if title == "Invalid Request"
+                    && detail.contains("can not be empty")
+                    && type_url.ends_with("/invalid-request")
+        ));
+        assert!(!error.is_duplicate_content());
+
+        // A v1.1 style error without a title gets the status reason
+        let error = parse_api_error(
+            401,
+            r#"{"errors":[{"code":32,"message":"Could not authenticate you."}]}"#,
+        );
+        assert!(matches!(
+            &error,
+            TwitterError::ApiStructured { title, type_url: None, .. } if title == "Unauthorized"
+        ));
+
+        // Non-JSON bodies and unrelated JSON stay as text
+        assert!(matches!(
+            parse_api_error(502, "Bad Gateway"),
+            TwitterError::ApiError { status: 502, message } if message == "Bad Gateway"
+        ));
+        assert!(matches!(
+            parse_api_error(500, r#"{"unexpected": true}"#),
+            TwitterError::ApiError { status: 500, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_post_tweet_duplicate_content_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "detail": "You are not allowed to create a Tweet with duplicate content.",
+                "type": "about:blank",
+                "title": "Forbidden",
+                "status": 403
+            })))
+            .mount(&server)
+            .await;
+
+        let error = mock_server_client(&server)
+            .post_tweet("Hello again")
+            .await
+            .unwrap_err();
+
+        match &error {
+            TwitterError::DuplicateContent(detail) => assert_eq!(
+                detail,
+                "You are not allowed to create a Tweet with duplicate content."
+            ),
+            other => panic!("Expected DuplicateContent, got {:?}", other),
+        }
+        assert!(error.is_duplicate_content());
+
+        // Other rejections of a post keep their structured form
+        let other = map_duplicate_content(parse_api_error(
+            403,
+            r#"{"title":"Forbidden","detail":"You are not permitted to perform this action."}"#,
+        ));
+        assert!(matches!(
+            other,
+            TwitterError::ApiStructured { status: 403, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_post_tweet_against_mock_server() {
+        use wiremock::matchers::{body_json, header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(header_regex("Authorization", "^OAuth .*oauth_signature="))
+            .and(body_json(
+                serde_json::json!({ "text": "Hello from Cliptions!" }),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": { "id": "1445880548472328192", "text": "Hello from Cliptions!" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = TwitterClient::new(test_config()).with_endpoints(TwitterEndpoints {
+            api_base: server.uri(),
+            upload_base: server.uri(),
+        });
+
+        let result = client.post_tweet("Hello from Cliptions!").await.unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.tweet.id, "1445880548472328192");
+        assert_eq!(result.tweet.text, "Hello from Cliptions!");
+        assert_eq!(
+            result.tweet.url,
+            "https://twitter.com/i/status/1445880548472328192"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_maps_to_network_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(serde_json::json!({ "data": { "id": "1", "text": "hi" } }))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = TwitterClient::new(test_config())
+            .with_endpoints(TwitterEndpoints {
+                api_base: server.uri(),
+                upload_base: server.uri(),
+            })
+            .with_timeouts(Duration::from_secs(1), Duration::from_millis(200));
+
+        let started = std::time::Instant::now();
+        let result = client.post_tweet("hi").await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        match result {
+            Err(TwitterError::NetworkError(message)) => assert!(message.contains("timed out")),
+            other => panic!("Expected NetworkError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_sent_on_json_and_multipart_requests() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/1.1/media/upload.json"))
+            .and(header("User-Agent", "cliptions-validator/test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "media_id_string": "77", "size": 4
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(header("User-Agent", "cliptions-validator/test"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": { "id": "1", "text": "frame" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // An injected client without a default User-Agent still sends the configured one
+        let client = mock_server_client(&server)
+            .with_http_client(reqwest::Client::new())
+            .with_user_agent("cliptions-validator/test");
+        let image = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        std::fs::write(image.path(), b"\x89PNG").unwrap();
+
+        let result = client
+            .post_tweet_with_image("frame", image.path().to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(result.tweet.id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_default_user_agent() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .and(header("User-Agent", DEFAULT_USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "id": "42", "username": "cliptions_test", "name": "Cliptions" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let user = mock_server_client(&server)
+            .get_authenticated_user()
+            .await
+            .unwrap();
+        assert_eq!(user.id, "42");
+        assert!(DEFAULT_USER_AGENT.starts_with("cliptions-twitter/"));
+    }
+
+    fn mock_server_client(server: &wiremock::MockServer) -> TwitterClient {
+        TwitterClient::new(test_config()).with_endpoints(TwitterEndpoints {
+            api_base: server.uri(),
+            upload_base: server.uri(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_user_id_cached_across_clones() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/validator"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "42", "name": "Validator", "username": "validator"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server);
+        assert_eq!(client.get_user_id("validator").await.unwrap(), "42");
+        assert_eq!(client.get_user_id("validator").await.unwrap(), "42");
+        // Clones share the cache, and usernames are case-insensitive
+        assert_eq!(client.clone().get_user_id("Validator").await.unwrap(), "42");
+
+        // Expired entries are looked up again
+        let expiring = mock_server_client(&server).with_user_id_ttl(Duration::ZERO);
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/validator"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "42", "name": "Validator", "username": "validator"}
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        expiring.get_user_id("validator").await.unwrap();
+        expiring.get_user_id("validator").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_user_id_retries_after_429() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/validator"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
+                "title": "Too Many Requests",
+                "detail": "Too Many Requests",
+                "status": 429
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/validator"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "42", "name": "Validator", "username": "validator"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server).with_retry_policy(RetryPolicy {
+            attempts: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        });
+        assert_eq!(client.get_user_id("validator").await.unwrap(), "42");
+
+        // Once the attempts run out the 429 is returned
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/busy"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let error = client.get_user_id("busy").await.unwrap_err();
+        assert_eq!(error.status(), Some(429));
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_paged_empty() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "meta": { "result_count": 0 } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_server_client(&server)
+            .search_replies_paged("12345", 100, SearchScope::Recent, None, Some(5))
+            .await
+            .unwrap();
+
+        assert!(result.tweets.is_empty());
+        assert!(result.complete);
+        assert_eq!(result.newest_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_paged_truncated() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "id": "999", "text": "first", "author_id": "1" },
+                    { "id": "1001", "text": "second", "author_id": "2" }
+                ],
+                "meta": { "result_count": 2, "next_token": "page2" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_server_client(&server)
+            .search_replies_paged("12345", 100, SearchScope::Recent, None, Some(1))
+            .await
+            .unwrap();
+
+        assert_eq!(result.tweets.len(), 2);
+        assert!(!result.complete);
+        assert_eq!(result.newest_id.as_deref(), Some("1001"));
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_paged_keeps_pages_before_error() {
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "1003", "text": "newest", "author_id": "1" }],
+                "meta": { "result_count": 1, "next_token": "page2" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "1002", "text": "older", "author_id": "2" }],
+                "meta": { "result_count": 1, "next_token": "page3" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param("pagination_token", "page3"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server);
+        let result = client
+            .search_replies_paged("12345", 100, SearchScope::Recent, None, None)
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["1003", "1002"]);
+        assert!(!result.complete);
+        assert!(result.error.as_deref().unwrap().contains("500"));
+        // The unfetched page is older, so there is no safe cursor to resume from
+        assert_eq!(result.newest_id, None);
+
+        // The unpaged form still reports the failure
+        assert!(matches!(
+            client
+                .search_replies("12345", 100, SearchScope::Recent, None)
+                .await,
+            Err(TwitterError::ApiError { status: 500, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_paged_keeps_pages_before_malformed_tweet() {
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "1003", "text": "newest", "author_id": "1" }],
+                "meta": { "result_count": 1, "next_token": "page2" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "id": "1002", "text": "older", "author_id": "2" },
+                    { "id": "1001", "author_id": "3" }
+                ],
+                "meta": { "result_count": 2, "next_token": "page3" }
+            })))
+            .mount(&server)
+            .await;
+
+        let result = mock_server_client(&server)
+            .search_replies_paged("12345", 100, SearchScope::Recent, None, None)
+            .await
+            .unwrap();
+
+        let ids: Vec<&str> = result.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["1003"]);
+        assert!(!result.complete);
+        assert!(result.error.as_deref().unwrap().contains("Missing tweet text"));
+        assert_eq!(result.newest_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_paged_first_page_error() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+            .mount(&server)
+            .await;
+
+        let result = mock_server_client(&server)
+            .search_replies_paged("12345", 100, SearchScope::Recent, None, None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(TwitterError::ApiError { status: 500, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_since_id() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param("since_id", "1001"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "1002", "text": "newer", "author_id": "3" }],
+                "meta": { "result_count": 1 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tweets = mock_server_client(&server)
+            .search_replies("12345", 100, SearchScope::Recent, Some("1001".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(tweets.len(), 1);
+        assert_eq!(tweets[0].id, "1002");
+    }
+
+    #[tokio::test]
+    async fn test_search_replies_with_injected_http_client() {
+        use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(header("x-cliptions-test", "injected"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": "1002",
+                    "text": "@cliptions first",
+                    "author_id": "7",
+                    "conversation_id": "12345",
+                    "created_at": "2025-01-01T12:00:00.000Z",
+                    "public_metrics": {
+                        "retweet_count": 1, "reply_count": 2, "like_count": 3, "quote_count": 4
+                    }
+                }],
+                "meta": { "result_count": 1, "next_token": "page2" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(header("x-cliptions-test", "injected"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{ "id": "1001", "text": "@cliptions second", "author_id": "8" }],
+                "meta": { "result_count": 1 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server).with_http_client(injected_http_client());
+
+        // Stops after the page without a next_token; `expect(1)` checks no extra fetch
+        let tweets = client
+            .search_replies("12345", 100, SearchScope::Recent, None)
+            .await
+            .unwrap();
+
+        assert_eq!(tweets.len(), 2);
+        assert_eq!(tweets[0].id, "1002");
+        assert_eq!(tweets[0].author_id, "7");
+        assert_eq!(tweets[0].conversation_id.as_deref(), Some("12345"));
+        assert_eq!(
+            tweets[0].created_at,
+            Some("2025-01-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert_eq!(tweets[0].public_metrics.as_ref().unwrap().like_count, 3);
+        assert_eq!(tweets[1].id, "1001");
+        assert_eq!(tweets[1].author_id, "8");
+    }
+
+    /// HTTP client marking its requests, so mocks only answer requests sent through it
+    fn injected_http_client() -> reqwest::Client {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-cliptions-test", "injected".parse().unwrap());
+        reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_post_tweet_with_injected_http_client() {
+        use wiremock::matchers::{body_json, header, header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(header("x-cliptions-test", "injected"))
+            .and(header_regex("Authorization", "^OAuth .*oauth_signature="))
+            .and(body_json(serde_json::json!({ "text": "#block7 #commitmentsopen" })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": { "id": "1900000000000000007", "text": "#block7 #commitmentsopen" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(header("x-cliptions-test", "injected"))
+            .and(body_json(serde_json::json!({ "text": "unauthorized" })))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "title": "Unauthorized",
+                "detail": "Unauthorized",
+                "status": 401
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server).with_http_client(injected_http_client());
+
+        let result = client.post_tweet("#block7 #commitmentsopen").await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.tweet.id, "1900000000000000007");
+        assert_eq!(result.tweet.text, "#block7 #commitmentsopen");
+        assert_eq!(
+            result.tweet.url,
+            "https://twitter.com/i/status/1900000000000000007"
+        );
+
+        let error = client.post_tweet("unauthorized").await.unwrap_err();
+        assert_eq!(error.status(), Some(401));
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_tweet_with_injected_http_client() {
+        use wiremock::matchers::{header, method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/cliptions_test"))
+            .and(header("x-cliptions-test", "injected"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": { "id": "42" } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        // The exclude parameter isn't always honoured, so the page still holds a retweet
+        let timeline = serde_json::json!({
+            "data": [
+                { "id": "9", "text": "RT @a: one", "author_id": "42",
+                  "referenced_tweets": [{ "type": "retweeted", "id": "1" }] },
+                { "id": "8", "text": "#block3 #revealsopen", "author_id": "42",
+                  "conversation_id": "8",
+                  "created_at": "2025-01-01T12:00:00.000Z" }
+            ]
+        });
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/tweets"))
+            .and(header("x-cliptions-test", "injected"))
+            .and(query_param("max_results", "10"))
+            .and(query_param("exclude", "retweets,replies"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(timeline.clone()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/tweets"))
+            .and(header("x-cliptions-test", "injected"))
+            .and(query_param_is_missing("exclude"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(timeline))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server).with_http_client(injected_http_client());
+
+        // A single page without a next_token; `expect(1)` checks no extra fetch
+        let tweet = client
+            .get_latest_tweet("cliptions_test", true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tweet.id, "8");
+        assert_eq!(tweet.text, "#block3 #revealsopen");
+        assert_eq!(tweet.author_id, "42");
+        assert_eq!(tweet.conversation_id.as_deref(), Some("8"));
+        assert_eq!(
+            tweet.created_at,
+            Some("2025-01-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+
+        // Without exclusion the newest entry wins; the user ID comes from the cache
+        let tweet = client
+            .get_latest_tweet("cliptions_test", false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tweet.id, "9");
+    }
+
+    #[tokio::test]
+    async fn test_get_authenticated_user() {
+        use wiremock::matchers::{header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .and(header_regex("Authorization", "^OAuth "))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "id": "42", "username": "cliptions_test", "name": "Cliptions" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Even with a bearer token configured, /users/me is signed with OAuth 1.0a
+        let client = TwitterClient::new(test_config().with_bearer_token("app-token"))
+            .with_endpoints(TwitterEndpoints {
+                api_base: server.uri(),
+                upload_base: server.uri(),
+            });
+        let user = client.get_authenticated_user().await.unwrap();
+
+        assert_eq!(user.id, "42");
+        assert_eq!(user.username, "cliptions_test");
+        assert_eq!(user.verified, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_users_by_username_chunks_requests() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+        // Every requested username exists except ghost_7
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by"))
+            .respond_with(|request: &Request| {
+                let usernames = request
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == "usernames")
+                    .map(|(_, value)| value.into_owned())
+                    .unwrap_or_default();
+                let data: Vec<serde_json::Value> = usernames
+                    .split(',')
+                    .filter(|name| *name != "ghost_7")
+                    .map(|name| {
+                        serde_json::json!({
+                            "id": format!("id_{}", name),
+                            "username": name,
+                            "name": name,
+                        })
+                    })
+                    .collect();
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data }))
+            })
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = TwitterClient::new(test_config()).with_endpoints(TwitterEndpoints {
+            api_base: server.uri(),
+            upload_base: server.uri(),
+        });
+        let names: Vec<String> = (0..150)
+            .map(|i| {
+                if i == 107 {
+                    "ghost_7".to_string()
+                } else {
+                    format!("user_{}", i)
+                }
+            })
+            .collect();
+
+        let users = client.get_users_by_username(&names).await.unwrap();
+
+        assert_eq!(users.len(), 149);
+        assert_eq!(users[0].id, "id_user_0");
+        assert_eq!(users[148].username, "user_149");
+        assert_eq!(missing_usernames(&names, &users), vec!["ghost_7"]);
+        let requests = server.received_requests().await.unwrap();
+        let batch_sizes: Vec<usize> = requests
+            .iter()
+            .map(|r| {
+                r.url
+                    .query_pairs()
+                    .find(|(key, _)| key == "usernames")
+                    .map_or(0, |(_, value)| value.split(',').count())
+            })
+            .collect();
+        assert_eq!(batch_sizes, vec![100, 50]);
+
+        // No usernames, no requests
+        assert!(client.get_users_by_username(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_default_get_users_by_username_looks_up_each_user() {
+        let mut mock_client = MockTwitterApiClient::new();
+        mock_client
+            .expect_get_user_by_username()
+            .times(3)
+            .returning(|username| {
+                Ok((username != "ghost").then(|| TwitterUser {
+                    id: format!("id_{}", username),
+                    username: username.to_string(),
+                    name: username.to_string(),
+                    verified: None,
+                }))
+            });
+
+        let names = vec!["alice".to_string(), "ghost".to_string(), "bob".to_string()];
+        let users = mock_client.get_users_by_username(&names).await.unwrap();
+
+        let ids: Vec<&str> = users.iter().map(|u| u.id.as_str()).collect();
+        assert_eq!(ids, vec!["id_alice", "id_bob"]);
+        assert_eq!(missing_usernames(&names, &users), vec!["ghost"]);
+    }
+
+    #[test]
+    fn test_parse_users_response() {
+        let json = serde_json::json!({
+            "data": [{ "id": "1", "username": "Alice", "name": "Alice" }],
+            "errors": [{ "value": "bob", "title": "Not Found Error" }]
+        });
+        let users = parse_users_response(&json).unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(
+            missing_usernames(&["alice".to_string(), "bob".to_string()], &users),
+            vec!["bob"]
+        );
+
+        let none_found = serde_json::json!({
+            "errors": [{ "value": "bob", "title": "Not Found Error" }]
+        });
+        assert!(parse_users_response(&none_found).unwrap().is_empty());
+        assert!(parse_users_response(&serde_json::json!({ "data": [{ "id": 1 }] })).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_tracker_waits_for_reset() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        assert_eq!(
+            endpoint_family("https://api.twitter.com/2/users/42/tweets?max_results=5"),
+            "/2/users/:id/tweets"
+        );
+        assert_eq!(
+            endpoint_family("/2/tweets/search/recent"),
+            "/2/tweets/search/recent"
+        );
+
+        let reset = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 2;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-rate-limit-remaining", "0")
+                    .insert_header("x-rate-limit-reset", reset.to_string().as_str())
+                    .set_body_json(serde_json::json!({
+                        "data": { "id": "42", "username": "cliptions_test", "name": "Cliptions" }
+                    })),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        // Two clients sharing one tracker: the second sees the first's exhausted window
+        let tracker = Arc::new(RateLimitTracker::new());
+        let first = mock_server_client(&server).with_rate_limit_tracker(tracker.clone());
+        let second = mock_server_client(&server).with_rate_limit_tracker(tracker.clone());
+        assert_eq!(tracker.remaining("/2/users/me"), None);
+
+        first.get_authenticated_user().await.unwrap();
+        assert_eq!(tracker.remaining("/2/users/me"), Some(0));
+        assert_eq!(
+            second
+                .rate_limits()
+                .remaining(&format!("{}/2/users/me", server.uri())),
+            Some(0)
+        );
+        assert!(tracker.delay("/2/users/me").is_some());
+        assert_eq!(tracker.delay("/2/tweets/search/recent"), None);
+
+        let started = std::time::Instant::now();
+        second.get_authenticated_user().await.unwrap();
+        assert!(
+            started.elapsed() >= Duration::from_millis(900),
+            "request was sent before the window reset ({:?})",
+            started.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_user_tweets_paginates() {
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/cliptions_test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": { "id": "42" } })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/tweets"))
+            .and(query_param("exclude", "retweets,replies"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "id": "3", "text": "third", "author_id": "42" },
+                    { "id": "2", "text": "second", "author_id": "42" }
+                ],
+                "meta": { "next_token": "page2" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/tweets"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "id": "1", "text": "first", "author_id": "42" },
+                    { "id": "0", "text": "zeroth", "author_id": "42" }
+                ],
+                "meta": { "next_token": "page3" }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_server_client(&server);
+        let tweets = client.get_user_tweets("cliptions_test", 3).await.unwrap();
+
+        let ids: Vec<&str> = tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "2", "1"]);
+    }
+
+    /// Mount a user lookup plus two timeline pages: retweets and a reply, then an original
+    async fn mount_timeline(server: &wiremock::MockServer) {
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, ResponseTemplate};
+
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/cliptions_test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "data": { "id": "42" } })),
+            )
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/tweets"))
+            .and(query_param("max_results", "5"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "id": "9", "text": "RT @a: one", "author_id": "42",
+                      "referenced_tweets": [{ "type": "retweeted", "id": "1" }] },
+                    { "id": "8", "text": "RT @b: two", "author_id": "42",
+                      "referenced_tweets": [{ "type": "retweeted", "id": "2" }] },
+                    { "id": "7", "text": "@c thanks", "author_id": "42",
+                      "referenced_tweets": [{ "type": "replied_to", "id": "3" }] }
+                ],
+                "meta": { "next_token": "page2" }
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/tweets"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    { "id": "6", "text": "#block3 #commitmentsopen", "author_id": "42",
+                      "referenced_tweets": [{ "type": "quoted", "id": "4" }] }
+                ]
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_tweet_skips_retweets_across_pages() {
+        let server = wiremock::MockServer::start().await;
+        mount_timeline(&server).await;
+        let client = mock_server_client(&server);
+
+        let options = LatestTweetOptions {
+            page_size: 5,
+            max_pages: 3,
+            exclude_retweets_replies: true,
+        };
+        let tweet = client
+            .get_latest_tweet_with_options("cliptions_test", &options)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tweet.id, "6");
+
+        // Without exclusion the newest entry wins, whatever it is
+        let options = LatestTweetOptions {
+            exclude_retweets_replies: false,
+            ..options
+        };
+        let tweet = client
+            .get_latest_tweet_with_options("cliptions_test", &options)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tweet.id, "9");
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_tweet_page_budget_exhausted() {
+        let server = wiremock::MockServer::start().await;
+        mount_timeline(&server).await;
+        let client = mock_server_client(&server);
+
+        let options = LatestTweetOptions {
+            page_size: 5,
+            max_pages: 1,
+            exclude_retweets_replies: true,
+        };
+        let tweet = client
+            .get_latest_tweet_with_options("cliptions_test", &options)
+            .await
+            .unwrap();
+        assert!(tweet.is_none());
+    }
+
+    /// Current value of an exported series, e.g. `name{label="value"}`, or 0
+    #[cfg(feature = "metrics")]
+    fn exported_value(series: &str) -> f64 {
+        cliptions_metrics::export_prometheus()
+            .lines()
+            .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+            .map_or(0.0, |value| value.parse().unwrap())
+    }
+
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn test_metrics_count_posts_and_api_errors() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": { "id": "1", "text": "hi" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(serde_json::json!({
+                "title": "Too Many Requests",
+                "detail": "Too Many Requests",
+                "status": 429
+            })))
+            .mount(&server)
+            .await;
+        let client = mock_server_client(&server);
+
+        // Other tests record into the same registry, so compare against the
+        // values before this flow
+        let posted_before = exported_value("cliptions_tweets_posted_total");
+        let errors_before = exported_value("cliptions_api_errors_total{status=\"429\"}");
+
+        client.post_tweet("hi").await.unwrap();
+        client.get_authenticated_user().await.unwrap_err();
+
+        let exported = cliptions_metrics::export_prometheus();
+        assert!(exported.contains("# TYPE cliptions_tweets_posted_total counter\n"));
+        assert!(exported.contains("# TYPE cliptions_api_errors_total counter\n"));
+        assert!(exported_value("cliptions_tweets_posted_total") >= posted_before + 1.0);
+        assert!(
+            exported_value("cliptions_api_errors_total{status=\"429\"}") >= errors_before + 1.0
+        );
+    }
+
+    #[test]
+    fn test_config_debug_redacts_secrets() {
+        let config = TwitterConfig {
+            api_key: "consumer_key_0123456789".to_string(),
+            api_secret: "consumer_secret_0123456789".to_string(),
+            access_token: "12345-access_token_abcdef".to_string(),
+            access_token_secret: "short".to_string(),
+            bearer_token: Some("AAAAAAAAAAAAAAAAAAAAA_bearer_wxyz".to_string()),
+        };
+        let debug = format!("{:?}", config);
+        for secret in [
+            &config.api_key,
+            &config.api_secret,
+            &config.access_token,
+            &config.access_token_secret,
+            config.bearer_token.as_ref().unwrap(),
+        ] {
+            assert!(
+                !debug.contains(secret.as_str()),
+                "{} leaked in {}",
+                secret,
+                debug
+            );
+        }
+        assert!(debug.contains(r#"api_key: "****6789""#));
+        assert!(debug.contains(r#"access_token_secret: "****""#));
+        assert!(debug.contains(r#"bearer_token: Some("****wxyz")"#));
+        assert!(format!("{:#?}", config).contains("****cdef"));
+
+        assert_eq!(redact_secret(""), "");
+        assert_eq!(redact_secret("abc"), "****");
+        assert_eq!(redact_secret("ünïcödé_sëcrét_kéy"), "****_kéy");
+    }
 }
@@ -0,0 +1,667 @@
+//! In-memory [`TwitterApi`] for examples and tests
+//!
+//! Enabled with the `testing` feature. [`InMemoryTwitterApi`] needs no
+//! credentials or network: posted tweets are kept in memory with incrementing
+//! IDs, replies and quotes are linked to the tweets they reference, and canned
+//! replies can be seeded for `search_replies` to find. Failures can be queued
+//! to exercise error handling.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    extract_tweet_id, parse_api_error, weighted_tweet_length, PostTweetResult, ReferencedTweet, Result,
+    SearchResult, SearchScope, Tweet, TwitterApi, TwitterError, TwitterUser, MAX_TWEET_LENGTH,
+};
+
+/// Tweets held by an [`InMemoryTwitterApi`], oldest first
+#[derive(Debug)]
+struct Timeline {
+    tweets: Vec<Tweet>,
+    next_id: u64,
+    /// Image attached to each tweet posted with one, by tweet ID
+    images: HashMap<String, PathBuf>,
+    /// Status and body of the error response the next call gets
+    failure: Option<(u16, String)>,
+    /// Replies kept and error reported by the next search, which stops early
+    interruption: Option<(usize, String)>,
+}
+
+/// A [`TwitterApi`] backed by a `Vec` of tweets
+///
+/// Posts are authored by the authenticated user (`validator`, ID `1` unless
+/// changed with [`with_user`](Self::with_user)). Like Twitter, posting the
+/// same text twice fails with [`TwitterError::DuplicateContent`]. Calls can
+/// be slowed down with [`with_latency`](Self::with_latency) to check how
+/// many overlap.
+#[derive(Debug)]
+pub struct InMemoryTwitterApi {
+    user: TwitterUser,
+    timeline: Mutex<Timeline>,
+    /// How long every call takes
+    latency: Duration,
+    inflight: AtomicUsize,
+    max_inflight: AtomicUsize,
+}
+
+impl Default for InMemoryTwitterApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryTwitterApi {
+    /// Create an empty timeline for the default user
+    pub fn new() -> Self {
+        Self {
+            user: TwitterUser {
+                id: "1".to_string(),
+                username: "validator".to_string(),
+                name: "Validator".to_string(),
+                verified: None,
+            },
+            timeline: Mutex::new(Timeline {
+                tweets: Vec::new(),
+                next_id: 1000,
+                images: HashMap::new(),
+                failure: None,
+                interruption: None,
+            }),
+            latency: Duration::ZERO,
+            inflight: AtomicUsize::new(0),
+            max_inflight: AtomicUsize::new(0),
+        }
+    }
+
+    /// Post as the given user instead of the default
+    pub fn with_user(mut self, id: impl Into<String>, username: impl Into<String>) -> Self {
+        self.user.id = id.into();
+        self.user.username = username.into();
+        self
+    }
+
+    /// Make every call take `latency`, so concurrent calls overlap
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Most calls that were ever in flight at once
+    pub fn max_inflight(&self) -> usize {
+        self.max_inflight.load(Ordering::SeqCst)
+    }
+
+    /// Every stored tweet, oldest first
+    pub fn tweets(&self) -> Vec<Tweet> {
+        self.timeline.lock().unwrap().tweets.clone()
+    }
+
+    /// Look up a stored tweet by ID
+    pub fn tweet(&self, tweet_id: &str) -> Option<Tweet> {
+        self.timeline
+            .lock()
+            .unwrap()
+            .tweets
+            .iter()
+            .find(|t| t.id == tweet_id)
+            .cloned()
+    }
+
+    /// Image attached to a stored tweet, if it was posted with one
+    pub fn image_path(&self, tweet_id: &str) -> Option<PathBuf> {
+        self.timeline.lock().unwrap().images.get(tweet_id).cloned()
+    }
+
+    /// Make the next call fail as if the API had answered with `status` and `body`
+    ///
+    /// The error is built with [`parse_api_error`], so a JSON error envelope
+    /// becomes [`TwitterError::ApiStructured`].
+    pub fn fail_next_call(&self, status: u16, body: &str) {
+        self.timeline.lock().unwrap().failure = Some((status, body.to_string()));
+    }
+
+    /// Make the next search stop after its `kept` newest replies, as if a
+    /// later page had failed with `error`
+    ///
+    /// The result is incomplete and has no `newest_id`, like a real partial search.
+    pub fn interrupt_next_search(&self, kept: usize, error: &str) {
+        self.timeline.lock().unwrap().interruption = Some((kept, error.to_string()));
+    }
+
+    /// Store a canned reply from another account to `tweet_id`
+    ///
+    /// The reply gets the next ID, so it sorts after everything posted so far.
+    pub fn seed_reply(&self, tweet_id: &str, author_id: &str, text: &str) -> Result<Tweet> {
+        let mut timeline = self.timeline.lock().unwrap();
+        let parent = find(&timeline.tweets, tweet_id)?;
+        let mut reply = new_tweet(
+            &mut timeline,
+            author_id,
+            &format!("user_{}", author_id),
+            text,
+        );
+        link_reply(&mut reply, &parent);
+        timeline.tweets.push(reply.clone());
+        Ok(reply)
+    }
+
+    /// Store a prebuilt tweet as-is
+    ///
+    /// Numeric IDs advance the ID counter so later posts still sort after it.
+    pub fn seed(&self, tweet: Tweet) {
+        let mut timeline = self.timeline.lock().unwrap();
+        if let Ok(id) = tweet.id.parse::<u64>() {
+            timeline.next_id = timeline.next_id.max(id + 1);
+        }
+        timeline.tweets.push(tweet);
+    }
+
+    /// Count a call as in flight for the configured latency, then fail it
+    /// if a failure was queued
+    async fn call(&self) -> Result<()> {
+        let inflight = Inflight::enter(&self.inflight);
+        self.max_inflight.fetch_max(inflight.count, Ordering::SeqCst);
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        match self.timeline.lock().unwrap().failure.take() {
+            Some((status, body)) => Err(parse_api_error(status, &body)),
+            None => Ok(()),
+        }
+    }
+
+    /// Post `text` as the authenticated user, linked by `link` to what it references
+    fn post(
+        &self,
+        text: &str,
+        link: impl FnOnce(&mut Tweet, &[Tweet]) -> Result<()>,
+    ) -> Result<PostTweetResult> {
+        let length = weighted_tweet_length(text);
+        if length > MAX_TWEET_LENGTH {
+            return Err(TwitterError::InvalidInput(format!(
+                "Tweet text too long: {} characters (max {})",
+                length, MAX_TWEET_LENGTH
+            )));
+        }
+
+        let mut timeline = self.timeline.lock().unwrap();
+        if timeline
+            .tweets
+            .iter()
+            .any(|t| t.author_id == self.user.id && t.text == text)
+        {
+            return Err(TwitterError::DuplicateContent(
+                "You are not allowed to create a Tweet with duplicate content.".to_string(),
+            ));
+        }
+
+        let mut tweet = new_tweet(&mut timeline, &self.user.id, &self.user.username, text);
+        link(&mut tweet, &timeline.tweets)?;
+        timeline.tweets.push(tweet.clone());
+        Ok(PostTweetResult {
+            tweet,
+            success: true,
+        })
+    }
+
+    fn reply(&self, text: &str, reply_to_tweet_id: &str) -> Result<PostTweetResult> {
+        self.post(text, |tweet, tweets| {
+            link_reply(tweet, &find(tweets, reply_to_tweet_id)?);
+            Ok(())
+        })
+    }
+
+    fn attach_image(&self, tweet: &Tweet, image_path: &Path) {
+        self.timeline
+            .lock()
+            .unwrap()
+            .images
+            .insert(tweet.id.clone(), image_path.to_path_buf());
+    }
+
+    /// The authenticated user's tweets, newest first
+    fn own_tweets(&self, username: &str, exclude_retweets_replies: bool) -> Vec<Tweet> {
+        if username.trim_start_matches('@') != self.user.username {
+            return Vec::new();
+        }
+
+        let timeline = self.timeline.lock().unwrap();
+        timeline
+            .tweets
+            .iter()
+            .rev()
+            .filter(|t| t.author_id == self.user.id)
+            .filter(|t| {
+                !exclude_retweets_replies
+                    || !t
+                        .referenced_tweets
+                        .iter()
+                        .any(|r| r.kind == "replied_to" || r.kind == "retweeted")
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// A call in flight, counted until dropped
+struct Inflight<'a> {
+    counter: &'a AtomicUsize,
+    /// Calls in flight including this one
+    count: usize,
+}
+
+impl<'a> Inflight<'a> {
+    fn enter(counter: &'a AtomicUsize) -> Self {
+        let count = counter.fetch_add(1, Ordering::SeqCst) + 1;
+        Self { counter, count }
+    }
+}
+
+impl Drop for Inflight<'_> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Allocate the next ID and build an unlinked tweet
+fn new_tweet(timeline: &mut Timeline, author_id: &str, username: &str, text: &str) -> Tweet {
+    let id = timeline.next_id.to_string();
+    timeline.next_id += 1;
+    Tweet {
+        url: format!("https://twitter.com/{}/status/{}", username, id),
+        conversation_id: Some(id.clone()),
+        id,
+        text: text.to_string(),
+        author_id: author_id.to_string(),
+        created_at: Some(Utc::now()),
+        ..Tweet::default()
+    }
+}
+
+/// Make `reply` a reply to `parent`, joining its conversation
+fn link_reply(reply: &mut Tweet, parent: &Tweet) {
+    reply.conversation_id = parent
+        .conversation_id
+        .clone()
+        .or_else(|| Some(parent.id.clone()));
+    reply.in_reply_to_user_id = Some(parent.author_id.clone());
+    reply.referenced_tweets.push(ReferencedTweet {
+        kind: "replied_to".to_string(),
+        id: parent.id.clone(),
+    });
+}
+
+fn find(tweets: &[Tweet], tweet_id: &str) -> Result<Tweet> {
+    tweets
+        .iter()
+        .find(|t| t.id == tweet_id)
+        .cloned()
+        .ok_or_else(|| TwitterError::ApiError {
+            status: 404,
+            message: format!("Tweet {} not found", tweet_id),
+        })
+}
+
+/// Compare tweet IDs numerically, as Twitter's snowflake IDs are ordered
+fn id_order(id: &str) -> (usize, &str) {
+    (id.len(), id)
+}
+
+#[async_trait]
+impl TwitterApi for InMemoryTwitterApi {
+    async fn post_tweet(&self, text: &str) -> Result<PostTweetResult> {
+        self.call().await?;
+        self.post(text, |_, _| Ok(()))
+    }
+
+    async fn post_tweet_with_image<P: AsRef<Path> + Send + 'static>(
+        &self,
+        text: &str,
+        image_path: P,
+    ) -> Result<PostTweetResult> {
+        self.call().await?;
+        let posted = self.post(text, |_, _| Ok(()))?;
+        self.attach_image(&posted.tweet, image_path.as_ref());
+        Ok(posted)
+    }
+
+    async fn reply_to_tweet(&self, text: &str, reply_to_tweet_id: &str) -> Result<PostTweetResult> {
+        self.call().await?;
+        self.reply(text, reply_to_tweet_id)
+    }
+
+    async fn reply_to_tweet_with_image<P: AsRef<Path> + Send + 'static>(
+        &self,
+        text: &str,
+        reply_to_tweet_id: &str,
+        image_path: P,
+    ) -> Result<PostTweetResult> {
+        self.call().await?;
+        let posted = self.reply(text, reply_to_tweet_id)?;
+        self.attach_image(&posted.tweet, image_path.as_ref());
+        Ok(posted)
+    }
+
+    async fn get_latest_tweet(
+        &self,
+        username: &str,
+        exclude_retweets_replies: bool,
+    ) -> Result<Option<Tweet>> {
+        self.call().await?;
+        Ok(self
+            .own_tweets(username, exclude_retweets_replies)
+            .into_iter()
+            .next())
+    }
+
+    async fn get_user_tweets(&self, username: &str, max_results: u32) -> Result<Vec<Tweet>> {
+        self.call().await?;
+        let mut tweets = self.own_tweets(username, true);
+        tweets.truncate(max_results as usize);
+        Ok(tweets)
+    }
+
+    async fn search_replies(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+    ) -> Result<Vec<Tweet>> {
+        let result = self
+            .search_replies_paged(tweet_id, max_results, scope, since_id, None)
+            .await?;
+        Ok(result.tweets)
+    }
+
+    async fn search_replies_paged(
+        &self,
+        tweet_id: &str,
+        _max_results: u32,
+        _scope: SearchScope,
+        since_id: Option<String>,
+        _max_pages: Option<u32>,
+    ) -> Result<SearchResult> {
+        self.call().await?;
+        let mut timeline = self.timeline.lock().unwrap();
+        let mut tweets: Vec<Tweet> = timeline
+            .tweets
+            .iter()
+            .filter(|t| t.id != tweet_id && t.conversation_id.as_deref() == Some(tweet_id))
+            .filter(|t| {
+                since_id
+                    .as_deref()
+                    .is_none_or(|since| id_order(&t.id) > id_order(since))
+            })
+            .cloned()
+            .collect();
+        tweets.sort_by(|a, b| id_order(&b.id).cmp(&id_order(&a.id)));
+
+        if let Some((kept, error)) = timeline.interruption.take() {
+            tweets.truncate(kept);
+            return Ok(SearchResult {
+                tweets,
+                complete: false,
+                newest_id: None,
+                error: Some(error),
+            });
+        }
+        Ok(SearchResult {
+            newest_id: tweets.first().map(|t| t.id.clone()),
+            tweets,
+            complete: true,
+            error: None,
+        })
+    }
+
+    async fn quote_tweet(&self, text: &str, quoted_tweet_url: &str) -> Result<PostTweetResult> {
+        self.call().await?;
+        let quoted_id = extract_tweet_id(quoted_tweet_url)?;
+        self.post(text, |tweet, tweets| {
+            find(tweets, &quoted_id)?;
+            tweet.referenced_tweets.push(ReferencedTweet {
+                kind: "quoted".to_string(),
+                id: quoted_id.clone(),
+            });
+            Ok(())
+        })
+    }
+
+    async fn delete_tweet(&self, tweet_id: &str) -> Result<bool> {
+        self.call().await?;
+        let mut timeline = self.timeline.lock().unwrap();
+        let index = timeline
+            .tweets
+            .iter()
+            .position(|t| t.id == tweet_id && t.author_id == self.user.id)
+            .ok_or_else(|| TwitterError::ApiError {
+                status: 404,
+                message: format!("Tweet {} not found", tweet_id),
+            })?;
+        timeline.tweets.remove(index);
+        Ok(true)
+    }
+
+    async fn get_authenticated_user(&self) -> Result<TwitterUser> {
+        self.call().await?;
+        Ok(self.user.clone())
+    }
+
+    /// Only the authenticated user and the authors of seeded replies (`user_<id>`) exist
+    async fn get_users_by_username(&self, usernames: &[String]) -> Result<Vec<TwitterUser>> {
+        self.call().await?;
+        let timeline = self.timeline.lock().unwrap();
+        Ok(usernames
+            .iter()
+            .filter_map(|name| {
+                if self.user.username.eq_ignore_ascii_case(name) {
+                    return Some(self.user.clone());
+                }
+                let id = name.strip_prefix("user_")?;
+                timeline
+                    .tweets
+                    .iter()
+                    .any(|t| t.author_id == id)
+                    .then(|| TwitterUser {
+                        id: id.to_string(),
+                        username: name.to_string(),
+                        name: name.to_string(),
+                        verified: None,
+                    })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_post_then_get_user_tweets() {
+        let api = InMemoryTwitterApi::new();
+
+        let first = api.post_tweet("first").await.unwrap();
+        let second = api.post_tweet("second").await.unwrap();
+        assert!(first.success);
+        assert_eq!(first.tweet.id, "1000");
+        assert_eq!(second.tweet.id, "1001");
+        assert_eq!(
+            second.tweet.url,
+            "https://twitter.com/validator/status/1001"
+        );
+
+        let tweets = api.get_user_tweets("validator", 10).await.unwrap();
+        let texts: Vec<&str> = tweets.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["second", "first"]);
+        assert_eq!(api.get_user_tweets("validator", 1).await.unwrap().len(), 1);
+        assert!(api
+            .get_user_tweets("someone_else", 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let latest = api.get_latest_tweet("@validator", true).await.unwrap();
+        assert_eq!(latest.unwrap().id, second.tweet.id);
+    }
+
+    #[tokio::test]
+    async fn test_replies_and_quotes_are_linked() {
+        let api = InMemoryTwitterApi::new();
+        let root = api
+            .post_tweet("#block1 #commitmentsopen")
+            .await
+            .unwrap()
+            .tweet;
+
+        let reply = api
+            .reply_to_tweet("target frame", &root.id)
+            .await
+            .unwrap()
+            .tweet;
+        assert_eq!(reply.replied_to_id(), Some(root.id.as_str()));
+        assert_eq!(reply.conversation_id.as_deref(), Some(root.id.as_str()));
+        assert_eq!(reply.in_reply_to_user_id.as_deref(), Some("1"));
+
+        let quote = api.quote_tweet("results", &root.url).await.unwrap().tweet;
+        assert_eq!(quote.referenced_tweets[0].kind, "quoted");
+        assert_eq!(quote.referenced_tweets[0].id, root.id);
+
+        // Replies are left out when asked, and missing targets are 404s
+        let latest = api.get_latest_tweet("validator", true).await.unwrap();
+        assert_eq!(latest.unwrap().id, quote.id);
+        assert!(matches!(
+            api.reply_to_tweet("lost", "999").await,
+            Err(TwitterError::ApiError { status: 404, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_finds_seeded_replies() {
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#block1 #revealsopen").await.unwrap().tweet;
+        let first = api
+            .seed_reply(&root.id, "42", "Guess: a red fox\nSalt: s1")
+            .unwrap();
+        let nested = api.seed_reply(&first.id, "43", "nice guess").unwrap();
+        let second = api
+            .seed_reply(&root.id, "44", "Guess: a blue whale\nSalt: s2")
+            .unwrap();
+
+        // Newest first, including replies further down the thread
+        let replies = api
+            .search_replies(&root.id, 100, SearchScope::Recent, None)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = replies.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec![&second.id, &nested.id, &first.id]);
+        assert_eq!(nested.replied_to_id(), Some(first.id.as_str()));
+
+        let newer = api
+            .search_replies_paged(&root.id, 100, SearchScope::Recent, Some(nested.id), None)
+            .await
+            .unwrap();
+        assert_eq!(newer.tweets.len(), 1);
+        assert_eq!(newer.newest_id, Some(second.id.clone()));
+
+        // An interrupted search keeps the newest replies and reports the error once
+        api.interrupt_next_search(1, "page 2 failed");
+        let partial = api
+            .search_replies_paged(&root.id, 100, SearchScope::Recent, None, None)
+            .await
+            .unwrap();
+        assert_eq!(partial.tweets.len(), 1);
+        assert_eq!(partial.tweets[0].id, second.id);
+        assert!(!partial.complete);
+        assert_eq!(partial.newest_id, None);
+        assert_eq!(partial.error.as_deref(), Some("page 2 failed"));
+        let full = api
+            .search_replies_paged(&root.id, 100, SearchScope::Recent, None, None)
+            .await
+            .unwrap();
+        assert!(full.complete);
+        assert_eq!(full.tweets.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_and_delete() {
+        let api = InMemoryTwitterApi::new().with_user("7", "cliptions");
+        let tweet = api.post_tweet("hello").await.unwrap().tweet;
+
+        assert!(matches!(
+            api.post_tweet("hello").await,
+            Err(TwitterError::DuplicateContent(_))
+        ));
+        assert_eq!(
+            api.get_authenticated_user().await.unwrap().username,
+            "cliptions"
+        );
+
+        assert!(api.delete_tweet(&tweet.id).await.unwrap());
+        assert!(api.tweet(&tweet.id).is_none());
+        assert!(api.delete_tweet(&tweet.id).await.is_err());
+        assert!(api.post_tweet("hello").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_images_and_queued_failure() {
+        let api = InMemoryTwitterApi::new();
+        let root = api
+            .post_tweet_with_image("#block1", "frames/1.jpg")
+            .await
+            .unwrap()
+            .tweet;
+        assert_eq!(api.image_path(&root.id), Some(PathBuf::from("frames/1.jpg")));
+
+        api.fail_next_call(
+            403,
+            r#"{"detail":"You are not permitted to perform this action.","title":"Forbidden","status":403}"#,
+        );
+        let err = api.reply_to_tweet("reply", &root.id).await.unwrap_err();
+        assert_eq!(err.status(), Some(403));
+        assert!(!err.is_duplicate_content());
+
+        // Only the next call fails, and the failed post was not stored
+        assert!(api.reply_to_tweet("reply", &root.id).await.is_ok());
+        assert_eq!(api.tweets().len(), 2);
+
+        api.fail_next_call(401, "Unauthorized");
+        let err = api.get_authenticated_user().await.unwrap_err();
+        assert_eq!(err.status(), Some(401));
+        assert!(api.get_authenticated_user().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_latency_overlaps_concurrent_calls() {
+        let api = InMemoryTwitterApi::new().with_latency(Duration::from_millis(20));
+        assert_eq!(api.max_inflight(), 0);
+
+        let (first, second) = tokio::join!(api.post_tweet("first"), api.post_tweet("second"));
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(api.max_inflight(), 2);
+
+        api.post_tweet("third").await.unwrap();
+        assert_eq!(api.max_inflight(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_known_users() {
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#block1").await.unwrap().tweet;
+        api.seed_reply(&root.id, "42", "Commit: abc").unwrap();
+
+        let usernames: Vec<String> = ["Validator", "user_42", "user_43", "stranger"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let users = api.get_users_by_username(&usernames).await.unwrap();
+        let ids: Vec<&str> = users.iter().map(|u| u.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "42"]);
+    }
+}
@@ -2,8 +2,21 @@
 
 pub mod new_block;
 pub mod generate_commitment;
+pub mod check_reveal;
 pub mod collect_commitments;
 pub mod post_target_frame;
 pub mod collect_reveals;
 pub mod verify_commitments;
-pub mod calculate_scores;
\ No newline at end of file
+pub mod calculate_scores;
+pub mod preview_announcements;
+pub mod doctor;
+pub mod reconstruct;
+pub mod archive_blocks;
+pub mod simulate_block;
+pub mod round_report;
+pub mod list_blocks;
+pub mod diff_blocks;
+pub(crate) mod common;
+pub(crate) mod reply_store;
+#[cfg(feature = "status-server")]
+pub mod serve_status;
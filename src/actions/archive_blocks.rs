@@ -0,0 +1,306 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use clap::Parser;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::error::{CliptionsError, Result};
+use crate::types::{BlockData, BlockStatus};
+
+#[derive(Parser)]
+pub struct ArchiveBlocksArgs {
+    /// Archive finished blocks whose reveal deadline is before this date (YYYY-MM-DD, UTC)
+    #[arg(long)]
+    pub before: NaiveDate,
+
+    /// Path to blocks.json file (default: data/blocks.json)
+    #[arg(long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Directory for archive files (default: `archive` next to the blocks file)
+    #[arg(long)]
+    pub archive_dir: Option<PathBuf>,
+
+    /// Report which blocks would move without changing any files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// Blocks keyed by block number, kept as raw JSON so archiving never drops fields
+type RawBlocks = BTreeMap<String, serde_json::Value>;
+
+/// A block selected for archiving and the archive file it belongs in
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedBlock {
+    pub block_num: String,
+    pub archive_file: PathBuf,
+}
+
+pub fn run(args: ArchiveBlocksArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    let archive_dir = args
+        .archive_dir
+        .clone()
+        .unwrap_or_else(|| default_archive_dir(&args.blocks_file));
+    let cutoff = args
+        .before
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc();
+
+    let moved = archive_blocks(&args.blocks_file, &archive_dir, cutoff, args.dry_run)?;
+    if moved.is_empty() {
+        println!("No finished blocks before {}", args.before);
+        return Ok(());
+    }
+
+    for block in &moved {
+        println!(
+            "📦 block {} -> {}",
+            block.block_num,
+            block.archive_file.display()
+        );
+    }
+
+    if args.dry_run {
+        println!(
+            "{} {} block(s) would be archived",
+            "Dry run:".yellow().bold(),
+            moved.len()
+        );
+    } else {
+        println!(
+            "{} Archived {} block(s) from {}",
+            "Success:".green().bold(),
+            moved.len(),
+            args.blocks_file.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Default archive location: an `archive` directory beside the blocks file
+pub fn default_archive_dir(blocks_file: &Path) -> PathBuf {
+    blocks_file
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join("archive")
+}
+
+/// Archive file name for the quarter containing `date`, e.g. `blocks-2024Q1.json`
+pub fn archive_file_name(date: DateTime<Utc>) -> String {
+    format!("blocks-{}Q{}.json", date.year(), (date.month() - 1) / 3 + 1)
+}
+
+/// Move `Complete` blocks whose reveal deadline is before `cutoff` into
+/// quarterly archive files
+///
+/// Archive files are written before blocks.json, each through a temporary
+/// file and a rename, so an interrupted run can leave a block in both places
+/// but never in neither. With `dry_run` set nothing is written.
+pub fn archive_blocks(
+    blocks_file: &Path,
+    archive_dir: &Path,
+    cutoff: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<Vec<ArchivedBlock>> {
+    let mut active = read_blocks(blocks_file)?;
+
+    let mut by_file: BTreeMap<PathBuf, RawBlocks> = BTreeMap::new();
+    let mut moved = Vec::new();
+    for (block_num, value) in &active {
+        let block: BlockData = serde_json::from_value(value.clone()).map_err(|e| {
+            CliptionsError::ValidationError(format!(
+                "Block {} in {} is malformed: {}",
+                block_num,
+                blocks_file.display(),
+                e
+            ))
+        })?;
+        if block.status != BlockStatus::Complete || block.reveal_deadline >= cutoff {
+            continue;
+        }
+
+        let archive_file = archive_dir.join(archive_file_name(block.reveal_deadline));
+        by_file
+            .entry(archive_file.clone())
+            .or_default()
+            .insert(block_num.clone(), value.clone());
+        moved.push(ArchivedBlock {
+            block_num: block_num.clone(),
+            archive_file,
+        });
+    }
+
+    if dry_run || moved.is_empty() {
+        return Ok(moved);
+    }
+
+    fs::create_dir_all(archive_dir)?;
+    for (archive_file, blocks) in by_file {
+        let mut archived = if archive_file.exists() {
+            read_blocks(&archive_file)?
+        } else {
+            RawBlocks::new()
+        };
+        archived.extend(blocks);
+        write_blocks(&archive_file, &archived)?;
+    }
+
+    for block in &moved {
+        active.remove(&block.block_num);
+    }
+    write_blocks(blocks_file, &active)?;
+
+    Ok(moved)
+}
+
+/// Find a block in the archive files under `archive_dir`
+///
+/// Returns `Ok(None)` if the directory doesn't exist or no archive holds the block.
+pub fn load_archived_block(archive_dir: &Path, block_num: &str) -> Result<Option<BlockData>> {
+    if !archive_dir.is_dir() {
+        return Ok(None);
+    }
+
+    let mut archive_files: Vec<PathBuf> = fs::read_dir(archive_dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("blocks-") && name.ends_with(".json"))
+        })
+        .collect();
+    archive_files.sort();
+
+    for archive_file in archive_files {
+        if let Some(value) = read_blocks(&archive_file)?.remove(block_num) {
+            return Ok(Some(serde_json::from_value(value)?));
+        }
+    }
+    Ok(None)
+}
+
+fn read_blocks(path: &Path) -> Result<RawBlocks> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read blocks file {}: {}", path.display(), e))?;
+    if content.trim().is_empty() {
+        return Ok(RawBlocks::new());
+    }
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Write blocks to a temporary file and rename it into place
+fn write_blocks(path: &Path, blocks: &RawBlocks) -> Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    fs::write(&tmp_path, serde_json::to_string_pretty(blocks)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::common::load_block;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn block(block_num: &str, status: BlockStatus, reveal_deadline: DateTime<Utc>) -> BlockData {
+        let mut block = BlockData::with_deadlines(
+            block_num.to_string(),
+            format!("block{}.jpg", block_num),
+            format!("social_{}", block_num),
+            100.0,
+            reveal_deadline - chrono::Duration::hours(24),
+            reveal_deadline,
+        );
+        block.status = status;
+        block
+    }
+
+    fn write_fixture(dir: &Path) -> PathBuf {
+        let blocks_file = dir.join("blocks.json");
+        let date = |month, day| Utc.with_ymd_and_hms(2024, month, day, 12, 0, 0).unwrap();
+        let blocks: BTreeMap<String, BlockData> = [
+            block("1", BlockStatus::Complete, date(2, 10)),
+            block("2", BlockStatus::Open, date(2, 11)),
+            block("3", BlockStatus::Complete, date(5, 1)),
+        ]
+        .into_iter()
+        .map(|block| (block.block_num.clone(), block))
+        .collect();
+        fs::write(&blocks_file, serde_json::to_string_pretty(&blocks).unwrap()).unwrap();
+        blocks_file
+    }
+
+    #[test]
+    fn test_archive_file_name() {
+        let date = |month| Utc.with_ymd_and_hms(2024, month, 15, 0, 0, 0).unwrap();
+        assert_eq!(archive_file_name(date(1)), "blocks-2024Q1.json");
+        assert_eq!(archive_file_name(date(3)), "blocks-2024Q1.json");
+        assert_eq!(archive_file_name(date(4)), "blocks-2024Q2.json");
+        assert_eq!(archive_file_name(date(12)), "blocks-2024Q4.json");
+    }
+
+    #[test]
+    fn test_archive_moves_finished_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks_file = write_fixture(temp_dir.path());
+        let archive_dir = default_archive_dir(&blocks_file);
+        let cutoff = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+
+        let moved = archive_blocks(&blocks_file, &archive_dir, cutoff, false).unwrap();
+
+        // Only the finished block before the cutoff moves; the open one stays
+        assert_eq!(
+            moved,
+            vec![ArchivedBlock {
+                block_num: "1".to_string(),
+                archive_file: archive_dir.join("blocks-2024Q1.json"),
+            }]
+        );
+        let active = read_blocks(&blocks_file).unwrap();
+        assert_eq!(active.keys().collect::<Vec<_>>(), vec!["2", "3"]);
+        assert!(load_block(&blocks_file, "2").is_ok());
+
+        // The archived block is still retrievable by number
+        let archived = load_archived_block(&archive_dir, "1").unwrap().unwrap();
+        assert_eq!(archived.status, BlockStatus::Complete);
+        assert_eq!(load_block(&blocks_file, "1").unwrap().social_id, "social_1");
+        assert!(load_block(&blocks_file, "4").is_err());
+
+        // Running again finds nothing more to move
+        assert!(archive_blocks(&blocks_file, &archive_dir, cutoff, false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(fs::read_dir(&archive_dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_archive_dry_run_changes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks_file = write_fixture(temp_dir.path());
+        let before = fs::read_to_string(&blocks_file).unwrap();
+        let archive_dir = default_archive_dir(&blocks_file);
+        let cutoff = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let moved = archive_blocks(&blocks_file, &archive_dir, cutoff, true).unwrap();
+
+        let block_nums: Vec<&str> = moved.iter().map(|b| b.block_num.as_str()).collect();
+        assert_eq!(block_nums, vec!["1", "3"]);
+        assert_eq!(moved[1].archive_file, archive_dir.join("blocks-2024Q2.json"));
+        assert_eq!(fs::read_to_string(&blocks_file).unwrap(), before);
+        assert!(!archive_dir.exists());
+    }
+}
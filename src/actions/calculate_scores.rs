@@ -5,18 +5,17 @@
 //!
 //! See MVP Slice 6 (v0.6.6) for requirements.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use clap::Parser;
 use colored::Colorize;
 
-use crate::embedder::{ClipEmbedder, MockEmbedder};
+use crate::embedder::{ClipEmbedder, EmbedderTrait, MockEmbedder};
 use crate::block_processor::BlockProcessor;
-use crate::scoring::ClipBatchStrategy;
+use crate::scoring::{ClipBatchStrategy, ScoringStrategy};
+use crate::scoring_cache::ScoringCache;
 use crate::types::{Participant, ScoringResult};
 use crate::error::Result;
-// Note: PayoutCalculator and PayoutConfig are imported for future use
-// when we integrate the payout system more deeply
-// use crate::payout::{PayoutCalculator, PayoutConfig};
+use crate::payout::{PayoutCalculator, PayoutConfig, DEFAULT_CURRENCY_LABEL};
 
 /// Command-line arguments for calculate-scores
 #[derive(Parser)]
@@ -38,6 +37,12 @@ Examples:
   
   # Save results to JSON file
   cliptions calculate-scores --block-num block1 --prize-pool 1000.0 --output json --output-file results.json
+  
+  # Export the full guess similarity matrix for analysis
+  cliptions calculate-scores --block-num block1 --prize-pool 1000.0 --similarity-matrix matrix.csv
+
+Similarities are cached in scoring_cache.json beside the blocks file, so re-running a block
+with the same image, guesses and model skips the embedder. Use --no-score-cache to recompute.
 ")]
 pub struct CalculateScoresArgs {
     /// Block ID to calculate scores for
@@ -64,11 +69,52 @@ pub struct CalculateScoresArgs {
     #[arg(long)]
     pub use_mock: bool,
     
+    /// CLIP model directory (default: $CLIPTIONS_MODEL_DIR or ~/.cliptions/models)
+    #[arg(long, conflicts_with = "use_mock")]
+    pub clip_model: Option<PathBuf>,
+    
+    /// Currency label shown next to prize pool and payout amounts
+    #[arg(long, default_value = DEFAULT_CURRENCY_LABEL)]
+    pub currency_label: String,
+    
+    /// Refuse to pay out prize pools smaller than this
+    #[arg(long, default_value = "0")]
+    pub min_prize_pool: f64,
+    
+    /// Write the image and guess similarity matrix to this file (CSV for .csv, otherwise JSON)
+    #[arg(long)]
+    pub similarity_matrix: Option<PathBuf>,
+    
+    /// Recompute every similarity instead of reusing the scoring cache
+    #[arg(long)]
+    pub no_score_cache: bool,
+    
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+impl CalculateScoresArgs {
+    /// Payout settings for the prize pool, currency label and minimum pool
+    fn payout_config(&self) -> PayoutConfig {
+        PayoutConfig {
+            prize_pool: self.prize_pool,
+            currency_label: self.currency_label.clone(),
+            min_prize_pool: self.min_prize_pool,
+            ..PayoutConfig::default()
+        }
+    }
+
+    /// Scoring cache kept beside the blocks file, unless disabled
+    fn scoring_cache(&self) -> Result<Option<ScoringCache>> {
+        if self.no_score_cache {
+            return Ok(None);
+        }
+        let data_dir = Path::new(&self.blocks_file).parent().unwrap_or(Path::new(""));
+        ScoringCache::in_data_dir(data_dir).map(Some)
+    }
+}
+
 /// Load verified participants from blocks data
 fn load_verified_participants(block_num: &str, blocks_file: &str) -> Result<Vec<Participant>> {
     // Create embedder and processor
@@ -100,21 +146,24 @@ fn load_verified_participants(block_num: &str, blocks_file: &str) -> Result<Vec<
 /// Calculate scores and payouts for participants using the PayoutCalculator
 fn calculate_scores_and_payouts(
     participants: &[Participant],
-    block_num: &str,
-    blocks_file: &str,
-    prize_pool: f64,
-    use_mock: bool,
-    verbose: bool,
+    args: &CalculateScoresArgs,
 ) -> Result<Vec<ScoringResult>> {
+    let block_num = args.block_num.as_str();
+    let blocks_file = args.blocks_file.as_str();
+    let verbose = args.verbose;
+    
     // Create embedder based on user preference
     let strategy = ClipBatchStrategy::new();
     
-    if use_mock {
+    if args.use_mock {
         if verbose {
             println!("Using MockEmbedder for testing");
         }
         let embedder = MockEmbedder::clip_like();
         let mut processor = BlockProcessor::new(blocks_file.to_string(), embedder, strategy);
+        if let Some(cache) = args.scoring_cache()? {
+            processor = processor.with_scoring_cache(cache);
+        }
         
         // Load blocks data
         processor.load_blocks()?;
@@ -134,14 +183,25 @@ fn calculate_scores_and_payouts(
             println!("Successfully calculated scores and payouts for {} participants", results.len());
         }
         
+        if let Some(path) = &args.similarity_matrix {
+            export_similarity_matrix(&mut processor, block_num, path, verbose)?;
+        }
+        
         Ok(results)
     } else {
-        match ClipEmbedder::new() {
+        let clip_embedder = match &args.clip_model {
+            Some(dir) => ClipEmbedder::new_in(dir),
+            None => ClipEmbedder::new(),
+        };
+        match clip_embedder {
             Ok(clip_embedder) => {
                 if verbose {
                     println!("Using CLIP embedder for semantic scoring");
                 }
                 let mut processor = BlockProcessor::new(blocks_file.to_string(), clip_embedder, strategy);
+                if let Some(cache) = args.scoring_cache()? {
+                    processor = processor.with_scoring_cache(cache);
+                }
                 
                 // Load blocks data
                 processor.load_blocks()?;
@@ -156,11 +216,16 @@ fn calculate_scores_and_payouts(
                 
                 // Process block payouts using the existing BlockProcessor logic
                 let results = processor.process_block_payouts(block_num)?;
+                print_warnings(processor.take_warnings());
                 
                 if verbose {
                     println!("Successfully calculated scores and payouts for {} participants", results.len());
                 }
                 
+                if let Some(path) = &args.similarity_matrix {
+                    export_similarity_matrix(&mut processor, block_num, path, verbose)?;
+                }
+                
                 Ok(results)
             }
             Err(e) => {
@@ -170,6 +235,27 @@ fn calculate_scores_and_payouts(
     }
 }
 
+/// Write a block's similarity matrix as CSV or JSON, chosen by the file extension
+fn export_similarity_matrix<E: EmbedderTrait, S: ScoringStrategy>(
+    processor: &mut BlockProcessor<E, S>,
+    block_num: &str,
+    path: &Path,
+    verbose: bool,
+) -> Result<()> {
+    let matrix = processor.similarity_matrix(block_num)?;
+    let content = match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("csv") => matrix.to_csv()?,
+        _ => serde_json::to_string_pretty(&matrix)?,
+    };
+    std::fs::write(path, content)?;
+    
+    if verbose {
+        println!("Similarity matrix saved to {}", path.display());
+    }
+    
+    Ok(())
+}
+
 /// Update the blocks.json file with calculated scores, payouts, and prize pool
 fn update_blocks_file(
     block_num: &str,
@@ -244,12 +330,19 @@ fn update_blocks_file(
     Ok(())
 }
 
+/// Report processor warnings, such as truncated guesses, on stderr
+fn print_warnings(warnings: Vec<String>) {
+    for warning in warnings {
+        eprintln!("{} {}", "Warning:".yellow().bold(), warning);
+    }
+}
+
 /// Display results in the specified format
 fn display_results(results: &[ScoringResult], args: &CalculateScoresArgs) -> Result<()> {
     match args.output.as_str() {
         "table" => display_table_format(results, args),
-        "json" => display_json_format(results),
-        "csv" => display_csv_format(results),
+        "json" => display_json_format(results, &args.payout_config()),
+        "csv" => display_csv_format(results, &args.payout_config()),
         _ => Err(crate::error::CliptionsError::ValidationError(
             format!("Unsupported output format: {}", args.output)
         )),
@@ -260,8 +353,9 @@ fn display_results(results: &[ScoringResult], args: &CalculateScoresArgs) -> Res
 fn display_table_format(results: &[ScoringResult], args: &CalculateScoresArgs) -> Result<()> {
     println!("\n{}", "Block Results:".bold().underline());
     println!("{}", "=".repeat(80));
+    let payout_config = args.payout_config();
     println!("Block ID: {}", args.block_num.bold().blue());
-    println!("Prize Pool: {:.9} TAO", args.prize_pool);
+    println!("Prize Pool: {}", payout_config.format_amount(args.prize_pool));
     println!("{}", "=".repeat(80));
 
     for (i, result) in results.iter().enumerate() {
@@ -272,7 +366,7 @@ fn display_table_format(results: &[ScoringResult], args: &CalculateScoresArgs) -
         if let Some(rank_num) = result.rank {
             println!("   Rank: {}", rank_num);
         }
-        println!("   Payout: {:.9} TAO", result.payout.unwrap_or(0.0));
+        println!("   Payout: {}", payout_config.format_amount(result.payout.unwrap_or(0.0)));
 
         if i == 0 {
             println!("   Status: {}", "🏆 Winner".green().bold());
@@ -284,7 +378,7 @@ fn display_table_format(results: &[ScoringResult], args: &CalculateScoresArgs) -
 
     println!("{}", "=".repeat(80));
     let total_payout: f64 = results.iter().filter_map(|r| r.payout).sum();
-    println!("Total Distributed: {:.9} TAO", total_payout);
+    println!("Total Distributed: {}", payout_config.format_amount(total_payout));
     
     let efficiency = (total_payout / args.prize_pool) * 100.0;
     println!("Distribution Efficiency: {:.2}%", efficiency);
@@ -293,7 +387,7 @@ fn display_table_format(results: &[ScoringResult], args: &CalculateScoresArgs) -
 }
 
 /// Display results in JSON format
-fn display_json_format(results: &[ScoringResult]) -> Result<()> {
+fn display_json_format(results: &[ScoringResult], payout_config: &PayoutConfig) -> Result<()> {
     let mut output = serde_json::Map::new();
     
     let rankings: Vec<serde_json::Value> = results.iter().map(|result| {
@@ -308,6 +402,7 @@ fn display_json_format(results: &[ScoringResult]) -> Result<()> {
 
     output.insert("rankings".to_string(), serde_json::Value::Array(rankings));
     output.insert("num_participants".to_string(), serde_json::Value::from(results.len()));
+    output.insert("currency".to_string(), serde_json::Value::from(payout_config.currency_label.clone()));
     output.insert("timestamp".to_string(), serde_json::Value::from(chrono::Utc::now().to_rfc3339()));
 
     let json_output = serde_json::to_string_pretty(&output).map_err(|e| crate::error::CliptionsError::Json(e))?;
@@ -317,26 +412,38 @@ fn display_json_format(results: &[ScoringResult]) -> Result<()> {
 }
 
 /// Display results in CSV format
-fn display_csv_format(results: &[ScoringResult]) -> Result<()> {
-    println!("rank,username,guess,similarity_score,payout");
+fn display_csv_format(results: &[ScoringResult], payout_config: &PayoutConfig) -> Result<()> {
+    print!("{}", csv_content(results, payout_config)?);
+    Ok(())
+}
+
+/// Results as RFC 4180 CSV, quoting fields that contain quotes, commas or newlines
+fn csv_content(results: &[ScoringResult], payout_config: &PayoutConfig) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["rank", "username", "guess", "similarity_score", "payout", "currency"])?;
 
     for result in results {
-        let escaped_guess = result.participant.guess.text.replace("\"", "\"\"");
-        println!(
-            "{},\"{}\",\"{}\",{:.6},{:.9}",
-            result.rank.unwrap_or(0),
-            result.participant.username,
-            escaped_guess,
-            result.raw_score,
-            result.payout.unwrap_or(0.0)
-        );
+        writer.write_record([
+            &result.rank.unwrap_or(0).to_string(),
+            &result.participant.username,
+            &result.participant.guess.text,
+            &format!("{:.6}", result.raw_score),
+            &format!("{:.9}", result.payout.unwrap_or(0.0)),
+            &payout_config.currency_label,
+        ])?;
     }
 
-    Ok(())
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    String::from_utf8(bytes).map_err(|e| crate::error::CliptionsError::Generic(e.to_string()))
 }
 
 /// Save results to file
-fn save_results(results: &[ScoringResult], output_file: &PathBuf, format: &str) -> Result<()> {
+fn save_results(
+    results: &[ScoringResult],
+    output_file: &PathBuf,
+    format: &str,
+    payout_config: &PayoutConfig,
+) -> Result<()> {
     let content = match format {
         "json" => {
             let mut output = serde_json::Map::new();
@@ -353,27 +460,12 @@ fn save_results(results: &[ScoringResult], output_file: &PathBuf, format: &str)
 
             output.insert("rankings".to_string(), serde_json::Value::Array(rankings));
             output.insert("num_participants".to_string(), serde_json::Value::from(results.len()));
+            output.insert("currency".to_string(), serde_json::Value::from(payout_config.currency_label.clone()));
             output.insert("timestamp".to_string(), serde_json::Value::from(chrono::Utc::now().to_rfc3339()));
 
             serde_json::to_string_pretty(&output).map_err(|e| crate::error::CliptionsError::Json(e))?
         }
-        "csv" => {
-            let mut content = String::from("rank,username,guess,similarity_score,payout\n");
-
-            for result in results {
-                let escaped_guess = result.participant.guess.text.replace("\"", "\"\"");
-                content.push_str(&format!(
-                    "{},\"{}\",\"{}\",{:.6},{:.9}\n",
-                    result.rank.unwrap_or(0),
-                    result.participant.username,
-                    escaped_guess,
-                    result.raw_score,
-                    result.payout.unwrap_or(0.0)
-                ));
-            }
-
-            content
-        }
+        "csv" => csv_content(results, payout_config)?,
         "table" => {
             let mut content = String::from("Block Results\n");
             content.push_str(&"=".repeat(50));
@@ -383,7 +475,7 @@ fn save_results(results: &[ScoringResult], output_file: &PathBuf, format: &str)
                 content.push_str(&format!("{}. {}\n", result.rank.unwrap_or(0), result.participant.username));
                 content.push_str(&format!("   Guess: \"{}\"\n", result.participant.guess.text));
                 content.push_str(&format!("   Similarity score: {:.4}\n", result.raw_score));
-                content.push_str(&format!("   Payout: {:.9}\n\n", result.payout.unwrap_or(0.0)));
+                content.push_str(&format!("   Payout: {}\n\n", payout_config.format_amount(result.payout.unwrap_or(0.0))));
             }
 
             content
@@ -399,6 +491,8 @@ fn save_results(results: &[ScoringResult], output_file: &PathBuf, format: &str)
 
 /// Entry point for the calculate-scores subcommand
 pub fn run(args: CalculateScoresArgs) -> Result<()> {
+    PayoutCalculator::with_config(args.payout_config()).check_prize_pool()?;
+    
     // Load verified participants
     let participants = load_verified_participants(&args.block_num, &args.blocks_file)?;
     
@@ -407,14 +501,7 @@ pub fn run(args: CalculateScoresArgs) -> Result<()> {
     }
     
     // Calculate scores and payouts
-    let results = calculate_scores_and_payouts(
-        &participants,
-        &args.block_num,
-        &args.blocks_file,
-        args.prize_pool,
-        args.use_mock,
-        args.verbose,
-    )?;
+    let results = calculate_scores_and_payouts(&participants, &args)?;
     
     if args.verbose {
         println!("Successfully processed block {} with {} results", args.block_num, results.len());
@@ -425,7 +512,7 @@ pub fn run(args: CalculateScoresArgs) -> Result<()> {
     
     // Save results to file if requested
     if let Some(output_file) = &args.output_file {
-        save_results(&results, output_file, &args.output)?;
+        save_results(&results, output_file, &args.output, &args.payout_config())?;
         if args.verbose {
             println!("Results saved to {}", output_file.display());
         }
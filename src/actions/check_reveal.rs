@@ -0,0 +1,258 @@
+//! Check a reveal against the miner's stored commitment before posting it
+//!
+//! A reveal only counts if the guess and salt hash to the
+//! commitment posted earlier. This runs the same check the validator will,
+//! against the commitments kept in the miner's commitment store.
+
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::commitment::CommitmentVerifier;
+use crate::commitment_store::{CommitmentStore, StoredCommitment};
+use crate::error::{CliptionsError, Result};
+
+#[derive(Parser)]
+#[command(name = "check-reveal")]
+#[command(about = "Check that a reveal will verify against your stored commitment")]
+#[command(long_about = "
+Check a reveal before replying with it.
+
+Hashes the guess with the salt and compares the result with the
+commitments kept for the block in your commitment store. On a mismatch, reports
+whether the guess or the salt is the likely culprit.
+
+Examples:
+  cliptions check-reveal --block 42 --guess \"Cat sanctuary with woman wearing snoopy sweater\" --salt \"random_secret_123\"
+")]
+pub struct CheckRevealArgs {
+    /// Block number the commitment was made for
+    #[arg(long = "block")]
+    pub block_num: String,
+
+    /// Guess you are about to reveal
+    #[arg(long)]
+    pub guess: String,
+
+    /// Salt you are about to reveal
+    #[arg(long)]
+    pub salt: String,
+
+    /// Commitment store file (default: ~/.cliptions/miner/commitments.json)
+    #[arg(long)]
+    pub store: Option<PathBuf>,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// Outcome of checking a reveal against a block's stored commitments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevealCheck {
+    /// The reveal hashes to this stored commitment
+    Matches { commitment: String },
+    /// The guess matches a stored commitment with its stored salt, so the salt is wrong
+    WrongSalt { commitment: String },
+    /// The salt matches a stored commitment with its stored guess, so the guess is wrong
+    WrongGuess {
+        commitment: String,
+        stored_guess: String,
+    },
+    /// Neither the guess nor the salt matches any stored commitment
+    NoMatch { commitments: usize },
+    /// Nothing is stored for the block
+    NoCommitment,
+}
+
+impl RevealCheck {
+    pub fn is_match(&self) -> bool {
+        matches!(self, RevealCheck::Matches { .. })
+    }
+}
+
+/// Check `guess` and `salt` against the commitments stored for `block_num`
+pub fn check_reveal(
+    store: &CommitmentStore,
+    block_num: &str,
+    guess: &str,
+    salt: &str,
+) -> RevealCheck {
+    let records = store.get_for_block(block_num);
+    if records.is_empty() {
+        return RevealCheck::NoCommitment;
+    }
+
+    let verify = |record: &StoredCommitment, guess: &str, salt: &str| {
+        CommitmentVerifier::new()
+            .with_hash_algo(record.hash_algo)
+            .verify(guess, salt, &record.commitment)
+    };
+
+    if let Some(record) = records.iter().find(|r| verify(r, guess, salt)) {
+        return RevealCheck::Matches {
+            commitment: record.commitment.clone(),
+        };
+    }
+
+    // Swap in each stored half to see which of the two was mistyped
+    for record in &records {
+        if verify(record, guess, &record.salt) {
+            return RevealCheck::WrongSalt {
+                commitment: record.commitment.clone(),
+            };
+        }
+        if verify(record, &record.guess, salt) {
+            return RevealCheck::WrongGuess {
+                commitment: record.commitment.clone(),
+                stored_guess: record.guess.clone(),
+            };
+        }
+    }
+
+    RevealCheck::NoMatch {
+        commitments: records.len(),
+    }
+}
+
+pub fn run(args: CheckRevealArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    let store_path = match &args.store {
+        Some(path) => path.clone(),
+        None => CommitmentStore::default_path()?,
+    };
+    let store = CommitmentStore::open(&store_path)?;
+
+    let check = check_reveal(&store, &args.block_num, &args.guess, &args.salt);
+    match &check {
+        RevealCheck::Matches { commitment } => {
+            println!(
+                "{} Reveal matches commitment {} for block {}",
+                "PASS:".green().bold(),
+                commitment,
+                args.block_num
+            );
+            println!("  Guess: {:?}", args.guess);
+        }
+        RevealCheck::WrongSalt { commitment } => {
+            println!(
+                "{} The guess matches commitment {}, but the salt does not",
+                "FAIL:".red().bold(),
+                commitment
+            );
+            println!("  Check the salt for typos or extra characters");
+        }
+        RevealCheck::WrongGuess {
+            commitment,
+            stored_guess,
+        } => {
+            println!(
+                "{} The salt matches commitment {}, but the guess does not",
+                "FAIL:".red().bold(),
+                commitment
+            );
+            println!("  Revealed guess:  {:?}", args.guess);
+            println!("  Committed guess: {:?}", stored_guess);
+        }
+        RevealCheck::NoMatch { commitments } => {
+            println!(
+                "{} Neither the guess nor the salt matches a stored commitment",
+                "FAIL:".red().bold()
+            );
+            println!(
+                "  Block {} has {} stored commitment(s)",
+                args.block_num, commitments
+            );
+        }
+        RevealCheck::NoCommitment => {
+            println!(
+                "{} No commitment stored for block {} in {}",
+                "FAIL:".red().bold(),
+                args.block_num,
+                store_path.display()
+            );
+        }
+    }
+
+    if check.is_match() {
+        Ok(())
+    } else {
+        Err(CliptionsError::ValidationError(format!(
+            "Reveal for block {} would not verify",
+            args.block_num
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::CommitmentGenerator;
+    use tempfile::TempDir;
+
+    fn store_with_commitment(dir: &TempDir, guess: &str, salt: &str) -> (CommitmentStore, String) {
+        let commitment = CommitmentGenerator::new().generate(guess, salt).unwrap();
+        let mut store = CommitmentStore::open(dir.path().join("store.json")).unwrap();
+        store
+            .add(StoredCommitment::new(
+                "42".to_string(),
+                guess.to_string(),
+                salt.to_string(),
+                commitment.clone(),
+            ))
+            .unwrap();
+        (store, commitment)
+    }
+
+    #[test]
+    fn test_matching_reveal_passes() {
+        let dir = TempDir::new().unwrap();
+        let (store, commitment) = store_with_commitment(&dir, "A cat on a mat", "salt123");
+
+        assert_eq!(
+            check_reveal(&store, "42", "A cat on a mat", "salt123"),
+            RevealCheck::Matches {
+                commitment: commitment.clone()
+            }
+        );
+        // Guesses are hashed byte for byte, so case and spacing matter
+        assert_eq!(
+            check_reveal(&store, "42", "  a CAT  on a mat", "salt123"),
+            RevealCheck::WrongGuess {
+                commitment,
+                stored_guess: "A cat on a mat".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_mismatching_reveal_names_culprit() {
+        let dir = TempDir::new().unwrap();
+        let (store, commitment) = store_with_commitment(&dir, "A cat on a mat", "salt123");
+
+        assert_eq!(
+            check_reveal(&store, "42", "A cat on a mat", "salt124"),
+            RevealCheck::WrongSalt {
+                commitment: commitment.clone()
+            }
+        );
+        assert_eq!(
+            check_reveal(&store, "42", "A cat on a hat", "salt123"),
+            RevealCheck::WrongGuess {
+                commitment,
+                stored_guess: "A cat on a mat".to_string()
+            }
+        );
+        assert_eq!(
+            check_reveal(&store, "42", "A dog", "other"),
+            RevealCheck::NoMatch { commitments: 1 }
+        );
+        assert_eq!(
+            check_reveal(&store, "7", "A cat on a mat", "salt123"),
+            RevealCheck::NoCommitment
+        );
+    }
+}
@@ -1,10 +1,19 @@
 use clap::Parser;
 use colored::Colorize;
+use chrono::Duration;
 use std::fs;
+use std::sync::Arc;
 use std::path::PathBuf;
+use crate::actions::common::{load_block, reply_timing};
+use crate::actions::reply_store::{
+    block_results_path, validator_data_dir, ReplyStore, COMMITMENTS_DIR,
+};
+use crate::clock::{Clock, SystemClock};
+use crate::commitment::HashAlgo;
 use crate::config::ConfigManager;
 use crate::error::Result;
-use twitter_api::{TwitterApi, TwitterClient, TwitterError};
+use crate::types::{BlockData, SubmissionPhase, SubmissionTiming, DEFAULT_DEADLINE_GRACE_SECS};
+use twitter_api::{SearchScope, TwitterApi, TwitterClient, TwitterError};
 
 #[derive(Parser)]
 pub struct CollectCommitmentsArgs {
@@ -20,7 +29,9 @@ pub struct CollectCommitmentsArgs {
     #[arg(long, short, default_value = "text", value_parser = ["text", "json", "csv"])]
     pub output: String,
 
-    /// Save collected commitments to file (JSON format, defaults to ~/.cliptions/validator/collected_commitments.json)
+    /// Save collected commitments to file (JSON format, defaults to
+    /// ~/.cliptions/validator/collected_commitments/<block>.json with --block-num,
+    /// else ~/.cliptions/validator/collected_commitments.json)
     #[arg(long)]
     pub save_to: Option<PathBuf>,
 
@@ -43,6 +54,27 @@ pub struct CollectCommitmentsArgs {
     /// Config file path (default: config/config.yaml)
     #[arg(long, default_value = "config/config.yaml")]
     pub config: String,
+
+    /// Block ID in the blocks file; commitments posted outside its window are rejected
+    #[arg(long)]
+    pub block_num: Option<String>,
+
+    /// Path to blocks.json file (default: data/blocks.json)
+    #[arg(long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Seconds after the commitment deadline that commitments are still accepted
+    #[arg(
+        long,
+        default_value_t = DEFAULT_DEADLINE_GRACE_SECS,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub deadline_grace_secs: i64,
+
+    /// Only fetch replies newer than the last run for this block, merging them into
+    /// ~/.cliptions/validator/collected_commitments/<block>.json
+    #[arg(long, requires = "block_num", conflicts_with_all = ["save_to", "no_save"])]
+    pub incremental: bool,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -54,6 +86,12 @@ pub struct CollectedCommitmentData {
     pub timestamp: String,
     pub author_id: String,
     pub conversation_id: Option<String>,
+    /// Posted after the commitment deadline, inside the grace period
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub within_grace: bool,
+    /// Algorithm named on the reply's `Hash:` line, SHA-256 when there is none
+    #[serde(default, skip_serializing_if = "HashAlgo::is_default")]
+    pub hash_algo: HashAlgo,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -82,18 +120,33 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
     let twitter = &config.twitter;
     println!("\u{2705} Loaded config from: {}", &args.config);
 
+    // Load the block whose deadlines gate collection, if one was given
+    let block = match &args.block_num {
+        Some(block_num) => Some(load_block(&args.blocks_file, block_num)?),
+        None => None,
+    };
+
     // Create TwitterClient
     let twitter_config = twitter_api::TwitterConfig {
         api_key: twitter.api_key.clone(),
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(twitter_config);
 
+    if args.incremental {
+        let block_num = args.block_num.as_deref().unwrap_or_default();
+        let collector = CommitmentCollector::new(client, validator_data_dir()?)
+            .with_max_results(args.max_results)
+            .with_deadline_grace(Duration::seconds(args.deadline_grace_secs));
+        return run_incremental(&args, &collector, block_num, block.as_ref()).await;
+    }
+
     // Search for replies
     let result = client
-        .search_replies(&args.tweet_id, args.max_results)
+        .search_replies(&args.tweet_id, args.max_results, SearchScope::Recent, None)
         .await;
 
     match result {
@@ -103,6 +156,9 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
 
             // Parse and collect commitment data
             let mut collected_commitments = Vec::new();
+            let grace = Duration::seconds(args.deadline_grace_secs);
+            let mut rejected_late = 0;
+            let mut within_grace = 0;
             
             for (i, reply) in replies.iter().enumerate() {
                 if args.verbose {
@@ -130,11 +186,42 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
                 }
 
                 // Try to parse commitment data from reply text
-                if let Some(commitment_data) = parse_commitment_from_reply(reply) {
+                if let Some(mut commitment_data) = parse_commitment_from_reply(reply, &SystemClock) {
+                    let timing = reply_timing(
+                        SubmissionPhase::Commitment,
+                        block.as_ref(),
+                        reply,
+                        grace,
+                        &SystemClock,
+                    );
+                    match timing {
+                        SubmissionTiming::OnTime => {}
+                        SubmissionTiming::WithinGrace => {
+                            commitment_data.within_grace = true;
+                            within_grace += 1;
+                        }
+                        SubmissionTiming::Outside => {
+                            rejected_late += 1;
+                            if !args.quiet {
+                                println!("⏰ Rejected commitment outside the block window: {}", reply.url);
+                            }
+                            continue;
+                        }
+                    }
                     collected_commitments.push(commitment_data);
                 }
             }
 
+            if rejected_late > 0 && !args.quiet {
+                println!("⏰ Rejected {} late commitments", rejected_late);
+            }
+            if within_grace > 0 && !args.quiet {
+                println!(
+                    "⏳ Accepted {} commitments within the {}s grace period",
+                    within_grace, args.deadline_grace_secs
+                );
+            }
+
             // Create results structure
             let results = CollectedCommitmentsResults {
                 commitments: collected_commitments.clone(),
@@ -148,25 +235,18 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
 
             // Save to file (default behavior unless --no-save is specified)
             if !args.no_save {
-                let save_path = if let Some(custom_path) = &args.save_to {
-                    custom_path.clone()
-                } else {
-                    // Default to ~/.cliptions/validator/collected_commitments.json
-                    let home_dir = dirs::home_dir()
-                        .ok_or_else(|| "Could not determine home directory".to_string())?;
-                    let cliptions_dir = home_dir.join(".cliptions");
-                    let validator_dir = cliptions_dir.join("validator");
-                    
-                    // Create directories if they don't exist
-                    if !cliptions_dir.exists() {
-                        fs::create_dir_all(&cliptions_dir)?;
-                    }
-                    if !validator_dir.exists() {
-                        fs::create_dir_all(&validator_dir)?;
-                    }
-                    
-                    validator_dir.join("collected_commitments.json")
+                let save_path = match (&args.save_to, &args.block_num) {
+                    (Some(custom_path), _) => custom_path.clone(),
+                    // One file per block, so blocks never mix
+                    (None, Some(block_num)) => block_results_path(
+                        &validator_data_dir()?.join(COMMITMENTS_DIR),
+                        block_num,
+                    )?,
+                    (None, None) => validator_data_dir()?.join("collected_commitments.json"),
                 };
+                if let Some(dir) = save_path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
 
                 save_results(&results, &save_path)?;
 
@@ -194,6 +274,14 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
             }
             return Err(format!("Twitter API error: {} - {}", status, message).into());
         }
+        Err(TwitterError::ApiStructured { status, title, detail, .. }) => {
+            let message = format!("{}: {}", title, detail);
+            println!("❌ Twitter API error: {} - {}", status, message);
+            if status == 404 {
+                println!("💡 Make sure the tweet ID exists and is correct");
+            }
+            return Err(format!("Twitter API error: {} - {}", status, message).into());
+        }
         Err(TwitterError::NetworkError(e)) => {
             println!("❌ Network error: {}", e);
             return Err(format!("Network error: {}", e).into());
@@ -214,6 +302,14 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
             println!("❌ Invalid input: {}", e);
             return Err(format!("Invalid input: {}", e).into());
         }
+        Err(TwitterError::DuplicateContent(e)) => {
+            println!("❌ Duplicate content: {}", e);
+            return Err(format!("Duplicate content: {}", e).into());
+        }
+        Err(TwitterError::Unsupported(e)) => {
+            println!("❌ Not supported: {}", e);
+            return Err(format!("Not supported: {}", e).into());
+        }
         Err(TwitterError::FileError(e)) => {
             println!("❌ File error: {}", e);
             return Err(format!("File error: {}", e).into());
@@ -231,17 +327,244 @@ pub async fn run(args: CollectCommitmentsArgs) -> Result<()> {
     Ok(())
 }
 
-fn parse_commitment_from_reply(reply: &twitter_api::Tweet) -> Option<CollectedCommitmentData> {
+async fn run_incremental<C: TwitterApi>(
+    args: &CollectCommitmentsArgs,
+    collector: &CommitmentCollector<C>,
+    block_num: &str,
+    block: Option<&BlockData>,
+) -> Result<()> {
+    if args.verbose {
+        match collector.cursor(block_num)? {
+            Some(since_id) => println!("Resuming block {} from tweet {}", block_num, since_id),
+            None => println!("No saved cursor for block {}, scanning all replies", block_num),
+        }
+    }
+
+    let collection = collector.collect(block_num, &args.tweet_id, block).await?;
+
+    if !args.quiet {
+        match &collection.search_error {
+            Some(e) => println!("⚠️  Search stopped early, will resume next run: {}", e),
+            None => println!("✅ Search complete!"),
+        }
+        println!("New replies found: {}", collection.replies_seen);
+        if collection.rejected_late > 0 {
+            println!("⏰ Rejected {} late commitments", collection.rejected_late);
+        }
+        if collection.within_grace > 0 {
+            println!(
+                "⏳ Accepted {} commitments within the {}s grace period",
+                collection.within_grace, args.deadline_grace_secs
+            );
+        }
+        println!("New commitments collected: {}", collection.new_commitments);
+        if collection.replaced > 0 {
+            println!("✏️  Replaced {} edited commitments", collection.replaced);
+        }
+    }
+
+    display_results(&collection.results, args)?;
+
+    if !args.quiet {
+        println!(
+            "{} Collected commitments saved to {}",
+            "Success:".green().bold(),
+            collector.commitments_path(block_num)?.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Outcome of one incremental collection run
+pub struct CommitmentCollection {
+    /// Replies returned by the search, newer than the saved cursor
+    pub replies_seen: usize,
+    /// Set when the search failed partway, leaving the cursor where it was
+    pub search_error: Option<String>,
+    /// Commitments from authors with no stored commitment
+    pub new_commitments: usize,
+    /// Stored commitments replaced by a newer one from the same author
+    pub replaced: usize,
+    /// Commitments dropped for falling outside the block's commitment window
+    pub rejected_late: usize,
+    /// Commitments accepted after the deadline, inside the grace period
+    pub within_grace: usize,
+    /// All stored commitments after merging
+    pub results: CollectedCommitmentsResults,
+}
+
+/// Collects commitments incrementally, resuming from the newest reply seen on the last run
+///
+/// The newest-seen tweet ID for each block is kept in `commitment_cursors.json`
+/// and passed as `since_id` on the next search; new commitments are merged into
+/// the block's own `collected_commitments/<block>.json`. Both live in the
+/// collector's data directory. Each author keeps only their latest commitment,
+/// so one edited in a later reply replaces the earlier one, even across runs.
+pub struct CommitmentCollector<C: TwitterApi> {
+    client: C,
+    store: ReplyStore,
+    max_results: u32,
+    deadline_grace: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl<C: TwitterApi> CommitmentCollector<C> {
+    /// Create a collector storing its files in `data_dir`
+    pub fn new(client: C, data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client,
+            store: ReplyStore::new(data_dir.into(), "commitment_cursors.json", COMMITMENTS_DIR),
+            max_results: 100,
+            deadline_grace: Duration::seconds(DEFAULT_DEADLINE_GRACE_SECS),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Set the maximum results per page
+    pub fn with_max_results(mut self, max_results: u32) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Accept commitments up to `deadline_grace` after the commitment deadline
+    pub fn with_deadline_grace(mut self, deadline_grace: Duration) -> Self {
+        self.deadline_grace = deadline_grace;
+        self
+    }
+
+    /// Use `clock` to time replies without a timestamp and to stamp collections
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// The client replies are searched with
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Path of the per-block cursor file
+    pub fn cursors_path(&self) -> PathBuf {
+        self.store.cursors_path()
+    }
+
+    /// Path of the merged commitments file for a block
+    pub fn commitments_path(&self, block_num: &str) -> Result<PathBuf> {
+        self.store.results_path(block_num)
+    }
+
+    /// Newest tweet ID seen for a block, if it has been collected before
+    pub fn cursor(&self, block_num: &str) -> Result<Option<String>> {
+        self.store.cursor(block_num)
+    }
+
+    /// Fetch replies newer than the block's cursor and merge any commitments among them
+    ///
+    /// Running twice with no new replies leaves the stored commitments and cursor unchanged.
+    pub async fn collect(
+        &self,
+        block_num: &str,
+        tweet_id: &str,
+        block: Option<&BlockData>,
+    ) -> Result<CommitmentCollection> {
+        // Oldest first, so an author's latest reply is merged last
+        let search = self
+            .store
+            .fetch(&self.client, block_num, tweet_id, self.max_results, SearchScope::Recent)
+            .await?;
+        let replies = search.tweets;
+
+        let mut results = self
+            .store
+            .load_results(block_num)?
+            .unwrap_or_else(|| CollectedCommitmentsResults {
+                commitments: Vec::new(),
+                total_collected: 0,
+                original_tweet_id: tweet_id.to_string(),
+                collection_timestamp: self.clock.now().to_rfc3339(),
+            });
+        let mut new_commitments = 0;
+        let mut replaced = 0;
+        let mut rejected_late = 0;
+        let mut within_grace = 0;
+        for reply in &replies {
+            let Some(mut commitment_data) = parse_commitment_from_reply(reply, &self.clock) else {
+                continue;
+            };
+            let timing = reply_timing(
+                SubmissionPhase::Commitment,
+                block,
+                reply,
+                self.deadline_grace,
+                &self.clock,
+            );
+            match timing {
+                SubmissionTiming::OnTime => {}
+                SubmissionTiming::WithinGrace => {
+                    commitment_data.within_grace = true;
+                    within_grace += 1;
+                }
+                SubmissionTiming::Outside => {
+                    rejected_late += 1;
+                    continue;
+                }
+            }
+            match results
+                .commitments
+                .iter_mut()
+                .find(|c| c.author_id == commitment_data.author_id)
+            {
+                Some(existing) if existing.tweet_url == commitment_data.tweet_url => {}
+                Some(existing) => {
+                    *existing = commitment_data;
+                    replaced += 1;
+                }
+                None => {
+                    results.commitments.push(commitment_data);
+                    new_commitments += 1;
+                }
+            }
+        }
+        results.total_collected = results.commitments.len();
+        results.collection_timestamp = self.clock.now().to_rfc3339();
+
+        self.store.save(block_num, &results, search.newest_id)?;
+
+        Ok(CommitmentCollection {
+            replies_seen: replies.len(),
+            search_error: search.error,
+            new_commitments,
+            replaced,
+            rejected_late,
+            within_grace,
+            results,
+        })
+    }
+}
+
+/// Commitment in `reply`, stamped with `clock`'s time if the reply has no timestamp
+fn parse_commitment_from_reply(
+    reply: &twitter_api::Tweet,
+    clock: &dyn Clock,
+) -> Option<CollectedCommitmentData> {
     let text = &reply.text;
     
     // Look for patterns like:
     // "Commit: [hash]"
     // "Wallet: [address]"
+    // "Hash: blake3" (optional)
     let commit_pattern = regex::Regex::new(r"Commit:\s*([a-fA-F0-9]{64})").ok()?;
     let wallet_pattern = regex::Regex::new(r"Wallet:\s*([^\s\n]+)").ok()?;
+    let hash_pattern = regex::Regex::new(r"\bHash:\s*([^\s\n]+)").ok()?;
     
     let commitment_hash = commit_pattern.captures(text)?.get(1)?.as_str().to_string();
     let wallet_address = wallet_pattern.captures(text)?.get(1)?.as_str().to_string();
+    // A commitment under an unknown algorithm could never be verified
+    let hash_algo = match hash_pattern.captures(text) {
+        Some(captures) => captures.get(1)?.as_str().parse().ok()?,
+        None => HashAlgo::default(),
+    };
     
     // Extract username from author_id (we'll need to get the actual username)
     let username = format!("user_{}", reply.author_id);
@@ -253,9 +576,11 @@ fn parse_commitment_from_reply(reply: &twitter_api::Tweet) -> Option<CollectedCo
         tweet_url: reply.url.clone(),
         timestamp: reply.created_at
             .map(|dt| dt.to_rfc3339())
-            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+            .unwrap_or_else(|| clock.now().to_rfc3339()),
         author_id: reply.author_id.clone(),
         conversation_id: reply.conversation_id.clone(),
+        within_grace: false,
+        hash_algo,
     })
 }
 
@@ -298,6 +623,9 @@ fn display_text_format(
             println!("{}: {}", "Wallet Address".blue().bold(), data.wallet_address);
             println!("{}: {}", "Tweet URL".blue().bold(), data.tweet_url);
             println!("{}: {}", "Timestamp".blue().bold(), data.timestamp);
+            if data.within_grace {
+                println!("{}", "Posted within the deadline grace period".yellow());
+            }
         } else {
             // Simple format
             println!("Commitment: {}", data.commitment_hash);
@@ -324,6 +652,9 @@ fn display_text_format(
             println!("  Wallet: {}", data.wallet_address);
             println!("  URL: {}", data.tweet_url);
             println!("  Time: {}", data.timestamp);
+            if data.within_grace {
+                println!("  {}", "Posted within the deadline grace period".yellow());
+            }
             println!();
         }
     }
@@ -338,32 +669,35 @@ fn display_json_format(results: &CollectedCommitmentsResults) -> Result<()> {
 }
 
 fn display_csv_format(results: &CollectedCommitmentsResults) -> Result<()> {
-    println!("username,commitment_hash,wallet_address,tweet_url,timestamp,author_id,conversation_id");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record([
+        "username",
+        "commitment_hash",
+        "wallet_address",
+        "tweet_url",
+        "timestamp",
+        "author_id",
+        "conversation_id",
+        "within_grace",
+    ])?;
 
     for data in &results.commitments {
-        println!(
-            "{},{},{},{},{},{},{}",
-            csv_escape(&data.username),
-            data.commitment_hash,
-            csv_escape(&data.wallet_address),
-            csv_escape(&data.tweet_url),
-            csv_escape(&data.timestamp),
-            csv_escape(&data.author_id),
-            data.conversation_id.as_deref().map_or("".to_string(), |s| csv_escape(s))
-        );
+        writer.write_record([
+            data.username.as_str(),
+            &data.commitment_hash,
+            &data.wallet_address,
+            &data.tweet_url,
+            &data.timestamp,
+            &data.author_id,
+            data.conversation_id.as_deref().unwrap_or(""),
+            &data.within_grace.to_string(),
+        ])?;
     }
 
+    writer.flush()?;
     Ok(())
 }
 
-fn csv_escape(field: &str) -> String {
-    if field.contains(',') || field.contains('"') || field.contains('\n') {
-        format!("\"{}\"", field.replace('"', "\"\""))
-    } else {
-        field.to_string()
-    }
-}
-
 fn save_results(
     results: &CollectedCommitmentsResults,
     save_path: &PathBuf,
@@ -398,6 +732,7 @@ fn save_results(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use twitter_api::testing::InMemoryTwitterApi;
 
     #[test]
     fn test_collect_commitments_args_parsing() {
@@ -411,6 +746,10 @@ mod tests {
             no_color: false,
             quiet: false,
             config: "test_config.yaml".to_string(),
+            block_num: None,
+            blocks_file: PathBuf::from("data/blocks.json"),
+            deadline_grace_secs: DEFAULT_DEADLINE_GRACE_SECS,
+            incremental: false,
         };
 
         assert_eq!(args.tweet_id, "123456789");
@@ -434,6 +773,10 @@ mod tests {
             no_color: false,
             quiet: false,
             config: "config/config.yaml".to_string(),
+            block_num: None,
+            blocks_file: PathBuf::from("data/blocks.json"),
+            deadline_grace_secs: DEFAULT_DEADLINE_GRACE_SECS,
+            incremental: false,
         };
 
         assert_eq!(args.max_results, 100);
@@ -456,22 +799,167 @@ mod tests {
             created_at: Some(chrono::Utc::now()),
             conversation_id: Some("123456789".to_string()),
             public_metrics: None,
+            in_reply_to_user_id: None,
+            referenced_tweets: Vec::new(),
         };
 
-        let result = parse_commitment_from_reply(&reply);
+        let result = parse_commitment_from_reply(&reply, &SystemClock);
         assert!(result.is_some());
         
         let commitment_data = result.unwrap();
         assert_eq!(commitment_data.commitment_hash, "abc123def4567890abcdef1234567890abcdef1234567890abcdef1234567890");
         assert_eq!(commitment_data.wallet_address, "5Co2unDtZKZDzYNZHT2fUMkEnpVWnassfbuabvZmGTrYKgtD");
         assert_eq!(commitment_data.author_id, "987654321");
+        assert_eq!(commitment_data.hash_algo, HashAlgo::Sha256);
+
+        // An optional Hash line names the algorithm; an unknown one can't be verified
+        let mut blake3 = reply.clone();
+        blake3.text.push_str("\nHash: blake3");
+        let commitment_data = parse_commitment_from_reply(&blake3, &SystemClock).unwrap();
+        assert_eq!(commitment_data.hash_algo, HashAlgo::Blake3);
+        let mut unknown = reply;
+        unknown.text.push_str("\nHash: md5");
+        assert!(parse_commitment_from_reply(&unknown, &SystemClock).is_none());
     }
 
-    #[test]
-    fn test_csv_escape() {
-        assert_eq!(csv_escape("simple"), "simple");
-        assert_eq!(csv_escape("with,comma"), "\"with,comma\"");
-        assert_eq!(csv_escape("with\"quote"), "\"with\"\"quote\"");
-        assert_eq!(csv_escape("with\nline"), "\"with\nline\"");
+    /// Text of a commitment reply whose hash repeats `hash_byte`
+    fn commitment_text(author: &str, hash_byte: char) -> String {
+        format!(
+            "Commit: {}\nWallet: wallet_{}",
+            hash_byte.to_string().repeat(64),
+            author
+        )
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_commitment_collector_incremental_runs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#block1 #commitmentsopen").await.unwrap().tweet;
+        let collector = CommitmentCollector::new(api, temp_dir.path().join("validator"));
+        let reply = |author: &str, hash_byte| {
+            collector
+                .client()
+                .seed_reply(&root.id, author, &commitment_text(author, hash_byte))
+                .unwrap()
+        };
+        reply("alice", 'a');
+        let bob = reply("bob", 'b');
+
+        // First run: no cursor, everything is collected
+        let first = collector.collect("1", &root.id, None).await.unwrap();
+        assert_eq!(first.new_commitments, 2);
+        assert_eq!(first.replaced, 0);
+        assert_eq!(collector.cursor("1").unwrap(), Some(bob.id));
+
+        // Second run: carol commits for the first time and alice edits hers
+        reply("carol", 'd');
+        let edit = reply("alice", 'c');
+        let second = collector.collect("1", &root.id, None).await.unwrap();
+        assert_eq!(second.replies_seen, 2);
+        assert_eq!(second.new_commitments, 1);
+        assert_eq!(second.replaced, 1);
+        assert_eq!(collector.cursor("1").unwrap(), Some(edit.id.clone()));
+
+        let stored: CollectedCommitmentsResults = serde_json::from_str(
+            &fs::read_to_string(collector.commitments_path("1").unwrap()).unwrap(),
+        )
+        .unwrap();
+        let by_author: Vec<(&str, &str)> = stored
+            .commitments
+            .iter()
+            .map(|c| (c.author_id.as_str(), &c.commitment_hash[..1]))
+            .collect();
+        assert_eq!(by_author, vec![("alice", "c"), ("bob", "b"), ("carol", "d")]);
+        assert_eq!(stored.total_collected, 3);
+        assert_eq!(stored.commitments[0].tweet_url, edit.url);
+
+        // A third run with nothing new changes nothing
+        let third = collector.collect("1", &root.id, None).await.unwrap();
+        assert_eq!(third.replies_seen, 0);
+        assert_eq!(third.results.commitments.len(), 3);
+        assert_eq!(collector.cursor("1").unwrap(), Some(edit.id));
+    }
+
+    #[tokio::test]
+    async fn test_commitment_collector_latest_edit_in_one_run() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#block1 #commitmentsopen").await.unwrap().tweet;
+        api.seed_reply(&root.id, "alice", &commitment_text("alice", 'a'))
+            .unwrap();
+        api.seed_reply(&root.id, "alice", &commitment_text("alice", 'c'))
+            .unwrap();
+        let collector = CommitmentCollector::new(api, temp_dir.path());
+
+        let collection = collector.collect("1", &root.id, None).await.unwrap();
+        assert_eq!(collection.results.commitments.len(), 1);
+        assert!(collection.results.commitments[0].commitment_hash.starts_with('c'));
+    }
+
+    #[tokio::test]
+    async fn test_commitment_collector_keeps_blocks_apart() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let api = InMemoryTwitterApi::new();
+        let block1 = api.post_tweet("#block1 #commitmentsopen").await.unwrap().tweet;
+        let block2 = api.post_tweet("#block2 #commitmentsopen").await.unwrap().tweet;
+        api.seed_reply(&block1.id, "alice", &commitment_text("alice", 'a'))
+            .unwrap();
+        api.seed_reply(&block2.id, "bob", &commitment_text("bob", 'b'))
+            .unwrap();
+        let collector = CommitmentCollector::new(api, temp_dir.path());
+
+        collector.collect("1", &block1.id, None).await.unwrap();
+        let second = collector.collect("2", &block2.id, None).await.unwrap();
+
+        // Block 2's results hold only its own commitment, and block 1's file is untouched
+        let authors: Vec<&str> = second
+            .results
+            .commitments
+            .iter()
+            .map(|c| c.author_id.as_str())
+            .collect();
+        assert_eq!(authors, vec!["bob"]);
+        let block1_results: CollectedCommitmentsResults = serde_json::from_str(
+            &fs::read_to_string(collector.commitments_path("1").unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(block1_results.commitments.len(), 1);
+        assert_eq!(block1_results.commitments[0].author_id, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_commitment_collector_times_undated_replies_by_its_clock() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#block1 #commitmentsopen").await.unwrap().tweet;
+        let mut undated = api
+            .seed_reply(&root.id, "alice", &commitment_text("alice", 'a'))
+            .unwrap();
+        undated.id = "2000".to_string();
+        undated.created_at = None;
+        api.seed(undated);
+
+        let deadline = chrono::Utc::now();
+        let block = BlockData::with_deadlines(
+            "1".to_string(),
+            "target.jpg".to_string(),
+            "123".to_string(),
+            100.0,
+            deadline,
+            deadline + Duration::hours(1),
+        );
+        let clock = Arc::new(crate::clock::MockClock::new(deadline + Duration::hours(2)));
+        let collector = CommitmentCollector::new(api, temp_dir.path()).with_clock(clock.clone());
+
+        // The dated reply is on time; the undated copy is seen after the deadline
+        let collection = collector.collect("1", &root.id, Some(&block)).await.unwrap();
+        assert_eq!(collection.replies_seen, 2);
+        assert_eq!(collection.rejected_late, 1);
+        assert_eq!(collection.results.commitments.len(), 1);
+        assert_eq!(
+            collection.results.collection_timestamp,
+            clock.now().to_rfc3339()
+        );
+    }
+}
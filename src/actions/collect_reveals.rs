@@ -1,10 +1,17 @@
+use chrono::Duration;
 use clap::Parser;
 use colored::Colorize;
 use std::fs;
+use std::sync::Arc;
 use std::path::PathBuf;
+use crate::actions::common::{load_block, reply_timing};
+use crate::actions::reply_store::{block_results_path, validator_data_dir, ReplyStore, REVEALS_DIR};
+use crate::clock::{Clock, SystemClock};
 use crate::config::ConfigManager;
-use crate::error::Result;
-use twitter_api::{TwitterApi, TwitterClient, TwitterError};
+use crate::error::{CliptionsError, Result};
+use crate::social::{build_reply_tree, ReplyTree};
+use crate::types::{BlockData, SubmissionPhase, Guess, SubmissionTiming, DEFAULT_DEADLINE_GRACE_SECS};
+use twitter_api::{SearchScope, TwitterApi, TwitterClient, TwitterError};
 
 #[derive(Parser)]
 pub struct CollectRevealsArgs {
@@ -16,11 +23,17 @@ pub struct CollectRevealsArgs {
     #[arg(short, long, default_value = "100")]
     pub max_results: u32,
 
+    /// Search the full archive instead of the last 7 days (requires elevated API access)
+    #[arg(long)]
+    pub full_archive: bool,
+
     /// Output format: text, json, csv
     #[arg(long, short, default_value = "text", value_parser = ["text", "json", "csv"])]
     pub output: String,
 
-    /// Save collected reveals to file (JSON format, defaults to ~/.cliptions/validator/collected_reveals.json)
+    /// Save collected reveals to file (JSON format, defaults to
+    /// ~/.cliptions/validator/collected_reveals/<block>.json with --block-num,
+    /// else ~/.cliptions/validator/collected_reveals.json)
     #[arg(long)]
     pub save_to: Option<PathBuf>,
 
@@ -47,6 +60,85 @@ pub struct CollectRevealsArgs {
     /// Config file path (default: config/config.yaml)
     #[arg(long, default_value = "config/config.yaml")]
     pub config: String,
+
+    /// Block ID in the blocks file; reveals posted outside its window are rejected
+    #[arg(long)]
+    pub block_num: Option<String>,
+
+    /// Path to blocks.json file (default: data/blocks.json)
+    #[arg(long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Seconds after the reveal deadline that reveals are still accepted
+    #[arg(
+        long,
+        default_value_t = DEFAULT_DEADLINE_GRACE_SECS,
+        value_parser = clap::value_parser!(i64).range(0..)
+    )]
+    pub deadline_grace_secs: i64,
+
+    /// Only fetch replies newer than the last run for this block, merging them into
+    /// ~/.cliptions/validator/collected_reveals/<block>.json
+    #[arg(long, requires = "block_num", conflicts_with_all = ["save_to", "no_save", "raw"])]
+    pub incremental: bool,
+
+    /// Also accept the older `Prediction:` and `Nonce:` reveal labels
+    #[arg(long)]
+    pub legacy_labels: bool,
+}
+
+/// Label aliases accepted when parsing a reveal reply
+///
+/// Aliases are tried in order and the first one found in the text wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevealFormat {
+    pub guess_labels: Vec<String>,
+    pub salt_labels: Vec<String>,
+}
+
+impl Default for RevealFormat {
+    /// The current `Guess:` / `Salt:` labels
+    fn default() -> Self {
+        Self {
+            guess_labels: vec!["Guess".to_string()],
+            salt_labels: vec!["Salt".to_string()],
+        }
+    }
+}
+
+impl RevealFormat {
+    /// The current labels plus those used by earlier blocks
+    pub fn with_legacy_labels() -> Self {
+        Self {
+            guess_labels: vec!["Guess".to_string(), "Prediction".to_string()],
+            salt_labels: vec!["Salt".to_string(), "Nonce".to_string()],
+        }
+    }
+
+    /// Extract the guess and salt from reveal text
+    pub fn parse(&self, text: &str) -> Result<(String, String)> {
+        let guess = Self::find_value(&self.guess_labels, r"(.+)", text)
+            .ok_or_else(|| Self::missing("guess", &self.guess_labels))?;
+        let salt = Self::find_value(&self.salt_labels, r"(\S+)", text)
+            .ok_or_else(|| Self::missing("salt", &self.salt_labels))?;
+        Ok((guess, salt))
+    }
+
+    fn find_value(labels: &[String], value_pattern: &str, text: &str) -> Option<String> {
+        labels.iter().find_map(|label| {
+            let pattern = format!(r"{}:\s*{}", regex::escape(label), value_pattern);
+            let captures = regex::Regex::new(&pattern).ok()?.captures(text)?;
+            Some(captures.get(1)?.as_str().trim().to_string())
+        })
+    }
+
+    fn missing(field: &str, labels: &[String]) -> CliptionsError {
+        CliptionsError::ValidationError(format!(
+            "No {} found; expected one of: {}",
+            field,
+            labels.iter().map(|l| format!("{}:", l)).collect::<Vec<_>>().join(", ")
+        ))
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -58,6 +150,9 @@ pub struct CollectedRevealData {
     pub timestamp: String,
     pub author_id: String,
     pub conversation_id: Option<String>,
+    /// Posted after the reveal deadline, inside the grace period
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub within_grace: bool,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -89,18 +184,46 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
         println!("✅ Loaded config from: {}", &args.config);
     }
 
+    // Load the block whose deadlines gate collection, if one was given
+    let block = match &args.block_num {
+        Some(block_num) => Some(load_block(&args.blocks_file, block_num)?),
+        None => None,
+    };
+
     // Create TwitterClient
     let twitter_config = twitter_api::TwitterConfig {
         api_key: twitter.api_key.clone(),
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(twitter_config);
 
     // Search for replies
+    let scope = if args.full_archive {
+        SearchScope::FullArchive
+    } else {
+        SearchScope::Recent
+    };
+    let format = if args.legacy_labels {
+        RevealFormat::with_legacy_labels()
+    } else {
+        RevealFormat::default()
+    };
+
+    if args.incremental {
+        let block_num = args.block_num.as_deref().unwrap_or_default();
+        let collector = RevealCollector::new(client, validator_data_dir()?)
+            .with_max_results(args.max_results)
+            .with_scope(scope)
+            .with_format(format)
+            .with_deadline_grace(Duration::seconds(args.deadline_grace_secs));
+        return run_incremental(&args, &collector, block_num, block.as_ref()).await;
+    }
+
     let result = client
-        .search_replies(&args.tweet_id, args.max_results)
+        .search_replies(&args.tweet_id, args.max_results, scope, None)
         .await;
 
     match result {
@@ -134,7 +257,14 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
             }
 
             // Parse and collect reveal data
+            let validator_id = validator_account_id(&client).await?;
+            let tree = build_reply_tree(&replies);
             let mut collected_reveals = Vec::new();
+            let grace = Duration::seconds(args.deadline_grace_secs);
+            let mut rejected_late = 0;
+            let mut within_grace = 0;
+            let mut own_replies = 0;
+            let mut nested = 0;
             
             for (i, reply) in replies.iter().enumerate() {
                 if args.verbose {
@@ -161,12 +291,67 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
                     }
                 }
 
+                if is_own_reply(reply, &args.tweet_id, &validator_id) {
+                    own_replies += 1;
+                    if args.verbose {
+                        println!("🤖 Skipping validator tweet: {}", reply.url);
+                    }
+                    continue;
+                }
+                if !is_direct_reply(&tree, reply, &args.tweet_id) {
+                    nested += 1;
+                    if args.verbose {
+                        println!("↪️  Skipping nested reply: {}", reply.url);
+                    }
+                    continue;
+                }
+
                 // Try to parse reveal data from reply text
-                if let Some(reveal_data) = parse_reveal_from_reply(reply) {
+                if let Some(mut reveal_data) = parse_reveal_from_reply(reply, &format, &SystemClock) {
+                    let timing = reply_timing(
+                        SubmissionPhase::Reveal,
+                        block.as_ref(),
+                        reply,
+                        grace,
+                        &SystemClock,
+                    );
+                    if !timing.is_accepted() {
+                        rejected_late += 1;
+                        if !args.quiet {
+                            println!("⏰ Rejected reveal outside the block window: {}", reply.url);
+                        }
+                        continue;
+                    }
+                    if let Err(e) = Guess::new_checked(reveal_data.guess.clone()) {
+                        if !args.quiet {
+                            println!("🚫 Rejected invalid guess in {}: {}", reply.url, e);
+                        }
+                        continue;
+                    }
+                    if timing == SubmissionTiming::WithinGrace {
+                        reveal_data.within_grace = true;
+                        within_grace += 1;
+                    }
                     collected_reveals.push(reveal_data);
                 }
             }
 
+            if rejected_late > 0 && !args.quiet {
+                println!("⏰ Rejected {} late reveals", rejected_late);
+            }
+            if within_grace > 0 && !args.quiet {
+                println!(
+                    "⏳ Accepted {} reveals within the {}s grace period",
+                    within_grace, args.deadline_grace_secs
+                );
+            }
+            if own_replies > 0 && !args.quiet {
+                println!("🤖 Skipped {} of the validator's own tweets", own_replies);
+            }
+            if nested > 0 && !args.quiet {
+                println!("↪️  Skipped {} replies to other replies", nested);
+            }
+
             // Create results structure
             let results = CollectedRevealsResults {
                 reveals: collected_reveals.clone(),
@@ -180,25 +365,17 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
 
             // Save to file (default behavior unless --no-save is specified)
             if !args.no_save {
-                let save_path = if let Some(custom_path) = &args.save_to {
-                    custom_path.clone()
-                } else {
-                    // Default to ~/.cliptions/validator/collected_reveals.json
-                    let home_dir = dirs::home_dir()
-                        .ok_or_else(|| "Could not determine home directory".to_string())?;
-                    let cliptions_dir = home_dir.join(".cliptions");
-                    let validator_dir = cliptions_dir.join("validator");
-                    
-                    // Create directories if they don't exist
-                    if !cliptions_dir.exists() {
-                        fs::create_dir_all(&cliptions_dir)?;
+                let save_path = match (&args.save_to, &args.block_num) {
+                    (Some(custom_path), _) => custom_path.clone(),
+                    // One file per block, so blocks never mix
+                    (None, Some(block_num)) => {
+                        block_results_path(&validator_data_dir()?.join(REVEALS_DIR), block_num)?
                     }
-                    if !validator_dir.exists() {
-                        fs::create_dir_all(&validator_dir)?;
-                    }
-                    
-                    validator_dir.join("collected_reveals.json")
+                    (None, None) => validator_data_dir()?.join("collected_reveals.json"),
                 };
+                if let Some(dir) = save_path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
 
                 save_results(&results, &save_path)?;
 
@@ -233,6 +410,19 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
             }
             return Err(error_msg.into());
         }
+        Err(TwitterError::ApiStructured { status, title, detail, .. }) => {
+            let message = format!("{}: {}", title, detail);
+            let error_msg = format!("Twitter API error: {} - {}", status, message);
+            if args.quiet {
+                eprintln!("{}", error_msg);
+            } else {
+                println!("❌ {}", error_msg);
+                if status == 404 {
+                    println!("💡 Make sure the tweet ID exists and is correct");
+                }
+            }
+            return Err(error_msg.into());
+        }
         Err(TwitterError::NetworkError(e)) => {
             let error_msg = format!("Network error: {}", e);
             if args.quiet {
@@ -278,6 +468,24 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
             }
             return Err(error_msg.into());
         }
+        Err(TwitterError::DuplicateContent(e)) => {
+            let error_msg = format!("Duplicate content: {}", e);
+            if args.quiet {
+                eprintln!("{}", error_msg);
+            } else {
+                println!("❌ {}", error_msg);
+            }
+            return Err(error_msg.into());
+        }
+        Err(TwitterError::Unsupported(e)) => {
+            let error_msg = format!("Not supported: {}", e);
+            if args.quiet {
+                eprintln!("{}", error_msg);
+            } else {
+                println!("❌ {}", error_msg);
+            }
+            return Err(error_msg.into());
+        }
         Err(TwitterError::FileError(e)) => {
             let error_msg = format!("File error: {}", e);
             if args.quiet {
@@ -310,21 +518,285 @@ pub async fn run(args: CollectRevealsArgs) -> Result<()> {
     Ok(())
 }
 
-fn parse_reveal_from_reply(reply: &twitter_api::Tweet) -> Option<CollectedRevealData> {
-    let text = &reply.text;
-    
+async fn run_incremental<C: TwitterApi>(
+    args: &CollectRevealsArgs,
+    collector: &RevealCollector<C>,
+    block_num: &str,
+    block: Option<&BlockData>,
+) -> Result<()> {
+    if args.verbose {
+        match collector.cursor(block_num)? {
+            Some(since_id) => println!("Resuming block {} from tweet {}", block_num, since_id),
+            None => println!("No saved cursor for block {}, scanning all replies", block_num),
+        }
+    }
+
+    let collection = collector.collect(block_num, &args.tweet_id, block).await?;
+
+    if !args.quiet {
+        match &collection.search_error {
+            Some(e) => println!("⚠️  Search stopped early, will resume next run: {}", e),
+            None => println!("✅ Search complete!"),
+        }
+        println!("New replies found: {}", collection.replies_seen);
+        if collection.rejected_late > 0 {
+            println!("⏰ Rejected {} late reveals", collection.rejected_late);
+        }
+        if collection.within_grace > 0 {
+            println!(
+                "⏳ Accepted {} reveals within the {}s grace period",
+                collection.within_grace, args.deadline_grace_secs
+            );
+        }
+        for reason in &collection.rejected_invalid {
+            println!("🚫 Rejected invalid guess in {}", reason);
+        }
+        if collection.own_replies > 0 {
+            println!("🤖 Skipped {} of the validator's own tweets", collection.own_replies);
+        }
+        if collection.nested > 0 {
+            println!("↪️  Skipped {} replies to other replies", collection.nested);
+        }
+        println!("New reveals collected: {}", collection.new_reveals);
+    }
+
+    display_results(&collection.results, args)?;
+
+    if !args.quiet {
+        println!(
+            "{} Collected reveals saved to {}",
+            "Success:".green().bold(),
+            collector.reveals_path(block_num)?.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Outcome of one incremental collection run
+pub struct RevealCollection {
+    /// Replies returned by the search, newer than the saved cursor
+    pub replies_seen: usize,
+    /// Set when the search failed partway, leaving the cursor where it was
+    pub search_error: Option<String>,
+    /// Reveals added to the stored file by this run
+    pub new_reveals: usize,
+    /// Reveals dropped for falling outside the block's reveal window
+    pub rejected_late: usize,
+    /// Reveals accepted after the deadline, inside the grace period
+    pub within_grace: usize,
+    /// Reveals dropped because their guess failed validation, with the reason for each
+    pub rejected_invalid: Vec<String>,
+    /// Replies from the validator's own account, including the reveal-phase tweet
+    pub own_replies: usize,
+    /// Replies to other replies rather than to the reveal-phase tweet
+    pub nested: usize,
+    /// All stored reveals after merging
+    pub results: CollectedRevealsResults,
+}
+
+/// Collects reveals incrementally, resuming from the newest reply seen on the last run
+///
+/// The newest-seen tweet ID for each block is kept in `reveal_cursors.json` and
+/// passed as `since_id` on the next search; new reveals are merged into the
+/// block's own `collected_reveals/<block>.json`. Both live in the collector's
+/// data directory.
+pub struct RevealCollector<C: TwitterApi> {
+    client: C,
+    store: ReplyStore,
+    max_results: u32,
+    scope: SearchScope,
+    format: RevealFormat,
+    deadline_grace: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl<C: TwitterApi> RevealCollector<C> {
+    /// Create a collector storing its files in `data_dir`
+    pub fn new(client: C, data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client,
+            store: ReplyStore::new(data_dir.into(), "reveal_cursors.json", REVEALS_DIR),
+            max_results: 100,
+            scope: SearchScope::Recent,
+            format: RevealFormat::default(),
+            deadline_grace: Duration::seconds(DEFAULT_DEADLINE_GRACE_SECS),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Set the maximum results per page
+    pub fn with_max_results(mut self, max_results: u32) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Set the search scope
+    pub fn with_scope(mut self, scope: SearchScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Set the labels accepted when parsing reveals
+    pub fn with_format(mut self, format: RevealFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Accept reveals up to `deadline_grace` after the reveal deadline
+    pub fn with_deadline_grace(mut self, deadline_grace: Duration) -> Self {
+        self.deadline_grace = deadline_grace;
+        self
+    }
+
+    /// Use `clock` to time replies without a timestamp and to stamp collections
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// The client replies are searched with
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Path of the per-block cursor file
+    pub fn cursors_path(&self) -> PathBuf {
+        self.store.cursors_path()
+    }
+
+    /// Path of the merged reveals file for a block
+    pub fn reveals_path(&self, block_num: &str) -> Result<PathBuf> {
+        self.store.results_path(block_num)
+    }
+
+    /// Newest tweet ID seen for a block, if it has been collected before
+    pub fn cursor(&self, block_num: &str) -> Result<Option<String>> {
+        self.store.cursor(block_num)
+    }
+
+    /// Fetch replies newer than the block's cursor and merge any reveals among them
+    ///
+    /// Running twice with no new replies leaves the stored reveals and cursor unchanged.
+    pub async fn collect(
+        &self,
+        block_num: &str,
+        tweet_id: &str,
+        block: Option<&BlockData>,
+    ) -> Result<RevealCollection> {
+        let search = self
+            .store
+            .fetch(&self.client, block_num, tweet_id, self.max_results, self.scope)
+            .await?;
+        let replies = search.tweets;
+        let validator_id = validator_account_id(&self.client).await?;
+
+        let tree = build_reply_tree(&replies);
+        let mut results = self
+            .store
+            .load_results(block_num)?
+            .unwrap_or_else(|| CollectedRevealsResults {
+                reveals: Vec::new(),
+                total_collected: 0,
+                original_tweet_id: tweet_id.to_string(),
+                collection_timestamp: self.clock.now().to_rfc3339(),
+            });
+        let mut new_reveals = 0;
+        let mut rejected_late = 0;
+        let mut within_grace = 0;
+        let mut rejected_invalid = Vec::new();
+        let mut own_replies = 0;
+        let mut nested = 0;
+        for reply in &replies {
+            if is_own_reply(reply, tweet_id, &validator_id) {
+                own_replies += 1;
+                continue;
+            }
+            if !is_direct_reply(&tree, reply, tweet_id) {
+                nested += 1;
+                continue;
+            }
+            let Some(mut reveal_data) = parse_reveal_from_reply(reply, &self.format, &self.clock)
+            else {
+                continue;
+            };
+            let timing = reply_timing(
+                SubmissionPhase::Reveal,
+                block,
+                reply,
+                self.deadline_grace,
+                &self.clock,
+            );
+            if !timing.is_accepted() {
+                rejected_late += 1;
+                continue;
+            }
+            if let Err(e) = Guess::new_checked(reveal_data.guess.clone()) {
+                rejected_invalid.push(format!("{}: {}", reveal_data.tweet_url, e));
+                continue;
+            }
+            if results.reveals.iter().any(|r| r.tweet_url == reveal_data.tweet_url) {
+                continue;
+            }
+            if timing == SubmissionTiming::WithinGrace {
+                reveal_data.within_grace = true;
+                within_grace += 1;
+            }
+            results.reveals.push(reveal_data);
+            new_reveals += 1;
+        }
+        results.total_collected = results.reveals.len();
+        results.collection_timestamp = self.clock.now().to_rfc3339();
+
+        self.store.save(block_num, &results, search.newest_id)?;
+
+        Ok(RevealCollection {
+            replies_seen: replies.len(),
+            search_error: search.error,
+            new_reveals,
+            rejected_late,
+            within_grace,
+            rejected_invalid,
+            own_replies,
+            nested,
+            results,
+        })
+    }
+}
+
+/// Id of the authenticated (validator) account, whose replies are never reveals
+async fn validator_account_id<C: TwitterApi>(client: &C) -> Result<String> {
+    let user = client
+        .get_authenticated_user()
+        .await
+        .map_err(|e| {
+            CliptionsError::ApiError(format!("Failed to look up the validator account: {}", e))
+        })?;
+    Ok(user.id)
+}
+
+/// Whether `reply` is the reveal-phase tweet itself or was posted by the validator
+fn is_own_reply(reply: &twitter_api::Tweet, tweet_id: &str, validator_id: &str) -> bool {
+    reply.id == tweet_id || reply.author_id == validator_id
+}
+
+/// Whether `reply` answers `tweet_id` itself rather than another reply in its thread
+///
+/// Replies whose parent can't be determined are kept.
+fn is_direct_reply(tree: &ReplyTree, reply: &twitter_api::Tweet, tweet_id: &str) -> bool {
+    tree.parent(&reply.id).is_none_or(|parent| parent == tweet_id)
+}
+
+/// Reveal in `reply`, stamped with `clock`'s time if the reply has no timestamp
+fn parse_reveal_from_reply(
+    reply: &twitter_api::Tweet,
+    format: &RevealFormat,
+    clock: &dyn Clock,
+) -> Option<CollectedRevealData> {
     // Look for patterns like:
     // "Guess: [text]"
     // "Salt: [salt]"
-    let guess_pattern = regex::Regex::new(r"Guess:\s*(.+)").ok()?;
-    let salt_pattern = regex::Regex::new(r"Salt:\s*([^\s\n]+)").ok()?;
-    
-    // Try to find guess and salt in the text
-    let guess_capture = guess_pattern.captures(text)?;
-    let salt_capture = salt_pattern.captures(text)?;
-    
-    let guess = guess_capture.get(1)?.as_str().trim().to_string();
-    let salt = salt_capture.get(1)?.as_str().trim().to_string();
+    let (guess, salt) = format.parse(&reply.text).ok()?;
 
     if guess == "[your-guess]" && salt == "[your-salt]" { // Ignore placeholder values from the validator tweet
         return None;
@@ -340,9 +812,10 @@ fn parse_reveal_from_reply(reply: &twitter_api::Tweet) -> Option<CollectedReveal
         tweet_url: reply.url.clone(),
         timestamp: reply.created_at
             .map(|dt| dt.to_rfc3339())
-            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+            .unwrap_or_else(|| clock.now().to_rfc3339()),
         author_id: reply.author_id.clone(),
         conversation_id: reply.conversation_id.clone(),
+        within_grace: false,
     })
 }
 
@@ -385,6 +858,9 @@ fn display_text_format(
             println!("{}: {}", "Salt".blue().bold(), data.salt);
             println!("{}: {}", "Tweet URL".blue().bold(), data.tweet_url);
             println!("{}: {}", "Timestamp".blue().bold(), data.timestamp);
+            if data.within_grace {
+                println!("{}", "Posted within the deadline grace period".yellow());
+            }
         } else {
             // Simple format
             println!("Guess: {}", data.guess);
@@ -411,6 +887,9 @@ fn display_text_format(
             println!("  Salt: {}", data.salt);
             println!("  URL: {}", data.tweet_url);
             println!("  Time: {}", data.timestamp);
+            if data.within_grace {
+                println!("  {}", "Posted within the deadline grace period".yellow());
+            }
             println!();
         }
     }
@@ -425,32 +904,35 @@ fn display_json_format(results: &CollectedRevealsResults) -> Result<()> {
 }
 
 fn display_csv_format(results: &CollectedRevealsResults) -> Result<()> {
-    println!("username,guess,salt,tweet_url,timestamp,author_id,conversation_id");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record([
+        "username",
+        "guess",
+        "salt",
+        "tweet_url",
+        "timestamp",
+        "author_id",
+        "conversation_id",
+        "within_grace",
+    ])?;
 
     for data in &results.reveals {
-        println!(
-            "{},{},{},{},{},{},{}",
-            csv_escape(&data.username),
-            csv_escape(&data.guess),
-            csv_escape(&data.salt),
-            csv_escape(&data.tweet_url),
-            csv_escape(&data.timestamp),
-            csv_escape(&data.author_id),
-            data.conversation_id.as_deref().map_or("".to_string(), |s| csv_escape(s))
-        );
+        writer.write_record([
+            data.username.as_str(),
+            &data.guess,
+            &data.salt,
+            &data.tweet_url,
+            &data.timestamp,
+            &data.author_id,
+            data.conversation_id.as_deref().unwrap_or(""),
+            &data.within_grace.to_string(),
+        ])?;
     }
 
+    writer.flush()?;
     Ok(())
 }
 
-fn csv_escape(field: &str) -> String {
-    if field.contains(',') || field.contains('"') || field.contains('\n') {
-        format!("\"{}\"", field.replace('"', "\"\""))
-    } else {
-        field.to_string()
-    }
-}
-
 fn save_results(
     results: &CollectedRevealsResults,
     save_path: &PathBuf,
@@ -485,12 +967,15 @@ fn save_results(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MAX_GUESS_LENGTH;
+    use twitter_api::testing::InMemoryTwitterApi;
 
     #[test]
     fn test_collect_reveals_args_parsing() {
         let args = CollectRevealsArgs {
             tweet_id: "123456789".to_string(),
             max_results: 50,
+            full_archive: false,
             output: "json".to_string(),
             save_to: Some(PathBuf::from("test.json")),
             no_save: false,
@@ -499,6 +984,11 @@ mod tests {
             quiet: false,
             raw: false,
             config: "test_config.yaml".to_string(),
+            block_num: None,
+            blocks_file: PathBuf::from("data/blocks.json"),
+            deadline_grace_secs: DEFAULT_DEADLINE_GRACE_SECS,
+            incremental: false,
+            legacy_labels: false,
         };
 
         assert_eq!(args.tweet_id, "123456789");
@@ -515,6 +1005,7 @@ mod tests {
         let args = CollectRevealsArgs {
             tweet_id: "123456789".to_string(),
             max_results: 100,
+            full_archive: false,
             output: "text".to_string(),
             save_to: None,
             no_save: false,
@@ -523,6 +1014,11 @@ mod tests {
             quiet: false,
             raw: false,
             config: "config/config.yaml".to_string(),
+            block_num: None,
+            blocks_file: PathBuf::from("data/blocks.json"),
+            deadline_grace_secs: DEFAULT_DEADLINE_GRACE_SECS,
+            incremental: false,
+            legacy_labels: false,
         };
 
         assert_eq!(args.max_results, 100);
@@ -545,9 +1041,11 @@ mod tests {
             created_at: Some(chrono::Utc::now()),
             conversation_id: Some("123456789".to_string()),
             public_metrics: None,
+            in_reply_to_user_id: None,
+            referenced_tweets: Vec::new(),
         };
 
-        let result = parse_reveal_from_reply(&reply);
+        let result = parse_reveal_from_reply(&reply, &RevealFormat::default(), &SystemClock);
         assert!(result.is_some());
         
         let reveal_data = result.unwrap();
@@ -557,10 +1055,218 @@ mod tests {
     }
 
     #[test]
-    fn test_csv_escape() {
-        assert_eq!(csv_escape("simple"), "simple");
-        assert_eq!(csv_escape("with,comma"), "\"with,comma\"");
-        assert_eq!(csv_escape("with\"quote"), "\"with\"\"quote\"");
-        assert_eq!(csv_escape("with\nline"), "\"with\nline\"");
+    fn test_reveal_format_accepts_legacy_labels() {
+        let format = RevealFormat::with_legacy_labels();
+        for (text, guess, salt) in [
+            ("Guess: a red fox\nSalt: s1", "a red fox", "s1"),
+            ("Prediction: a red fox\nSalt: s2", "a red fox", "s2"),
+            ("Guess: a blue whale\nNonce: s3", "a blue whale", "s3"),
+            ("Prediction: a blue whale\nNonce: s4", "a blue whale", "s4"),
+        ] {
+            assert_eq!(
+                format.parse(text).unwrap(),
+                (guess.to_string(), salt.to_string()),
+                "{}",
+                text
+            );
+        }
+
+        // The first alias present wins
+        let (guess, _) = format.parse("Prediction: old\nGuess: new\nSalt: s").unwrap();
+        assert_eq!(guess, "new");
+    }
+
+    #[test]
+    fn test_reveal_format_rejects_unknown_labels() {
+        // Legacy labels are only accepted when asked for
+        let err = RevealFormat::default()
+            .parse("Prediction: a red fox\nSalt: s1")
+            .unwrap_err();
+        assert!(err.to_string().contains("expected one of: Guess:"));
+
+        let format = RevealFormat::with_legacy_labels();
+        assert!(format.parse("Answer: a red fox\nSalt: s1").is_err());
+        let err = format.parse("Guess: a red fox\nPepper: s1").unwrap_err();
+        assert!(err.to_string().contains("No salt found; expected one of: Salt:, Nonce:"));
+    }
+
+    fn reveal_text(author_id: &str, guess: &str) -> String {
+        format!("Guess: {}\nSalt: salt_{}", guess, author_id)
     }
-} 
\ No newline at end of file
+
+    /// An empty timeline and the reveal-phase tweet the replies answer
+    async fn reveal_thread() -> (InMemoryTwitterApi, String) {
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#revealsopen").await.unwrap().tweet;
+        (api, root.id)
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_resumes_from_cursor() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (api, root) = reveal_thread().await;
+        api.seed_reply(&root, "41", &reveal_text("41", "a red fox")).unwrap();
+        let second = api.seed_reply(&root, "42", &reveal_text("42", "a blue whale")).unwrap();
+        let collector = RevealCollector::new(api, temp_dir.path().join("validator"));
+
+        // First run: no cursor, everything is collected
+        assert_eq!(collector.cursor("1").unwrap(), None);
+        let first = collector.collect("1", &root, None).await.unwrap();
+        assert_eq!(first.new_reveals, 2);
+        assert_eq!(collector.cursor("1").unwrap(), Some(second.id));
+
+        // Second run only sees the reply posted since
+        let third = collector
+            .client()
+            .seed_reply(&root, "43", &reveal_text("43", "a green frog"))
+            .unwrap();
+        let second = collector.collect("1", &root, None).await.unwrap();
+        assert_eq!(second.replies_seen, 1);
+        assert_eq!(second.new_reveals, 1);
+        assert_eq!(collector.cursor("1").unwrap(), Some(third.id));
+
+        let stored: CollectedRevealsResults = serde_json::from_str(
+            &fs::read_to_string(collector.reveals_path("1").unwrap()).unwrap(),
+        )
+        .unwrap();
+        let guesses: Vec<&str> = stored.reveals.iter().map(|r| r.guess.as_str()).collect();
+        assert_eq!(guesses, vec!["a red fox", "a blue whale", "a green frog"]);
+        assert_eq!(stored.total_collected, 3);
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_idempotent() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (api, root) = reveal_thread().await;
+        let reply = api.seed_reply(&root, "41", &reveal_text("41", "a red fox")).unwrap();
+        let collector = RevealCollector::new(api, temp_dir.path());
+
+        collector.collect("1", &root, None).await.unwrap();
+        let again = collector.collect("1", &root, None).await.unwrap();
+
+        assert_eq!(again.replies_seen, 0);
+        assert_eq!(again.new_reveals, 0);
+        assert_eq!(again.results.reveals.len(), 1);
+        assert_eq!(collector.cursor("1").unwrap(), Some(reply.id));
+
+        // Cursors are kept per block
+        assert_eq!(collector.cursor("2").unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_keeps_blocks_apart() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let api = InMemoryTwitterApi::new();
+        let first_root = api.post_tweet("#block1 #revealsopen").await.unwrap().tweet.id;
+        let second_root = api.post_tweet("#block2 #revealsopen").await.unwrap().tweet.id;
+        api.seed_reply(&first_root, "41", &reveal_text("41", "a red fox")).unwrap();
+        api.seed_reply(&second_root, "41", &reveal_text("41", "a blue whale")).unwrap();
+        let collector = RevealCollector::new(api, temp_dir.path());
+
+        collector.collect("1", &first_root, None).await.unwrap();
+        let second = collector.collect("2", &second_root, None).await.unwrap();
+        assert_eq!(second.results.reveals.len(), 1);
+        assert_eq!(second.results.reveals[0].guess, "a blue whale");
+
+        // Each block's file only holds its own reveals
+        let first: CollectedRevealsResults = serde_json::from_str(
+            &fs::read_to_string(collector.reveals_path("1").unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(first.reveals.len(), 1);
+        assert_eq!(first.reveals[0].guess, "a red fox");
+        assert_eq!(
+            collector.reveals_path("2").unwrap(),
+            temp_dir.path().join(REVEALS_DIR).join("2.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_drops_invalid_guesses() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let long_guess = "x".repeat(MAX_GUESS_LENGTH + 1);
+        let (api, root) = reveal_thread().await;
+        api.seed_reply(&root, "41", &reveal_text("41", "a red fox")).unwrap();
+        let too_long = api.seed_reply(&root, "42", &reveal_text("42", &long_guess)).unwrap();
+        api.seed_reply(&root, "43", &reveal_text("43", "a \u{7}bell")).unwrap();
+        let collector = RevealCollector::new(api, temp_dir.path());
+
+        let collection = collector.collect("1", &root, None).await.unwrap();
+
+        assert_eq!(collection.new_reveals, 1);
+        assert_eq!(collection.results.reveals[0].guess, "a red fox");
+        assert_eq!(collection.rejected_invalid.len(), 2);
+        assert!(collection.rejected_invalid[0].contains(&too_long.url));
+        assert!(collection.rejected_invalid[0].contains("at most 300 characters"));
+        assert!(collection.rejected_invalid[1].contains("control character"));
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_keeps_partial_search() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (api, root) = reveal_thread().await;
+        api.seed_reply(&root, "41", &reveal_text("41", "a red fox")).unwrap();
+        let newest = api.seed_reply(&root, "42", &reveal_text("42", "a blue whale")).unwrap();
+        api.interrupt_next_search(1, "page 2 failed");
+        let collector = RevealCollector::new(api, temp_dir.path());
+
+        // What was fetched is saved, but the cursor doesn't move past the gap
+        let partial = collector.collect("1", &root, None).await.unwrap();
+        assert_eq!(partial.search_error.as_deref(), Some("page 2 failed"));
+        assert_eq!(partial.new_reveals, 1);
+        assert_eq!(collector.cursor("1").unwrap(), None);
+
+        // The next run searches from the start again without duplicating what was saved
+        let resumed = collector.collect("1", &root, None).await.unwrap();
+        assert_eq!(resumed.search_error, None);
+        assert_eq!(resumed.replies_seen, 2);
+        assert_eq!(resumed.new_reveals, 1);
+        assert_eq!(resumed.results.reveals.len(), 2);
+        assert_eq!(collector.cursor("1").unwrap(), Some(newest.id));
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_counts_only_direct_replies() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (api, root) = reveal_thread().await;
+        let first = api.seed_reply(&root, "41", &reveal_text("41", "a red fox")).unwrap();
+        // This one answers the first reveal instead of the reveal-phase tweet
+        api.seed_reply(&first.id, "42", &reveal_text("42", "a blue whale")).unwrap();
+        api.seed_reply(&root, "43", &reveal_text("43", "a green frog")).unwrap();
+        let collector = RevealCollector::new(api, temp_dir.path());
+
+        let collection = collector.collect("1", &root, None).await.unwrap();
+
+        assert_eq!(collection.replies_seen, 3);
+        assert_eq!(collection.nested, 1);
+        let guesses: Vec<&str> = collection
+            .results
+            .reveals
+            .iter()
+            .map(|r| r.guess.as_str())
+            .collect();
+        assert_eq!(guesses, vec!["a red fox", "a green frog"]);
+    }
+
+    #[tokio::test]
+    async fn test_reveal_collector_skips_validator_replies() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (api, root) = reveal_thread().await;
+        api.seed_reply(&root, "41", &reveal_text("41", "a red fox")).unwrap();
+        // The validator's follow-up is formatted like a reveal and must still be ignored
+        api.reply_to_tweet(&reveal_text("validator", "the answer"), &root)
+            .await
+            .unwrap();
+        let collector = RevealCollector::new(api, temp_dir.path());
+
+        let collection = collector.collect("1", &root, None).await.unwrap();
+
+        assert_eq!(collection.own_replies, 1);
+        assert_eq!(collection.new_reveals, 1);
+        assert_eq!(collection.results.reveals[0].guess, "a red fox");
+
+        // The reveal-phase tweet itself never counts, whoever posted it
+        let target = collector.client().tweet(&root).unwrap();
+        assert!(is_own_reply(&target, &root, "another_account"));
+    }
+}
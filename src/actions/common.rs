@@ -0,0 +1,174 @@
+//! Helpers shared by the validator actions
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::Duration;
+use twitter_api::Tweet;
+
+use crate::actions::archive_blocks;
+use crate::clock::Clock;
+use crate::error::Result;
+use crate::types::{BlockData, SubmissionPhase, SubmissionTiming};
+
+/// Load a single block from a blocks.json file keyed by block number
+///
+/// Blocks moved out by `archive-blocks` are looked up in the archive
+/// directory beside the blocks file.
+pub(crate) fn load_block(blocks_file: &Path, block_num: &str) -> Result<BlockData> {
+    let content = fs::read_to_string(blocks_file).map_err(|e| {
+        format!("Failed to read blocks file {}: {}", blocks_file.display(), e)
+    })?;
+    let mut blocks: BTreeMap<String, BlockData> = serde_json::from_str(&content)?;
+    if let Some(block) = blocks.remove(block_num) {
+        return Ok(block);
+    }
+
+    let archive_dir = archive_blocks::default_archive_dir(blocks_file);
+    archive_blocks::load_archived_block(&archive_dir, block_num)?
+        .ok_or_else(|| format!("Block {} not found in {}", block_num, blocks_file.display()).into())
+}
+
+/// Timing of a reply against the block's window for `phase`
+///
+/// A reply without a timestamp is timed at `clock`'s current time, when the
+/// collector saw it. Without a block there is nothing to check against, so
+/// the reply counts as on time.
+pub(crate) fn reply_timing(
+    phase: SubmissionPhase,
+    block: Option<&BlockData>,
+    reply: &Tweet,
+    grace: Duration,
+    clock: &dyn Clock,
+) -> SubmissionTiming {
+    match block {
+        Some(block) => {
+            let posted_at = reply.created_at.unwrap_or_else(|| clock.now());
+            block.submission_timing(phase, posted_at, grace)
+        }
+        None => SubmissionTiming::OnTime,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{MockClock, SystemClock};
+    use crate::types::DEFAULT_DEADLINE_GRACE_SECS;
+    use chrono::{DateTime, Utc};
+    use SubmissionPhase::{Commitment, Reveal};
+
+    fn block(commitment_deadline: DateTime<Utc>) -> BlockData {
+        BlockData::with_deadlines(
+            "1".to_string(),
+            "target.jpg".to_string(),
+            "123".to_string(),
+            100.0,
+            commitment_deadline,
+            commitment_deadline + Duration::hours(1),
+        )
+    }
+
+    fn reply_at(created_at: Option<DateTime<Utc>>) -> Tweet {
+        Tweet {
+            id: "1".to_string(),
+            text: String::new(),
+            author_id: "2".to_string(),
+            url: String::new(),
+            created_at,
+            conversation_id: None,
+            public_metrics: None,
+            in_reply_to_user_id: None,
+            referenced_tweets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_commitment_timing() {
+        let deadline = Utc::now();
+        let block = block(deadline);
+
+        let grace = Duration::seconds(DEFAULT_DEADLINE_GRACE_SECS);
+        let before = reply_at(Some(deadline - Duration::minutes(5)));
+        let after = reply_at(Some(deadline + Duration::minutes(5)));
+        let timing = |block: Option<&BlockData>, reply: &Tweet| {
+            reply_timing(Commitment, block, reply, grace, &SystemClock)
+        };
+        assert_eq!(timing(Some(&block), &before), SubmissionTiming::OnTime);
+        assert_eq!(timing(Some(&block), &after), SubmissionTiming::Outside);
+
+        // Without a block there is nothing to check against
+        assert_eq!(timing(None, &after), SubmissionTiming::OnTime);
+    }
+
+    #[test]
+    fn test_reply_without_timestamp_is_timed_by_the_clock() {
+        let deadline = Utc::now();
+        let block = block(deadline);
+        let grace = Duration::seconds(60);
+        let undated = reply_at(None);
+        let clock = MockClock::new(deadline - Duration::minutes(5));
+        let timing = |phase| reply_timing(phase, Some(&block), &undated, grace, &clock);
+
+        assert_eq!(timing(Commitment), SubmissionTiming::OnTime);
+        assert_eq!(timing(Reveal), SubmissionTiming::Outside);
+
+        clock.advance(Duration::minutes(5) + Duration::seconds(30));
+        assert_eq!(timing(Commitment), SubmissionTiming::WithinGrace);
+
+        clock.advance(Duration::minutes(1));
+        assert_eq!(timing(Commitment), SubmissionTiming::Outside);
+        assert_eq!(timing(Reveal), SubmissionTiming::OnTime);
+    }
+
+    #[test]
+    fn test_commitment_deadline_grace_window() {
+        let deadline = Utc::now();
+        let block = block(deadline);
+        let grace = Duration::seconds(60);
+        let at = |offset| {
+            let reply = reply_at(Some(deadline + offset));
+            reply_timing(Commitment, Some(&block), &reply, grace, &SystemClock)
+        };
+
+        assert_eq!(at(Duration::zero()), SubmissionTiming::OnTime);
+        assert_eq!(at(Duration::milliseconds(1)), SubmissionTiming::WithinGrace);
+        assert_eq!(at(Duration::seconds(60)), SubmissionTiming::WithinGrace);
+        assert_eq!(at(Duration::seconds(61)), SubmissionTiming::Outside);
+
+        // A zero grace period is the strict deadline
+        let late = reply_at(Some(deadline + Duration::seconds(1)));
+        let strict = reply_timing(Commitment, Some(&block), &late, Duration::zero(), &SystemClock);
+        assert_eq!(strict, SubmissionTiming::Outside);
+    }
+
+    #[test]
+    fn test_reveal_timing() {
+        let deadline = Utc::now();
+        let block = block(deadline);
+
+        let grace = Duration::seconds(DEFAULT_DEADLINE_GRACE_SECS);
+        let before = reply_at(Some(deadline - Duration::minutes(5)));
+        let after = reply_at(Some(deadline + Duration::minutes(5)));
+        let timing = |block: Option<&BlockData>, reply: &Tweet| {
+            reply_timing(Reveal, block, reply, grace, &SystemClock)
+        };
+        assert_eq!(timing(Some(&block), &before), SubmissionTiming::Outside);
+        assert_eq!(timing(Some(&block), &after), SubmissionTiming::OnTime);
+
+        // Without a block there is nothing to check against
+        assert_eq!(timing(None, &before), SubmissionTiming::OnTime);
+
+        // Around the reveal deadline, late reveals inside the grace period are kept
+        let reveal_deadline = deadline + Duration::hours(1);
+        let at = |offset| timing(Some(&block), &reply_at(Some(reveal_deadline + offset)));
+        assert_eq!(at(Duration::zero()), SubmissionTiming::OnTime);
+        assert_eq!(at(Duration::seconds(30)), SubmissionTiming::WithinGrace);
+        assert_eq!(at(grace), SubmissionTiming::WithinGrace);
+        assert_eq!(at(grace + Duration::seconds(1)), SubmissionTiming::Outside);
+        // Reveals before the commitment deadline get no grace
+        let early = reply_at(Some(deadline - Duration::seconds(1)));
+        assert_eq!(timing(Some(&block), &early), SubmissionTiming::Outside);
+    }
+}
@@ -0,0 +1,352 @@
+use clap::Parser;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::data_file;
+use crate::error::{CliptionsError, Result};
+use crate::payout::PAYOUT_TOLERANCE;
+use crate::types::{BlockData, BlockStatus, Participant};
+
+#[derive(Parser)]
+pub struct DiffBlocksArgs {
+    /// Earlier blocks.json file
+    pub old: PathBuf,
+
+    /// Later blocks.json file
+    pub new: PathBuf,
+
+    /// Output format: text, json
+    #[arg(long, short, default_value = "text", value_parser = ["text", "json"])]
+    pub output: String,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// Differences between two versions of blocks.json
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct BlocksDiff {
+    pub added_blocks: Vec<String>,
+    pub removed_blocks: Vec<String>,
+    /// Blocks present in both files that changed, ordered by block number
+    pub changed_blocks: Vec<BlockChanges>,
+}
+
+impl BlocksDiff {
+    /// Whether the two files hold the same blocks with no tracked changes
+    pub fn is_empty(&self) -> bool {
+        self.added_blocks.is_empty()
+            && self.removed_blocks.is_empty()
+            && self.changed_blocks.is_empty()
+    }
+}
+
+/// Changes to one block present in both files
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockChanges {
+    pub block_num: String,
+    pub old_participant_count: usize,
+    pub new_participant_count: usize,
+    pub changes: Vec<BlockChange>,
+}
+
+/// A single change to a block; participants are matched by social ID
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BlockChange {
+    Status { old: BlockStatus, new: BlockStatus },
+    PrizePool { old: f64, new: f64 },
+    TotalPayout { old: f64, new: f64 },
+    ParticipantAdded { social_id: String, username: String },
+    ParticipantRemoved { social_id: String, username: String },
+    Verified { social_id: String, old: bool, new: bool },
+    Payout { social_id: String, old: f64, new: f64 },
+}
+
+pub fn run(args: DiffBlocksArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    let diff = diff_blocks(&read_blocks(&args.old)?, &read_blocks(&args.new)?);
+    if args.output == "json" {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    if diff.is_empty() {
+        println!("No differences between {} and {}", args.old.display(), args.new.display());
+        return Ok(());
+    }
+    for block_num in &diff.added_blocks {
+        println!("{} block {}", "+".green().bold(), block_num);
+    }
+    for block_num in &diff.removed_blocks {
+        println!("{} block {}", "-".red().bold(), block_num);
+    }
+    for block in &diff.changed_blocks {
+        println!(
+            "{} block {} (participants {} -> {})",
+            "~".yellow().bold(),
+            block.block_num,
+            block.old_participant_count,
+            block.new_participant_count
+        );
+        for change in &block.changes {
+            println!("    {}", describe(change));
+        }
+    }
+
+    Ok(())
+}
+
+fn describe(change: &BlockChange) -> String {
+    match change {
+        BlockChange::Status { old, new } => format!("status: {:?} -> {:?}", old, new),
+        BlockChange::PrizePool { old, new } => format!("prize pool: {} -> {}", old, new),
+        BlockChange::TotalPayout { old, new } => {
+            format!("total payout: {} -> {} ({:+})", old, new, new - old)
+        }
+        BlockChange::ParticipantAdded { social_id, username } => {
+            format!("participant added: {} ({})", username, social_id)
+        }
+        BlockChange::ParticipantRemoved { social_id, username } => {
+            format!("participant removed: {} ({})", username, social_id)
+        }
+        BlockChange::Verified { social_id, old, new } => {
+            format!("{} verified: {} -> {}", social_id, old, new)
+        }
+        BlockChange::Payout { social_id, old, new } => {
+            format!("{} payout: {} -> {} ({:+})", social_id, old, new, new - old)
+        }
+    }
+}
+
+fn read_blocks(path: &Path) -> Result<BTreeMap<String, BlockData>> {
+    let content = data_file::read_to_string(path).map_err(|e| {
+        CliptionsError::ValidationError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    serde_json::from_str(&content).map_err(|e| {
+        CliptionsError::ValidationError(format!("Failed to parse {}: {}", path.display(), e))
+    })
+}
+
+/// Compare two sets of blocks keyed by block number
+pub fn diff_blocks(
+    old: &BTreeMap<String, BlockData>,
+    new: &BTreeMap<String, BlockData>,
+) -> BlocksDiff {
+    let mut diff = BlocksDiff {
+        added_blocks: new.keys().filter(|k| !old.contains_key(*k)).cloned().collect(),
+        removed_blocks: old.keys().filter(|k| !new.contains_key(*k)).cloned().collect(),
+        changed_blocks: Vec::new(),
+    };
+
+    for (block_num, old_block) in old {
+        let Some(new_block) = new.get(block_num) else {
+            continue;
+        };
+        let changes = diff_block(old_block, new_block);
+        if !changes.is_empty() {
+            diff.changed_blocks.push(BlockChanges {
+                block_num: block_num.clone(),
+                old_participant_count: old_block.participants.len(),
+                new_participant_count: new_block.participants.len(),
+                changes,
+            });
+        }
+    }
+
+    let numeric_order = |s: &str| (s.parse::<u64>().ok(), s.to_string());
+    diff.added_blocks.sort_by_key(|b| numeric_order(b));
+    diff.removed_blocks.sort_by_key(|b| numeric_order(b));
+    diff.changed_blocks.sort_by_key(|b| numeric_order(&b.block_num));
+    diff
+}
+
+fn diff_block(old: &BlockData, new: &BlockData) -> Vec<BlockChange> {
+    let differs = |a: f64, b: f64| (a - b).abs() > PAYOUT_TOLERANCE;
+    let mut changes = Vec::new();
+
+    if old.status != new.status {
+        changes.push(BlockChange::Status {
+            old: old.status.clone(),
+            new: new.status.clone(),
+        });
+    }
+    if differs(old.prize_pool, new.prize_pool) {
+        changes.push(BlockChange::PrizePool {
+            old: old.prize_pool,
+            new: new.prize_pool,
+        });
+    }
+    if differs(old.total_payout, new.total_payout) {
+        changes.push(BlockChange::TotalPayout {
+            old: old.total_payout,
+            new: new.total_payout,
+        });
+    }
+
+    let find = |participants: &[Participant], social_id: &str| {
+        participants.iter().find(|p| p.social_id == social_id).cloned()
+    };
+    for participant in &new.participants {
+        match find(&old.participants, &participant.social_id) {
+            None => changes.push(BlockChange::ParticipantAdded {
+                social_id: participant.social_id.clone(),
+                username: participant.username.clone(),
+            }),
+            Some(before) => {
+                if before.verified != participant.verified {
+                    changes.push(BlockChange::Verified {
+                        social_id: participant.social_id.clone(),
+                        old: before.verified,
+                        new: participant.verified,
+                    });
+                }
+                if differs(before.payout.amount, participant.payout.amount) {
+                    changes.push(BlockChange::Payout {
+                        social_id: participant.social_id.clone(),
+                        old: before.payout.amount,
+                        new: participant.payout.amount,
+                    });
+                }
+            }
+        }
+    }
+    for participant in &old.participants {
+        if find(&new.participants, &participant.social_id).is_none() {
+            changes.push(BlockChange::ParticipantRemoved {
+                social_id: participant.social_id.clone(),
+                username: participant.username.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Guess;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn participant(social_id: &str) -> Participant {
+        Participant::new(
+            social_id.to_string(),
+            format!("user_{}", social_id),
+            Guess::new("a red fox".to_string()),
+            format!("commitment_{}", social_id),
+        )
+    }
+
+    fn fixture() -> BTreeMap<String, BlockData> {
+        ["1", "2"]
+            .into_iter()
+            .map(|block_num| {
+                let mut block = BlockData::new(
+                    block_num.to_string(),
+                    "target.jpg".to_string(),
+                    format!("social_{}", block_num),
+                    100.0,
+                );
+                block.add_participant(participant("a"));
+                block.add_participant(participant("b"));
+                (block_num.to_string(), block)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_blocks_status_and_added_participant() {
+        let old = fixture();
+        let mut new = old.clone();
+        let block = new.get_mut("2").unwrap();
+        block.status = BlockStatus::Processing;
+        block.add_participant(participant("c"));
+
+        let diff = diff_blocks(&old, &new);
+
+        assert!(diff.added_blocks.is_empty());
+        assert!(diff.removed_blocks.is_empty());
+        assert_eq!(
+            diff.changed_blocks,
+            vec![BlockChanges {
+                block_num: "2".to_string(),
+                old_participant_count: 2,
+                new_participant_count: 3,
+                changes: vec![
+                    BlockChange::Status {
+                        old: BlockStatus::Open,
+                        new: BlockStatus::Processing,
+                    },
+                    BlockChange::ParticipantAdded {
+                        social_id: "c".to_string(),
+                        username: "user_c".to_string(),
+                    },
+                ],
+            }]
+        );
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["changed_blocks"][0]["changes"][0]["kind"], "status");
+        assert_eq!(json["changed_blocks"][0]["changes"][1]["kind"], "participant_added");
+    }
+
+    #[test]
+    fn test_diff_blocks_added_removed_and_payouts() {
+        let old = fixture();
+        let mut new = old.clone();
+        new.remove("1");
+        let mut block = new["2"].clone();
+        block.block_num = "10".to_string();
+        new.insert("10".to_string(), block);
+        let block = new.get_mut("2").unwrap();
+        block.participants.retain(|p| p.social_id != "a");
+        block.participants[0].payout.amount = 42.5;
+        block.participants[0].verified = true;
+        block.total_payout = 42.5;
+
+        let diff = diff_blocks(&old, &new);
+
+        assert_eq!(diff.added_blocks, vec!["10"]);
+        assert_eq!(diff.removed_blocks, vec!["1"]);
+        assert_eq!(
+            diff.changed_blocks[0].changes,
+            vec![
+                BlockChange::TotalPayout { old: 0.0, new: 42.5 },
+                BlockChange::Verified {
+                    social_id: "b".to_string(),
+                    old: false,
+                    new: true,
+                },
+                BlockChange::Payout {
+                    social_id: "b".to_string(),
+                    old: 0.0,
+                    new: 42.5,
+                },
+                BlockChange::ParticipantRemoved {
+                    social_id: "a".to_string(),
+                    username: "user_a".to_string(),
+                },
+            ]
+        );
+        assert!(diff_blocks(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_read_blocks_from_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("blocks.json.gz");
+        data_file::write(&path, serde_json::to_string(&fixture()).unwrap()).unwrap();
+        assert_eq!(read_blocks(&path).unwrap().len(), 2);
+
+        let broken = temp_dir.path().join("broken.json");
+        fs::write(&broken, "{").unwrap();
+        assert!(read_blocks(&broken).unwrap_err().to_string().contains("broken.json"));
+    }
+}
@@ -0,0 +1,357 @@
+use clap::Parser;
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::{CliptionsConfig, ConfigManager};
+use crate::embedder::{ClipEmbedder, EmbedderTrait};
+use crate::error::Result;
+use twitter_api::{TwitterApi, TwitterClient};
+
+#[derive(Parser)]
+pub struct DoctorArgs {
+    /// Config file path (default: config/config.yaml)
+    #[arg(long, default_value = "config/config.yaml")]
+    pub config: String,
+
+    /// Skip the Twitter credentials check (for offline environments)
+    #[arg(long)]
+    pub skip_twitter: bool,
+
+    /// Skip loading the CLIP model (for offline environments)
+    #[arg(long)]
+    pub skip_clip: bool,
+
+    /// CLIP model directory (default: $CLIPTIONS_MODEL_DIR or ~/.cliptions/models)
+    #[arg(long)]
+    pub clip_model: Option<PathBuf>,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Not critical, but worth fixing
+    Warn,
+    /// Critical; the validator can't run a block
+    Fail,
+    Skipped,
+}
+
+/// Result of one doctor check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// How to fix a warning or failure
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn problem(
+        status: CheckStatus,
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn skipped(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Skipped,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+}
+
+pub async fn run(args: DoctorArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    let mut checks = Vec::new();
+
+    let (config_check, config) = check_config(Path::new(&args.config));
+    checks.push(config_check);
+
+    if args.skip_twitter {
+        checks.push(CheckResult::skipped("Twitter credentials", "--skip-twitter"));
+    } else if let Some(config) = &config {
+        let twitter = &config.twitter;
+        let client = TwitterClient::new(twitter_api::TwitterConfig {
+            api_key: twitter.api_key.clone(),
+            api_secret: twitter.api_secret.clone(),
+            access_token: twitter.access_token.clone(),
+            access_token_secret: twitter.access_token_secret.clone(),
+            bearer_token: twitter.bearer_token.clone(),
+        });
+        checks.push(check_twitter(&client, &twitter.validator_username).await);
+        checks.push(check_bearer_token(config));
+    } else {
+        checks.push(CheckResult::skipped("Twitter credentials", "config did not load"));
+    }
+
+    if args.skip_clip {
+        checks.push(CheckResult::skipped("CLIP model", "--skip-clip"));
+    } else {
+        checks.push(match &args.clip_model {
+            Some(dir) => check_clip_model(|| ClipEmbedder::new_in(dir)),
+            None => check_clip_model(ClipEmbedder::new),
+        });
+    }
+
+    for dir in data_dirs()? {
+        checks.push(check_writable(&dir));
+    }
+
+    display_checks(&checks);
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(format!("{} critical check(s) failed", failed).into());
+    }
+
+    Ok(())
+}
+
+/// Load and validate the config file
+pub fn check_config(path: &Path) -> (CheckResult, Option<CliptionsConfig>) {
+    match ConfigManager::with_path(path) {
+        Ok(manager) => (
+            CheckResult::pass("Config", format!("{} loaded and validated", path.display())),
+            Some(manager.get_config().clone()),
+        ),
+        Err(e) => (
+            CheckResult::problem(
+                CheckStatus::Fail,
+                "Config",
+                e.to_string(),
+                "Copy config/config.yaml.template to config/config.yaml and fill in every required field",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Authenticate against Twitter as the validator account
+pub async fn check_twitter<C: TwitterApi>(client: &C, validator_username: &str) -> CheckResult {
+    match client.get_authenticated_user().await {
+        Ok(user) if user.username.eq_ignore_ascii_case(validator_username) => {
+            CheckResult::pass("Twitter credentials", format!("authenticated as @{}", user.username))
+        }
+        Ok(user) => CheckResult::problem(
+            CheckStatus::Warn,
+            "Twitter credentials",
+            format!(
+                "authenticated as @{} but the config names @{}",
+                user.username, validator_username
+            ),
+            "Check twitter.validator_username or the access token in the config",
+        ),
+        Err(e) => CheckResult::problem(
+            CheckStatus::Fail,
+            "Twitter credentials",
+            e.to_string(),
+            "Regenerate the API key and access tokens in the Twitter developer portal",
+        ),
+    }
+}
+
+/// Warn when reads will use user-context OAuth instead of an app-only bearer token
+pub fn check_bearer_token(config: &CliptionsConfig) -> CheckResult {
+    match &config.twitter.bearer_token {
+        Some(_) => CheckResult::pass("Twitter bearer token", "configured for read requests"),
+        None => CheckResult::problem(
+            CheckStatus::Warn,
+            "Twitter bearer token",
+            "not configured; reads count against the user rate limit",
+            "Set twitter.bearer_token in the config",
+        ),
+    }
+}
+
+/// Load the CLIP model using `load`
+pub fn check_clip_model<E: EmbedderTrait>(load: impl FnOnce() -> Result<E>) -> CheckResult {
+    match load() {
+        Ok(embedder) => CheckResult::pass(
+            "CLIP model",
+            format!("{} loaded ({} dimensions)", embedder.name(), embedder.embedding_dim()),
+        ),
+        Err(e) => CheckResult::problem(
+            CheckStatus::Fail,
+            "CLIP model",
+            e.to_string(),
+            "Check network access for the model download, or pass --skip-clip when offline",
+        ),
+    }
+}
+
+/// Check a data directory exists and can be written to
+///
+/// Nothing is created: a missing directory is only a warning, as the
+/// validator creates it on first use.
+pub fn check_writable(dir: &Path) -> CheckResult {
+    let name = format!("Writable {}", dir.display());
+    match fs::metadata(dir) {
+        Ok(metadata) if !metadata.is_dir() => {
+            return CheckResult::problem(
+                CheckStatus::Fail,
+                name,
+                "exists but is not a directory",
+                "Move the file out of the way",
+            );
+        }
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return CheckResult::problem(
+                CheckStatus::Warn,
+                name,
+                "directory does not exist yet",
+                format!(
+                    "Create it with `mkdir -p {}` or let the validator create it",
+                    dir.display()
+                ),
+            );
+        }
+        Err(e) => {
+            return CheckResult::problem(
+                CheckStatus::Fail,
+                name,
+                e.to_string(),
+                "Fix the directory's permissions or ownership",
+            );
+        }
+    }
+
+    let probe = dir.join(".cliptions_doctor");
+    let result = fs::write(&probe, b"ok").and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => CheckResult::pass(name, "directory is writable"),
+        Err(e) => CheckResult::problem(
+            CheckStatus::Fail,
+            name,
+            e.to_string(),
+            "Fix the directory's permissions or ownership",
+        ),
+    }
+}
+
+/// Directories the validator writes to
+fn data_dirs() -> Result<Vec<PathBuf>> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| "Could not determine home directory".to_string())?;
+    let cliptions_dir = home_dir.join(".cliptions");
+    Ok(vec![
+        cliptions_dir.join("validator"),
+        cliptions_dir.join("data"),
+        PathBuf::from("data"),
+    ])
+}
+
+fn display_checks(checks: &[CheckResult]) {
+    println!("{}", "Cliptions Doctor".bold().underline());
+    for check in checks {
+        let status = match check.status {
+            CheckStatus::Pass => "PASS".green().bold(),
+            CheckStatus::Warn => "WARN".yellow().bold(),
+            CheckStatus::Fail => "FAIL".red().bold(),
+            CheckStatus::Skipped => "SKIP".dimmed(),
+        };
+        println!("[{}] {}: {}", status, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("       {} {}", "Hint:".blue().bold(), hint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedder::MockEmbedder;
+    use crate::error::EmbeddingError;
+    use std::path::Path as StdPath;
+    use twitter_api::testing::InMemoryTwitterApi;
+
+    #[tokio::test]
+    async fn test_check_twitter() {
+        let validator = InMemoryTwitterApi::new().with_user("42", "cliptions");
+        let ok = check_twitter(&validator, "Cliptions").await;
+        assert_eq!(ok.status, CheckStatus::Pass);
+        assert!(ok.detail.contains("@cliptions"));
+
+        let someone_else = InMemoryTwitterApi::new().with_user("42", "someone_else");
+        let wrong_account = check_twitter(&someone_else, "cliptions").await;
+        assert_eq!(wrong_account.status, CheckStatus::Warn);
+
+        let rejected = InMemoryTwitterApi::new();
+        rejected.fail_next_call(401, "Unauthorized");
+        let unauthorized = check_twitter(&rejected, "cliptions").await;
+        assert_eq!(unauthorized.status, CheckStatus::Fail);
+        assert!(unauthorized.hint.is_some());
+    }
+
+    #[test]
+    fn test_check_clip_model() {
+        let loaded = check_clip_model(|| Ok(MockEmbedder::new(512)));
+        assert_eq!(loaded.status, CheckStatus::Pass);
+        assert!(loaded.detail.contains("512"));
+
+        let missing = check_clip_model(|| -> Result<MockEmbedder> {
+            Err(EmbeddingError::ModelLoadFailed.into())
+        });
+        assert_eq!(missing.status, CheckStatus::Fail);
+        assert!(missing.hint.unwrap().contains("--skip-clip"));
+    }
+
+    #[test]
+    fn test_check_config() {
+        let (check, config) = check_config(StdPath::new("does/not/exist.yaml"));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(config.is_none());
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("validator");
+
+        // A missing directory is reported, not created
+        let missing = check_writable(&dir);
+        assert_eq!(missing.status, CheckStatus::Warn);
+        assert!(!dir.exists());
+
+        fs::create_dir(&dir).unwrap();
+        let check = check_writable(&dir);
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+
+        // A regular file is not a data directory
+        let file = temp_dir.path().join("file");
+        fs::write(&file, "x").unwrap();
+        assert_eq!(check_writable(&file).status, CheckStatus::Fail);
+    }
+}
@@ -7,9 +7,10 @@ use clap::Parser;
 use colored::Colorize;
 use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::commitment::CommitmentGenerator;
+use crate::commitment::{CommitmentGenerator, HashAlgo};
+use crate::commitment_store::{CommitmentStore, StoredCommitment};
 use crate::config::ConfigManager;
 use crate::error::Result;
 
@@ -25,16 +26,16 @@ ensuring fair gameplay in the prediction market.
 
 Examples:
   # Basic commitment generation (saves to ~/.cliptions/miner/commitments.json by default)
-  cliptions generate-commitment \"Cat sanctuary with woman wearing snoopy sweater\" --salt \"random_secret_123\"
+  cliptions generate-commitment \"Cat sanctuary with woman wearing snoopy sweater\" --salt \"random_secret_123\" --block-num 42
   
   # Save to custom location
-  cliptions generate-commitment \"My prediction\" --salt \"mysalt\" --save-to predictions.json
+  cliptions generate-commitment \"My prediction\" --salt \"mysalt\" --block-num 42 --save-to predictions.json
   
   # Don't save locally
   cliptions generate-commitment \"My prediction\" --salt \"mysalt\" --no-save
   
   # Generate multiple commitments from JSON input
-  cliptions generate-commitment --batch-file commitments.json
+  cliptions generate-commitment --batch-file batch.json --block-num 42
   
   # Quiet mode (only output the hash)
   cliptions generate-commitment \"My prediction\" --salt \"mysalt\" --quiet
@@ -51,7 +52,11 @@ pub struct GenerateCommitmentArgs {
     #[arg(long, short, default_value = "text", value_parser = ["text", "json", "csv"])]
     pub output: String,
 
-    /// Save commitment data to file (JSON format, defaults to ~/.cliptions/miner/commitments.json)
+    /// Block the commitment is for (required unless --no-save)
+    #[arg(long)]
+    pub block_num: Option<String>,
+
+    /// Commitment store to save to (defaults to ~/.cliptions/miner/commitments.json)
     #[arg(long)]
     pub save_to: Option<PathBuf>,
 
@@ -82,6 +87,10 @@ pub struct GenerateCommitmentArgs {
     /// Include timestamp in output
     #[arg(long)]
     pub timestamp: bool,
+
+    /// Hash algorithm: sha256 (default, what validators expect) or blake3
+    #[arg(long, default_value = "sha256")]
+    pub hash_algo: HashAlgo,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -91,6 +100,9 @@ struct CommitmentData {
     commitment: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     timestamp: Option<String>,
+    /// Algorithm the commitment was hashed with; older files predate it and are SHA-256
+    #[serde(default)]
+    hash_algo: HashAlgo,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -141,27 +153,13 @@ pub fn run(args: GenerateCommitmentArgs) -> Result<()> {
 
     // Save to file (default behavior unless --no-save is specified)
     if !args.no_save {
-        let save_path = if let Some(custom_path) = &args.save_to {
-            custom_path.clone()
-        } else {
-            // Default to ~/.cliptions/miner/commitments.json
-            let home_dir = dirs::home_dir()
-                .ok_or_else(|| "Could not determine home directory".to_string())?;
-            let cliptions_dir = home_dir.join(".cliptions");
-            let miner_dir = cliptions_dir.join("miner");
-            
-            // Create directories if they don't exist
-            if !cliptions_dir.exists() {
-                fs::create_dir_all(&cliptions_dir)?;
-            }
-            if !miner_dir.exists() {
-                fs::create_dir_all(&miner_dir)?;
-            }
-            
-            miner_dir.join("commitments.json")
+        let save_path = match &args.save_to {
+            Some(custom_path) => custom_path.clone(),
+            None => CommitmentStore::default_path()?,
         };
+        let block_num = args.block_num.as_deref().unwrap_or_default();
 
-        save_results(&results, &save_path)?;
+        save_results(&results, block_num, &save_path)?;
 
         if !args.quiet {
             println!(
@@ -176,6 +174,13 @@ pub fn run(args: GenerateCommitmentArgs) -> Result<()> {
 }
 
 fn validate_inputs(args: &GenerateCommitmentArgs) -> Result<()> {
+    // Stored commitments are looked up by block when revealing
+    if !args.no_save && args.block_num.as_deref().is_none_or(str::is_empty) {
+        return Err("--block-num is required to save the commitment (or pass --no-save)"
+            .to_string()
+            .into());
+    }
+
     // Batch mode validation
     if args.batch_file.is_some() {
         if args.message.is_some() || args.salt.is_some() {
@@ -213,7 +218,7 @@ fn validate_inputs(args: &GenerateCommitmentArgs) -> Result<()> {
 fn generate_single_commitment(
     args: &GenerateCommitmentArgs,
 ) -> Result<CommitmentResults> {
-    let generator = CommitmentGenerator::new();
+    let generator = CommitmentGenerator::new().with_hash_algo(args.hash_algo);
 
     let message = args.message.as_ref().unwrap();
     let salt = args.salt.as_ref().unwrap().clone();
@@ -240,6 +245,7 @@ fn generate_single_commitment(
         salt,
         commitment,
         timestamp,
+        hash_algo: generator.hash_algo(),
     };
 
     Ok(CommitmentResults {
@@ -255,7 +261,7 @@ fn generate_batch_commitments(
     let file_content = fs::read_to_string(batch_file)?;
     let batch_data: Value = serde_json::from_str(&file_content)?;
 
-    let generator = CommitmentGenerator::new();
+    let generator = CommitmentGenerator::new().with_hash_algo(args.hash_algo);
     let mut commitments = Vec::new();
 
     if let Some(batch_array) = batch_data.as_array() {
@@ -286,6 +292,7 @@ fn generate_batch_commitments(
                 salt,
                 commitment,
                 timestamp,
+                hash_algo: generator.hash_algo(),
             });
         }
     } else {
@@ -338,6 +345,7 @@ fn display_text_format(
             println!("{}: {}", "Message".blue().bold(), data.message);
             println!("{}: {}", "Salt".blue().bold(), data.salt);
             println!("{}: {}", "Commitment".green().bold(), data.commitment);
+            println!("{}: {}", "Hash algorithm".blue().bold(), data.hash_algo);
 
             if let Some(ref timestamp) = data.timestamp {
                 println!("{}: {}", "Timestamp".blue().bold(), timestamp);
@@ -346,6 +354,13 @@ fn display_text_format(
             // Simple format matching the original Python script
             println!("Commitment: {}", data.commitment);
         }
+        if !data.hash_algo.is_default() {
+            println!(
+                "{} Add \"Hash: {}\" to your commitment reply so validators verify it with the same algorithm",
+                "Note:".yellow().bold(),
+                data.hash_algo
+            );
+        }
     } else {
         // Batch mode
         println!(
@@ -390,55 +405,36 @@ fn display_json_format(results: &CommitmentResults) -> Result<()> {
 }
 
 fn display_csv_format(results: &CommitmentResults) -> Result<()> {
-    println!("message,salt,commitment,timestamp");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["message", "salt", "commitment", "timestamp", "hash_algo"])?;
 
     for data in &results.commitments {
-        println!(
-            "{},{},{},{}",
-            csv_escape(&data.message),
-            csv_escape(&data.salt),
-            data.commitment,
-            data.timestamp.as_deref().unwrap_or("")
-        );
+        writer.write_record([
+            data.message.as_str(),
+            &data.salt,
+            &data.commitment,
+            data.timestamp.as_deref().unwrap_or(""),
+            &data.hash_algo.to_string(),
+        ])?;
     }
 
+    writer.flush()?;
     Ok(())
 }
 
-fn csv_escape(field: &str) -> String {
-    if field.contains(',') || field.contains('"') || field.contains('\n') {
-        format!("\"{}\"", field.replace('"', "\"\""))
-    } else {
-        field.to_string()
-    }
-}
-
-fn save_results(
-    results: &CommitmentResults,
-    save_path: &PathBuf,
-) -> Result<()> {
-    // Load existing commitments if file exists
-    let mut existing_commitments = Vec::new();
-    if save_path.exists() {
-        let file_content = fs::read_to_string(save_path)?;
-        if let Ok(existing_results) = serde_json::from_str::<CommitmentResults>(&file_content) {
-            existing_commitments = existing_results.commitments;
-        }
+/// Add the generated commitments for `block_num` to the commitment store at `save_path`
+fn save_results(results: &CommitmentResults, block_num: &str, save_path: &Path) -> Result<()> {
+    let mut store = CommitmentStore::open(save_path)?;
+    for data in &results.commitments {
+        let record = StoredCommitment::new(
+            block_num.to_string(),
+            data.message.clone(),
+            data.salt.clone(),
+            data.commitment.clone(),
+        )
+        .with_hash_algo(data.hash_algo);
+        store.add(record)?;
     }
-
-    // Combine existing and new commitments
-    let mut all_commitments = existing_commitments;
-    all_commitments.extend(results.commitments.clone());
-
-    // Create new results with combined commitments
-    let total_count = all_commitments.len();
-    let combined_results = CommitmentResults {
-        commitments: all_commitments,
-        total_generated: total_count,
-    };
-
-    let json_output = serde_json::to_string_pretty(&combined_results)?;
-    fs::write(save_path, json_output)?;
     Ok(())
 }
 
@@ -452,6 +448,7 @@ mod tests {
             message: Some("test message".to_string()),
             salt: Some("test_salt".to_string()),
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: None,
             no_save: false,
             batch_file: None,
@@ -460,6 +457,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         assert!(validate_inputs(&args).is_ok());
@@ -471,6 +469,7 @@ mod tests {
             message: Some("test message".to_string()),
             salt: None,
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: None,
             no_save: false,
             batch_file: None,
@@ -479,6 +478,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         assert!(validate_inputs(&args).is_err());
@@ -490,6 +490,7 @@ mod tests {
             message: None,
             salt: Some("test_salt".to_string()),
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: None,
             no_save: false,
             batch_file: None,
@@ -498,6 +499,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         assert!(validate_inputs(&args).is_err());
@@ -509,6 +511,7 @@ mod tests {
             message: Some("".to_string()),
             salt: Some("test_salt".to_string()),
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: None,
             no_save: false,
             batch_file: None,
@@ -517,6 +520,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         assert!(validate_inputs(&args).is_err());
@@ -528,6 +532,7 @@ mod tests {
             message: Some("test message".to_string()),
             salt: Some("".to_string()),
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: None,
             no_save: false,
             batch_file: None,
@@ -536,6 +541,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         assert!(validate_inputs(&args).is_err());
@@ -547,6 +553,7 @@ mod tests {
             message: Some("test message".to_string()),
             salt: Some("test_salt".to_string()),
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: None,
             no_save: false,
             batch_file: None,
@@ -555,6 +562,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         let result = generate_single_commitment(&args).unwrap();
@@ -566,89 +574,102 @@ mod tests {
     }
 
     #[test]
-    fn test_csv_escape() {
-        assert_eq!(csv_escape("simple"), "simple");
-        assert_eq!(csv_escape("with,comma"), "\"with,comma\"");
-        assert_eq!(csv_escape("with\"quote"), "\"with\"\"quote\"");
-        assert_eq!(csv_escape("with\nline"), "\"with\nline\"");
+    fn test_generate_single_commitment_with_blake3() {
+        let args = GenerateCommitmentArgs {
+            message: Some("test message".to_string()),
+            salt: Some("test_salt".to_string()),
+            output: "text".to_string(),
+            block_num: Some("1".to_string()),
+            save_to: None,
+            no_save: false,
+            batch_file: None,
+            verbose: false,
+            no_color: false,
+            quiet: false,
+            config: None,
+            timestamp: false,
+            hash_algo: HashAlgo::Blake3,
+        };
+
+        let result = generate_single_commitment(&args).unwrap();
+        let data = &result.commitments[0];
+        assert_eq!(data.hash_algo, HashAlgo::Blake3);
+        assert!(crate::commitment::CommitmentVerifier::new()
+            .with_hash_algo(HashAlgo::Blake3)
+            .verify(&data.message, &data.salt, &data.commitment));
+
+        // Files written before the algorithm was recorded load as SHA-256
+        let legacy: CommitmentData =
+            serde_json::from_str(r#"{"message":"m","salt":"s","commitment":"c"}"#).unwrap();
+        assert_eq!(legacy.hash_algo, HashAlgo::Sha256);
     }
 
     #[test]
     fn test_save_results_to_file() {
-        use std::fs;
         use tempfile::tempdir;
 
-        // Create a temporary directory for testing
         let temp_dir = tempdir().unwrap();
-        let test_file = temp_dir.path().join("test_commitments.json");
+        let test_file = temp_dir.path().join("miner").join("commitments.json");
+        let generator = CommitmentGenerator::new();
+        let data = |message: &str, salt: &str| CommitmentData {
+            message: message.to_string(),
+            salt: salt.to_string(),
+            commitment: generator.generate(message, salt).unwrap(),
+            timestamp: None,
+            hash_algo: HashAlgo::default(),
+        };
 
-        // Create test commitment results
         let results = CommitmentResults {
-            commitments: vec![
-                CommitmentData {
-                    message: "Test prediction 1".to_string(),
-                    salt: "salt1".to_string(),
-                    commitment: "abc123def456".to_string(),
-                    timestamp: Some("2024-01-01T12:00:00Z".to_string()),
-                },
-                CommitmentData {
-                    message: "Test prediction 2".to_string(),
-                    salt: "salt2".to_string(),
-                    commitment: "def456ghi789".to_string(),
-                    timestamp: Some("2024-01-01T13:00:00Z".to_string()),
-                },
-            ],
+            commitments: vec![data("Test prediction 1", "salt1"), data("Test prediction 2", "salt2")],
             total_generated: 2,
         };
+        save_results(&results, "42", &test_file).unwrap();
 
-        // Test saving to a new file
-        let save_result = save_results(&results, &test_file);
-        assert!(save_result.is_ok(), "Failed to save results: {:?}", save_result);
-
-        // Verify file exists
-        assert!(test_file.exists(), "Commitment file was not created");
-
-        // Read and verify the saved content
-        let file_content = fs::read_to_string(&test_file).unwrap();
-        let saved_results: CommitmentResults = serde_json::from_str(&file_content).unwrap();
-
-        assert_eq!(saved_results.total_generated, 2);
-        assert_eq!(saved_results.commitments.len(), 2);
-        assert_eq!(saved_results.commitments[0].message, "Test prediction 1");
-        assert_eq!(saved_results.commitments[0].salt, "salt1");
-        assert_eq!(saved_results.commitments[0].commitment, "abc123def456");
-        assert_eq!(saved_results.commitments[1].message, "Test prediction 2");
-        assert_eq!(saved_results.commitments[1].salt, "salt2");
-        assert_eq!(saved_results.commitments[1].commitment, "def456ghi789");
-
-        // Test appending to existing file
+        // Further commitments are added to the same store
         let additional_results = CommitmentResults {
-            commitments: vec![
-                CommitmentData {
-                    message: "Test prediction 3".to_string(),
-                    salt: "salt3".to_string(),
-                    commitment: "ghi789jkl012".to_string(),
-                    timestamp: Some("2024-01-01T14:00:00Z".to_string()),
-                },
-            ],
+            commitments: vec![data("Test prediction 3", "salt3")],
             total_generated: 1,
         };
+        save_results(&additional_results, "43", &test_file).unwrap();
+
+        let store = CommitmentStore::open(&test_file).unwrap();
+        assert_eq!(store.records().len(), 3);
+        let block_42 = store.get_for_block("42");
+        assert_eq!(block_42.len(), 2);
+        assert_eq!(block_42[0].guess, "Test prediction 1");
+        assert_eq!(block_42[0].salt, "salt1");
+        assert_eq!(block_42[0].commitment, results.commitments[0].commitment);
+        assert_eq!(store.get_for_block("43")[0].guess, "Test prediction 3");
+        assert!(store.records().iter().all(StoredCommitment::verify));
+
+        // Saving the same commitment twice is refused
+        assert!(save_results(&additional_results, "43", &test_file).is_err());
+    }
 
-        let append_result = save_results(&additional_results, &test_file);
-        assert!(append_result.is_ok(), "Failed to append results: {:?}", append_result);
-
-        // Verify the file now contains all 3 commitments
-        let updated_content = fs::read_to_string(&test_file).unwrap();
-        let updated_results: CommitmentResults = serde_json::from_str(&updated_content).unwrap();
-
-        assert_eq!(updated_results.total_generated, 3);
-        assert_eq!(updated_results.commitments.len(), 3);
-        assert_eq!(updated_results.commitments[2].message, "Test prediction 3");
-        assert_eq!(updated_results.commitments[2].salt, "salt3");
-        assert_eq!(updated_results.commitments[2].commitment, "ghi789jkl012");
+    #[test]
+    fn test_validate_inputs_requires_block_num_to_save() {
+        let args = GenerateCommitmentArgs {
+            message: Some("test message".to_string()),
+            salt: Some("test_salt".to_string()),
+            output: "text".to_string(),
+            block_num: None,
+            save_to: None,
+            no_save: false,
+            batch_file: None,
+            verbose: false,
+            no_color: false,
+            quiet: false,
+            config: None,
+            timestamp: false,
+            hash_algo: HashAlgo::default(),
+        };
+        assert!(validate_inputs(&args).is_err());
 
-        // Clean up
-        temp_dir.close().unwrap();
+        let args = GenerateCommitmentArgs {
+            no_save: true,
+            ..args
+        };
+        assert!(validate_inputs(&args).is_ok());
     }
 
     #[test]
@@ -664,6 +685,7 @@ mod tests {
             message: Some("test message".to_string()),
             salt: Some("test_salt".to_string()),
             output: "text".to_string(),
+            block_num: Some("1".to_string()),
             save_to: Some(test_file.clone()),
             no_save: true, // This should prevent saving
             batch_file: None,
@@ -672,6 +694,7 @@ mod tests {
             quiet: false,
             config: None,
             timestamp: false,
+            hash_algo: HashAlgo::default(),
         };
 
         // Run the function
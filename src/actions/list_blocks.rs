@@ -0,0 +1,148 @@
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+use crate::block_processor::{BlockListQuery, BlockProcessor, BlockSort};
+use crate::embedder::MockEmbedder;
+use crate::error::Result;
+use crate::scoring::ClipBatchStrategy;
+use crate::types::BlockStatus;
+
+#[derive(Parser)]
+pub struct ListBlocksArgs {
+    /// Path to blocks.json file (default: data/blocks.json)
+    #[arg(short = 'f', long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Only list blocks with this status
+    #[arg(long, value_parser = ["open", "processing", "complete", "cancelled"])]
+    pub status: Option<String>,
+
+    /// Order blocks by this field, newest/largest first
+    #[arg(long, default_value = "created_at", value_parser = ["block_num", "created_at", "prize_pool"])]
+    pub sort: String,
+
+    /// List smallest/oldest first instead
+    #[arg(long)]
+    pub ascending: bool,
+
+    /// Maximum number of blocks to list
+    #[arg(long)]
+    pub limit: Option<usize>,
+
+    /// Number of blocks to skip, after filtering and sorting
+    #[arg(long, default_value = "0")]
+    pub offset: usize,
+
+    /// Output format: text, json
+    #[arg(long, short, default_value = "text", value_parser = ["text", "json"])]
+    pub output: String,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+impl ListBlocksArgs {
+    /// The listing query described by the arguments
+    pub fn query(&self) -> BlockListQuery {
+        BlockListQuery {
+            status: self.status.as_deref().map(|status| match status {
+                "processing" => BlockStatus::Processing,
+                "complete" => BlockStatus::Complete,
+                "cancelled" => BlockStatus::Cancelled,
+                _ => BlockStatus::Open,
+            }),
+            sort: match self.sort.as_str() {
+                "block_num" => BlockSort::BlockNum,
+                "prize_pool" => BlockSort::PrizePool,
+                _ => BlockSort::CreatedAt,
+            },
+            ascending: self.ascending,
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
+
+pub fn run(args: ListBlocksArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    // Listing only reads stored blocks, so the embedder is never used
+    let mut processor = BlockProcessor::new(
+        args.blocks_file.to_string_lossy().to_string(),
+        MockEmbedder::clip_like(),
+        ClipBatchStrategy::new(),
+    );
+    let summaries = processor.list_blocks(&args.query())?;
+
+    if args.output == "json" {
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+        return Ok(());
+    }
+
+    if summaries.is_empty() {
+        println!("No blocks to list");
+        return Ok(());
+    }
+
+    println!(
+        "{:<10} {:<11} {:>12} {:>12} {:<20}",
+        "Block".bold(),
+        "Status".bold(),
+        "Verified".bold(),
+        "Prize pool".bold(),
+        "Created".bold()
+    );
+    for summary in &summaries {
+        println!(
+            "{:<10} {:<11} {:>12} {:>12.4} {:<20}",
+            summary.block_num,
+            format!("{:?}", summary.status),
+            format!("{}/{}", summary.verified_count, summary.participant_count),
+            summary.prize_pool,
+            summary.created_at.format("%Y-%m-%d %H:%M")
+        );
+    }
+    println!(
+        "{} block(s) shown, starting at offset {}",
+        summaries.len(),
+        args.offset
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_blocks_args_query() {
+        let args = ListBlocksArgs::parse_from([
+            "list-blocks",
+            "--status",
+            "complete",
+            "--sort",
+            "prize_pool",
+            "--ascending",
+            "--limit",
+            "5",
+            "--offset",
+            "10",
+        ]);
+        let query = args.query();
+        assert_eq!(query.status, Some(BlockStatus::Complete));
+        assert_eq!(query.sort, BlockSort::PrizePool);
+        assert!(query.ascending);
+        assert_eq!((query.offset, query.limit), (10, Some(5)));
+
+        // Recent-first over every block by default
+        let query = ListBlocksArgs::parse_from(["list-blocks"]).query();
+        assert_eq!(query.status, None);
+        assert_eq!(query.sort, BlockSort::CreatedAt);
+        assert!(!query.ascending);
+        assert_eq!((query.offset, query.limit), (0, None));
+    }
+}
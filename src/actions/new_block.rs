@@ -1,7 +1,9 @@
 use crate::error::Result;
 use clap::Parser;
 use crate::config::ConfigManager;
-use crate::social::{AnnouncementData, AnnouncementFormatter, TweetCache, TweetCacheManager};
+use crate::social::{
+    AnnouncementData, AnnouncementFormatter, DeadlineFormat, TweetCache, TweetCacheManager,
+};
 use crate::twitter_utils::post_tweet_flexible;
 
 use twitter_api::{TwitterClient, TwitterConfig};
@@ -32,6 +34,10 @@ pub struct NewBlockArgs {
     #[arg(long)]
     prize_pool: Option<f64>,
 
+    /// Timezone deadlines are shown in (IANA name, e.g. Europe/London)
+    #[arg(long, default_value = "America/New_York")]
+    timezone: chrono_tz::Tz,
+
     /// Show verbose output
     #[arg(short, long)]
     verbose: bool,
@@ -78,33 +84,36 @@ pub async fn run(args: NewBlockArgs) -> Result<()> {
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(twitter_config);
 
     // Calculate target time
     let target_time = chrono::Utc::now() + chrono::Duration::hours(args.target_time_hours as i64);
-    let eastern = chrono_tz::US::Eastern;
-    let target_time_eastern = target_time.with_timezone(&eastern);
-    let formatted_target_time = format!(
-        "{} | {} | EST",
-        target_time_eastern.format("%Y-%m-%d"),
-        target_time_eastern.format("%H:%M:%S")
-    );
+    let formatter = AnnouncementFormatter::new()
+        .with_deadline_format(DeadlineFormat::new(args.timezone));
 
     // Create announcement data
     let announcement_data = AnnouncementData {
         block_num: args.block_num,
         state_name: "commitmentsopen".to_string(),
-        target_time: formatted_target_time,
+        target_time: formatter.format_deadline(target_time),
         hashtags: args.hashtags.unwrap_or_default(),
         message: args.message.unwrap_or_default(),
         prize_pool: args.prize_pool,
         livestream_url: Some(args.livestream_url),
+        deadline: Some(target_time),
     };
 
-    // Format the announcement
-    let formatter = AnnouncementFormatter::new();
-    let tweet_text = formatter.create_commitment_announcement(&announcement_data);
+    // Format the announcement, dropping custom hashtags that don't fit
+    let fitted = formatter.create_commitment_announcement_fitted(&announcement_data);
+    if !fitted.dropped_hashtags.is_empty() {
+        eprintln!(
+            "⚠️  Dropped hashtags to fit the tweet limit: {}",
+            fitted.dropped_hashtags.join(" ")
+        );
+    }
+    let tweet_text = fitted.text;
 
     if args.verbose {
         println!("📝 Generated tweet text:");
@@ -5,8 +5,7 @@ use crate::config::ConfigManager;
 use crate::error::Result;
 use twitter_api::{TwitterApi, TwitterClient, TwitterError};
 use chrono::{Duration as ChronoDuration, Utc};
-use chrono_tz;
-use crate::social::AnnouncementFormatter;
+use crate::social::{AnnouncementFormatter, DeadlineFormat};
 use crate::twitter_utils::post_tweet_flexible;
 
 #[derive(Parser)]
@@ -27,6 +26,10 @@ pub struct PostTargetFrameArgs {
     #[arg(long)]
     pub target_time: u64,
 
+    /// Timezone deadlines are shown in (IANA name, e.g. Europe/London)
+    #[arg(long, default_value = "America/New_York")]
+    pub timezone: chrono_tz::Tz,
+
     /// Show verbose output
     #[arg(short, long)]
     pub verbose: bool,
@@ -90,20 +93,17 @@ pub async fn run(args: PostTargetFrameArgs) -> Result<()> {
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(twitter_config);
 
     // Calculate target time (hours from now)
     let target_time = Utc::now() + ChronoDuration::hours(args.target_time as i64);
 
-    // Format target time as "2025-04-01 | 16:30:57 | EST"
-    let eastern = chrono_tz::US::Eastern;
-    let target_time_eastern = target_time.with_timezone(&eastern);
-    let formatted_target_time = format!(
-        "{} | {} | EST",
-        target_time_eastern.format("%Y-%m-%d"),
-        target_time_eastern.format("%H:%M:%S")
-    );
+    // Format target time as "2025-04-01 | 16:30:57 | EDT (2025-04-01T20:30:57Z)"
+    let formatter = AnnouncementFormatter::new()
+        .with_deadline_format(DeadlineFormat::new(args.timezone));
+    let formatted_target_time = formatter.format_deadline(target_time);
 
     // Create announcement data for reveals
     let announcement_data = crate::social::AnnouncementData {
@@ -114,10 +114,10 @@ pub async fn run(args: PostTargetFrameArgs) -> Result<()> {
         message: String::new(), // Not used for reveal announcements
         prize_pool: None,
         livestream_url: None, // Optional for reveals
+        deadline: Some(target_time),
     };
 
     // Format the reveals announcement
-    let formatter = AnnouncementFormatter::new();
     let tweet_text = formatter.create_reveals_announcement(&announcement_data);
 
     if args.verbose {
@@ -184,6 +184,16 @@ pub async fn run(args: PostTargetFrameArgs) -> Result<()> {
             }
             Err(error_msg.into())
         }
+        Err(TwitterError::ApiStructured { status, title, detail, .. }) => {
+            let message = format!("{}: {}", title, detail);
+            let error_msg = format!("Twitter API error: {} - {}", status, message);
+            if args.quiet {
+                eprintln!("{}", error_msg);
+            } else {
+                println!("❌ {}", error_msg);
+            }
+            Err(error_msg.into())
+        }
         Err(TwitterError::NetworkError(e)) => {
             let error_msg = format!("Network error: {}", e);
             if args.quiet {
@@ -229,6 +239,25 @@ pub async fn run(args: PostTargetFrameArgs) -> Result<()> {
             }
             Err(error_msg.into())
         }
+        Err(TwitterError::DuplicateContent(e)) => {
+            let error_msg = format!("Duplicate content: {}", e);
+            if args.quiet {
+                eprintln!("{}", error_msg);
+            } else {
+                println!("❌ {}", error_msg);
+                println!("💡 This text was already posted; the existing tweet was left as is");
+            }
+            Err(error_msg.into())
+        }
+        Err(TwitterError::Unsupported(e)) => {
+            let error_msg = format!("Not supported: {}", e);
+            if args.quiet {
+                eprintln!("{}", error_msg);
+            } else {
+                println!("❌ {}", error_msg);
+            }
+            Err(error_msg.into())
+        }
         Err(TwitterError::FileError(e)) => {
             let error_msg = format!("File error: {}", e);
             if args.quiet {
@@ -282,6 +311,7 @@ mod tests {
         assert!(!args.no_color);
         assert!(!args.quiet);
         assert_eq!(args.config, "config/config.yaml");
+        assert_eq!(args.timezone, chrono_tz::America::New_York);
     }
 
     #[test]
@@ -296,6 +326,7 @@ mod tests {
             "--no-color",
             "--quiet",
             "--config", "custom_config.yaml",
+            "--timezone", "Asia/Tokyo",
         ]).unwrap();
 
         assert_eq!(args.reply_to, "123456789");
@@ -306,6 +337,7 @@ mod tests {
         assert!(args.no_color);
         assert!(args.quiet);
         assert_eq!(args.config, "custom_config.yaml");
+        assert_eq!(args.timezone, chrono_tz::Asia::Tokyo);
     }
 
     #[test]
@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use colored::Colorize;
+use crate::error::Result;
+use crate::payout::DEFAULT_CURRENCY_LABEL;
+use crate::social::{AnnouncementData, AnnouncementFormatter, DeadlineFormat};
+use twitter_api::{weighted_tweet_length, MAX_TWEET_LENGTH};
+
+#[derive(Parser)]
+pub struct PreviewAnnouncementsArgs {
+    /// Block number
+    #[arg(short, long)]
+    pub block_num: u64,
+
+    /// Livestream URL shown in the commitment announcement
+    #[arg(short, long)]
+    pub livestream_url: String,
+
+    /// Commitment deadline in hours from now
+    #[arg(long, default_value = "24")]
+    pub commitment_deadline_hours: u64,
+
+    /// Reveals deadline in hours from now
+    #[arg(long, default_value = "48")]
+    pub reveals_deadline_hours: u64,
+
+    /// Prize pool amount (optional)
+    #[arg(long)]
+    pub prize_pool: Option<f64>,
+
+    /// Timezone deadlines are shown in (IANA name, e.g. Europe/London)
+    #[arg(long, default_value = "America/New_York")]
+    pub timezone: chrono_tz::Tz,
+
+    /// Currency label shown next to the prize pool
+    #[arg(long, default_value = DEFAULT_CURRENCY_LABEL)]
+    pub currency_label: String,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// A rendered lifecycle announcement
+pub struct AnnouncementPreview {
+    pub phase: &'static str,
+    pub text: String,
+    pub weighted_length: usize,
+}
+
+impl AnnouncementPreview {
+    fn new(phase: &'static str, text: String) -> Self {
+        let weighted_length = weighted_tweet_length(&text);
+        Self {
+            phase,
+            text,
+            weighted_length,
+        }
+    }
+
+    /// Whether the tweet would be rejected for length
+    pub fn is_too_long(&self) -> bool {
+        self.weighted_length > MAX_TWEET_LENGTH
+    }
+}
+
+pub fn run(args: PreviewAnnouncementsArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    let now = Utc::now();
+    let commitment_deadline = now + chrono::Duration::hours(args.commitment_deadline_hours as i64);
+    let reveals_deadline = now + chrono::Duration::hours(args.reveals_deadline_hours as i64);
+
+    let previews = build_previews(
+        args.block_num,
+        &args.livestream_url,
+        commitment_deadline,
+        reveals_deadline,
+        args.prize_pool,
+        DeadlineFormat::new(args.timezone),
+        &args.currency_label,
+    );
+
+    for preview in &previews {
+        println!("{}", format!("=== {} ===", preview.phase).bold());
+        println!("{}", preview.text);
+
+        let length = format!("{}/{}", preview.weighted_length, MAX_TWEET_LENGTH);
+        if preview.is_too_long() {
+            println!("{} {} (too long)", "Weighted length:".bold(), length.red().bold());
+        } else {
+            println!("{} {}", "Weighted length:".bold(), length.green());
+        }
+        println!();
+    }
+
+    let too_long = previews.iter().filter(|p| p.is_too_long()).count();
+    if too_long > 0 {
+        println!(
+            "{} {} announcement(s) exceed {} characters and would fail to post",
+            "Warning:".yellow().bold(),
+            too_long,
+            MAX_TWEET_LENGTH
+        );
+    }
+
+    Ok(())
+}
+
+/// Render every lifecycle announcement for a block without posting anything
+pub fn build_previews(
+    block_num: u64,
+    livestream_url: &str,
+    commitment_deadline: DateTime<Utc>,
+    reveals_deadline: DateTime<Utc>,
+    prize_pool: Option<f64>,
+    deadline_format: DeadlineFormat,
+    currency_label: &str,
+) -> Vec<AnnouncementPreview> {
+    let formatter = AnnouncementFormatter::new()
+        .with_deadline_format(deadline_format)
+        .with_currency_label(currency_label);
+    let data = |state_name: &str, deadline: DateTime<Utc>| AnnouncementData {
+        block_num,
+        state_name: state_name.to_string(),
+        target_time: formatter.format_deadline(deadline),
+        hashtags: vec![],
+        message: String::new(),
+        prize_pool,
+        livestream_url: Some(livestream_url.to_string()),
+        deadline: Some(deadline),
+    };
+
+    vec![
+        AnnouncementPreview::new(
+            "Commitments open",
+            formatter
+                .create_commitment_announcement(&data("commitmentsopen", commitment_deadline)),
+        ),
+        AnnouncementPreview::new(
+            "Reveals open",
+            formatter.create_reveals_announcement(&data("revealsopen", reveals_deadline)),
+        ),
+        AnnouncementPreview::new(
+            "Reveals closed",
+            formatter.create_closed_announcement(&data("revealsclosed", reveals_deadline)),
+        ),
+        AnnouncementPreview::new(
+            "Results",
+            formatter.create_results_announcement(&data("finished", reveals_deadline)),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_build_previews() {
+        let commitment_deadline = Utc.with_ymd_and_hms(2025, 4, 1, 20, 30, 0).unwrap();
+        let reveals_deadline = Utc.with_ymd_and_hms(2025, 4, 2, 20, 30, 0).unwrap();
+
+        let previews = build_previews(
+            7,
+            "https://youtube.com/live/abc",
+            commitment_deadline,
+            reveals_deadline,
+            Some(50.0),
+            DeadlineFormat::default(),
+            DEFAULT_CURRENCY_LABEL,
+        );
+
+        let phases: Vec<&str> = previews.iter().map(|p| p.phase).collect();
+        assert_eq!(
+            phases,
+            vec!["Commitments open", "Reveals open", "Reveals closed", "Results"]
+        );
+
+        let commitment = &previews[0].text;
+        assert!(commitment.contains("BLOCK 7 - Commitment Phase"));
+        assert!(commitment.contains("#commitmentsopen"));
+        assert!(commitment.contains("https://youtube.com/live/abc"));
+        assert!(commitment.contains("2025-04-01 | 16:30:00 | EDT"));
+        assert!(commitment.contains("(2025-04-01T20:30:00Z)"));
+
+        let reveals = &previews[1].text;
+        assert!(reveals.contains("BLOCK 7 - REVEAL PHASE"));
+        assert!(reveals.contains("#revealsopen"));
+        assert!(reveals.contains("2025-04-02 | 16:30:00 | EDT"));
+        assert!(reveals.contains("(2025-04-02T20:30:00Z)"));
+
+        assert!(previews[2].text.contains("Reveals are now closed"));
+        assert!(previews[2].text.contains("#revealsclosed"));
+
+        assert!(previews[3].text.contains("BLOCK 7 - RESULTS"));
+        assert!(previews[3].text.contains("Prize pool: 50 TAO"));
+
+        for preview in &previews {
+            assert_eq!(preview.weighted_length, weighted_tweet_length(&preview.text));
+        }
+        // With both the local and the UTC deadline the reveals instructions
+        // run past the tweet limit, which the preview reports
+        let too_long: Vec<&str> = previews
+            .iter()
+            .filter(|p| p.is_too_long())
+            .map(|p| p.phase)
+            .collect();
+        assert_eq!(too_long, vec!["Reveals open"]);
+    }
+}
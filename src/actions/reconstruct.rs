@@ -0,0 +1,386 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use clap::Parser;
+use colored::Colorize;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::block_engine::state_machine::parse_state_from_string;
+use crate::config::ConfigManager;
+use crate::error::{CliptionsError, Result};
+use crate::payout::DEFAULT_CURRENCY_LABEL;
+use crate::social::{parse_announcement_metadata, HashtagManager};
+use crate::types::{BlockData, BlockStatus};
+use twitter_api::{Tweet, TwitterApi, TwitterClient};
+
+#[derive(Parser)]
+pub struct ReconstructArgs {
+    /// Validator Twitter username whose announcements are replayed
+    #[arg(short, long)]
+    pub username: String,
+
+    /// Maximum number of recent tweets to scan
+    #[arg(long, default_value = "200")]
+    pub max_tweets: u32,
+
+    /// Path to write the rebuilt blocks.json to (default: data/blocks.json)
+    #[arg(long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Overwrite the blocks file if it already exists
+    #[arg(long)]
+    pub force: bool,
+
+    /// Print the reconstructed blocks without writing them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Config file path (default: config/config.yaml)
+    #[arg(long, default_value = "config/config.yaml")]
+    pub config: String,
+
+    /// Currency label the announcements quote prize pools in
+    #[arg(long, default_value = DEFAULT_CURRENCY_LABEL)]
+    pub currency_label: String,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// A validator tweet announcing a block state
+#[derive(Debug, Clone)]
+pub struct StateTweet {
+    pub block_num: String,
+    /// State name as returned by `parse_state_from_string`, e.g. "RevealsOpen"
+    pub state: String,
+    /// Deadline quoted in the announcement, if any
+    pub deadline: Option<DateTime<Utc>>,
+    /// Prize pool quoted in the announcement, if any
+    pub prize_pool: Option<f64>,
+    pub tweet: Tweet,
+}
+
+pub async fn run(args: ReconstructArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    if args.blocks_file.exists() && !args.force && !args.dry_run {
+        return Err(CliptionsError::ValidationError(format!(
+            "{} already exists; pass --force to overwrite it",
+            args.blocks_file.display()
+        )));
+    }
+
+    let config_manager = ConfigManager::with_path(&args.config)
+        .map_err(|e| format!("Failed to load config file: {}", e))?;
+    let twitter = &config_manager.get_config().twitter;
+    let client = TwitterClient::new(twitter_api::TwitterConfig {
+        api_key: twitter.api_key.clone(),
+        api_secret: twitter.api_secret.clone(),
+        access_token: twitter.access_token.clone(),
+        access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
+    });
+
+    println!(
+        "🔍 Scanning up to {} tweets from @{}...",
+        args.max_tweets, args.username
+    );
+    let tweets = client
+        .get_user_tweets(&args.username, args.max_tweets)
+        .await
+        .map_err(|e| CliptionsError::ApiError(format!("Failed to fetch tweets: {}", e)))?;
+
+    let blocks = reconstruct_blocks(&tweets, &args.currency_label);
+    if blocks.is_empty() {
+        println!(
+            "{} No block announcements found in {} tweets",
+            "Warning:".yellow().bold(),
+            tweets.len()
+        );
+        return Ok(());
+    }
+
+    for (block_num, block) in &blocks {
+        println!(
+            "{} block {}: {:?} (commitments until {}, reveals until {})",
+            "✅".green(),
+            block_num,
+            block.status,
+            block.commitment_deadline.format("%Y-%m-%d %H:%M:%S UTC"),
+            block.reveal_deadline.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    }
+
+    let content = serde_json::to_string_pretty(&blocks)?;
+    if args.dry_run {
+        println!("{}", content);
+        return Ok(());
+    }
+
+    if let Some(parent) = args.blocks_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&args.blocks_file, content)?;
+    println!(
+        "💾 Wrote {} block(s) to {}",
+        blocks.len(),
+        args.blocks_file.display()
+    );
+    println!("Participants, salts and guesses are not recoverable from announcements.");
+
+    Ok(())
+}
+
+/// Parse a validator tweet into its block number and announced state
+///
+/// Reads the metadata trailer when the tweet has one. Older tweets without
+/// it are parsed from their hashtags, and return `None` unless they have
+/// both a `#block<N>` and a state hashtag.
+///
+/// Prize pools are read when quoted in `currency_label`.
+pub fn parse_state_tweet(tweet: &Tweet, currency_label: &str) -> Option<StateTweet> {
+    if let Some(metadata) = parse_announcement_metadata(&tweet.text) {
+        return Some(StateTweet {
+            block_num: metadata.block_num.to_string(),
+            state: parse_state_from_string(&metadata.state)?,
+            deadline: metadata.deadline.or_else(|| parse_deadline(&tweet.text)),
+            prize_pool: parse_prize_pool(&tweet.text, currency_label),
+            tweet: tweet.clone(),
+        });
+    }
+
+    let hashtags = HashtagManager::new().extract_hashtags(&tweet.text);
+
+    let block_num = hashtags.iter().find_map(|h| {
+        let num = h.to_lowercase().strip_prefix("#block")?.to_string();
+        (!num.is_empty() && num.chars().all(|c| c.is_ascii_digit())).then_some(num)
+    })?;
+    let state = hashtags
+        .iter()
+        .find_map(|h| parse_state_from_string(&h[1..]))?;
+
+    Some(StateTweet {
+        block_num,
+        state,
+        deadline: parse_deadline(&tweet.text),
+        prize_pool: parse_prize_pool(&tweet.text, currency_label),
+        tweet: tweet.clone(),
+    })
+}
+
+/// Rebuild block skeletons from a validator's announcement tweets
+///
+/// Each block takes its status from the most recent state announced for it,
+/// the commitment deadline from its `#commitmentsopen` tweet and the reveal
+/// deadline from its `#revealsopen` tweet. A deadline that was never
+/// announced falls back to the time of the block's latest tweet.
+pub fn reconstruct_blocks(tweets: &[Tweet], currency_label: &str) -> BTreeMap<String, BlockData> {
+    let mut by_block: BTreeMap<String, Vec<StateTweet>> = BTreeMap::new();
+    for state_tweet in tweets
+        .iter()
+        .filter_map(|tweet| parse_state_tweet(tweet, currency_label))
+    {
+        by_block
+            .entry(state_tweet.block_num.clone())
+            .or_default()
+            .push(state_tweet);
+    }
+
+    by_block
+        .into_iter()
+        .map(|(block_num, mut states)| {
+            states.sort_by_key(|s| tweet_order_key(&s.tweet));
+            let block = build_block(block_num.clone(), &states);
+            (block_num, block)
+        })
+        .collect()
+}
+
+/// Build one block from its announcements, ordered oldest first
+fn build_block(block_num: String, states: &[StateTweet]) -> BlockData {
+    let find = |state: &str| states.iter().rev().find(|s| s.state == state);
+    let first = &states[0];
+    let latest = &states[states.len() - 1];
+    let first_seen = first.tweet.created_at.unwrap_or_else(Utc::now);
+    let last_seen = latest.tweet.created_at.unwrap_or(first_seen);
+
+    let commitments = find("CommitmentsOpen");
+    let commitment_deadline = commitments.and_then(|s| s.deadline);
+    let reveal_deadline = find("RevealsOpen").and_then(|s| s.deadline);
+    let social_id = commitments.unwrap_or(first).tweet.id.clone();
+    let prize_pool = states.iter().rev().find_map(|s| s.prize_pool).unwrap_or(0.0);
+
+    let mut block = BlockData::with_deadlines(
+        block_num,
+        String::new(),
+        social_id,
+        prize_pool,
+        commitment_deadline.unwrap_or(last_seen),
+        reveal_deadline.unwrap_or(last_seen),
+    );
+    block.status = status_for_state(&latest.state);
+    block.created_at = first_seen;
+    block.updated_at = last_seen;
+    block
+}
+
+/// Map an announced state onto the stored block status
+fn status_for_state(state: &str) -> BlockStatus {
+    match state {
+        "RevealsClosed" | "Payouts" => BlockStatus::Processing,
+        "Finished" => BlockStatus::Complete,
+        _ => BlockStatus::Open,
+    }
+}
+
+/// Order tweets by creation time, then by id (ids increase over time)
+fn tweet_order_key(tweet: &Tweet) -> (Option<DateTime<Utc>>, u64) {
+    (tweet.created_at, tweet.id.parse().unwrap_or(0))
+}
+
+/// Parse the first deadline in an announcement
+///
+/// Reads the UTC timestamp from "2025-04-01 | 16:30:57 | EDT (2025-04-01T20:30:57Z)",
+/// falling back to the older "2025-04-01 | 16:30:57 | EST" form without one.
+fn parse_deadline(text: &str) -> Option<DateTime<Utc>> {
+    let utc_regex = Regex::new(r"\((\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z)\)").unwrap();
+    if let Some(captures) = utc_regex.captures(text) {
+        return DateTime::parse_from_rfc3339(&captures[1])
+            .ok()
+            .map(|time| time.with_timezone(&Utc));
+    }
+
+    let deadline_regex =
+        Regex::new(r"(\d{4}-\d{2}-\d{2}) \| (\d{2}:\d{2}:\d{2}) \| EST").unwrap();
+    let captures = deadline_regex.captures(text)?;
+    let naive = NaiveDateTime::parse_from_str(
+        &format!("{} {}", &captures[1], &captures[2]),
+        "%Y-%m-%d %H:%M:%S",
+    )
+    .ok()?;
+    chrono_tz::US::Eastern
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Parse "Prize pool: 50 TAO" (for `currency_label` "TAO") from a results announcement
+fn parse_prize_pool(text: &str, currency_label: &str) -> Option<f64> {
+    let prize_regex = Regex::new(&format!(
+        r"Prize pool: ([0-9.]+) {}\b",
+        regex::escape(currency_label)
+    ))
+    .unwrap();
+    prize_regex.captures(text)?[1].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::preview_announcements::build_previews;
+    use crate::social::DeadlineFormat;
+
+    fn tweet(id: &str, minutes: i64, text: String) -> Tweet {
+        Tweet {
+            id: id.to_string(),
+            text,
+            author_id: "validator".to_string(),
+            created_at: Some(
+                Utc.with_ymd_and_hms(2025, 4, 1, 12, 0, 0).unwrap()
+                    + chrono::Duration::minutes(minutes),
+            ),
+            conversation_id: Some(id.to_string()),
+            public_metrics: None,
+            url: format!("https://twitter.com/validator/status/{}", id),
+            in_reply_to_user_id: None,
+            referenced_tweets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_reconstruct_blocks() {
+        let commitment_deadline = Utc.with_ymd_and_hms(2025, 4, 1, 20, 30, 0).unwrap();
+        let reveals_deadline = Utc.with_ymd_and_hms(2025, 4, 2, 20, 30, 0).unwrap();
+        let block_1 = build_previews(
+            1,
+            "https://youtube.com/live/a",
+            commitment_deadline,
+            reveals_deadline,
+            Some(50.0),
+            DeadlineFormat::default(),
+            DEFAULT_CURRENCY_LABEL,
+        );
+        let block_2 = build_previews(
+            2,
+            "https://youtube.com/live/b",
+            commitment_deadline,
+            reveals_deadline,
+            None,
+            DeadlineFormat::new(chrono_tz::Europe::London),
+            DEFAULT_CURRENCY_LABEL,
+        );
+
+        // Newest first, as the timeline endpoint returns them
+        let tweets = vec![
+            tweet("106", 60, block_2[0].text.clone()),
+            tweet("105", 50, "gm, no hashtags here".to_string()),
+            tweet("104", 40, block_1[3].text.clone()),
+            tweet("103", 30, block_1[2].text.clone()),
+            tweet("102", 20, block_1[1].text.clone()),
+            tweet("101", 10, block_1[0].text.clone()),
+        ];
+
+        let blocks = reconstruct_blocks(&tweets, DEFAULT_CURRENCY_LABEL);
+        assert_eq!(blocks.keys().collect::<Vec<_>>(), vec!["1", "2"]);
+
+        let finished = &blocks["1"];
+        assert_eq!(finished.status, BlockStatus::Complete);
+        assert_eq!(finished.social_id, "101");
+        assert_eq!(finished.prize_pool, 50.0);
+        assert_eq!(finished.commitment_deadline, commitment_deadline);
+        assert_eq!(finished.reveal_deadline, reveals_deadline);
+        assert_eq!(finished.created_at, tweets[5].created_at.unwrap());
+        assert_eq!(finished.updated_at, tweets[2].created_at.unwrap());
+        assert!(finished.participants.is_empty());
+
+        let open = &blocks["2"];
+        assert_eq!(open.status, BlockStatus::Open);
+        assert_eq!(open.social_id, "106");
+        assert_eq!(open.commitment_deadline, commitment_deadline);
+        // No reveals announcement yet
+        assert_eq!(open.reveal_deadline, tweets[0].created_at.unwrap());
+    }
+
+    #[test]
+    fn test_parse_state_tweet() {
+        let parse = |id: &str, text: &str| {
+            parse_state_tweet(&tweet(id, 0, text.to_string()), DEFAULT_CURRENCY_LABEL)
+        };
+        let parsed = parse("1", "#cliptions #block12 #revealsclosed\n\nBLOCK 12").unwrap();
+        assert_eq!(parsed.block_num, "12");
+        assert_eq!(parsed.state, "RevealsClosed");
+        assert_eq!(parsed.deadline, None);
+
+        // Announcements from before deadlines carried a UTC timestamp
+        let legacy =
+            parse("4", "#block12 #revealsopen\n\nDeadline: 2025-04-01 | 16:30:00 | EST").unwrap();
+        assert_eq!(
+            legacy.deadline,
+            Some(Utc.with_ymd_and_hms(2025, 4, 1, 20, 30, 0).unwrap())
+        );
+
+        assert!(parse("2", "#block12 no state").is_none());
+        assert!(parse("3", "#blockchain #finished").is_none());
+
+        // Prize pools are only read in the validator's currency
+        let results = tweet("5", 0, "#block12 #finished\n\nPrize pool: 25 USDC\n".to_string());
+        assert_eq!(parse_state_tweet(&results, "USDC").unwrap().prize_pool, Some(25.0));
+        assert_eq!(
+            parse_state_tweet(&results, DEFAULT_CURRENCY_LABEL).unwrap().prize_pool,
+            None
+        );
+    }
+}
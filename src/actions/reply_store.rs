@@ -0,0 +1,201 @@
+//! Storage shared by the incremental reply collectors
+//!
+//! A collector keeps the newest tweet ID it has seen for each block in a
+//! cursors file, and what it collected for each block in a file of that
+//! block's own, so a run for one block never touches another block's results.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use twitter_api::{SearchResult, SearchScope, TwitterApi};
+
+use crate::error::{CliptionsError, Result};
+
+/// Directory under the validator data directory holding collected commitments, one file per block
+pub(crate) const COMMITMENTS_DIR: &str = "collected_commitments";
+
+/// Directory under the validator data directory holding collected reveals, one file per block
+pub(crate) const REVEALS_DIR: &str = "collected_reveals";
+
+/// Default validator data directory: `~/.cliptions/validator`
+pub(crate) fn validator_data_dir() -> Result<PathBuf> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| "Could not determine home directory".to_string())?;
+    Ok(home_dir.join(".cliptions").join("validator"))
+}
+
+/// File holding what was collected for `block_num` in `results_dir`
+pub(crate) fn block_results_path(results_dir: &Path, block_num: &str) -> Result<PathBuf> {
+    let is_plain_name = !block_num.is_empty()
+        && block_num != "."
+        && block_num != ".."
+        && !block_num.contains(['/', '\\']);
+    if !is_plain_name {
+        return Err(CliptionsError::ValidationError(format!(
+            "Invalid block number for a results file: {:?}",
+            block_num
+        )));
+    }
+    Ok(results_dir.join(format!("{}.json", block_num)))
+}
+
+/// Cursors and per-block results of one collector
+pub(crate) struct ReplyStore {
+    data_dir: PathBuf,
+    cursors_file: &'static str,
+    results_dir: &'static str,
+}
+
+impl ReplyStore {
+    /// Store cursors in `data_dir/cursors_file` and results under `data_dir/results_dir`
+    pub(crate) fn new(
+        data_dir: PathBuf,
+        cursors_file: &'static str,
+        results_dir: &'static str,
+    ) -> Self {
+        Self {
+            data_dir,
+            cursors_file,
+            results_dir,
+        }
+    }
+
+    /// Path of the per-block cursor file
+    pub(crate) fn cursors_path(&self) -> PathBuf {
+        self.data_dir.join(self.cursors_file)
+    }
+
+    /// Path of the results file for a block
+    pub(crate) fn results_path(&self, block_num: &str) -> Result<PathBuf> {
+        block_results_path(&self.data_dir.join(self.results_dir), block_num)
+    }
+
+    /// Newest tweet ID seen for a block, if it has been collected before
+    pub(crate) fn cursor(&self, block_num: &str) -> Result<Option<String>> {
+        Ok(self.load_cursors()?.remove(block_num))
+    }
+
+    /// Results stored for a block, if any
+    pub(crate) fn load_results<T: DeserializeOwned>(&self, block_num: &str) -> Result<Option<T>> {
+        let path = self.results_path(block_num)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    /// Search for replies to `tweet_id` newer than the block's cursor, oldest first
+    pub(crate) async fn fetch<C: TwitterApi>(
+        &self,
+        client: &C,
+        block_num: &str,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+    ) -> Result<SearchResult> {
+        let since_id = self.cursor(block_num)?;
+        let mut search = client
+            .search_replies_paged(tweet_id, max_results, scope, since_id, None)
+            .await
+            .map_err(|e| CliptionsError::ApiError(e.to_string()))?;
+        search
+            .tweets
+            .sort_by_key(|reply| reply.id.parse::<u64>().unwrap_or(0));
+        Ok(search)
+    }
+
+    /// Save a block's merged results, then move its cursor to `newest_id`
+    ///
+    /// A partial search has no `newest_id`; its results are still saved, but
+    /// the cursor stays put so the next run fetches the missed pages again.
+    pub(crate) fn save<T: Serialize>(
+        &self,
+        block_num: &str,
+        results: &T,
+        newest_id: Option<String>,
+    ) -> Result<()> {
+        let results_path = self.results_path(block_num)?;
+        if let Some(dir) = results_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        write_json(&results_path, results)?;
+
+        if let Some(newest_id) = newest_id {
+            let mut cursors = self.load_cursors()?;
+            cursors.insert(block_num.to_string(), newest_id);
+            write_json(&self.cursors_path(), &cursors)?;
+        }
+        Ok(())
+    }
+
+    fn load_cursors(&self) -> Result<BTreeMap<String, String>> {
+        let path = self.cursors_path();
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use twitter_api::testing::InMemoryTwitterApi;
+
+    #[test]
+    fn test_block_results_path_rejects_path_like_block_numbers() {
+        let dir = Path::new("results");
+        assert_eq!(
+            block_results_path(dir, "42").unwrap(),
+            PathBuf::from("results/42.json")
+        );
+        for block_num in ["", ".", "..", "../1", "a/b", "a\\b"] {
+            assert!(block_results_path(dir, block_num).is_err(), "{:?}", block_num);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_results_and_cursors_are_kept_per_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ReplyStore::new(temp_dir.path().join("validator"), "cursors.json", "results");
+        let api = InMemoryTwitterApi::new();
+        let root = api.post_tweet("#block1").await.unwrap().tweet;
+        let first = api.seed_reply(&root.id, "42", "first").unwrap();
+        let second = api.seed_reply(&root.id, "43", "second").unwrap();
+
+        // Replies come back oldest first
+        let search = store
+            .fetch(&api, "1", &root.id, 100, SearchScope::Recent)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = search.tweets.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec![first.id.as_str(), second.id.as_str()]);
+
+        store.save("1", &vec!["one"], search.newest_id).unwrap();
+        store.save("2", &vec!["two"], None).unwrap();
+
+        assert_eq!(store.load_results::<Vec<String>>("1").unwrap().unwrap(), vec!["one"]);
+        assert_eq!(store.load_results::<Vec<String>>("2").unwrap().unwrap(), vec!["two"]);
+        assert!(store.load_results::<Vec<String>>("3").unwrap().is_none());
+        assert_eq!(store.cursor("1").unwrap(), Some(second.id.clone()));
+        assert_eq!(store.cursor("2").unwrap(), None);
+
+        // The next fetch only sees replies newer than the cursor
+        let third = api.seed_reply(&root.id, "44", "third").unwrap();
+        let search = store
+            .fetch(&api, "1", &root.id, 100, SearchScope::Recent)
+            .await
+            .unwrap();
+        assert_eq!(search.tweets.len(), 1);
+        assert_eq!(search.tweets[0].id, third.id);
+    }
+}
@@ -0,0 +1,79 @@
+use clap::Parser;
+use colored::Colorize;
+use std::fs;
+use std::path::PathBuf;
+use crate::block_processor::BlockProcessor;
+use crate::embedder::MockEmbedder;
+use crate::error::Result;
+use crate::payout::PayoutConfig;
+use crate::report_html::render_html;
+use crate::scoring::ClipBatchStrategy;
+
+#[derive(Parser)]
+pub struct RoundReportArgs {
+    /// Block to report on
+    #[arg(short, long)]
+    pub block_num: String,
+
+    /// Path to blocks.json file (default: data/blocks.json)
+    #[arg(short = 'f', long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Report format: json, or html for a self-contained page to share
+    #[arg(long, default_value = "json", value_parser = ["json", "html"])]
+    pub output: String,
+
+    /// File to write the report to (default: round_report_<block>.<format>)
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Percentage of the prize pool kept as the validator fee
+    #[arg(long, default_value = "0")]
+    pub validator_fee_percent: f64,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+pub fn run(args: RoundReportArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    // The report only reads stored results, so the embedder is never used
+    let mut processor = BlockProcessor::new(
+        args.blocks_file.to_string_lossy().to_string(),
+        MockEmbedder::clip_like(),
+        ClipBatchStrategy::new(),
+    )
+    .with_payout_config(PayoutConfig {
+        validator_fee_percent: args.validator_fee_percent,
+        ..PayoutConfig::default()
+    })?;
+    let report = processor.generate_report(&args.block_num)?;
+
+    let output_file = args.output_file.clone().unwrap_or_else(|| {
+        PathBuf::from(format!("round_report_{}.{}", args.block_num, args.output))
+    });
+    let contents = match args.output.as_str() {
+        "html" => render_html(&report),
+        _ => serde_json::to_string_pretty(&report)?,
+    };
+    fs::write(&output_file, contents)?;
+
+    let ranked = report.participants.iter().filter(|p| p.rank.is_some()).count();
+    println!(
+        "{} Round report for block {} ({} participants, {} ranked) written to {}",
+        "Success:".green().bold(),
+        args.block_num,
+        report.participants.len(),
+        ranked,
+        output_file.display()
+    );
+    if ranked == 0 {
+        println!("{} Block {} has no stored scores yet", "Note:".yellow().bold(), args.block_num);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+use tokio::net::TcpListener;
+use crate::block_processor::BlockProcessor;
+use crate::embedder::MockEmbedder;
+use crate::error::Result;
+use crate::scoring::ClipBatchStrategy;
+use crate::status_server;
+
+#[derive(Parser)]
+pub struct ServeStatusArgs {
+    /// Path to blocks.json file (default: data/blocks.json)
+    #[arg(short = 'f', long, default_value = "data/blocks.json")]
+    pub blocks_file: PathBuf,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8787")]
+    pub bind: String,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+pub async fn run(args: ServeStatusArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    // The server only reads stored blocks, so the embedder is never used
+    let processor = BlockProcessor::new(
+        args.blocks_file.to_string_lossy().to_string(),
+        MockEmbedder::clip_like(),
+        ClipBatchStrategy::new(),
+    );
+    let listener = TcpListener::bind(&args.bind).await?;
+    println!(
+        "{} Serving {} at http://{} (GET /blocks, GET /blocks/:num)",
+        "Status:".green().bold(),
+        args.blocks_file.display(),
+        listener.local_addr()?
+    );
+    status_server::serve(listener, processor).await
+}
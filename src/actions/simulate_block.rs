@@ -0,0 +1,342 @@
+use chrono::{Duration, Utc};
+use clap::Parser;
+use colored::Colorize;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use crate::block_engine::dry_run::{DryRunTwitterClient, PostedTweet};
+use crate::block_engine::frame::{FrameProvider, MockFrameProvider};
+use crate::block_engine::state_machine::Block;
+use crate::clock::MockClock;
+use crate::commitment::CommitmentGenerator;
+use crate::embedder::MockEmbedder;
+use crate::error::{CliptionsError, Result};
+use crate::payout::{PayoutCalculator, PayoutConfig, PayoutInfo};
+use crate::scoring::{ClipBatchStrategy, ScoreValidator};
+use crate::types::{Guess, Participant};
+
+#[derive(Parser)]
+pub struct SimulateBlockArgs {
+    /// Scenario file (JSON) describing the block and its participants
+    #[arg(long)]
+    pub scenario: PathBuf,
+
+    /// Image path the mock frame provider returns as the captured target frame
+    #[arg(long, default_value = "simulated_frame.jpg")]
+    pub frame_path: PathBuf,
+
+    /// Disable colored output
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+/// A block to simulate, read from a scenario file
+///
+/// ```json
+/// {
+///   "block_num": "1",
+///   "prize_pool": 100.0,
+///   "participants": [
+///     { "username": "alice", "guess": "a red sunset over the sea" },
+///     { "username": "bob", "guess": "a city at night", "revealed_guess": "a forest" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    #[serde(default = "default_block_num")]
+    pub block_num: String,
+    #[serde(default = "default_prize_pool")]
+    pub prize_pool: f64,
+    #[serde(default = "default_livestream_url")]
+    pub livestream_url: String,
+    pub participants: Vec<ScenarioParticipant>,
+}
+
+/// A participant's commitment and reveal in a scenario
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScenarioParticipant {
+    pub username: String,
+    /// Guess the participant commits to
+    pub guess: String,
+    /// Salt for the commitment; generated if omitted
+    #[serde(default)]
+    pub salt: Option<String>,
+    #[serde(default)]
+    pub wallet: Option<String>,
+    /// Guess revealed after the frame is posted, if different from the committed one
+    #[serde(default)]
+    pub revealed_guess: Option<String>,
+}
+
+fn default_block_num() -> String {
+    "1".to_string()
+}
+
+fn default_prize_pool() -> f64 {
+    100.0
+}
+
+fn default_livestream_url() -> String {
+    "https://www.twitch.tv/cliptions".to_string()
+}
+
+impl Scenario {
+    /// Read a scenario from a JSON file
+    pub fn load(path: &std::path::Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read scenario file {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Everything a simulated block produced
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    pub block_num: String,
+    /// Every tweet posted, by the validator and the participants, in order
+    pub tweets: Vec<PostedTweet>,
+    /// Payouts for participants whose reveal matched their commitment, best first
+    pub payouts: Vec<PayoutInfo>,
+    /// Participants whose reveal didn't match their commitment
+    pub rejected: Vec<String>,
+    /// State the block ended in
+    pub final_state: &'static str,
+}
+
+impl SimulationReport {
+    /// The first-ranked participant, if anyone was paid
+    pub fn winner(&self) -> Option<&PayoutInfo> {
+        self.payouts.iter().find(|p| p.rank == 1)
+    }
+}
+
+pub async fn run(args: SimulateBlockArgs) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    let scenario = Scenario::load(&args.scenario)?;
+    let report = simulate_block(&scenario, &MockFrameProvider::new(&args.frame_path)).await?;
+
+    for tweet in &report.tweets {
+        let heading = match &tweet.reply_to {
+            Some(reply_to) => format!("=== #{} @{} (reply to #{}) ===", tweet.id, tweet.author, reply_to),
+            None => format!("=== #{} @{} ===", tweet.id, tweet.author),
+        };
+        println!("{}", heading.bold());
+        println!("{}", tweet.text);
+        if let Some(image_path) = &tweet.image_path {
+            println!("[image: {}]", image_path.display());
+        }
+        println!();
+    }
+
+    for username in &report.rejected {
+        println!("{} {}'s reveal does not match their commitment", "Rejected:".yellow().bold(), username);
+    }
+
+    println!("{}", format!("=== Payouts for block {} ===", report.block_num).bold());
+    for payout in &report.payouts {
+        println!(
+            "{:>2}. {:<16} {:>10.4}  (score {:.4}) \"{}\"",
+            payout.rank, payout.username, payout.payout, payout.score, payout.guess
+        );
+    }
+    if let Some(winner) = report.winner() {
+        println!("{} {}", "Winner:".green().bold(), winner.username);
+    }
+    println!("Block finished in state {}", report.final_state);
+
+    Ok(())
+}
+
+/// Run a block through its whole lifecycle without touching the network
+///
+/// Announcements go to a `DryRunTwitterClient`, each participant's commitment
+/// and reveal are posted as replies, the target frame comes from
+/// `frame_provider`, and guesses are scored with `MockEmbedder`.
+pub async fn simulate_block(
+    scenario: &Scenario,
+    frame_provider: &dyn FrameProvider,
+) -> Result<SimulationReport> {
+    let client = DryRunTwitterClient::new();
+    let generator = CommitmentGenerator::new();
+
+    let now = Utc::now();
+    let commitment_deadline = now + Duration::hours(1);
+    let target_timestamp = now + Duration::hours(2);
+    let reveals_deadline = now + Duration::hours(3);
+    let clock = MockClock::new(now);
+
+    // Commitments
+    let block = Block::new(
+        scenario.block_num.clone(),
+        format!("Simulated block {}", scenario.block_num),
+        scenario.livestream_url.clone(),
+        target_timestamp,
+    )
+    .open_commitments(commitment_deadline, &client)
+    .await?;
+    let announcement_id = last_tweet_id(&client)?;
+
+    let mut participants = Vec::new();
+    for entry in &scenario.participants {
+        let salt = entry.salt.clone().unwrap_or_else(|| generator.generate_salt());
+        let commitment = generator.generate(&entry.guess, &salt)?;
+        let wallet = entry
+            .wallet
+            .clone()
+            .unwrap_or_else(|| format!("wallet_{}", entry.username));
+        client.add_reply(
+            &entry.username,
+            &format!("Commit: {}\nWallet: {}", commitment, wallet),
+            &announcement_id,
+        );
+        participants.push((entry, salt, commitment, wallet));
+    }
+    let block = block.close_commitments(&client).await?;
+
+    // Target frame
+    clock.set(target_timestamp);
+    let frame_path = frame_provider.capture(&scenario.livestream_url, target_timestamp)?;
+    let block = block
+        .capture_frame(frame_path.clone(), &clock)?
+        .open_reveals(reveals_deadline, &client, &announcement_id)
+        .await?;
+    let frame_tweet_id = last_tweet_id(&client)?;
+
+    // Reveals
+    let mut verified = Vec::new();
+    let mut rejected = Vec::new();
+    for (entry, salt, commitment, wallet) in participants {
+        let revealed = entry.revealed_guess.as_deref().unwrap_or(&entry.guess);
+        client.add_reply(
+            &entry.username,
+            &format!("Guess: {}\nSalt: {}", revealed, salt),
+            &frame_tweet_id,
+        );
+
+        let mut participant = Participant::new(
+            format!("user_{}", entry.username),
+            entry.username.clone(),
+            Guess::new(revealed.to_string()),
+            commitment,
+        )
+        .with_salt(salt);
+        participant.wallet = wallet;
+        if participant.verify_and_mark() {
+            verified.push(participant);
+        } else {
+            rejected.push(entry.username.clone());
+        }
+    }
+    let block = block.close_reveals(&client).await?;
+
+    // Scoring and payouts
+    let payouts = if verified.is_empty() {
+        Vec::new()
+    } else {
+        let validator = ScoreValidator::new(MockEmbedder::clip_like(), ClipBatchStrategy::new());
+        let guesses: Vec<String> = verified.iter().map(|p| p.guess.text.clone()).collect();
+        let scores = validator.calculate_batch_similarities(&frame_path.to_string_lossy(), &guesses)?;
+        let participant_scores: Vec<(Participant, f64)> = verified.into_iter().zip(scores).collect();
+
+        PayoutCalculator::with_config(PayoutConfig {
+            prize_pool: scenario.prize_pool,
+            ..PayoutConfig::default()
+        })
+        .process_payouts_with_scores(&participant_scores)?
+    };
+    let block = block.process_payouts(&client).await?;
+
+    Ok(SimulationReport {
+        block_num: scenario.block_num.clone(),
+        tweets: client.posted(),
+        payouts,
+        rejected,
+        final_state: block.state_name(),
+    })
+}
+
+fn last_tweet_id(client: &DryRunTwitterClient) -> Result<String> {
+    client
+        .posted()
+        .last()
+        .map(|tweet| tweet.id.clone())
+        .ok_or_else(|| CliptionsError::ValidationError("No tweet was posted".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_engine::dry_run::DRY_RUN_USERNAME;
+
+    fn scenario(json: &str) -> Scenario {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_simulate_two_participants() {
+        let scenario = scenario(
+            r#"{
+                "block_num": "7",
+                "prize_pool": 50.0,
+                "participants": [
+                    { "username": "alice", "guess": "a red sunset over the sea" },
+                    { "username": "bob", "guess": "a city skyline at night", "salt": "pepper" }
+                ]
+            }"#,
+        );
+
+        let report = simulate_block(&scenario, &MockFrameProvider::new("frame.jpg"))
+            .await
+            .unwrap();
+
+        assert_eq!(report.final_state, "Finished");
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.payouts.len(), 2);
+        let winner = report.winner().unwrap();
+        assert!(["alice", "bob"].contains(&winner.username.as_str()));
+        let total: f64 = report.payouts.iter().map(|p| p.payout).sum();
+        assert!((total - 50.0).abs() < 1e-9);
+
+        // Three announcements from the validator plus a commitment and a reveal each
+        let validator_tweets: Vec<_> = report
+            .tweets
+            .iter()
+            .filter(|t| t.author == DRY_RUN_USERNAME)
+            .collect();
+        assert_eq!(validator_tweets.len(), 3);
+        assert_eq!(report.tweets.len(), 7);
+        assert_eq!(
+            validator_tweets[2].image_path.as_deref(),
+            Some(std::path::Path::new("frame.jpg"))
+        );
+        assert!(report
+            .tweets
+            .iter()
+            .any(|t| t.author == "bob" && t.text.contains("Salt: pepper")));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_rejects_mismatched_reveal() {
+        let scenario = scenario(
+            r#"{
+                "participants": [
+                    { "username": "alice", "guess": "a red sunset" },
+                    { "username": "bob", "guess": "a city at night" },
+                    { "username": "carol", "guess": "a forest", "revealed_guess": "a desert" }
+                ]
+            }"#,
+        );
+
+        let report = simulate_block(&scenario, &MockFrameProvider::new("frame.jpg"))
+            .await
+            .unwrap();
+
+        assert_eq!(report.rejected, vec!["carol".to_string()]);
+        assert_eq!(report.payouts.len(), 2);
+        assert!(report.payouts.iter().all(|p| p.username != "carol"));
+    }
+}
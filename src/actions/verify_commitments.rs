@@ -2,10 +2,16 @@ use clap::Parser;
 use colored::Colorize;
 use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use crate::actions::reply_store::{
+    block_results_path, validator_data_dir, COMMITMENTS_DIR, REVEALS_DIR,
+};
 use crate::config::ConfigManager;
 use crate::error::Result;
 use crate::commitment::CommitmentGenerator;
+use crate::embedder::{cosine_similarity, ClipEmbedder, EmbedderTrait};
+use crate::payout::DEFAULT_CURRENCY_LABEL;
+use crate::types::{BlockData, Guess, Participant};
 use serde_json::json;
 
 #[derive(Parser)]
@@ -14,11 +20,13 @@ pub struct VerifyCommitmentsArgs {
     #[arg(short, long)]
     pub block_tweet_id: String,
 
-    /// Path to collected commitments file (default: ~/.cliptions/validator/collected_commitments.json)
+    /// Path to collected commitments file (default: ~/.cliptions/validator/collected_commitments/<block>.json
+    /// for --block-num when collected incrementally, else ~/.cliptions/validator/collected_commitments.json)
     #[arg(long)]
     pub commitments_file: Option<PathBuf>,
 
-    /// Path to collected reveals file (default: ~/.cliptions/validator/collected_reveals.json)
+    /// Path to collected reveals file (default: ~/.cliptions/validator/collected_reveals/<block>.json
+    /// for --block-num when collected incrementally, else ~/.cliptions/validator/collected_reveals.json)
     #[arg(long)]
     pub reveals_file: Option<PathBuf>,
 
@@ -45,6 +53,27 @@ pub struct VerifyCommitmentsArgs {
     /// Block ID to save results under (e.g., "block4")
     #[arg(long)]
     pub block_num: Option<String>,
+
+    /// Currency label participants' payouts are recorded in when saving to the blocks file
+    #[arg(long, default_value = DEFAULT_CURRENCY_LABEL)]
+    pub currency_label: String,
+
+    /// Report salts revealed for more than one commitment, across every block in the reveals file
+    #[arg(long)]
+    pub check_salt_reuse: bool,
+
+    /// Flag revealed guesses whose CLIP cosine similarity to the target image is below this
+    /// (e.g. 0.15); flagged guesses keep their commitment validity
+    #[arg(long)]
+    pub min_clip_score: Option<f64>,
+
+    /// Target image for --min-clip-score (default: the target image of --block-num in the blocks file)
+    #[arg(long)]
+    pub target_image: Option<PathBuf>,
+
+    /// CLIP model directory (default: $CLIPTIONS_MODEL_DIR or ~/.cliptions/models)
+    #[arg(long)]
+    pub clip_model: Option<PathBuf>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -59,6 +88,12 @@ struct VerificationResult {
     verification_error: Option<String>,
     commitment_tweet_url: String,
     reveal_tweet_url: String,
+    /// Cosine similarity of the guess to the target image, when checked
+    #[serde(default)]
+    clip_score: Option<f64>,
+    /// Whether `clip_score` fell below the minimum; says nothing about commitment validity
+    #[serde(default)]
+    below_min_clip_score: bool,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -67,6 +102,10 @@ struct VerificationResults {
     total_participants: usize,
     valid_commitments: usize,
     invalid_commitments: usize,
+    #[serde(default)]
+    min_clip_score: Option<f64>,
+    #[serde(default)]
+    below_min_clip_score: usize,
     results: Vec<VerificationResult>,
     verification_timestamp: String,
 }
@@ -86,14 +125,26 @@ pub async fn run(args: VerifyCommitmentsArgs) -> Result<()> {
         .map_err(|e| format!("Failed to load config file: {}", e))?;
     
     // Determine file paths
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Could not determine home directory".to_string())?;
-    let cliptions_dir = home_dir.join(".cliptions").join("validator");
-    
-    let commitments_path = args.commitments_file.clone().unwrap_or_else(|| 
-        cliptions_dir.join("collected_commitments.json"));
-    let reveals_path = args.reveals_file.clone().unwrap_or_else(|| 
-        cliptions_dir.join("collected_reveals.json"));
+    let cliptions_dir = validator_data_dir()?;
+
+    let commitments_path = match &args.commitments_file {
+        Some(path) => path.clone(),
+        None => default_collected_path(
+            &cliptions_dir,
+            COMMITMENTS_DIR,
+            "collected_commitments.json",
+            args.block_num.as_deref(),
+        )?,
+    };
+    let reveals_path = match &args.reveals_file {
+        Some(path) => path.clone(),
+        None => default_collected_path(
+            &cliptions_dir,
+            REVEALS_DIR,
+            "collected_reveals.json",
+            args.block_num.as_deref(),
+        )?,
+    };
 
     // Load commitments and reveals
     let commitments = load_commitments(&commitments_path, &args.block_tweet_id)?;
@@ -132,17 +183,34 @@ pub async fn run(args: VerifyCommitmentsArgs) -> Result<()> {
                 verification_error: Some("No reveal found".to_string()),
                 commitment_tweet_url: commitment.tweet_url.clone(),
                 reveal_tweet_url: "".to_string(),
+                clip_score: None,
+                below_min_clip_score: false,
             };
             invalid_count += 1;
             verification_results.push(verification_result);
         }
     }
 
+    // Plausibility of each guess, reported alongside commitment validity
+    let mut below_min_clip_score = 0;
+    if let Some(min_score) = args.min_clip_score {
+        let target_image = resolve_target_image(&args)?;
+        let embedder = clip_score_embedder(&args)?;
+        below_min_clip_score = flag_low_clip_scores(
+            &mut verification_results,
+            &embedder,
+            &target_image.to_string_lossy(),
+            min_score,
+        )?;
+    }
+
     let results = VerificationResults {
         block_tweet_id: args.block_tweet_id.clone(),
         total_participants: verification_results.len(),
         valid_commitments: valid_count,
         invalid_commitments: invalid_count,
+        min_clip_score: args.min_clip_score,
+        below_min_clip_score,
         results: verification_results,
         verification_timestamp: chrono::Utc::now().to_rfc3339(),
     };
@@ -150,9 +218,14 @@ pub async fn run(args: VerifyCommitmentsArgs) -> Result<()> {
     // Display results
     display_verification_results(&results, &args)?;
 
+    if args.check_salt_reuse {
+        let all_reveals = load_all_reveals(&reveals_path)?;
+        display_salt_reuse(&find_salt_reuse(&all_reveals), all_reveals.len());
+    }
+
     // Save to blocks.json if block_num is provided
     if let Some(block_num) = &args.block_num {
-        save_to_blocks_json(&results, &args.blocks_file, block_num)?;
+        save_to_blocks_json(&results, &args.blocks_file, block_num, &args.currency_label)?;
         
         if args.verbose {
             println!("✅ Verification results saved to {} under block '{}'", 
@@ -183,16 +256,9 @@ fn load_commitments(path: &PathBuf, block_tweet_id: &str) -> Result<BTreeMap<Str
 }
 
 fn load_reveals(path: &PathBuf, block_tweet_id: &str) -> Result<BTreeMap<String, CollectedRevealData>> {
-    if !path.exists() {
-        return Err("Reveals file not found".to_string().into());
-    }
-
-    let content = fs::read_to_string(path)?;
-    let results: CollectedRevealsResults = serde_json::from_str(&content)?;
-    
     // Filter by block
     let mut reveals = BTreeMap::new();
-    for reveal in results.reveals {
+    for reveal in load_all_reveals(path)? {
         if reveal.conversation_id.as_deref() == Some(block_tweet_id) {
             reveals.insert(reveal.author_id.clone(), reveal);
         }
@@ -201,13 +267,23 @@ fn load_reveals(path: &PathBuf, block_tweet_id: &str) -> Result<BTreeMap<String,
     Ok(reveals)
 }
 
+fn load_all_reveals(path: &PathBuf) -> Result<Vec<CollectedRevealData>> {
+    if !path.exists() {
+        return Err("Reveals file not found".to_string().into());
+    }
+
+    let content = fs::read_to_string(path)?;
+    let results: CollectedRevealsResults = serde_json::from_str(&content)?;
+    Ok(results.reveals)
+}
+
 fn verify_commitment(
     commitment: CollectedCommitmentData, 
     reveal: &CollectedRevealData, 
     verbose: bool
 ) -> VerificationResult {
-    // Generate hash from guess + salt
-    let generator = CommitmentGenerator::new();
+    // Generate hash from guess + salt, with the algorithm the commitment names
+    let generator = CommitmentGenerator::new().with_hash_algo(commitment.hash_algo);
     let computed_hash = match generator.generate(&reveal.guess, &reveal.salt) {
         Ok(hash) => hash,
         Err(_) => "ERROR".to_string(),
@@ -238,9 +314,78 @@ fn verify_commitment(
         verification_error,
         commitment_tweet_url: commitment.tweet_url,
         reveal_tweet_url: reveal.tweet_url.clone(),
+        clip_score: None,
+        below_min_clip_score: false,
     }
 }
 
+/// The block's own collected file under `results_dir` when `block_num` names
+/// one, else the single `legacy_file` shared by every block
+fn default_collected_path(
+    validator_dir: &Path,
+    results_dir: &str,
+    legacy_file: &str,
+    block_num: Option<&str>,
+) -> Result<PathBuf> {
+    if let Some(block_num) = block_num {
+        let path = block_results_path(&validator_dir.join(results_dir), block_num)?;
+        if path.exists() {
+            return Ok(path);
+        }
+    }
+    Ok(validator_dir.join(legacy_file))
+}
+
+/// Target image for `--min-clip-score`: `--target-image`, else the block's stored target image
+fn resolve_target_image(args: &VerifyCommitmentsArgs) -> Result<PathBuf> {
+    if let Some(path) = &args.target_image {
+        return Ok(path.clone());
+    }
+    let block_num = args.block_num.as_deref().ok_or_else(|| {
+        "--min-clip-score needs --target-image, or --block-num with a target image in the blocks file"
+            .to_string()
+    })?;
+    let block = super::common::load_block(&args.blocks_file, block_num)?;
+    if block.target_image_path.is_empty() {
+        return Err(format!("Block {} has no target image; pass --target-image", block_num).into());
+    }
+    Ok(PathBuf::from(block.target_image_path))
+}
+
+fn clip_score_embedder(args: &VerifyCommitmentsArgs) -> Result<Box<dyn EmbedderTrait>> {
+    let embedder = match &args.clip_model {
+        Some(dir) => ClipEmbedder::new_in(dir)?,
+        None => ClipEmbedder::new()?,
+    };
+    Ok(Box::new(embedder))
+}
+
+/// Score each revealed guess against the target image and flag those below `min_score`
+///
+/// The score is the cosine similarity of the guess and image embeddings, not
+/// the softmax percentage used for ranking, so it doesn't depend on the other
+/// guesses. Participants without a reveal aren't scored. Returns the number
+/// flagged.
+fn flag_low_clip_scores<E: EmbedderTrait>(
+    results: &mut [VerificationResult],
+    embedder: &E,
+    image_path: &str,
+    min_score: f64,
+) -> Result<usize> {
+    let image_embedding = embedder.get_image_embedding(image_path)?;
+    let mut flagged = 0;
+    for result in results.iter_mut().filter(|r| !r.guess.is_empty()) {
+        let text_embedding = embedder.get_text_embedding(&result.guess)?;
+        let score = cosine_similarity(&image_embedding, &text_embedding)?;
+        result.clip_score = Some(score);
+        result.below_min_clip_score = score < min_score;
+        if result.below_min_clip_score {
+            flagged += 1;
+        }
+    }
+    Ok(flagged)
+}
+
 fn display_verification_results(
     results: &VerificationResults,
     _args: &VerifyCommitmentsArgs,
@@ -256,6 +401,13 @@ fn display_text_format(results: &VerificationResults) -> Result<()> {
     println!("Total Participants: {}", results.total_participants);
     println!("Valid Commitments: {}", results.valid_commitments.to_string().green());
     println!("Invalid Commitments: {}", results.invalid_commitments.to_string().red());
+    if let Some(min_score) = results.min_clip_score {
+        println!(
+            "Below Minimum CLIP Score ({}): {}",
+            min_score,
+            results.below_min_clip_score.to_string().yellow()
+        );
+    }
     println!();
 
     for (i, result) in results.results.iter().enumerate() {
@@ -269,54 +421,51 @@ fn display_text_format(results: &VerificationResults) -> Result<()> {
         if let Some(error) = &result.verification_error {
             println!("  Error: {}", error.red());
         }
+        if let Some(score) = result.clip_score {
+            if result.below_min_clip_score {
+                let score = format!("{:.4} (below minimum)", score);
+                println!("  CLIP Score: {}", score.yellow());
+            } else {
+                println!("  CLIP Score: {:.4}", score);
+            }
+        }
         println!();
     }
 
     Ok(())
 }
 
-fn display_json_format(results: &VerificationResults) -> Result<()> {
-    let json_output = serde_json::to_string_pretty(results)?;
-    println!("{}", json_output);
-    Ok(())
-}
-
-fn display_csv_format(results: &VerificationResults) -> Result<()> {
-    println!("author_id,username,wallet_address,commitment_hash,guess,salt,is_valid,verification_error,commitment_tweet_url,reveal_tweet_url");
-
-    for result in &results.results {
-        println!(
-            "{},{},{},{},{},{},{},{},{},{}",
-            csv_escape(&result.author_id),
-            csv_escape(&result.username),
-            csv_escape(&result.wallet_address),
-            result.commitment_hash,
-            csv_escape(&result.guess),
-            csv_escape(&result.salt),
-            result.is_valid,
-            result.verification_error.as_deref().map_or("".to_string(), |s| csv_escape(s)),
-            csv_escape(&result.commitment_tweet_url),
-            csv_escape(&result.reveal_tweet_url)
-        );
+fn display_salt_reuse(reused: &[SaltReuse], total_reveals: usize) {
+    if reused.is_empty() {
+        println!("{} No salt reuse across {} reveals", "✅".green(), total_reveals);
+        return;
     }
 
-    Ok(())
-}
-
-fn csv_escape(field: &str) -> String {
-    if field.contains(',') || field.contains('"') || field.contains('\n') {
-        format!("\"{}\"", field.replace('"', "\"\""))
-    } else {
-        field.to_string()
+    println!(
+        "{} {} salt(s) revealed for more than one commitment",
+        "⚠️  Salt reuse:".yellow().bold(),
+        reused.len()
+    );
+    for reuse in reused {
+        println!("  Salt '{}' used {} times:", reuse.salt, reuse.reveals.len());
+        for reveal in &reuse.reveals {
+            println!(
+                "    - {} ({}) in conversation {}: {}",
+                reveal.username,
+                reveal.author_id,
+                reveal.conversation_id.as_deref().unwrap_or("unknown"),
+                reveal.tweet_url
+            );
+        }
     }
 }
 
-
 // Add new function to save to blocks.json
 fn save_to_blocks_json(
     results: &VerificationResults,
     blocks_file: &PathBuf,
     block_num: &str,
+    currency_label: &str,
 ) -> Result<()> {
     // Load existing blocks data
     let mut blocks_data: BTreeMap<String, serde_json::Value> = if blocks_file.exists() {
@@ -344,7 +493,7 @@ fn save_to_blocks_json(
             "score": 0.0, // Will be calculated in Slice 6
             "payout": {
                 "amount": 0.0,
-                "currency": "TAO",
+                "currency": currency_label,
                 "url": ""
             },
             "salt": result.salt,
@@ -379,6 +528,394 @@ fn save_to_blocks_json(
     Ok(())
 }
 
+/// Outcome of merging collected commitments and reveals into a block
+///
+/// Each list holds the author IDs of the affected participants.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MergeReport {
+    /// Reveals that matched a commitment and verified against it
+    pub verified: Vec<String>,
+    /// Reveals that matched a commitment but failed verification
+    pub failed_verification: Vec<String>,
+    /// Reveals from authors with no commitment
+    pub unmatched_reveals: Vec<String>,
+    /// Commitments whose author never revealed
+    pub unmatched_commitments: Vec<String>,
+}
+
+/// Join collected commitments and reveals into the block's participants
+///
+/// Commitments become participants (keyed by author ID) if the block doesn't
+/// already have them. Each reveal is matched to its author's commitment, its
+/// guess and salt are recorded on the participant, and the participant is
+/// marked verified when the commitment matches hash(guess + salt).
+pub fn merge_reveals_into_block(
+    block: &mut BlockData,
+    commitments: &[CollectedCommitmentData],
+    reveals: &[CollectedRevealData],
+) -> MergeReport {
+    let mut report = MergeReport::default();
+
+    for commitment in commitments {
+        if !block.participants.iter().any(|p| p.social_id == commitment.author_id) {
+            block.add_participant(
+                Participant::new(
+                    commitment.author_id.clone(),
+                    commitment.username.clone(),
+                    Guess::new(String::new()),
+                    commitment.commitment_hash.clone(),
+                )
+                .with_commitment_url(commitment.tweet_url.clone())
+                .with_wallet(commitment.wallet_address.clone())
+                .with_hash_algo(commitment.hash_algo),
+            );
+        }
+    }
+
+    for reveal in reveals {
+        let Some(participant) = block
+            .participants
+            .iter_mut()
+            .find(|p| p.social_id == reveal.author_id)
+        else {
+            report.unmatched_reveals.push(reveal.author_id.clone());
+            continue;
+        };
+
+        // A participant's first valid reveal stands
+        if participant.verified {
+            continue;
+        }
+
+        participant.guess = Guess::new(reveal.guess.clone());
+        participant.salt = Some(reveal.salt.clone());
+        participant.guess_url = reveal.tweet_url.clone();
+        if participant.verify_and_mark() {
+            report.verified.push(reveal.author_id.clone());
+        } else if !report.failed_verification.contains(&reveal.author_id) {
+            report.failed_verification.push(reveal.author_id.clone());
+        }
+    }
+    block.updated_at = chrono::Utc::now();
+
+    // A later valid reveal supersedes earlier failed ones
+    report.failed_verification.retain(|id| !report.verified.contains(id));
+    report.unmatched_commitments = block
+        .participants
+        .iter()
+        .filter(|p| p.salt.is_none())
+        .map(|p| p.social_id.clone())
+        .collect();
+
+    report
+}
+
+/// A salt revealed for more than one commitment
+#[derive(Clone)]
+pub struct SaltReuse {
+    pub salt: String,
+    /// Every reveal using the salt, in input order
+    pub reveals: Vec<CollectedRevealData>,
+}
+
+/// Find salts revealed for more than one commitment, by one participant or several
+///
+/// Reveals are told apart by tweet URL, so the same reveal collected twice
+/// isn't reuse. Empty salts are ignored. Results are ordered by salt.
+pub fn find_salt_reuse(reveals: &[CollectedRevealData]) -> Vec<SaltReuse> {
+    let mut by_salt: BTreeMap<&str, Vec<&CollectedRevealData>> = BTreeMap::new();
+    for reveal in reveals.iter().filter(|r| !r.salt.is_empty()) {
+        let uses = by_salt.entry(reveal.salt.as_str()).or_default();
+        if !uses.iter().any(|r| r.tweet_url == reveal.tweet_url) {
+            uses.push(reveal);
+        }
+    }
+
+    by_salt
+        .into_iter()
+        .filter(|(_, uses)| uses.len() > 1)
+        .map(|(salt, uses)| SaltReuse {
+            salt: salt.to_string(),
+            reveals: uses.into_iter().cloned().collect(),
+        })
+        .collect()
+}
+
 // Import the data structures from other modules
 use crate::actions::collect_commitments::{CollectedCommitmentData, CollectedCommitmentsResults};
-use crate::actions::collect_reveals::{CollectedRevealData, CollectedRevealsResults}; 
\ No newline at end of file
+use crate::actions::collect_reveals::{CollectedRevealData, CollectedRevealsResults}; 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::HashAlgo;
+
+    fn block() -> BlockData {
+        BlockData::new(
+            "1".to_string(),
+            "target.jpg".to_string(),
+            "100".to_string(),
+            100.0,
+        )
+    }
+
+    fn commitment(author_id: &str, guess: &str, salt: &str) -> CollectedCommitmentData {
+        CollectedCommitmentData {
+            username: format!("user_{}", author_id),
+            commitment_hash: CommitmentGenerator::new().generate(guess, salt).unwrap(),
+            wallet_address: format!("5wallet{}", author_id),
+            tweet_url: format!("https://twitter.com/i/status/c{}", author_id),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            author_id: author_id.to_string(),
+            conversation_id: Some("100".to_string()),
+            within_grace: false,
+            hash_algo: HashAlgo::default(),
+        }
+    }
+
+    fn reveal(author_id: &str, guess: &str, salt: &str) -> CollectedRevealData {
+        CollectedRevealData {
+            username: format!("user_{}", author_id),
+            guess: guess.to_string(),
+            salt: salt.to_string(),
+            tweet_url: format!("https://twitter.com/i/status/r{}", author_id),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            author_id: author_id.to_string(),
+            conversation_id: Some("100".to_string()),
+            within_grace: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_clean_match() {
+        let mut block = block();
+        let report = merge_reveals_into_block(
+            &mut block,
+            &[commitment("1", "a red fox", "salt1")],
+            &[reveal("1", "a red fox", "salt1")],
+        );
+
+        assert_eq!(report.verified, vec!["1"]);
+        assert!(report.failed_verification.is_empty());
+        assert!(report.unmatched_reveals.is_empty());
+        assert!(report.unmatched_commitments.is_empty());
+
+        let participant = &block.participants[0];
+        assert!(participant.verified);
+        assert_eq!(participant.guess.text, "a red fox");
+        assert_eq!(participant.salt.as_deref(), Some("salt1"));
+        assert_eq!(participant.wallet, "5wallet1");
+        assert_eq!(participant.guess_url, "https://twitter.com/i/status/r1");
+    }
+
+    #[test]
+    fn test_merge_unmatched_reveal_and_commitment() {
+        let mut block = block();
+        let report = merge_reveals_into_block(
+            &mut block,
+            &[commitment("1", "a red fox", "salt1")],
+            &[reveal("2", "a blue whale", "salt2")],
+        );
+
+        assert!(report.verified.is_empty());
+        assert_eq!(report.unmatched_reveals, vec!["2"]);
+        assert_eq!(report.unmatched_commitments, vec!["1"]);
+        assert_eq!(block.participants.len(), 1);
+        assert!(!block.participants[0].verified);
+    }
+
+    #[test]
+    fn test_merge_failed_verification() {
+        let mut block = block();
+        let report = merge_reveals_into_block(
+            &mut block,
+            &[commitment("1", "a red fox", "salt1")],
+            &[reveal("1", "a red fox", "wrong_salt")],
+        );
+
+        assert!(report.verified.is_empty());
+        assert_eq!(report.failed_verification, vec!["1"]);
+        assert!(report.unmatched_commitments.is_empty());
+
+        let participant = &block.participants[0];
+        assert!(!participant.verified);
+        assert_eq!(participant.salt.as_deref(), Some("wrong_salt"));
+    }
+
+    #[test]
+    fn test_blake3_commitments_verify_with_their_algorithm() {
+        let mut blake3 = commitment("1", "a red fox", "salt1");
+        blake3.hash_algo = HashAlgo::Blake3;
+        blake3.commitment_hash = CommitmentGenerator::new()
+            .with_hash_algo(HashAlgo::Blake3)
+            .generate("a red fox", "salt1")
+            .unwrap();
+        let reveal = reveal("1", "a red fox", "salt1");
+
+        assert!(verify_commitment(blake3.clone(), &reveal, false).is_valid);
+
+        let mut block = block();
+        let report = merge_reveals_into_block(&mut block, &[blake3], &[reveal]);
+        assert_eq!(report.verified, vec!["1"]);
+        assert_eq!(block.participants[0].hash_algo, HashAlgo::Blake3);
+    }
+
+    #[test]
+    fn test_find_salt_reuse() {
+        let mut other_block = reveal("1", "a blue whale", "pepper");
+        other_block.tweet_url = "https://twitter.com/i/status/r1b".to_string();
+        other_block.conversation_id = Some("200".to_string());
+        let reveals = vec![
+            reveal("1", "a red fox", "pepper"),
+            reveal("2", "a green frog", "salt2"),
+            reveal("3", "a red fox", "pepper"),
+            // The same participant in another block
+            other_block,
+            // A duplicate copy of the same reveal tweet
+            reveal("2", "a green frog", "salt2"),
+        ];
+
+        let reused = find_salt_reuse(&reveals);
+        assert_eq!(reused.len(), 1);
+        assert_eq!(reused[0].salt, "pepper");
+        let authors: Vec<(&str, Option<&str>)> = reused[0]
+            .reveals
+            .iter()
+            .map(|r| (r.author_id.as_str(), r.conversation_id.as_deref()))
+            .collect();
+        assert_eq!(
+            authors,
+            vec![("1", Some("100")), ("3", Some("100")), ("1", Some("200"))]
+        );
+    }
+
+    #[test]
+    fn test_find_salt_reuse_clean_dataset() {
+        let reveals = vec![
+            reveal("1", "a red fox", "salt1"),
+            reveal("2", "a red fox", "salt2"),
+            reveal("3", "", ""),
+            reveal("4", "", ""),
+        ];
+
+        assert!(find_salt_reuse(&reveals).is_empty());
+    }
+
+    /// Embeds the image along one axis and each known guess at a fixed angle to it
+    struct AngleEmbedder;
+
+    impl EmbedderTrait for AngleEmbedder {
+        fn get_image_embedding(&self, _image_path: &str) -> Result<ndarray::Array1<f64>> {
+            Ok(ndarray::arr1(&[1.0, 0.0]))
+        }
+
+        fn get_text_embedding(&self, text: &str) -> Result<ndarray::Array1<f64>> {
+            Ok(match text {
+                "a red fox in the snow" => ndarray::arr1(&[0.3, 0.954]),
+                _ => ndarray::arr1(&[0.05, 0.999]),
+            })
+        }
+
+        fn calculate_batch_similarities(
+            &self,
+            image_path: &str,
+            texts: &[String],
+        ) -> Result<Vec<f64>> {
+            let image_embedding = self.get_image_embedding(image_path)?;
+            texts
+                .iter()
+                .map(|text| cosine_similarity(&image_embedding, &self.get_text_embedding(text)?))
+                .collect()
+        }
+
+        fn embedding_dim(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn test_flag_low_clip_scores() {
+        let mut results = vec![
+            verify_commitment(
+                commitment("1", "a red fox in the snow", "salt1"),
+                &reveal("1", "a red fox in the snow", "salt1"),
+                false,
+            ),
+            verify_commitment(
+                commitment("2", "buy followers now", "salt2"),
+                &reveal("2", "buy followers now", "salt2"),
+                false,
+            ),
+            // Fails verification but is still scored; the two signals are separate
+            verify_commitment(
+                commitment("3", "a red fox in the snow", "salt3"),
+                &reveal("3", "a red fox in the snow", "wrong"),
+                false,
+            ),
+        ];
+
+        let flagged = flag_low_clip_scores(&mut results, &AngleEmbedder, "target.jpg", 0.2).unwrap();
+
+        assert_eq!(flagged, 1);
+        assert!((results[0].clip_score.unwrap() - 0.3).abs() < 1e-3);
+        assert!(!results[0].below_min_clip_score);
+        assert!(results[0].is_valid);
+        assert!((results[1].clip_score.unwrap() - 0.05).abs() < 1e-3);
+        assert!(results[1].below_min_clip_score);
+        assert!(results[1].is_valid);
+        assert!(!results[2].below_min_clip_score);
+        assert!(!results[2].is_valid);
+
+        // A stricter threshold flags the on-topic guess too
+        let flagged = flag_low_clip_scores(&mut results, &AngleEmbedder, "target.jpg", 0.5).unwrap();
+        assert_eq!(flagged, 3);
+
+        // The flagged scores are the embedder's own batch similarities
+        let guesses: Vec<String> = results.iter().map(|r| r.guess.clone()).collect();
+        let batch = AngleEmbedder
+            .calculate_batch_similarities("target.jpg", &guesses)
+            .unwrap();
+        for (result, similarity) in results.iter().zip(batch) {
+            assert!((result.clip_score.unwrap() - similarity).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_flag_low_clip_scores_skips_missing_reveals() {
+        let mut results = vec![verify_commitment(
+            commitment("1", "a red fox in the snow", "salt1"),
+            &reveal("1", "", ""),
+            false,
+        )];
+
+        let flagged = flag_low_clip_scores(&mut results, &AngleEmbedder, "target.jpg", 0.9).unwrap();
+        assert_eq!(flagged, 0);
+        assert_eq!(results[0].clip_score, None);
+    }
+
+    #[test]
+    fn test_default_collected_path_prefers_the_block_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let validator_dir = temp_dir.path();
+        let legacy = validator_dir.join("collected_commitments.json");
+        let path = |block_num| {
+            default_collected_path(
+                validator_dir,
+                COMMITMENTS_DIR,
+                "collected_commitments.json",
+                block_num,
+            )
+            .unwrap()
+        };
+
+        // Until the block is collected incrementally, the shared file is used
+        assert_eq!(path(None), legacy);
+        assert_eq!(path(Some("1")), legacy);
+
+        let block_file = validator_dir.join(COMMITMENTS_DIR).join("1.json");
+        fs::create_dir_all(block_file.parent().unwrap()).unwrap();
+        fs::write(&block_file, "{}").unwrap();
+        assert_eq!(path(Some("1")), block_file);
+        assert_eq!(path(Some("2")), legacy);
+    }
+}
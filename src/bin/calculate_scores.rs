@@ -11,9 +11,12 @@ use std::process;
 
 use cliptions_core::config::ConfigManager;
 use cliptions_core::embedder::{ClipEmbedder, EmbedderTrait, MockEmbedder};
+use cliptions_core::payout::{PayoutCalculator, PayoutConfig, DEFAULT_CURRENCY_LABEL};
 use cliptions_core::scoring::{
-    calculate_payouts, calculate_rankings, ClipBatchStrategy, ScoreValidator,
+    parse_strategy_param, rank_items, ScoreValidator, ScoringStrategy, StrategyParams,
+    StrategyRegistry,
 };
+use cliptions_core::scoring_version::ScoringVersionRegistry;
 
 #[derive(Parser)]
 #[command(name = "calculate_scores")]
@@ -38,6 +41,9 @@ Examples:
   
   # Load configuration from file
   calculate_scores --config config.yaml target.jpg 100.0 \"guess1\" \"guess2\"
+  
+  # Score with a named strategy and its parameters
+  calculate_scores --strategy blended --strategy-param alpha=0.7 --strategy-param reference=\"ocean waves\" target.jpg 100.0 \"guess1\"
 ")]
 struct Args {
     /// Path to the target image
@@ -57,7 +63,7 @@ struct Args {
     #[arg(long)]
     output_file: Option<PathBuf>,
 
-    /// Path to CLIP model directory (optional, uses default if not specified)
+    /// CLIP model directory (default: $CLIPTIONS_MODEL_DIR or ~/.cliptions/models)
     #[arg(long)]
     clip_model: Option<PathBuf>,
 
@@ -88,6 +94,26 @@ struct Args {
     /// Use MockEmbedder instead of CLIP for testing (fast, deterministic)
     #[arg(long)]
     use_mock: bool,
+
+    /// Scoring strategy: blended, clip-batch, precomputed (default: clip-batch)
+    #[arg(long)]
+    strategy: Option<String>,
+
+    /// Strategy parameter as KEY=VALUE; VALUE is parsed as JSON, else taken as a string
+    #[arg(long, value_parser = parse_strategy_param)]
+    strategy_param: Vec<(String, serde_json::Value)>,
+
+    /// Currency label shown next to prize pool and payout amounts
+    #[arg(long, default_value = DEFAULT_CURRENCY_LABEL)]
+    currency_label: String,
+
+    /// Scoring version registry (default: ~/.cliptions/data/scoring_versions.json)
+    #[arg(long)]
+    scoring_versions: Option<PathBuf>,
+
+    /// Scoring version to stamp results with (default: the registry's default version)
+    #[arg(long)]
+    scoring_version: Option<String>,
 }
 
 fn main() {
@@ -174,11 +200,29 @@ fn main() {
         process::exit(1);
     }
 
+    let scoring_version = match resolve_scoring_version(&args) {
+        Ok(version) => version,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            process::exit(1);
+        }
+    };
+    if let (Some(version), true) = (&scoring_version, args.verbose) {
+        println!("{} Using scoring version {}", "Info:".blue().bold(), version);
+    }
+
     // Create embedder and calculate results
+    let config = payout_config(&args);
     match calculate_scores_with_embedder(&args, &filtered_guesses) {
         Ok((ranked_results, payouts)) => {
             // Display results
-            if let Err(e) = display_results(&ranked_results, &payouts, args.prize_pool, &args) {
+            if let Err(e) = display_results(
+                &ranked_results,
+                &payouts,
+                &config,
+                scoring_version.as_deref(),
+                &args,
+            ) {
                 eprintln!("{} Failed to display results: {}", "Error:".red().bold(), e);
                 process::exit(1);
             }
@@ -188,7 +232,8 @@ fn main() {
                 if let Err(e) = save_results(
                     &ranked_results,
                     &payouts,
-                    args.prize_pool,
+                    &config,
+                    scoring_version.as_deref(),
                     output_file,
                     &args.output,
                 ) {
@@ -217,6 +262,30 @@ fn main() {
     }
 }
 
+/// Id of the scoring version named by `--scoring-version`, else the registry's default
+///
+/// Without a registry file there is no version, and results are not stamped.
+fn resolve_scoring_version(args: &Args) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let path = match &args.scoring_versions {
+        Some(path) => path.clone(),
+        None => ScoringVersionRegistry::default_path()?,
+    };
+    let registry = ScoringVersionRegistry::load(&path)?;
+    Ok(registry
+        .resolve(args.scoring_version.as_deref())?
+        .map(|version| version.id.clone()))
+}
+
+/// Payout settings for the prize pool and currency given on the command line
+fn payout_config(args: &Args) -> PayoutConfig {
+    PayoutConfig {
+        prize_pool: args.prize_pool,
+        minimum_players: 1,
+        currency_label: args.currency_label.clone(),
+        ..PayoutConfig::default()
+    }
+}
+
 fn validate_inputs(args: &Args) -> Result<(), String> {
     // Validate prize pool
     if args.prize_pool <= 0.0 {
@@ -278,21 +347,41 @@ fn filter_guesses(guesses: &[String], min_len: usize, max_len: usize) -> Vec<Str
         .collect()
 }
 
+/// Build the strategy named by `--strategy` from the built-in registry
+fn build_strategy(args: &Args) -> Result<Box<dyn ScoringStrategy>, Box<dyn std::error::Error>> {
+    let params: StrategyParams = args.strategy_param.iter().cloned().collect();
+    let name = args
+        .strategy
+        .as_deref()
+        .unwrap_or(StrategyRegistry::DEFAULT_STRATEGY);
+    Ok(StrategyRegistry::with_builtin().create(name, &params)?)
+}
+
 fn calculate_scores_with_embedder(
     args: &Args,
     guesses: &[String],
 ) -> Result<(Vec<(String, f64)>, Vec<f64>), Box<dyn std::error::Error>> {
+    let strategy = build_strategy(args)?;
+    if args.verbose {
+        println!("{} Using {} scoring strategy", "Info:".blue().bold(), strategy.name());
+    }
+
+    // Strategies with their own scores never call the embedder, so no model is loaded
+    if !strategy.uses_embedder() {
+        return calculate_with_embedder(MockEmbedder::clip_like(), strategy, args, guesses);
+    }
+
     // Create embedder based on user preference (defaults to CLIP)
     if args.use_mock {
         if args.verbose {
             println!("{} Using MockEmbedder for testing", "Info:".blue().bold());
         }
         let embedder = MockEmbedder::clip_like();
-        calculate_with_embedder(embedder, args, guesses)
+        calculate_with_embedder(embedder, strategy, args, guesses)
     } else {
         // Default: Use CLIP embedder
         if let Some(model_path) = &args.clip_model {
-            match ClipEmbedder::from_path(&model_path.to_string_lossy()) {
+            match ClipEmbedder::new_in(model_path) {
                 Ok(embedder) => {
                     if args.verbose {
                         println!(
@@ -301,7 +390,7 @@ fn calculate_scores_with_embedder(
                             model_path.display()
                         );
                     }
-                    calculate_with_embedder(embedder, args, guesses)
+                    calculate_with_embedder(embedder, strategy, args, guesses)
                 }
                 Err(e) => {
                     panic!(
@@ -317,7 +406,7 @@ fn calculate_scores_with_embedder(
                     if args.verbose {
                         println!("{} Using default CLIP embedder", "Info:".blue().bold());
                     }
-                    calculate_with_embedder(embedder, args, guesses)
+                    calculate_with_embedder(embedder, strategy, args, guesses)
                 }
                 Err(e) => {
                     panic!(
@@ -332,11 +421,16 @@ fn calculate_scores_with_embedder(
 
 fn calculate_with_embedder<E: EmbedderTrait>(
     embedder: E,
+    strategy: Box<dyn ScoringStrategy>,
     args: &Args,
     guesses: &[String],
 ) -> Result<(Vec<(String, f64)>, Vec<f64>), Box<dyn std::error::Error>> {
-    let strategy = ClipBatchStrategy::new();
     let validator = ScoreValidator::new(embedder, strategy);
+    for guess in guesses {
+        if let Some(truncation) = validator.truncation(guess)? {
+            eprintln!("{} Guess {:?} was {}", "Warning:".yellow().bold(), guess, truncation);
+        }
+    }
 
     // Calculate rankings
     if args.verbose {
@@ -347,15 +441,20 @@ fn calculate_with_embedder<E: EmbedderTrait>(
         );
     }
 
-    let ranked_results = calculate_rankings(&args.target_image_path, guesses, &validator)
-        .map_err(|e| format!("Failed to calculate rankings: {}", e))?;
+    let ranked_results: Vec<(String, f64)> =
+        rank_items(&args.target_image_path, guesses, &validator)
+            .map_err(|e| format!("Failed to calculate rankings: {}", e))?
+            .into_iter()
+            .map(|(guess, score)| (guess.clone(), score))
+            .collect();
 
     // Calculate payouts
     if args.verbose {
         println!("{} Computing payout distribution...", "Info:".blue().bold());
     }
 
-    let payouts = calculate_payouts(&ranked_results, args.prize_pool)
+    let payouts = PayoutCalculator::with_config(payout_config(args))
+        .calculate_payouts(&ranked_results)
         .map_err(|e| format!("Failed to calculate payouts: {}", e))?;
 
     Ok((ranked_results, payouts))
@@ -364,13 +463,14 @@ fn calculate_with_embedder<E: EmbedderTrait>(
 fn display_results(
     ranked_results: &[(String, f64)],
     payouts: &[f64],
-    prize_pool: f64,
+    config: &PayoutConfig,
+    scoring_version: Option<&str>,
     args: &Args,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match args.output.as_str() {
-        "table" => display_table_format(ranked_results, payouts, prize_pool, args),
-        "json" => display_json_format(ranked_results, payouts, prize_pool),
-        "csv" => display_csv_format(ranked_results, payouts),
+        "table" => display_table_format(ranked_results, payouts, config, args),
+        "json" => display_json_format(ranked_results, payouts, config, scoring_version),
+        "csv" => display_csv_format(ranked_results, payouts, config),
         _ => Err(format!("Unsupported output format: {}", args.output).into()),
     }
 }
@@ -378,7 +478,7 @@ fn display_results(
 fn display_table_format(
     ranked_results: &[(String, f64)],
     payouts: &[f64],
-    prize_pool: f64,
+    config: &PayoutConfig,
     args: &Args,
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n{}", "Rankings and Payouts:".bold().underline());
@@ -391,7 +491,7 @@ fn display_table_format(
 
         if args.detailed {
             println!("   {} {:.6}", "Similarity:".dimmed(), similarity);
-            println!("   {} {:.9} TAO", "Payout:".dimmed(), payout);
+            println!("   {} {}", "Payout:".dimmed(), config.format_amount(*payout));
 
             if i == 0 {
                 println!("   {} {}", "Status:".dimmed(), "🏆 Winner".green().bold());
@@ -400,22 +500,27 @@ fn display_table_format(
             }
         } else {
             println!(
-                "   Similarity: {:.4} | Payout: {:.9} TAO",
-                similarity, payout
+                "   Similarity: {:.4} | Payout: {}",
+                similarity,
+                config.format_amount(*payout)
             );
         }
         println!();
     }
 
     println!("{}", "=".repeat(80));
-    println!("{} {:.9} TAO", "Total Prize Pool:".bold(), prize_pool);
     println!(
-        "{} {:.9} TAO",
+        "{} {}",
+        "Total Prize Pool:".bold(),
+        config.format_amount(config.prize_pool)
+    );
+    println!(
+        "{} {}",
         "Total Distributed:".bold(),
-        payouts.iter().sum::<f64>()
+        config.format_amount(payouts.iter().sum::<f64>())
     );
 
-    let efficiency = (payouts.iter().sum::<f64>() / prize_pool) * 100.0;
+    let efficiency = (payouts.iter().sum::<f64>() / config.prize_pool) * 100.0;
     println!("{} {:.2}%", "Distribution Efficiency:".bold(), efficiency);
 
     Ok(())
@@ -424,7 +529,8 @@ fn display_table_format(
 fn display_json_format(
     ranked_results: &[(String, f64)],
     payouts: &[f64],
-    prize_pool: f64,
+    config: &PayoutConfig,
+    scoring_version: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut results = serde_json::Map::new();
 
@@ -445,16 +551,23 @@ fn display_json_format(
     results.insert("rankings".to_string(), serde_json::Value::Array(rankings));
     results.insert(
         "prize_pool".to_string(),
-        serde_json::Value::from(prize_pool),
+        serde_json::Value::from(config.prize_pool),
     );
     results.insert(
         "total_distributed".to_string(),
         serde_json::Value::from(payouts.iter().sum::<f64>()),
     );
+    results.insert(
+        "currency".to_string(),
+        serde_json::Value::from(config.currency_label.as_str()),
+    );
     results.insert(
         "num_participants".to_string(),
         serde_json::Value::from(ranked_results.len()),
     );
+    if let Some(version) = scoring_version {
+        results.insert("scoring_version".to_string(), serde_json::Value::from(version));
+    }
 
     let json_output = serde_json::to_string_pretty(&results)?;
     println!("{}", json_output);
@@ -465,29 +578,40 @@ fn display_json_format(
 fn display_csv_format(
     ranked_results: &[(String, f64)],
     payouts: &[f64],
+    config: &PayoutConfig,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    println!("rank,guess,similarity_score,payout");
+    print!("{}", csv_content(ranked_results, payouts, config)?);
+    Ok(())
+}
+
+/// Rankings as RFC 4180 CSV, quoting fields that contain quotes, commas or newlines
+fn csv_content(
+    ranked_results: &[(String, f64)],
+    payouts: &[f64],
+    config: &PayoutConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(["rank", "guess", "similarity_score", "payout", "currency"])?;
 
     for (i, ((guess, similarity), payout)) in ranked_results.iter().zip(payouts.iter()).enumerate()
     {
-        // Escape quotes in CSV format
-        let escaped_guess = guess.replace("\"", "\"\"");
-        println!(
-            "{},\"{}\",{:.6},{:.9}",
-            i + 1,
-            escaped_guess,
-            similarity,
-            payout
-        );
+        writer.write_record([
+            &(i + 1).to_string(),
+            guess.as_str(),
+            &format!("{:.6}", similarity),
+            &format!("{:.9}", payout),
+            &config.currency_label,
+        ])?;
     }
 
-    Ok(())
+    Ok(String::from_utf8(writer.into_inner()?)?)
 }
 
 fn save_results(
     ranked_results: &[(String, f64)],
     payouts: &[f64],
-    prize_pool: f64,
+    config: &PayoutConfig,
+    scoring_version: Option<&str>,
     output_file: &PathBuf,
     format: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -512,37 +636,27 @@ fn save_results(
             results.insert("rankings".to_string(), serde_json::Value::Array(rankings));
             results.insert(
                 "prize_pool".to_string(),
-                serde_json::Value::from(prize_pool),
+                serde_json::Value::from(config.prize_pool),
             );
             results.insert(
                 "total_distributed".to_string(),
                 serde_json::Value::from(payouts.iter().sum::<f64>()),
             );
+            results.insert(
+                "currency".to_string(),
+                serde_json::Value::from(config.currency_label.as_str()),
+            );
             results.insert(
                 "timestamp".to_string(),
                 serde_json::Value::from(chrono::Utc::now().to_rfc3339()),
             );
-
-            serde_json::to_string_pretty(&results)?
-        }
-        "csv" => {
-            let mut content = String::from("rank,guess,similarity_score,payout\n");
-
-            for (i, ((guess, similarity), payout)) in
-                ranked_results.iter().zip(payouts.iter()).enumerate()
-            {
-                let escaped_guess = guess.replace("\"", "\"\"");
-                content.push_str(&format!(
-                    "{},\"{}\",{:.6},{:.9}\n",
-                    i + 1,
-                    escaped_guess,
-                    similarity,
-                    payout
-                ));
+            if let Some(version) = scoring_version {
+                results.insert("scoring_version".to_string(), serde_json::Value::from(version));
             }
 
-            content
+            serde_json::to_string_pretty(&results)?
         }
+        "csv" => csv_content(ranked_results, payouts, config)?,
         "table" => {
             let mut content = String::from("Rankings and Payouts\n");
             content.push_str(&"=".repeat(50));
@@ -553,15 +667,18 @@ fn save_results(
             {
                 content.push_str(&format!("{}. \"{}\"\n", i + 1, guess));
                 content.push_str(&format!("   Similarity score: {:.4}\n", similarity));
-                content.push_str(&format!("   Payout: {:.9}\n\n", payout));
+                content.push_str(&format!("   Payout: {}\n\n", config.format_amount(*payout)));
             }
 
             content.push_str(&"=".repeat(50));
             content.push('\n');
-            content.push_str(&format!("Total prize pool: {:.9}\n", prize_pool));
             content.push_str(&format!(
-                "Total payout: {:.9}\n",
-                payouts.iter().sum::<f64>()
+                "Total prize pool: {}\n",
+                config.format_amount(config.prize_pool)
+            ));
+            content.push_str(&format!(
+                "Total payout: {}\n",
+                config.format_amount(payouts.iter().sum::<f64>())
             ));
 
             content
@@ -576,6 +693,7 @@ fn save_results(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cliptions_core::scoring::{calculate_payouts, ClipBatchStrategy};
 
     #[test]
     fn test_validate_inputs_valid() {
@@ -593,6 +711,11 @@ mod tests {
             max_guess_length: 200,
             detailed: false,
             use_mock: false,
+            strategy: None,
+            strategy_param: Vec::new(),
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            scoring_versions: None,
+            scoring_version: None,
         };
 
         // This will fail if the test image doesn't exist, which is expected
@@ -618,6 +741,11 @@ mod tests {
             max_guess_length: 200,
             detailed: false,
             use_mock: false,
+            strategy: None,
+            strategy_param: Vec::new(),
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            scoring_versions: None,
+            scoring_version: None,
         };
 
         let result = validate_inputs(&args);
@@ -627,6 +755,42 @@ mod tests {
             .contains("Prize pool must be greater than zero"));
     }
 
+    #[test]
+    fn test_precomputed_strategy_scores_without_an_embedder() {
+        let similarities = tempfile::NamedTempFile::new().unwrap();
+        fs::write(similarities.path(), r#"{"a red car": 40.0, "a blue boat": 60.0}"#).unwrap();
+        let guesses = vec!["a red car".to_string(), "a blue boat".to_string()];
+        let args = Args {
+            target_image_path: "missing.jpg".to_string(),
+            prize_pool: 100.0,
+            guesses: guesses.clone(),
+            output: "table".to_string(),
+            output_file: None,
+            clip_model: None,
+            verbose: false,
+            no_color: false,
+            config: None,
+            min_guess_length: 1,
+            max_guess_length: 200,
+            detailed: false,
+            use_mock: false,
+            strategy: Some("precomputed".to_string()),
+            strategy_param: vec![parse_strategy_param(&format!(
+                "similarities_file={}",
+                similarities.path().display()
+            ))
+            .unwrap()],
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            scoring_versions: None,
+            scoring_version: None,
+        };
+
+        // No CLIP model is loaded, so this runs without one installed
+        let (ranked, payouts) = calculate_scores_with_embedder(&args, &guesses).unwrap();
+        assert_eq!(ranked[0], ("a blue boat".to_string(), 60.0));
+        assert!((payouts.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_filter_guesses() {
         let guesses = vec![
@@ -654,7 +818,11 @@ mod tests {
             "guess3".to_string(),
         ];
 
-        let ranked_results = calculate_rankings("test.jpg", &guesses, &validator).unwrap();
+        let ranked_results: Vec<(String, f64)> = rank_items("test.jpg", &guesses, &validator)
+            .unwrap()
+            .into_iter()
+            .map(|(guess, score)| (guess.clone(), score))
+            .collect();
         let payouts = calculate_payouts(&ranked_results, 100.0).unwrap();
 
         assert_eq!(ranked_results.len(), 3);
@@ -664,4 +832,25 @@ mod tests {
         let total: f64 = payouts.iter().sum();
         assert!((total - 100.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_csv_content_quotes_guesses() {
+        let ranked_results = vec![
+            ("a \"red\", fox".to_string(), 0.9),
+            ("plain".to_string(), 0.5),
+        ];
+        let config = PayoutConfig {
+            currency_label: "ETH".to_string(),
+            ..PayoutConfig::default()
+        };
+
+        let content = csv_content(&ranked_results, &[60.0, 40.0], &config).unwrap();
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(&records[0][1], "a \"red\", fox");
+        assert_eq!(&records[0][3], "60.000000000");
+        assert_eq!(&records[1][4], "ETH");
+    }
 }
@@ -78,34 +78,8 @@ async fn main() -> Result<()> {
 
     if args.verbose {
         println!("✅ Configuration loaded successfully");
-        println!("🔑 Using Twitter API key: {}", config.twitter.api_key);
-        println!(
-            "  api_secret: {}...",
-            &config
-                .twitter
-                .api_secret
-                .chars()
-                .take(4)
-                .collect::<String>()
-        );
-        println!(
-            "  access_token: {}...",
-            &config
-                .twitter
-                .access_token
-                .chars()
-                .take(4)
-                .collect::<String>()
-        );
-        println!(
-            "  access_token_secret: {}...",
-            &config
-                .twitter
-                .access_token_secret
-                .chars()
-                .take(4)
-                .collect::<String>()
-        );
+        // Debug output redacts the keys and tokens
+        println!("🔑 Using Twitter credentials: {:#?}", config.twitter);
         println!("📄 Loading config from: {}", &args.config);
         println!("🔍 Loaded config: [masked]");
     }
@@ -116,6 +90,7 @@ async fn main() -> Result<()> {
         api_secret: config.twitter.api_secret.clone(),
         access_token: config.twitter.access_token.clone(),
         access_token_secret: config.twitter.access_token_secret.clone(),
+        bearer_token: config.twitter.bearer_token.clone(),
     };
     let twitter_client = TwitterClient::new(twitter_config);
 
@@ -396,29 +396,22 @@ fn display_json_format(results: &CommitmentResults) -> Result<(), Box<dyn std::e
 }
 
 fn display_csv_format(results: &CommitmentResults) -> Result<(), Box<dyn std::error::Error>> {
-    println!("message,salt,commitment,timestamp");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["message", "salt", "commitment", "timestamp"])?;
 
     for data in &results.commitments {
-        println!(
-            "{},{},{},{}",
-            csv_escape(&data.message),
-            csv_escape(&data.salt),
-            data.commitment,
-            data.timestamp.as_deref().unwrap_or("")
-        );
+        writer.write_record([
+            data.message.as_str(),
+            &data.salt,
+            &data.commitment,
+            data.timestamp.as_deref().unwrap_or(""),
+        ])?;
     }
 
+    writer.flush()?;
     Ok(())
 }
 
-fn csv_escape(field: &str) -> String {
-    if field.contains(',') || field.contains('"') || field.contains('\n') {
-        format!("\"{}\"", field.replace('"', "\"\""))
-    } else {
-        field.to_string()
-    }
-}
-
 fn save_results(
     results: &CommitmentResults,
     save_path: &PathBuf,
@@ -544,12 +537,4 @@ mod tests {
         assert_eq!(result.commitments[0].salt, "test_salt");
         assert_eq!(result.commitments[0].commitment.len(), 64); // SHA-256 hex length
     }
-
-    #[test]
-    fn test_csv_escape() {
-        assert_eq!(csv_escape("simple"), "simple");
-        assert_eq!(csv_escape("with,comma"), "\"with,comma\"");
-        assert_eq!(csv_escape("with\"quote"), "\"with\"\"quote\"");
-        assert_eq!(csv_escape("with\nline"), "\"with\nline\"");
-    }
 }
@@ -7,13 +7,19 @@
 use clap::Parser;
 use colored::Colorize;
 use std::fs;
+use std::io;
 use std::path::PathBuf;
 use std::process;
 
 use cliptions_core::config::ConfigManager;
 use cliptions_core::embedder::{ClipEmbedder, EmbedderTrait, MockEmbedder};
-use cliptions_core::block_processor::BlockProcessor;
-use cliptions_core::scoring::ClipBatchStrategy;
+use cliptions_core::block_processor::{BlockProcessor, TerminalProgress};
+use cliptions_core::block_store::{is_stdio, BlockStore, JsonBlockStore, MemoryBlockStore};
+use cliptions_core::payout::{PayoutConfig, PayoutCurve, DEFAULT_CURRENCY_LABEL};
+use cliptions_core::scoring::{
+    parse_strategy_param, ScoringStrategy, StrategyParams, StrategyRegistry,
+};
+use cliptions_core::scoring_version::{ScoringVersion, ScoringVersionRegistry};
 
 #[derive(Parser)]
 #[command(name = "process_payouts")]
@@ -39,6 +45,24 @@ Examples:
   
   # Process with custom blocks file
   process_payouts --block block1 --blocks-file data/custom_blocks.json
+  
+  # Score offline from precomputed {guess: similarity} JSON, without an embedder
+  process_payouts --block block1 --similarities-file similarities.json
+  
+  # Score with a named strategy and its parameters
+  process_payouts --block block1 --strategy blended --strategy-param alpha=0.7 --strategy-param reference=\"a red car\"
+  
+  # Blocks with a reference_caption use it instead of the reference parameter
+  process_payouts --block block1 --strategy blended --strategy-param alpha=0.7
+  
+  # Pay only the top three guesses, evenly
+  process_payouts --block block1 --curve top-n:3
+  
+  # Pay whole TAO units (9 decimal places) so payouts add up to the pool exactly
+  process_payouts --block block1 --decimals 9
+  
+  # Read blocks from stdin and write JSON results to stdout only
+  cat blocks.json | process_payouts --block block1 --blocks-file - --output json --output-file -
 ")]
 struct Args {
     /// Process all blocks
@@ -49,7 +73,7 @@ struct Args {
     #[arg(long)]
     block: Option<String>,
 
-    /// Path to blocks file
+    /// Path to blocks file, or - to read blocks from stdin (nothing is written back)
     #[arg(long, default_value = "blocks.json")]
     blocks_file: PathBuf,
 
@@ -57,18 +81,31 @@ struct Args {
     #[arg(long, short, default_value = "table", value_parser = ["table", "json", "csv"])]
     output: String,
 
-    /// Save results to file
-    #[arg(long, short)]
+    /// Save results to file, or - to write them to stdout only
+    #[arg(long)]
     output_file: Option<PathBuf>,
 
     /// Use MockEmbedder instead of CLIP for testing (fast, deterministic)
     #[arg(long)]
     use_mock: bool,
 
-    /// Path to CLIP model directory (optional, uses default if not specified)
+    /// CLIP model directory (default: $CLIPTIONS_MODEL_DIR or ~/.cliptions/models)
     #[arg(long)]
     clip_model: Option<PathBuf>,
 
+    /// Score from a JSON file of {guess: similarity} instead of running an embedder
+    #[arg(long, conflicts_with_all = ["use_mock", "clip_model"])]
+    similarities_file: Option<PathBuf>,
+
+    /// Scoring strategy: blended, clip-batch, precomputed (default: clip-batch, or
+    /// precomputed with --similarities-file)
+    #[arg(long)]
+    strategy: Option<String>,
+
+    /// Strategy parameter as KEY=VALUE; VALUE is parsed as JSON, else taken as a string
+    #[arg(long, value_parser = parse_strategy_param)]
+    strategy_param: Vec<(String, serde_json::Value)>,
+
     /// Enable verbose output with detailed progress information
     #[arg(short, long)]
     verbose: bool,
@@ -96,6 +133,54 @@ struct Args {
     /// Maximum number of blocks to process (for --all, 0 = unlimited)
     #[arg(long, default_value = "0")]
     max_blocks: usize,
+
+    /// Percentage of each block's prize pool kept as the validator fee
+    #[arg(long, default_value = "0")]
+    validator_fee_percent: f64,
+
+    /// Currency label shown next to payout amounts
+    #[arg(long, default_value = DEFAULT_CURRENCY_LABEL)]
+    currency_label: String,
+
+    /// Refuse to pay out prize pools smaller than this
+    #[arg(long, default_value = "0")]
+    min_prize_pool: f64,
+
+    /// How each block's pool is split: linear, proportional, exponential:BASE,
+    /// winner-take-all or top-n:N
+    #[arg(long, default_value = "linear")]
+    curve: PayoutCurve,
+
+    /// Round payouts to this many decimal places so each block's payouts sum
+    /// exactly to its distributable pool (e.g. 9 for TAO)
+    #[arg(long)]
+    decimals: Option<u32>,
+
+    /// Scoring version registry (default: ~/.cliptions/data/scoring_versions.json)
+    #[arg(long)]
+    scoring_versions: Option<PathBuf>,
+
+    /// Scoring version to stamp results with (default: the registry's default version)
+    #[arg(long)]
+    scoring_version: Option<String>,
+}
+
+impl Args {
+    /// Whether the results are written to stdout (`--output-file -`)
+    fn results_to_stdout(&self) -> bool {
+        self.output_file.as_ref().is_some_and(is_stdio)
+    }
+}
+
+/// Print an informational line, on stderr when stdout carries the results
+macro_rules! info {
+    ($args:expr, $($arg:tt)*) => {
+        if $args.results_to_stdout() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
 }
 
 fn main() {
@@ -111,7 +196,8 @@ fn main() {
         match ConfigManager::with_path(config_path) {
             Ok(manager) => {
                 if args.verbose {
-                    println!(
+                    info!(
+                        args,
                         "{} Loaded configuration from {}",
                         "Info:".blue().bold(),
                         config_path.display()
@@ -133,13 +219,14 @@ fn main() {
         match ConfigManager::new() {
             Ok(manager) => {
                 if args.verbose {
-                    println!("{} Using default configuration", "Info:".blue().bold());
+                    info!(args, "{} Using default configuration", "Info:".blue().bold());
                 }
                 Some(manager)
             }
             Err(_) => {
                 if args.verbose {
-                    println!(
+                    info!(
+                        args,
                         "{} No configuration file found, using built-in defaults",
                         "Info:".blue().bold()
                     );
@@ -164,28 +251,36 @@ fn main() {
 
     match results {
         Ok(output_data) => {
+            // With --output-file -, stdout carries only the saved results
+            let to_stdout = args.results_to_stdout();
+
             // Display results
-            if let Err(e) = display_results(&output_data, &args) {
-                eprintln!("{} Failed to display results: {}", "Error:".red().bold(), e);
-                process::exit(1);
+            if !to_stdout {
+                if let Err(e) = display_results(&output_data, &args) {
+                    eprintln!("{} Failed to display results: {}", "Error:".red().bold(), e);
+                    process::exit(1);
+                }
             }
 
             // Save to file if requested
             if let Some(output_file) = &args.output_file {
-                if let Err(e) = save_results(&output_data, output_file, &args.output) {
+                if let Err(e) = save_results(&output_data, output_file, &args.output, &args.currency_label) {
                     eprintln!("{} Failed to save results: {}", "Error:".red().bold(), e);
                     process::exit(1);
                 }
 
-                println!(
-                    "{} Results saved to {}",
-                    "Success:".green().bold(),
-                    output_file.display()
-                );
+                if !to_stdout {
+                    println!(
+                        "{} Results saved to {}",
+                        "Success:".green().bold(),
+                        output_file.display()
+                    );
+                }
             }
 
             if args.verbose {
-                println!(
+                info!(
+                    args,
                     "{} Payout processing completed successfully",
                     "Success:".green().bold()
                 );
@@ -208,8 +303,8 @@ fn validate_inputs(args: &Args) -> Result<(), String> {
         return Err("Cannot specify both --all and --block".to_string());
     }
 
-    // Validate blocks file exists
-    if !args.blocks_file.exists() {
+    // Validate blocks file exists (stdin is read when processing starts)
+    if !is_stdio(&args.blocks_file) && !args.blocks_file.exists() {
         return Err(format!(
             "Blocks file does not exist: {}",
             args.blocks_file.display()
@@ -226,8 +321,25 @@ fn validate_inputs(args: &Args) -> Result<(), String> {
         }
     }
 
+    // Validate similarities file if provided
+    if let Some(similarities_file) = &args.similarities_file {
+        if !similarities_file.exists() {
+            return Err(format!(
+                "Similarities file does not exist: {}",
+                similarities_file.display()
+            ));
+        }
+    }
+
+    // Precomputed similarities replace the embedder, so only that strategy can use them
+    if args.similarities_file.is_some()
+        && args.strategy.as_deref().is_some_and(|name| name != "precomputed")
+    {
+        return Err("--similarities-file can only be used with --strategy precomputed".to_string());
+    }
+
     // Validate output file directory exists if specified
-    if let Some(output_file) = &args.output_file {
+    if let Some(output_file) = args.output_file.as_ref().filter(|f| !is_stdio(f)) {
         if let Some(parent) = output_file.parent() {
             if !parent.exists() {
                 return Err(format!(
@@ -243,22 +355,62 @@ fn validate_inputs(args: &Args) -> Result<(), String> {
         return Err("Minimum participants must be at least 1".to_string());
     }
 
+    // Validate validator fee
+    if !(0.0..100.0).contains(&args.validator_fee_percent) {
+        return Err("Validator fee percentage must be between 0 and 100".to_string());
+    }
+
+    // Validate minimum prize pool
+    if args.min_prize_pool < 0.0 || args.min_prize_pool.is_nan() {
+        return Err("Minimum prize pool must not be negative".to_string());
+    }
+
     Ok(())
 }
 
 fn create_processor_and_process(
     args: &Args,
 ) -> Result<ProcessingResults, Box<dyn std::error::Error>> {
-    let strategy = ClipBatchStrategy::new();
+    if is_stdio(&args.blocks_file) {
+        let store = MemoryBlockStore::from_reader(io::stdin().lock())?;
+        process_from_store(store, args)
+    } else {
+        let store = JsonBlockStore::new(args.blocks_file.to_string_lossy());
+        process_from_store(store, args)
+    }
+}
+
+fn process_from_store<B: BlockStore>(
+    store: B,
+    args: &Args,
+) -> Result<ProcessingResults, Box<dyn std::error::Error>> {
+    let strategy = build_strategy(args)?;
+    if args.verbose {
+        info!(args, "{} Using {} scoring strategy", "Info:".blue().bold(), strategy.name());
+    }
+
+    // Strategies with their own scores, like precomputed similarities, never
+    // call the embedder, so no model is loaded for them
+    if !strategy.uses_embedder() {
+        if args.verbose {
+            info!(args, "{} Scoring without an embedder", "Info:".blue().bold());
+        }
+        let processor = BlockProcessor::with_store(
+            store,
+            MockEmbedder::clip_like(),
+            strategy,
+        );
+        return process_with_processor(processor, args);
+    }
 
     // Create processor and process based on embedder type (defaults to CLIP)
     if args.use_mock {
         if args.verbose {
-            println!("{} Using MockEmbedder for testing", "Info:".blue().bold());
+            info!(args, "{} Using MockEmbedder for testing", "Info:".blue().bold());
         }
         let embedder = MockEmbedder::clip_like();
-        let processor = BlockProcessor::new(
-            args.blocks_file.to_string_lossy().to_string(),
+        let processor = BlockProcessor::with_store(
+            store,
             embedder,
             strategy,
         );
@@ -266,17 +418,18 @@ fn create_processor_and_process(
     } else {
         // Default: Use CLIP embedder
         if let Some(model_path) = &args.clip_model {
-            match ClipEmbedder::from_path(&model_path.to_string_lossy()) {
+            match ClipEmbedder::new_in(model_path) {
                 Ok(embedder) => {
                     if args.verbose {
-                        println!(
+                        info!(
+                            args,
                             "{} Using CLIP embedder from {}",
                             "Info:".blue().bold(),
                             model_path.display()
                         );
                     }
-                    let processor = BlockProcessor::new(
-                        args.blocks_file.to_string_lossy().to_string(),
+                    let processor = BlockProcessor::with_store(
+                        store,
                         embedder,
                         strategy,
                     );
@@ -294,10 +447,10 @@ fn create_processor_and_process(
             match ClipEmbedder::new() {
                 Ok(embedder) => {
                     if args.verbose {
-                        println!("{} Using default CLIP embedder", "Info:".blue().bold());
+                        info!(args, "{} Using default CLIP embedder", "Info:".blue().bold());
                     }
-                    let processor = BlockProcessor::new(
-                        args.blocks_file.to_string_lossy().to_string(),
+                    let processor = BlockProcessor::with_store(
+                        store,
                         embedder,
                         strategy,
                     );
@@ -314,12 +467,62 @@ fn create_processor_and_process(
     }
 }
 
-fn process_with_processor<E: EmbedderTrait>(
-    processor: BlockProcessor<E, ClipBatchStrategy>,
+/// Build the strategy named by `--strategy` from the built-in registry
+///
+/// `--similarities-file` is passed to the strategy as its `similarities_file` parameter.
+fn build_strategy(args: &Args) -> Result<Box<dyn ScoringStrategy>, Box<dyn std::error::Error>> {
+    let mut params: StrategyParams = args.strategy_param.iter().cloned().collect();
+    let name = match (&args.strategy, &args.similarities_file) {
+        (Some(name), _) => name.as_str(),
+        (None, Some(_)) => "precomputed",
+        (None, None) => StrategyRegistry::DEFAULT_STRATEGY,
+    };
+    if let Some(similarities_file) = &args.similarities_file {
+        params.insert(
+            "similarities_file".to_string(),
+            serde_json::Value::String(similarities_file.to_string_lossy().into_owned()),
+        );
+    }
+    Ok(StrategyRegistry::with_builtin().create(name, &params)?)
+}
+
+/// Scoring version named by `--scoring-version`, else the registry's default
+///
+/// Without a registry file there is no version, and results are not stamped.
+fn resolve_scoring_version(
+    args: &Args,
+) -> Result<Option<ScoringVersion>, Box<dyn std::error::Error>> {
+    let path = match &args.scoring_versions {
+        Some(path) => path.clone(),
+        None => ScoringVersionRegistry::default_path()?,
+    };
+    let registry = ScoringVersionRegistry::load(&path)?;
+    Ok(registry.resolve(args.scoring_version.as_deref())?.cloned())
+}
+
+fn process_with_processor<E: EmbedderTrait, S: ScoringStrategy, B: BlockStore>(
+    processor: BlockProcessor<E, S, B>,
     args: &Args,
 ) -> Result<ProcessingResults, Box<dyn std::error::Error>> {
+    let mut processor = processor.with_payout_config(PayoutConfig {
+        validator_fee_percent: args.validator_fee_percent,
+        // Blocks short of --min-participants are skipped after scoring instead
+        minimum_players: 1,
+        curve: args.curve,
+        decimals: args.decimals,
+        currency_label: args.currency_label.clone(),
+        min_prize_pool: args.min_prize_pool,
+        ..PayoutConfig::default()
+    })?;
+    if let Some(version) = resolve_scoring_version(args)? {
+        if args.verbose {
+            info!(args, "{} Using scoring version {}", "Info:".blue().bold(), version.id);
+        }
+        processor = processor.with_scoring_version(version);
+    }
     if args.all {
-        process_all_blocks(processor, args)
+        // Scoring every block with CLIP can take minutes; show progress on stderr
+        process_all_blocks(processor.with_progress_reporter(TerminalProgress::new()), args)
     } else if let Some(block_num) = &args.block {
         process_single_block(processor, block_num, args)
     } else {
@@ -327,29 +530,38 @@ fn process_with_processor<E: EmbedderTrait>(
     }
 }
 
+/// Block number, its scoring results, and the validator fee taken from its pool
+type BlockPayouts = (String, Vec<cliptions_core::types::ScoringResult>, f64);
+
 #[derive(Debug)]
 struct ProcessingResults {
-    blocks: Vec<(String, Vec<cliptions_core::types::ScoringResult>)>,
+    blocks: Vec<BlockPayouts>,
     total_blocks_processed: usize,
     total_participants: usize,
     total_payout: f64,
+    total_validator_fee: f64,
+    /// Committed participants who never revealed, across all processed blocks
+    total_unrevealed: usize,
     errors: Vec<String>,
 }
 
 fn process_all_blocks(
-    mut processor: BlockProcessor<impl EmbedderTrait, ClipBatchStrategy>,
+    mut processor: BlockProcessor<impl EmbedderTrait, impl ScoringStrategy, impl BlockStore>,
     args: &Args,
 ) -> Result<ProcessingResults, Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("{} Processing all blocks...", "Info:".blue().bold());
+        info!(args, "{} Processing all blocks...", "Info:".blue().bold());
     }
 
     let all_results = processor.process_all_blocks()?;
+    print_warnings(processor.take_warnings());
     let mut results = ProcessingResults {
         blocks: Vec::new(),
         total_blocks_processed: 0,
         total_participants: 0,
         total_payout: 0.0,
+        total_validator_fee: 0.0,
+        total_unrevealed: 0,
         errors: Vec::new(),
     };
 
@@ -359,7 +571,8 @@ fn process_all_blocks(
         // Check max blocks limit
         if args.max_blocks > 0 && processed_count >= args.max_blocks {
             if args.verbose {
-                println!(
+                info!(
+                    args,
                     "{} Reached maximum blocks limit ({})",
                     "Info:".blue().bold(),
                     args.max_blocks
@@ -371,7 +584,8 @@ fn process_all_blocks(
         // Check minimum participants requirement
         if block_results.len() < args.min_participants {
             if args.verbose {
-                println!(
+                info!(
+                    args,
                     "{} Skipping block {} (only {} participants, minimum {})",
                     "Info:".blue().bold(),
                     block_num,
@@ -383,22 +597,27 @@ fn process_all_blocks(
         }
 
         let block_payout: f64 = block_results.iter().filter_map(|r| r.payout).sum();
+        let validator_fee = processor.validator_fee(&block_num)?;
 
         results
             .blocks
-            .push((block_num.clone(), block_results.clone()));
+            .push((block_num.clone(), block_results.clone(), validator_fee));
         results.total_blocks_processed += 1;
         results.total_participants += block_results.len();
         results.total_payout += block_payout;
+        results.total_validator_fee += validator_fee;
+        results.total_unrevealed += processor.unrevealed_participants(&block_num)?.len();
         processed_count += 1;
 
         if args.verbose {
-            println!(
-                "{} Processed block {} ({} participants, {:.9} TAO)",
+            info!(
+                args,
+                "{} Processed block {} ({} participants, {:.9} {})",
                 "Info:".blue().bold(),
                 block_num,
                 block_results.len(),
-                block_payout
+                block_payout,
+                args.currency_label
             );
         }
     }
@@ -407,15 +626,16 @@ fn process_all_blocks(
 }
 
 fn process_single_block(
-    mut processor: BlockProcessor<impl EmbedderTrait, ClipBatchStrategy>,
+    mut processor: BlockProcessor<impl EmbedderTrait, impl ScoringStrategy, impl BlockStore>,
     block_num: &str,
     args: &Args,
 ) -> Result<ProcessingResults, Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("{} Processing block: {}", "Info:".blue().bold(), block_num);
+        info!(args, "{} Processing block: {}", "Info:".blue().bold(), block_num);
     }
 
     let block_results = processor.process_block_payouts(block_num)?;
+    print_warnings(processor.take_warnings());
 
     // Check minimum participants requirement
     if block_results.len() < args.min_participants {
@@ -429,28 +649,41 @@ fn process_single_block(
     }
 
     let block_payout: f64 = block_results.iter().filter_map(|r| r.payout).sum();
+    let validator_fee = processor.validator_fee(block_num)?;
+    let unrevealed = processor.unrevealed_participants(block_num)?.len();
 
     let results = ProcessingResults {
-        blocks: vec![(block_num.to_string(), block_results.clone())],
+        blocks: vec![(block_num.to_string(), block_results.clone(), validator_fee)],
         total_blocks_processed: 1,
         total_participants: block_results.len(),
         total_payout: block_payout,
+        total_validator_fee: validator_fee,
+        total_unrevealed: unrevealed,
         errors: Vec::new(),
     };
 
     if args.verbose {
-        println!(
-            "{} Processed block {} ({} participants, {:.9} TAO)",
+        info!(
+            args,
+            "{} Processed block {} ({} participants, {:.9} {})",
             "Info:".blue().bold(),
             block_num,
             block_results.len(),
-            block_payout
+            block_payout,
+            args.currency_label
         );
     }
 
     Ok(results)
 }
 
+/// Report processor warnings on stderr, keeping JSON and CSV output on stdout clean
+fn print_warnings(warnings: Vec<String>) {
+    for warning in warnings {
+        eprintln!("{} {}", "Warning:".yellow().bold(), warning);
+    }
+}
+
 fn display_results(
     results: &ProcessingResults,
     args: &Args,
@@ -475,7 +708,7 @@ fn display_table_format(
         return Ok(());
     }
 
-    for (block_num, block_results) in &results.blocks {
+    for (block_num, block_results, validator_fee) in &results.blocks {
         println!("\n{} {}", "Block:".bold().blue(), block_num.bright_white());
         println!("Participants: {}", block_results.len());
 
@@ -498,7 +731,7 @@ fn display_table_format(
                 println!("     Score: {:.4}", result.effective_score());
 
                 if let Some(payout) = result.payout {
-                    println!("     Payout: {:.9} TAO", payout);
+                    println!("     Payout: {:.9} {}", payout, args.currency_label);
                 } else {
                     println!("     Payout: N/A");
                 }
@@ -514,14 +747,22 @@ fn display_table_format(
 
         let block_payout: f64 = block_results.iter().filter_map(|r| r.payout).sum();
 
-                    println!("Block Total: {:.9} TAO", block_payout);
+        println!("Validator fee: {:.9} {}", validator_fee, args.currency_label);
+        println!("Block Total: {:.9} {}", block_payout, args.currency_label);
     }
 
     println!("\n{}", "=".repeat(80));
     println!("{} {}", "Summary:".bold(), "");
     println!("Blocks Processed: {}", results.total_blocks_processed);
     println!("Total Participants: {}", results.total_participants);
-    println!("Total Payouts: {:.9} TAO", results.total_payout);
+    println!("Validator fee: {:.9} {}", results.total_validator_fee, args.currency_label);
+    println!("Total Payouts: {:.9} {}", results.total_payout, args.currency_label);
+    if results.total_unrevealed > 0 {
+        println!(
+            "Unrevealed Commitments: {} (not scored or paid)",
+            results.total_unrevealed
+        );
+    }
 
     if !results.errors.is_empty() {
         println!(
@@ -543,7 +784,7 @@ fn display_json_format(results: &ProcessingResults) -> Result<(), Box<dyn std::e
     let blocks_data: Vec<serde_json::Value> = results
         .blocks
         .iter()
-        .map(|(block_num, block_results)| {
+        .map(|(block_num, block_results, validator_fee)| {
             let participants: Vec<serde_json::Value> = block_results
                 .iter()
                 .map(|result| {
@@ -553,7 +794,8 @@ fn display_json_format(results: &ProcessingResults) -> Result<(), Box<dyn std::e
                         "guess": result.participant.guess.text,
                         "score": result.effective_score(),
                         "rank": result.rank,
-                        "payout": result.payout
+                        "payout": result.payout,
+                        "currency": result.participant.payout.currency
                     })
                 })
                 .collect();
@@ -564,6 +806,7 @@ fn display_json_format(results: &ProcessingResults) -> Result<(), Box<dyn std::e
                 "block_num": block_num,
                 "participants": participants,
                 "participant_count": block_results.len(),
+                "validator_fee": validator_fee,
                 "total_payout": block_payout
             })
         })
@@ -576,6 +819,8 @@ fn display_json_format(results: &ProcessingResults) -> Result<(), Box<dyn std::e
             "total_blocks_processed": results.total_blocks_processed,
             "total_participants": results.total_participants,
             "total_payout": results.total_payout,
+            "total_validator_fee": results.total_validator_fee,
+            "total_unrevealed": results.total_unrevealed,
             "errors": results.errors
         }),
     );
@@ -591,36 +836,51 @@ fn display_json_format(results: &ProcessingResults) -> Result<(), Box<dyn std::e
 }
 
 fn display_csv_format(results: &ProcessingResults) -> Result<(), Box<dyn std::error::Error>> {
-    println!("block_num,username,user_id,guess,score,rank,payout");
+    print!("{}", csv_content(results)?);
+    Ok(())
+}
 
-    for (block_num, block_results) in &results.blocks {
+/// Results as RFC 4180 CSV, quoting fields that contain quotes, commas or newlines
+///
+/// Each row repeats its block's validator fee, so the fee and payouts of a
+/// block add up to its prize pool.
+fn csv_content(results: &ProcessingResults) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "block_num", "username", "user_id", "guess", "score", "rank", "payout", "currency",
+        "validator_fee",
+    ])?;
+
+    for (block_num, block_results, validator_fee) in &results.blocks {
+        let fee_str = format!("{:.9}", validator_fee);
         for result in block_results {
-            let escaped_guess = result.participant.guess.text.replace("\"", "\"\"");
             let rank_str = result.rank.map_or("".to_string(), |r| r.to_string());
             let payout_str = result
                 .payout
                 .map_or("".to_string(), |p| format!("{:.9}", p));
 
-            println!(
-                "{},\"{}\",\"{}\",\"{}\",{:.6},{},{}",
-                block_num,
-                result.participant.username,
-                result.participant.social_id,
-                escaped_guess,
-                result.effective_score(),
-                rank_str,
-                payout_str
-            );
+            writer.write_record([
+                block_num.as_str(),
+                &result.participant.username,
+                &result.participant.social_id,
+                &result.participant.guess.text,
+                &format!("{:.6}", result.effective_score()),
+                &rank_str,
+                &payout_str,
+                &result.participant.payout.currency,
+                &fee_str,
+            ])?;
         }
     }
 
-    Ok(())
+    Ok(String::from_utf8(writer.into_inner()?)?)
 }
 
 fn save_results(
     results: &ProcessingResults,
     output_file: &PathBuf,
     format: &str,
+    currency_label: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let content = match format {
         "json" => {
@@ -629,7 +889,7 @@ fn save_results(
             let blocks_data: Vec<serde_json::Value> = results
                 .blocks
                 .iter()
-                .map(|(block_num, block_results)| {
+                .map(|(block_num, block_results, validator_fee)| {
                     let participants: Vec<serde_json::Value> = block_results
                         .iter()
                         .map(|result| {
@@ -639,7 +899,8 @@ fn save_results(
                                 "guess": result.participant.guess.text,
                                 "score": result.effective_score(),
                                 "rank": result.rank,
-                                "payout": result.payout
+                                "payout": result.payout,
+                                "currency": result.participant.payout.currency
                             })
                         })
                         .collect();
@@ -650,6 +911,7 @@ fn save_results(
                         "block_num": block_num,
                         "participants": participants,
                         "participant_count": block_results.len(),
+                        "validator_fee": validator_fee,
                         "total_payout": block_payout
                     })
                 })
@@ -662,6 +924,8 @@ fn save_results(
                     "total_blocks_processed": results.total_blocks_processed,
                     "total_participants": results.total_participants,
                     "total_payout": results.total_payout,
+                    "total_validator_fee": results.total_validator_fee,
+                    "total_unrevealed": results.total_unrevealed,
                     "errors": results.errors
                 }),
             );
@@ -672,38 +936,13 @@ fn save_results(
 
             serde_json::to_string_pretty(&output)?
         }
-        "csv" => {
-            let mut content = String::from("block_num,username,user_id,guess,score,rank,payout\n");
-
-            for (block_num, block_results) in &results.blocks {
-                for result in block_results {
-                    let escaped_guess = result.participant.guess.text.replace("\"", "\"\"");
-                    let rank_str = result.rank.map_or("".to_string(), |r| r.to_string());
-                    let payout_str = result
-                        .payout
-                        .map_or("".to_string(), |p| format!("{:.9}", p));
-
-                    content.push_str(&format!(
-                        "{},\"{}\",\"{}\",\"{}\",{:.6},{},{}\n",
-                        block_num,
-                        result.participant.username,
-                        result.participant.social_id,
-                        escaped_guess,
-                        result.effective_score(),
-                        rank_str,
-                        payout_str
-                    ));
-                }
-            }
-
-            content
-        }
+        "csv" => csv_content(results)?,
         "table" => {
             let mut content = String::from("Payout Processing Results\n");
             content.push_str(&"=".repeat(50));
             content.push('\n');
 
-            for (block_num, block_results) in &results.blocks {
+            for (block_num, block_results, validator_fee) in &results.blocks {
                 content.push_str(&format!("\nBlock: {}\n", block_num));
                 content.push_str(&format!("Participants: {}\n", block_results.len()));
 
@@ -720,13 +959,14 @@ fn save_results(
                     ));
                     content.push_str(&format!("     Score: {:.4}\n", result.effective_score()));
                     if let Some(payout) = result.payout {
-                        content.push_str(&format!("     Payout: {:.9}\n", payout));
+                        content.push_str(&format!("     Payout: {:.9} {}\n", payout, currency_label));
                     }
                     content.push('\n');
                 }
 
                 let block_payout: f64 = block_results.iter().filter_map(|r| r.payout).sum();
-                content.push_str(&format!("Block Total: {:.9}\n", block_payout));
+                content.push_str(&format!("Validator fee: {:.9} {}\n", validator_fee, currency_label));
+                content.push_str(&format!("Block Total: {:.9} {}\n", block_payout, currency_label));
             }
 
             content.push_str(&"=".repeat(50));
@@ -739,14 +979,25 @@ fn save_results(
                 "Total Participants: {}\n",
                 results.total_participants
             ));
-            content.push_str(&format!("Total Payouts: {:.9}\n", results.total_payout));
+            content.push_str(&format!(
+                "Validator fee: {:.9} {}\n",
+                results.total_validator_fee, currency_label
+            ));
+            content.push_str(&format!(
+                "Total Payouts: {:.9} {}\n",
+                results.total_payout, currency_label
+            ));
 
             content
         }
         _ => return Err(format!("Unsupported output format for file save: {}", format).into()),
     };
 
-    fs::write(output_file, content)?;
+    if is_stdio(output_file) {
+        println!("{}", content.trim_end());
+    } else {
+        fs::write(output_file, content)?;
+    }
     Ok(())
 }
 
@@ -768,6 +1019,9 @@ mod tests {
             output_file: None,
             use_mock: false,
             clip_model: None,
+            similarities_file: None,
+            strategy: None,
+            strategy_param: Vec::new(),
             verbose: false,
             no_color: false,
             config: None,
@@ -775,6 +1029,13 @@ mod tests {
             detailed: false,
             min_participants: 1,
             max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
         };
 
         // This will fail if the test file doesn't exist, which is expected
@@ -792,6 +1053,9 @@ mod tests {
             output_file: None,
             use_mock: false,
             clip_model: None,
+            similarities_file: None,
+            strategy: None,
+            strategy_param: Vec::new(),
             verbose: false,
             no_color: false,
             config: None,
@@ -799,6 +1063,13 @@ mod tests {
             detailed: false,
             min_participants: 1,
             max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
         };
 
         let result = validate_inputs(&args);
@@ -818,6 +1089,9 @@ mod tests {
             output_file: None,
             use_mock: false,
             clip_model: None,
+            similarities_file: None,
+            strategy: None,
+            strategy_param: Vec::new(),
             verbose: false,
             no_color: false,
             config: None,
@@ -825,6 +1099,13 @@ mod tests {
             detailed: false,
             min_participants: 1,
             max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
         };
 
         let result = validate_inputs(&args);
@@ -880,6 +1161,9 @@ mod tests {
             output_file: None,
             use_mock: false,
             clip_model: None,
+            similarities_file: None,
+            strategy: None,
+            strategy_param: Vec::new(),
             verbose: false,
             no_color: false,
             config: None,
@@ -887,10 +1171,258 @@ mod tests {
             detailed: false,
             min_participants: 1,
             max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
         };
 
         // Test validation passes
         let result = validate_inputs(&args);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_process_payouts_with_similarities_file() {
+        let target_image = NamedTempFile::new().unwrap();
+        let mut block = BlockData::new(
+            "test_block".to_string(),
+            target_image.path().to_string_lossy().to_string(),
+            "test_social_id".to_string(),
+            60.0,
+        );
+
+        let commitment_gen = CommitmentGenerator::new();
+        for (i, guess) in ["a red car", "a blue boat", "a green tree"].iter().enumerate() {
+            let salt = format!("salt{}", i);
+            let commitment = commitment_gen.generate(guess, &salt).unwrap();
+            block.add_participant(
+                Participant::new(
+                    format!("user{}", i),
+                    format!("user_user{}", i),
+                    Guess::new(guess.to_string()),
+                    commitment,
+                )
+                .with_salt(salt)
+                .mark_verified(),
+            );
+        }
+
+        let blocks_file = NamedTempFile::new().unwrap();
+        let blocks = HashMap::from([("test_block".to_string(), block)]);
+        std::fs::write(blocks_file.path(), serde_json::to_string(&blocks).unwrap()).unwrap();
+
+        let similarities_file = NamedTempFile::new().unwrap();
+        std::fs::write(
+            similarities_file.path(),
+            r#"{"a red car": 30.0, "a blue boat": 50.0, "a green tree": 20.0}"#,
+        )
+        .unwrap();
+
+        let args = Args {
+            all: false,
+            block: Some("test_block".to_string()),
+            blocks_file: blocks_file.path().to_path_buf(),
+            output: "json".to_string(),
+            output_file: None,
+            use_mock: false,
+            clip_model: None,
+            similarities_file: Some(similarities_file.path().to_path_buf()),
+            strategy: None,
+            strategy_param: Vec::new(),
+            verbose: false,
+            no_color: false,
+            config: None,
+            continue_on_error: false,
+            detailed: false,
+            min_participants: 1,
+            max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
+        };
+        assert!(validate_inputs(&args).is_ok());
+
+        let results = create_processor_and_process(&args).unwrap();
+        let (_, block_results, _) = &results.blocks[0];
+        let ranked: Vec<(&str, usize, f64)> = block_results
+            .iter()
+            .map(|r| (r.participant.guess.text.as_str(), r.rank.unwrap(), r.payout.unwrap()))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                ("a blue boat", 1, 30.0),
+                ("a red car", 2, 20.0),
+                ("a green tree", 3, 10.0),
+            ]
+        );
+        assert!(block_results
+            .iter()
+            .all(|r| r.strategy_name.as_deref() == Some("Precomputed")));
+        assert_eq!(results.total_payout, 60.0);
+    }
+
+    #[test]
+    fn test_process_payouts_from_stdin() {
+        let target_image = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/City street with vintage cars and neon signs at night.png"
+        );
+        let mut block = BlockData::new(
+            "test_block".to_string(),
+            target_image.to_string(),
+            "test_social_id".to_string(),
+            30.0,
+        );
+        let commitment_gen = CommitmentGenerator::new();
+        for (i, guess) in ["a red car", "a blue boat"].iter().enumerate() {
+            let salt = format!("salt{}", i);
+            let commitment = commitment_gen.generate(guess, &salt).unwrap();
+            block.add_participant(
+                Participant::new(
+                    format!("user{}", i),
+                    format!("user_user{}", i),
+                    Guess::new(guess.to_string()),
+                    commitment,
+                )
+                .with_salt(salt)
+                .mark_verified(),
+            );
+        }
+        let json = serde_json::to_string(&HashMap::from([("test_block".to_string(), block)]))
+            .unwrap();
+
+        let args = Args {
+            all: false,
+            block: Some("test_block".to_string()),
+            blocks_file: PathBuf::from("-"),
+            output: "json".to_string(),
+            output_file: Some(PathBuf::from("-")),
+            use_mock: true,
+            clip_model: None,
+            similarities_file: None,
+            strategy: None,
+            strategy_param: Vec::new(),
+            verbose: false,
+            no_color: false,
+            config: None,
+            continue_on_error: false,
+            detailed: false,
+            min_participants: 1,
+            max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
+        };
+        // "-" needn't exist as a file
+        assert!(validate_inputs(&args).is_ok());
+
+        let store = MemoryBlockStore::from_reader(json.as_bytes()).unwrap();
+        let results = process_from_store(store, &args).unwrap();
+        assert_eq!(results.total_blocks_processed, 1);
+        assert_eq!(results.total_participants, 2);
+        assert!((results.total_payout - 30.0).abs() < 1e-9);
+        assert!(!PathBuf::from("-").exists());
+    }
+
+    #[test]
+    fn test_csv_round_trips_guess_with_newline_and_comma() {
+        let guess = "a cat, on a \"mat\"\nunder the stars";
+        let mut participant = Participant::new(
+            "user1".to_string(),
+            "user, \"one\"".to_string(),
+            Guess::new(guess.to_string()),
+            "commitment".to_string(),
+        );
+        // Blocks files from before guess normalization can hold raw newlines
+        participant.guess.text = guess.to_string();
+        let mut result = cliptions_core::types::ScoringResult::new(participant, 0.5);
+        result.rank = Some(1);
+        result.payout = Some(10.0);
+        let results = ProcessingResults {
+            blocks: vec![("block1".to_string(), vec![result], 1.5)],
+            total_blocks_processed: 1,
+            total_participants: 1,
+            total_payout: 10.0,
+            total_validator_fee: 1.5,
+            total_unrevealed: 0,
+            errors: Vec::new(),
+        };
+
+        let content = csv_content(&results).unwrap();
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(&records[0][1], "user, \"one\"");
+        assert_eq!(&records[0][3], guess);
+        assert_eq!(&records[0][5], "1");
+        assert_eq!(&records[0][7], DEFAULT_CURRENCY_LABEL);
+        assert_eq!(&records[0][8], "1.500000000");
+    }
+
+    #[test]
+    fn test_build_strategy_by_name() {
+        let mut args = Args {
+            all: false,
+            block: Some("block1".to_string()),
+            blocks_file: PathBuf::from("blocks.json"),
+            output: "table".to_string(),
+            output_file: None,
+            use_mock: true,
+            clip_model: None,
+            similarities_file: None,
+            strategy: None,
+            strategy_param: Vec::new(),
+            verbose: false,
+            no_color: false,
+            config: None,
+            continue_on_error: false,
+            detailed: false,
+            min_participants: 1,
+            max_blocks: 0,
+            validator_fee_percent: 0.0,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            scoring_versions: None,
+            scoring_version: None,
+        };
+        assert_eq!(build_strategy(&args).unwrap().name(), "ClipBatch");
+
+        args.strategy = Some("blended".to_string());
+        args.strategy_param = vec![
+            parse_strategy_param("alpha=0.7").unwrap(),
+            parse_strategy_param("reference=a red car").unwrap(),
+        ];
+        assert_eq!(args.strategy_param[1].1, serde_json::json!("a red car"));
+        assert_eq!(build_strategy(&args).unwrap().name(), "Blended");
+
+        // A precomputed strategy named with --strategy doesn't need an embedder
+        let similarities = NamedTempFile::new().unwrap();
+        fs::write(similarities.path(), r#"{"a red car": 40.0}"#).unwrap();
+        args.strategy = Some("precomputed".to_string());
+        args.strategy_param = vec![parse_strategy_param(&format!(
+            "similarities_file={}",
+            similarities.path().display()
+        ))
+        .unwrap()];
+        assert!(!build_strategy(&args).unwrap().uses_embedder());
+
+        args.strategy = Some("unknown".to_string());
+        let err = build_strategy(&args).err().unwrap().to_string();
+        assert!(err.contains("blended, clip-batch, precomputed"), "{}", err);
+    }
 }
@@ -5,7 +5,7 @@
 use clap::Parser;
 use cliptions_core::config::ConfigManager;
 use std::env;
-use twitter_api::{TwitterApi, TwitterClient, TwitterError};
+use twitter_api::{LatestTweetOptions, TwitterClient, TwitterError};
 
 #[derive(Parser)]
 #[command(name = "twitter_latest_tweet")]
@@ -19,6 +19,14 @@ struct Args {
     #[arg(long)]
     exclude_retweets_replies: bool,
 
+    /// Tweets fetched per timeline page (5-100)
+    #[arg(long, default_value = "10")]
+    page_size: u32,
+
+    /// Pages to search before giving up on finding a qualifying tweet
+    #[arg(long, default_value = "3")]
+    max_pages: u32,
+
     /// Show verbose output
     #[arg(short, long)]
     verbose: bool,
@@ -50,12 +58,18 @@ async fn main() {
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(config);
 
     // Get the latest tweet
+    let options = LatestTweetOptions {
+        page_size: args.page_size,
+        max_pages: args.max_pages,
+        exclude_retweets_replies: args.exclude_retweets_replies,
+    };
     let result = client
-        .get_latest_tweet(&args.username, args.exclude_retweets_replies)
+        .get_latest_tweet_with_options(&args.username, &options)
         .await;
 
     match result {
@@ -88,6 +102,7 @@ async fn main() {
             println!("❌ No tweets found for user @{}", args.username);
             if args.exclude_retweets_replies {
                 println!("💡 Try without --exclude-retweets-replies to see all tweets");
+                println!("💡 Or search further back with --max-pages");
             }
         }
         Err(TwitterError::ApiError { status, message }) => {
@@ -97,6 +112,14 @@ async fn main() {
             }
             std::process::exit(1);
         }
+        Err(TwitterError::ApiStructured { status, title, detail, .. }) => {
+            let message = format!("{}: {}", title, detail);
+            println!("❌ Twitter API error: {} - {}", status, message);
+            if status == 404 {
+                println!("💡 Make sure the username exists and is spelled correctly");
+            }
+            std::process::exit(1);
+        }
         Err(TwitterError::NetworkError(e)) => {
             println!("❌ Network error: {}", e);
             std::process::exit(1);
@@ -117,6 +140,14 @@ async fn main() {
             println!("❌ Invalid input: {}", e);
             std::process::exit(1);
         }
+        Err(TwitterError::DuplicateContent(e)) => {
+            println!("❌ Duplicate content: {}", e);
+            std::process::exit(1);
+        }
+        Err(TwitterError::Unsupported(e)) => {
+            println!("❌ Not supported: {}", e);
+            std::process::exit(1);
+        }
         Err(TwitterError::FileError(e)) => {
             println!("❌ File error: {}", e);
             std::process::exit(1);
@@ -5,7 +5,6 @@
 //! Supports text tweets, replies, and image attachments
 
 use chrono::{Duration as ChronoDuration, Utc};
-use chrono_tz;
 use clap::Parser;
 use cliptions_core::config::ConfigManager;
 use cliptions_core::social::AnnouncementFormatter;
@@ -66,25 +65,8 @@ async fn main() {
 
     if args.verbose {
         println!("✅ Loaded config from: {}", &args.config);
-        println!("TwitterConfig being sent to API: {{");
-        println!("  api_key: {}", &twitter.api_key);
-        println!(
-            "  api_secret: {}...",
-            &twitter.api_secret.chars().take(4).collect::<String>()
-        );
-        println!(
-            "  access_token: {}...",
-            &twitter.access_token.chars().take(4).collect::<String>()
-        );
-        println!(
-            "  access_token_secret: {}...",
-            &twitter
-                .access_token_secret
-                .chars()
-                .take(4)
-                .collect::<String>()
-        );
-        println!("}}\n");
+        // Debug output redacts the keys and tokens
+        println!("TwitterConfig being sent to API: {:#?}\n", twitter);
         println!("[DEBUG] Calling post_tweet_flexible from twitter_post.rs");
         println!("  reply_to: {:?}", args.reply_to);
         println!("  image: {:?}", args.image);
@@ -97,14 +79,9 @@ async fn main() {
         // Calculate target time (hours from now)
         let target_time = Utc::now() + ChronoDuration::hours(hours as i64);
 
-        // Format target time as "2025-04-01 | 16:30:57 | EST"
-        let eastern = chrono_tz::US::Eastern;
-        let target_time_eastern = target_time.with_timezone(&eastern);
-        let formatted_target_time = format!(
-            "{} | {} | EST",
-            target_time_eastern.format("%Y-%m-%d"),
-            target_time_eastern.format("%H:%M:%S")
-        );
+        // Format target time as "2025-04-01 | 16:30:57 | EDT (2025-04-01T20:30:57Z)"
+        let formatter = AnnouncementFormatter::new();
+        let formatted_target_time = formatter.format_deadline(target_time);
 
         // Create announcement data
         let announcement_data = cliptions_core::social::AnnouncementData {
@@ -115,10 +92,10 @@ async fn main() {
             message: String::new(), // Not used for commitment announcements
             prize_pool: None,
             livestream_url: args.livestream.clone(), // Optional - None for reveals, Some for commitments
+            deadline: Some(target_time),
         };
 
         // Format the announcement based on state
-        let formatter = AnnouncementFormatter::new();
         if state.to_lowercase() == "revealsopen" {
             // For reveals announcements, use the dedicated reveals formatter
             formatter.create_reveals_announcement(&announcement_data)
@@ -156,6 +133,7 @@ async fn main() {
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(config);
 
@@ -192,6 +170,11 @@ async fn main() {
             println!("❌ Twitter API error: {} - {}", status, message);
             std::process::exit(1);
         }
+        Err(TwitterError::ApiStructured { status, title, detail, .. }) => {
+            let message = format!("{}: {}", title, detail);
+            println!("❌ Twitter API error: {} - {}", status, message);
+            std::process::exit(1);
+        }
         Err(TwitterError::NetworkError(e)) => {
             println!("❌ Network error: {}", e);
             std::process::exit(1);
@@ -212,6 +195,15 @@ async fn main() {
             println!("❌ Invalid input: {}", e);
             std::process::exit(1);
         }
+        Err(TwitterError::DuplicateContent(e)) => {
+            println!("❌ Duplicate content: {}", e);
+            println!("💡 This text was already posted; the existing tweet was left as is");
+            std::process::exit(1);
+        }
+        Err(TwitterError::Unsupported(e)) => {
+            println!("❌ Not supported: {}", e);
+            std::process::exit(1);
+        }
         Err(TwitterError::FileError(e)) => {
             println!("❌ File error: {}", e);
             std::process::exit(1);
@@ -5,7 +5,7 @@
 use clap::Parser;
 use cliptions_core::config::ConfigManager;
 use std::env;
-use twitter_api::{TwitterApi, TwitterClient, TwitterError};
+use twitter_api::{SearchScope, TwitterApi, TwitterClient, TwitterError};
 
 #[derive(Parser)]
 #[command(name = "twitter_search_replies")]
@@ -47,12 +47,13 @@ async fn main() {
         api_secret: twitter.api_secret.clone(),
         access_token: twitter.access_token.clone(),
         access_token_secret: twitter.access_token_secret.clone(),
+        bearer_token: twitter.bearer_token.clone(),
     };
     let client = TwitterClient::new(config);
 
     // Search for replies
     let result = client
-        .search_replies(&args.tweet_id, args.max_results)
+        .search_replies(&args.tweet_id, args.max_results, SearchScope::Recent, None)
         .await;
 
     match result {
@@ -101,6 +102,14 @@ async fn main() {
             }
             std::process::exit(1);
         }
+        Err(TwitterError::ApiStructured { status, title, detail, .. }) => {
+            let message = format!("{}: {}", title, detail);
+            println!("❌ Twitter API error: {} - {}", status, message);
+            if status == 404 {
+                println!("💡 Make sure the tweet ID exists and is correct");
+            }
+            std::process::exit(1);
+        }
         Err(TwitterError::NetworkError(e)) => {
             println!("❌ Network error: {}", e);
             std::process::exit(1);
@@ -121,6 +130,14 @@ async fn main() {
             println!("❌ Invalid input: {}", e);
             std::process::exit(1);
         }
+        Err(TwitterError::DuplicateContent(e)) => {
+            println!("❌ Duplicate content: {}", e);
+            std::process::exit(1);
+        }
+        Err(TwitterError::Unsupported(e)) => {
+            println!("❌ Not supported: {}", e);
+            std::process::exit(1);
+        }
         Err(TwitterError::FileError(e)) => {
             println!("❌ File error: {}", e);
             std::process::exit(1);
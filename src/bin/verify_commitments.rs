@@ -7,12 +7,15 @@
 use clap::Parser;
 use colored::Colorize;
 use std::fs;
+use std::io;
 use std::path::PathBuf;
 use std::process;
 
 use cliptions_core::config::ConfigManager;
 use cliptions_core::embedder::{ClipEmbedder, EmbedderTrait, MockEmbedder};
 use cliptions_core::block_processor::BlockProcessor;
+use cliptions_core::block_store::{is_stdio, BlockStore, JsonBlockStore, MemoryBlockStore};
+use cliptions_core::commitment::{CommitmentVerification, FailReason};
 use cliptions_core::scoring::ClipBatchStrategy;
 
 #[derive(Parser)]
@@ -42,6 +45,12 @@ Examples:
   
   # Batch verify multiple blocks
   verify_commitments --all --continue-on-error --output csv
+
+  # Show why each invalid commitment failed
+  verify_commitments block1 --explain
+
+  # Read blocks from stdin and write JSON results to stdout only
+  cat blocks.json | verify_commitments block1 --blocks-file - --output json --output-file -
 ")]
 struct Args {
     /// Block ID to verify (required unless --all is specified)
@@ -51,7 +60,7 @@ struct Args {
     #[arg(long)]
     all: bool,
 
-    /// Path to blocks file
+    /// Path to blocks file, or - to read blocks from stdin (nothing is written back)
     #[arg(long, default_value = "blocks.json")]
     blocks_file: PathBuf,
 
@@ -59,7 +68,7 @@ struct Args {
     #[arg(long, short, default_value = "table", value_parser = ["table", "json", "csv"])]
     output: String,
 
-    /// Save results to file
+    /// Save results to file, or - to write them to stdout only
     #[arg(long, short)]
     output_file: Option<PathBuf>,
 
@@ -67,7 +76,7 @@ struct Args {
     #[arg(long)]
     use_mock: bool,
 
-    /// Path to CLIP model directory (optional, uses default if not specified)
+    /// CLIP model directory (default: $CLIPTIONS_MODEL_DIR or ~/.cliptions/models)
     #[arg(long)]
     clip_model: Option<PathBuf>,
 
@@ -99,6 +108,10 @@ struct Args {
     #[arg(long)]
     invalid_only: bool,
 
+    /// Explain why each invalid commitment failed (hash mismatch, missing salt, malformed commitment)
+    #[arg(long)]
+    explain: bool,
+
     /// Maximum number of blocks to process (for --all, 0 = unlimited)
     #[arg(long, default_value = "0")]
     max_blocks: usize,
@@ -170,10 +183,15 @@ fn main() {
 
     match results {
         Ok(verification_data) => {
+            // With --output-file -, stdout carries only the saved results
+            let to_stdout = args.output_file.as_ref().is_some_and(is_stdio);
+
             // Display results
-            if let Err(e) = display_results(&verification_data, &args) {
-                eprintln!("{} Failed to display results: {}", "Error:".red().bold(), e);
-                process::exit(1);
+            if !to_stdout {
+                if let Err(e) = display_results(&verification_data, &args) {
+                    eprintln!("{} Failed to display results: {}", "Error:".red().bold(), e);
+                    process::exit(1);
+                }
             }
 
             // Save to file if requested
@@ -183,18 +201,20 @@ fn main() {
                     process::exit(1);
                 }
 
-                println!(
-                    "{} Results saved to {}",
-                    "Success:".green().bold(),
-                    output_file.display()
-                );
+                if !to_stdout {
+                    println!(
+                        "{} Results saved to {}",
+                        "Success:".green().bold(),
+                        output_file.display()
+                    );
+                }
             }
 
             // Check for failures and exit with appropriate code
             let has_failures = verification_data
                 .blocks
                 .iter()
-                .any(|(_, results, _)| results.iter().any(|&r| !r));
+                .any(|(_, results, _)| results.iter().any(|v| !v.valid));
 
             if args.strict && has_failures {
                 eprintln!(
@@ -228,8 +248,8 @@ fn validate_inputs(args: &Args) -> Result<(), String> {
         return Err("Cannot specify both a block ID and --all".to_string());
     }
 
-    // Validate blocks file exists
-    if !args.blocks_file.exists() {
+    // Validate blocks file exists (stdin is read when verification starts)
+    if !is_stdio(&args.blocks_file) && !args.blocks_file.exists() {
         return Err(format!(
             "Blocks file does not exist: {}",
             args.blocks_file.display()
@@ -247,7 +267,7 @@ fn validate_inputs(args: &Args) -> Result<(), String> {
     }
 
     // Validate output file directory exists if specified
-    if let Some(output_file) = &args.output_file {
+    if let Some(output_file) = args.output_file.as_ref().filter(|f| !is_stdio(f)) {
         if let Some(parent) = output_file.parent() {
             if !parent.exists() {
                 return Err(format!(
@@ -263,6 +283,19 @@ fn validate_inputs(args: &Args) -> Result<(), String> {
 
 fn create_processor_and_verify(
     args: &Args,
+) -> Result<VerificationResults, Box<dyn std::error::Error>> {
+    if is_stdio(&args.blocks_file) {
+        let store = MemoryBlockStore::from_reader(io::stdin().lock())?;
+        verify_from_store(store, args)
+    } else {
+        let store = JsonBlockStore::new(args.blocks_file.to_string_lossy());
+        verify_from_store(store, args)
+    }
+}
+
+fn verify_from_store<B: BlockStore>(
+    store: B,
+    args: &Args,
 ) -> Result<VerificationResults, Box<dyn std::error::Error>> {
     let strategy = ClipBatchStrategy::new();
 
@@ -275,8 +308,8 @@ fn create_processor_and_verify(
             );
         }
         let embedder = MockEmbedder::clip_like();
-        let processor = BlockProcessor::new(
-            args.blocks_file.to_string_lossy().to_string(),
+        let processor = BlockProcessor::with_store(
+            store,
             embedder,
             strategy,
         );
@@ -284,7 +317,7 @@ fn create_processor_and_verify(
     } else {
         // Default: Use CLIP embedder
         if let Some(model_path) = &args.clip_model {
-            match ClipEmbedder::from_path(&model_path.to_string_lossy()) {
+            match ClipEmbedder::new_in(model_path) {
                 Ok(embedder) => {
                     if args.verbose {
                         println!(
@@ -293,8 +326,8 @@ fn create_processor_and_verify(
                             model_path.display()
                         );
                     }
-                    let processor = BlockProcessor::new(
-                        args.blocks_file.to_string_lossy().to_string(),
+                    let processor = BlockProcessor::with_store(
+                        store,
                         embedder,
                         strategy,
                     );
@@ -314,8 +347,8 @@ fn create_processor_and_verify(
                     if args.verbose {
                         println!("{} Using default CLIP embedder", "Info:".blue().bold());
                     }
-                    let processor = BlockProcessor::new(
-                        args.blocks_file.to_string_lossy().to_string(),
+                    let processor = BlockProcessor::with_store(
+                        store,
                         embedder,
                         strategy,
                     );
@@ -332,8 +365,8 @@ fn create_processor_and_verify(
     }
 }
 
-fn verify_with_processor<E: EmbedderTrait>(
-    mut processor: BlockProcessor<E, ClipBatchStrategy>,
+fn verify_with_processor<E: EmbedderTrait, B: BlockStore>(
+    mut processor: BlockProcessor<E, ClipBatchStrategy, B>,
     args: &Args,
 ) -> Result<VerificationResults, Box<dyn std::error::Error>> {
     // Load blocks first
@@ -350,7 +383,11 @@ fn verify_with_processor<E: EmbedderTrait>(
 
 #[derive(Debug)]
 struct VerificationResults {
-    blocks: Vec<(String, Vec<bool>, Vec<cliptions_core::types::Participant>)>,
+    blocks: Vec<(
+        String,
+        Vec<CommitmentVerification>,
+        Vec<cliptions_core::types::Participant>,
+    )>,
     total_blocks_processed: usize,
     total_participants: usize,
     total_valid: usize,
@@ -358,8 +395,8 @@ struct VerificationResults {
     errors: Vec<String>,
 }
 
-fn verify_all_blocks<E: EmbedderTrait>(
-    mut processor: BlockProcessor<E, ClipBatchStrategy>,
+fn verify_all_blocks<E: EmbedderTrait, B: BlockStore>(
+    mut processor: BlockProcessor<E, ClipBatchStrategy, B>,
     args: &Args,
 ) -> Result<VerificationResults, Box<dyn std::error::Error>> {
     if args.verbose {
@@ -393,7 +430,7 @@ fn verify_all_blocks<E: EmbedderTrait>(
 
         match process_block_verification(&mut processor, &block_num, args) {
             Ok((verification_results, participants)) => {
-                let valid_count = verification_results.iter().filter(|&&r| r).count();
+                let valid_count = verification_results.iter().filter(|v| v.valid).count();
                 let invalid_count = verification_results.len() - valid_count;
 
                 let verification_len = verification_results.len();
@@ -434,8 +471,8 @@ fn verify_all_blocks<E: EmbedderTrait>(
     Ok(results)
 }
 
-fn verify_single_block<E: EmbedderTrait>(
-    mut processor: BlockProcessor<E, ClipBatchStrategy>,
+fn verify_single_block<E: EmbedderTrait, B: BlockStore>(
+    mut processor: BlockProcessor<E, ClipBatchStrategy, B>,
     block_num: &str,
     args: &Args,
 ) -> Result<VerificationResults, Box<dyn std::error::Error>> {
@@ -446,7 +483,7 @@ fn verify_single_block<E: EmbedderTrait>(
     let (verification_results, participants) =
         process_block_verification(&mut processor, block_num, args)?;
 
-    let valid_count = verification_results.iter().filter(|&&r| r).count();
+    let valid_count = verification_results.iter().filter(|v| v.valid).count();
     let invalid_count = verification_results.len() - valid_count;
 
     let verification_len = verification_results.len();
@@ -472,11 +509,17 @@ fn verify_single_block<E: EmbedderTrait>(
     Ok(results)
 }
 
-fn process_block_verification<E: EmbedderTrait>(
-    processor: &mut BlockProcessor<E, ClipBatchStrategy>,
+fn process_block_verification<E: EmbedderTrait, B: BlockStore>(
+    processor: &mut BlockProcessor<E, ClipBatchStrategy, B>,
     block_num: &str,
     args: &Args,
-) -> Result<(Vec<bool>, Vec<cliptions_core::types::Participant>), Box<dyn std::error::Error>> {
+) -> Result<
+    (
+        Vec<CommitmentVerification>,
+        Vec<cliptions_core::types::Participant>,
+    ),
+    Box<dyn std::error::Error>,
+> {
     // Get block info
     let block = processor.get_block(block_num)?;
 
@@ -504,7 +547,7 @@ fn process_block_verification<E: EmbedderTrait>(
     let participants = block.participants.clone();
 
     // Verify commitments
-    let verification_results = processor.verify_commitments(block_num)?;
+    let verification_results = processor.verify_commitments_detailed(block_num)?;
 
     Ok((verification_results, participants))
 }
@@ -539,7 +582,7 @@ fn display_table_format(
     for (block_num, verification_results, participants) in &results.blocks {
         println!("\n{} {}", "Block:".bold().blue(), block_num.bright_white());
 
-        let valid_count = verification_results.iter().filter(|&&r| r).count();
+        let valid_count = verification_results.iter().filter(|v| v.valid).count();
         let total_count = verification_results.len();
 
         println!(
@@ -548,16 +591,18 @@ fn display_table_format(
             total_count
         );
 
-        if args.detailed && !participants.is_empty() {
+        if (args.detailed || args.explain) && !participants.is_empty() {
             println!("\n{}", "Detailed Verification:".dimmed());
 
-            for (i, (participant, &is_valid)) in participants
+            for (i, (participant, verification)) in participants
                 .iter()
                 .zip(verification_results.iter())
                 .enumerate()
             {
-                // Skip valid commitments if only showing invalid ones
-                if args.invalid_only && is_valid {
+                let is_valid = verification.valid;
+
+                // Skip valid commitments if only showing invalid ones (--explain alone lists failures only)
+                if (args.invalid_only || !args.detailed) && is_valid {
                     continue;
                 }
 
@@ -590,6 +635,12 @@ fn display_table_format(
                         println!("     Status: {}", "Unverified".yellow());
                     }
                 }
+
+                if args.explain {
+                    if let Some(reason) = &verification.reason {
+                        display_fail_reason(reason, participant);
+                    }
+                }
                 println!();
             }
         }
@@ -642,6 +693,32 @@ fn display_table_format(
     Ok(())
 }
 
+fn display_fail_reason(reason: &FailReason, participant: &cliptions_core::types::Participant) {
+    match reason {
+        FailReason::MissingSalt => {
+            println!("     Reason: {}", "Salt was not revealed".red());
+        }
+        FailReason::MalformedCommitment => {
+            println!(
+                "     Reason: {} (expected 64 hex characters, got {} characters)",
+                "Malformed commitment".red(),
+                participant.commitment.chars().count()
+            );
+        }
+        FailReason::InvalidReveal(e) => {
+            println!("     Reason: {} ({})", "Reveal could not be hashed".red(), e);
+        }
+        FailReason::HashMismatch { expected, provided } => {
+            println!(
+                "     Reason: {} (wrong salt or edited guess)",
+                "Hash mismatch".red()
+            );
+            println!("     Expected hash: {}", expected);
+            println!("     Provided hash: {}", provided);
+        }
+    }
+}
+
 fn display_json_format(results: &VerificationResults) -> Result<(), Box<dyn std::error::Error>> {
     let mut output = serde_json::Map::new();
 
@@ -652,7 +729,7 @@ fn display_json_format(results: &VerificationResults) -> Result<(), Box<dyn std:
             let participant_data: Vec<serde_json::Value> = participants
                 .iter()
                 .zip(verification_results.iter())
-                .map(|(participant, &is_valid)| {
+                .map(|(participant, verification)| {
                     serde_json::json!({
                         "username": participant.username,
                         "user_id": participant.social_id,
@@ -660,12 +737,13 @@ fn display_json_format(results: &VerificationResults) -> Result<(), Box<dyn std:
                         "commitment": participant.commitment,
                         "salt": participant.salt,
                         "is_verified": participant.verified,
-                        "commitment_valid": is_valid
+                        "commitment_valid": verification.valid,
+                        "failure_reason": verification.reason.as_ref().map(|r| r.to_string())
                     })
                 })
                 .collect();
 
-            let valid_count = verification_results.iter().filter(|&&r| r).count();
+            let valid_count = verification_results.iter().filter(|v| v.valid).count();
 
             serde_json::json!({
                 "block_num": block_num,
@@ -705,31 +783,47 @@ fn display_json_format(results: &VerificationResults) -> Result<(), Box<dyn std:
 }
 
 fn display_csv_format(results: &VerificationResults) -> Result<(), Box<dyn std::error::Error>> {
-    println!("block_num,username,user_id,guess,commitment,salt,is_verified,commitment_valid");
+    print!("{}", csv_content(results)?);
+    Ok(())
+}
+
+/// Results as RFC 4180 CSV, quoting fields that contain quotes, commas or newlines
+fn csv_content(results: &VerificationResults) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "block_num",
+        "username",
+        "user_id",
+        "guess",
+        "commitment",
+        "salt",
+        "is_verified",
+        "commitment_valid",
+        "failure_reason",
+    ])?;
 
     for (block_num, verification_results, participants) in &results.blocks {
-        for (participant, &is_valid) in participants.iter().zip(verification_results.iter()) {
-            let escaped_guess = participant.guess.text.replace("\"", "\"\"");
-            let salt_str = participant
-                .salt
+        for (participant, verification) in participants.iter().zip(verification_results.iter()) {
+            let failure_reason = verification
+                .reason
                 .as_ref()
-                .map_or("".to_string(), |s| s.clone());
-
-            println!(
-                "{},\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{}",
-                block_num,
-                participant.username,
-                participant.social_id,
-                escaped_guess,
-                participant.commitment,
-                salt_str,
-                participant.verified,
-                is_valid
-            );
+                .map(|r| r.to_string())
+                .unwrap_or_default();
+            writer.write_record([
+                block_num.as_str(),
+                &participant.username,
+                &participant.social_id,
+                &participant.guess.text,
+                &participant.commitment,
+                participant.salt.as_deref().unwrap_or(""),
+                &participant.verified.to_string(),
+                &verification.valid.to_string(),
+                &failure_reason,
+            ])?;
         }
     }
 
-    Ok(())
+    Ok(String::from_utf8(writer.into_inner()?)?)
 }
 
 fn save_results(
@@ -748,7 +842,7 @@ fn save_results(
                     let participant_data: Vec<serde_json::Value> = participants
                         .iter()
                         .zip(verification_results.iter())
-                        .map(|(participant, &is_valid)| {
+                        .map(|(participant, verification)| {
                             serde_json::json!({
                                 "username": participant.username,
                                 "user_id": participant.social_id,
@@ -756,12 +850,13 @@ fn save_results(
                                 "commitment": participant.commitment,
                                 "salt": participant.salt,
                                 "is_verified": participant.verified,
-                                "commitment_valid": is_valid
+                                "commitment_valid": verification.valid,
+                                "failure_reason": verification.reason.as_ref().map(|r| r.to_string())
                             })
                         })
                         .collect();
 
-                    let valid_count = verification_results.iter().filter(|&&r| r).count();
+                    let valid_count = verification_results.iter().filter(|v| v.valid).count();
 
                     serde_json::json!({
                         "block_num": block_num,
@@ -796,36 +891,7 @@ fn save_results(
 
             serde_json::to_string_pretty(&output)?
         }
-        "csv" => {
-            let mut content = String::from(
-                "block_num,username,user_id,guess,commitment,salt,is_verified,commitment_valid\n",
-            );
-
-            for (block_num, verification_results, participants) in &results.blocks {
-                for (participant, &is_valid) in participants.iter().zip(verification_results.iter())
-                {
-                    let escaped_guess = participant.guess.text.replace("\"", "\"\"");
-                    let salt_str = participant
-                        .salt
-                        .as_ref()
-                        .map_or("".to_string(), |s| s.clone());
-
-                    content.push_str(&format!(
-                        "{},\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{}\n",
-                        block_num,
-                        participant.username,
-                        participant.social_id,
-                        escaped_guess,
-                        participant.commitment,
-                        salt_str,
-                        participant.verified,
-                        is_valid
-                    ));
-                }
-            }
-
-            content
-        }
+        "csv" => csv_content(results)?,
         "table" => {
             let mut content = String::from("Commitment Verification Results\n");
             content.push_str(&"=".repeat(50));
@@ -834,19 +900,19 @@ fn save_results(
             for (block_num, verification_results, participants) in &results.blocks {
                 content.push_str(&format!("\nBlock: {}\n", block_num));
 
-                let valid_count = verification_results.iter().filter(|&&r| r).count();
+                let valid_count = verification_results.iter().filter(|v| v.valid).count();
                 content.push_str(&format!(
                     "Valid commitments: {}/{}\n",
                     valid_count,
                     verification_results.len()
                 ));
 
-                for (i, (participant, &is_valid)) in participants
+                for (i, (participant, verification)) in participants
                     .iter()
                     .zip(verification_results.iter())
                     .enumerate()
                 {
-                    let status = if is_valid { "VALID" } else { "INVALID" };
+                    let status = if verification.valid { "VALID" } else { "INVALID" };
                     content.push_str(&format!(
                         "  {}. {} ({}): {}\n",
                         i + 1,
@@ -881,7 +947,11 @@ fn save_results(
         _ => return Err(format!("Unsupported output format for file save: {}", format).into()),
     };
 
-    fs::write(output_file, content)?;
+    if is_stdio(output_file) {
+        println!("{}", content.trim_end());
+    } else {
+        fs::write(output_file, content)?;
+    }
     Ok(())
 }
 
@@ -910,6 +980,7 @@ mod tests {
             detailed: false,
             strict: false,
             invalid_only: false,
+            explain: false,
             max_blocks: 0,
         };
 
@@ -935,6 +1006,7 @@ mod tests {
             detailed: false,
             strict: false,
             invalid_only: false,
+            explain: false,
             max_blocks: 0,
         };
 
@@ -962,6 +1034,7 @@ mod tests {
             detailed: false,
             strict: false,
             invalid_only: false,
+            explain: false,
             max_blocks: 0,
         };
 
@@ -1024,6 +1097,7 @@ mod tests {
             detailed: false,
             strict: false,
             invalid_only: false,
+            explain: false,
             max_blocks: 0,
         };
 
@@ -1078,10 +1152,124 @@ mod tests {
             detailed: false,
             strict: false,
             invalid_only: false,
+            explain: false,
             max_blocks: 0,
         };
 
         let result = validate_inputs(&args);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_verify_commitments_from_stdin() {
+        let mut block = BlockData::new(
+            "test_block".to_string(),
+            "test.jpg".to_string(),
+            "test_social_id".to_string(),
+            1000.0,
+        );
+        let commitment = CommitmentGenerator::new().generate("test guess", "test_salt").unwrap();
+        for (user, commitment) in [("user1", commitment), ("user2", "invalid".to_string())] {
+            block.add_participant(
+                Participant::new(
+                    user.to_string(),
+                    format!("user_{}", user),
+                    Guess::new("test guess".to_string()),
+                    commitment,
+                )
+                .with_salt("test_salt".to_string()),
+            );
+        }
+        let json = serde_json::to_string(&HashMap::from([("test_block".to_string(), block)]))
+            .unwrap();
+
+        let args = Args {
+            block_num: Some("test_block".to_string()),
+            all: false,
+            blocks_file: PathBuf::from("-"),
+            output: "json".to_string(),
+            output_file: Some(PathBuf::from("-")),
+            use_mock: true,
+            clip_model: None,
+            verbose: false,
+            no_color: false,
+            config: None,
+            continue_on_error: false,
+            detailed: false,
+            strict: false,
+            invalid_only: false,
+            explain: false,
+            max_blocks: 0,
+        };
+        // "-" needn't exist as a file
+        assert!(validate_inputs(&args).is_ok());
+
+        let store = MemoryBlockStore::from_reader(json.as_bytes()).unwrap();
+        let results = verify_from_store(store, &args).unwrap();
+        assert_eq!(results.total_participants, 2);
+        assert_eq!((results.total_valid, results.total_invalid), (1, 1));
+        assert!(!PathBuf::from("-").exists());
+    }
+
+    #[test]
+    fn test_csv_round_trips_guess_with_newline_and_comma() {
+        let guess = "a cat, on a \"mat\"\nunder the stars";
+        let mut participant = Participant::new(
+            "user1".to_string(),
+            "user_user1".to_string(),
+            Guess::new(guess.to_string()),
+            "commitment".to_string(),
+        )
+        .with_salt("salt,\r\nwith breaks".to_string());
+        // Blocks files from before guess normalization can hold raw newlines
+        participant.guess.text = guess.to_string();
+        let results = VerificationResults {
+            blocks: vec![(
+                "block1".to_string(),
+                vec![CommitmentVerification::valid()],
+                vec![participant],
+            )],
+            total_blocks_processed: 1,
+            total_participants: 1,
+            total_valid: 1,
+            total_invalid: 0,
+            errors: Vec::new(),
+        };
+
+        let content = csv_content(&results).unwrap();
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(&records[0][3], guess);
+        assert_eq!(&records[0][5], "salt,\r\nwith breaks");
+        assert_eq!(&records[0][7], "true");
+        assert_eq!(&records[0][8], "");
+    }
+
+    #[test]
+    fn test_csv_includes_failure_reason() {
+        let participant = Participant::new(
+            "user1".to_string(),
+            "user_user1".to_string(),
+            Guess::new("a cat".to_string()),
+            "commitment".to_string(),
+        );
+        let verification = CommitmentVerification::invalid(FailReason::MissingSalt);
+        let reason = FailReason::MissingSalt.to_string();
+        let results = VerificationResults {
+            blocks: vec![("block1".to_string(), vec![verification], vec![participant])],
+            total_blocks_processed: 1,
+            total_participants: 1,
+            total_valid: 0,
+            total_invalid: 1,
+            errors: Vec::new(),
+        };
+
+        let content = csv_content(&results).unwrap();
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+        assert_eq!(&reader.headers().unwrap()[8], "failure_reason");
+        let record = reader.records().next().unwrap().unwrap();
+        assert_eq!(&record[7], "false");
+        assert_eq!(&record[8], reason);
+    }
 }
@@ -0,0 +1,304 @@
+//! Twitter client that records tweets instead of posting them
+//!
+//! `DryRunTwitterClient` implements `TwitterApi` entirely in memory: posts get
+//! sequential ids and are kept in order, and searches and timelines are answered
+//! from what was posted. It lets the block lifecycle run end to end without
+//! credentials or network access.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use twitter_api::{PostTweetResult, SearchResult, SearchScope, Tweet, TwitterApi, TwitterUser};
+
+/// Username the dry-run client posts as
+pub const DRY_RUN_USERNAME: &str = "cliptions_dry_run";
+
+/// A tweet recorded by `DryRunTwitterClient`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostedTweet {
+    pub id: String,
+    pub author: String,
+    pub text: String,
+    pub reply_to: Option<String>,
+    pub image_path: Option<PathBuf>,
+    pub quoted_tweet_url: Option<String>,
+}
+
+/// In-memory `TwitterApi` that records every post
+///
+/// Clones share the same record, so a clone handed to the code under test can
+/// be inspected afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunTwitterClient {
+    tweets: Arc<Mutex<Vec<PostedTweet>>>,
+}
+
+impl DryRunTwitterClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every tweet posted so far, oldest first
+    pub fn posted(&self) -> Vec<PostedTweet> {
+        self.tweets.lock().unwrap().clone()
+    }
+
+    /// Record a reply from another account, as if a participant had tweeted it
+    pub fn add_reply(&self, author: &str, text: &str, reply_to: &str) -> PostedTweet {
+        self.record(author, text, Some(reply_to), None, None)
+    }
+
+    fn record(
+        &self,
+        author: &str,
+        text: &str,
+        reply_to: Option<&str>,
+        image_path: Option<PathBuf>,
+        quoted_tweet_url: Option<&str>,
+    ) -> PostedTweet {
+        let mut tweets = self.tweets.lock().unwrap();
+        // Ids keep increasing after deletes, like real tweet ids
+        let id = tweets
+            .last()
+            .and_then(|t| t.id.parse::<u64>().ok())
+            .map_or(1, |last| last + 1);
+        let tweet = PostedTweet {
+            id: id.to_string(),
+            author: author.to_string(),
+            text: text.to_string(),
+            reply_to: reply_to.map(|id| id.to_string()),
+            image_path,
+            quoted_tweet_url: quoted_tweet_url.map(|url| url.to_string()),
+        };
+        tweets.push(tweet.clone());
+        tweet
+    }
+
+    fn post(
+        &self,
+        text: &str,
+        reply_to: Option<&str>,
+        image_path: Option<PathBuf>,
+        quoted_tweet_url: Option<&str>,
+    ) -> PostTweetResult {
+        let posted = self.record(
+            DRY_RUN_USERNAME,
+            text,
+            reply_to,
+            image_path,
+            quoted_tweet_url,
+        );
+        PostTweetResult {
+            tweet: to_tweet(&posted),
+            success: true,
+        }
+    }
+
+    fn replies_to(&self, tweet_id: &str, since_id: Option<&str>) -> Vec<Tweet> {
+        let since = since_id.and_then(|id| id.parse::<u64>().ok()).unwrap_or(0);
+        self.tweets
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| t.reply_to.as_deref() == Some(tweet_id))
+            .filter(|t| t.id.parse::<u64>().is_ok_and(|id| id > since))
+            .map(to_tweet)
+            .collect()
+    }
+}
+
+fn to_tweet(posted: &PostedTweet) -> Tweet {
+    Tweet {
+        id: posted.id.clone(),
+        text: posted.text.clone(),
+        author_id: posted.author.clone(),
+        created_at: Some(Utc::now()),
+        conversation_id: posted.reply_to.clone(),
+        public_metrics: None,
+        url: format!("https://twitter.com/i/status/{}", posted.id),
+        in_reply_to_user_id: None,
+        referenced_tweets: Vec::new(),
+    }
+}
+
+#[async_trait]
+impl TwitterApi for DryRunTwitterClient {
+    async fn post_tweet(&self, text: &str) -> twitter_api::Result<PostTweetResult> {
+        Ok(self.post(text, None, None, None))
+    }
+
+    async fn post_tweet_with_image<P: AsRef<Path> + Send + 'static>(
+        &self,
+        text: &str,
+        image_path: P,
+    ) -> twitter_api::Result<PostTweetResult> {
+        Ok(self.post(text, None, Some(image_path.as_ref().to_path_buf()), None))
+    }
+
+    async fn reply_to_tweet(
+        &self,
+        text: &str,
+        reply_to_tweet_id: &str,
+    ) -> twitter_api::Result<PostTweetResult> {
+        Ok(self.post(text, Some(reply_to_tweet_id), None, None))
+    }
+
+    async fn reply_to_tweet_with_image<P: AsRef<Path> + Send + 'static>(
+        &self,
+        text: &str,
+        reply_to_tweet_id: &str,
+        image_path: P,
+    ) -> twitter_api::Result<PostTweetResult> {
+        Ok(self.post(
+            text,
+            Some(reply_to_tweet_id),
+            Some(image_path.as_ref().to_path_buf()),
+            None,
+        ))
+    }
+
+    async fn get_latest_tweet(
+        &self,
+        username: &str,
+        _exclude_retweets_replies: bool,
+    ) -> twitter_api::Result<Option<Tweet>> {
+        Ok(self.get_user_tweets(username, 1).await?.into_iter().next())
+    }
+
+    async fn get_user_tweets(
+        &self,
+        username: &str,
+        max_results: u32,
+    ) -> twitter_api::Result<Vec<Tweet>> {
+        Ok(self
+            .tweets
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|t| t.author == username)
+            .take(max_results as usize)
+            .map(to_tweet)
+            .collect())
+    }
+
+    async fn search_replies(
+        &self,
+        tweet_id: &str,
+        _max_results: u32,
+        _scope: SearchScope,
+        since_id: Option<String>,
+    ) -> twitter_api::Result<Vec<Tweet>> {
+        Ok(self.replies_to(tweet_id, since_id.as_deref()))
+    }
+
+    async fn search_replies_paged(
+        &self,
+        tweet_id: &str,
+        _max_results: u32,
+        _scope: SearchScope,
+        since_id: Option<String>,
+        _max_pages: Option<u32>,
+    ) -> twitter_api::Result<SearchResult> {
+        let tweets = self.replies_to(tweet_id, since_id.as_deref());
+        let newest_id = twitter_api::newest_tweet_id(&tweets);
+        Ok(SearchResult {
+            tweets,
+            complete: true,
+            newest_id,
+            error: None,
+        })
+    }
+
+    async fn quote_tweet(
+        &self,
+        text: &str,
+        quoted_tweet_url: &str,
+    ) -> twitter_api::Result<PostTweetResult> {
+        Ok(self.post(text, None, None, Some(quoted_tweet_url)))
+    }
+
+    async fn delete_tweet(&self, tweet_id: &str) -> twitter_api::Result<bool> {
+        let mut tweets = self.tweets.lock().unwrap();
+        let before = tweets.len();
+        tweets.retain(|t| t.id != tweet_id);
+        Ok(tweets.len() < before)
+    }
+
+    async fn get_authenticated_user(&self) -> twitter_api::Result<TwitterUser> {
+        Ok(TwitterUser {
+            id: DRY_RUN_USERNAME.to_string(),
+            username: DRY_RUN_USERNAME.to_string(),
+            name: "Cliptions (dry run)".to_string(),
+            verified: None,
+        })
+    }
+
+    /// Every account that has posted, the dry-run account included, exists
+    async fn get_users_by_username(
+        &self,
+        usernames: &[String],
+    ) -> twitter_api::Result<Vec<TwitterUser>> {
+        let tweets = self.tweets.lock().unwrap();
+        Ok(usernames
+            .iter()
+            .filter(|name| *name == DRY_RUN_USERNAME || tweets.iter().any(|t| t.author == **name))
+            .map(|name| TwitterUser {
+                id: name.to_string(),
+                username: name.to_string(),
+                name: name.to_string(),
+                verified: None,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_dry_run_client_records_and_searches() {
+        let client = DryRunTwitterClient::new();
+        let root = client.post_tweet("announcement").await.unwrap().tweet;
+        client.add_reply("alice", "first", &root.id);
+        let reply = client
+            .reply_to_tweet("own reply", &root.id)
+            .await
+            .unwrap()
+            .tweet;
+        client.add_reply("bob", "nested", &reply.id);
+
+        let replies = client
+            .search_replies(&root.id, 100, SearchScope::Recent, None)
+            .await
+            .unwrap();
+        let texts: Vec<&str> = replies.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["first", "own reply"]);
+
+        let newer = client
+            .search_replies_paged(
+                &root.id,
+                100,
+                SearchScope::Recent,
+                Some("2".to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(newer.tweets.len(), 1);
+        assert_eq!(newer.newest_id.as_deref(), Some("3"));
+
+        // Timelines only hold the dry-run account's own tweets, newest first
+        let latest = client
+            .get_latest_tweet(DRY_RUN_USERNAME, false)
+            .await
+            .unwrap();
+        assert_eq!(latest.unwrap().text, "own reply");
+
+        assert!(client.delete_tweet(&reply.id).await.unwrap());
+        assert!(!client.delete_tweet(&reply.id).await.unwrap());
+        assert_eq!(client.posted().len(), 3);
+    }
+}
@@ -0,0 +1,36 @@
+//! Sources of the target frame captured from a block's livestream
+
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+/// Captures the livestream frame a block's guesses are scored against
+pub trait FrameProvider: Send + Sync {
+    /// Capture the frame shown on `livestream_url` at `target_time` and
+    /// return the path of the saved image
+    fn capture(&self, livestream_url: &str, target_time: DateTime<Utc>) -> Result<PathBuf>;
+}
+
+/// Frame provider that returns a fixed image path without capturing anything
+///
+/// The file doesn't need to exist as long as the embedder scoring it doesn't
+/// read it, as with `MockEmbedder`.
+#[derive(Debug, Clone)]
+pub struct MockFrameProvider {
+    frame_path: PathBuf,
+}
+
+impl MockFrameProvider {
+    pub fn new(frame_path: impl Into<PathBuf>) -> Self {
+        Self {
+            frame_path: frame_path.into(),
+        }
+    }
+}
+
+impl FrameProvider for MockFrameProvider {
+    fn capture(&self, _livestream_url: &str, _target_time: DateTime<Utc>) -> Result<PathBuf> {
+        Ok(self.frame_path.clone())
+    }
+}
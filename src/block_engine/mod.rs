@@ -1 +1,4 @@
 pub mod state_machine;
+pub mod dry_run;
+pub mod frame;
+pub mod round_engine;
@@ -0,0 +1,586 @@
+//! Advancing many blocks at once without flooding the Twitter API
+//!
+//! `RoundEngine` runs a transition over a batch of blocks concurrently. At most
+//! `max_concurrent_blocks` blocks advance at a time and the rest wait their
+//! turn, while every Twitter call goes through a `LimitedClient` that allows at
+//! most `max_inflight_requests` calls in flight across all blocks.
+//!
+//! A state transition (e.g. `open_commitments`) is the atomic unit of work.
+//! Once started it runs to completion, and `advance_and_save` checkpoints the
+//! block in its new state before the next one. When shutdown is signalled the
+//! engine starts no further transitions, so a stopped engine leaves every
+//! block either untouched or fully transitioned and saved; a later run resumes
+//! from the checkpoints.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{watch, Semaphore, SemaphorePermit};
+use tokio::task::JoinSet;
+use twitter_api::{PostTweetResult, SearchResult, SearchScope, Tweet, TwitterApi, TwitterUser};
+
+use crate::block_engine::state_machine::{Block, Pending, StateMarker};
+use crate::error::{CliptionsError, Result};
+
+/// Limits applied by `RoundEngine`
+///
+/// A limit of zero is treated as one, so work always makes progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundEngineConfig {
+    /// Blocks advancing at the same time; further blocks queue
+    pub max_concurrent_blocks: usize,
+    /// Twitter API calls in flight at the same time, across all blocks
+    pub max_inflight_requests: usize,
+}
+
+impl Default for RoundEngineConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_blocks: 4,
+            max_inflight_requests: 2,
+        }
+    }
+}
+
+/// `TwitterApi` wrapper that caps the number of calls in flight
+///
+/// Calls beyond the cap wait for an earlier call to finish.
+pub struct LimitedClient<T> {
+    inner: T,
+    permits: Semaphore,
+}
+
+impl<T> LimitedClient<T> {
+    pub fn new(inner: T, max_inflight_requests: usize) -> Self {
+        Self {
+            inner,
+            permits: Semaphore::new(max_inflight_requests.max(1)),
+        }
+    }
+
+    /// The wrapped client
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    async fn permit(&self) -> SemaphorePermit<'_> {
+        self.permits
+            .acquire()
+            .await
+            .expect("request semaphore is never closed")
+    }
+}
+
+#[async_trait]
+impl<T: TwitterApi + Send + Sync> TwitterApi for LimitedClient<T> {
+    async fn post_tweet(&self, text: &str) -> twitter_api::Result<PostTweetResult> {
+        let _permit = self.permit().await;
+        self.inner.post_tweet(text).await
+    }
+
+    async fn post_tweet_with_image<P: AsRef<Path> + Send + 'static>(
+        &self,
+        text: &str,
+        image_path: P,
+    ) -> twitter_api::Result<PostTweetResult> {
+        let _permit = self.permit().await;
+        self.inner.post_tweet_with_image(text, image_path).await
+    }
+
+    async fn reply_to_tweet(
+        &self,
+        text: &str,
+        reply_to_tweet_id: &str,
+    ) -> twitter_api::Result<PostTweetResult> {
+        let _permit = self.permit().await;
+        self.inner.reply_to_tweet(text, reply_to_tweet_id).await
+    }
+
+    async fn reply_to_tweet_with_image<P: AsRef<Path> + Send + 'static>(
+        &self,
+        text: &str,
+        reply_to_tweet_id: &str,
+        image_path: P,
+    ) -> twitter_api::Result<PostTweetResult> {
+        let _permit = self.permit().await;
+        self.inner
+            .reply_to_tweet_with_image(text, reply_to_tweet_id, image_path)
+            .await
+    }
+
+    async fn get_latest_tweet(
+        &self,
+        username: &str,
+        exclude_retweets_replies: bool,
+    ) -> twitter_api::Result<Option<Tweet>> {
+        let _permit = self.permit().await;
+        self.inner
+            .get_latest_tweet(username, exclude_retweets_replies)
+            .await
+    }
+
+    async fn get_user_tweets(
+        &self,
+        username: &str,
+        max_results: u32,
+    ) -> twitter_api::Result<Vec<Tweet>> {
+        let _permit = self.permit().await;
+        self.inner.get_user_tweets(username, max_results).await
+    }
+
+    async fn search_replies(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+    ) -> twitter_api::Result<Vec<Tweet>> {
+        let _permit = self.permit().await;
+        self.inner
+            .search_replies(tweet_id, max_results, scope, since_id)
+            .await
+    }
+
+    async fn search_replies_paged(
+        &self,
+        tweet_id: &str,
+        max_results: u32,
+        scope: SearchScope,
+        since_id: Option<String>,
+        max_pages: Option<u32>,
+    ) -> twitter_api::Result<SearchResult> {
+        let _permit = self.permit().await;
+        self.inner
+            .search_replies_paged(tweet_id, max_results, scope, since_id, max_pages)
+            .await
+    }
+
+    async fn quote_tweet(
+        &self,
+        text: &str,
+        quoted_tweet_url: &str,
+    ) -> twitter_api::Result<PostTweetResult> {
+        let _permit = self.permit().await;
+        self.inner.quote_tweet(text, quoted_tweet_url).await
+    }
+
+    async fn delete_tweet(&self, tweet_id: &str) -> twitter_api::Result<bool> {
+        let _permit = self.permit().await;
+        self.inner.delete_tweet(tweet_id).await
+    }
+
+    async fn get_authenticated_user(&self) -> twitter_api::Result<TwitterUser> {
+        let _permit = self.permit().await;
+        self.inner.get_authenticated_user().await
+    }
+
+    async fn get_user_by_username(
+        &self,
+        username: &str,
+    ) -> twitter_api::Result<Option<TwitterUser>> {
+        let _permit = self.permit().await;
+        self.inner.get_user_by_username(username).await
+    }
+
+    async fn get_users_by_username(
+        &self,
+        usernames: &[String],
+    ) -> twitter_api::Result<Vec<TwitterUser>> {
+        let _permit = self.permit().await;
+        self.inner.get_users_by_username(usernames).await
+    }
+}
+
+/// A block as saved by `CheckpointStore`, with the state it had reached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBlock {
+    /// State name, e.g. "CommitmentsOpen"
+    pub state: String,
+    /// The block's data; restore its state with `Block::into_state`
+    pub block: Block<Pending>,
+}
+
+/// A block state that can be checkpointed after a transition
+pub trait Checkpoint {
+    fn to_saved(&self) -> SavedBlock;
+}
+
+impl<S: StateMarker + Clone> Checkpoint for Block<S> {
+    fn to_saved(&self) -> SavedBlock {
+        SavedBlock {
+            state: S::state_name().to_string(),
+            block: self.clone().into_state(),
+        }
+    }
+}
+
+/// Directory of per-block checkpoint files
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, block_id: &str) -> PathBuf {
+        self.dir.join(format!("block_{}.json", block_id))
+    }
+
+    /// Save a block, replacing its previous checkpoint
+    ///
+    /// The file is written beside the old one and renamed over it, so an
+    /// interrupted save never leaves a partial checkpoint.
+    pub fn save(&self, block: &impl Checkpoint) -> Result<()> {
+        let saved = block.to_saved();
+        let previous_state = if cliptions_metrics::ENABLED {
+            self.load(&saved.block.id)
+                .ok()
+                .flatten()
+                .map(|previous| previous.state)
+        } else {
+            None
+        };
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(&saved.block.id);
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(&saved)?)?;
+        fs::rename(&temp_path, &path)?;
+
+        // Blocks per state, moved as each checkpoint records a transition
+        if let Some(previous_state) = previous_state {
+            cliptions_metrics::add_gauge("cliptions_blocks", &[("state", &previous_state)], -1.0);
+        }
+        cliptions_metrics::add_gauge("cliptions_blocks", &[("state", &saved.state)], 1.0);
+        Ok(())
+    }
+
+    /// The last checkpoint saved for a block, if any
+    pub fn load(&self, block_id: &str) -> Result<Option<SavedBlock>> {
+        let path = self.path(block_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+}
+
+/// Runs block transitions concurrently within `RoundEngineConfig` limits
+pub struct RoundEngine<T> {
+    client: Arc<LimitedClient<T>>,
+    blocks: Arc<Semaphore>,
+    config: RoundEngineConfig,
+    shutdown: Option<watch::Receiver<bool>>,
+}
+
+impl<T: TwitterApi + Send + Sync + 'static> RoundEngine<T> {
+    pub fn new(client: T, config: RoundEngineConfig) -> Self {
+        Self {
+            client: Arc::new(LimitedClient::new(client, config.max_inflight_requests)),
+            blocks: Arc::new(Semaphore::new(config.max_concurrent_blocks.max(1))),
+            config,
+            shutdown: None,
+        }
+    }
+
+    /// Stop starting transitions once `true` is sent on the channel
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Whether shutdown has been signalled
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.as_ref().is_some_and(|rx| *rx.borrow())
+    }
+
+    pub fn config(&self) -> RoundEngineConfig {
+        self.config
+    }
+
+    /// The request-limited client shared by every block
+    pub fn client(&self) -> Arc<LimitedClient<T>> {
+        Arc::clone(&self.client)
+    }
+
+    /// Apply `step` to every block, returning the results in input order
+    ///
+    /// Blocks beyond `max_concurrent_blocks` wait for a running block to
+    /// finish rather than failing. `step` receives the shared request-limited
+    /// client, so its Twitter calls also count towards `max_inflight_requests`.
+    /// One block failing does not stop the others.
+    ///
+    /// Shutdown is checked as each block's turn comes: transitions already
+    /// running finish, and blocks not yet started report
+    /// `CliptionsError::ShutdownRequested` without being touched.
+    pub async fn advance<B, R, F, Fut>(&self, blocks: Vec<B>, step: F) -> Vec<Result<R>>
+    where
+        B: Send + 'static,
+        R: Send + 'static,
+        F: Fn(B, Arc<LimitedClient<T>>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R>> + Send + 'static,
+    {
+        let step = Arc::new(step);
+        let mut tasks = JoinSet::new();
+        for (index, block) in blocks.into_iter().enumerate() {
+            let permits = Arc::clone(&self.blocks);
+            let client = Arc::clone(&self.client);
+            let step = Arc::clone(&step);
+            let shutdown = self.shutdown.clone();
+            tasks.spawn(async move {
+                let _permit = permits
+                    .acquire_owned()
+                    .await
+                    .expect("block semaphore is never closed");
+                if shutdown.is_some_and(|rx| *rx.borrow()) {
+                    return (index, Err(CliptionsError::ShutdownRequested));
+                }
+                (index, step(block, client).await)
+            });
+        }
+
+        let mut results: Vec<Option<Result<R>>> = Vec::new();
+        results.resize_with(tasks.len(), || None);
+        while let Some(joined) = tasks.join_next().await {
+            // Tasks are never aborted, so a join error means the step panicked
+            let (index, result) =
+                joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            results[index] = Some(result);
+        }
+        results
+            .into_iter()
+            .map(|result| result.expect("every block task reports a result"))
+            .collect()
+    }
+
+    /// `advance`, saving each block to `store` as soon as its transition succeeds
+    pub async fn advance_and_save<B, R, F, Fut>(
+        &self,
+        blocks: Vec<B>,
+        store: &CheckpointStore,
+        step: F,
+    ) -> Vec<Result<R>>
+    where
+        B: Send + 'static,
+        R: Checkpoint + Send + 'static,
+        F: Fn(B, Arc<LimitedClient<T>>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R>> + Send + 'static,
+    {
+        let store = store.clone();
+        self.advance(blocks, move |block, client| {
+            let store = store.clone();
+            let transition = step(block, client);
+            async move {
+                let next = transition.await?;
+                store.save(&next)?;
+                Ok(next)
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_engine::state_machine::{CommitmentsClosed, CommitmentsOpen};
+    use chrono::{Duration, Utc};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+    use twitter_api::testing::InMemoryTwitterApi;
+
+    /// Tracks the high-water mark of a concurrently held count
+    #[derive(Default)]
+    struct Gauge {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    impl Gauge {
+        fn enter(&self) {
+            let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max.fetch_max(now, Ordering::SeqCst);
+        }
+
+        fn exit(&self) {
+            self.current.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        fn max(&self) -> usize {
+            self.max.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Client whose posts take long enough to overlap
+    fn slow_client() -> InMemoryTwitterApi {
+        InMemoryTwitterApi::new().with_latency(std::time::Duration::from_millis(20))
+    }
+
+    fn pending_blocks(count: usize) -> Vec<Block<Pending>> {
+        (1..=count)
+            .map(|id| {
+                Block::new(
+                    id.to_string(),
+                    "Test Theme".to_string(),
+                    "http://twitch.tv/test".to_string(),
+                    Utc::now() + Duration::days(1),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_round_engine_limits_concurrent_blocks() {
+        let engine = RoundEngine::new(
+            slow_client(),
+            RoundEngineConfig {
+                max_concurrent_blocks: 2,
+                max_inflight_requests: 10,
+            },
+        );
+        let active_blocks = Arc::new(Gauge::default());
+        let deadline = Utc::now() + Duration::hours(1);
+
+        let gauge = Arc::clone(&active_blocks);
+        let results = engine
+            .advance(pending_blocks(6), move |block, client| {
+                let gauge = Arc::clone(&gauge);
+                async move {
+                    gauge.enter();
+                    let opened = block.open_commitments(deadline, &*client).await;
+                    gauge.exit();
+                    opened
+                }
+            })
+            .await;
+
+        // Every block advanced, none failed for being queued, and order is kept
+        let ids: Vec<String> = results
+            .into_iter()
+            .map(|result: Result<Block<CommitmentsOpen>>| result.unwrap().id)
+            .collect();
+        assert_eq!(ids, vec!["1", "2", "3", "4", "5", "6"]);
+        assert_eq!(active_blocks.max(), 2);
+        assert_eq!(engine.client().inner().max_inflight(), 2);
+        assert_eq!(engine.client().inner().tweets().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_round_engine_limits_inflight_requests() {
+        let engine = RoundEngine::new(
+            slow_client(),
+            RoundEngineConfig {
+                max_concurrent_blocks: 4,
+                max_inflight_requests: 1,
+            },
+        );
+        let deadline = Utc::now() + Duration::hours(1);
+
+        let results = engine
+            .advance(pending_blocks(4), move |block, client| async move {
+                block.open_commitments(deadline, &*client).await
+            })
+            .await;
+
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert_eq!(engine.client().inner().max_inflight(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_round_engine_failures_stay_per_block() {
+        let engine = RoundEngine::new(
+            slow_client(),
+            RoundEngineConfig {
+                max_concurrent_blocks: 0,
+                max_inflight_requests: 0,
+            },
+        );
+
+        let results = engine
+            .advance(vec![1, 2, 3], |n, _client| async move {
+                if n == 2 {
+                    Err(CliptionsError::ValidationError(
+                        "block 2 failed".to_string(),
+                    ))
+                } else {
+                    Ok(n * 10)
+                }
+            })
+            .await;
+
+        assert_eq!(results[0].as_ref().unwrap(), &10);
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap(), &30);
+    }
+
+    #[tokio::test]
+    async fn test_round_engine_shutdown_after_one_transition() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = CheckpointStore::new(temp_dir.path());
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let shutdown_tx = Arc::new(shutdown_tx);
+        let engine = RoundEngine::new(
+            slow_client(),
+            RoundEngineConfig {
+                max_concurrent_blocks: 1,
+                max_inflight_requests: 1,
+            },
+        )
+        .with_shutdown(shutdown_rx);
+        let deadline = Utc::now() + Duration::hours(1);
+
+        // The first block to run signals shutdown once its transition is done
+        let results = engine
+            .advance_and_save(pending_blocks(3), &store, move |block, client| {
+                let shutdown_tx = Arc::clone(&shutdown_tx);
+                async move {
+                    let opened = block.open_commitments(deadline, &*client).await;
+                    shutdown_tx.send_replace(true);
+                    opened
+                }
+            })
+            .await;
+
+        assert!(engine.is_shutting_down());
+        let advanced: Vec<&Block<CommitmentsOpen>> =
+            results.iter().filter_map(|r| r.as_ref().ok()).collect();
+        assert_eq!(advanced.len(), 1);
+        assert!(results
+            .iter()
+            .filter_map(|r| r.as_ref().err())
+            .all(|e| matches!(e, CliptionsError::ShutdownRequested)));
+        assert_eq!(engine.client().inner().tweets().len(), 1);
+
+        // Only the transitioned block was saved, in its new state
+        let finished_id = advanced[0].id.clone();
+        for id in ["1", "2", "3"] {
+            let saved = store.load(id).unwrap();
+            if id == finished_id {
+                assert_eq!(saved.unwrap().state, "CommitmentsOpen");
+            } else {
+                assert!(saved.is_none());
+            }
+        }
+
+        // A fresh engine resumes the block from its checkpoint
+        let saved = store.load(&finished_id).unwrap().unwrap();
+        let resumed = vec![saved.block.into_state::<CommitmentsOpen>()];
+        let engine = RoundEngine::new(slow_client(), RoundEngineConfig::default());
+        let results = engine
+            .advance_and_save(resumed, &store, |block, client| async move {
+                block.close_commitments(&*client).await
+            })
+            .await;
+        let closed: &Block<CommitmentsClosed> = results[0].as_ref().unwrap();
+        assert_eq!(closed.commitment_deadline, Some(deadline));
+        assert_eq!(
+            store.load(&finished_id).unwrap().unwrap().state,
+            "CommitmentsClosed"
+        );
+    }
+}
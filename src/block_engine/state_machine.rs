@@ -4,14 +4,15 @@
 //! Each state is a marker type that ensures operations can only be performed
 //! when the block is in the correct state, enforced by the compiler.
 
-use crate::error::{CliptionsError, Result};
+use crate::clock::Clock;
+use crate::error::{BlockError, CliptionsError, Result};
 use crate::social::{AnnouncementData, AnnouncementFormatter};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
-use std::path::Path;
 use std::path::PathBuf;
-use twitter_api::TwitterApi;
+use twitter_api::{PostTweetResult, TwitterApi, TwitterError};
 
 // --- State Markers ---
 
@@ -111,6 +112,12 @@ pub struct Block<S> {
     pub commitment_deadline: Option<DateTime<Utc>>,
     pub reveals_deadline: Option<DateTime<Utc>>,
 
+    // --- Posted Announcements ---
+    /// Ledger of the phase announcements already posted, keyed by the state
+    /// they announced, with the tweet ID when it is known
+    #[serde(default)]
+    pub posted_tweets: BTreeMap<String, Option<String>>,
+
     #[serde(skip)]
     pub state: std::marker::PhantomData<S>,
 }
@@ -134,6 +141,24 @@ where
     }
 }
 
+/// Check the result of posting a phase announcement, returning the tweet ID
+///
+/// Twitter rejects a tweet identical to one already posted, which happens when
+/// a transition is retried after its announcement went out; that counts as
+/// posted rather than as a failed transition, though the tweet ID is unknown.
+fn announcement_posted(
+    result: std::result::Result<PostTweetResult, TwitterError>,
+) -> Result<Option<String>> {
+    match result {
+        Ok(posted) => Ok(Some(posted.tweet.id)),
+        Err(e) if e.is_duplicate_content() => {
+            eprintln!("Warning: announcement was already posted ({})", e);
+            Ok(None)
+        }
+        Err(e) => Err(CliptionsError::ApiError(e.to_string())),
+    }
+}
+
 /// Implementation for Pending state
 impl Block<Pending> {
     /// Create a new pending block
@@ -152,6 +177,7 @@ impl Block<Pending> {
             target_frame_path: None,
             commitment_deadline: None,
             reveals_deadline: None,
+            posted_tweets: BTreeMap::new(),
             state: std::marker::PhantomData,
         }
     }
@@ -171,13 +197,12 @@ impl Block<Pending> {
             message: String::new(), // Not used for commitment announcements
             prize_pool: None,
             livestream_url: Some(self.livestream_url.clone()),
+            deadline: Some(commitment_deadline),
         };
         let tweet_text = formatter.create_commitment_announcement(&announcement_data);
 
-        client
-            .post_tweet(&tweet_text)
-            .await
-            .map_err(|e| CliptionsError::ApiError(e.to_string()))?;
+        let tweet_id = announcement_posted(client.post_tweet(&tweet_text).await)?;
+        self.posted_tweets.insert(CommitmentsOpen::state_name().to_string(), tweet_id);
 
         self.commitment_deadline = Some(commitment_deadline);
 
@@ -190,6 +215,7 @@ impl Block<Pending> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         })
     }
@@ -199,7 +225,7 @@ impl Block<Pending> {
 impl Block<CommitmentsOpen> {
     /// Close commitments
     pub async fn close_commitments<T: TwitterApi>(
-        self,
+        mut self,
         client: &T,
     ) -> Result<Block<CommitmentsClosed>> {
         let formatter = AnnouncementFormatter::new();
@@ -211,17 +237,16 @@ impl Block<CommitmentsOpen> {
             message: format!(
                 "Block '{}': Commitments are now closed. Waiting for target time at {}.",
                 self.id,
-                self.target_timestamp.to_rfc3339()
+                formatter.format_deadline(self.target_timestamp)
             ),
             prize_pool: None,
             livestream_url: Some(self.livestream_url.clone()),
+            deadline: Some(self.target_timestamp),
         };
         let tweet_text = formatter.format_announcement(&announcement_data, true);
 
-        client
-            .post_tweet(&tweet_text)
-            .await
-            .map_err(|e| CliptionsError::ApiError(e.to_string()))?;
+        let tweet_id = announcement_posted(client.post_tweet(&tweet_text).await)?;
+        self.posted_tweets.insert(CommitmentsClosed::state_name().to_string(), tweet_id);
 
         Ok(Block {
             id: self.id,
@@ -232,6 +257,7 @@ impl Block<CommitmentsOpen> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         })
     }
@@ -239,10 +265,14 @@ impl Block<CommitmentsOpen> {
 
 /// Implementation for CommitmentsClosed state
 impl Block<CommitmentsClosed> {
-    /// Capture the frame after the target time has passed.
+    /// Capture the frame after the target time has passed by `clock`.
     /// This is an internal state transition and does not tweet.
-    pub fn capture_frame(mut self, target_frame_path: PathBuf) -> Result<Block<FrameCaptured>> {
-        if Utc::now() < self.target_timestamp {
+    pub fn capture_frame(
+        mut self,
+        target_frame_path: PathBuf,
+        clock: &dyn Clock,
+    ) -> Result<Block<FrameCaptured>> {
+        if clock.now() < self.target_timestamp {
             return Err(CliptionsError::ValidationError(
                 "Target timestamp has not yet been reached.".to_string(),
             ));
@@ -257,6 +287,7 @@ impl Block<CommitmentsClosed> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         })
     }
@@ -264,13 +295,64 @@ impl Block<CommitmentsClosed> {
 
 /// Implementation for FrameCaptured state
 impl Block<FrameCaptured> {
+    /// Fill an empty ledger from a checkpoint saved before the ledger existed
+    ///
+    /// Such a block only reached this state by closing commitments, and only
+    /// a posted reveals announcement sets the reveals deadline; the tweet IDs
+    /// weren't recorded.
+    fn migrate_legacy_ledger(&mut self) {
+        if !self.posted_tweets.is_empty() {
+            return;
+        }
+        if self.commitment_deadline.is_some() {
+            self.posted_tweets.insert(CommitmentsOpen::state_name().to_string(), None);
+        }
+        self.posted_tweets.insert(CommitmentsClosed::state_name().to_string(), None);
+        if self.reveals_deadline.is_some() {
+            self.posted_tweets.insert(RevealsOpen::state_name().to_string(), None);
+        }
+    }
+
+    /// Check that opening reveals is safe, returning the target frame to post
+    ///
+    /// Commitments must have been closed, a target frame must have been
+    /// captured and no reveals announcement may be in the ledger yet, so a
+    /// retried transition never posts a second one. Call
+    /// `migrate_legacy_ledger` first for checkpoints without a ledger.
+    fn check_reveals_can_open(&self) -> Result<PathBuf> {
+        if let Some(tweet_id) = self.posted_tweets.get(RevealsOpen::state_name()) {
+            return Err(BlockError::RevealsAlreadyOpen {
+                block_num: self.id.clone(),
+                tweet_id: tweet_id.clone().unwrap_or_else(|| "unknown".to_string()),
+            }
+            .into());
+        }
+        if !self.posted_tweets.contains_key(CommitmentsClosed::state_name()) {
+            return Err(BlockError::CommitmentsNotClosed {
+                block_num: self.id.clone(),
+            }
+            .into());
+        }
+        let frame_path = self.target_frame_path.clone().ok_or_else(|| {
+            BlockError::TargetFrameNotCaptured {
+                block_num: self.id.clone(),
+            }
+        })?;
+        Ok(frame_path)
+    }
+
     /// Open the reveals phase by publishing the target frame.
+    ///
+    /// Nothing is posted if `check_reveals_can_open` fails.
     pub async fn open_reveals<T: TwitterApi>(
         mut self,
         reveals_deadline: DateTime<Utc>,
         client: &T,
         parent_tweet_id: &str,
     ) -> Result<Block<RevealsOpen>> {
+        self.migrate_legacy_ledger();
+        let frame_path = self.check_reveals_can_open()?;
+
         let formatter = AnnouncementFormatter::new();
         let announcement_data = AnnouncementData {
             block_num: self.id.parse().expect(&format!("CRITICAL: Invalid block ID '{}' - cannot proceed with block announcements", self.id)),
@@ -280,21 +362,20 @@ impl Block<FrameCaptured> {
             message: format!(
                 "Block '{}': Target frame revealed! Reveals are open until {}.",
                 self.id,
-                reveals_deadline.to_rfc3339()
+                formatter.format_deadline(reveals_deadline)
             ),
             prize_pool: None,
             livestream_url: None, // No livestream URL needed for reveals open announcement
+            deadline: Some(reveals_deadline),
         };
         let tweet_text = formatter.format_announcement(&announcement_data, true);
 
-        let frame_path = self.target_frame_path.clone().ok_or_else(|| {
-            CliptionsError::ValidationError("Target frame path not set".to_string())
-        })?;
-
-        client
-            .reply_to_tweet_with_image(&tweet_text, parent_tweet_id, frame_path) // Pass owned PathBuf
-            .await
-            .map_err(|e| CliptionsError::ApiError(e.to_string()))?;
+        let tweet_id = announcement_posted(
+            client
+                .reply_to_tweet_with_image(&tweet_text, parent_tweet_id, frame_path) // Pass owned PathBuf
+                .await,
+        )?;
+        self.posted_tweets.insert(RevealsOpen::state_name().to_string(), tweet_id);
 
         self.reveals_deadline = Some(reveals_deadline);
 
@@ -307,6 +388,7 @@ impl Block<FrameCaptured> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         })
     }
@@ -326,6 +408,7 @@ impl Block<RevealsOpen> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         })
     }
@@ -344,6 +427,7 @@ impl Block<Payouts> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         })
     }
@@ -368,6 +452,7 @@ impl<S> Block<S> {
             target_frame_path: self.target_frame_path,
             commitment_deadline: self.commitment_deadline,
             reveals_deadline: self.reveals_deadline,
+            posted_tweets: self.posted_tweets,
             state: std::marker::PhantomData,
         }
     }
@@ -391,88 +476,15 @@ pub fn parse_state_from_string(state_str: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use async_trait::async_trait;
+    use crate::clock::MockClock;
     use chrono::Duration;
     use chrono::Utc;
     use std::path::PathBuf;
-    use std::sync::{Arc, Mutex};
-    use twitter_api::{PostTweetResult, Tweet, TwitterError};
-
-    /// A mock Twitter client that records calls for verification.
-    #[derive(Clone)]
-    struct MockTwitterClient {
-        last_tweet_text: Arc<Mutex<Option<String>>>,
-        last_image_path: Arc<Mutex<Option<PathBuf>>>,
-    }
+    use twitter_api::testing::InMemoryTwitterApi;
 
-    impl MockTwitterClient {
-        fn new() -> Self {
-            Self {
-                last_tweet_text: Arc::new(Mutex::new(None)),
-                last_image_path: Arc::new(Mutex::new(None)),
-            }
-        }
-    }
-
-    #[async_trait]
-    impl TwitterApi for MockTwitterClient {
-        async fn post_tweet(&self, text: &str) -> twitter_api::Result<PostTweetResult> {
-            *self.last_tweet_text.lock().unwrap() = Some(text.to_string());
-            *self.last_image_path.lock().unwrap() = None;
-            Ok(PostTweetResult {
-                tweet: Tweet::default(),
-                success: true,
-            })
-        }
-        async fn post_tweet_with_image<P: AsRef<Path> + Send + 'static>(
-            &self,
-            text: &str,
-            image_path: P,
-        ) -> twitter_api::Result<PostTweetResult> {
-            *self.last_tweet_text.lock().unwrap() = Some(text.to_string());
-            *self.last_image_path.lock().unwrap() = Some(image_path.as_ref().to_path_buf());
-            Ok(PostTweetResult {
-                tweet: Tweet::default(),
-                success: true,
-            })
-        }
-        async fn reply_to_tweet(
-            &self,
-            _text: &str,
-            _reply_to_tweet_id: &str,
-        ) -> twitter_api::Result<PostTweetResult> {
-            Ok(PostTweetResult {
-                tweet: Tweet::default(),
-                success: true,
-            })
-        }
-        async fn reply_to_tweet_with_image<P: AsRef<Path> + Send + 'static>(
-            &self,
-            text: &str,
-            reply_to_tweet_id: &str,
-            image_path: P,
-        ) -> twitter_api::Result<PostTweetResult> {
-            *self.last_tweet_text.lock().unwrap() = Some(text.to_string());
-            *self.last_image_path.lock().unwrap() = Some(image_path.as_ref().to_path_buf());
-            Ok(PostTweetResult {
-                tweet: Tweet::default(),
-                success: true,
-            })
-        }
-        async fn get_latest_tweet(
-            &self,
-            _username: &str,
-            _exclude_retweets_replies: bool,
-        ) -> twitter_api::Result<Option<Tweet>> {
-            unimplemented!()
-        }
-        async fn search_replies(
-            &self,
-            _tweet_id: &str,
-            _max_results: u32,
-        ) -> twitter_api::Result<Vec<Tweet>> {
-            unimplemented!()
-        }
+    /// Text of the most recent tweet the client posted
+    fn last_tweet_text(client: &InMemoryTwitterApi) -> String {
+        client.tweets().last().unwrap().text.clone()
     }
 
     fn common_block() -> Block<Pending> {
@@ -486,7 +498,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_full_lifecycle_correct_flow() {
-        let client = MockTwitterClient::new();
+        let client = InMemoryTwitterApi::new();
         let commitment_deadline = Utc::now() + Duration::hours(24);
         let reveals_deadline = Utc::now() + Duration::hours(48);
 
@@ -498,7 +510,7 @@ mod tests {
             .unwrap();
         assert_eq!(block.state_name(), "CommitmentsOpen");
         assert_eq!(block.commitment_deadline, Some(commitment_deadline));
-        let tweet1 = client.last_tweet_text.lock().unwrap().clone().unwrap();
+        let tweet1 = last_tweet_text(&client);
         assert!(tweet1.contains("BLOCK 1 - Commitment Phase"));
         assert!(tweet1.contains("livestream: http://twitch.tv/test"));
         assert!(tweet1.contains("How To Play:"));
@@ -507,36 +519,43 @@ mod tests {
         // 2. CommitmentsOpen -> CommitmentsClosed
         let block = block.close_commitments(&client).await.unwrap();
         assert_eq!(block.state_name(), "CommitmentsClosed");
-        let tweet2 = client.last_tweet_text.lock().unwrap().clone().unwrap();
+        let tweet2 = last_tweet_text(&client);
         assert!(tweet2.contains("Commitments are now closed"));
 
         // 3. CommitmentsClosed -> FrameCaptured (Internal state change)
-        // We simulate time passing for the check inside capture_frame
-        let mut block = block;
-        block.target_timestamp = Utc::now() - Duration::seconds(1);
+        let clock = MockClock::new(block.target_timestamp);
         let frame_path = PathBuf::from("/tmp/test_frame.jpg");
-        let block = block.capture_frame(frame_path.clone()).unwrap();
+        let block = block.capture_frame(frame_path.clone(), &clock).unwrap();
         assert_eq!(block.state_name(), "FrameCaptured");
         assert_eq!(block.target_frame_path.clone().unwrap(), frame_path);
 
         // 4. FrameCaptured -> RevealsOpen
+        let commitments_tweet_id = block.posted_tweets["CommitmentsOpen"].clone().unwrap();
         let block = block
-            .open_reveals(reveals_deadline, &client, &tweet1)
+            .open_reveals(reveals_deadline, &client, &commitments_tweet_id)
             .await
             .unwrap();
         assert_eq!(block.state_name(), "RevealsOpen");
         assert_eq!(block.reveals_deadline, Some(reveals_deadline));
-        let tweet3 = client.last_tweet_text.lock().unwrap().clone().unwrap();
-        let image_path = client.last_image_path.lock().unwrap().clone().unwrap();
-        assert!(tweet3.contains("Target frame revealed!"));
-        assert_eq!(image_path, frame_path);
+        let reveals_tweet = client.tweets().last().unwrap().clone();
+        assert!(reveals_tweet.text.contains("Target frame revealed!"));
+        assert_eq!(reveals_tweet.replied_to_id(), Some(commitments_tweet_id.as_str()));
+        assert_eq!(client.image_path(&reveals_tweet.id), Some(frame_path));
+        assert_eq!(
+            block.posted_tweets["RevealsOpen"].as_deref(),
+            Some(reveals_tweet.id.as_str())
+        );
+        assert_eq!(
+            block.posted_tweets.keys().collect::<Vec<_>>(),
+            vec!["CommitmentsClosed", "CommitmentsOpen", "RevealsOpen"]
+        );
 
         // ... subsequent states would follow
     }
 
     #[tokio::test]
     async fn test_capture_frame_before_timestamp_fails() {
-        let client = MockTwitterClient::new();
+        let client = InMemoryTwitterApi::new();
         let block = common_block()
             .open_commitments(Utc::now() + Duration::hours(1), &client)
             .await
@@ -546,12 +565,153 @@ mod tests {
             .unwrap();
 
         // This should fail because the target_timestamp is in the future
-        let result = block.capture_frame(PathBuf::from("/tmp/fail.jpg"));
+        let clock = MockClock::new(block.target_timestamp - Duration::seconds(1));
+        let result = block.clone().capture_frame(PathBuf::from("/tmp/fail.jpg"), &clock);
         assert!(result.is_err());
         if let Err(CliptionsError::ValidationError(msg)) = result {
             assert_eq!(msg, "Target timestamp has not yet been reached.");
         } else {
             panic!("Expected a ValidationError");
         }
+
+        // Once the clock reaches the target timestamp the frame can be captured
+        clock.advance(Duration::seconds(1));
+        let block = block
+            .capture_frame(PathBuf::from("/tmp/frame.jpg"), &clock)
+            .unwrap();
+        assert_eq!(block.state_name(), "FrameCaptured");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_announcement_counts_as_posted() {
+        let client = InMemoryTwitterApi::new();
+        let deadline = Utc::now() + Duration::hours(1);
+        let first = common_block().open_commitments(deadline, &client).await.unwrap();
+        assert!(first.posted_tweets["CommitmentsOpen"].is_some());
+
+        // Retrying the transition posts the same text, which Twitter rejects
+        let block = common_block().open_commitments(deadline, &client).await.unwrap();
+        assert_eq!(block.state_name(), "CommitmentsOpen");
+        assert_eq!(block.posted_tweets["CommitmentsOpen"], None);
+        assert_eq!(client.tweets().len(), 1);
+
+        // Any other rejection still fails the transition
+        client.fail_next_call(
+            403,
+            r#"{"detail":"You are not permitted to perform this action.","title":"Forbidden","status":403}"#,
+        );
+        match block.close_commitments(&client).await {
+            Err(CliptionsError::ApiError(msg)) => assert!(msg.contains("not permitted")),
+            other => panic!("Expected an ApiError, got {:?}", other.map(|b| b.to_string())),
+        }
+    }
+
+    async fn frame_captured_block(
+        client: &InMemoryTwitterApi,
+        frame_path: PathBuf,
+    ) -> Block<FrameCaptured> {
+        let block = common_block()
+            .open_commitments(Utc::now() + Duration::hours(1), client)
+            .await
+            .unwrap()
+            .close_commitments(client)
+            .await
+            .unwrap();
+        let clock = MockClock::new(block.target_timestamp);
+        block.capture_frame(frame_path, &clock).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_open_reveals_without_frame_fails() {
+        let client = InMemoryTwitterApi::new();
+        let mut block = frame_captured_block(&client, PathBuf::from("/tmp/frame.jpg")).await;
+        block.target_frame_path = None;
+        let parent = block.posted_tweets["CommitmentsOpen"].clone().unwrap();
+
+        match block
+            .open_reveals(Utc::now() + Duration::hours(48), &client, &parent)
+            .await
+        {
+            Err(CliptionsError::Block(BlockError::TargetFrameNotCaptured { block_num })) => {
+                assert_eq!(block_num, "1")
+            }
+            other => panic!(
+                "Expected TargetFrameNotCaptured, got {:?}",
+                other.map(|b| b.to_string())
+            ),
+        }
+        assert_eq!(client.tweets().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_open_reveals_twice_is_rejected() {
+        let client = InMemoryTwitterApi::new();
+        let block = frame_captured_block(&client, PathBuf::from("/tmp/frame.jpg")).await;
+        let parent = block.posted_tweets["CommitmentsOpen"].clone().unwrap();
+
+        // A block without the commitments-closed announcement can't open reveals
+        let mut unclosed = block.clone();
+        unclosed.posted_tweets.remove("CommitmentsClosed");
+        assert!(matches!(
+            unclosed.open_reveals(Utc::now() + Duration::hours(48), &client, &parent).await,
+            Err(CliptionsError::Block(BlockError::CommitmentsNotClosed { .. }))
+        ));
+
+        let opened = block
+            .open_reveals(Utc::now() + Duration::hours(48), &client, &parent)
+            .await
+            .unwrap();
+        assert!(opened.posted_tweets.contains_key("RevealsOpen"));
+
+        // Retrying from a FrameCaptured checkpoint saved after the post
+        let posted = client.tweets().len();
+        let retried = opened.into_state::<FrameCaptured>();
+        match retried
+            .open_reveals(Utc::now() + Duration::hours(48), &client, &parent)
+            .await
+        {
+            Err(CliptionsError::Block(BlockError::RevealsAlreadyOpen { block_num, .. })) => {
+                assert_eq!(block_num, "1")
+            }
+            other => panic!(
+                "Expected RevealsAlreadyOpen, got {:?}",
+                other.map(|b| b.to_string())
+            ),
+        }
+        assert_eq!(client.tweets().len(), posted);
+    }
+
+    #[tokio::test]
+    async fn test_open_reveals_from_checkpoint_without_ledger() {
+        let client = InMemoryTwitterApi::new();
+        let block = frame_captured_block(&client, PathBuf::from("/tmp/frame.jpg")).await;
+        let parent = block.posted_tweets["CommitmentsOpen"].clone().unwrap();
+
+        // Checkpoints saved before the ledger existed have no posted_tweets
+        let mut json = serde_json::to_value(&block).unwrap();
+        json.as_object_mut().unwrap().remove("posted_tweets");
+        let legacy: Block<FrameCaptured> = serde_json::from_value(json).unwrap();
+        assert!(legacy.posted_tweets.is_empty());
+
+        let opened = legacy
+            .clone()
+            .open_reveals(Utc::now() + Duration::hours(48), &client, &parent)
+            .await
+            .unwrap();
+        assert_eq!(
+            opened.posted_tweets.keys().collect::<Vec<_>>(),
+            vec!["CommitmentsClosed", "CommitmentsOpen", "RevealsOpen"]
+        );
+        assert!(opened.posted_tweets["RevealsOpen"].is_some());
+
+        // A legacy checkpoint saved after reveals opened has its reveals deadline set
+        let mut reopened = legacy;
+        reopened.reveals_deadline = Some(Utc::now() + Duration::hours(48));
+        let posted = client.tweets().len();
+        assert!(matches!(
+            reopened.open_reveals(Utc::now() + Duration::hours(48), &client, &parent).await,
+            Err(CliptionsError::Block(BlockError::RevealsAlreadyOpen { .. }))
+        ));
+        assert_eq!(client.tweets().len(), posted);
     }
 }
@@ -0,0 +1,260 @@
+//! Advisory per-block lock files
+//!
+//! Two processes working on the same blocks file could both process a block
+//! and pay it out twice. Before mutating a block, `BlockProcessor` takes an
+//! exclusive OS file lock on a lock file for it in a `locks` directory beside
+//! the blocks file, recording the holder's PID and when it was taken, and
+//! clears the file once the change is saved. The OS releases the lock when
+//! its holder exits, so a lock left behind by a process that died is taken
+//! over atomically by the next one, which reports whose lock it reclaimed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{BlockError, CliptionsError, Result};
+
+/// Who holds a block lock, as recorded in the lock file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub acquired_at: DateTime<Utc>,
+}
+
+/// A held block lock, released when dropped
+#[derive(Debug)]
+pub struct BlockLock {
+    file: File,
+    path: PathBuf,
+    holder: LockHolder,
+    reclaimed_from: Option<LockHolder>,
+}
+
+/// Lock file guarding `block_num` in the blocks file at `blocks_file`
+///
+/// e.g. `data/locks/blocks.json.block_7.lock` for block 7 of `data/blocks.json`
+pub fn lock_path(blocks_file: impl AsRef<Path>, block_num: &str) -> PathBuf {
+    let blocks_file = blocks_file.as_ref();
+    let file_name = blocks_file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    blocks_file
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join("locks")
+        .join(format!("{}.block_{}.lock", file_name, block_num))
+}
+
+/// Lock file serializing rewrites of the whole blocks file at `blocks_file`
+///
+/// e.g. `data/locks/blocks.json.lock` for `data/blocks.json`
+pub fn file_lock_path(blocks_file: impl AsRef<Path>) -> PathBuf {
+    let blocks_file = blocks_file.as_ref();
+    let file_name = blocks_file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    blocks_file
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join("locks")
+        .join(format!("{}.lock", file_name))
+}
+
+/// Exclusive lock on a whole blocks file, held while it is read and rewritten
+///
+/// Unlike `BlockLock` this waits for the current holder, since a rewrite
+/// takes moments. Released when dropped.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Wait for and take the lock at `path` (see `file_lock_path`)
+    pub fn acquire(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(lock_dir) = path.parent() {
+            fs::create_dir_all(lock_dir)?;
+        }
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        file.lock()?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+impl BlockLock {
+    /// Take the lock for `block_num` at `path` (see `lock_path`)
+    ///
+    /// Fails with `BlockError::Locked` while another holder has the lock.
+    pub fn acquire(path: impl Into<PathBuf>, block_num: &str) -> Result<Self> {
+        let path = path.into();
+        if let Some(lock_dir) = path.parent() {
+            fs::create_dir_all(lock_dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let holder = read_holder(&path).unwrap_or_else(|| unknown_holder(&path));
+                return Err(locked(block_num, &holder));
+            }
+            Err(TryLockError::Error(e)) => return Err(e.into()),
+        }
+
+        // A holder still recorded in the file exited without clearing it
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let reclaimed_from = parse_holder(&content, &path);
+
+        let holder = LockHolder {
+            pid: std::process::id(),
+            acquired_at: Utc::now(),
+        };
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serde_json::to_string(&holder)?.as_bytes())?;
+        file.sync_all()?;
+        Ok(Self {
+            file,
+            path,
+            holder,
+            reclaimed_from,
+        })
+    }
+
+    pub fn holder(&self) -> &LockHolder {
+        &self.holder
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Holder of a lock that was left behind and taken over by this one
+    pub fn reclaimed_from(&self) -> Option<&LockHolder> {
+        self.reclaimed_from.as_ref()
+    }
+}
+
+impl Drop for BlockLock {
+    fn drop(&mut self) {
+        // Clear the holder before the lock is released by closing the file
+        let _ = self.file.set_len(0);
+        let _ = self.file.unlock();
+    }
+}
+
+/// The recorded holder of a lock file, or `None` if nobody holds it
+fn read_holder(path: &Path) -> Option<LockHolder> {
+    parse_holder(&fs::read_to_string(path).ok()?, path)
+}
+
+/// A cleared lock file has no holder; one that can't be parsed (for instance
+/// one caught mid-write) is dated by its modification time
+fn parse_holder(content: &str, path: &Path) -> Option<LockHolder> {
+    if content.trim().is_empty() {
+        return None;
+    }
+    serde_json::from_str(content)
+        .ok()
+        .or_else(|| Some(unknown_holder(path)))
+}
+
+fn unknown_holder(path: &Path) -> LockHolder {
+    let acquired_at = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    LockHolder {
+        pid: 0,
+        acquired_at,
+    }
+}
+
+fn locked(block_num: &str, holder: &LockHolder) -> CliptionsError {
+    BlockError::Locked {
+        block_num: block_num.to_string(),
+        pid: holder.pid,
+        acquired_at: holder.acquired_at.to_rfc3339(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_held_lock_cannot_be_reacquired() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks_file = temp_dir.path().join("blocks.json");
+
+        let path = lock_path(&blocks_file, "7");
+        assert_eq!(
+            path,
+            temp_dir
+                .path()
+                .join("locks")
+                .join("blocks.json.block_7.lock")
+        );
+        let lock = BlockLock::acquire(&path, "7").unwrap();
+        assert_eq!(lock.holder().pid, std::process::id());
+        assert!(lock.path().exists());
+
+        match BlockLock::acquire(&path, "7") {
+            Err(CliptionsError::Block(BlockError::Locked { block_num, pid, .. })) => {
+                assert_eq!(block_num, "7");
+                assert_eq!(pid, std::process::id());
+            }
+            other => panic!("Expected a Locked error, got {:?}", other),
+        }
+        // Other blocks and other blocks files are unaffected
+        BlockLock::acquire(lock_path(&blocks_file, "8"), "8").unwrap();
+        let other_file = temp_dir.path().join("other.json");
+        BlockLock::acquire(lock_path(&other_file, "7"), "7").unwrap();
+
+        // Dropping the lock releases it and clears the holder
+        drop(lock);
+        assert!(read_holder(&path).is_none());
+        let relocked = BlockLock::acquire(&path, "7").unwrap();
+        assert!(relocked.reclaimed_from().is_none());
+    }
+
+    #[test]
+    fn test_abandoned_lock_is_reclaimed() {
+        let temp_dir = TempDir::new().unwrap();
+        // A process that died while holding the lock left its holder behind
+        let abandoned = LockHolder {
+            pid: 999_999,
+            acquired_at: Utc::now() - chrono::Duration::hours(2),
+        };
+        let path = lock_path(temp_dir.path().join("blocks.json"), "3");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, serde_json::to_string(&abandoned).unwrap()).unwrap();
+
+        let lock = BlockLock::acquire(&path, "3").unwrap();
+        assert_eq!(lock.holder().pid, std::process::id());
+        assert_eq!(lock.reclaimed_from(), Some(&abandoned));
+        assert_eq!(read_holder(&path).unwrap(), *lock.holder());
+    }
+}
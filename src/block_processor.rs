@@ -4,76 +4,287 @@
 //! including participant management, commitment verification, scoring, and payout calculation.
 
 use std::collections::HashMap;
-use std::fs;
+use std::io::{IsTerminal, Write};
 use std::path::Path;
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
-use serde_json;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
-use crate::commitment::CommitmentVerifier;
+use crate::block_store::{BlockGuard, BlockStore, JsonBlockStore};
+use crate::commitment::{CommitmentVerification, CommitmentVerifier};
 use crate::embedder::EmbedderTrait;
-use crate::error::{Result, BlockError};
-use crate::scoring::{process_participants, ScoreValidator, ScoringStrategy};
-use crate::types::{Participant, BlockData, BlockStatus, ScoringResult};
+use crate::error::{BlockError, CliptionsError, Result, ScoringError};
+use crate::payout::{PayoutCalculator, PayoutConfig};
+use crate::scoring::{
+    process_participants_with_calculator, ScoreValidator, ScoringStrategy, SimilarityMatrix,
+};
+use crate::scoring_cache::ScoringCache;
+use crate::scoring_version::ScoringVersion;
+use crate::social::UrlParser;
+use crate::types::{Participant, BlockData, BlockStatus, BlockSummary, ScoringResult};
+
+/// Receives progress while `process_all_blocks` works through a batch
+///
+/// Scoring a block with CLIP can take a while, so front ends use this to
+/// show which block is being scored. Every method defaults to doing nothing.
+pub trait ProgressReporter: Send + Sync {
+    /// Scoring of `block_num` started; `index` counts from 0 up to `total`
+    fn block_started(&self, _block_num: &str, _index: usize, _total: usize) {}
+
+    /// `block_num` was scored; `completed` blocks of `total` are done
+    fn block_finished(&self, _block_num: &str, _completed: usize, _total: usize) {}
+
+    /// The batch is done
+    fn finish(&self) {}
+}
+
+/// Reporter that reports nothing, the default for library use
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SilentProgress;
+
+impl ProgressReporter for SilentProgress {}
+
+/// Reporter that draws a progress bar on stderr
+///
+/// Does nothing when stderr isn't a terminal, so piped and logged output
+/// stays clean. Colors follow `colored`'s global override, which `--no-color`
+/// sets.
+#[derive(Debug, Clone, Copy)]
+pub struct TerminalProgress {
+    enabled: bool,
+}
+
+impl TerminalProgress {
+    /// Width of the bar in characters
+    const BAR_WIDTH: usize = 30;
+
+    /// Draw progress if stderr is a terminal
+    pub fn new() -> Self {
+        Self {
+            enabled: std::io::stderr().is_terminal(),
+        }
+    }
+
+    fn draw(&self, done: usize, total: usize, status: &str) {
+        if !self.enabled || total == 0 {
+            return;
+        }
+        let filled = Self::BAR_WIDTH * done / total;
+        let bar = format!(
+            "{}{}",
+            "#".repeat(filled).green(),
+            "-".repeat(Self::BAR_WIDTH - filled).dimmed()
+        );
+        let mut stderr = std::io::stderr();
+        let _ = write!(stderr, "\r\x1b[2K[{}] {}/{} {}", bar, done, total, status);
+        let _ = stderr.flush();
+    }
+}
+
+impl Default for TerminalProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for TerminalProgress {
+    fn block_started(&self, block_num: &str, index: usize, total: usize) {
+        self.draw(index, total, &format!("scoring block {}", block_num));
+    }
+
+    fn block_finished(&self, block_num: &str, completed: usize, total: usize) {
+        self.draw(completed, total, &format!("scored block {}", block_num));
+    }
+
+    fn finish(&self) {
+        if self.enabled {
+            eprintln!();
+        }
+    }
+}
 
 /// Block processor for managing prediction blocks
-pub struct BlockProcessor<E: EmbedderTrait, S: ScoringStrategy> {
-    blocks_file: String,
+///
+/// Blocks are read and written through a `BlockStore`, a JSON file by default.
+pub struct BlockProcessor<E: EmbedderTrait, S: ScoringStrategy, B: BlockStore = JsonBlockStore> {
+    store: B,
     commitment_verifier: CommitmentVerifier,
     score_validator: ScoreValidator<E, S>,
     blocks_cache: HashMap<String, BlockData>,
+    scoring_version: Option<ScoringVersion>,
+    payout_config: PayoutConfig,
+    progress: Arc<dyn ProgressReporter>,
+    warnings: Vec<String>,
 }
 
 impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
     /// Create a new block processor
     pub fn new(blocks_file: String, embedder: E, scoring_strategy: S) -> Self {
+        Self::with_store(JsonBlockStore::new(blocks_file), embedder, scoring_strategy)
+    }
+
+    /// Path of the blocks file this processor reads and writes
+    pub fn blocks_file(&self) -> &str {
+        self.store.path()
+    }
+
+    /// Save blocks data to file
+    pub fn save_blocks(&self, blocks: &HashMap<String, BlockData>) -> Result<()> {
+        self.store.save_all(blocks)
+    }
+}
+
+impl<E: EmbedderTrait, S: ScoringStrategy, B: BlockStore> BlockProcessor<E, S, B> {
+    /// Create a block processor keeping its blocks in `store`
+    pub fn with_store(store: B, embedder: E, scoring_strategy: S) -> Self {
         Self {
-            blocks_file,
+            store,
             commitment_verifier: CommitmentVerifier::new(),
             score_validator: ScoreValidator::new(embedder, scoring_strategy),
             blocks_cache: HashMap::new(),
+            scoring_version: None,
+            payout_config: PayoutConfig {
+                minimum_players: 1,
+                ..PayoutConfig::default()
+            },
+            progress: Arc::new(SilentProgress),
+            warnings: Vec::new(),
         }
     }
 
-    /// Load blocks data from file
-    pub fn load_blocks(&mut self) -> Result<()> {
-        if !Path::new(&self.blocks_file).exists() {
-                    // Create empty blocks file if it doesn't exist
-        let empty_blocks: HashMap<String, BlockData> = HashMap::new();
-            self.save_blocks(&empty_blocks)?;
-            return Ok(());
-        }
+    /// The store blocks are read from and written to
+    pub fn store(&self) -> &B {
+        &self.store
+    }
 
-        let content =
-            fs::read_to_string(&self.blocks_file).map_err(|_e| BlockError::DataFileNotFound {
-                path: self.blocks_file.clone(),
-            })?;
+    /// Report batch progress to `progress` (silent by default)
+    pub fn with_progress_reporter(mut self, progress: impl ProgressReporter + 'static) -> Self {
+        self.progress = Arc::new(progress);
+        self
+    }
 
-        // Handle empty file case
-        if content.trim().is_empty() {
-            let empty_blocks: HashMap<String, BlockData> = HashMap::new();
-            self.save_blocks(&empty_blocks)?;
-            return Ok(());
+    /// Reuse similarities from `cache` instead of re-running the embedder
+    pub fn with_scoring_cache(self, cache: ScoringCache) -> Self {
+        Self {
+            score_validator: self.score_validator.with_scoring_cache(cache),
+            ..self
         }
+    }
 
-        let blocks: HashMap<String, BlockData> = serde_json::from_str(&content)?;
-        self.blocks_cache = blocks;
+    /// Pay blocks out under `config`
+    ///
+    /// Uses the config's payout curve, rounding, minimum players, currency
+    /// label, minimum prize pool and validator fee; each block's own prize
+    /// pool replaces `config.prize_pool`.
+    pub fn with_payout_config(mut self, config: PayoutConfig) -> Result<Self> {
+        if !(0.0..100.0).contains(&config.validator_fee_percent) {
+            return Err(CliptionsError::ValidationError(
+                "Validator fee percentage must be between 0 and 100".to_string(),
+            ));
+        }
+        self.payout_config = config;
+        Ok(self)
+    }
 
-        Ok(())
+    /// Payout calculator for a block with the given prize pool
+    fn payout_calculator(&self, prize_pool: f64) -> PayoutCalculator {
+        PayoutCalculator::with_config(PayoutConfig {
+            prize_pool,
+            ..self.payout_config.clone()
+        })
     }
 
-    /// Save blocks data to file
-    pub fn save_blocks(&self, blocks: &HashMap<String, BlockData>) -> Result<()> {
-        let content = serde_json::to_string_pretty(blocks)?;
-        fs::write(&self.blocks_file, content)?;
+    /// Validator fee taken from a block's prize pool before payouts
+    pub fn validator_fee(&mut self, block_num: &str) -> Result<f64> {
+        let prize_pool = self.get_block(block_num)?.prize_pool;
+        Ok(self.payout_calculator(prize_pool).calculate_validator_fee())
+    }
+
+    /// Stamp processed blocks and their results with the given scoring version
+    pub fn with_scoring_version(mut self, scoring_version: ScoringVersion) -> Self {
+        self.scoring_version = Some(scoring_version);
+        self
+    }
+
+    /// Check whether a block was processed under a different scoring version
+    ///
+    /// Returns the recorded version id if it differs from the processor's current version.
+    pub fn scoring_version_mismatch(&mut self, block_num: &str) -> Result<Option<String>> {
+        let current = match &self.scoring_version {
+            Some(version) => version.id.clone(),
+            None => return Ok(None),
+        };
+        let block = self.get_block(block_num)?;
+        Ok(block.scoring_version_mismatch(&current).map(str::to_string))
+    }
+
+    /// Load every block from the store
+    pub fn load_blocks(&mut self) -> Result<()> {
+        self.blocks_cache = self.store.load_all()?;
         Ok(())
     }
 
-    /// Get a block by ID
-    pub fn get_block(&mut self, block_num: &str) -> Result<&BlockData> {
+    /// Discard cached blocks and read the store again
+    ///
+    /// Picks up changes saved by other processes since the last load.
+    pub fn reload_blocks(&mut self) -> Result<()> {
+        self.blocks_cache.clear();
+        self.load_blocks()
+    }
+
+    /// Save one cached block back to the store
+    fn save_block(&mut self, block_num: &str) -> Result<()> {
+        let block = self.blocks_cache.get(block_num).ok_or_else(|| BlockError::BlockNotFound {
+            block_num: block_num.to_string(),
+        })?;
+        self.store.put(block)
+    }
+
+    /// Lock `block_num`, then re-read it from the store
+    ///
+    /// Another process may have saved the block between our last read and
+    /// taking the lock; working from the cached copy would overwrite its
+    /// changes or process the block a second time.
+    fn lock_block(&mut self, block_num: &str) -> Result<BlockGuard> {
+        let lock = self.store.lock_block(block_num)?;
+        if let Some(previous) = lock.reclaimed_from() {
+            self.warnings.push(format!(
+                "Reclaimed the lock on block {} left by process {} since {}",
+                block_num, previous.pid, previous.acquired_at
+            ));
+        }
+        match self.store.get(block_num)? {
+            Some(block) => self.blocks_cache.insert(block_num.to_string(), block),
+            None => self.blocks_cache.remove(block_num),
+        };
+        Ok(lock)
+    }
+
+    /// Warnings raised since the last call, such as reclaimed block locks or truncated guesses
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Make sure `block_num` is cached if the store has it
+    ///
+    /// Blocks saved by other processes after the last load are fetched on demand.
+    fn cache_block(&mut self, block_num: &str) -> Result<()> {
         if self.blocks_cache.is_empty() {
             self.load_blocks()?;
         }
+        if !self.blocks_cache.contains_key(block_num) {
+            if let Some(block) = self.store.get(block_num)? {
+                self.blocks_cache.insert(block_num.to_string(), block);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a block by ID
+    pub fn get_block(&mut self, block_num: &str) -> Result<&BlockData> {
+        self.cache_block(block_num)?;
 
         self.blocks_cache.get(block_num).ok_or_else(|| {
             BlockError::BlockNotFound {
@@ -85,9 +296,7 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
 
     /// Get a mutable reference to a block
     pub fn get_block_mut(&mut self, block_num: &str) -> Result<&mut BlockData> {
-        if self.blocks_cache.is_empty() {
-            self.load_blocks()?;
-        }
+        self.cache_block(block_num)?;
 
         self.blocks_cache.get_mut(block_num).ok_or_else(|| {
             BlockError::BlockNotFound {
@@ -97,6 +306,32 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
         })
     }
 
+    /// Similarity matrix between a block's target image and its verified guesses
+    pub fn similarity_matrix(&mut self, block_num: &str) -> Result<SimilarityMatrix> {
+        let block = self.get_block(block_num)?;
+        let target_image_path = block.target_image_path.clone();
+        let guesses: Vec<String> = block
+            .participants
+            .iter()
+            .filter(|p| p.verified)
+            .map(|p| p.guess.text.clone())
+            .collect();
+
+        self.score_validator
+            .similarity_matrix(&target_image_path, &guesses)
+    }
+
+    /// Participants who committed but never revealed, so can't be scored or paid
+    pub fn unrevealed_participants(&mut self, block_num: &str) -> Result<Vec<Participant>> {
+        let block = self.get_block(block_num)?;
+        Ok(block
+            .participants
+            .iter()
+            .filter(|p| !p.commitment.is_empty() && p.salt.as_deref().is_none_or(str::is_empty))
+            .cloned()
+            .collect())
+    }
+
     /// Create a new block
     pub fn create_block(
         &mut self,
@@ -107,40 +342,69 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
         commitment_deadline: Option<DateTime<Utc>>,
         reveal_deadline: Option<DateTime<Utc>>,
     ) -> Result<()> {
-        if self.blocks_cache.is_empty() {
-            self.load_blocks()?;
-        }
+        let _lock = self.lock_block(&block_num)?;
 
         if self.blocks_cache.contains_key(&block_num) {
             return Err(BlockError::AlreadyProcessed.into());
         }
 
-        let block = if let (Some(commit_deadline), Some(reveal_deadline)) = (commitment_deadline, reveal_deadline) {
+        self.insert_new_block(BlockParams {
+            block_num,
+            target_image_path,
+            social_id,
+            prize_pool,
+            commitment_deadline,
+            reveal_deadline,
+        })
+    }
+
+    /// Return the block if it exists, or create and save it
+    ///
+    /// Safe to retry: the returned flag is true only when the block was newly
+    /// created. An existing block must match `params`; deadlines are only
+    /// compared when given.
+    pub fn ensure_block(&mut self, params: BlockParams) -> Result<(BlockData, bool)> {
+        let _lock = self.lock_block(&params.block_num)?;
+
+        if let Some(existing) = self.blocks_cache.get(&params.block_num) {
+            params.check_matches(existing)?;
+            return Ok((existing.clone(), false));
+        }
+
+        let block_num = params.block_num.clone();
+        self.insert_new_block(params)?;
+        Ok((self.get_block(&block_num)?.clone(), true))
+    }
+
+    /// Build a block from `params` and save it; the caller holds its lock
+    fn insert_new_block(&mut self, params: BlockParams) -> Result<()> {
+        let block = if let (Some(commit_deadline), Some(reveal_deadline)) =
+            (params.commitment_deadline, params.reveal_deadline)
+        {
             BlockData::with_deadlines(
-                block_num.clone(),
-                target_image_path,
-                social_id,
-                prize_pool,
+                params.block_num.clone(),
+                params.target_image_path,
+                params.social_id,
+                params.prize_pool,
                 commit_deadline,
                 reveal_deadline,
             )
         } else {
             BlockData::new(
-                block_num.clone(),
-                target_image_path,
-                social_id,
-                prize_pool,
+                params.block_num.clone(),
+                params.target_image_path,
+                params.social_id,
+                params.prize_pool,
             )
         };
 
-        self.blocks_cache.insert(block_num, block);
-        self.save_blocks(&self.blocks_cache)?;
-
-        Ok(())
+        self.blocks_cache.insert(params.block_num.clone(), block);
+        self.save_block(&params.block_num)
     }
 
     /// Add a participant to a block
     pub fn add_participant(&mut self, block_num: &str, participant: Participant) -> Result<()> {
+        let _lock = self.lock_block(block_num)?;
         let block = self.get_block_mut(block_num)?;
 
         // if !block.is_open() {
@@ -148,17 +412,29 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
         // }
 
         block.add_participant(participant);
-        self.save_blocks(&self.blocks_cache)?;
+        self.save_block(block_num)?;
 
         Ok(())
     }
 
     /// Verify commitments for a block
     pub fn verify_commitments(&mut self, block_num: &str) -> Result<Vec<bool>> {
-        // Load blocks if needed
-        if self.blocks_cache.is_empty() {
-            self.load_blocks()?;
-        }
+        Ok(self
+            .verify_commitments_detailed(block_num)?
+            .into_iter()
+            .map(|verification| verification.valid)
+            .collect())
+    }
+
+    /// Verify commitments for a block, explaining each failure
+    pub fn verify_commitments_detailed(
+        &mut self,
+        block_num: &str,
+    ) -> Result<Vec<CommitmentVerification>> {
+        let _lock = self.lock_block(block_num)?;
+
+        // Load the block if needed
+        self.cache_block(block_num)?;
 
         let block =
             self.blocks_cache
@@ -170,36 +446,37 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
         let mut results = Vec::new();
 
         for participant in &mut block.participants {
-            if let Some(salt) = &participant.salt {
-                let is_valid = self.commitment_verifier.verify(
-                    &participant.guess.text,
-                    salt,
-                    &participant.commitment,
-                );
-
-                if is_valid {
-                    participant.verified = true;
-                }
-
-                results.push(is_valid);
-            } else {
-                results.push(false);
+            let verification = participant.explain_commitment_with(&self.commitment_verifier);
+            if verification.valid {
+                participant.verified = true;
             }
+            results.push(verification);
         }
 
-        self.save_blocks(&self.blocks_cache)?;
+        self.save_block(block_num)?;
         Ok(results)
     }
 
     /// Process block payouts
     pub fn process_block_payouts(&mut self, block_num: &str) -> Result<Vec<ScoringResult>> {
-        // Load blocks if needed
-        if self.blocks_cache.is_empty() {
-            self.load_blocks()?;
+        // Held until the results are saved, so no other process pays this block concurrently
+        let _lock = self.lock_block(block_num)?;
+
+        // Load the block if needed
+        self.cache_block(block_num)?;
+
+        // Re-processing with a different scoring version would change historical results
+        if let Some(recorded) = self.scoring_version_mismatch(block_num)? {
+            return Err(BlockError::ScoringVersionMismatch {
+                block_num: block_num.to_string(),
+                recorded,
+                current: self.scoring_version.as_ref().map(|v| v.id.clone()).unwrap_or_default(),
+            }
+            .into());
         }
 
         // Get block data first
-        let (target_image_path, prize_pool, verified_participants) = {
+        let (target_image_path, prize_pool, verified_participants, embedder_spec, reference) = {
             let block =
                 self.blocks_cache
                     .get(block_num)
@@ -234,59 +511,223 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
                 block.target_image_path.clone(),
                 block.prize_pool,
                 verified_participants,
+                block.embedder_spec.clone(),
+                block.reference_caption.clone(),
             )
         };
 
-        // Process participants and calculate scores
-        let results = process_participants(
-            &verified_participants,
-            &target_image_path,
-            prize_pool,
-            &self.score_validator,
-        )?;
+        // Pools below the configured minimum aren't worth distributing
+        let calculator = self.payout_calculator(prize_pool);
+        calculator.check_prize_pool()?;
+
+        // Participants are paid from the pool left after the fees, split by the configured curve
+        let distributable_pool = calculator.calculate_available_pool();
+        if distributable_pool <= 0.0 {
+            return Err(ScoringError::InvalidPrizePool {
+                amount: distributable_pool,
+            }
+            .into());
+        }
+        // Blocks that name their own embedder are scored with it instead of the default
+        let mut results = match &embedder_spec {
+            Some(spec) => {
+                let validator = self.score_validator.with_embedder(spec.build()?);
+                self.warnings
+                    .extend(truncation_warnings(&validator, block_num, &verified_participants)?);
+                score_block(
+                    &validator,
+                    reference.as_deref(),
+                    &verified_participants,
+                    &target_image_path,
+                    &calculator,
+                )?
+            }
+            None => {
+                self.warnings.extend(truncation_warnings(
+                    &self.score_validator,
+                    block_num,
+                    &verified_participants,
+                )?);
+                score_block(
+                    &self.score_validator,
+                    reference.as_deref(),
+                    &verified_participants,
+                    &target_image_path,
+                    &calculator,
+                )?
+            }
+        };
+
+        for result in &mut results {
+            result.participant.payout.currency = self.payout_config.currency_label.clone();
+        }
 
         // Update block status to Complete (but don't add redundant results section)
         let block = self.blocks_cache.get_mut(block_num).unwrap(); // Safe because we checked above
+        if let Some(first) = results.first() {
+            // Results carry the embedder and strategy that actually scored the block,
+            // which a block's own embedder or reference caption may have changed
+            if let (Some(embedder), Some(strategy)) = (&first.embedder_name, &first.strategy_name) {
+                block.stamp_provenance(embedder.clone(), strategy.clone());
+            }
+        }
+        if let Some(version) = &self.scoring_version {
+            block.stamp_scoring_version(version.id.clone());
+            results = results
+                .into_iter()
+                .map(|result| result.with_scoring_version(version.id.clone()))
+                .collect();
+        }
         block.set_status(BlockStatus::Complete);
-        self.save_blocks(&self.blocks_cache)?;
+        self.save_block(block_num)?;
 
         Ok(results)
     }
 
     /// Get all block IDs
     pub fn get_block_nums(&mut self) -> Result<Vec<String>> {
-        if self.blocks_cache.is_empty() {
-            self.load_blocks()?;
-        }
-
-        Ok(self.blocks_cache.keys().cloned().collect())
+        self.store.list_nums()
     }
 
     /// Process all blocks
     pub fn process_all_blocks(&mut self) -> Result<HashMap<String, Vec<ScoringResult>>> {
-        let block_nums = self.get_block_nums()?;
-        let mut all_results = HashMap::new();
-
-        for block_num in block_nums {
-            // Only process blocks that are open or processing
+        // Only process blocks that are open or processing
+        let mut block_nums = Vec::new();
+        for block_num in self.get_block_nums()? {
             let block = self.get_block(&block_num)?;
             if matches!(block.status, BlockStatus::Open | BlockStatus::Processing) {
-                match self.process_block_payouts(&block_num) {
-                    Ok(results) => {
-                        all_results.insert(block_num, results);
-                    }
-                    Err(e) => {
-                        panic!("CRITICAL: Failed to process block {}: {}. Cannot continue batch processing with incomplete results as this could lead to missing payouts.", block_num, e);
-                    }
+                block_nums.push(block_num);
+            }
+        }
+
+        let total = block_nums.len();
+        let mut all_results = HashMap::new();
+
+        for (index, block_num) in block_nums.into_iter().enumerate() {
+            self.progress.block_started(&block_num, index, total);
+            match self.process_block_payouts(&block_num) {
+                Ok(results) => {
+                    self.progress.block_finished(&block_num, index + 1, total);
+                    all_results.insert(block_num, results);
+                }
+                Err(e) => {
+                    self.progress.finish();
+                    panic!("CRITICAL: Failed to process block {}: {}. Cannot continue batch processing with incomplete results as this could lead to missing payouts.", block_num, e);
                 }
             }
         }
+        self.progress.finish();
 
         Ok(all_results)
     }
 
+    /// Summaries of every block, ordered by block number
+    pub fn summarize_all(&mut self) -> Result<Vec<BlockSummary>> {
+        if self.blocks_cache.is_empty() {
+            self.load_blocks()?;
+        }
+
+        let mut summaries: Vec<BlockSummary> =
+            self.blocks_cache.values().map(BlockData::summary).collect();
+        summaries.sort_by_key(|s| (s.block_num.parse::<u64>().ok(), s.block_num.clone()));
+        Ok(summaries)
+    }
+
+    /// Block summaries filtered, ordered and paged as `query` asks
+    pub fn list_blocks(&mut self, query: &BlockListQuery) -> Result<Vec<BlockSummary>> {
+        Ok(query.apply(self.summarize_all()?))
+    }
+
+    /// Assemble the audit report for a block from its stored data
+    ///
+    /// Scores, ranks and payouts come from the block's stored results, or from
+    /// the scores and payouts recorded on its verified participants if it has
+    /// none. Commitments are re-verified for the report; nothing is rescored
+    /// and the block isn't modified.
+    pub fn generate_report(&mut self, block_num: &str) -> Result<RoundReport> {
+        let validator_fee = self.validator_fee(block_num)?;
+        let url_parser = UrlParser::new()?;
+        let block =
+            self.blocks_cache
+                .get(block_num)
+                .ok_or_else(|| BlockError::BlockNotFound {
+                    block_num: block_num.to_string(),
+                })?;
+
+        // Scored participants by index, best first
+        let mut scored: Vec<(usize, f64, Option<f64>)> = Vec::new();
+        if block.results.is_empty() {
+            let recorded = block
+                .participants
+                .iter()
+                .any(|p| p.verified && (p.score != 0.0 || p.payout.amount != 0.0));
+            if recorded {
+                scored = block
+                    .participants
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.verified)
+                    .map(|(i, p)| (i, p.score, Some(p.payout.amount)))
+                    .collect();
+                scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            }
+        } else {
+            let mut results: Vec<&ScoringResult> = block.results.iter().collect();
+            results.sort_by_key(|r| r.rank.unwrap_or(usize::MAX));
+            for result in results {
+                let index = block.participants.iter().position(|p| {
+                    p.username == result.participant.username
+                        && p.commitment == result.participant.commitment
+                });
+                if let Some(index) = index {
+                    scored.push((index, result.effective_score(), result.payout));
+                }
+            }
+        }
+
+        let tweet_id = |url: &str| url_parser.extract_tweet_id(url).ok();
+        let mut participants: Vec<ParticipantReport> = block
+            .participants
+            .iter()
+            .map(|p| ParticipantReport {
+                username: p.username.clone(),
+                social_id: p.social_id.clone(),
+                wallet: p.wallet.clone(),
+                commitment: p.commitment.clone(),
+                commitment_tweet_id: tweet_id(&p.commitment_url),
+                guess: p.guess.raw_text().to_string(),
+                salt: p.salt.clone(),
+                reveal_tweet_id: tweet_id(&p.guess_url),
+                verified: p.verified,
+                verification: p.explain_commitment_with(&self.commitment_verifier),
+                score: None,
+                rank: None,
+                payout: None,
+            })
+            .collect();
+        for (position, (index, score, payout)) in scored.iter().enumerate() {
+            let report = &mut participants[*index];
+            report.score = Some(*score);
+            report.rank = Some(position + 1);
+            report.payout = *payout;
+        }
+        // Ranked participants first, in rank order
+        participants.sort_by_key(|p| p.rank.unwrap_or(usize::MAX));
+
+        Ok(RoundReport {
+            summary: block.summary(),
+            target_image_path: block.target_image_path.clone(),
+            announcement_tweet_id: block.social_id.clone(),
+            scoring_version: block.scoring_version.clone(),
+            validator_fee,
+            participants,
+            generated_at: Utc::now(),
+        })
+    }
+
     /// Get block statistics
     pub fn get_block_stats(&mut self, block_num: &str) -> Result<BlockStats> {
+        let validator_fee = self.validator_fee(block_num)?;
         let block = self.get_block(block_num)?;
 
         let total_participants = block.participants.len();
@@ -305,6 +746,7 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
             total_participants,
             verified_participants,
             total_prize_pool,
+            validator_fee,
             total_payout,
             is_complete,
             status: block.status.clone(),
@@ -312,6 +754,167 @@ impl<E: EmbedderTrait, S: ScoringStrategy> BlockProcessor<E, S> {
     }
 }
 
+/// Score a block's participants, comparing guesses against the block's reference
+/// caption if it has one and the strategy uses it
+fn score_block<E: EmbedderTrait, S: ScoringStrategy>(
+    validator: &ScoreValidator<E, S>,
+    reference: Option<&str>,
+    participants: &[Participant],
+    target_image_path: &str,
+    calculator: &PayoutCalculator,
+) -> Result<Vec<ScoringResult>> {
+    match reference.and_then(|reference| validator.strategy().with_block_reference(reference)) {
+        Some(strategy) => process_participants_with_calculator(
+            participants,
+            target_image_path,
+            calculator,
+            &validator.with_strategy(strategy),
+        ),
+        None => process_participants_with_calculator(
+            participants,
+            target_image_path,
+            calculator,
+            validator,
+        ),
+    }
+}
+
+/// A warning for each participant whose guess the embedder truncates
+fn truncation_warnings<E: EmbedderTrait, S: ScoringStrategy>(
+    validator: &ScoreValidator<E, S>,
+    block_num: &str,
+    participants: &[Participant],
+) -> Result<Vec<String>> {
+    let mut warnings = Vec::new();
+    for participant in participants {
+        if let Some(truncation) = validator.truncation(&participant.guess.text)? {
+            warnings.push(format!(
+                "Block {}: guess by {} was {}",
+                block_num, participant.username, truncation
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
+/// Parameters for creating a block with `ensure_block`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockParams {
+    pub block_num: String,
+    pub target_image_path: String,
+    pub social_id: String,
+    pub prize_pool: f64,
+    /// Defaults from the creation time when not given
+    pub commitment_deadline: Option<DateTime<Utc>>,
+    /// Defaults from the creation time when not given
+    pub reveal_deadline: Option<DateTime<Utc>>,
+}
+
+impl BlockParams {
+    /// Error unless `block` was created from parameters like these
+    fn check_matches(&self, block: &BlockData) -> Result<()> {
+        let mismatch = |field: &str, existing: String, requested: String| -> Result<()> {
+            Err(BlockError::ParamsMismatch {
+                block_num: self.block_num.clone(),
+                field: field.to_string(),
+                existing,
+                requested,
+            }
+            .into())
+        };
+
+        if block.target_image_path != self.target_image_path {
+            return mismatch(
+                "target image",
+                block.target_image_path.clone(),
+                self.target_image_path.clone(),
+            );
+        }
+        if block.social_id != self.social_id {
+            return mismatch("social id", block.social_id.clone(), self.social_id.clone());
+        }
+        if block.prize_pool != self.prize_pool {
+            return mismatch(
+                "prize pool",
+                block.prize_pool.to_string(),
+                self.prize_pool.to_string(),
+            );
+        }
+        let commitment_deadline = self
+            .commitment_deadline
+            .filter(|d| *d != block.commitment_deadline);
+        if let Some(deadline) = commitment_deadline {
+            return mismatch(
+                "commitment deadline",
+                block.commitment_deadline.to_rfc3339(),
+                deadline.to_rfc3339(),
+            );
+        }
+        if let Some(deadline) = self.reveal_deadline.filter(|d| *d != block.reveal_deadline) {
+            return mismatch(
+                "reveal deadline",
+                block.reveal_deadline.to_rfc3339(),
+                deadline.to_rfc3339(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Field block listings are ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockSort {
+    BlockNum,
+    #[default]
+    CreatedAt,
+    PrizePool,
+}
+
+/// Filtering, ordering and paging for block listings
+///
+/// The default lists every block, most recently created first.
+#[derive(Debug, Clone, Default)]
+pub struct BlockListQuery {
+    /// Only list blocks with this status
+    pub status: Option<BlockStatus>,
+    pub sort: BlockSort,
+    /// List smallest first instead of newest/largest first
+    pub ascending: bool,
+    /// Blocks to skip after filtering and ordering
+    pub offset: usize,
+    /// Maximum number of blocks to list
+    pub limit: Option<usize>,
+}
+
+impl BlockListQuery {
+    /// Filter, order and page `summaries`
+    pub fn apply(&self, mut summaries: Vec<BlockSummary>) -> Vec<BlockSummary> {
+        if let Some(status) = &self.status {
+            summaries.retain(|s| &s.status == status);
+        }
+
+        let block_num_key =
+            |s: &BlockSummary| (s.block_num.parse::<u64>().ok(), s.block_num.clone());
+        summaries.sort_by(|a, b| {
+            let order = match self.sort {
+                BlockSort::BlockNum => block_num_key(a).cmp(&block_num_key(b)),
+                BlockSort::CreatedAt => a.created_at.cmp(&b.created_at),
+                BlockSort::PrizePool => a.prize_pool.total_cmp(&b.prize_pool),
+            };
+            order.then_with(|| block_num_key(a).cmp(&block_num_key(b)))
+        });
+        if !self.ascending {
+            summaries.reverse();
+        }
+
+        summaries
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
 /// Statistics for a block
 #[derive(Debug, Clone)]
 pub struct BlockStats {
@@ -319,15 +922,55 @@ pub struct BlockStats {
     pub total_participants: usize,
     pub verified_participants: usize,
     pub total_prize_pool: f64,
+    pub validator_fee: f64,
     pub total_payout: f64,
     pub is_complete: bool,
     pub status: BlockStatus,
 }
 
+/// Everything recorded about one block, the canonical audit artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundReport {
+    pub summary: BlockSummary,
+    pub target_image_path: String,
+    /// Tweet that opened the block; participants reply to it
+    pub announcement_tweet_id: String,
+    /// Id of the scoring version the block was processed under
+    pub scoring_version: Option<String>,
+    pub validator_fee: f64,
+    /// Every participant, ranked ones first in rank order
+    pub participants: Vec<ParticipantReport>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// A participant's commitment, reveal, verification and payout in a `RoundReport`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipantReport {
+    pub username: String,
+    pub social_id: String,
+    pub wallet: String,
+    pub commitment: String,
+    pub commitment_tweet_id: Option<String>,
+    /// Revealed guess as the participant wrote it
+    pub guess: String,
+    pub salt: Option<String>,
+    pub reveal_tweet_id: Option<String>,
+    /// Whether the block records the commitment as verified
+    pub verified: bool,
+    /// Fresh check of the reveal against the commitment
+    pub verification: CommitmentVerification,
+    pub score: Option<f64>,
+    /// 1-based rank, `None` if the participant wasn't scored
+    pub rank: Option<usize>,
+    pub payout: Option<f64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::block_lock::{self, BlockLock};
     use crate::embedder::MockEmbedder;
+    use std::fs;
     use crate::scoring::ClipBatchStrategy;
     use crate::types::Guess;
     use tempfile::NamedTempFile;
@@ -343,6 +986,23 @@ mod tests {
         (processor, file_path)
     }
 
+    fn fee_config(validator_fee_percent: f64) -> PayoutConfig {
+        PayoutConfig {
+            validator_fee_percent,
+            ..PayoutConfig::default()
+        }
+    }
+
+    fn create_test_processor_for(
+        file_path: &str,
+    ) -> BlockProcessor<MockEmbedder, ClipBatchStrategy> {
+        BlockProcessor::new(
+            file_path.to_string(),
+            MockEmbedder::clip_like(),
+            ClipBatchStrategy::new(),
+        )
+    }
+
     fn create_test_participant(user_id: &str, guess_text: &str, commitment: &str) -> Participant {
         let guess = Guess::new(guess_text.to_string());
         Participant::new(
@@ -445,12 +1105,29 @@ mod tests {
             .add_participant("test_block", participant)
             .unwrap();
 
+        // A BLAKE3 commitment verifies with the algorithm recorded on its participant
+        let blake3 = crate::commitment::HashAlgo::Blake3;
+        let blake3_commitment = crate::commitment::CommitmentGenerator::new()
+            .with_hash_algo(blake3)
+            .generate(message, salt)
+            .unwrap();
+        let blake3_participant = Participant::new(
+            "user2".to_string(),
+            "user_user2".to_string(),
+            Guess::new(message.to_string()),
+            blake3_commitment,
+        )
+        .with_salt(salt.to_string())
+        .with_hash_algo(blake3);
+        processor
+            .add_participant("test_block", blake3_participant)
+            .unwrap();
+
         let results = processor.verify_commitments("test_block").unwrap();
-        assert_eq!(results.len(), 1);
-        assert!(results[0]); // Should be valid
+        assert_eq!(results, vec![true, true]);
 
         let block = processor.get_block("test_block").unwrap();
-        assert!(block.participants[0].verified);
+        assert!(block.participants.iter().all(|p| p.verified));
     }
 
     #[test]
@@ -480,6 +1157,58 @@ mod tests {
         assert!(!stats.is_complete);
     }
 
+    #[test]
+    fn test_summarize_all() {
+        let (mut processor, _) = create_test_processor();
+        let commitment_deadline = Utc::now() + chrono::Duration::days(1);
+        let reveal_deadline = Utc::now() + chrono::Duration::days(2);
+
+        for block_num in ["10", "2"] {
+            processor
+                .create_block(
+                    block_num.to_string(),
+                    "test.jpg".to_string(),
+                    "test_social_id".to_string(),
+                    100.0,
+                    Some(commitment_deadline),
+                    Some(reveal_deadline),
+                )
+                .unwrap();
+        }
+
+        processor
+            .add_participant("10", create_test_participant("user1", "a cat", "c1"))
+            .unwrap();
+        processor
+            .add_participant("10", create_test_participant("user2", "a dog", "c2"))
+            .unwrap();
+        let unverified = Participant::new(
+            "user3".to_string(),
+            "user_user3".to_string(),
+            Guess::new("a bird".to_string()),
+            "c3".to_string(),
+        );
+        processor.add_participant("10", unverified).unwrap();
+
+        let summaries = processor.summarize_all().unwrap();
+        let block_nums: Vec<&str> = summaries.iter().map(|s| s.block_num.as_str()).collect();
+        assert_eq!(block_nums, vec!["2", "10"]);
+
+        let summary = &summaries[1];
+        assert_eq!(summary.participant_count, 3);
+        assert_eq!(summary.verified_count, 2);
+        assert_eq!(summary.status, BlockStatus::Open);
+        assert_eq!(summary.prize_pool, 100.0);
+        assert_eq!(summary.total_payout, 0.0);
+        assert_eq!(summary.commitment_deadline, commitment_deadline);
+        assert_eq!(summary.reveal_deadline, reveal_deadline);
+        assert_eq!(summaries[0].participant_count, 0);
+
+        let json = serde_json::to_value(summary).unwrap();
+        assert_eq!(json["verified_count"], 2);
+        assert!(json.get("participants").is_none());
+    }
+
     #[test]
     fn test_nonexistent_block() {
         let (mut processor, _) = create_test_processor();
@@ -492,4 +1221,940 @@ mod tests {
             ))
         ));
     }
+
+    #[test]
+    fn test_scoring_version_stamping_and_mismatch() {
+        let (processor, file_path) = create_test_processor();
+        let mut processor =
+            processor.with_scoring_version(ScoringVersion::new("v1", "ClipBatch"));
+        let target_image = NamedTempFile::new().unwrap();
+
+        processor
+            .create_block(
+                "test_block".to_string(),
+                target_image.path().to_string_lossy().to_string(),
+                "test_social_id".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+        for (user, guess) in [("user1", "a red car"), ("user2", "a blue boat")] {
+            processor
+                .add_participant("test_block", create_test_participant(user, guess, "c"))
+                .unwrap();
+        }
+
+        let results = processor.process_block_payouts("test_block").unwrap();
+        assert!(results
+            .iter()
+            .all(|r| r.scoring_version.as_deref() == Some("v1")));
+        assert_eq!(
+            processor.get_block("test_block").unwrap().scoring_version.as_deref(),
+            Some("v1")
+        );
+        assert_eq!(processor.scoring_version_mismatch("test_block").unwrap(), None);
+
+        // A processor on a newer scoring version detects the recorded one
+        let mut updated = BlockProcessor::new(
+            file_path,
+            MockEmbedder::clip_like(),
+            ClipBatchStrategy::new(),
+        )
+        .with_scoring_version(ScoringVersion::new("v2", "ClipBatch"));
+        assert_eq!(
+            updated.scoring_version_mismatch("test_block").unwrap(),
+            Some("v1".to_string())
+        );
+
+        // ...and refuses to re-process the block under it
+        let err = updated.process_block_payouts("test_block").unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::CliptionsError::Block(BlockError::ScoringVersionMismatch {
+                ref recorded,
+                ref current,
+                ..
+            }) if recorded == "v1" && current == "v2"
+        ));
+    }
+
+    #[test]
+    fn test_validator_fee_taken_before_payouts() {
+        let (processor, _) = create_test_processor();
+        let mut processor = processor.with_payout_config(fee_config(10.0)).unwrap();
+        let target_image = NamedTempFile::new().unwrap();
+
+        processor
+            .create_block(
+                "test_block".to_string(),
+                target_image.path().to_string_lossy().to_string(),
+                "test_social_id".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+        for (user, guess) in [("user1", "a red car"), ("user2", "a blue boat")] {
+            processor
+                .add_participant("test_block", create_test_participant(user, guess, "c"))
+                .unwrap();
+        }
+
+        let results = processor.process_block_payouts("test_block").unwrap();
+        let distributed: f64 = results.iter().filter_map(|r| r.payout).sum();
+        let fee = processor.validator_fee("test_block").unwrap();
+
+        assert!((fee - 10.0).abs() < 1e-10);
+        assert!((fee + distributed - 100.0).abs() < 1e-10);
+        assert_eq!(processor.get_block_stats("test_block").unwrap().validator_fee, fee);
+
+        let (processor, _) = create_test_processor();
+        assert!(processor.with_payout_config(fee_config(100.0)).is_err());
+    }
+
+    #[test]
+    fn test_payout_config_sets_currency_and_minimum_pool() {
+        let (processor, _) = create_test_processor();
+        let config = PayoutConfig {
+            currency_label: "USDC".to_string(),
+            min_prize_pool: 50.0,
+            ..PayoutConfig::default()
+        };
+        let mut processor = processor.with_payout_config(config).unwrap();
+        let target_image = NamedTempFile::new().unwrap();
+        for (block_num, prize_pool) in [("small", 10.0), ("large", 100.0)] {
+            processor
+                .create_block(
+                    block_num.to_string(),
+                    target_image.path().to_string_lossy().to_string(),
+                    "test_social_id".to_string(),
+                    prize_pool,
+                    None,
+                    None,
+                )
+                .unwrap();
+            for (user, guess) in [("user1", "a red car"), ("user2", "a blue boat")] {
+                processor
+                    .add_participant(block_num, create_test_participant(user, guess, "c"))
+                    .unwrap();
+            }
+        }
+
+        assert!(processor.process_block_payouts("small").is_err());
+        let results = processor.process_block_payouts("large").unwrap();
+        assert!(results.iter().all(|r| r.participant.payout.currency == "USDC"));
+    }
+
+    #[test]
+    fn test_payout_curve_applies_to_processed_blocks() {
+        use crate::payout::PayoutCurve;
+
+        let (processor, _) = create_test_processor();
+        let mut processor = processor
+            .with_payout_config(PayoutConfig {
+                curve: PayoutCurve::WinnerTakeAll,
+                ..PayoutConfig::default()
+            })
+            .unwrap();
+        let target_image = NamedTempFile::new().unwrap();
+        processor
+            .create_block(
+                "test_block".to_string(),
+                target_image.path().to_string_lossy().to_string(),
+                "test_social_id".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+        for (user, guess) in [
+            ("user1", "a red car"),
+            ("user2", "a blue boat"),
+            ("user3", "a green tree"),
+        ] {
+            processor
+                .add_participant("test_block", create_test_participant(user, guess, "c"))
+                .unwrap();
+        }
+
+        let results = processor.process_block_payouts("test_block").unwrap();
+        let payouts: Vec<f64> = results.iter().map(|r| r.payout.unwrap()).collect();
+        assert_eq!(payouts, vec![100.0, 0.0, 0.0]);
+        assert_eq!(results[0].rank, Some(1));
+    }
+
+    #[test]
+    fn test_rounded_payouts_sum_to_distributable_pool() {
+        use crate::payout::TAO_DECIMALS;
+
+        let (processor, _) = create_test_processor();
+        let mut processor = processor
+            .with_payout_config(PayoutConfig {
+                validator_fee_percent: 3.3,
+                decimals: Some(TAO_DECIMALS),
+                ..PayoutConfig::default()
+            })
+            .unwrap();
+        let target_image = NamedTempFile::new().unwrap();
+        processor
+            .create_block(
+                "test_block".to_string(),
+                target_image.path().to_string_lossy().to_string(),
+                "test_social_id".to_string(),
+                1.0,
+                None,
+                None,
+            )
+            .unwrap();
+        for (user, guess) in [
+            ("user1", "a red car"),
+            ("user2", "a blue boat"),
+            ("user3", "a green tree"),
+            ("user4", "a yellow kite"),
+            ("user5", "a grey cat"),
+            ("user6", "a white owl"),
+            ("user7", "a black dog"),
+        ] {
+            processor
+                .add_participant("test_block", create_test_participant(user, guess, "c"))
+                .unwrap();
+        }
+
+        let results = processor.process_block_payouts("test_block").unwrap();
+        let units = |amount: f64| amount * 1e9;
+        let payout_units: Vec<f64> = results.iter().map(|r| units(r.payout.unwrap())).collect();
+
+        // Every payout is a whole number of units, and together they are the whole pool
+        for payout in &payout_units {
+            assert!((payout - payout.round()).abs() < 1e-6, "{}", payout);
+        }
+        let total: u64 = payout_units.iter().map(|p| p.round() as u64).sum();
+        let distributable = 1.0 - processor.validator_fee("test_block").unwrap();
+        assert_eq!(total, units(distributable).round() as u64);
+        assert_eq!(total, 967_000_000);
+    }
+
+    #[test]
+    fn test_generate_report() {
+        use crate::commitment::CommitmentGenerator;
+
+        let (processor, file_path) = create_test_processor();
+        let mut processor = processor.with_payout_config(fee_config(10.0)).unwrap();
+        let target_image = NamedTempFile::new().unwrap();
+        processor
+            .create_block(
+                "test_block".to_string(),
+                target_image.path().to_string_lossy().to_string(),
+                "1000".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let generator = CommitmentGenerator::new();
+        for (user, guess) in [("1", "a red car"), ("2", "a blue boat"), ("3", "a green tree")] {
+            let commitment = generator.generate(guess, "test_salt").unwrap();
+            let participant = create_test_participant(user, guess, &commitment)
+                .with_commitment_url(format!("https://x.com/user_{}/status/10{}", user, user))
+                .with_guess_url(format!("https://x.com/user_{}/status/20{}", user, user));
+            processor.add_participant("test_block", participant).unwrap();
+        }
+        // A participant whose reveal doesn't match their commitment
+        let mut cheater = create_test_participant("4", "a yellow bus", "not_the_commitment");
+        cheater.verified = false;
+        processor.add_participant("test_block", cheater).unwrap();
+
+        // Unscored blocks report commitments and reveals without ranks
+        let report = processor.generate_report("test_block").unwrap();
+        assert_eq!(report.participants.len(), 4);
+        assert!(report.participants.iter().all(|p| p.rank.is_none()));
+
+        let results = processor.process_block_payouts("test_block").unwrap();
+        processor.get_block_mut("test_block").unwrap().set_results(results);
+        processor.save_blocks(&processor.blocks_cache).unwrap();
+
+        let mut reloaded = create_test_processor_for(&file_path)
+            .with_payout_config(fee_config(10.0))
+            .unwrap();
+        let report = reloaded.generate_report("test_block").unwrap();
+
+        assert_eq!(report.summary.block_num, "test_block");
+        assert_eq!(report.summary.participant_count, 4);
+        assert_eq!(report.summary.verified_count, 3);
+        assert_eq!(report.announcement_tweet_id, "1000");
+        assert!((report.validator_fee - 10.0).abs() < 1e-10);
+
+        // Ranks are a permutation of 1..=scored, and payouts follow them
+        let mut ranks: Vec<usize> = report.participants.iter().filter_map(|p| p.rank).collect();
+        ranks.sort();
+        assert_eq!(ranks, vec![1, 2, 3]);
+        let ranked: Vec<&ParticipantReport> =
+            report.participants.iter().filter(|p| p.rank.is_some()).collect();
+        for pair in ranked.windows(2) {
+            assert!(pair[0].rank < pair[1].rank);
+            assert!(pair[0].score >= pair[1].score);
+            assert!(pair[0].payout >= pair[1].payout);
+        }
+        let paid: f64 = report.participants.iter().filter_map(|p| p.payout).sum();
+        assert!((paid + report.validator_fee - report.summary.prize_pool).abs() < 1e-9);
+
+        // Verification, tweet ids and the unranked cheater are all reported
+        for participant in &ranked {
+            assert!(participant.verified && participant.verification.valid);
+            let user = participant.social_id.as_str();
+            assert_eq!(participant.commitment_tweet_id, Some(format!("10{}", user)));
+            assert_eq!(participant.reveal_tweet_id, Some(format!("20{}", user)));
+        }
+        let cheater = report.participants.last().unwrap();
+        assert_eq!(cheater.username, "user_4");
+        assert!(!cheater.verified && !cheater.verification.valid);
+        assert_eq!((cheater.rank, cheater.payout), (None, None));
+
+        // The report round-trips through JSON
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: RoundReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.participants.len(), 4);
+    }
+
+    #[test]
+    fn test_unrevealed_participants() {
+        let (mut processor, _) = create_test_processor();
+        processor
+            .create_block(
+                "test_block".to_string(),
+                "target.jpg".to_string(),
+                "1000".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+
+        processor
+            .add_participant("test_block", create_test_participant("1", "a red car", "c1"))
+            .unwrap();
+        let mut silent = create_test_participant("2", "", "c2");
+        silent.salt = None;
+        silent.verified = false;
+        processor.add_participant("test_block", silent).unwrap();
+        processor
+            .add_participant("test_block", create_test_participant("3", "a green tree", "c3"))
+            .unwrap();
+
+        let unrevealed = processor.unrevealed_participants("test_block").unwrap();
+        assert_eq!(unrevealed.len(), 1);
+        assert_eq!(unrevealed[0].username, "user_2");
+        assert_eq!(unrevealed[0].commitment, "c2");
+
+        assert!(processor.unrevealed_participants("missing_block").is_err());
+    }
+
+    /// Stands in for a CLIP model that isn't available in tests
+    struct UnavailableClip;
+
+    impl EmbedderTrait for UnavailableClip {
+        fn get_image_embedding(&self, _image_path: &str) -> Result<ndarray::Array1<f64>> {
+            Err(crate::error::EmbeddingError::ModelLoadFailed.into())
+        }
+        fn get_text_embedding(&self, _text: &str) -> Result<ndarray::Array1<f64>> {
+            Err(crate::error::EmbeddingError::ModelLoadFailed.into())
+        }
+        fn calculate_batch_similarities(&self, _image_path: &str, _texts: &[String]) -> Result<Vec<f64>> {
+            Err(crate::error::EmbeddingError::ModelLoadFailed.into())
+        }
+        fn embedding_dim(&self) -> usize {
+            512
+        }
+        fn name(&self) -> &str {
+            "ClipEmbedder"
+        }
+    }
+
+    #[test]
+    fn test_block_embedder_spec_overrides_default() {
+        use crate::embedder::EmbedderSpec;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let file_path = temp_file.path().to_string_lossy().to_string();
+        let target_image = NamedTempFile::new().unwrap();
+        let target_path = target_image.path().to_string_lossy().to_string();
+
+        let mut blocks = HashMap::new();
+        for (block_num, spec) in [("mock_block", Some(EmbedderSpec::Mock { dim: 128 })), ("default_block", None)] {
+            let mut block = BlockData::new(block_num.to_string(), target_path.clone(), "social".to_string(), 100.0);
+            block.embedder_spec = spec;
+            block.add_participant(create_test_participant("user1", "a red car", "c"));
+            block.add_participant(create_test_participant("user2", "a blue boat", "c"));
+            blocks.insert(block_num.to_string(), block);
+        }
+        fs::write(&file_path, serde_json::to_string(&blocks).unwrap()).unwrap();
+
+        // CLIP is the global default, but the tagged block is scored with the mock
+        let mut processor = BlockProcessor::new(file_path, UnavailableClip, ClipBatchStrategy::new());
+        let results = processor.process_block_payouts("mock_block").unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|r| r.embedder_name.as_deref() == Some("MockEmbedder")));
+
+        // Untagged blocks fall back to the processor's embedder
+        assert!(processor.process_block_payouts("default_block").is_err());
+
+        // The spec and the provenance are kept when the processed block is saved
+        let mut reloaded = create_test_processor_for(temp_file.path().to_str().unwrap());
+        let block = reloaded.get_block("mock_block").unwrap();
+        assert_eq!(block.embedder_spec, Some(EmbedderSpec::Mock { dim: 128 }));
+        assert_eq!(block.embedder_name.as_deref(), Some("MockEmbedder"));
+        assert_eq!(block.strategy_name.as_deref(), Some("ClipBatch"));
+    }
+
+    #[test]
+    fn test_block_reference_caption_feeds_blended_strategy() {
+        use crate::scoring::BlendedStrategy;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let file_path = temp_file.path().to_string_lossy().to_string();
+        let target_image = NamedTempFile::new().unwrap();
+        let target_path = target_image.path().to_string_lossy().to_string();
+
+        let mut blocks = HashMap::new();
+        for (block_num, reference) in [("captioned", Some("a blue boat")), ("uncaptioned", None)] {
+            let mut block = BlockData::new(block_num.to_string(), target_path.clone(), "social".to_string(), 100.0);
+            block.reference_caption = reference.map(str::to_string);
+            block.add_participant(create_test_participant("user1", "a red car", "c"));
+            block.add_participant(create_test_participant("user2", "a blue boat", "c"));
+            blocks.insert(block_num.to_string(), block);
+        }
+        fs::write(&file_path, serde_json::to_string(&blocks).unwrap()).unwrap();
+
+        // Purely lexical, with no reference configured on the strategy
+        let strategy = BlendedStrategy::new(ClipBatchStrategy::new(), 0.0).unwrap();
+        let mut processor = BlockProcessor::new(file_path, MockEmbedder::clip_like(), strategy);
+        let results = processor.process_block_payouts("captioned").unwrap();
+        assert_eq!(results[0].participant.guess.text, "a blue boat");
+        assert!((results[0].effective_score() - 100.0).abs() < 1e-9);
+
+        // A block without a caption has nothing to compare against
+        assert!(processor.process_block_payouts("uncaptioned").is_err());
+    }
+
+    #[test]
+    fn test_malformed_blocks_file() {
+        let (mut processor, file_path) = create_test_processor();
+        processor
+            .create_block(
+                "good_block".to_string(),
+                "test.jpg".to_string(),
+                "test_social_id".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+        let valid_block = serde_json::to_value(processor.get_block("good_block").unwrap()).unwrap();
+
+        // Broken JSON syntax reports the path and location
+        fs::write(&file_path, "{\n  \"good_block\": {\n    \"block_version\": 1,,\n").unwrap();
+        let mut processor = create_test_processor_for(&file_path);
+        let error = processor.load_blocks().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::CliptionsError::Block(BlockError::MalformedDataFile { line: 3, .. })
+        ));
+        let message = error.to_string();
+        assert!(message.contains(&file_path));
+        assert!(message.contains("line 3"));
+
+        // Valid JSON with one bad block names that block
+        let mut bad_block = valid_block.clone();
+        bad_block["prize_pool"] = serde_json::json!("lots");
+        let blocks = serde_json::json!({ "good_block": valid_block, "bad_block": bad_block });
+        fs::write(&file_path, serde_json::to_string_pretty(&blocks).unwrap()).unwrap();
+        let mut processor = create_test_processor_for(&file_path);
+        let error = processor.load_blocks().unwrap_err();
+        match &error {
+            crate::error::CliptionsError::Block(BlockError::MalformedBlock {
+                path,
+                block_num,
+                line,
+                ..
+            }) => {
+                assert_eq!(path, &file_path);
+                assert_eq!(block_num, "bad_block");
+                assert!(*line > 0);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert!(error.to_string().contains("bad_block"));
+    }
+
+    #[test]
+    fn test_list_blocks_sorts_and_pages() {
+        let (mut processor, _) = create_test_processor();
+        let start = Utc::now() - chrono::Duration::days(10);
+        // Block numbers, creation order and prize pools all disagree
+        let fixture = [
+            ("1", 2, 50.0),
+            ("2", 1, 300.0),
+            ("10", 3, 100.0),
+            ("3", 0, 200.0),
+        ];
+        for (block_num, days, prize_pool) in fixture {
+            processor
+                .create_block(
+                    block_num.to_string(),
+                    "test.jpg".to_string(),
+                    "social".to_string(),
+                    prize_pool,
+                    None,
+                    None,
+                )
+                .unwrap();
+            processor.get_block_mut(block_num).unwrap().created_at =
+                start + chrono::Duration::days(days);
+        }
+        processor.get_block_mut("2").unwrap().status = BlockStatus::Complete;
+
+        let mut list = |query: BlockListQuery| -> Vec<String> {
+            processor
+                .list_blocks(&query)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.block_num)
+                .collect()
+        };
+
+        // Most recently created first by default
+        assert_eq!(list(BlockListQuery::default()), vec!["10", "1", "2", "3"]);
+        assert_eq!(
+            list(BlockListQuery {
+                sort: BlockSort::BlockNum,
+                ascending: true,
+                ..Default::default()
+            }),
+            vec!["1", "2", "3", "10"]
+        );
+        assert_eq!(
+            list(BlockListQuery {
+                sort: BlockSort::PrizePool,
+                ..Default::default()
+            }),
+            vec!["2", "3", "10", "1"]
+        );
+
+        // Pages are cut from the ordered list
+        assert_eq!(
+            list(BlockListQuery {
+                offset: 1,
+                limit: Some(2),
+                ..Default::default()
+            }),
+            vec!["1", "2"]
+        );
+        assert!(list(BlockListQuery {
+            offset: 10,
+            ..Default::default()
+        })
+        .is_empty());
+
+        // Paging applies after filtering
+        assert_eq!(
+            list(BlockListQuery {
+                status: Some(BlockStatus::Open),
+                limit: Some(2),
+                ..Default::default()
+            }),
+            vec!["10", "1"]
+        );
+    }
+
+    fn block_params(block_num: &str) -> BlockParams {
+        let commitment_deadline = Utc::now() + chrono::Duration::days(1);
+        BlockParams {
+            block_num: block_num.to_string(),
+            target_image_path: "target.jpg".to_string(),
+            social_id: "1000".to_string(),
+            prize_pool: 100.0,
+            commitment_deadline: Some(commitment_deadline),
+            reveal_deadline: Some(commitment_deadline + chrono::Duration::days(1)),
+        }
+    }
+
+    #[test]
+    fn test_ensure_block_creates_new() {
+        let (mut processor, file_path) = create_test_processor();
+
+        let (block, created) = processor.ensure_block(block_params("1")).unwrap();
+        assert!(created);
+        assert_eq!(block.block_num, "1");
+        assert_eq!(block.social_id, "1000");
+
+        // Persisted, not just cached
+        let mut reloaded = create_test_processor_for(&file_path);
+        assert_eq!(reloaded.get_block("1").unwrap().prize_pool, 100.0);
+    }
+
+    #[test]
+    fn test_ensure_block_returns_existing() {
+        let (mut processor, file_path) = create_test_processor();
+        let params = block_params("1");
+        processor.ensure_block(params.clone()).unwrap();
+        processor
+            .add_participant("1", create_test_participant("user1", "a red car", "c1"))
+            .unwrap();
+
+        // A retry, even from a fresh processor, leaves the block alone
+        let mut retried = create_test_processor_for(&file_path);
+        let (block, created) = retried.ensure_block(params.clone()).unwrap();
+        assert!(!created);
+        assert_eq!(block.participants.len(), 1);
+
+        // Omitted deadlines aren't compared
+        let loose = BlockParams {
+            commitment_deadline: None,
+            reveal_deadline: None,
+            ..params
+        };
+        assert!(!retried.ensure_block(loose).unwrap().1);
+        assert_eq!(retried.get_block_nums().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_ensure_block_rejects_mismatched_params() {
+        let (mut processor, _) = create_test_processor();
+        let params = block_params("1");
+        processor.ensure_block(params.clone()).unwrap();
+
+        let error = processor
+            .ensure_block(BlockParams {
+                prize_pool: 250.0,
+                ..params.clone()
+            })
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            CliptionsError::Block(BlockError::ParamsMismatch { field, .. }) if field == "prize pool"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Block processing error: Block 1 already exists with prize pool 100, not 250"
+        );
+
+        let later = params.reveal_deadline.unwrap() + chrono::Duration::hours(1);
+        assert!(processor
+            .ensure_block(BlockParams {
+                reveal_deadline: Some(later),
+                ..params
+            })
+            .is_err());
+        assert_eq!(processor.get_block("1").unwrap().prize_pool, 100.0);
+    }
+
+    #[test]
+    fn test_locked_block_is_not_mutated() {
+        let (mut processor, file_path) = create_test_processor();
+        processor.ensure_block(block_params("1")).unwrap();
+
+        // Another process is working on block 1
+        let held = BlockLock::acquire(block_lock::lock_path(&file_path, "1"), "1").unwrap();
+        let error = processor
+            .add_participant("1", create_test_participant("a", "a red fox", "c"))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CliptionsError::Block(BlockError::Locked { ref block_num, .. }) if block_num == "1"
+        ));
+        assert!(processor.verify_commitments("1").is_err());
+        assert!(processor.process_block_payouts("1").is_err());
+        assert!(processor.get_block("1").unwrap().participants.is_empty());
+
+        // Once released the block can be changed, and the lock is released again after saving
+        drop(held);
+        processor
+            .add_participant("1", create_test_participant("a", "a red fox", "c"))
+            .unwrap();
+        processor
+            .add_participant("1", create_test_participant("b", "a blue bird", "d"))
+            .unwrap();
+        assert!(BlockLock::acquire(block_lock::lock_path(&file_path, "1"), "1").is_ok());
+        let reloaded = create_test_processor_for(&file_path).get_block("1").unwrap().clone();
+        assert_eq!(reloaded.participants.len(), 2);
+    }
+
+    #[test]
+    fn test_ensure_block_waits_for_the_block_lock() {
+        let (mut processor, file_path) = create_test_processor();
+
+        // Another process is creating block 1
+        let held = BlockLock::acquire(block_lock::lock_path(&file_path, "1"), "1").unwrap();
+        let error = processor.ensure_block(block_params("1")).unwrap_err();
+        assert!(matches!(
+            error,
+            CliptionsError::Block(BlockError::Locked { ref block_num, .. }) if block_num == "1"
+        ));
+        assert!(processor
+            .create_block("1".to_string(), "target.jpg".to_string(), "social".to_string(), 100.0, None, None)
+            .is_err());
+        assert!(create_test_processor_for(&file_path).get_block("1").is_err());
+
+        drop(held);
+        let (_, created) = processor.ensure_block(block_params("1")).unwrap();
+        assert!(created);
+        assert!(BlockLock::acquire(block_lock::lock_path(&file_path, "1"), "1").is_ok());
+    }
+
+    #[test]
+    fn test_locked_changes_start_from_the_stored_block() {
+        let (mut processor, file_path) = create_test_processor();
+        processor.ensure_block(block_params("1")).unwrap();
+
+        // Another process adds a participant after this one cached the block
+        create_test_processor_for(&file_path)
+            .add_participant("1", create_test_participant("a", "a red fox", "c"))
+            .unwrap();
+        assert!(processor.get_block("1").unwrap().participants.is_empty());
+
+        processor
+            .add_participant("1", create_test_participant("b", "a blue bird", "d"))
+            .unwrap();
+        let reloaded = create_test_processor_for(&file_path).get_block("1").unwrap().clone();
+        assert_eq!(reloaded.participants.len(), 2);
+        assert!(processor.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_reclaimed_lock_is_reported_as_a_warning() {
+        let (mut processor, file_path) = create_test_processor();
+        processor.ensure_block(block_params("1")).unwrap();
+
+        // A process died holding the lock on block 1
+        let path = block_lock::lock_path(&file_path, "1");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, r#"{"pid":999999,"acquired_at":"2024-01-01T00:00:00Z"}"#).unwrap();
+
+        processor
+            .add_participant("1", create_test_participant("a", "a red fox", "c"))
+            .unwrap();
+        let warnings = processor.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("block 1 left by process 999999"));
+        assert!(processor.take_warnings().is_empty());
+    }
+
+    /// Mock embedder that truncates guesses longer than five words
+    struct ShortTokenLimit(MockEmbedder);
+
+    impl EmbedderTrait for ShortTokenLimit {
+        fn get_image_embedding(&self, image_path: &str) -> Result<ndarray::Array1<f64>> {
+            self.0.get_image_embedding(image_path)
+        }
+        fn get_text_embedding(&self, text: &str) -> Result<ndarray::Array1<f64>> {
+            self.0.get_text_embedding(text)
+        }
+        fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+            self.0.calculate_batch_similarities(image_path, texts)
+        }
+        fn embedding_dim(&self) -> usize {
+            self.0.embedding_dim()
+        }
+        fn truncation(&self, text: &str) -> Result<Option<crate::embedder::Truncation>> {
+            let tokens = text.split_whitespace().count();
+            Ok((tokens > 5).then_some(crate::embedder::Truncation { tokens, max: 5 }))
+        }
+    }
+
+    #[test]
+    fn test_truncated_guesses_are_reported_as_warnings() {
+        let target_image = NamedTempFile::new().unwrap();
+        let temp_file = NamedTempFile::new().unwrap();
+        let file_path = temp_file.path().to_string_lossy().to_string();
+        let mut processor = BlockProcessor::new(
+            file_path,
+            ShortTokenLimit(MockEmbedder::clip_like()),
+            ClipBatchStrategy::new(),
+        );
+        processor
+            .ensure_block(BlockParams {
+                target_image_path: target_image.path().to_string_lossy().to_string(),
+                ..block_params("1")
+            })
+            .unwrap();
+        for (user, guess) in [("alice", "a red fox"), ("bob", "a red fox in the deep snow")] {
+            processor
+                .add_participant("1", create_test_participant(user, guess, "c"))
+                .unwrap();
+        }
+
+        let results = processor.process_block_payouts("1").unwrap();
+        assert_eq!(results.len(), 2);
+        let warnings = processor.take_warnings();
+        assert_eq!(
+            warnings,
+            vec!["Block 1: guess by user_bob was truncated from 7 to 5 tokens; only its beginning is scored"]
+        );
+    }
+
+    #[test]
+    fn test_compressed_blocks_file_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let plain_path = temp_dir.path().join("blocks.json");
+        let compressed_path = temp_dir.path().join("blocks.json.gz");
+
+        let mut blocks = HashMap::new();
+        for block_num in ["1", "2"] {
+            let mut block = BlockData::new(block_num.to_string(), "target.jpg".to_string(), "social".to_string(), 100.0);
+            block.add_participant(create_test_participant("user1", "a red car", "c1"));
+            block.add_participant(create_test_participant("user2", "a blue boat", "c2"));
+            blocks.insert(block_num.to_string(), block);
+        }
+        for path in [&plain_path, &compressed_path] {
+            create_test_processor_for(path.to_str().unwrap()).save_blocks(&blocks).unwrap();
+        }
+
+        // Only the .json.gz file is gzip on disk
+        assert_eq!(&fs::read(&compressed_path).unwrap()[..2], &[0x1f, 0x8b]);
+        assert_eq!(fs::read(&plain_path).unwrap()[0], b'{');
+
+        let mut loaded = Vec::new();
+        for path in [&plain_path, &compressed_path] {
+            let mut processor = create_test_processor_for(path.to_str().unwrap());
+            processor.load_blocks().unwrap();
+            loaded.push(serde_json::to_value(&processor.blocks_cache).unwrap());
+        }
+        assert_eq!(loaded[0], loaded[1]);
+        assert_eq!(loaded[1], serde_json::to_value(&blocks).unwrap());
+    }
+
+    /// Records every progress event it receives
+    #[derive(Clone, Default)]
+    struct RecordingReporter {
+        events: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl ProgressReporter for RecordingReporter {
+        fn block_started(&self, block_num: &str, index: usize, total: usize) {
+            self.events.lock().unwrap().push(format!("start {} {}/{}", block_num, index, total));
+        }
+
+        fn block_finished(&self, block_num: &str, completed: usize, total: usize) {
+            self.events.lock().unwrap().push(format!("done {} {}/{}", block_num, completed, total));
+        }
+
+        fn finish(&self) {
+            self.events.lock().unwrap().push("finish".to_string());
+        }
+    }
+
+    #[test]
+    fn test_progress_reported_per_block() {
+        let reporter = RecordingReporter::default();
+        let (processor, _) = create_test_processor();
+        let mut processor = processor.with_progress_reporter(reporter.clone());
+        let target_image = NamedTempFile::new().unwrap();
+
+        for block_num in ["1", "2", "3"] {
+            processor
+                .create_block(
+                    block_num.to_string(),
+                    target_image.path().to_string_lossy().to_string(),
+                    format!("social_{}", block_num),
+                    100.0,
+                    None,
+                    None,
+                )
+                .unwrap();
+            processor
+                .add_participant(block_num, create_test_participant("user1", "a red car", "c"))
+                .unwrap();
+        }
+        // Finished blocks are skipped and not counted
+        processor.get_block_mut("3").unwrap().status = BlockStatus::Complete;
+
+        let results = processor.process_all_blocks().unwrap();
+        assert_eq!(results.len(), 2);
+
+        let events = reporter.events.lock().unwrap().clone();
+        let mut finished: Vec<&String> = events.iter().filter(|e| e.starts_with("done")).collect();
+        finished.sort();
+        assert_eq!(finished.len(), 2);
+        assert!(finished[0].starts_with("done 1 ") && finished[1].starts_with("done 2 "));
+        // Ticks count up to the number of processed blocks, then the batch finishes
+        let ticks: Vec<&str> = events
+            .iter()
+            .filter(|e| e.starts_with("done"))
+            .map(|e| e.rsplit(' ').next().unwrap())
+            .collect();
+        assert_eq!(ticks, vec!["1/2", "2/2"]);
+        assert_eq!(events.len(), 5);
+        assert_eq!(events.last().unwrap(), "finish");
+    }
+
+    /// Blocks kept in memory, counting writes
+    #[derive(Default)]
+    struct MemoryBlockStore {
+        blocks: HashMap<String, BlockData>,
+        puts: usize,
+    }
+
+    impl BlockStore for MemoryBlockStore {
+        fn load_all(&self) -> Result<HashMap<String, BlockData>> {
+            Ok(self.blocks.clone())
+        }
+
+        fn get(&self, block_num: &str) -> Result<Option<BlockData>> {
+            Ok(self.blocks.get(block_num).cloned())
+        }
+
+        fn put(&mut self, block: &BlockData) -> Result<()> {
+            self.blocks.insert(block.block_num.clone(), block.clone());
+            self.puts += 1;
+            Ok(())
+        }
+
+        fn list_nums(&self) -> Result<Vec<String>> {
+            Ok(self.blocks.keys().cloned().collect())
+        }
+    }
+
+    #[test]
+    fn test_processor_with_memory_store() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let target_image = temp_dir.path().join("target.jpg");
+        fs::write(&target_image, b"image").unwrap();
+
+        let mut processor = BlockProcessor::with_store(
+            MemoryBlockStore::default(),
+            MockEmbedder::clip_like(),
+            ClipBatchStrategy::new(),
+        );
+        processor
+            .create_block(
+                "1".to_string(),
+                target_image.to_string_lossy().to_string(),
+                "social_1".to_string(),
+                100.0,
+                None,
+                None,
+            )
+            .unwrap();
+        processor
+            .add_participant("1", create_test_participant("a", "a red fox", "c1"))
+            .unwrap();
+        processor
+            .add_participant("1", create_test_participant("b", "a blue bird", "c2"))
+            .unwrap();
+
+        let results = processor.process_all_blocks().unwrap();
+        assert_eq!(results["1"].len(), 2);
+        assert_eq!(processor.get_block_nums().unwrap(), vec!["1"]);
+
+        // Every change went to the store, not a file
+        let stored = processor.store().get("1").unwrap().unwrap();
+        assert_eq!(stored.participants.len(), 2);
+        assert_eq!(stored.status, BlockStatus::Complete);
+        assert_eq!(processor.store().puts, 4);
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
 }
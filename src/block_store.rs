@@ -0,0 +1,332 @@
+//! Persistence backends for block data
+//!
+//! `BlockProcessor` reads and writes blocks through a `BlockStore`, so the
+//! processing logic doesn't depend on where blocks live. `JsonBlockStore`
+//! keeps every block in one JSON file (gzip-compressed for `.json.gz` paths),
+//! as the processor always has; `MemoryBlockStore` holds blocks read from
+//! stdin. A database backend only needs to implement the trait.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use crate::block_lock::{self, BlockLock, FileLock, LockHolder};
+use crate::data_file;
+use crate::error::{BlockError, Result};
+use crate::types::BlockData;
+
+/// Path that stands for stdin or stdout instead of a file
+pub const STDIO_PATH: &str = "-";
+
+/// Storage for blocks, keyed by block number
+pub trait BlockStore {
+    /// Every stored block by block number
+    fn load_all(&self) -> Result<HashMap<String, BlockData>>;
+
+    /// The block stored as `block_num`, if any
+    fn get(&self, block_num: &str) -> Result<Option<BlockData>>;
+
+    /// Save `block` under its block number, replacing any stored version
+    fn put(&mut self, block: &BlockData) -> Result<()>;
+
+    /// Numbers of every stored block, in no particular order
+    fn list_nums(&self) -> Result<Vec<String>>;
+
+    /// Lock `block_num` against other processes until the guard drops
+    ///
+    /// Stores without a cross-process lock of their own return an unlocked
+    /// guard.
+    fn lock_block(&self, _block_num: &str) -> Result<BlockGuard> {
+        Ok(BlockGuard::unlocked())
+    }
+}
+
+/// Lock on a block taken through `BlockStore::lock_block`, released when dropped
+#[derive(Debug, Default)]
+pub struct BlockGuard {
+    lock: Option<BlockLock>,
+}
+
+impl BlockGuard {
+    /// A guard that holds no lock
+    pub fn unlocked() -> Self {
+        Self::default()
+    }
+
+    /// Holder of an abandoned lock this guard took over, if any
+    pub fn reclaimed_from(&self) -> Option<&LockHolder> {
+        self.lock.as_ref().and_then(BlockLock::reclaimed_from)
+    }
+}
+
+impl From<BlockLock> for BlockGuard {
+    fn from(lock: BlockLock) -> Self {
+        Self { lock: Some(lock) }
+    }
+}
+
+/// Blocks kept together in one JSON file
+#[derive(Debug, Clone)]
+pub struct JsonBlockStore {
+    path: String,
+}
+
+impl JsonBlockStore {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the blocks file
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Replace the whole file with `blocks`
+    pub fn save_all(&self, blocks: &HashMap<String, BlockData>) -> Result<()> {
+        let _lock = self.lock_file()?;
+        self.write_all(blocks)
+    }
+
+    /// Wait until no other store is rewriting the file
+    fn lock_file(&self) -> Result<FileLock> {
+        FileLock::acquire(block_lock::file_lock_path(&self.path))
+    }
+
+    fn write_all(&self, blocks: &HashMap<String, BlockData>) -> Result<()> {
+        let content = serde_json::to_string_pretty(blocks)?;
+        data_file::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+impl BlockStore for JsonBlockStore {
+    /// A missing or empty file holds no blocks
+    fn load_all(&self) -> Result<HashMap<String, BlockData>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(HashMap::new());
+        }
+
+        let content =
+            data_file::read_to_string(&self.path).map_err(|_e| BlockError::DataFileNotFound {
+                path: self.path.clone(),
+            })?;
+        if content.trim().is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        parse_blocks(&self.path, &content)
+    }
+
+    fn get(&self, block_num: &str) -> Result<Option<BlockData>> {
+        Ok(self.load_all()?.remove(block_num))
+    }
+
+    /// Rewrites the file under a file lock, keeping blocks other processes saved
+    fn put(&mut self, block: &BlockData) -> Result<()> {
+        let _lock = self.lock_file()?;
+        let mut blocks = self.load_all()?;
+        blocks.insert(block.block_num.clone(), block.clone());
+        self.write_all(&blocks)
+    }
+
+    fn list_nums(&self) -> Result<Vec<String>> {
+        Ok(self.load_all()?.into_keys().collect())
+    }
+
+    /// A lock file beside the blocks file (see `block_lock`)
+    fn lock_block(&self, block_num: &str) -> Result<BlockGuard> {
+        BlockLock::acquire(block_lock::lock_path(&self.path, block_num), block_num).map(Into::into)
+    }
+}
+
+/// Blocks held in memory, such as a blocks file piped in on stdin
+///
+/// Nothing is written anywhere: `put` only updates the in-memory copy.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBlockStore {
+    blocks: HashMap<String, BlockData>,
+}
+
+impl MemoryBlockStore {
+    pub fn new(blocks: HashMap<String, BlockData>) -> Self {
+        Self { blocks }
+    }
+
+    /// Read blocks JSON from `reader`, which may be empty
+    ///
+    /// Parse errors report the location as [`STDIO_PATH`].
+    pub fn from_reader(mut reader: impl Read) -> Result<Self> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(Self::new(parse_blocks(STDIO_PATH, &content)?))
+    }
+
+    /// Every block, including changes made through the store
+    pub fn blocks(&self) -> &HashMap<String, BlockData> {
+        &self.blocks
+    }
+}
+
+impl BlockStore for MemoryBlockStore {
+    fn load_all(&self) -> Result<HashMap<String, BlockData>> {
+        Ok(self.blocks.clone())
+    }
+
+    fn get(&self, block_num: &str) -> Result<Option<BlockData>> {
+        Ok(self.blocks.get(block_num).cloned())
+    }
+
+    fn put(&mut self, block: &BlockData) -> Result<()> {
+        self.blocks.insert(block.block_num.clone(), block.clone());
+        Ok(())
+    }
+
+    fn list_nums(&self) -> Result<Vec<String>> {
+        Ok(self.blocks.keys().cloned().collect())
+    }
+}
+
+/// Whether `path` is `-`, meaning stdin or stdout rather than a file
+pub fn is_stdio(path: impl AsRef<Path>) -> bool {
+    path.as_ref() == Path::new(STDIO_PATH)
+}
+
+/// Parse blocks data, reporting the file location and offending block on failure
+fn parse_blocks(path: &str, content: &str) -> Result<HashMap<String, BlockData>> {
+    let error = match serde_json::from_str(content) {
+        Ok(blocks) => return Ok(blocks),
+        Err(e) => e,
+    };
+
+    // Syntax errors leave nothing to attribute to a block
+    let raw_blocks: serde_json::Map<String, serde_json::Value> = match serde_json::from_str(content)
+    {
+        Ok(raw_blocks) => raw_blocks,
+        Err(_) => {
+            return Err(BlockError::MalformedDataFile {
+                path: path.to_string(),
+                line: error.line(),
+                column: error.column(),
+                message: error.to_string(),
+            }
+            .into())
+        }
+    };
+
+    // The JSON is well-formed, so find the block that doesn't match BlockData
+    for (block_num, value) in raw_blocks {
+        if let Err(e) = serde_json::from_value::<BlockData>(value) {
+            return Err(BlockError::MalformedBlock {
+                path: path.to_string(),
+                block_num,
+                line: error.line(),
+                column: error.column(),
+                message: e.to_string(),
+            }
+            .into());
+        }
+    }
+
+    Err(BlockError::MalformedDataFile {
+        path: path.to_string(),
+        line: error.line(),
+        column: error.column(),
+        message: error.to_string(),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CliptionsError;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_put_keeps_other_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("blocks.json");
+        let mut store = JsonBlockStore::new(path.to_string_lossy());
+        // Reading a missing file doesn't create it
+        assert!(store.load_all().unwrap().is_empty());
+        assert!(!path.exists());
+
+        let block = |num: &str, prize_pool: f64| {
+            BlockData::new(
+                num.to_string(),
+                "target.jpg".to_string(),
+                "social".to_string(),
+                prize_pool,
+            )
+        };
+        store.put(&block("1", 100.0)).unwrap();
+        // Another store on the same file saves a block in the meantime
+        JsonBlockStore::new(path.to_string_lossy())
+            .put(&block("2", 50.0))
+            .unwrap();
+        store.put(&block("1", 200.0)).unwrap();
+
+        let mut nums = store.list_nums().unwrap();
+        nums.sort();
+        assert_eq!(nums, vec!["1", "2"]);
+        assert_eq!(store.get("1").unwrap().unwrap().prize_pool, 200.0);
+        assert!(store.get("3").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_concurrent_puts_keep_every_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("blocks.json").to_string_lossy().to_string();
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let block = BlockData::new(
+                        i.to_string(),
+                        "target.jpg".to_string(),
+                        "social".to_string(),
+                        100.0,
+                    );
+                    JsonBlockStore::new(path).put(&block).unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(JsonBlockStore::new(path).list_nums().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_memory_store_reads_blocks_from_reader() {
+        let block = BlockData::new(
+            "1".to_string(),
+            "target.jpg".to_string(),
+            "social".to_string(),
+            100.0,
+        );
+        let json = serde_json::to_string(&HashMap::from([("1".to_string(), block)])).unwrap();
+
+        let mut store = MemoryBlockStore::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(store.get("1").unwrap().unwrap().prize_pool, 100.0);
+        let mut updated = store.get("1").unwrap().unwrap();
+        updated.prize_pool = 50.0;
+        store.put(&updated).unwrap();
+        assert_eq!(store.blocks()["1"].prize_pool, 50.0);
+
+        assert!(MemoryBlockStore::from_reader(&b""[..])
+            .unwrap()
+            .blocks()
+            .is_empty());
+        let err = MemoryBlockStore::from_reader(&b"{not json"[..]).unwrap_err();
+        assert!(matches!(
+            err,
+            CliptionsError::Block(BlockError::MalformedDataFile { ref path, .. }) if path == STDIO_PATH
+        ));
+    }
+}
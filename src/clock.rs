@@ -0,0 +1,84 @@
+//! Source of the current time for deadline and freshness checks
+//!
+//! Time-sensitive code takes a `Clock` instead of calling `Utc::now()`
+//! directly, so tests can step a `MockClock` across a boundary instead of
+//! sleeping or back-dating timestamps.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Provides the current time
+pub trait Clock: Send + Sync {
+    /// Current time in UTC
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// The system's wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Move the clock forward by `duration` (backward if negative)
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Set the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_mock_clock_advances_only_when_told() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let clock = Arc::new(MockClock::new(start));
+        let shared: Arc<dyn Clock> = clock.clone();
+
+        assert_eq!(shared.now(), start);
+        clock.advance(Duration::minutes(15));
+        assert_eq!(shared.now(), start + Duration::minutes(15));
+        clock.set(start);
+        assert_eq!(shared.now(), start);
+
+        let before = Utc::now();
+        let now = SystemClock.now();
+        assert!(now >= before && now <= Utc::now());
+    }
+}
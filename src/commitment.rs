@@ -5,35 +5,201 @@
 //! them until the reveal phase, preventing gaming of the system.
 
 use crate::error::{CommitmentError, Result};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalize guess text so equivalent guesses commit to the same hash
+///
+/// Replaces typographic quotes with their ASCII forms, trims and collapses
+/// whitespace, lowercases, and applies Unicode NFC normalization. Commitments
+/// and reveals must go through the same normalization to match: opt in on both
+/// sides with `with_normalization(true)`. `Guess::new` always normalizes, but
+/// keeps the submitted text for verification.
+pub fn normalize_guess(text: &str) -> String {
+    normalize_guess_with(text, true)
+}
+
+/// Normalize guess text, optionally preserving its case
+pub fn normalize_guess_with(text: &str, lowercase: bool) -> String {
+    let unquoted: String = text
+        .chars()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+            other => other,
+        })
+        .collect();
+
+    let collapsed = unquoted.split_whitespace().collect::<Vec<_>>().join(" ");
+    let cased = if lowercase {
+        collapsed.to_lowercase()
+    } else {
+        collapsed
+    };
+
+    cased.nfc().collect()
+}
+
+/// Source of random bytes for salts
+///
+/// Implement this to draw salts from a specific generator, such as a
+/// hardware RNG, or from fixed bytes in tests.
+pub trait SaltSource: fmt::Debug + Send + Sync {
+    /// Fill `buf` with random bytes
+    fn fill(&self, buf: &mut [u8]);
+}
+
+/// Default salt source backed by `rand::thread_rng`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadRngSaltSource;
+
+impl SaltSource for ThreadRngSaltSource {
+    fn fill(&self, buf: &mut [u8]) {
+        use rand::RngCore;
+        rand::thread_rng().fill_bytes(buf);
+    }
+}
+
+/// Salt source that reads directly from the operating system's CSPRNG
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsRngSaltSource;
+
+impl SaltSource for OsRngSaltSource {
+    fn fill(&self, buf: &mut [u8]) {
+        use rand::RngCore;
+        rand::rngs::OsRng.fill_bytes(buf);
+    }
+}
+
+/// Hash function a commitment is computed with
+///
+/// SHA-256 is the default and what every existing commitment uses. BLAKE3
+/// is faster on large verification batches; a commitment made with it only
+/// verifies under BLAKE3, so the algorithm is stored alongside it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgo {
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Hex digest of `message` followed by `salt`
+    fn digest(self, message: &[u8], salt: &[u8]) -> String {
+        match self {
+            HashAlgo::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(message);
+                hasher.update(salt);
+                format!("{:x}", hasher.finalize())
+            }
+            HashAlgo::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(message);
+                hasher.update(salt);
+                hasher.finalize().to_hex().to_string()
+            }
+        }
+    }
+
+    /// Whether this is the default algorithm, for skipping it when serializing
+    pub fn is_default(&self) -> bool {
+        *self == HashAlgo::default()
+    }
+}
+
+impl fmt::Display for HashAlgo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashAlgo::Sha256 => write!(f, "sha256"),
+            HashAlgo::Blake3 => write!(f, "blake3"),
+        }
+    }
+}
+
+impl std::str::FromStr for HashAlgo {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "sha256" => Ok(HashAlgo::Sha256),
+            "blake3" => Ok(HashAlgo::Blake3),
+            _ => Err(format!(
+                "Invalid hash algorithm: {}. Must be 'sha256' or 'blake3'",
+                s
+            )),
+        }
+    }
+}
 
 /// Commitment generator for creating cryptographic commitments
 #[derive(Debug, Clone)]
 pub struct CommitmentGenerator {
     salt_length: usize,
+    normalize: bool,
+    salt_source: Arc<dyn SaltSource>,
+    hash_algo: HashAlgo,
 }
 
 impl CommitmentGenerator {
     /// Create a new commitment generator with default salt length
     pub fn new() -> Self {
-        Self { salt_length: 32 }
+        Self::with_salt_length(32)
     }
 
     /// Create a commitment generator with custom salt length
     pub fn with_salt_length(salt_length: usize) -> Self {
-        Self { salt_length }
+        Self {
+            salt_length,
+            normalize: false,
+            salt_source: Arc::new(ThreadRngSaltSource),
+            hash_algo: HashAlgo::default(),
+        }
+    }
+
+    /// Hash commitments with `hash_algo` instead of SHA-256
+    pub fn with_hash_algo(mut self, hash_algo: HashAlgo) -> Self {
+        self.hash_algo = hash_algo;
+        self
+    }
+
+    /// The hash algorithm commitments are computed with
+    pub fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    /// Draw salts from `salt_source` instead of `rand::thread_rng`
+    pub fn with_salt_source(mut self, salt_source: impl SaltSource + 'static) -> Self {
+        self.salt_source = Arc::new(salt_source);
+        self
+    }
+
+    /// Pass messages through `normalize_guess` before hashing
+    ///
+    /// Off by default, so commitments match the Python implementation and
+    /// those made before normalization was introduced.
+    pub fn with_normalization(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
     }
 
     /// Generate a commitment hash from a message and salt
     ///
-    /// This matches the Python implementation: hash(message + salt)
+    /// This matches the Python implementation: hash(message + salt). With
+    /// `with_normalization(true)` the message is first passed through
+    /// [`normalize_guess`].
     ///
     /// # Arguments
     /// * `message` - The plaintext message to commit to
     /// * `salt` - A random salt value to prevent brute force attacks
     ///
     /// # Returns
-    /// The hex-encoded SHA-256 hash of the message concatenated with the salt
+    /// The hex-encoded hash of the message concatenated with the salt, using
+    /// the generator's `HashAlgo` (SHA-256 by default)
     ///
     /// # Errors
     /// Returns `CommitmentError::EmptySalt` if the salt is empty
@@ -45,12 +211,13 @@ impl CommitmentGenerator {
             return Err(CommitmentError::EmptySalt.into());
         }
 
-        let mut hasher = Sha256::new();
-        hasher.update(message.as_bytes());
-        hasher.update(salt.as_bytes());
-        let result = hasher.finalize();
+        let message = if self.normalize {
+            normalize_guess(message)
+        } else {
+            message.to_string()
+        };
 
-        Ok(format!("{:x}", result))
+        Ok(self.hash_algo.digest(message.as_bytes(), salt.as_bytes()))
     }
 
     /// Generate a random salt of the specified length
@@ -58,11 +225,71 @@ impl CommitmentGenerator {
     /// # Returns
     /// A random hex-encoded salt string
     pub fn generate_salt(&self) -> String {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let bytes: Vec<u8> = (0..self.salt_length).map(|_| rng.gen::<u8>()).collect();
+        let mut bytes = vec![0u8; self.salt_length];
+        self.salt_source.fill(&mut bytes);
         hex::encode(bytes)
     }
+
+    /// Commit to several messages, each with a fresh salt
+    ///
+    /// # Arguments
+    /// * `messages` - The plaintext messages to commit to
+    ///
+    /// # Returns
+    /// A vector of (salt, commitment) pairs in the same order as `messages`
+    ///
+    /// # Errors
+    /// Returns `CommitmentError::EmptyMessage` if any message is empty, or
+    /// `CommitmentError::DuplicateSalt` if the salt source repeats a salt
+    pub fn generate_batch(&self, messages: &[&str]) -> Result<Vec<(String, String)>> {
+        let salts: Vec<String> = messages.iter().map(|_| self.generate_salt()).collect();
+        Self::check_unique_salts(&salts)?;
+
+        messages
+            .iter()
+            .zip(salts)
+            .map(|(message, salt)| {
+                let commitment = self.generate(message, &salt)?;
+                Ok((salt, commitment))
+            })
+            .collect()
+    }
+
+    /// Parallel batch generation for large batches
+    ///
+    /// Salts are drawn sequentially from the salt source; only the hashing
+    /// runs in parallel.
+    ///
+    /// # Arguments
+    /// * `messages` - The plaintext messages to commit to
+    ///
+    /// # Returns
+    /// A vector of (salt, commitment) pairs in the same order as `messages`
+    pub fn generate_batch_parallel(&self, messages: &[&str]) -> Result<Vec<(String, String)>> {
+        use rayon::prelude::*;
+
+        let salts: Vec<String> = messages.iter().map(|_| self.generate_salt()).collect();
+        Self::check_unique_salts(&salts)?;
+
+        messages
+            .par_iter()
+            .zip(salts.into_par_iter())
+            .map(|(message, salt)| {
+                let commitment = self.generate(message, &salt)?;
+                Ok((salt, commitment))
+            })
+            .collect()
+    }
+
+    /// Reject a batch whose salts aren't all distinct
+    fn check_unique_salts(salts: &[String]) -> Result<()> {
+        let mut seen = std::collections::HashSet::new();
+        if salts.iter().all(|salt| seen.insert(salt)) {
+            Ok(())
+        } else {
+            Err(CommitmentError::DuplicateSalt.into())
+        }
+    }
 }
 
 impl Default for CommitmentGenerator {
@@ -71,6 +298,61 @@ impl Default for CommitmentGenerator {
     }
 }
 
+/// Why a commitment failed verification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FailReason {
+    /// No salt was revealed, so the commitment can't be recomputed
+    MissingSalt,
+    /// The commitment is not a 64-character hex string
+    MalformedCommitment,
+    /// The revealed guess and salt can't be hashed (e.g. an empty guess)
+    InvalidReveal(String),
+    /// The reveal hashes to a different value than the committed one
+    HashMismatch { expected: String, provided: String },
+}
+
+impl fmt::Display for FailReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailReason::MissingSalt => write!(f, "Salt was not revealed"),
+            FailReason::MalformedCommitment => {
+                write!(f, "Commitment is not a 64-character hex string")
+            }
+            FailReason::InvalidReveal(e) => write!(f, "Reveal could not be hashed: {}", e),
+            FailReason::HashMismatch { expected, provided } => write!(
+                f,
+                "Hash mismatch: reveal hashes to {} but commitment is {}",
+                expected, provided
+            ),
+        }
+    }
+}
+
+/// Outcome of verifying a commitment, with the reason when it failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitmentVerification {
+    pub valid: bool,
+    pub reason: Option<FailReason>,
+}
+
+impl CommitmentVerification {
+    /// A successful verification
+    pub fn valid() -> Self {
+        Self {
+            valid: true,
+            reason: None,
+        }
+    }
+
+    /// A failed verification with its reason
+    pub fn invalid(reason: FailReason) -> Self {
+        Self {
+            valid: false,
+            reason: Some(reason),
+        }
+    }
+}
+
 /// Commitment verifier for validating commitments
 #[derive(Debug, Clone)]
 pub struct CommitmentVerifier {
@@ -85,6 +367,18 @@ impl CommitmentVerifier {
         }
     }
 
+    /// Verify commitments made on messages passed through `normalize_guess`
+    pub fn with_normalization(mut self, normalize: bool) -> Self {
+        self.generator = self.generator.with_normalization(normalize);
+        self
+    }
+
+    /// Verify commitments that were made with `hash_algo`
+    pub fn with_hash_algo(mut self, hash_algo: HashAlgo) -> Self {
+        self.generator = self.generator.with_hash_algo(hash_algo);
+        self
+    }
+
     /// Verify that a commitment matches the provided message and salt
     ///
     /// # Arguments
@@ -101,6 +395,35 @@ impl CommitmentVerifier {
         }
     }
 
+    /// Verify a commitment and explain why it failed, if it did
+    ///
+    /// Unlike [`verify`](Self::verify), this distinguishes a missing salt, a
+    /// malformed commitment, an unhashable reveal and a plain hash mismatch.
+    pub fn explain(
+        &self,
+        message: &str,
+        salt: Option<&str>,
+        commitment: &str,
+    ) -> CommitmentVerification {
+        let salt = match salt {
+            Some(salt) if !salt.is_empty() => salt,
+            _ => return CommitmentVerification::invalid(FailReason::MissingSalt),
+        };
+
+        if commitment.len() != 64 || !commitment.chars().all(|c| c.is_ascii_hexdigit()) {
+            return CommitmentVerification::invalid(FailReason::MalformedCommitment);
+        }
+
+        match self.generator.generate(message, salt) {
+            Ok(expected) if expected == commitment => CommitmentVerification::valid(),
+            Ok(expected) => CommitmentVerification::invalid(FailReason::HashMismatch {
+                expected,
+                provided: commitment.to_string(),
+            }),
+            Err(e) => CommitmentVerification::invalid(FailReason::InvalidReveal(e.to_string())),
+        }
+    }
+
     /// Batch verify multiple commitments
     ///
     /// # Arguments
@@ -117,21 +440,56 @@ impl CommitmentVerifier {
 
     /// Parallel batch verification for better performance
     ///
+    /// Each distinct (message, salt) pair is hashed once, so blocks where many
+    /// participants reveal the same guess and salt don't repeat the work.
+    ///
     /// # Arguments
     /// * `commitments` - A slice of tuples containing (message, salt, commitment)
     ///
     /// # Returns
-    /// A vector of boolean values indicating whether each commitment is valid
+    /// A vector of boolean values indicating whether each commitment is valid,
+    /// in the same order as `commitments`
     pub fn verify_batch_parallel(&self, commitments: &[(&str, &str, &str)]) -> Vec<bool> {
-        use rayon::prelude::*;
-
-        commitments
-            .par_iter()
-            .map(|(message, salt, commitment)| self.verify(message, salt, commitment))
-            .collect()
+        verify_deduplicated(commitments, |message, salt| {
+            self.generator.generate(message, salt).ok()
+        })
     }
 }
 
+/// Verify commitments, hashing each distinct (message, salt) pair once
+///
+/// `hash` returns `None` for pairs that can't be hashed, which never verify.
+fn verify_deduplicated<F>(commitments: &[(&str, &str, &str)], hash: F) -> Vec<bool>
+where
+    F: Fn(&str, &str) -> Option<String> + Sync,
+{
+    use rayon::prelude::*;
+    use std::collections::HashMap;
+
+    let mut slots: HashMap<(&str, &str), usize> = HashMap::new();
+    let mut unique = Vec::new();
+    let positions: Vec<usize> = commitments
+        .iter()
+        .map(|&(message, salt, _)| {
+            *slots.entry((message, salt)).or_insert_with(|| {
+                unique.push((message, salt));
+                unique.len() - 1
+            })
+        })
+        .collect();
+
+    let hashes: Vec<Option<String>> = unique
+        .par_iter()
+        .map(|&(message, salt)| hash(message, salt))
+        .collect();
+
+    commitments
+        .iter()
+        .zip(positions)
+        .map(|(&(_, _, commitment), slot)| hashes[slot].as_deref() == Some(commitment))
+        .collect()
+}
+
 impl Default for CommitmentVerifier {
     fn default() -> Self {
         Self::new()
@@ -239,6 +597,82 @@ mod tests {
         assert!(salt2.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    /// Repeats a fixed byte pattern
+    #[derive(Debug)]
+    struct FixedSaltSource(Vec<u8>);
+
+    impl SaltSource for FixedSaltSource {
+        fn fill(&self, buf: &mut [u8]) {
+            for (byte, fixed) in buf.iter_mut().zip(self.0.iter().cycle()) {
+                *byte = *fixed;
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_batch() {
+        let generator = CommitmentGenerator::new();
+        let messages = ["a red fox", "a blue whale", "a red fox"];
+
+        for pairs in [
+            generator.generate_batch(&messages).unwrap(),
+            generator.generate_batch_parallel(&messages).unwrap(),
+        ] {
+            assert_eq!(pairs.len(), messages.len());
+            for (message, (salt, commitment)) in messages.iter().zip(&pairs) {
+                assert_eq!(salt.len(), 64);
+                assert_eq!(*commitment, generator.generate(message, salt).unwrap());
+            }
+
+            // Repeated guesses still get distinct salts and commitments
+            let salts: std::collections::HashSet<&String> = pairs.iter().map(|(s, _)| s).collect();
+            assert_eq!(salts.len(), messages.len());
+            assert_ne!(pairs[0].1, pairs[2].1);
+        }
+
+        assert!(generator.generate_batch(&[]).unwrap().is_empty());
+        assert!(matches!(
+            generator.generate_batch(&["ok", " "]),
+            Err(crate::error::CliptionsError::Commitment(
+                CommitmentError::EmptyMessage
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_generate_batch_rejects_repeated_salts() {
+        let generator = CommitmentGenerator::new().with_salt_source(FixedSaltSource(vec![1, 2]));
+        assert_eq!(generator.generate_batch(&["only one"]).unwrap().len(), 1);
+
+        for result in [
+            generator.generate_batch(&["a", "b"]),
+            generator.generate_batch_parallel(&["a", "b"]),
+        ] {
+            assert!(matches!(
+                result,
+                Err(crate::error::CliptionsError::Commitment(
+                    CommitmentError::DuplicateSalt
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_salt_source() {
+        let generator = CommitmentGenerator::with_salt_length(4)
+            .with_salt_source(FixedSaltSource(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(generator.generate_salt(), "deadbeef");
+        assert_eq!(generator.generate_salt(), "deadbeef");
+
+        let generator = CommitmentGenerator::new().with_salt_source(FixedSaltSource(vec![0x0a]));
+        assert_eq!(generator.generate_salt(), "0a".repeat(32));
+
+        let salt = CommitmentGenerator::new()
+            .with_salt_source(OsRngSaltSource)
+            .generate_salt();
+        assert_eq!(salt.len(), 64);
+    }
+
     #[test]
     fn test_batch_verification() {
         let generator = CommitmentGenerator::new();
@@ -297,6 +731,77 @@ mod tests {
         assert!(sequential_results.iter().all(|&r| r));
     }
 
+    #[test]
+    fn test_parallel_verification_hashes_duplicates_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let generator = CommitmentGenerator::new();
+        let bot_commitment = generator.generate("a red fox", "botsalt").unwrap();
+        let other_commitment = generator.generate("a blue whale", "salt2").unwrap();
+
+        let mut commitments = Vec::new();
+        for i in 0..100 {
+            commitments.push(("a red fox", "botsalt", bot_commitment.as_str()));
+            if i % 10 == 0 {
+                // Same pair, but a forged commitment
+                commitments.push(("a red fox", "botsalt", other_commitment.as_str()));
+                commitments.push(("a blue whale", "salt2", other_commitment.as_str()));
+                commitments.push(("a blue whale", "", other_commitment.as_str()));
+            }
+        }
+
+        let calls = AtomicUsize::new(0);
+        let results = verify_deduplicated(&commitments, |message, salt| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            generator.generate(message, salt).ok()
+        });
+
+        // Three distinct (message, salt) pairs, one of them unhashable
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let verifier = CommitmentVerifier::new();
+        assert_eq!(results, verifier.verify_batch(&commitments));
+        assert_eq!(results, verifier.verify_batch_parallel(&commitments));
+        assert_eq!(results.len(), 130);
+        assert_eq!(results.iter().filter(|&&valid| valid).count(), 110);
+    }
+
+    #[test]
+    fn test_blake3_commitment() {
+        let generator = CommitmentGenerator::new().with_hash_algo(HashAlgo::Blake3);
+        assert_eq!(generator.hash_algo(), HashAlgo::Blake3);
+
+        let commitment = generator.generate("a red fox", "salt").unwrap();
+        assert_eq!(commitment, blake3::hash(b"a red foxsalt").to_hex().as_str());
+        assert_ne!(
+            commitment,
+            CommitmentGenerator::new().generate("a red fox", "salt").unwrap()
+        );
+
+        let blake3_verifier = CommitmentVerifier::new().with_hash_algo(HashAlgo::Blake3);
+        assert!(blake3_verifier.verify("a red fox", "salt", &commitment));
+        assert!(blake3_verifier
+            .clone()
+            .with_normalization(true)
+            .verify("A  Red Fox ", "salt", &commitment));
+        assert!(!blake3_verifier.verify("a red fox", "other", &commitment));
+        assert!(!CommitmentVerifier::new().verify("a red fox", "salt", &commitment));
+        assert_eq!(
+            blake3_verifier.verify_batch_parallel(&[("a red fox", "salt", commitment.as_str())]),
+            vec![true]
+        );
+        assert_eq!(
+            blake3_verifier.explain("a red fox", Some("salt"), &commitment),
+            CommitmentVerification::valid()
+        );
+
+        assert_eq!("BLAKE3".parse::<HashAlgo>().unwrap(), HashAlgo::Blake3);
+        assert_eq!("sha-256".parse::<HashAlgo>().unwrap(), HashAlgo::Sha256);
+        assert!("md5".parse::<HashAlgo>().is_err());
+        assert_eq!(serde_json::to_string(&HashAlgo::Blake3).unwrap(), "\"blake3\"");
+        assert_eq!(HashAlgo::default(), HashAlgo::Sha256);
+    }
+
     #[test]
     fn test_rust_core_functionality() {
         // Test that our core implementation works correctly
@@ -312,4 +817,123 @@ mod tests {
         assert!(!verifier.verify("different message", salt, &commitment));
         assert!(!verifier.verify(message, "different_salt", &commitment));
     }
+
+    #[test]
+    fn test_normalize_guess() {
+        assert_eq!(
+            normalize_guess("  Sunset   over\tthe  City\n"),
+            "sunset over the city"
+        );
+        assert_eq!(
+            normalize_guess("\u{201C}It\u{2019}s a bird\u{201D}"),
+            "\"it's a bird\""
+        );
+        // Decomposed e + combining acute becomes the composed form
+        assert_eq!(normalize_guess("Cafe\u{0301}"), "caf\u{e9}");
+        assert_eq!(
+            normalize_guess_with("  Keep  The Case ", false),
+            "Keep The Case"
+        );
+
+        // Normalizing twice changes nothing
+        let once = normalize_guess("  \u{201C}MiXeD\u{201D}  Case\u{0301} ");
+        assert_eq!(normalize_guess(&once), once);
+    }
+
+    #[test]
+    fn test_commitment_verifies_differently_formatted_reveal() {
+        let generator = CommitmentGenerator::new().with_normalization(true);
+        let verifier = CommitmentVerifier::new().with_normalization(true);
+        let salt = "test_salt";
+
+        let commitment = generator
+            .generate(&normalize_guess("Sunset over city skyline"), salt)
+            .unwrap();
+
+        assert!(verifier.verify("SUNSET over  City skyline ", salt, &commitment));
+        assert!(verifier.verify("sunset over city skyline", salt, &commitment));
+        assert!(!verifier.verify("sunrise over city skyline", salt, &commitment));
+
+        // Without opting in, the reveal must match byte for byte
+        assert!(!CommitmentVerifier::new().verify(
+            "SUNSET over  City skyline ",
+            salt,
+            &commitment
+        ));
+    }
+
+    #[test]
+    fn test_commitment_matches_python_by_default() {
+        // Reference hash shared with the Python implementation
+        let generator = CommitmentGenerator::new();
+        let commitment = generator
+            .generate("Sunset over city skyline with birds flying", "test_salt")
+            .unwrap();
+        assert_eq!(
+            commitment,
+            "05ba60fa7bb9efb3e7b3bfe1946d91d6bae3d0cc88918072ece01efbd1207cad"
+        );
+
+        let verifier = CommitmentVerifier::new();
+        assert!(verifier.verify(
+            "Sunset over city skyline with birds flying",
+            "test_salt",
+            &commitment
+        ));
+        assert!(!verifier.verify(
+            "sunset over city skyline with birds flying",
+            "test_salt",
+            &commitment
+        ));
+    }
+
+    #[test]
+    fn test_explain_failure_reasons() {
+        let generator = CommitmentGenerator::new();
+        let verifier = CommitmentVerifier::new();
+        let commitment = generator.generate("a red car", "salt").unwrap();
+
+        assert_eq!(
+            verifier.explain("a red car", Some("salt"), &commitment),
+            CommitmentVerification::valid()
+        );
+
+        // Missing salt
+        assert_eq!(
+            verifier.explain("a red car", None, &commitment).reason,
+            Some(FailReason::MissingSalt)
+        );
+        assert_eq!(
+            verifier.explain("a red car", Some(""), &commitment).reason,
+            Some(FailReason::MissingSalt)
+        );
+
+        // Wrong length and non-hex commitments are malformed
+        assert_eq!(
+            verifier.explain("a red car", Some("salt"), "abc123").reason,
+            Some(FailReason::MalformedCommitment)
+        );
+        let non_hex = "z".repeat(64);
+        assert_eq!(
+            verifier.explain("a red car", Some("salt"), &non_hex).reason,
+            Some(FailReason::MalformedCommitment)
+        );
+
+        // Empty guess can't be hashed
+        assert!(matches!(
+            verifier.explain("   ", Some("salt"), &commitment).reason,
+            Some(FailReason::InvalidReveal(_))
+        ));
+
+        // Wrong salt or edited guess shows both hashes
+        let result = verifier.explain("a blue car", Some("salt"), &commitment);
+        assert!(!result.valid);
+        match result.reason {
+            Some(FailReason::HashMismatch { expected, provided }) => {
+                assert_eq!(expected, generator.generate("a blue car", "salt").unwrap());
+                assert_eq!(provided, commitment);
+            }
+            other => panic!("unexpected reason: {:?}", other),
+        }
+    }
 }
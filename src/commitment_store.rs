@@ -0,0 +1,301 @@
+//! Persistent store for a miner's own commitments
+//!
+//! A commitment can only be revealed with the salt it was made with, so the
+//! store writes atomically and keeps a SHA-256 checksum over its records.
+//! A file that was truncated, corrupted or edited by hand fails to load
+//! instead of silently losing salts.
+
+use crate::commitment::{CommitmentVerifier, HashAlgo};
+use crate::error::{CliptionsError, CommitmentError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Current on-disk format version
+const STORE_VERSION: u32 = 1;
+
+/// A commitment the miner made, with everything needed to reveal it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredCommitment {
+    pub block_num: String,
+    pub guess: String,
+    pub salt: String,
+    pub commitment: String,
+    pub created_at: DateTime<Utc>,
+    /// Omitted for SHA-256 so records written before BLAKE3 support keep
+    /// their checksum
+    #[serde(default, skip_serializing_if = "HashAlgo::is_default")]
+    pub hash_algo: HashAlgo,
+}
+
+impl StoredCommitment {
+    /// Create a record timestamped now
+    pub fn new(block_num: String, guess: String, salt: String, commitment: String) -> Self {
+        Self {
+            block_num,
+            guess,
+            salt,
+            commitment,
+            created_at: Utc::now(),
+            hash_algo: HashAlgo::default(),
+        }
+    }
+
+    /// Record that the commitment was hashed with `hash_algo`
+    pub fn with_hash_algo(mut self, hash_algo: HashAlgo) -> Self {
+        self.hash_algo = hash_algo;
+        self
+    }
+
+    /// Check the stored guess and salt against the commitment
+    pub fn verify(&self) -> bool {
+        CommitmentVerifier::new()
+            .with_hash_algo(self.hash_algo)
+            .verify(&self.guess, &self.salt, &self.commitment)
+    }
+}
+
+/// On-disk layout of the store
+#[derive(Serialize, Deserialize)]
+struct StoreFile {
+    version: u32,
+    /// Hex SHA-256 of the JSON-serialized `records`
+    checksum: String,
+    records: Vec<StoredCommitment>,
+}
+
+/// Checksummed file of a miner's commitments
+#[derive(Debug)]
+pub struct CommitmentStore {
+    path: PathBuf,
+    records: Vec<StoredCommitment>,
+}
+
+impl CommitmentStore {
+    /// Default store location in the miner directory, ~/.cliptions/miner/commitments.json
+    ///
+    /// `generate-commitment` saves here unless given another path.
+    pub fn default_path() -> Result<PathBuf> {
+        let home_dir =
+            dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
+        Ok(home_dir
+            .join(".cliptions")
+            .join("miner")
+            .join("commitments.json"))
+    }
+
+    /// Open the store at `path`, starting empty if the file doesn't exist
+    ///
+    /// # Errors
+    /// Returns a JSON error if the file isn't a commitment store, and
+    /// `CommitmentError::ChecksumMismatch` if it fails its integrity check
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let records = if path.exists() {
+            read_store(&path)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path, records })
+    }
+
+    /// Path the store reads from and writes to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All stored commitments, oldest first
+    pub fn records(&self) -> &[StoredCommitment] {
+        &self.records
+    }
+
+    /// Add a commitment and persist the store
+    ///
+    /// # Errors
+    /// Returns a validation error if the same commitment is already stored
+    pub fn add(&mut self, record: StoredCommitment) -> Result<()> {
+        if self
+            .records
+            .iter()
+            .any(|r| r.block_num == record.block_num && r.commitment == record.commitment)
+        {
+            return Err(CliptionsError::ValidationError(format!(
+                "Commitment {} is already stored for block {}",
+                record.commitment, record.block_num
+            )));
+        }
+
+        self.records.push(record);
+        if let Err(e) = self.save() {
+            self.records.pop();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Commitments made for `block_num`
+    pub fn get_for_block(&self, block_num: &str) -> Vec<&StoredCommitment> {
+        self.records
+            .iter()
+            .filter(|r| r.block_num == block_num)
+            .collect()
+    }
+
+    /// Re-read the file on disk and check it against its checksum
+    pub fn verify_integrity(&self) -> Result<()> {
+        read_store(&self.path).map(|_| ())
+    }
+
+    /// Write the store to a temporary file and rename it into place
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let store = StoreFile {
+            version: STORE_VERSION,
+            checksum: checksum(&self.records)?,
+            records: self.records.clone(),
+        };
+
+        let mut tmp_name = self.path.as_os_str().to_owned();
+        tmp_name.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_name);
+
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(serde_json::to_string_pretty(&store)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Load and verify the records in a store file
+fn read_store(path: &Path) -> Result<Vec<StoredCommitment>> {
+    let content = fs::read_to_string(path)?;
+    let store: StoreFile = serde_json::from_str(&content)?;
+
+    if store.version != STORE_VERSION {
+        return Err(CliptionsError::ValidationError(format!(
+            "Unsupported commitment store version {} in {}",
+            store.version,
+            path.display()
+        )));
+    }
+    if checksum(&store.records)? != store.checksum {
+        return Err(CommitmentError::ChecksumMismatch.into());
+    }
+
+    Ok(store.records)
+}
+
+/// Hex SHA-256 over the serialized records
+fn checksum(records: &[StoredCommitment]) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(records)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::CommitmentGenerator;
+    use tempfile::TempDir;
+
+    fn record(block_num: &str, guess: &str) -> StoredCommitment {
+        let generator = CommitmentGenerator::new();
+        let salt = generator.generate_salt();
+        let commitment = generator.generate(guess, &salt).unwrap();
+        StoredCommitment::new(block_num.to_string(), guess.to_string(), salt, commitment)
+    }
+
+    #[test]
+    fn test_add_and_reload() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("miner").join("commitment_store.json");
+
+        let mut store = CommitmentStore::open(&path).unwrap();
+        assert!(store.records().is_empty());
+
+        let first = record("1", "a red fox");
+        store.add(first.clone()).unwrap();
+        store.add(record("1", "a blue whale")).unwrap();
+        store.add(record("2", "a green frog")).unwrap();
+        assert!(store.add(first.clone()).is_err());
+
+        let reloaded = CommitmentStore::open(&path).unwrap();
+        assert_eq!(reloaded.records(), store.records());
+        assert_eq!(reloaded.get_for_block("1").len(), 2);
+        assert_eq!(reloaded.get_for_block("1")[0], &first);
+        assert_eq!(reloaded.get_for_block("2")[0].guess, "a green frog");
+        assert!(reloaded.get_for_block("3").is_empty());
+        reloaded.verify_integrity().unwrap();
+
+        // No temporary file is left behind
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_records_hash_algo() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("commitment_store.json");
+
+        let generator = CommitmentGenerator::new().with_hash_algo(HashAlgo::Blake3);
+        let salt = generator.generate_salt();
+        let commitment = generator.generate("a red fox", &salt).unwrap();
+        let blake3_record =
+            StoredCommitment::new("1".to_string(), "a red fox".to_string(), salt, commitment)
+                .with_hash_algo(HashAlgo::Blake3);
+        assert!(blake3_record.verify());
+
+        let mut store = CommitmentStore::open(&path).unwrap();
+        store.add(blake3_record).unwrap();
+        store.add(record("1", "a blue whale")).unwrap();
+
+        let reloaded = CommitmentStore::open(&path).unwrap();
+        assert_eq!(reloaded.records()[0].hash_algo, HashAlgo::Blake3);
+        assert_eq!(reloaded.records()[1].hash_algo, HashAlgo::Sha256);
+        assert!(reloaded.records().iter().all(StoredCommitment::verify));
+
+        // SHA-256 records are written exactly as before the field existed
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches("hash_algo").count(), 1);
+    }
+
+    #[test]
+    fn test_detects_corruption() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("commitment_store.json");
+
+        let mut store = CommitmentStore::open(&path).unwrap();
+        store.add(record("1", "a red fox")).unwrap();
+
+        // Tampering with a salt breaks the checksum
+        let content = fs::read_to_string(&path).unwrap();
+        let salt = &store.records()[0].salt;
+        fs::write(&path, content.replace(salt.as_str(), "tampered")).unwrap();
+
+        assert!(matches!(
+            store.verify_integrity(),
+            Err(CliptionsError::Commitment(
+                CommitmentError::ChecksumMismatch
+            ))
+        ));
+        assert!(matches!(
+            CommitmentStore::open(&path),
+            Err(CliptionsError::Commitment(
+                CommitmentError::ChecksumMismatch
+            ))
+        ));
+
+        // A truncated file isn't a store at all
+        fs::write(&path, &content[..content.len() / 2]).unwrap();
+        assert!(matches!(
+            CommitmentStore::open(&path),
+            Err(CliptionsError::Json(_))
+        ));
+    }
+}
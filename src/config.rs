@@ -2,11 +2,13 @@ use crate::error::{CliptionsError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use twitter_api::redact_secret;
 
 /// OpenAI configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpenAIConfig {
     pub api_key: String,
     pub model: String,
@@ -29,6 +31,20 @@ impl Default for OpenAIConfig {
     }
 }
 
+/// Redacts the API key, so configs can be logged
+impl fmt::Debug for OpenAIConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenAIConfig")
+            .field("api_key", &redact_secret(&self.api_key))
+            .field("model", &self.model)
+            .field("temperature", &self.temperature)
+            .field("daily_spending_limit_usd", &self.daily_spending_limit_usd)
+            .field("max_tokens", &self.max_tokens)
+            .field("project_id", &self.project_id)
+            .finish()
+    }
+}
+
 /// Browser use configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrowserUseConfig {
@@ -66,13 +82,16 @@ impl Default for CostTrackingConfig {
 }
 
 /// Twitter API configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TwitterConfig {
     pub api_key: String,
     pub api_secret: String,
     pub access_token: String,
     pub access_token_secret: String,
     pub validator_username: String,
+    /// Optional app-only bearer token for read-only API calls
+    #[serde(default)]
+    pub bearer_token: Option<String>,
 }
 
 impl Default for TwitterConfig {
@@ -83,10 +102,25 @@ impl Default for TwitterConfig {
             access_token: String::new(),
             access_token_secret: String::new(),
             validator_username: String::new(),
+            bearer_token: None,
         }
     }
 }
 
+/// Redacts the keys and tokens, so configs can be logged
+impl fmt::Debug for TwitterConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TwitterConfig")
+            .field("api_key", &redact_secret(&self.api_key))
+            .field("api_secret", &redact_secret(&self.api_secret))
+            .field("access_token", &redact_secret(&self.access_token))
+            .field("access_token_secret", &redact_secret(&self.access_token_secret))
+            .field("validator_username", &self.validator_username)
+            .field("bearer_token", &self.bearer_token.as_deref().map(redact_secret))
+            .finish()
+    }
+}
+
 /// Base blockchain configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaseConfig {
@@ -686,4 +720,31 @@ cost_tracking:
             None => env::remove_var("OPENAI_API_KEY"),
         }
     }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let mut config = CliptionsConfig::default();
+        config.openai.api_key = "sk-openai-secret-key-1234".to_string();
+        config.twitter.api_key = "twitter-api-key-abcd".to_string();
+        config.twitter.api_secret = "twitter-api-secret-efgh".to_string();
+        config.twitter.access_token = "twitter-access-token-ijkl".to_string();
+        config.twitter.access_token_secret = "twitter-token-secret-mnop".to_string();
+        config.twitter.bearer_token = Some("twitter-bearer-token-qrst".to_string());
+
+        let twitter_debug = format!("{:?}", config.twitter);
+        let config_debug = format!("{:#?}", config);
+        for secret in [
+            &config.openai.api_key,
+            &config.twitter.api_key,
+            &config.twitter.api_secret,
+            &config.twitter.access_token,
+            &config.twitter.access_token_secret,
+            config.twitter.bearer_token.as_ref().unwrap(),
+        ] {
+            assert!(!twitter_debug.contains(secret.as_str()));
+            assert!(!config_debug.contains(secret.as_str()));
+        }
+        assert!(twitter_debug.contains("****abcd"));
+        assert!(config_debug.contains("****1234"));
+    }
 }
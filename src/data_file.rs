@@ -0,0 +1,87 @@
+//! Reading and writing JSON data files, gzip-compressed when the name says so
+//!
+//! A path ending in `.json.gz` is compressed on write and decompressed on
+//! read; any other path is plain text, exactly as before.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// Suffix marking a gzip-compressed JSON file
+pub const GZIP_JSON_SUFFIX: &str = ".json.gz";
+
+/// Whether `path` names a gzip-compressed JSON file
+pub fn is_compressed(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .to_str()
+        .is_some_and(|p| p.to_ascii_lowercase().ends_with(GZIP_JSON_SUFFIX))
+}
+
+/// Read a data file to a string, decompressing `.json.gz` files
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    if !is_compressed(path) {
+        return fs::read_to_string(path);
+    }
+
+    let mut content = String::new();
+    GzDecoder::new(fs::File::open(path)?).read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Write a data file, compressing `.json.gz` files
+pub fn write(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    if !is_compressed(path) {
+        return fs::write(path, content);
+    }
+
+    let mut encoder = GzEncoder::new(fs::File::create(path)?, Compression::default());
+    encoder.write_all(content.as_ref())?;
+    encoder.finish()?.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_compressed() {
+        assert!(is_compressed("data/blocks.json.gz"));
+        assert!(is_compressed("BLOCKS.JSON.GZ"));
+        assert!(!is_compressed("data/blocks.json"));
+        assert!(!is_compressed("data/blocks.gz"));
+    }
+
+    #[test]
+    fn test_round_trip_compressed_and_plain() {
+        let temp_dir = TempDir::new().unwrap();
+        let plain = temp_dir.path().join("data.json");
+        let compressed = temp_dir.path().join("data.json.gz");
+        let content = format!("{{\"items\": [{}]}}", vec!["1"; 1000].join(", "));
+
+        write(&plain, &content).unwrap();
+        write(&compressed, &content).unwrap();
+
+        assert_eq!(read_to_string(&plain).unwrap(), content);
+        assert_eq!(read_to_string(&compressed).unwrap(), content);
+        // The compressed file is gzip on disk, not plain JSON
+        let raw = fs::read(&compressed).unwrap();
+        assert_eq!(&raw[..2], &[0x1f, 0x8b]);
+        assert!(raw.len() < content.len());
+    }
+
+    #[test]
+    fn test_corrupt_compressed_file_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.json.gz");
+        fs::write(&path, "{}").unwrap();
+
+        assert!(read_to_string(&path).is_err());
+    }
+}
@@ -5,17 +5,90 @@
 
 use crate::error::{EmbeddingError, Result};
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Candle imports for native CLIP support
 use candle_core::{DType, Device, Tensor};
 use candle_nn::{ops::softmax, VarBuilder};
+use candle_transformers::models::clip::text_model::{Activation, ClipTextConfig};
+use candle_transformers::models::clip::vision_model::ClipVisionConfig;
 use candle_transformers::models::clip::{ClipConfig, ClipModel};
+use sha2::{Digest, Sha256};
 use std::fs;
+use std::io::{Read, Write};
+use std::time::Duration;
 use tokenizers::Tokenizer;
 
+/// Number of times a model file is fetched before giving up on a bad download
+pub const MODEL_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Environment variable overriding the directory CLIP models are found in and downloaded to
+pub const MODEL_DIR_ENV: &str = "CLIPTIONS_MODEL_DIR";
+
+/// Subdirectory of a model directory holding the default ViT-B/32 model
+const DEFAULT_MODEL_SUBDIR: &str = "clip-vit-base-patch32";
+
+/// Directory CLIP models are looked up in and downloaded to
+///
+/// `$CLIPTIONS_MODEL_DIR` if set, otherwise `~/.cliptions/models`.
+pub fn default_model_dir() -> PathBuf {
+    model_dir_from(std::env::var_os(MODEL_DIR_ENV), dirs::home_dir())
+}
+
+fn model_dir_from(env_dir: Option<std::ffi::OsString>, home_dir: Option<PathBuf>) -> PathBuf {
+    match env_dir.filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => home_dir
+            .map(|home| home.join(".cliptions").join("models"))
+            .unwrap_or_else(|| PathBuf::from("models")),
+    }
+}
+
+/// Find a complete CLIP model in `dir` or one of its known model subdirectories
+pub fn find_model_in(dir: &Path) -> Option<PathBuf> {
+    [
+        dir.to_path_buf(),
+        dir.join(DEFAULT_MODEL_SUBDIR),
+        dir.join("openai-clip-vit-base-patch32"),
+    ]
+    .into_iter()
+    .find(|path| ClipEmbedder::check_model_files_exist(&path.to_string_lossy()))
+}
+
+/// How failed model file downloads are retried
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DownloadRetryConfig {
+    /// Total fetches per file, including the first
+    pub attempts: u32,
+    /// Delay before the first retry; doubles on each further retry
+    pub base_delay: Duration,
+    /// Upper bound on the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for DownloadRetryConfig {
+    fn default() -> Self {
+        Self {
+            attempts: MODEL_DOWNLOAD_ATTEMPTS,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl DownloadRetryConfig {
+    /// Delay after failed attempt `attempt` (starting at 1)
+    ///
+    /// See `base_api::retry::backoff`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        base_api::retry::backoff(self.base_delay, self.max_delay, attempt)
+    }
+}
+
 /// Trait for embedding models that can convert images and text to feature vectors
 ///
 /// This corresponds to the Python IEmbedder interface
@@ -53,6 +126,94 @@ pub trait EmbedderTrait: Send + Sync {
 
     /// Get the dimensionality of embeddings produced by this model
     fn embedding_dim(&self) -> usize;
+
+    /// Name of this embedder, recorded as scoring provenance
+    fn name(&self) -> &str {
+        "unknown"
+    }
+
+    /// Identity of the model and settings the similarities come from
+    ///
+    /// Cached scores are only reused for the same id, so anything that changes
+    /// the similarities, such as other weights or another softmax
+    /// temperature, must change it.
+    fn model_id(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// Number of tokens the embedder's tokenizer produces for `text`
+    ///
+    /// `None` for embedders without a tokenizer.
+    fn token_count(&self, _text: &str) -> Result<Option<usize>> {
+        Ok(None)
+    }
+
+    /// How `text` would be cut down to the embedder's token limit, if at all
+    ///
+    /// `None` for text within the limit and for embedders without a tokenizer.
+    fn truncation(&self, _text: &str) -> Result<Option<Truncation>> {
+        Ok(None)
+    }
+}
+
+impl EmbedderTrait for Box<dyn EmbedderTrait> {
+    fn get_image_embedding(&self, image_path: &str) -> Result<Array1<f64>> {
+        (**self).get_image_embedding(image_path)
+    }
+
+    fn get_text_embedding(&self, text: &str) -> Result<Array1<f64>> {
+        (**self).get_text_embedding(text)
+    }
+
+    fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+        (**self).calculate_batch_similarities(image_path, texts)
+    }
+
+    fn embedding_dim(&self) -> usize {
+        (**self).embedding_dim()
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn model_id(&self) -> String {
+        (**self).model_id()
+    }
+
+    fn token_count(&self, text: &str) -> Result<Option<usize>> {
+        (**self).token_count(text)
+    }
+
+    fn truncation(&self, text: &str) -> Result<Option<Truncation>> {
+        (**self).truncation(text)
+    }
+}
+
+/// Which embedder to score a block with, stored on the block itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EmbedderSpec {
+    /// Deterministic `MockEmbedder` with the given embedding dimension
+    Mock { dim: usize },
+    /// CLIP model, loaded from `model_path` or the default model location
+    Clip {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model_path: Option<String>,
+    },
+}
+
+impl EmbedderSpec {
+    /// Construct the embedder this spec describes
+    pub fn build(&self) -> Result<Box<dyn EmbedderTrait>> {
+        Ok(match self {
+            EmbedderSpec::Mock { dim } => Box::new(MockEmbedder::new(*dim)),
+            EmbedderSpec::Clip { model_path: None } => Box::new(ClipEmbedder::new()?),
+            EmbedderSpec::Clip {
+                model_path: Some(path),
+            } => Box::new(ClipEmbedder::from_path(path)?),
+        })
+    }
 }
 
 /// Mock embedder for testing and development
@@ -62,12 +223,22 @@ pub trait EmbedderTrait: Send + Sync {
 #[derive(Debug, Clone)]
 pub struct MockEmbedder {
     embedding_dim: usize,
+    batch_config: BatchSimilarityConfig,
 }
 
 impl MockEmbedder {
     /// Create a new mock embedder with specified dimensions
     pub fn new(embedding_dim: usize) -> Self {
-        Self { embedding_dim }
+        Self {
+            embedding_dim,
+            batch_config: BatchSimilarityConfig::default(),
+        }
+    }
+
+    /// Override the softmax settings used for batch similarities
+    pub fn with_batch_config(mut self, config: BatchSimilarityConfig) -> Self {
+        self.batch_config = config;
+        self
     }
 
     /// Create a mock embedder with CLIP-like dimensions (512)
@@ -125,6 +296,10 @@ impl EmbedderTrait for MockEmbedder {
     }
 
     fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
         // Get image embedding
         let image_embedding = self.get_image_embedding(image_path)?;
 
@@ -137,25 +312,666 @@ impl EmbedderTrait for MockEmbedder {
         }
 
         // Apply softmax to create competitive rankings (simulating CLIP's behavior)
-        let max_sim = raw_similarities
-            .iter()
-            .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-        let exp_sims: Vec<f64> = raw_similarities
-            .iter()
-            .map(|&x| (x - max_sim).exp())
-            .collect();
-        let sum_exp: f64 = exp_sims.iter().sum();
+        softmax_percentages(&raw_similarities, self.batch_config.temperature)
+    }
 
-        // Convert to percentages
-        let softmax_percentages: Vec<f64> =
-            exp_sims.iter().map(|&x| (x / sum_exp) * 100.0).collect();
+    fn embedding_dim(&self) -> usize {
+        self.embedding_dim
+    }
+
+    fn name(&self) -> &str {
+        "MockEmbedder"
+    }
+
+    fn model_id(&self) -> String {
+        format!(
+            "mock-{}d temperature={}",
+            self.embedding_dim, self.batch_config.temperature
+        )
+    }
+}
+
+/// Settings for ranking a batch of texts against an image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchSimilarityConfig {
+    /// Softmax temperature: below 1.0 sharpens the winner, above 1.0 flattens the ranking
+    pub temperature: f64,
+}
+
+impl BatchSimilarityConfig {
+    /// Batch settings with the given softmax temperature
+    pub fn with_temperature(temperature: f64) -> Result<Self> {
+        if !temperature.is_finite() || temperature <= 0.0 {
+            return Err(EmbeddingError::InvalidTemperature(temperature).into());
+        }
+        Ok(Self { temperature })
+    }
+}
+
+impl Default for BatchSimilarityConfig {
+    fn default() -> Self {
+        Self { temperature: 1.0 }
+    }
+}
+
+/// Softmax over raw similarities, expressed as percentages summing to 100
+///
+/// Each similarity is scaled as `exp((x - max) / temperature)`. An empty input
+/// yields an empty output and a single similarity always gets 100%. Non-finite
+/// inputs are rejected rather than spreading NaN to every score.
+pub(crate) fn softmax_percentages(raw_similarities: &[f64], temperature: f64) -> Result<Vec<f64>> {
+    match raw_similarities {
+        [] => return Ok(Vec::new()),
+        values if values.iter().any(|x| !x.is_finite()) => {
+            return Err(EmbeddingError::NonFiniteSimilarity.into())
+        }
+        [_] => return Ok(vec![100.0]),
+        _ => {}
+    }
+
+    let max_sim = raw_similarities
+        .iter()
+        .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let exp_sims: Vec<f64> = raw_similarities
+        .iter()
+        .map(|&x| ((x - max_sim) / temperature).exp())
+        .collect();
+    let sum_exp: f64 = exp_sims.iter().sum();
+
+    finite_percentages(exp_sims.iter().map(|&x| (x / sum_exp) * 100.0).collect())
+}
+
+/// Reject similarity percentages containing NaN or infinity
+fn finite_percentages(percentages: Vec<f64>) -> Result<Vec<f64>> {
+    if percentages.iter().all(|x| x.is_finite()) {
+        Ok(percentages)
+    } else {
+        Err(EmbeddingError::NonFiniteSimilarity.into())
+    }
+}
+
+/// Magic bytes identifying a saved embeddings file (format version 1)
+const EMBEDDINGS_MAGIC: &[u8; 8] = b"CLIPEMB1";
+
+/// Save text embeddings to a compact binary file
+///
+/// Layout (all integers little-endian): the magic `CLIPEMB1`, a `u64` entry
+/// count, then per entry a `u32` key length, the UTF-8 key, a `u32` dimension
+/// and that many `f64` values. Entries are written sorted by key so the same
+/// map always produces the same file.
+pub fn save_embeddings(path: &Path, embeddings: &HashMap<String, Array1<f64>>) -> Result<()> {
+    let mut keys: Vec<&String> = embeddings.keys().collect();
+    keys.sort();
+
+    let mut writer = std::io::BufWriter::new(fs::File::create(path)?);
+    writer.write_all(EMBEDDINGS_MAGIC)?;
+    writer.write_all(&(keys.len() as u64).to_le_bytes())?;
+
+    for key in keys {
+        let vector = &embeddings[key];
+        writer.write_all(&(key.len() as u32).to_le_bytes())?;
+        writer.write_all(key.as_bytes())?;
+        writer.write_all(&(vector.len() as u32).to_le_bytes())?;
+        for value in vector.iter() {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Load text embeddings written by [`save_embeddings`]
+///
+/// Lengths read from the file are checked against its size before anything
+/// is allocated, so a truncated or corrupt file is an error rather than an abort.
+pub fn load_embeddings(path: &Path) -> Result<HashMap<String, Array1<f64>>> {
+    let file = fs::File::open(path)?;
+    let mut remaining = file.metadata()?.len();
+    let mut reader = std::io::BufReader::new(file);
+
+    // Reserve `needed` bytes of what's left in the file
+    let mut consume = |needed: u64| -> Result<()> {
+        if needed > remaining {
+            return Err(EmbeddingError::TruncatedEmbeddings { needed, remaining }.into());
+        }
+        remaining -= needed;
+        Ok(())
+    };
+
+    let mut magic = [0u8; 8];
+    consume(8).map_err(|_| EmbeddingError::UnsupportedFormat)?;
+    reader.read_exact(&mut magic)?;
+    if &magic != EMBEDDINGS_MAGIC {
+        return Err(EmbeddingError::UnsupportedFormat.into());
+    }
+
+    let mut u64_buf = [0u8; 8];
+    let mut u32_buf = [0u8; 4];
+    consume(8)?;
+    reader.read_exact(&mut u64_buf)?;
+    let count = u64::from_le_bytes(u64_buf);
+
+    let mut embeddings = HashMap::new();
+    for _ in 0..count {
+        consume(4)?;
+        reader.read_exact(&mut u32_buf)?;
+        let key_len = u32::from_le_bytes(u32_buf) as u64;
+        consume(key_len)?;
+        let mut key = vec![0u8; key_len as usize];
+        reader.read_exact(&mut key)?;
+        let key = String::from_utf8(key).map_err(|_| EmbeddingError::UnsupportedFormat)?;
+
+        consume(4)?;
+        reader.read_exact(&mut u32_buf)?;
+        let dim = u32::from_le_bytes(u32_buf) as u64;
+        consume(dim * 8)?;
+        let mut values = Vec::with_capacity(dim as usize);
+        for _ in 0..dim {
+            reader.read_exact(&mut u64_buf)?;
+            values.push(f64::from_le_bytes(u64_buf));
+        }
+
+        embeddings.insert(key, Array1::from_vec(values));
+    }
+
+    Ok(embeddings)
+}
+
+/// Embedder that serves text embeddings from a precomputed map
+///
+/// Texts missing from the map are embedded by the optional fallback embedder,
+/// or rejected with `EmbeddingError::MissingEmbedding` when there is none.
+/// Image embeddings always come from the fallback.
+pub struct PrecomputedEmbedder {
+    embeddings: HashMap<String, Array1<f64>>,
+    embedding_dim: usize,
+    fallback: Option<Box<dyn EmbedderTrait>>,
+    batch_config: BatchSimilarityConfig,
+}
+
+impl PrecomputedEmbedder {
+    /// Create an embedder over precomputed vectors, which must share one dimension
+    pub fn new(embeddings: HashMap<String, Array1<f64>>) -> Result<Self> {
+        let embedding_dim = embeddings.values().next().map_or(0, |v| v.len());
+        if let Some(other) = embeddings.values().find(|v| v.len() != embedding_dim) {
+            return Err(EmbeddingError::InvalidDimensions {
+                a: embedding_dim,
+                b: other.len(),
+            }
+            .into());
+        }
 
-        Ok(softmax_percentages)
+        Ok(Self {
+            embeddings,
+            embedding_dim,
+            fallback: None,
+            batch_config: BatchSimilarityConfig::default(),
+        })
+    }
+
+    /// Load precomputed vectors from a file written by [`save_embeddings`]
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Self::new(load_embeddings(path)?)
+    }
+
+    /// Override the softmax settings used for batch similarities
+    pub fn with_batch_config(mut self, config: BatchSimilarityConfig) -> Self {
+        self.batch_config = config;
+        self
+    }
+
+    /// Embed cache misses and images with another embedder
+    pub fn with_fallback(mut self, fallback: Box<dyn EmbedderTrait>) -> Result<Self> {
+        if !self.embeddings.is_empty() && fallback.embedding_dim() != self.embedding_dim {
+            return Err(EmbeddingError::InvalidDimensions {
+                a: self.embedding_dim,
+                b: fallback.embedding_dim(),
+            }
+            .into());
+        }
+        self.embedding_dim = fallback.embedding_dim();
+        self.fallback = Some(fallback);
+        Ok(self)
+    }
+}
+
+impl EmbedderTrait for PrecomputedEmbedder {
+    fn get_image_embedding(&self, image_path: &str) -> Result<Array1<f64>> {
+        match &self.fallback {
+            Some(fallback) => fallback.get_image_embedding(image_path),
+            None => Err(EmbeddingError::UnsupportedFormat.into()),
+        }
+    }
+
+    fn get_text_embedding(&self, text: &str) -> Result<Array1<f64>> {
+        if let Some(embedding) = self.embeddings.get(text) {
+            return Ok(embedding.clone());
+        }
+
+        match &self.fallback {
+            Some(fallback) => fallback.get_text_embedding(text),
+            None => Err(EmbeddingError::MissingEmbedding(text.to_string()).into()),
+        }
+    }
+
+    fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let image_embedding = self.get_image_embedding(image_path)?;
+
+        let mut raw_similarities = Vec::with_capacity(texts.len());
+        for text in texts {
+            let text_embedding = self.get_text_embedding(text)?;
+            raw_similarities.push(cosine_similarity(&image_embedding, &text_embedding)?);
+        }
+
+        softmax_percentages(&raw_similarities, self.batch_config.temperature)
     }
 
     fn embedding_dim(&self) -> usize {
         self.embedding_dim
     }
+
+    fn name(&self) -> &str {
+        "PrecomputedEmbedder"
+    }
+
+    /// A digest of the precomputed vectors, plus the fallback's own id
+    fn model_id(&self) -> String {
+        let mut texts: Vec<&String> = self.embeddings.keys().collect();
+        texts.sort();
+
+        let mut hasher = Sha256::new();
+        for text in texts {
+            hasher.update(text.as_bytes());
+            for value in self.embeddings[text].iter() {
+                hasher.update(value.to_le_bytes());
+            }
+        }
+
+        let fallback = self
+            .fallback
+            .as_ref()
+            .map_or_else(|| "none".to_string(), |fallback| fallback.model_id());
+        format!(
+            "precomputed-{} temperature={} fallback={}",
+            hex::encode(hasher.finalize()),
+            self.batch_config.temperature,
+            fallback
+        )
+    }
+}
+
+/// Compute the hex-encoded SHA-256 digest of a file
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Side of the square RGB image pixels are hashed at by [`image_content_hash`]
+pub const CONTENT_HASH_SIZE: u32 = 64;
+
+/// Low bits dropped from each channel before hashing, absorbing re-encoding noise
+const CONTENT_HASH_DROPPED_BITS: u32 = 4;
+
+/// Compute the hex-encoded SHA-256 of an image's pixels in a canonical form
+///
+/// The image is decoded, resized to [`CONTENT_HASH_SIZE`] square RGB and each
+/// channel reduced to 16 levels, so the same picture saved as PNG or as a
+/// good-quality JPEG normally hashes the same while different pictures don't.
+/// A channel sitting right on a level boundary can still round either way
+/// after re-encoding. Unlike [`sha256_file`], the file's encoding and metadata
+/// play no part.
+pub fn image_content_hash(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let undecodable = |reason: String| EmbeddingError::UndecodableImage {
+        path: path.display().to_string(),
+        reason,
+    };
+
+    let img = image::ImageReader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| match e {
+            image::ImageError::Unsupported(e) => undecodable(format!("unsupported format ({})", e)),
+            e => undecodable(e.to_string()),
+        })?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_pixels(&img));
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// RGB bytes of `img` at the content hash size, with low bits dropped
+fn canonical_pixels(img: &image::DynamicImage) -> Vec<u8> {
+    img.resize_exact(
+        CONTENT_HASH_SIZE,
+        CONTENT_HASH_SIZE,
+        image::imageops::FilterType::Triangle,
+    )
+    .to_rgb8()
+    .into_raw()
+    .into_iter()
+    .map(|channel| channel >> CONTENT_HASH_DROPPED_BITS)
+    .collect()
+}
+
+/// Expected SHA-256 of a file fetched into the HF Hub cache, if known
+///
+/// The cache stores blobs under their etag, which for LFS files (the model
+/// weights) is the SHA-256 of the content. Small git-tracked files use a git
+/// object id instead and have no digest to check.
+fn expected_sha256_from_cache(path: &Path) -> Option<String> {
+    let blob_path = fs::canonicalize(path).ok()?;
+    let name = blob_path.file_name()?.to_str()?;
+
+    (name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| name.to_ascii_lowercase())
+}
+
+/// Convert an HF Hub error, treating client errors other than timeouts and
+/// rate limits (e.g. 404 for a missing file) as permanent
+fn download_error(error: hf_hub::api::sync::ApiError) -> crate::error::CliptionsError {
+    use hf_hub::api::sync::ApiError;
+
+    let message = error.to_string();
+    let status = match error {
+        ApiError::RequestError(e) => e.into_response().map(|response| response.status()),
+        ApiError::TooManyRetries(inner) => return download_error(*inner),
+        _ => None,
+    };
+    match status {
+        Some(status) if (400..500).contains(&status) && status != 408 && status != 429 => {
+            EmbeddingError::DownloadUnavailable(message).into()
+        }
+        _ => EmbeddingError::DownloadFailed(message).into(),
+    }
+}
+
+/// Copy a fetched model file into place, verifying its checksum
+///
+/// `fetch` is called with the attempt number (starting at 1) and returns the path
+/// of the downloaded file. The copy goes to a `.part` file that is only renamed
+/// to `target` once it is non-empty and its digest matches, so an interrupted or
+/// corrupt download never looks complete. Fetch failures, empty files and
+/// mismatches are retried with backoff up to `retry.attempts` times before the
+/// last error is returned; `DownloadUnavailable` is returned at once.
+fn install_verified_file<F>(target: &Path, retry: &DownloadRetryConfig, mut fetch: F) -> Result<()>
+where
+    F: FnMut(u32) -> Result<PathBuf>,
+{
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let partial_path = target.with_file_name(format!("{}.part", file_name));
+
+    let mut last_error = None;
+    for attempt in 1..=retry.attempts {
+        let result = fetch(attempt).and_then(|source| {
+            let copied = fs::copy(&source, &partial_path).map_err(|e| {
+                EmbeddingError::DownloadFailed(format!("Failed to copy {}: {}", file_name, e))
+            })?;
+            if copied == 0 {
+                return Err(
+                    EmbeddingError::DownloadFailed(format!("{} is empty", file_name)).into(),
+                );
+            }
+
+            if let Some(expected) = expected_sha256_from_cache(&source) {
+                let actual = sha256_file(&partial_path)?;
+                if actual != expected {
+                    return Err(EmbeddingError::ChecksumMismatch {
+                        file: file_name.clone(),
+                        expected,
+                        actual,
+                    }
+                    .into());
+                }
+            }
+
+            fs::rename(&partial_path, target).map_err(|e| {
+                EmbeddingError::DownloadFailed(format!("Failed to move {}: {}", file_name, e))
+                    .into()
+            })
+        });
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                eprintln!(
+                    "Attempt {}/{} for {} failed: {}",
+                    attempt, retry.attempts, file_name, e
+                );
+                let _ = fs::remove_file(&partial_path);
+                if matches!(
+                    e,
+                    crate::error::CliptionsError::Embedding(EmbeddingError::DownloadUnavailable(_))
+                ) {
+                    return Err(e);
+                }
+                last_error = Some(e);
+                if attempt < retry.attempts {
+                    std::thread::sleep(retry.backoff(attempt));
+                }
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| EmbeddingError::DownloadFailed(file_name).into()))
+}
+
+/// How a frame is fitted to the model's square input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CropMode {
+    /// Scale to cover the square, then crop the overflow (`resize_to_fill`)
+    Fill,
+    /// Scale each axis independently, distorting non-square frames
+    Stretch,
+    /// Resize the shorter side to the target size, then crop the center square
+    CenterCrop,
+}
+
+/// Image preprocessing applied before CLIP image embedding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImagePreprocessConfig {
+    /// Side length of the square model input in pixels
+    pub size: u32,
+    /// Resampling filter used when resizing
+    pub filter: image::imageops::FilterType,
+    /// How non-square frames are fitted to the input
+    pub crop_mode: CropMode,
+}
+
+impl ImagePreprocessConfig {
+    /// Default preprocessing at the given input size
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            size: size as u32,
+            ..Self::default()
+        }
+    }
+
+    /// Resize and crop an image to a `size` x `size` square
+    pub fn apply(&self, img: image::DynamicImage) -> image::DynamicImage {
+        let size = self.size;
+        match self.crop_mode {
+            CropMode::Fill => img.resize_to_fill(size, size, self.filter),
+            CropMode::Stretch => img.resize_exact(size, size, self.filter),
+            CropMode::CenterCrop => {
+                let (width, height) = (img.width(), img.height());
+                let scale = size as f64 / width.min(height) as f64;
+                let new_width = ((width as f64 * scale).round() as u32).max(size);
+                let new_height = ((height as f64 * scale).round() as u32).max(size);
+                let resized = img.resize_exact(new_width, new_height, self.filter);
+                resized.crop_imm((new_width - size) / 2, (new_height - size) / 2, size, size)
+            }
+        }
+    }
+}
+
+impl Default for ImagePreprocessConfig {
+    fn default() -> Self {
+        Self {
+            size: 224,
+            filter: image::imageops::FilterType::Triangle,
+            crop_mode: CropMode::Fill,
+        }
+    }
+}
+
+/// Supported CLIP model architectures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipVariant {
+    /// openai/clip-vit-base-patch32
+    VitBasePatch32,
+    /// openai/clip-vit-base-patch16
+    VitBasePatch16,
+    /// openai/clip-vit-large-patch14
+    VitLargePatch14,
+}
+
+impl ClipVariant {
+    /// Candle configuration matching this variant's weights
+    pub fn config(&self) -> ClipConfig {
+        match self {
+            ClipVariant::VitBasePatch32 => ClipConfig::vit_base_patch32(),
+            ClipVariant::VitBasePatch16 => {
+                let mut config = ClipConfig::vit_base_patch32();
+                config.vision_config.patch_size = 16;
+                config
+            }
+            ClipVariant::VitLargePatch14 => ClipConfig {
+                text_config: ClipTextConfig {
+                    vocab_size: 49408,
+                    embed_dim: 768,
+                    activation: Activation::QuickGelu,
+                    intermediate_size: 3072,
+                    max_position_embeddings: 77,
+                    pad_with: None,
+                    num_hidden_layers: 12,
+                    num_attention_heads: 12,
+                    projection_dim: 768,
+                },
+                vision_config: ClipVisionConfig {
+                    embed_dim: 1024,
+                    activation: Activation::QuickGelu,
+                    intermediate_size: 4096,
+                    num_hidden_layers: 24,
+                    num_attention_heads: 16,
+                    projection_dim: 768,
+                    num_channels: 3,
+                    image_size: 224,
+                    patch_size: 14,
+                },
+                logit_scale_init_value: 2.6592,
+                image_size: 224,
+            },
+        }
+    }
+
+    /// Dimension of the projected image/text embeddings
+    pub fn embedding_dim(&self) -> usize {
+        self.config().text_config.projection_dim
+    }
+
+    /// Identify the variant from a Hugging Face `config.json`
+    ///
+    /// Uses the vision tower's `hidden_size` and `patch_size`, which default to
+    /// the ViT-B/32 values when omitted as in the `transformers` library.
+    pub fn from_config_json(config_path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(config_path)?;
+        let json: serde_json::Value = serde_json::from_str(&content)?;
+
+        let vision = &json["vision_config"];
+        let hidden_size = vision["hidden_size"].as_u64().unwrap_or(768);
+        let patch_size = vision["patch_size"].as_u64().unwrap_or(32);
+
+        match (hidden_size, patch_size) {
+            (768, 32) => Ok(ClipVariant::VitBasePatch32),
+            (768, 16) => Ok(ClipVariant::VitBasePatch16),
+            (1024, 14) => Ok(ClipVariant::VitLargePatch14),
+            _ => Err(EmbeddingError::UnsupportedFormat.into()),
+        }
+    }
+
+    /// Pick the variant for a model directory, falling back to ViT-B/32 without a `config.json`
+    pub fn detect(model_path: &Path) -> Result<Self> {
+        let config_path = model_path.join("config.json");
+        if config_path.exists() {
+            Self::from_config_json(&config_path)
+        } else {
+            Ok(ClipVariant::VitBasePatch32)
+        }
+    }
+}
+
+/// What a CLIP embedder does with text longer than its token limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenLimitPolicy {
+    /// Keep the leading tokens and the end-of-text token
+    #[default]
+    Truncate,
+    /// Fail with `EmbeddingError::TextTooLong`
+    Reject,
+}
+
+impl TokenLimitPolicy {
+    /// Fit token ids to at most `max` tokens
+    ///
+    /// Truncation keeps the final token, since CLIP pools the text embedding
+    /// at the end-of-text token.
+    pub fn apply(&self, mut ids: Vec<u32>, max: usize) -> Result<Vec<u32>> {
+        if ids.len() <= max {
+            return Ok(ids);
+        }
+        match self {
+            TokenLimitPolicy::Truncate => {
+                let end = ids.pop();
+                ids.truncate(max.saturating_sub(1));
+                ids.extend(end);
+                Ok(ids)
+            }
+            TokenLimitPolicy::Reject => Err(EmbeddingError::TextTooLong {
+                tokens: ids.len(),
+                max,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Text over a CLIP embedder's token limit that was scored on its beginning only
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Truncation {
+    /// Tokens the text encodes to
+    pub tokens: usize,
+    /// Tokens kept
+    pub max: usize,
+}
+
+impl std::fmt::Display for Truncation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "truncated from {} to {} tokens; only its beginning is scored",
+            self.tokens, self.max
+        )
+    }
 }
 
 /// Native Rust CLIP embedder using Candle ML framework
@@ -167,40 +983,55 @@ pub struct ClipEmbedder {
     tokenizer: Tokenizer,
     device: Device,
     embedding_dim: usize,
+    preprocess: ImagePreprocessConfig,
+    batch_config: BatchSimilarityConfig,
+    max_tokens: usize,
+    token_limit_policy: TokenLimitPolicy,
+    /// Architecture and weights file the model was loaded from
+    model_source: String,
 }
 
 impl ClipEmbedder {
     /// Create a new CLIP embedder with default model (ViT-B/32)
-    /// Automatically downloads model files if not found locally
+    ///
+    /// Looks in [`default_model_dir`] and then, for models downloaded by older
+    /// versions, in `models/` under the working directory. If neither has the
+    /// model it is downloaded into the default directory.
     pub fn new() -> Result<Self> {
-        // Try to find local CLIP model files in common locations
-        let possible_paths = [
-            "models/clip-vit-base-patch32",
-            "clip-vit-base-patch32",
-            "models/openai-clip-vit-base-patch32",
-            "openai-clip-vit-base-patch32",
-        ];
-
-        for path in &possible_paths {
-            if Self::check_model_files_exist(path) {
-                return Self::from_path(path);
+        let model_dir = default_model_dir();
+        if find_model_in(&model_dir).is_none() {
+            if let Some(path) = find_model_in(Path::new("models")) {
+                return Self::from_path(&path.to_string_lossy());
             }
         }
+        Self::new_in(&model_dir)
+    }
+
+    /// Create a CLIP embedder from the model in `dir`, downloading it there if missing
+    ///
+    /// `dir` may hold the model files itself or a `clip-vit-base-patch32`
+    /// subdirectory; downloads go to that subdirectory.
+    pub fn new_in(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        if let Some(path) = find_model_in(dir) {
+            return Self::from_path(&path.to_string_lossy());
+        }
 
-        // No local models found, try to download
-        let download_path = "models/clip-vit-base-patch32";
+        let download_path = dir.join(DEFAULT_MODEL_SUBDIR);
+        let download_path = download_path.to_string_lossy();
         println!(
-            "CLIP ViT-B/32 model not found locally. Downloading to {}...",
+            "CLIP ViT-B/32 model not found in {}. Downloading to {}...",
+            dir.display(),
             download_path
         );
 
-        if let Err(e) = Self::download_model(download_path) {
+        if let Err(e) = Self::download_model(&download_path, &DownloadRetryConfig::default()) {
             eprintln!("Failed to download model: {}", e);
             return Err(EmbeddingError::ModelLoadFailed.into());
         }
 
         println!("Model downloaded successfully!");
-        Self::from_path(download_path)
+        Self::from_path(&download_path)
     }
 
     /// Check if all required model files exist in the given path
@@ -226,7 +1057,11 @@ impl ClipEmbedder {
     }
 
     /// Download CLIP model files from Hugging Face using HF Hub API
-    fn download_model(download_path: &str) -> Result<()> {
+    ///
+    /// Each file is verified against the SHA-256 digest Hugging Face reports for
+    /// it and re-downloaded with backoff on a mismatch or a transient failure,
+    /// see [`install_verified_file`].
+    pub fn download_model(download_path: &str, retry: &DownloadRetryConfig) -> Result<()> {
         // Create directory if it doesn't exist
         fs::create_dir_all(download_path).map_err(|_| EmbeddingError::ModelLoadFailed)?;
 
@@ -242,12 +1077,18 @@ impl ClipEmbedder {
 
         for filename in &files_to_download {
             println!("Downloading {}...", filename);
-            let file_path = api
-                .get(filename)
-                .map_err(|_| EmbeddingError::ModelLoadFailed)?;
-
             let target_path = Path::new(download_path).join(filename);
-            fs::copy(&file_path, &target_path).map_err(|_| EmbeddingError::ModelLoadFailed)?;
+
+            install_verified_file(&target_path, retry, |attempt| {
+                // The first attempt may reuse the HF cache; retries force a fresh download
+                let fetched = if attempt == 1 {
+                    api.get(filename)
+                } else {
+                    println!("Retrying download of {} (attempt {})...", filename, attempt);
+                    api.download(filename)
+                };
+                fetched.map_err(download_error)
+            })?;
 
             println!("Downloaded {} to {}", filename, target_path.display());
         }
@@ -261,11 +1102,24 @@ impl ClipEmbedder {
     }
 
     /// Load CLIP model from a local path
+    ///
+    /// The architecture is read from the directory's `config.json` when present,
+    /// otherwise ViT-B/32 is assumed.
     pub fn from_path(model_path: &str) -> Result<Self> {
         if !Path::new(model_path).exists() {
             return Err(EmbeddingError::ModelLoadFailed.into());
         }
 
+        let variant = ClipVariant::detect(Path::new(model_path))?;
+        Self::from_path_with_variant(model_path, variant)
+    }
+
+    /// Load CLIP model from a local path using an explicit architecture
+    pub fn from_path_with_variant(model_path: &str, variant: ClipVariant) -> Result<Self> {
+        if !Path::new(model_path).exists() {
+            return Err(EmbeddingError::ModelLoadFailed.into());
+        }
+
         // Use CPU device for now (can be extended to support GPU)
         let device = Device::Cpu;
 
@@ -287,8 +1141,8 @@ impl ClipEmbedder {
         let tokenizer =
             Tokenizer::from_file(tokenizer_path).map_err(|_| EmbeddingError::TokenizationFailed)?;
 
-        // Use the same config as the working version - don't load from JSON
-        let config = ClipConfig::vit_base_patch32();
+        let config = variant.config();
+        let model_source = format!("{:?} {}", variant, weights_path.display());
 
         // Load model weights
         let vb = if weights_path.to_string_lossy().ends_with(".safetensors") {
@@ -304,16 +1158,68 @@ impl ClipEmbedder {
         // Create the model
         let model = ClipModel::new(vb, &config).map_err(|_| EmbeddingError::ModelLoadFailed)?;
 
-        let embedding_dim = config.text_config.embed_dim;
+        let embedding_dim = config.text_config.projection_dim;
 
         Ok(Self {
             model,
             tokenizer,
             device,
             embedding_dim,
+            preprocess: ImagePreprocessConfig::with_size(config.image_size),
+            batch_config: BatchSimilarityConfig::default(),
+            max_tokens: config.text_config.max_position_embeddings,
+            token_limit_policy: TokenLimitPolicy::default(),
+            model_source,
         })
     }
 
+    /// Override how images are resized and cropped before embedding
+    pub fn with_preprocess(mut self, config: ImagePreprocessConfig) -> Self {
+        self.preprocess = config;
+        self
+    }
+
+    /// Override the softmax settings used for batch similarities
+    pub fn with_batch_config(mut self, config: BatchSimilarityConfig) -> Self {
+        self.batch_config = config;
+        self
+    }
+
+    /// Choose whether over-length text is truncated or rejected
+    pub fn with_token_limit_policy(mut self, policy: TokenLimitPolicy) -> Self {
+        self.token_limit_policy = policy;
+        self
+    }
+
+    /// Maximum tokens the text encoder accepts, including start and end tokens
+    pub fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    /// Number of tokens `text` encodes to, including start and end tokens
+    pub fn count_tokens(&self, text: &str) -> Result<usize> {
+        Ok(self.encode_text(text)?.len())
+    }
+
+    /// Tokenize text and fit it to the token limit
+    fn encode_text(&self, text: &str) -> Result<Vec<u32>> {
+        let encoding = self
+            .tokenizer
+            .encode(text, true)
+            .map_err(|_| EmbeddingError::TokenizationFailed)?;
+        Ok(encoding.get_ids().to_vec())
+    }
+
+    /// Apply the token limit policy, returning the truncation if the text was cut
+    fn fit_tokens(&self, ids: Vec<u32>) -> Result<(Vec<u32>, Option<Truncation>)> {
+        let truncation = (ids.len() > self.max_tokens).then_some(Truncation {
+            tokens: ids.len(),
+            max: self.max_tokens,
+        });
+        let ids = self.token_limit_policy.apply(ids, self.max_tokens)?;
+        Ok((ids, truncation))
+    }
+
     /// Process image and return embedding tensor
     fn process_image(&self, image_path: &str) -> Result<Tensor> {
         // Use exact same approach as working candle_clip_test.rs
@@ -322,12 +1228,8 @@ impl ClipEmbedder {
             .decode()
             .map_err(|_| EmbeddingError::ImageProcessingFailed)?;
 
-        let (height, width) = (224, 224);
-        let img = img.resize_to_fill(
-            width as u32,
-            height as u32,
-            image::imageops::FilterType::Triangle,
-        );
+        let img = self.preprocess.apply(img);
+        let (height, width) = (self.preprocess.size as usize, self.preprocess.size as usize);
         let img = img.to_rgb8();
         let img = img.into_raw();
 
@@ -354,13 +1256,7 @@ impl ClipEmbedder {
     /// Process text and return token tensor
     fn process_text(&self, text: &str) -> Result<Tensor> {
         // Tokenize text
-        let encoding = self
-            .tokenizer
-            .encode(text, true)
-            .map_err(|_| EmbeddingError::TokenizationFailed)?;
-
-        let tokens = encoding.get_ids();
-        let token_ids: Vec<u32> = tokens.iter().map(|&x| x as u32).collect();
+        let (token_ids, _) = self.fit_tokens(self.encode_text(text)?)?;
 
         // Convert to tensor and add batch dimension
         let tensor = Tensor::from_slice(&token_ids, (1, token_ids.len()), &self.device)
@@ -408,11 +1304,7 @@ impl ClipEmbedder {
 
         let mut tokens = vec![];
         for text in texts {
-            let encoding = self
-                .tokenizer
-                .encode(text.as_str(), true)
-                .map_err(|_| EmbeddingError::TokenizationFailed)?;
-            tokens.push(encoding.get_ids().to_vec());
+            tokens.push(self.fit_tokens(self.encode_text(text)?)?.0);
         }
 
         // Find max length and pad all sequences
@@ -457,7 +1349,12 @@ impl Default for ClipEmbedder {
             model,
             tokenizer,
             device,
-            embedding_dim: config.text_config.embed_dim,
+            embedding_dim: config.text_config.projection_dim,
+            preprocess: ImagePreprocessConfig::with_size(config.image_size),
+            batch_config: BatchSimilarityConfig::default(),
+            max_tokens: config.text_config.max_position_embeddings,
+            token_limit_policy: TokenLimitPolicy::default(),
+            model_source: format!("{:?} (zero weights)", ClipVariant::VitBasePatch32),
         }
     }
 }
@@ -512,21 +1409,24 @@ impl EmbedderTrait for ClipEmbedder {
             return Ok(Vec::new());
         }
 
+        // Tokenize all texts in batch, before the costlier image work
+        let text_tensor = self.tokenize_batch(texts)?;
+
         // Process image
         let image_tensor = self.process_image(image_path)?;
 
-        // Tokenize all texts in batch
-        let text_tensor = self.tokenize_batch(texts)?;
-
         // Use the CLIP model's forward pass (the correct way!)
         let (_logits_per_text, logits_per_image) = self
             .model
             .forward(&image_tensor, &text_tensor)
             .map_err(|_| EmbeddingError::ImageProcessingFailed)?;
 
-        // Apply softmax to get competitive probabilities
+        // Scale logits by the temperature, then softmax to get competitive probabilities
+        let scaled_logits = logits_per_image
+            .affine(1.0 / self.batch_config.temperature, 0.0)
+            .map_err(|_| EmbeddingError::ImageProcessingFailed)?;
         let softmax_result =
-            softmax(&logits_per_image, 1).map_err(|_| EmbeddingError::ImageProcessingFailed)?;
+            softmax(&scaled_logits, 1).map_err(|_| EmbeddingError::ImageProcessingFailed)?;
 
         // Convert to percentages
         let probabilities = softmax_result
@@ -538,12 +1438,31 @@ impl EmbedderTrait for ClipEmbedder {
         // Convert to f64 percentages
         let percentages: Vec<f64> = probabilities.iter().map(|&x| (x as f64) * 100.0).collect();
 
-        Ok(percentages)
+        finite_percentages(percentages)
     }
 
     fn embedding_dim(&self) -> usize {
         self.embedding_dim
     }
+
+    fn name(&self) -> &str {
+        "ClipEmbedder"
+    }
+
+    fn model_id(&self) -> String {
+        format!(
+            "{} temperature={}",
+            self.model_source, self.batch_config.temperature
+        )
+    }
+
+    fn token_count(&self, text: &str) -> Result<Option<usize>> {
+        self.count_tokens(text).map(Some)
+    }
+
+    fn truncation(&self, text: &str) -> Result<Option<Truncation>> {
+        Ok(self.fit_tokens(self.encode_text(text)?)?.1)
+    }
 }
 
 /// Calculate cosine similarity between two embedding vectors
@@ -556,7 +1475,11 @@ impl EmbedderTrait for ClipEmbedder {
 /// Cosine similarity score between -1 and 1
 pub fn cosine_similarity(a: &Array1<f64>, b: &Array1<f64>) -> Result<f64> {
     if a.len() != b.len() {
-        return Err(EmbeddingError::InvalidDimensions.into());
+        return Err(EmbeddingError::InvalidDimensions {
+            a: a.len(),
+            b: b.len(),
+        }
+        .into());
     }
 
     // For normalized vectors, cosine similarity is just the dot product
@@ -634,11 +1557,43 @@ mod tests {
         assert!(matches!(
             result,
             Err(crate::error::CliptionsError::Embedding(
-                EmbeddingError::InvalidDimensions
+                EmbeddingError::InvalidDimensions { a: 128, b: 256 }
             ))
         ));
     }
 
+    #[test]
+    fn test_precomputed_dimension_errors_carry_sizes() {
+        let mut embeddings = HashMap::new();
+        embeddings.insert("a".to_string(), Array1::zeros(512));
+        embeddings.insert("b".to_string(), Array1::zeros(768));
+        let error = PrecomputedEmbedder::new(embeddings).err().unwrap();
+        match error {
+            crate::error::CliptionsError::Embedding(EmbeddingError::InvalidDimensions { a, b }) => {
+                let mut sizes = [a, b];
+                sizes.sort();
+                assert_eq!(sizes, [512, 768]);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let mut embeddings = HashMap::new();
+        embeddings.insert("a".to_string(), Array1::zeros(512));
+        let error = PrecomputedEmbedder::new(embeddings)
+            .unwrap()
+            .with_fallback(Box::new(MockEmbedder::new(768)))
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            crate::error::CliptionsError::Embedding(EmbeddingError::InvalidDimensions {
+                a: 512,
+                b: 768
+            })
+        ));
+        assert!(error.to_string().contains("512 vs 768"));
+    }
+
     #[test]
     fn test_image_vs_text_embeddings() {
         let embedder = MockEmbedder::new(128);
@@ -700,4 +1655,610 @@ mod tests {
         let image_result = embedder.get_image_embedding("test.jpg");
         assert!(matches!(image_result, Err(_)));
     }
+
+    /// Whitespace word tokenizer with CLIP's start and end tokens
+    fn word_tokenizer() -> Tokenizer {
+        let special = |id: &str| serde_json::json!({ "SpecialToken": { "id": id, "type_id": 0 } });
+        let sequence = |id: &str| serde_json::json!({ "Sequence": { "id": id, "type_id": 0 } });
+        let special_token = |token: &str, id: u32| serde_json::json!({ "id": token, "ids": [id], "tokens": [token] });
+        let json = serde_json::json!({
+            "version": "1.0",
+            "truncation": null,
+            "padding": null,
+            "added_tokens": [],
+            "normalizer": null,
+            "pre_tokenizer": { "type": "Whitespace" },
+            "post_processor": {
+                "type": "TemplateProcessing",
+                "single": [special("<|startoftext|>"), sequence("A"), special("<|endoftext|>")],
+                "pair": [special("<|startoftext|>"), sequence("A"), sequence("B"), special("<|endoftext|>")],
+                "special_tokens": {
+                    "<|startoftext|>": special_token("<|startoftext|>", 0),
+                    "<|endoftext|>": special_token("<|endoftext|>", 1)
+                }
+            },
+            "decoder": null,
+            "model": {
+                "type": "WordLevel",
+                "vocab": { "<|startoftext|>": 0, "<|endoftext|>": 1, "fox": 2, "[UNK]": 3 },
+                "unk_token": "[UNK]"
+            }
+        });
+        json.to_string().parse().unwrap()
+    }
+
+    #[test]
+    fn test_clip_token_limit() {
+        let embedder = ClipEmbedder {
+            tokenizer: word_tokenizer(),
+            ..ClipEmbedder::default()
+        };
+        let long_text = "fox ".repeat(100);
+
+        assert_eq!(embedder.max_tokens(), 77);
+        assert_eq!(embedder.count_tokens("a red fox").unwrap(), 5);
+        assert_eq!(embedder.count_tokens(&long_text).unwrap(), 102);
+        assert_eq!(embedder.token_count(&long_text).unwrap(), Some(102));
+        assert_eq!(embedder.truncation("a red fox").unwrap(), None);
+        assert_eq!(
+            embedder.truncation(&long_text).unwrap(),
+            Some(Truncation {
+                tokens: 102,
+                max: 77
+            })
+        );
+
+        // Truncation keeps the end-of-text token CLIP pools on
+        let tensor = embedder.process_text(&long_text).unwrap();
+        assert_eq!(tensor.dims(), &[1, 77]);
+        let ids: Vec<u32> = tensor.squeeze(0).unwrap().to_vec1().unwrap();
+        assert_eq!((ids[0], ids[75], ids[76]), (0, 2, 1));
+
+        let embedder = embedder.with_token_limit_policy(TokenLimitPolicy::Reject);
+        assert!(embedder.process_text("a red fox").is_ok());
+        for result in [
+            embedder.get_text_embedding(&long_text).map(|_| ()),
+            embedder
+                .calculate_batch_similarities("test.jpg", &["fox".to_string(), long_text.clone()])
+                .map(|_| ()),
+        ] {
+            assert!(matches!(
+                result,
+                Err(crate::error::CliptionsError::Embedding(
+                    EmbeddingError::TextTooLong {
+                        tokens: 102,
+                        max: 77
+                    }
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_mock_batch_similarities_small_inputs() {
+        let embedder = MockEmbedder::new(128);
+        let texts = |items: &[&str]| items.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+
+        assert!(embedder
+            .calculate_batch_similarities("test.jpg", &[])
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            embedder
+                .calculate_batch_similarities("test.jpg", &texts(&["only guess"]))
+                .unwrap(),
+            vec![100.0]
+        );
+
+        let pair = embedder
+            .calculate_batch_similarities("test.jpg", &texts(&["first", "second"]))
+            .unwrap();
+        assert_eq!(pair.len(), 2);
+        assert!(pair.iter().all(|p| p.is_finite()));
+        assert!((pair.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_clip_batch_similarities_small_inputs() {
+        // Without model weights only the empty case can succeed, and it must not touch the model
+        let embedder = ClipEmbedder::default();
+        assert!(embedder
+            .calculate_batch_similarities("test.jpg", &[])
+            .unwrap()
+            .is_empty());
+
+        for texts in [
+            vec!["only guess".to_string()],
+            vec!["first".to_string(), "second".to_string()],
+        ] {
+            assert!(embedder
+                .calculate_batch_similarities("test.jpg", &texts)
+                .is_err());
+        }
+
+        // The CLIP output check rejects NaN rather than passing it through
+        assert_eq!(finite_percentages(vec![100.0]).unwrap(), vec![100.0]);
+        assert!(matches!(
+            finite_percentages(vec![f64::NAN, 50.0]),
+            Err(crate::error::CliptionsError::Embedding(
+                EmbeddingError::NonFiniteSimilarity
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_softmax_percentages_edge_cases() {
+        assert!(softmax_percentages(&[], 1.0).unwrap().is_empty());
+        assert_eq!(softmax_percentages(&[0.3], 1.0).unwrap(), vec![100.0]);
+
+        let pair = softmax_percentages(&[0.5, 0.5], 1.0).unwrap();
+        assert_eq!(pair, vec![50.0, 50.0]);
+
+        assert!(softmax_percentages(&[0.5, f64::NAN], 1.0).is_err());
+        assert!(softmax_percentages(&[f64::INFINITY], 1.0).is_err());
+    }
+
+    #[test]
+    fn test_softmax_temperature_sharpens_and_flattens() {
+        let raw = [0.3, 0.2, 0.1];
+        let top_share = |temperature| softmax_percentages(&raw, temperature).unwrap()[0];
+
+        assert!(top_share(0.05) > top_share(1.0));
+        assert!(top_share(1.0) > top_share(10.0));
+
+        let embedder = MockEmbedder::new(128);
+        let texts: Vec<String> = ["a", "b", "c"].iter().map(|t| t.to_string()).collect();
+        let default_scores = embedder
+            .calculate_batch_similarities("test.jpg", &texts)
+            .unwrap();
+        let sharp_scores = MockEmbedder::new(128)
+            .with_batch_config(BatchSimilarityConfig::with_temperature(0.01).unwrap())
+            .calculate_batch_similarities("test.jpg", &texts)
+            .unwrap();
+        let max = |scores: &[f64]| scores.iter().cloned().fold(f64::MIN, f64::max);
+        assert!(max(&sharp_scores) > max(&default_scores));
+
+        assert!(BatchSimilarityConfig::with_temperature(0.0).is_err());
+        assert!(BatchSimilarityConfig::with_temperature(f64::NAN).is_err());
+        assert_eq!(BatchSimilarityConfig::default().temperature, 1.0);
+    }
+
+    fn no_delay(attempts: u32) -> DownloadRetryConfig {
+        DownloadRetryConfig {
+            attempts,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_install_verified_file_checksum_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = b"fake model weights";
+        let digest = hex::encode(Sha256::digest(content));
+        let source = dir.path().join(&digest);
+        fs::write(&source, content).unwrap();
+        let target = dir.path().join("pytorch_model.bin");
+
+        install_verified_file(&target, &no_delay(MODEL_DOWNLOAD_ATTEMPTS), |_| {
+            Ok(source.clone())
+        })
+        .unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), content);
+        assert!(!dir.path().join("pytorch_model.bin.part").exists());
+    }
+
+    #[test]
+    fn test_install_verified_file_checksum_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        // Named after the digest of different content, like a truncated cache blob
+        let source = dir
+            .path()
+            .join(hex::encode(Sha256::digest(b"complete weights")));
+        fs::write(&source, b"truncated").unwrap();
+        let target = dir.path().join("pytorch_model.bin");
+
+        let mut attempts = 0;
+        let result = install_verified_file(&target, &no_delay(MODEL_DOWNLOAD_ATTEMPTS), |_| {
+            attempts += 1;
+            Ok(source.clone())
+        });
+
+        assert_eq!(attempts, MODEL_DOWNLOAD_ATTEMPTS);
+        assert!(matches!(
+            result,
+            Err(crate::error::CliptionsError::Embedding(
+                EmbeddingError::ChecksumMismatch { .. }
+            ))
+        ));
+        assert!(!target.exists());
+        assert!(!dir.path().join("pytorch_model.bin.part").exists());
+    }
+
+    #[test]
+    fn test_install_verified_file_recovers_on_retry() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = b"fake model weights";
+        let good = dir.path().join(hex::encode(Sha256::digest(content)));
+        fs::write(&good, content).unwrap();
+        let target = dir.path().join("pytorch_model.bin");
+
+        install_verified_file(&target, &no_delay(MODEL_DOWNLOAD_ATTEMPTS), |attempt| {
+            if attempt == 1 {
+                Err(EmbeddingError::DownloadFailed("connection reset".to_string()).into())
+            } else {
+                Ok(good.clone())
+            }
+        })
+        .unwrap();
+
+        assert_eq!(fs::read(&target).unwrap(), content);
+    }
+
+    #[test]
+    fn test_install_verified_file_retry_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("pytorch_model.bin");
+
+        // Transient failures are retried up to the configured number of attempts
+        let mut attempts = 0;
+        let result = install_verified_file(&target, &no_delay(5), |_| {
+            attempts += 1;
+            Err(EmbeddingError::DownloadFailed("connection reset".to_string()).into())
+        });
+        assert_eq!(attempts, 5);
+        assert!(result.is_err());
+
+        // An empty download counts as a failed attempt
+        let empty = dir.path().join("empty");
+        fs::write(&empty, b"").unwrap();
+        let mut attempts = 0;
+        let result = install_verified_file(&target, &no_delay(2), |_| {
+            attempts += 1;
+            Ok(empty.clone())
+        });
+        assert_eq!(attempts, 2);
+        assert!(matches!(
+            result,
+            Err(crate::error::CliptionsError::Embedding(EmbeddingError::DownloadFailed(msg)))
+                if msg.contains("empty")
+        ));
+
+        // A permanent error is not retried
+        let mut attempts = 0;
+        let result = install_verified_file(&target, &no_delay(5), |_| {
+            attempts += 1;
+            Err(EmbeddingError::DownloadUnavailable("404 Not Found".to_string()).into())
+        });
+        assert_eq!(attempts, 1);
+        assert!(matches!(
+            result,
+            Err(crate::error::CliptionsError::Embedding(
+                EmbeddingError::DownloadUnavailable(_)
+            ))
+        ));
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_model_dir_honors_env_var() {
+        let home = Some(PathBuf::from("/home/validator"));
+        assert_eq!(
+            model_dir_from(Some("/srv/models".into()), home.clone()),
+            PathBuf::from("/srv/models")
+        );
+        // Unset or empty falls back to the data directory under home
+        assert_eq!(
+            model_dir_from(None, home.clone()),
+            PathBuf::from("/home/validator/.cliptions/models")
+        );
+        assert_eq!(
+            model_dir_from(Some("".into()), home),
+            PathBuf::from("/home/validator/.cliptions/models")
+        );
+        assert_eq!(model_dir_from(None, None), PathBuf::from("models"));
+    }
+
+    #[test]
+    fn test_find_model_in_explicit_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(find_model_in(dir.path()), None);
+
+        // A model in the default subdirectory
+        let model = dir.path().join("clip-vit-base-patch32");
+        fs::create_dir_all(&model).unwrap();
+        fs::write(model.join("tokenizer.json"), "{}").unwrap();
+        assert_eq!(find_model_in(dir.path()), None, "weights are still missing");
+        fs::write(model.join("pytorch_model.bin"), b"weights").unwrap();
+        assert_eq!(find_model_in(dir.path()), Some(model.clone()));
+
+        // A directory holding the model files itself
+        assert_eq!(find_model_in(&model), Some(model));
+    }
+
+    #[test]
+    fn test_download_backoff_grows_with_jitter() {
+        let retry = DownloadRetryConfig {
+            attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+        };
+        for _ in 0..20 {
+            let first = retry.backoff(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let second = retry.backoff(2);
+            assert!(second >= Duration::from_millis(100) && second <= Duration::from_millis(200));
+            // Capped at max_delay
+            assert!(retry.backoff(10) <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn test_clip_variant_detect_from_config_json() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // No config.json falls back to ViT-B/32
+        let variant = ClipVariant::detect(dir.path()).unwrap();
+        assert_eq!(variant, ClipVariant::VitBasePatch32);
+        assert_eq!(variant.embedding_dim(), 512);
+
+        fs::write(
+            dir.path().join("config.json"),
+            r#"{
+                "projection_dim": 768,
+                "text_config": { "hidden_size": 768, "projection_dim": 768 },
+                "vision_config": { "hidden_size": 1024, "patch_size": 14, "image_size": 224 }
+            }"#,
+        )
+        .unwrap();
+
+        let variant = ClipVariant::detect(dir.path()).unwrap();
+        assert_eq!(variant, ClipVariant::VitLargePatch14);
+        assert_eq!(variant.embedding_dim(), 768);
+        assert_eq!(variant.config().vision_config.embed_dim, 1024);
+    }
+
+    #[test]
+    fn test_clip_variant_detect_base_patch16_and_unsupported() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+
+        fs::write(&config_path, r#"{ "vision_config": { "patch_size": 16 } }"#).unwrap();
+        let variant = ClipVariant::detect(dir.path()).unwrap();
+        assert_eq!(variant, ClipVariant::VitBasePatch16);
+        assert_eq!(variant.config().vision_config.patch_size, 16);
+
+        fs::write(
+            &config_path,
+            r#"{ "vision_config": { "hidden_size": 1280, "patch_size": 14 } }"#,
+        )
+        .unwrap();
+        assert!(ClipVariant::detect(dir.path()).is_err());
+    }
+
+    fn write_wide_test_image(dir: &Path) -> PathBuf {
+        // 16:9 frame with a distinct left edge so the crop can be checked
+        let img = image::RgbImage::from_fn(320, 180, |x, _| {
+            if x < 40 {
+                image::Rgb([255, 0, 0])
+            } else {
+                image::Rgb([0, 0, 255])
+            }
+        });
+        let path = dir.join("wide_frame.png");
+        img.save(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_center_crop_preprocess_produces_square_tensor() {
+        let dir = tempfile::tempdir().unwrap();
+        let image_path = write_wide_test_image(dir.path());
+
+        let embedder = ClipEmbedder::default().with_preprocess(ImagePreprocessConfig {
+            crop_mode: CropMode::CenterCrop,
+            ..ImagePreprocessConfig::default()
+        });
+        let tensor = embedder
+            .process_image(image_path.to_str().unwrap())
+            .unwrap();
+        assert_eq!(tensor.dims(), &[1, 3, 224, 224]);
+    }
+
+    #[test]
+    fn test_preprocess_crop_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let img = image::open(write_wide_test_image(dir.path())).unwrap();
+
+        let config = ImagePreprocessConfig {
+            size: 90,
+            filter: image::imageops::FilterType::Nearest,
+            crop_mode: CropMode::CenterCrop,
+        };
+        let cropped = config.apply(img.clone()).to_rgb8();
+        assert_eq!(cropped.dimensions(), (90, 90));
+        // The red left edge is cropped away from the centered square
+        assert_eq!(cropped.get_pixel(0, 45), &image::Rgb([0, 0, 255]));
+
+        let stretched = ImagePreprocessConfig {
+            crop_mode: CropMode::Stretch,
+            ..config
+        }
+        .apply(img)
+        .to_rgb8();
+        assert_eq!(stretched.dimensions(), (90, 90));
+        // Stretching keeps the full width, so the red edge survives
+        assert_eq!(stretched.get_pixel(0, 45), &image::Rgb([255, 0, 0]));
+
+        assert_eq!(ImagePreprocessConfig::default().crop_mode, CropMode::Fill);
+    }
+
+    fn sample_embeddings() -> HashMap<String, Array1<f64>> {
+        let embedder = MockEmbedder::new(16);
+        ["a red car", "a blue boat", "ünïcödé 🎯"]
+            .iter()
+            .map(|text| (text.to_string(), embedder.get_text_embedding(text).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_save_load_embeddings_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("phrases.emb");
+        let mut embeddings = sample_embeddings();
+        embeddings.insert(
+            "edge values".to_string(),
+            Array1::from_vec(vec![f64::MIN_POSITIVE, -0.0, 1.0 / 3.0, f64::MAX]),
+        );
+
+        save_embeddings(&path, &embeddings).unwrap();
+        let loaded = load_embeddings(&path).unwrap();
+
+        assert_eq!(loaded.len(), embeddings.len());
+        for (text, vector) in &embeddings {
+            let restored = &loaded[text];
+            assert_eq!(restored.len(), vector.len());
+            for (a, b) in vector.iter().zip(restored.iter()) {
+                assert_eq!(a.to_bits(), b.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_embeddings_rejects_bad_magic() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bogus.emb");
+        fs::write(&path, b"NOTEMBEDxxxxxxxx").unwrap();
+
+        assert!(load_embeddings(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_embeddings_rejects_oversized_lengths() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("phrases.emb");
+        save_embeddings(&path, &sample_embeddings()).unwrap();
+        let valid = fs::read(&path).unwrap();
+
+        // First key length claims ~4 GiB
+        let mut huge_key = valid.clone();
+        huge_key[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, &huge_key).unwrap();
+        let err = load_embeddings(&path).unwrap_err();
+        assert!(err.to_string().contains("truncated"), "{}", err);
+
+        // First vector dimension claims ~32 GiB
+        let key_len = u32::from_le_bytes(valid[16..20].try_into().unwrap()) as usize;
+        let dim_at = 20 + key_len;
+        let mut huge_dim = valid.clone();
+        huge_dim[dim_at..dim_at + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, &huge_dim).unwrap();
+        assert!(load_embeddings(&path).is_err());
+
+        // Cut off mid-vector
+        fs::write(&path, &valid[..valid.len() - 3]).unwrap();
+        assert!(load_embeddings(&path).is_err());
+    }
+
+    #[test]
+    fn test_precomputed_embedder_serves_and_misses() {
+        let embeddings = sample_embeddings();
+        let embedder = PrecomputedEmbedder::new(embeddings.clone()).unwrap();
+
+        assert_eq!(embedder.embedding_dim(), 16);
+        assert_eq!(
+            embedder.get_text_embedding("a red car").unwrap(),
+            embeddings["a red car"]
+        );
+        assert!(matches!(
+            embedder.get_text_embedding("unknown phrase"),
+            Err(crate::error::CliptionsError::Embedding(
+                EmbeddingError::MissingEmbedding(_)
+            ))
+        ));
+        assert!(embedder.get_image_embedding("frame.jpg").is_err());
+    }
+
+    #[test]
+    fn test_precomputed_embedder_with_fallback() {
+        let fallback = MockEmbedder::new(16);
+        let embedder = PrecomputedEmbedder::new(sample_embeddings())
+            .unwrap()
+            .with_fallback(Box::new(fallback.clone()))
+            .unwrap();
+
+        assert_eq!(
+            embedder.get_text_embedding("unknown phrase").unwrap(),
+            fallback.get_text_embedding("unknown phrase").unwrap()
+        );
+
+        let texts = vec!["a red car".to_string(), "unknown phrase".to_string()];
+        let scores = embedder
+            .calculate_batch_similarities("frame.jpg", &texts)
+            .unwrap();
+        assert_eq!(scores.len(), 2);
+        assert!((scores.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+
+        // A fallback with a different dimension is rejected
+        assert!(PrecomputedEmbedder::new(sample_embeddings())
+            .unwrap()
+            .with_fallback(Box::new(MockEmbedder::new(8)))
+            .is_err());
+    }
+
+    #[test]
+    fn test_image_content_hash_ignores_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+        // Flat quadrants on JPEG block boundaries, in channel values whose
+        // blends at the resized edges fall mid-way between hash levels
+        let source = image::RgbImage::from_fn(128, 128, |x, y| match (x < 64, y < 64) {
+            (true, true) => image::Rgb([200, 72, 72]),
+            (false, true) => image::Rgb([72, 200, 72]),
+            (true, false) => image::Rgb([72, 72, 200]),
+            (false, false) => image::Rgb([200, 200, 72]),
+        });
+        let png_path = dir.path().join("frame.png");
+        source.save(&png_path).unwrap();
+
+        // Re-save the PNG as a JPEG, as an archive might
+        let jpeg_path = dir.path().join("frame.jpg");
+        let decoded = image::open(&png_path).unwrap().to_rgb8();
+        let mut jpeg = fs::File::create(&jpeg_path).unwrap();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 95)
+            .encode_image(&decoded)
+            .unwrap();
+        assert_ne!(
+            sha256_file(&png_path).unwrap(),
+            sha256_file(&jpeg_path).unwrap()
+        );
+        assert_eq!(
+            image_content_hash(&png_path).unwrap(),
+            image_content_hash(&jpeg_path).unwrap()
+        );
+
+        let other_path = dir.path().join("other.png");
+        image::imageops::flip_horizontal(&source)
+            .save(&other_path)
+            .unwrap();
+        assert_ne!(
+            image_content_hash(&png_path).unwrap(),
+            image_content_hash(&other_path).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_image_content_hash_rejects_non_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.png");
+        fs::write(&path, "not an image").unwrap();
+
+        let error = image_content_hash(&path).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::CliptionsError::Embedding(EmbeddingError::UndecodableImage { .. })
+        ));
+        assert!(error.to_string().contains("notes.png"));
+    }
 }
@@ -35,12 +35,18 @@ pub enum CliptionsError {
     #[error("Browser integration error: {0}")]
     BrowserIntegrationError(String),
 
+    #[error("Shutdown requested before the transition started")]
+    ShutdownRequested,
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
     #[error("Parse error: {0}")]
     Parse(#[from] chrono::ParseError),
 
@@ -75,6 +81,12 @@ pub enum CommitmentError {
 
     #[error("Missing commitment data")]
     MissingData,
+
+    #[error("Salt source produced the same salt twice in one batch")]
+    DuplicateSalt,
+
+    #[error("Commitment store failed its integrity check (corrupted or modified)")]
+    ChecksumMismatch,
 }
 
 /// Scoring-related errors
@@ -94,6 +106,16 @@ pub enum ScoringError {
 
     #[error("Operation not supported for this strategy")]
     UnsupportedOperation,
+
+    #[error("No precomputed similarity for guess: {guess}")]
+    MissingSimilarity { guess: String },
+
+    #[error(
+        "Embedding sizes don't match ({a} vs {b}): a precomputed embedding cache was likely \
+         built with a different CLIP model than the one loaded; rebuild the cache or load the \
+         matching model"
+    )]
+    EmbeddingModelMismatch { a: usize, b: usize },
 }
 
 /// Embedding-related errors
@@ -102,8 +124,8 @@ pub enum EmbeddingError {
     #[error("Failed to load embedding model")]
     ModelLoadFailed,
 
-    #[error("Invalid embedding dimensions")]
-    InvalidDimensions,
+    #[error("Invalid embedding dimensions: {a} vs {b}")]
+    InvalidDimensions { a: usize, b: usize },
 
     #[error("Text tokenization failed")]
     TokenizationFailed,
@@ -116,6 +138,38 @@ pub enum EmbeddingError {
 
     #[error("Unsupported format")]
     UnsupportedFormat,
+
+    #[error("Checksum mismatch for {file}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        file: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Model download failed: {0}")]
+    DownloadFailed(String),
+
+    /// The server refused the file outright (e.g. 404); retrying won't help
+    #[error("Model file unavailable: {0}")]
+    DownloadUnavailable(String),
+
+    #[error("No precomputed embedding for text: {0}")]
+    MissingEmbedding(String),
+
+    #[error("Embeddings file is truncated: {needed} bytes needed, {remaining} remaining")]
+    TruncatedEmbeddings { needed: u64, remaining: u64 },
+
+    #[error("Similarity calculation produced non-finite scores")]
+    NonFiniteSimilarity,
+
+    #[error("Softmax temperature must be positive and finite, got {0}")]
+    InvalidTemperature(f64),
+
+    #[error("Text is {tokens} tokens, over the model's limit of {max}")]
+    TextTooLong { tokens: usize, max: usize },
+
+    #[error("Cannot decode image {path}: {reason}")]
+    UndecodableImage { path: String, reason: String },
 }
 
 /// Block processing errors
@@ -133,8 +187,62 @@ pub enum BlockError {
     #[error("Block data file not found: {path}")]
     DataFileNotFound { path: String },
 
+    #[error("Malformed block data file {path} at line {line}, column {column}: {message}")]
+    MalformedDataFile {
+        path: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    #[error("Malformed block {block_num} in {path} at line {line}, column {column}: {message}")]
+    MalformedBlock {
+        path: String,
+        block_num: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
     #[error("Block already processed")]
     AlreadyProcessed,
+
+    #[error("Block {block_num} already exists with {field} {existing}, not {requested}")]
+    ParamsMismatch {
+        block_num: String,
+        field: String,
+        existing: String,
+        requested: String,
+    },
+
+    #[error("Commitments for block {block_num} have not been closed")]
+    CommitmentsNotClosed { block_num: String },
+
+    #[error("No target frame has been captured for block {block_num}")]
+    TargetFrameNotCaptured { block_num: String },
+
+    #[error("Reveals for block {block_num} are already open (announced in tweet {tweet_id})")]
+    RevealsAlreadyOpen { block_num: String, tweet_id: String },
+
+    #[error(
+        "Block {block_num} is locked by process {pid} since {acquired_at}; another run may be \
+         processing it"
+    )]
+    Locked {
+        block_num: String,
+        pid: u32,
+        acquired_at: String,
+    },
+
+    #[error(
+        "Block {block_num} was scored with scoring version {recorded}, not {current}; \
+         re-processing it would change its historical results"
+    )]
+    ScoringVersionMismatch {
+        block_num: String,
+        recorded: String,
+        current: String,
+    },
 }
 
 /// Validation errors
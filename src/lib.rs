@@ -21,15 +21,23 @@
 // Core library modules
 // pub mod browser_integration;  // TODO: File missing, needs to be created or removed
 pub mod actions;
+pub mod block_lock;
+pub mod block_store;
+pub mod clock;
 pub mod commitment;
+pub mod commitment_store;
 pub mod config;
+pub mod data_file;
 pub mod data_models;
 pub mod embedder;
 pub mod error;
 pub mod models;
 pub mod payout;
+pub mod report_html;
 pub mod block_processor;
 pub mod scoring;
+pub mod scoring_cache;
+pub mod scoring_version;
 pub mod social;
 pub mod twitter_utils;
 pub mod types;
@@ -37,23 +45,50 @@ pub mod types;
 // New async block engine
 pub mod block_engine;
 
+// Read-only HTTP status server (conditional compilation)
+#[cfg(feature = "status-server")]
+pub mod status_server;
+
 // Python bindings module (conditional compilation)
 #[cfg(feature = "python")]
 pub mod python_bridge;
 
 // Re-export commonly used types
 // pub use browser_integration::{BrowserIntegration, Commitment, CommitmentCollectionResult};  // TODO: File missing
-pub use commitment::{CommitmentGenerator, CommitmentVerifier};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use commitment::{
+    normalize_guess, CommitmentGenerator, CommitmentVerification, CommitmentVerifier, FailReason,
+    HashAlgo, SaltSource,
+};
+pub use commitment_store::{CommitmentStore, StoredCommitment};
 pub use config::{CliptionsConfig, ConfigManager, CostTracker, OpenAIConfig, SpendingStatus};
-pub use embedder::{EmbedderTrait, MockEmbedder};
+pub use embedder::{
+    BatchSimilarityConfig, DownloadRetryConfig, EmbedderSpec, EmbedderTrait, MockEmbedder,
+    PrecomputedEmbedder, TokenLimitPolicy, Truncation,
+};
 pub use error::{CliptionsError, Result};
-pub use payout::{PayoutCalculator, PayoutConfig, PayoutInfo};
-pub use block_processor::BlockProcessor;
-pub use scoring::{ClipBatchStrategy, ScoreValidator, ScoringStrategy};
+pub use payout::{
+    reconcile, PayoutCalculator, PayoutConfig, PayoutCurve, PayoutInfo, ReconciliationMismatch,
+    ReconciliationReport, DEFAULT_CURRENCY_LABEL, PAYOUT_TOLERANCE, TAO_DECIMALS,
+};
+pub use block_store::{BlockStore, JsonBlockStore, MemoryBlockStore};
+pub use report_html::render_html;
+pub use block_processor::{
+    BlockListQuery, BlockParams, BlockProcessor, BlockSort, ParticipantReport, ProgressReporter,
+    RoundReport,
+};
+pub use scoring::{
+    BlendedStrategy, ClipBatchStrategy, GuessDiagnostic, PrecomputedScoringStrategy, Rankable,
+    ScoreValidator, ScoringStrategy, SimilarityMatrix, StrategyParams, StrategyRegistry,
+};
+pub use scoring_cache::ScoringCache;
+pub use scoring_version::{ScoringVersion, ScoringVersionRegistry};
 pub use social::{
-    AnnouncementData, AnnouncementFormatter, HashtagManager, SocialWorkflow, TweetId, UrlParser,
+    build_reply_tree, AnnouncementData, AnnouncementFormatter, AnnouncementMetadata,
+    ConversationRoot, FittedAnnouncement, HashtagManager, PayoutAnnouncementData, ReplyTree,
+    SocialWorkflow, TweetId, TweetTask, UrlParser,
 };
-pub use types::{Guess, Participant, BlockData, ScoringResult};
+pub use types::{Guess, Participant, BlockData, BlockSummary, ScoringResult, SubmissionPhase, SubmissionTiming};
 
 // Re-export Python module when feature is enabled
 #[cfg(feature = "python")]
@@ -8,11 +8,22 @@ use clap::{Parser, Subcommand};
 use cliptions_core::error::Result;
 use cliptions_core::actions::new_block::{NewBlockArgs, run as new_block_run};
 use cliptions_core::actions::generate_commitment::{GenerateCommitmentArgs, run as generate_commitment_run};
+use cliptions_core::actions::check_reveal::{CheckRevealArgs, run as check_reveal_run};
 use cliptions_core::actions::collect_commitments::{CollectCommitmentsArgs, run as collect_commitments_run};
 use cliptions_core::actions::post_target_frame::{PostTargetFrameArgs, run as post_target_frame_run};
 use cliptions_core::actions::collect_reveals::{CollectRevealsArgs, run as collect_reveals_run};
 use cliptions_core::actions::verify_commitments::{VerifyCommitmentsArgs, run as verify_commitments_run};
 use cliptions_core::actions::calculate_scores::{CalculateScoresArgs, run as calculate_scores_run};
+use cliptions_core::actions::preview_announcements::{PreviewAnnouncementsArgs, run as preview_announcements_run};
+use cliptions_core::actions::doctor::{DoctorArgs, run as doctor_run};
+use cliptions_core::actions::reconstruct::{ReconstructArgs, run as reconstruct_run};
+use cliptions_core::actions::archive_blocks::{ArchiveBlocksArgs, run as archive_blocks_run};
+use cliptions_core::actions::simulate_block::{SimulateBlockArgs, run as simulate_block_run};
+use cliptions_core::actions::round_report::{RoundReportArgs, run as round_report_run};
+use cliptions_core::actions::list_blocks::{ListBlocksArgs, run as list_blocks_run};
+use cliptions_core::actions::diff_blocks::{DiffBlocksArgs, run as diff_blocks_run};
+#[cfg(feature = "status-server")]
+use cliptions_core::actions::serve_status::{ServeStatusArgs, run as serve_status_run};
 
 #[derive(Parser)]
 #[command(name = "cliptions")]
@@ -24,11 +35,21 @@ Unified CLI tool for Cliptions prediction market operations.
 This tool provides all functionality through subcommands:
 - new-block: Open a new block and post commitment announcement
 - generate-commitment: Generate cryptographic commitments for predictions
+- check-reveal: Check a reveal against your stored commitment before posting it
 - collect-commitments: Collect commitment replies from a specific tweet
 - post-target-frame: Post target frame image as reply to commitment tweet
 - collect-reveals: Collect reveal replies from target frame tweet
 - verify-commitments: Verify commitments against reveals for a block
 - calculate-scores: Calculate scores and payouts for verified participants
+- preview-announcements: Print every block announcement without posting
+- doctor: Check config, credentials, CLIP model and data directories
+- reconstruct: Rebuild blocks.json from the validator's announcement tweets
+- archive-blocks: Move finished blocks out of blocks.json into quarterly archives
+- simulate-block: Run a whole block lifecycle offline from a scenario file
+- round-report: Write a block's full audit report as JSON or HTML
+- list-blocks: List blocks, sorted and paged
+- diff-blocks: Compare two blocks.json files
+- serve-status: Serve block summaries and reports over HTTP (status-server feature)
 
 Use 'cliptions <SUBCOMMAND> --help' for detailed help on each command.
 ")]
@@ -47,6 +68,10 @@ enum Commands {
     #[command(name = "generate-commitment")]
     GenerateCommitment(GenerateCommitmentArgs),
     
+    /// Check that a reveal will verify against your stored commitment
+    #[command(name = "check-reveal")]
+    CheckReveal(CheckRevealArgs),
+    
     /// Collect commitment replies from a specific tweet
     #[command(name = "collect-commitments")]
     CollectCommitments(CollectCommitmentsArgs),
@@ -66,6 +91,43 @@ enum Commands {
     /// Calculate scores and payouts for verified participants
     #[command(name = "calculate-scores")]
     CalculateScores(CalculateScoresArgs),
+
+    /// Print every block announcement and its length without posting
+    #[command(name = "preview-announcements")]
+    PreviewAnnouncements(PreviewAnnouncementsArgs),
+
+    /// Check config, Twitter credentials, CLIP model and data directories
+    #[command(name = "doctor")]
+    Doctor(DoctorArgs),
+
+    /// Rebuild blocks.json from the validator's announcement tweets
+    #[command(name = "reconstruct")]
+    Reconstruct(ReconstructArgs),
+
+    /// Move finished blocks older than a date into quarterly archive files
+    #[command(name = "archive-blocks")]
+    ArchiveBlocks(ArchiveBlocksArgs),
+
+    /// Run a block from announcement to payouts offline, printing every tweet
+    #[command(name = "simulate-block")]
+    SimulateBlock(SimulateBlockArgs),
+
+    /// Write an audit report (JSON or HTML) with a block's commitments, reveals, scores and payouts
+    #[command(name = "round-report")]
+    RoundReport(RoundReportArgs),
+
+    /// List block summaries with status filtering, sorting and paging
+    #[command(name = "list-blocks")]
+    ListBlocks(ListBlocksArgs),
+
+    /// Show blocks, participants, statuses and payouts that differ between two blocks.json files
+    #[command(name = "diff-blocks")]
+    DiffBlocks(DiffBlocksArgs),
+
+    /// Serve block summaries and round reports over read-only HTTP for dashboards
+    #[cfg(feature = "status-server")]
+    #[command(name = "serve-status")]
+    ServeStatus(ServeStatusArgs),
 }
 
 fn main() -> Result<()> {
@@ -76,6 +138,7 @@ fn main() -> Result<()> {
             tokio::runtime::Runtime::new()?.block_on(new_block_run(args))
         }
         Commands::GenerateCommitment(args) => generate_commitment_run(args),
+        Commands::CheckReveal(args) => check_reveal_run(args),
         Commands::CollectCommitments(args) => {
             tokio::runtime::Runtime::new()?.block_on(collect_commitments_run(args))
         }
@@ -89,5 +152,23 @@ fn main() -> Result<()> {
             tokio::runtime::Runtime::new()?.block_on(verify_commitments_run(args))
         }
         Commands::CalculateScores(args) => calculate_scores_run(args),
+        Commands::PreviewAnnouncements(args) => preview_announcements_run(args),
+        Commands::Doctor(args) => {
+            tokio::runtime::Runtime::new()?.block_on(doctor_run(args))
+        }
+        Commands::Reconstruct(args) => {
+            tokio::runtime::Runtime::new()?.block_on(reconstruct_run(args))
+        }
+        Commands::ArchiveBlocks(args) => archive_blocks_run(args),
+        Commands::SimulateBlock(args) => {
+            tokio::runtime::Runtime::new()?.block_on(simulate_block_run(args))
+        }
+        Commands::RoundReport(args) => round_report_run(args),
+        Commands::ListBlocks(args) => list_blocks_run(args),
+        Commands::DiffBlocks(args) => diff_blocks_run(args),
+        #[cfg(feature = "status-server")]
+        Commands::ServeStatus(args) => {
+            tokio::runtime::Runtime::new()?.block_on(serve_status_run(args))
+        }
     }
 } 
\ No newline at end of file
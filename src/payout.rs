@@ -1,5 +1,6 @@
 use crate::error::{CliptionsError, Result};
-use crate::types::Participant;
+use crate::scoring::rank_by_score;
+use crate::types::{Participant, ScoringResult};
 use serde::{Deserialize, Serialize};
 
 /// Represents payout information for a participant
@@ -10,6 +11,132 @@ pub struct PayoutInfo {
     pub score: f64,
     pub rank: usize,
     pub payout: f64,
+    /// Id of the scoring version the payout was calculated under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring_version: Option<String>,
+}
+
+/// How the prize pool is split across ranked players
+///
+/// Every curve assigns each position a weight; tied players share the average
+/// weight of the positions they occupy, and the pool is divided in proportion
+/// to the weights.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum PayoutCurve {
+    /// Payout proportional to each player's score
+    ///
+    /// Negative scores count as zero; if every score is zero the pool is split evenly.
+    ProportionalToScore,
+    /// With N players, 1st place gets weight N, 2nd N-1, ..., last 1
+    #[default]
+    LinearByRank,
+    /// Each place gets `1 / base` of the place above it (`base` must be greater than 1)
+    Exponential { base: f64 },
+    /// 1st place takes the whole pool
+    WinnerTakeAll,
+    /// The top `n` places split the pool evenly; everyone else gets nothing
+    TopN { n: usize },
+}
+
+impl PayoutCurve {
+    /// Check the curve's parameters
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            PayoutCurve::Exponential { base } if !(base.is_finite() && base > 1.0) => {
+                Err(CliptionsError::ValidationError(format!(
+                    "Exponential payout base must be greater than 1, got {}",
+                    base
+                )))
+            }
+            PayoutCurve::TopN { n: 0 } => Err(CliptionsError::ValidationError(
+                "TopN payout must pay at least one place".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Weight for each position in `ranked_results`, before ties are averaged
+    fn position_weights(&self, ranked_results: &[(String, f64)]) -> Vec<f64> {
+        let total_players = ranked_results.len();
+        (0..total_players)
+            .map(|position| match *self {
+                PayoutCurve::ProportionalToScore => ranked_results[position].1.max(0.0),
+                PayoutCurve::LinearByRank => (total_players - position) as f64,
+                PayoutCurve::Exponential { base } => base.powi(-(position as i32)),
+                PayoutCurve::WinnerTakeAll => {
+                    if position == 0 {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+                PayoutCurve::TopN { n } => {
+                    if position < n {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for PayoutCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PayoutCurve::ProportionalToScore => write!(f, "proportional"),
+            PayoutCurve::LinearByRank => write!(f, "linear"),
+            PayoutCurve::Exponential { base } => write!(f, "exponential:{}", base),
+            PayoutCurve::WinnerTakeAll => write!(f, "winner-take-all"),
+            PayoutCurve::TopN { n } => write!(f, "top-n:{}", n),
+        }
+    }
+}
+
+impl std::str::FromStr for PayoutCurve {
+    type Err = String;
+
+    /// Parse `proportional`, `linear`, `exponential:BASE`, `winner-take-all` or `top-n:N`
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let lowered = s.to_lowercase();
+        let (name, param) = match lowered.split_once(':') {
+            Some((name, param)) => (name, Some(param)),
+            None => (lowered.as_str(), None),
+        };
+        let curve = match (name, param) {
+            ("proportional", None) => PayoutCurve::ProportionalToScore,
+            ("linear", None) => PayoutCurve::LinearByRank,
+            ("winner-take-all", None) => PayoutCurve::WinnerTakeAll,
+            ("exponential", Some(base)) => PayoutCurve::Exponential {
+                base: base
+                    .parse()
+                    .map_err(|_| format!("Invalid exponential base: {}", base))?,
+            },
+            ("top-n", Some(n)) => PayoutCurve::TopN {
+                n: n.parse().map_err(|_| format!("Invalid number of places: {}", n))?,
+            },
+            _ => {
+                return Err(format!(
+                    "Invalid payout curve: {}. Must be 'proportional', 'linear', \
+                     'exponential:BASE', 'winner-take-all' or 'top-n:N'",
+                    s
+                ))
+            }
+        };
+        curve.validate().map_err(|e| e.to_string())?;
+        Ok(curve)
+    }
+}
+
+/// Number of decimal places TAO amounts are denominated in
+pub const TAO_DECIMALS: u32 = 9;
+
+/// Currency payouts are labelled with unless configured otherwise
+pub const DEFAULT_CURRENCY_LABEL: &str = "TAO";
+
+fn default_currency_label() -> String {
+    DEFAULT_CURRENCY_LABEL.to_string()
 }
 
 /// Configuration for payout calculations
@@ -17,7 +144,32 @@ pub struct PayoutInfo {
 pub struct PayoutConfig {
     pub prize_pool: f64,
     pub platform_fee_percentage: f64,
+    /// Percentage of the prize pool kept by the validator before distribution
+    #[serde(default)]
+    pub validator_fee_percent: f64,
     pub minimum_players: usize,
+    /// How the pool is split by rank and score
+    #[serde(default)]
+    pub curve: PayoutCurve,
+    /// Round payouts to this many decimal places (e.g. `TAO_DECIMALS`); `None` leaves them unrounded
+    #[serde(default)]
+    pub decimals: Option<u32>,
+    /// Currency label shown next to amounts, e.g. "TAO"
+    #[serde(default = "default_currency_label")]
+    pub currency_label: String,
+    /// Smallest prize pool worth distributing; smaller pools are rejected
+    #[serde(default)]
+    pub min_prize_pool: f64,
+}
+
+impl PayoutConfig {
+    /// Format an amount with the configured precision and currency label
+    ///
+    /// Uses `decimals` places, or `TAO_DECIMALS` when payouts aren't rounded.
+    pub fn format_amount(&self, amount: f64) -> String {
+        let decimals = self.decimals.unwrap_or(TAO_DECIMALS) as usize;
+        format!("{:.*} {}", decimals, amount, self.currency_label)
+    }
 }
 
 impl Default for PayoutConfig {
@@ -25,7 +177,12 @@ impl Default for PayoutConfig {
         Self {
             prize_pool: 1000.0,
             platform_fee_percentage: 0.0, // No platform fee by default
+            validator_fee_percent: 0.0,
             minimum_players: 2,
+            curve: PayoutCurve::default(),
+            decimals: None,
+            currency_label: default_currency_label(),
+            min_prize_pool: 0.0,
         }
     }
 }
@@ -34,6 +191,7 @@ impl Default for PayoutConfig {
 #[derive(Debug)]
 pub struct PayoutCalculator {
     config: PayoutConfig,
+    scoring_version: Option<String>,
 }
 
 impl PayoutCalculator {
@@ -41,26 +199,41 @@ impl PayoutCalculator {
     pub fn new() -> Self {
         Self {
             config: PayoutConfig::default(),
+            scoring_version: None,
         }
     }
 
     /// Create a new payout calculator with custom configuration
     pub fn with_config(config: PayoutConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            scoring_version: None,
+        }
+    }
+
+    /// Record the scoring version on every payout this calculator produces
+    pub fn with_scoring_version(mut self, scoring_version: impl Into<String>) -> Self {
+        self.scoring_version = Some(scoring_version.into());
+        self
+    }
+
+    /// Get the scoring version recorded on payouts, if any
+    pub fn scoring_version(&self) -> Option<&str> {
+        self.scoring_version.as_deref()
     }
 
-    /// Calculate payouts based on rankings using position-based scoring
+    /// Calculate payouts for results ranked from best to worst
     ///
-    /// The payout calculation uses a position-based scoring system where:
-    /// - Scores are based only on position (1st, 2nd, etc), not similarity values
-    /// - Equal similarity scores get equal payouts (ties split the combined payout)
-    /// - Scores sum to 1.0 to distribute full prize pool
-    /// - Higher positions get proportionally higher scores
+    /// The split follows the configured `PayoutCurve`:
+    /// - Equal similarity scores get equal payouts (ties share the weight of the positions they span)
+    /// - Payouts sum to the available pool; any floating-point remainder goes to the top group
+    /// - Higher positions never get less than lower ones
     pub fn calculate_payouts(&self, ranked_results: &[(String, f64)]) -> Result<Vec<f64>> {
         if ranked_results.is_empty() {
             return Ok(vec![]);
         }
 
+        self.check_prize_pool()?;
         let total_players = ranked_results.len();
         if total_players < self.config.minimum_players {
             return Err(CliptionsError::ValidationError(format!(
@@ -68,12 +241,10 @@ impl PayoutCalculator {
                 self.config.minimum_players, total_players
             )));
         }
+        self.config.curve.validate()?;
 
-        // Calculate available prize pool after platform fee
-        let available_pool =
-            self.config.prize_pool * (1.0 - self.config.platform_fee_percentage / 100.0);
-
-        let denominator: usize = (1..=total_players).sum();
+        // Calculate available prize pool after platform and validator fees
+        let available_pool = self.calculate_available_pool();
 
         // Group positions by similarity score
         let mut groups = Vec::new();
@@ -90,7 +261,7 @@ impl PayoutCalculator {
                     current_group.push(i);
                 }
                 Some(_) => {
-                    groups.push((current_group.clone(), current_similarity.unwrap()));
+                    groups.push(current_group.clone());
                     current_group = vec![i];
                     current_similarity = Some(*similarity);
                 }
@@ -98,31 +269,42 @@ impl PayoutCalculator {
         }
 
         if !current_group.is_empty() {
-            groups.push((current_group, current_similarity.unwrap()));
+            groups.push(current_group);
         }
 
-        // Calculate payouts
-        let mut payouts = vec![0.0; total_players];
-        let mut position = 0;
-
-        for (group, _similarity) in groups {
-            let group_size = group.len();
-            let group_points: usize = (0..group_size)
-                .map(|i| total_players - (position + i))
-                .sum();
+        // Tied positions share the average of their weights
+        let mut weights = self.config.curve.position_weights(ranked_results);
+        for group in &groups {
+            let group_weight =
+                group.iter().map(|&idx| weights[idx]).sum::<f64>() / group.len() as f64;
+            for &idx in group {
+                weights[idx] = group_weight;
+            }
+        }
 
-            let points_per_position = group_points as f64 / group_size as f64;
-            let score = points_per_position / denominator as f64;
-            let payout = score * available_pool;
+        // Only possible for ProportionalToScore when no one scored above zero
+        let total_weight: f64 = weights.iter().sum();
+        if total_weight <= 0.0 {
+            weights = vec![1.0; total_players];
+        }
+        let total_weight: f64 = weights.iter().sum();
 
-            for &idx in &group {
-                payouts[idx] = payout;
-            }
+        let mut payouts: Vec<f64> = weights
+            .iter()
+            .map(|weight| weight / total_weight * available_pool)
+            .collect();
 
-            position += group_size;
+        // Hand any rounding remainder to the top group so the pool is fully paid out
+        let remainder = available_pool - payouts.iter().sum::<f64>();
+        let top_group = &groups[0];
+        for &idx in top_group {
+            payouts[idx] += remainder / top_group.len() as f64;
         }
 
-        Ok(payouts)
+        match self.config.decimals {
+            Some(decimals) => round_payouts(&payouts, available_pool, decimals),
+            None => Ok(payouts),
+        }
     }
 
     /// Process complete payout calculation including ranking and validation
@@ -141,40 +323,34 @@ impl PayoutCalculator {
             return Ok(vec![]);
         }
 
-        // Create ranked results from participants
-        let mut ranked_results = Vec::new();
-        for (participant, score) in &valid_participants {
-            ranked_results.push((participant.guess.text.clone(), *score));
-        }
-
-        // Sort by score (highest first)
-        ranked_results.sort_by(|a, b| {
-            b.1.partial_cmp(&a.1).unwrap_or_else(|| {
-                panic!("CRITICAL: Invalid scores detected (NaN/Inf) for participants '{}' (score: {}) and '{}' (score: {}). Cannot calculate payouts reliably.", 
-                       a.0, a.1, b.0, b.1);
-            })
-        });
+        // Rank participants by score (highest first)
+        let mut ranked: Vec<(&Participant, f64)> = valid_participants
+            .iter()
+            .map(|(participant, score)| (participant, *score))
+            .collect();
+        rank_by_score(&mut ranked);
 
         // Calculate payouts
+        let ranked_results: Vec<(String, f64)> = ranked
+            .iter()
+            .map(|(participant, score)| (participant.guess.text.clone(), *score))
+            .collect();
         let payouts = self.calculate_payouts(&ranked_results)?;
 
         // Create payout info
-        let mut payout_infos = Vec::new();
-        for (i, ((guess, score), payout)) in ranked_results.iter().zip(payouts.iter()).enumerate() {
-            // Find the original participant
-            if let Some((participant, _)) = valid_participants
-                .iter()
-                .find(|(p, _)| p.guess.text == *guess)
-            {
-                payout_infos.push(PayoutInfo {
-                    username: participant.username.clone(),
-                    guess: guess.clone(),
-                    score: *score,
-                    rank: i + 1,
-                    payout: *payout,
-                });
-            }
-        }
+        let payout_infos = ranked
+            .into_iter()
+            .zip(payouts)
+            .enumerate()
+            .map(|(i, ((participant, score), payout))| PayoutInfo {
+                username: participant.username.clone(),
+                guess: participant.guess.text.clone(),
+                score,
+                rank: i + 1,
+                payout,
+                scoring_version: self.scoring_version.clone(),
+            })
+            .collect();
 
         Ok(payout_infos)
     }
@@ -194,12 +370,37 @@ impl PayoutCalculator {
             ));
         }
 
+        if self.config.validator_fee_percent < 0.0 || self.config.validator_fee_percent >= 100.0 {
+            return Err(CliptionsError::ValidationError(
+                "Validator fee percentage must be between 0 and 100".to_string(),
+            ));
+        }
+
+        if self.config.platform_fee_percentage + self.config.validator_fee_percent >= 100.0 {
+            return Err(CliptionsError::ValidationError(
+                "Platform and validator fees must leave part of the prize pool".to_string(),
+            ));
+        }
+
         if self.config.minimum_players == 0 {
             return Err(CliptionsError::ValidationError(
                 "Minimum players must be at least 1".to_string(),
             ));
         }
 
+        self.check_prize_pool()?;
+        self.config.curve.validate()
+    }
+
+    /// Reject prize pools below the configured minimum
+    pub fn check_prize_pool(&self) -> Result<()> {
+        if self.config.prize_pool < self.config.min_prize_pool {
+            return Err(CliptionsError::ValidationError(format!(
+                "Prize pool of {} is below the minimum of {}",
+                self.config.format_amount(self.config.prize_pool),
+                self.config.format_amount(self.config.min_prize_pool)
+            )));
+        }
         Ok(())
     }
 
@@ -208,9 +409,14 @@ impl PayoutCalculator {
         self.config.prize_pool * (self.config.platform_fee_percentage / 100.0)
     }
 
-    /// Get the available prize pool after platform fees
+    /// Get the validator fee taken from the prize pool
+    pub fn calculate_validator_fee(&self) -> f64 {
+        self.config.prize_pool * (self.config.validator_fee_percent / 100.0)
+    }
+
+    /// Get the available prize pool after platform and validator fees
     pub fn calculate_available_pool(&self) -> f64 {
-        self.config.prize_pool - self.calculate_platform_fee()
+        self.config.prize_pool - self.calculate_platform_fee() - self.calculate_validator_fee()
     }
 
     /// Set a new prize pool
@@ -235,22 +441,171 @@ impl PayoutCalculator {
         Ok(())
     }
 
+    /// Set validator fee percentage
+    pub fn set_validator_fee(&mut self, fee_percentage: f64) -> Result<()> {
+        if !(0.0..100.0).contains(&fee_percentage) {
+            return Err(CliptionsError::ValidationError(
+                "Validator fee percentage must be between 0 and 100".to_string(),
+            ));
+        }
+        self.config.validator_fee_percent = fee_percentage;
+        Ok(())
+    }
+
     /// Get current configuration
     pub fn get_config(&self) -> &PayoutConfig {
         &self.config
     }
 }
 
+/// Round payouts to `decimals` places so they sum exactly to the pool at that precision
+///
+/// Uses the largest-remainder method: every payout is rounded down to a whole
+/// number of units, then the units left over go one each to the payouts with the
+/// largest discarded fractions, earlier (higher-ranked) payouts first on ties.
+fn round_payouts(payouts: &[f64], pool: f64, decimals: u32) -> Result<Vec<f64>> {
+    let scale = 10f64.powi(decimals as i32);
+    let pool_units = (pool * scale).round();
+    // Beyond 2^53 units f64 can no longer represent every whole unit
+    if !pool_units.is_finite() || pool_units > (1u64 << 53) as f64 {
+        return Err(CliptionsError::ValidationError(format!(
+            "Cannot round a prize pool of {} to {} decimal places",
+            pool, decimals
+        )));
+    }
+
+    let scaled: Vec<f64> = payouts.iter().map(|p| p * scale).collect();
+    let mut units: Vec<u64> = scaled.iter().map(|p| p.floor().max(0.0) as u64).collect();
+    let assigned: u64 = units.iter().sum();
+    let leftover = (pool_units as u64).saturating_sub(assigned);
+
+    let mut by_remainder: Vec<usize> = (0..payouts.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        let frac_a = scaled[a] - scaled[a].floor();
+        let frac_b = scaled[b] - scaled[b].floor();
+        frac_b.total_cmp(&frac_a).then(a.cmp(&b))
+    });
+    for &idx in by_remainder.iter().cycle().take(leftover as usize) {
+        units[idx] += 1;
+    }
+
+    Ok(units.into_iter().map(|u| u as f64 / scale).collect())
+}
+
 impl Default for PayoutCalculator {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Largest difference between a stored and a computed payout treated as float rounding,
+/// relative to the payout size for payouts above 1
+pub const PAYOUT_TOLERANCE: f64 = 1e-6;
+
+/// A disagreement between a stored scoring result and a freshly computed payout
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum ReconciliationMismatch {
+    /// A stored result records a payout but the fresh computation pays nothing
+    MissingPayout { username: String, guess: String },
+    /// The fresh computation pays a participant with no stored result
+    UnexpectedPayout { username: String, guess: String },
+    /// The stored rank differs from the computed rank
+    Rank {
+        username: String,
+        guess: String,
+        stored: Option<usize>,
+        computed: usize,
+    },
+    /// The stored payout differs from the computed payout beyond [`PAYOUT_TOLERANCE`]
+    Payout {
+        username: String,
+        guess: String,
+        stored: Option<f64>,
+        computed: f64,
+    },
+}
+
+/// Outcome of checking stored scoring results against freshly computed payouts
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReconciliationReport {
+    /// Number of stored results matched to a computed payout
+    pub matched: usize,
+    pub mismatches: Vec<ReconciliationMismatch>,
+}
+
+impl ReconciliationReport {
+    /// True if every stored result agrees with the computed payouts
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Check that stored scoring results agree with freshly computed payouts
+///
+/// Results and payouts are matched by username and guess. Ranks must be equal;
+/// payouts may differ by float rounding up to [`PAYOUT_TOLERANCE`].
+pub fn reconcile(results: &[ScoringResult], payouts: &[PayoutInfo]) -> ReconciliationReport {
+    let mut report = ReconciliationReport::default();
+    let mut unmatched: Vec<&PayoutInfo> = payouts.iter().collect();
+
+    for result in results {
+        let username = &result.participant.username;
+        let guess = &result.participant.guess.text;
+        let position = unmatched
+            .iter()
+            .position(|p| &p.username == username && &p.guess == guess);
+        let Some(computed) = position.map(|i| unmatched.swap_remove(i)) else {
+            if result.payout.is_some_and(|payout| payout.abs() > PAYOUT_TOLERANCE) {
+                report.mismatches.push(ReconciliationMismatch::MissingPayout {
+                    username: username.clone(),
+                    guess: guess.clone(),
+                });
+            }
+            continue;
+        };
+
+        report.matched += 1;
+        if result.rank != Some(computed.rank) {
+            report.mismatches.push(ReconciliationMismatch::Rank {
+                username: username.clone(),
+                guess: guess.clone(),
+                stored: result.rank,
+                computed: computed.rank,
+            });
+        }
+        if !result
+            .payout
+            .is_some_and(|stored| payouts_match(stored, computed.payout))
+        {
+            report.mismatches.push(ReconciliationMismatch::Payout {
+                username: username.clone(),
+                guess: guess.clone(),
+                stored: result.payout,
+                computed: computed.payout,
+            });
+        }
+    }
+
+    // Keep the computed order for payouts nobody stored
+    unmatched.sort_by_key(|p| p.rank);
+    for payout in unmatched {
+        report.mismatches.push(ReconciliationMismatch::UnexpectedPayout {
+            username: payout.username.clone(),
+            guess: payout.guess.clone(),
+        });
+    }
+
+    report
+}
+
+fn payouts_match(stored: f64, computed: f64) -> bool {
+    (stored - computed).abs() <= PAYOUT_TOLERANCE * stored.abs().max(computed.abs()).max(1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Guess, Participant};
+    use crate::types::{Guess, Participant, ScoringResult};
 
     fn create_test_participants_with_scores(
         guesses_and_scores: Vec<(&str, &str, f64, bool)>,
@@ -365,7 +720,12 @@ mod tests {
         let config = PayoutConfig {
             prize_pool: 500.0,
             platform_fee_percentage: 0.0,
+            validator_fee_percent: 0.0,
             minimum_players: 2,
+            curve: PayoutCurve::LinearByRank,
+            decimals: None,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
         };
         let calculator = PayoutCalculator::with_config(config);
 
@@ -382,7 +742,12 @@ mod tests {
         let config = PayoutConfig {
             prize_pool: 1000.0,
             platform_fee_percentage: 10.0,
+            validator_fee_percent: 0.0,
             minimum_players: 2,
+            curve: PayoutCurve::LinearByRank,
+            decimals: None,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
         };
         let calculator = PayoutCalculator::with_config(config);
 
@@ -403,7 +768,12 @@ mod tests {
         let config = PayoutConfig {
             prize_pool: 1000.0,
             platform_fee_percentage: 0.0,
+            validator_fee_percent: 0.0,
             minimum_players: 3,
+            curve: PayoutCurve::LinearByRank,
+            decimals: None,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            min_prize_pool: 0.0,
         };
         let calculator = PayoutCalculator::with_config(config);
 
@@ -418,6 +788,53 @@ mod tests {
             .contains("Minimum 3 players required"));
     }
 
+    #[test]
+    fn test_min_prize_pool_guard() {
+        let calculator = PayoutCalculator::with_config(PayoutConfig {
+            prize_pool: 5.0,
+            min_prize_pool: 10.0,
+            currency_label: "USDC".to_string(),
+            decimals: Some(2),
+            ..PayoutConfig::default()
+        });
+        let ranked_results = vec![("Player1".to_string(), 0.9), ("Player2".to_string(), 0.6)];
+
+        let error = calculator.calculate_payouts(&ranked_results).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Prize pool of 5.00 USDC is below the minimum of 10.00 USDC"
+        );
+        assert!(calculator.validate_config().is_err());
+
+        // A pool at the minimum is paid out
+        let calculator = PayoutCalculator::with_config(PayoutConfig {
+            prize_pool: 10.0,
+            min_prize_pool: 10.0,
+            ..PayoutConfig::default()
+        });
+        assert!(calculator.check_prize_pool().is_ok());
+        assert!(calculator.calculate_payouts(&ranked_results).is_ok());
+    }
+
+    #[test]
+    fn test_currency_label_formatting() {
+        let default = PayoutConfig::default();
+        assert_eq!(default.format_amount(1.5), "1.500000000 TAO");
+
+        let config = PayoutConfig {
+            currency_label: "USDC".to_string(),
+            decimals: Some(2),
+            ..PayoutConfig::default()
+        };
+        assert_eq!(config.format_amount(12.345), "12.35 USDC");
+
+        // Configs saved before the label existed still default to TAO
+        let json = r#"{"prize_pool": 50.0, "platform_fee_percentage": 0.0, "minimum_players": 2}"#;
+        let config: PayoutConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.currency_label, DEFAULT_CURRENCY_LABEL);
+        assert_eq!(config.min_prize_pool, 0.0);
+    }
+
     #[test]
     fn test_invalid_guess_range() {
         let calculator = PayoutCalculator::new();
@@ -506,4 +923,347 @@ mod tests {
         assert!(calculator.set_platform_fee(100.0).is_err());
         assert!(calculator.set_platform_fee(50.0).is_ok());
     }
+
+    #[test]
+    fn test_payouts_record_scoring_version() {
+        let participants = create_test_participants_with_scores(vec![
+            ("alice", "Best guess", 0.9, true),
+            ("bob", "Good guess", 0.7, true),
+        ]);
+
+        let unversioned = PayoutCalculator::new()
+            .process_payouts_with_scores(&participants)
+            .unwrap();
+        assert!(unversioned.iter().all(|p| p.scoring_version.is_none()));
+
+        let calculator = PayoutCalculator::new().with_scoring_version("v2");
+        assert_eq!(calculator.scoring_version(), Some("v2"));
+        let payouts = calculator.process_payouts_with_scores(&participants).unwrap();
+        assert!(payouts
+            .iter()
+            .all(|p| p.scoring_version.as_deref() == Some("v2")));
+    }
+
+    fn calculator_with_curve(curve: PayoutCurve) -> PayoutCalculator {
+        PayoutCalculator::with_config(PayoutConfig {
+            curve,
+            ..PayoutConfig::default()
+        })
+    }
+
+    fn assert_conserves_pool(calculator: &PayoutCalculator, payouts: &[f64]) {
+        let total: f64 = payouts.iter().sum();
+        assert!((total - calculator.calculate_available_pool()).abs() < 1e-9);
+        assert!(payouts.iter().all(|p| *p >= 0.0));
+    }
+
+    #[test]
+    fn test_payout_curves_conserve_pool_and_order() {
+        let ranked_results = vec![
+            ("First".to_string(), 0.9),
+            ("Second".to_string(), 0.7),
+            ("Third".to_string(), 0.4),
+            ("Fourth".to_string(), 0.1),
+        ];
+        let curves = [
+            PayoutCurve::ProportionalToScore,
+            PayoutCurve::LinearByRank,
+            PayoutCurve::Exponential { base: 2.0 },
+            PayoutCurve::WinnerTakeAll,
+            PayoutCurve::TopN { n: 2 },
+        ];
+
+        for curve in curves {
+            let calculator = calculator_with_curve(curve);
+            let payouts = calculator.calculate_payouts(&ranked_results).unwrap();
+            assert_conserves_pool(&calculator, &payouts);
+            assert!(
+                payouts.windows(2).all(|pair| pair[0] >= pair[1]),
+                "{:?} payouts not ordered: {:?}",
+                curve,
+                payouts
+            );
+        }
+    }
+
+    #[test]
+    fn test_payout_curve_shapes() {
+        let ranked_results = vec![
+            ("First".to_string(), 0.6),
+            ("Second".to_string(), 0.3),
+            ("Third".to_string(), 0.1),
+        ];
+        let payouts_for = |curve| {
+            calculator_with_curve(curve)
+                .calculate_payouts(&ranked_results)
+                .unwrap()
+        };
+        let assert_close = |actual: Vec<f64>, expected: [f64; 3]| {
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+            }
+        };
+
+        assert_close(payouts_for(PayoutCurve::ProportionalToScore), [600.0, 300.0, 100.0]);
+        assert_close(
+            payouts_for(PayoutCurve::Exponential { base: 2.0 }),
+            [4000.0 / 7.0, 2000.0 / 7.0, 1000.0 / 7.0],
+        );
+        assert_close(payouts_for(PayoutCurve::WinnerTakeAll), [1000.0, 0.0, 0.0]);
+        assert_close(payouts_for(PayoutCurve::TopN { n: 2 }), [500.0, 500.0, 0.0]);
+    }
+
+    #[test]
+    fn test_payout_curve_ties() {
+        let ranked_results = vec![
+            ("Tie 1".to_string(), 0.8),
+            ("Tie 2".to_string(), 0.8),
+            ("Third".to_string(), 0.5),
+        ];
+
+        // Tied winners split the pot
+        let calculator = calculator_with_curve(PayoutCurve::WinnerTakeAll);
+        let payouts = calculator.calculate_payouts(&ranked_results).unwrap();
+        assert_eq!(payouts[0], payouts[1]);
+        assert_eq!(payouts[2], 0.0);
+        assert_conserves_pool(&calculator, &payouts);
+
+        // A tie straddling the cutoff shares the last paid place
+        let calculator = calculator_with_curve(PayoutCurve::TopN { n: 1 });
+        let payouts = calculator.calculate_payouts(&ranked_results).unwrap();
+        assert_eq!(payouts[0], payouts[1]);
+        assert_conserves_pool(&calculator, &payouts);
+    }
+
+    #[test]
+    fn test_payout_curve_validation() {
+        let ranked_results = vec![("Winner".to_string(), 0.9), ("Runner-up".to_string(), 0.6)];
+
+        for curve in [
+            PayoutCurve::Exponential { base: 1.0 },
+            PayoutCurve::Exponential { base: f64::NAN },
+            PayoutCurve::TopN { n: 0 },
+        ] {
+            let calculator = calculator_with_curve(curve);
+            assert!(calculator.validate_config().is_err());
+            assert!(calculator.calculate_payouts(&ranked_results).is_err());
+        }
+
+        // All-zero scores fall back to an even split
+        let calculator = calculator_with_curve(PayoutCurve::ProportionalToScore);
+        let payouts = calculator
+            .calculate_payouts(&[("A".to_string(), 0.0), ("B".to_string(), -0.2)])
+            .unwrap();
+        assert_eq!(payouts, vec![500.0, 500.0]);
+    }
+
+    #[test]
+    fn test_payout_curve_from_str() {
+        for curve in [
+            PayoutCurve::ProportionalToScore,
+            PayoutCurve::LinearByRank,
+            PayoutCurve::Exponential { base: 2.5 },
+            PayoutCurve::WinnerTakeAll,
+            PayoutCurve::TopN { n: 3 },
+        ] {
+            assert_eq!(curve.to_string().parse::<PayoutCurve>(), Ok(curve));
+        }
+        assert_eq!("Top-N:2".parse::<PayoutCurve>(), Ok(PayoutCurve::TopN { n: 2 }));
+
+        for invalid in ["", "linear:2", "exponential", "exponential:1", "top-n:0", "top-n:x"] {
+            assert!(invalid.parse::<PayoutCurve>().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_validator_fee() {
+        let calculator = PayoutCalculator::with_config(PayoutConfig {
+            prize_pool: 1000.0,
+            validator_fee_percent: 5.0,
+            ..PayoutConfig::default()
+        });
+        assert_eq!(calculator.calculate_validator_fee(), 50.0);
+        assert_eq!(calculator.calculate_available_pool(), 950.0);
+
+        let ranked_results = vec![
+            ("First".to_string(), 0.9),
+            ("Second".to_string(), 0.7),
+            ("Third".to_string(), 0.5),
+        ];
+        let payouts = calculator.calculate_payouts(&ranked_results).unwrap();
+        let distributed: f64 = payouts.iter().sum();
+
+        // Fee plus distributed payouts accounts for the whole pool
+        assert!((calculator.calculate_validator_fee() + distributed - 1000.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_validator_fee_validation() {
+        let mut calculator = PayoutCalculator::new();
+        assert!(calculator.set_validator_fee(-1.0).is_err());
+        assert!(calculator.set_validator_fee(100.0).is_err());
+        assert!(calculator.set_validator_fee(0.0).is_ok());
+        assert!(calculator.set_validator_fee(60.0).is_ok());
+        assert!(calculator.validate_config().is_ok());
+
+        // Fees together can't consume the whole pool
+        calculator.set_platform_fee(40.0).unwrap();
+        assert!(calculator.validate_config().is_err());
+    }
+
+    /// Stored results matching the calculated `payouts`
+    fn stored_results(
+        participant_scores: &[(Participant, f64)],
+        payouts: &[PayoutInfo],
+    ) -> Vec<ScoringResult> {
+        payouts
+            .iter()
+            .map(|info| {
+                let (participant, score) = participant_scores
+                    .iter()
+                    .find(|(p, _)| p.username == info.username)
+                    .unwrap();
+                ScoringResult::new(participant.clone(), *score)
+                    .with_rank(info.rank)
+                    .with_payout(info.payout)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reconcile_matching_results() {
+        let calculator = PayoutCalculator::new();
+        let participant_scores = create_test_participants_with_scores(vec![
+            ("alice", "red sunset", 0.9, true),
+            ("bob", "blue ocean", 0.6, true),
+            ("carol", "green field", 0.3, true),
+        ]);
+        let payouts = calculator
+            .process_payouts_with_scores(&participant_scores)
+            .unwrap();
+        let mut results = stored_results(&participant_scores, &payouts);
+
+        let report = reconcile(&results, &payouts);
+        assert!(report.is_consistent(), "{:?}", report.mismatches);
+        assert_eq!(report.matched, 3);
+
+        // Rounding noise from a JSON round trip is tolerated
+        results[1].payout = results[1].payout.map(|p| p + 1e-12);
+        assert!(reconcile(&results, &payouts).is_consistent());
+    }
+
+    #[test]
+    fn test_reconcile_flags_tampered_results() {
+        let calculator = PayoutCalculator::new();
+        let participant_scores = create_test_participants_with_scores(vec![
+            ("alice", "red sunset", 0.9, true),
+            ("bob", "blue ocean", 0.6, true),
+            ("carol", "green field", 0.3, true),
+        ]);
+        let payouts = calculator
+            .process_payouts_with_scores(&participant_scores)
+            .unwrap();
+        let mut results = stored_results(&participant_scores, &payouts);
+
+        // Bob's stored result claims first place and a bigger payout; carol's is lost
+        let bob_payout = results[1].payout.unwrap();
+        results[1].rank = Some(1);
+        results[1].payout = Some(bob_payout + 0.01);
+        results.pop();
+
+        let report = reconcile(&results, &payouts);
+        assert!(!report.is_consistent());
+        assert_eq!(report.matched, 2);
+        assert_eq!(
+            report.mismatches,
+            vec![
+                ReconciliationMismatch::Rank {
+                    username: "bob".to_string(),
+                    guess: "blue ocean".to_string(),
+                    stored: Some(1),
+                    computed: 2,
+                },
+                ReconciliationMismatch::Payout {
+                    username: "bob".to_string(),
+                    guess: "blue ocean".to_string(),
+                    stored: Some(bob_payout + 0.01),
+                    computed: bob_payout,
+                },
+                ReconciliationMismatch::UnexpectedPayout {
+                    username: "carol".to_string(),
+                    guess: "green field".to_string(),
+                },
+            ]
+        );
+
+        // A stored payout the fresh computation no longer makes is flagged too
+        let report = reconcile(&results, &payouts[..1]);
+        assert!(report
+            .mismatches
+            .contains(&ReconciliationMismatch::MissingPayout {
+                username: "bob".to_string(),
+                guess: "blue ocean".to_string(),
+            }));
+    }
+
+    /// Total of `payouts` in whole units of the given precision
+    fn total_units(payouts: &[f64], decimals: u32) -> u64 {
+        let scale = 10f64.powi(decimals as i32);
+        payouts.iter().map(|p| (p * scale).round() as u64).sum()
+    }
+
+    #[test]
+    fn test_rounded_payouts() {
+        let calculator = PayoutCalculator::with_config(PayoutConfig {
+            prize_pool: 100.0,
+            decimals: Some(2),
+            ..PayoutConfig::default()
+        });
+        let ranked_results = vec![
+            ("First".to_string(), 0.9),
+            ("Second".to_string(), 0.7),
+            ("Third".to_string(), 0.5),
+        ];
+
+        // 100 * 3/6, 2/6, 1/6 = 50, 33.333.., 16.666..; the leftover cent goes to
+        // the largest dropped fraction
+        let payouts = calculator.calculate_payouts(&ranked_results).unwrap();
+        assert_eq!(payouts, vec![50.0, 33.33, 16.67]);
+        assert_eq!(total_units(&payouts, 2), 10_000);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_rounded_payouts_sum_to_pool(
+            scores in proptest::collection::vec(0.0f64..1.0, 2..40),
+            pool_units in 1u64..10_000_000_000_000,
+            curve_index in 0usize..5,
+        ) {
+            let curve = [
+                PayoutCurve::ProportionalToScore,
+                PayoutCurve::LinearByRank,
+                PayoutCurve::Exponential { base: 1.5 },
+                PayoutCurve::WinnerTakeAll,
+                PayoutCurve::TopN { n: 3 },
+            ][curve_index];
+            let prize_pool = pool_units as f64 / 1e9;
+            let calculator = PayoutCalculator::with_config(PayoutConfig {
+                prize_pool,
+                decimals: Some(TAO_DECIMALS),
+                curve,
+                ..PayoutConfig::default()
+            });
+
+            let mut ranked_results: Vec<(String, f64)> = scores
+                .iter()
+                .enumerate()
+                .map(|(i, score)| (format!("guess {}", i), *score))
+                .collect();
+            ranked_results.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+            let payouts = calculator.calculate_payouts(&ranked_results).unwrap();
+            proptest::prop_assert_eq!(total_units(&payouts, TAO_DECIMALS), pool_units);
+            proptest::prop_assert!(payouts.iter().all(|p| *p >= 0.0));
+        }
+    }
 }
@@ -10,11 +10,11 @@ use pyo3::types::PyModule;
 use serde_json;
 
 use crate::commitment::{CommitmentGenerator, CommitmentVerifier};
-use crate::embedder::{cosine_similarity, ClipEmbedder, MockEmbedder};
+use crate::embedder::{cosine_similarity, ClipEmbedder, EmbedderTrait, MockEmbedder};
 use crate::error::CliptionsError;
 use crate::block_processor::BlockProcessor;
 use crate::scoring::{
-    calculate_payouts, calculate_rankings, ClipBatchStrategy, ScoreValidator, ScoringStrategy,
+    calculate_payouts, rank_items, ClipBatchStrategy, ScoreValidator, ScoringStrategy,
 };
 
 /// Convert CliptionsError to PyErr for Python integration
@@ -143,13 +143,13 @@ pub fn py_calculate_rankings(
     if use_mock {
         let embedder = MockEmbedder::clip_like();
         let validator = ScoreValidator::new(embedder, strategy);
-        calculate_rankings(target_image_path, &guesses, &validator).map_err(|e| e.into())
+        ranked_guesses(target_image_path, &guesses, &validator)
     } else {
         // Try CLIP - panic if it fails
         match ClipEmbedder::new() {
             Ok(embedder) => {
                 let validator = ScoreValidator::new(embedder, strategy);
-                calculate_rankings(target_image_path, &guesses, &validator).map_err(|e| e.into())
+                ranked_guesses(target_image_path, &guesses, &validator)
             }
             Err(e) => {
                 panic!("CRITICAL: Failed to load CLIP model: {}. Cannot proceed with invalid MockEmbedder fallback as this would produce unreliable scores.", e);
@@ -158,6 +158,18 @@ pub fn py_calculate_rankings(
     }
 }
 
+/// Rank guesses as owned (guess, similarity) pairs for Python
+fn ranked_guesses<E: EmbedderTrait, S: ScoringStrategy>(
+    target_image_path: &str,
+    guesses: &[String],
+    validator: &ScoreValidator<E, S>,
+) -> PyResult<Vec<(String, f64)>> {
+    Ok(rank_items(target_image_path, guesses, validator)?
+        .into_iter()
+        .map(|(guess, similarity)| (guess.clone(), similarity))
+        .collect())
+}
+
 /// Python function for calculating payouts
 #[pyfunction]
 pub fn py_calculate_payouts(
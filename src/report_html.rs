@@ -0,0 +1,232 @@
+//! Self-contained HTML rendering of a `RoundReport`
+//!
+//! The page has the block metadata in a header and one table row per
+//! participant. Styling is a small inline stylesheet with no external assets,
+//! so the file can be mailed or opened anywhere.
+
+use std::fmt::Write;
+
+use crate::block_processor::{ParticipantReport, RoundReport};
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.4em; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.2em 1em; }
+dt { font-weight: bold; }
+dd { margin: 0; }
+table { border-collapse: collapse; margin-top: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #f2f2f2; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+.pass { color: #1a7f37; }
+.fail { color: #c62828; }
+footer { margin-top: 1em; font-size: 0.85em; color: #666; }";
+
+/// Render `report` as a standalone HTML page
+pub fn render_html(report: &RoundReport) -> String {
+    let summary = &report.summary;
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    html.push_str("<meta charset=\"utf-8\">\n");
+    let _ = writeln!(
+        html,
+        "<title>Cliptions block {}</title>",
+        escape(&summary.block_num)
+    );
+    let _ = writeln!(html, "<style>\n{}\n</style>", STYLE);
+    html.push_str("</head>\n<body>\n<header>\n");
+    let _ = writeln!(html, "<h1>Block {}</h1>", escape(&summary.block_num));
+
+    html.push_str("<dl>\n");
+    let scoring_version = report.scoring_version.as_deref().unwrap_or("unrecorded");
+    let metadata = [
+        ("Status", format!("{:?}", summary.status)),
+        ("Target image", report.target_image_path.clone()),
+        ("Announcement tweet", report.announcement_tweet_id.clone()),
+        ("Scoring version", scoring_version.to_string()),
+        (
+            "Commitment deadline",
+            summary.commitment_deadline.to_rfc3339(),
+        ),
+        ("Reveal deadline", summary.reveal_deadline.to_rfc3339()),
+        (
+            "Participants",
+            format!(
+                "{} ({} verified)",
+                summary.participant_count, summary.verified_count
+            ),
+        ),
+        ("Prize pool", format!("{:.9}", summary.prize_pool)),
+        ("Validator fee", format!("{:.9}", report.validator_fee)),
+        ("Total payout", format!("{:.9}", summary.total_payout)),
+    ];
+    for (label, value) in metadata {
+        let _ = writeln!(html, "<dt>{}</dt><dd>{}</dd>", label, escape(&value));
+    }
+    html.push_str("</dl>\n</header>\n<main>\n<table>\n<thead>\n<tr>");
+    for heading in [
+        "Rank",
+        "Username",
+        "Guess",
+        "Score",
+        "Payout",
+        "Verification",
+    ] {
+        let _ = write!(html, "<th>{}</th>", heading);
+    }
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+    for participant in &report.participants {
+        write_row(&mut html, participant);
+    }
+    html.push_str("</tbody>\n</table>\n</main>\n");
+
+    let _ = writeln!(
+        html,
+        "<footer>Generated {}</footer>",
+        escape(&report.generated_at.to_rfc3339())
+    );
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn write_row(html: &mut String, participant: &ParticipantReport) {
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    let rank = optional(participant.rank.map(|r| r.to_string()));
+    let score = optional(participant.score.map(|s| format!("{:.6}", s)));
+    let payout = optional(participant.payout.map(|p| format!("{:.9}", p)));
+    let verification = match &participant.verification.reason {
+        None if participant.verification.valid => {
+            "<span class=\"pass\">verified</span>".to_string()
+        }
+        None => "<span class=\"fail\">failed</span>".to_string(),
+        Some(reason) => format!(
+            "<span class=\"fail\">{}</span>",
+            escape(&reason.to_string())
+        ),
+    };
+
+    let _ = writeln!(
+        html,
+        "<tr><td class=\"num\">{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td>\
+         <td class=\"num\">{}</td><td>{}</td></tr>",
+        rank,
+        escape(&participant.username),
+        escape(&participant.guess),
+        score,
+        payout,
+        verification
+    );
+}
+
+/// Escape text for use in HTML element content and quoted attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::{CommitmentVerification, FailReason};
+    use crate::types::{BlockStatus, BlockSummary};
+    use chrono::Utc;
+
+    fn participant(username: &str, rank: Option<usize>) -> ParticipantReport {
+        let verification = if rank.is_some() {
+            CommitmentVerification::valid()
+        } else {
+            CommitmentVerification::invalid(FailReason::MissingSalt)
+        };
+        ParticipantReport {
+            username: username.to_string(),
+            social_id: "1".to_string(),
+            wallet: "wallet".to_string(),
+            commitment: "abc".to_string(),
+            commitment_tweet_id: None,
+            guess: "a cat & a <dog>".to_string(),
+            salt: None,
+            reveal_tweet_id: None,
+            verified: rank.is_some(),
+            verification,
+            score: rank.map(|r| 1.0 / r as f64),
+            rank,
+            payout: rank.map(|r| 10.0 / r as f64),
+        }
+    }
+
+    fn report() -> RoundReport {
+        let now = Utc::now();
+        RoundReport {
+            summary: BlockSummary {
+                block_num: "42".to_string(),
+                status: BlockStatus::Complete,
+                participant_count: 3,
+                verified_count: 2,
+                prize_pool: 15.0,
+                total_payout: 15.0,
+                commitment_deadline: now,
+                reveal_deadline: now,
+                created_at: now,
+            },
+            target_image_path: "target.jpg".to_string(),
+            announcement_tweet_id: "1000".to_string(),
+            scoring_version: None,
+            validator_fee: 0.0,
+            participants: vec![
+                participant("alice", Some(1)),
+                participant("bob", Some(2)),
+                participant("<mallory>", None),
+            ],
+            generated_at: now,
+        }
+    }
+
+    /// Check that every element is closed in order, skipping void elements
+    fn assert_balanced(html: &str) {
+        const VOID: [&str; 2] = ["meta", "br"];
+        let mut open: Vec<String> = Vec::new();
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('!') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name), "mismatched </{}>", name);
+            } else {
+                let name = tag.split_whitespace().next().unwrap().to_string();
+                if !VOID.contains(&name.as_str()) {
+                    open.push(name);
+                }
+            }
+        }
+        assert!(open.is_empty(), "unclosed elements: {:?}", open);
+    }
+
+    #[test]
+    fn test_html_lists_every_participant() {
+        let html = render_html(&report());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Block 42</h1>"));
+        for username in ["alice", "bob", "&lt;mallory&gt;"] {
+            assert!(html.contains(&format!("<td>{}</td>", username)));
+        }
+        assert!(html.contains("a cat &amp; a &lt;dog&gt;"));
+        assert!(!html.contains("<link") && !html.contains("<script"));
+        assert_balanced(&html);
+    }
+}
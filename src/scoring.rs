@@ -3,11 +3,20 @@
 //! This module implements various scoring strategies for calculating similarity between
 //! image and text embeddings, as well as payout calculation based on rankings.
 
-use crate::embedder::EmbedderTrait;
-use crate::error::{Result, ScoringError};
+use crate::embedder::{
+    cosine_similarity, sha256_file, softmax_percentages, EmbedderTrait, TokenLimitPolicy,
+    Truncation,
+};
+use crate::error::{CliptionsError, EmbeddingError, Result, ScoringError};
+use crate::payout::PayoutCalculator;
+use crate::scoring_cache::ScoringCache;
 use crate::types::{Participant, ScoringResult};
 use ndarray::Array1;
-use std::sync::Arc;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 /// Trait for scoring strategies
 ///
@@ -29,6 +38,39 @@ pub trait ScoringStrategy: Send + Sync {
 
     /// Get the name of this scoring strategy
     fn name(&self) -> &str;
+
+    /// Adjust a batch of similarity scores (percentages) for the given guesses
+    ///
+    /// Called with the embedder's batch similarities before ranking. The default
+    /// returns them unchanged.
+    fn adjust_batch_scores(&self, _guesses: &[String], similarities: Vec<f64>) -> Result<Vec<f64>> {
+        Ok(similarities)
+    }
+
+    /// Similarity scores (percentages) for the given guesses, if the strategy
+    /// already has them
+    ///
+    /// When this returns `Some`, the embedder isn't called at all. The default
+    /// returns `None`.
+    fn precomputed_scores(&self, _guesses: &[String]) -> Result<Option<Vec<f64>>> {
+        Ok(None)
+    }
+
+    /// This strategy set up for a block with the given reference caption
+    ///
+    /// Strategies that don't compare guesses against a reference return `None`
+    /// and are used unchanged. The default returns `None`.
+    fn with_block_reference(&self, _reference: &str) -> Option<Box<dyn ScoringStrategy>> {
+        None
+    }
+
+    /// Whether scoring calls the embedder
+    ///
+    /// Strategies that bring their own scores for every guess return `false`,
+    /// so callers needn't load a model. The default returns `true`.
+    fn uses_embedder(&self) -> bool {
+        true
+    }
 }
 
 /// CLIP batch processing strategy
@@ -66,6 +108,364 @@ impl ScoringStrategy for ClipBatchStrategy {
     }
 }
 
+/// Strategy blending CLIP batch similarity with lexical overlap against a reference caption
+///
+/// The final score is `alpha * clip + (1 - alpha) * lexical`, where `lexical` is the
+/// token Jaccard similarity between the guess and the block's reference caption,
+/// scaled to a percentage so both terms share the same 0-100 range. A block's
+/// `reference_caption` replaces the reference the strategy was built with.
+#[derive(Debug, Clone)]
+pub struct BlendedStrategy {
+    inner: ClipBatchStrategy,
+    alpha: f64,
+    reference: Option<String>,
+}
+
+impl BlendedStrategy {
+    /// Create a blended strategy; `alpha` is the weight of the CLIP score and must be in [0, 1]
+    pub fn new(inner: ClipBatchStrategy, alpha: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&alpha) {
+            return Err(CliptionsError::ValidationError(format!(
+                "Blend alpha must be between 0 and 1, got {}",
+                alpha
+            )));
+        }
+        Ok(Self {
+            inner,
+            alpha,
+            reference: None,
+        })
+    }
+
+    /// Set the reference caption guesses are compared against
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    /// Weight of the CLIP score
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Token Jaccard similarity between a guess and the reference, as a percentage
+    pub fn lexical_score(&self, guess: &str) -> Result<f64> {
+        let reference = self.reference.as_deref().ok_or_else(|| {
+            CliptionsError::ValidationError(
+                "BlendedStrategy requires a reference caption".to_string(),
+            )
+        })?;
+        Ok(token_jaccard(guess, reference) * 100.0)
+    }
+}
+
+impl ScoringStrategy for BlendedStrategy {
+    fn calculate_score(
+        &self,
+        image_features: &Array1<f64>,
+        text_features: &Array1<f64>,
+    ) -> Result<f64> {
+        // Only the embeddings are known here, not the guess, so there is no
+        // lexical term; the CLIP term is weighted as in batch scoring
+        let clip = cosine_similarity(image_features, text_features)? * 100.0;
+        Ok(self.alpha * clip)
+    }
+
+    fn name(&self) -> &str {
+        "Blended"
+    }
+
+    fn with_block_reference(&self, reference: &str) -> Option<Box<dyn ScoringStrategy>> {
+        Some(Box::new(self.clone().with_reference(reference)))
+    }
+
+    fn adjust_batch_scores(&self, guesses: &[String], similarities: Vec<f64>) -> Result<Vec<f64>> {
+        let clip_scores = self.inner.adjust_batch_scores(guesses, similarities)?;
+        guesses
+            .iter()
+            .zip(clip_scores)
+            .map(|(guess, clip)| {
+                Ok(self.alpha * clip + (1.0 - self.alpha) * self.lexical_score(guess)?)
+            })
+            .collect()
+    }
+}
+
+/// Strategy that scores guesses from a fixed map of similarities
+///
+/// Used to score a block offline, e.g. in CI or to reproduce a past payout,
+/// without loading an embedder. Similarities are percentages, as the CLIP
+/// batch strategy produces them, keyed by the exact guess text.
+#[derive(Debug, Clone)]
+pub struct PrecomputedScoringStrategy {
+    similarities: HashMap<String, f64>,
+}
+
+impl PrecomputedScoringStrategy {
+    /// Create a strategy over `{guess: similarity}`
+    pub fn new(similarities: HashMap<String, f64>) -> Result<Self> {
+        if let Some(score) = similarities.values().find(|score| !score.is_finite()) {
+            return Err(ScoringError::InvalidScore { score: *score }.into());
+        }
+        Ok(Self { similarities })
+    }
+
+    /// Load `{guess: similarity}` from a JSON file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::new(serde_json::from_str(&content)?)
+    }
+}
+
+impl ScoringStrategy for PrecomputedScoringStrategy {
+    fn calculate_score(
+        &self,
+        _image_features: &Array1<f64>,
+        _text_features: &Array1<f64>,
+    ) -> Result<f64> {
+        Err(ScoringError::UnsupportedOperation.into())
+    }
+
+    fn name(&self) -> &str {
+        "Precomputed"
+    }
+
+    fn uses_embedder(&self) -> bool {
+        false
+    }
+
+    fn precomputed_scores(&self, guesses: &[String]) -> Result<Option<Vec<f64>>> {
+        guesses
+            .iter()
+            .map(|guess| {
+                self.similarities.get(guess).copied().ok_or_else(|| {
+                    ScoringError::MissingSimilarity {
+                        guess: guess.clone(),
+                    }
+                    .into()
+                })
+            })
+            .collect::<Result<Vec<f64>>>()
+            .map(Some)
+    }
+}
+
+impl ScoringStrategy for Box<dyn ScoringStrategy> {
+    fn calculate_score(
+        &self,
+        image_features: &Array1<f64>,
+        text_features: &Array1<f64>,
+    ) -> Result<f64> {
+        (**self).calculate_score(image_features, text_features)
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn adjust_batch_scores(&self, guesses: &[String], similarities: Vec<f64>) -> Result<Vec<f64>> {
+        (**self).adjust_batch_scores(guesses, similarities)
+    }
+
+    fn precomputed_scores(&self, guesses: &[String]) -> Result<Option<Vec<f64>>> {
+        (**self).precomputed_scores(guesses)
+    }
+
+    fn with_block_reference(&self, reference: &str) -> Option<Box<dyn ScoringStrategy>> {
+        (**self).with_block_reference(reference)
+    }
+
+    fn uses_embedder(&self) -> bool {
+        (**self).uses_embedder()
+    }
+}
+
+/// Parameters passed to a strategy factory, e.g. `{"alpha": 0.7}`
+pub type StrategyParams = BTreeMap<String, serde_json::Value>;
+
+/// Parse a `KEY=VALUE` strategy parameter from the command line
+///
+/// VALUE is parsed as JSON, else taken as a string.
+pub fn parse_strategy_param(param: &str) -> std::result::Result<(String, serde_json::Value), String> {
+    let (key, value) = param
+        .split_once('=')
+        .ok_or_else(|| format!("Expected KEY=VALUE, got '{}'", param))?;
+    let value = serde_json::from_str(value)
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+    Ok((key.to_string(), value))
+}
+
+type StrategyFactory =
+    Box<dyn Fn(&StrategyParams) -> Result<Box<dyn ScoringStrategy>> + Send + Sync>;
+
+/// Registry of scoring strategies that can be selected by name at runtime
+#[derive(Default)]
+pub struct StrategyRegistry {
+    factories: BTreeMap<String, StrategyFactory>,
+}
+
+impl StrategyRegistry {
+    /// Name of the strategy used when none is selected
+    pub const DEFAULT_STRATEGY: &'static str = "clip-batch";
+
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in strategies
+    ///
+    /// * `clip-batch` - `ClipBatchStrategy`, no parameters
+    /// * `blended` - `BlendedStrategy`; `alpha` (required) and `reference`
+    /// * `precomputed` - `PrecomputedScoringStrategy`; `similarities_file` (required)
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(Self::DEFAULT_STRATEGY, |_| Ok(Box::new(ClipBatchStrategy::new())));
+        registry.register("blended", |params| {
+            let alpha = params
+                .get("alpha")
+                .and_then(serde_json::Value::as_f64)
+                .ok_or_else(|| missing_param("blended", "alpha"))?;
+            let mut strategy = BlendedStrategy::new(ClipBatchStrategy::new(), alpha)?;
+            if let Some(reference) = params.get("reference").and_then(serde_json::Value::as_str) {
+                strategy = strategy.with_reference(reference);
+            }
+            Ok(Box::new(strategy))
+        });
+        registry.register("precomputed", |params| {
+            let path = params
+                .get("similarities_file")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| missing_param("precomputed", "similarities_file"))?;
+            Ok(Box::new(PrecomputedScoringStrategy::from_file(Path::new(path))?))
+        });
+        registry
+    }
+
+    /// Register a factory under `name`, replacing any existing one
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F)
+    where
+        F: Fn(&StrategyParams) -> Result<Box<dyn ScoringStrategy>> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.into(), Box::new(factory));
+    }
+
+    /// Registered strategy names, in sorted order
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    /// Build the strategy registered under `name`
+    pub fn create(
+        &self,
+        name: &str,
+        params: &StrategyParams,
+    ) -> Result<Box<dyn ScoringStrategy>> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            CliptionsError::ValidationError(format!(
+                "Unknown scoring strategy '{}'; available: {}",
+                name,
+                self.names().join(", ")
+            ))
+        })?;
+        factory(params)
+    }
+}
+
+fn missing_param(strategy: &str, param: &str) -> CliptionsError {
+    CliptionsError::ValidationError(format!(
+        "Strategy '{}' requires the '{}' parameter",
+        strategy, param
+    ))
+}
+
+/// Jaccard similarity of the lowercase alphanumeric word sets of two texts
+fn token_jaccard(a: &str, b: &str) -> f64 {
+    let tokens = |text: &str| -> HashSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (tokens(a), tokens(b));
+
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Why a guess scored as it did, from `ScoreValidator::validate_with_diagnostics`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GuessDiagnostic {
+    pub guess: String,
+    /// Cosine similarity between the guess and image embeddings, 0.0 for invalid guesses
+    pub raw_similarity: f64,
+    /// Score the guess is ranked by, as a percentage
+    pub softmax_score: f64,
+    /// 1-based position by score; tied guesses share a rank
+    pub rank: usize,
+    /// Tokens the embedder's tokenizer produced, `None` if it has no tokenizer
+    pub token_count: Option<usize>,
+    /// Whether the guess exceeds the token limit, so it was truncated or rejected
+    pub truncated: bool,
+}
+
+/// Scale CLIP applies to cosine similarities before its softmax
+const CLIP_LOGIT_SCALE: f64 = 100.0;
+
+/// Pairwise similarities between a block's references and its guesses
+///
+/// Row 0 is the target image and rows 1.. are the guesses themselves, in the
+/// same order as `guesses`, so the matrix has `guesses.len() + 1` rows and
+/// `guesses.len()` columns. A guess's own row holds 1.0 on the diagonal.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarityMatrix {
+    /// Row labels: the image path followed by each guess
+    pub references: Vec<String>,
+    /// Column labels
+    pub guesses: Vec<String>,
+    /// Cosine similarity between each reference and guess embedding
+    pub raw: Vec<Vec<f64>>,
+    /// Each row softmax-normalized to percentages summing to 100
+    pub softmax: Vec<Vec<f64>>,
+}
+
+impl SimilarityMatrix {
+    /// Render the matrix as CSV, one row per reference and matrix
+    ///
+    /// Columns are `matrix,reference` followed by one column per guess, with
+    /// the raw rows first and the softmax rows after.
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        let mut header = vec!["matrix", "reference"];
+        header.extend(self.guesses.iter().map(String::as_str));
+        writer.write_record(&header)?;
+
+        for (name, rows) in [("raw", &self.raw), ("softmax", &self.softmax)] {
+            for (reference, row) in self.references.iter().zip(rows) {
+                let mut record = vec![name.to_string(), reference.clone()];
+                record.extend(row.iter().map(|value| format!("{:.6}", value)));
+                writer.write_record(&record)?;
+            }
+        }
+
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+        String::from_utf8(bytes).map_err(|e| CliptionsError::Generic(e.to_string()))
+    }
+}
+
+/// Explain an embedding size mismatch in terms of its likely cause
+fn explain_dimension_mismatch(error: CliptionsError) -> CliptionsError {
+    match error {
+        CliptionsError::Embedding(EmbeddingError::InvalidDimensions { a, b }) => {
+            ScoringError::EmbeddingModelMismatch { a, b }.into()
+        }
+        other => other,
+    }
+}
+
 /// Score validator for validating guesses and calculating scores
 ///
 /// This corresponds to the Python ScoreValidator class
@@ -73,6 +473,8 @@ pub struct ScoreValidator<E: EmbedderTrait, S: ScoringStrategy> {
     embedder: Arc<E>,
     scoring_strategy: Arc<S>,
     max_tokens: usize,
+    token_limit_policy: TokenLimitPolicy,
+    scoring_cache: Option<Arc<Mutex<ScoringCache>>>,
 }
 
 impl<E: EmbedderTrait, S: ScoringStrategy> ScoreValidator<E, S> {
@@ -82,6 +484,52 @@ impl<E: EmbedderTrait, S: ScoringStrategy> ScoreValidator<E, S> {
             embedder: Arc::new(embedder),
             scoring_strategy: Arc::new(scoring_strategy),
             max_tokens: 77, // CLIP's maximum token limit
+            token_limit_policy: TokenLimitPolicy::default(),
+            scoring_cache: None,
+        }
+    }
+
+    /// Choose whether guesses over the token limit are scored truncated or rejected
+    ///
+    /// Rejection only applies with embedders that report token counts.
+    pub fn with_token_limit_policy(mut self, policy: TokenLimitPolicy) -> Self {
+        self.token_limit_policy = policy;
+        self
+    }
+
+    /// Reuse similarities stored in `cache`, saving newly computed ones to it
+    ///
+    /// Only the embedder's similarities are cached; the strategy's adjustments
+    /// are applied on every run.
+    pub fn with_scoring_cache(mut self, cache: ScoringCache) -> Self {
+        self.scoring_cache = Some(Arc::new(Mutex::new(cache)));
+        self
+    }
+
+    /// Create a validator using a different scoring strategy with the same embedder
+    pub fn with_strategy<S2: ScoringStrategy>(&self, scoring_strategy: S2) -> ScoreValidator<E, S2> {
+        ScoreValidator {
+            embedder: Arc::clone(&self.embedder),
+            scoring_strategy: Arc::new(scoring_strategy),
+            max_tokens: self.max_tokens,
+            token_limit_policy: self.token_limit_policy,
+            scoring_cache: self.scoring_cache.clone(),
+        }
+    }
+
+    /// The scoring strategy guesses are ranked with
+    pub fn strategy(&self) -> &S {
+        &self.scoring_strategy
+    }
+
+    /// Create a validator using a different embedder with the same scoring strategy
+    pub fn with_embedder<E2: EmbedderTrait>(&self, embedder: E2) -> ScoreValidator<E2, S> {
+        ScoreValidator {
+            embedder: Arc::new(embedder),
+            scoring_strategy: Arc::clone(&self.scoring_strategy),
+            max_tokens: self.max_tokens,
+            token_limit_policy: self.token_limit_policy,
+            scoring_cache: self.scoring_cache.clone(),
         }
     }
 
@@ -99,9 +547,37 @@ impl<E: EmbedderTrait, S: ScoringStrategy> ScoreValidator<E, S> {
             return false;
         }
 
+        // With a real tokenizer, the count is exact
+        if self.token_limit_policy == TokenLimitPolicy::Reject {
+            if let Ok(Some(tokens)) = self.embedder.token_count(guess) {
+                return tokens <= self.max_tokens;
+            }
+        }
+
         true
     }
 
+    /// How the embedder cuts `guess` down to the token limit, if it does
+    ///
+    /// Under the `Reject` policy over-length guesses are invalid rather than
+    /// truncated, so this is always `None`.
+    pub fn truncation(&self, guess: &str) -> Result<Option<Truncation>> {
+        if self.token_limit_policy == TokenLimitPolicy::Reject {
+            return Ok(None);
+        }
+        self.embedder.truncation(guess)
+    }
+
+    /// Name of the embedder used for scoring
+    pub fn embedder_name(&self) -> &str {
+        self.embedder.name()
+    }
+
+    /// Name of the scoring strategy used for scoring
+    pub fn strategy_name(&self) -> &str {
+        self.scoring_strategy.name()
+    }
+
     /// Get image embedding for a given image path
     ///
     /// This is a convenience method for Python bindings
@@ -125,6 +601,25 @@ impl<E: EmbedderTrait, S: ScoringStrategy> ScoreValidator<E, S> {
         image_path: &str,
         guesses: &[String],
     ) -> Result<Vec<f64>> {
+        Ok(self
+            .calculate_batch_scores(image_path, guesses)?
+            .into_iter()
+            .map(|(_, adjusted)| adjusted)
+            .collect())
+    }
+
+    /// Calculate `(raw, adjusted)` similarity scores for each guess
+    ///
+    /// The raw score is the embedder's similarity before the strategy adjusts
+    /// it; precomputed scores aren't adjusted, so both are the same. Invalid
+    /// guesses score zero.
+    pub fn calculate_batch_scores(
+        &self,
+        image_path: &str,
+        guesses: &[String],
+    ) -> Result<Vec<(f64, f64)>> {
+        let _timer = cliptions_metrics::Timer::start("cliptions_scoring_seconds");
+
         // Filter out invalid guesses and keep track of original indices
         let mut valid_guesses = Vec::new();
         let mut valid_indices = Vec::new();
@@ -138,21 +633,153 @@ impl<E: EmbedderTrait, S: ScoringStrategy> ScoreValidator<E, S> {
 
         if valid_guesses.is_empty() {
             // Return zeros for all invalid guesses
-            return Ok(vec![0.0; guesses.len()]);
+            return Ok(vec![(0.0, 0.0); guesses.len()]);
         }
 
-        // Use the embedder's batch similarity calculation
-        let valid_similarities = self
-            .embedder
-            .calculate_batch_similarities(image_path, &valid_guesses)?;
+        // Use the strategy's own scores if it has them, otherwise the
+        // embedder's batch similarity calculation
+        let valid_scores: Vec<(f64, f64)> =
+            match self.scoring_strategy.precomputed_scores(&valid_guesses)? {
+                Some(similarities) => similarities.into_iter().map(|s| (s, s)).collect(),
+                None => {
+                    let raw = self.embedder_similarities(image_path, &valid_guesses)?;
+                    let adjusted = self
+                        .scoring_strategy
+                        .adjust_batch_scores(&valid_guesses, raw.clone())?;
+                    raw.into_iter().zip(adjusted).collect()
+                }
+            };
 
         // Map back to original positions
-        let mut all_similarities = vec![0.0; guesses.len()];
+        let mut all_scores = vec![(0.0, 0.0); guesses.len()];
         for (valid_idx, &original_idx) in valid_indices.iter().enumerate() {
-            all_similarities[original_idx] = valid_similarities[valid_idx];
+            all_scores[original_idx] = valid_scores[valid_idx];
+        }
+
+        Ok(all_scores)
+    }
+
+    /// The embedder's batch similarities, served from the scoring cache when it has them
+    fn embedder_similarities(&self, image_path: &str, guesses: &[String]) -> Result<Vec<f64>> {
+        let compute = || {
+            self.embedder
+                .calculate_batch_similarities(image_path, guesses)
+                .map_err(explain_dimension_mismatch)
+        };
+
+        let Some(cache) = &self.scoring_cache else {
+            return compute();
+        };
+        // Images that can't be read, like a mock embedder's placeholder paths,
+        // have no content hash to key on
+        let Ok(image_hash) = sha256_file(Path::new(image_path)) else {
+            return compute();
+        };
+        let embedder = format!("{}/{}", self.embedder.name(), self.embedder.model_id());
+
+        if let Some(similarities) = cache.lock().unwrap().lookup(&image_hash, &embedder, guesses) {
+            return Ok(similarities);
         }
 
-        Ok(all_similarities)
+        let similarities = compute()?;
+        let mut cache = cache.lock().unwrap();
+        cache.insert(&image_hash, &embedder, guesses, &similarities);
+        cache.save()?;
+        Ok(similarities)
+    }
+
+    /// Score guesses and report the details behind each score
+    ///
+    /// Returns one diagnostic per guess, in the same order as `guesses`.
+    pub fn validate_with_diagnostics(
+        &self,
+        image_path: &str,
+        guesses: &[String],
+    ) -> Result<Vec<GuessDiagnostic>> {
+        let scores = self.calculate_batch_similarities(image_path, guesses)?;
+        let image_embedding = self.embedder.get_image_embedding(image_path)?;
+
+        let mut order: Vec<usize> = (0..guesses.len()).collect();
+        order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+        let mut ranks = vec![0; guesses.len()];
+        for (position, &index) in order.iter().enumerate() {
+            ranks[index] = match position {
+                0 => 1,
+                _ if (scores[order[position - 1]] - scores[index]).abs() < f64::EPSILON => {
+                    ranks[order[position - 1]]
+                }
+                _ => position + 1,
+            };
+        }
+
+        guesses
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                let raw_similarity = if self.validate_guess(guess) {
+                    let text_embedding = self.embedder.get_text_embedding(guess)?;
+                    cosine_similarity(&image_embedding, &text_embedding)
+                        .map_err(explain_dimension_mismatch)?
+                } else {
+                    0.0
+                };
+                let token_count = self.embedder.token_count(guess)?;
+                Ok(GuessDiagnostic {
+                    guess: guess.clone(),
+                    raw_similarity,
+                    softmax_score: scores[i],
+                    rank: ranks[i],
+                    token_count,
+                    truncated: token_count.is_some_and(|count| count > self.max_tokens),
+                })
+            })
+            .collect()
+    }
+
+    /// Full similarity matrix between the image, the guesses and each other
+    ///
+    /// Unlike `calculate_batch_similarities`, guesses aren't filtered: every
+    /// guess gets a column. The image row's softmax is the embedder's batch
+    /// similarity; guess rows are softmaxed at CLIP's logit scale.
+    pub fn similarity_matrix(
+        &self,
+        image_path: &str,
+        guesses: &[String],
+    ) -> Result<SimilarityMatrix> {
+        let image_embedding = self.embedder.get_image_embedding(image_path)?;
+        let text_embeddings = guesses
+            .iter()
+            .map(|guess| self.embedder.get_text_embedding(guess))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut raw = Vec::with_capacity(guesses.len() + 1);
+        for reference in std::iter::once(&image_embedding).chain(&text_embeddings) {
+            let row = text_embeddings
+                .iter()
+                .map(|text| cosine_similarity(reference, text))
+                .collect::<Result<Vec<_>>>()
+                .map_err(explain_dimension_mismatch)?;
+            raw.push(row);
+        }
+
+        let mut softmax = Vec::with_capacity(raw.len());
+        softmax.push(
+            self.embedder
+                .calculate_batch_similarities(image_path, guesses)
+                .map_err(explain_dimension_mismatch)?,
+        );
+        for row in &raw[1..] {
+            softmax.push(softmax_percentages(row, 1.0 / CLIP_LOGIT_SCALE)?);
+        }
+
+        Ok(SimilarityMatrix {
+            references: std::iter::once(image_path.to_string())
+                .chain(guesses.iter().cloned())
+                .collect(),
+            guesses: guesses.to_vec(),
+            raw,
+            softmax,
+        })
     }
 
     /// Get raw batch similarities directly from embedder (for testing)
@@ -178,45 +805,93 @@ impl<E: EmbedderTrait, S: ScoringStrategy> ScoreValidator<E, S> {
     }
 }
 
-/// Calculate rankings for guesses based on similarity to target image
+/// Something ranked by how similar its guess is to the target image
+///
+/// Guesses and participants are ranked by the same code, so a fix to the
+/// ordering applies to both.
+pub trait Rankable {
+    /// Guess text scored against the target image
+    fn guess_text(&self) -> &str;
+}
+
+impl Rankable for String {
+    fn guess_text(&self) -> &str {
+        self
+    }
+}
+
+impl Rankable for Participant {
+    fn guess_text(&self) -> &str {
+        &self.guess.text
+    }
+}
+
+impl<T: Rankable + ?Sized> Rankable for &T {
+    fn guess_text(&self) -> &str {
+        (**self).guess_text()
+    }
+}
+
+/// Sort scored items by score, highest first
+///
+/// Items with equal scores keep their input order, so the ranking is the same
+/// on every run.
+pub fn rank_by_score<T: Rankable>(scored: &mut [(T, f64)]) {
+    // Stable, so ties stay in input order
+    scored.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1).unwrap_or_else(|| {
+            panic!("CRITICAL: Invalid similarity scores detected (NaN/Inf) for guesses '{}' (score: {}) and '{}' (score: {}). Cannot rank participants reliably.", 
+                   a.0.guess_text(), a.1, b.0.guess_text(), b.1);
+        })
+    });
+}
+
+/// Score items against the target image and rank them
 ///
 /// Uses proper CLIP batch processing with softmax for competitive rankings.
 ///
 /// # Arguments
 /// * `target_image_path` - Path to the target image
-/// * `guesses` - List of text guesses to rank
+/// * `items` - Guesses or participants to rank
 /// * `validator` - Score validator to use
 ///
 /// # Returns
-/// List of tuples (guess, similarity) sorted by similarity (highest to lowest)
-pub fn calculate_rankings<E: EmbedderTrait, S: ScoringStrategy>(
+/// Each item with its similarity, sorted by similarity (highest to lowest).
+/// Items with equal similarity keep their input order.
+pub fn rank_items<'a, T: Rankable, E: EmbedderTrait, S: ScoringStrategy>(
     target_image_path: &str,
-    guesses: &[String],
+    items: &'a [T],
     validator: &ScoreValidator<E, S>,
-) -> Result<Vec<(String, f64)>> {
-    if guesses.is_empty() {
+) -> Result<Vec<(&'a T, f64)>> {
+    if items.is_empty() {
         return Err(ScoringError::EmptyGuesses.into());
     }
 
     // Use the new batch similarity calculation (correct CLIP approach)
-    let similarities = validator.calculate_batch_similarities(target_image_path, guesses)?;
-
-    // Pair guesses with their similarities
-    let mut paired_results: Vec<(String, f64)> = guesses
-        .iter()
-        .zip(similarities.iter())
-        .map(|(guess, &sim)| (guess.clone(), sim))
-        .collect();
+    let guesses: Vec<String> = items.iter().map(|item| item.guess_text().to_string()).collect();
+    let similarities = validator.calculate_batch_similarities(target_image_path, &guesses)?;
 
-    // Sort by similarity score (highest to lowest)
-    paired_results.sort_by(|a, b| {
-        b.1.partial_cmp(&a.1).unwrap_or_else(|| {
-            panic!("CRITICAL: Invalid similarity scores detected (NaN/Inf) for guesses '{}' (score: {}) and '{}' (score: {}). Cannot rank participants reliably.", 
-                   a.0, a.1, b.0, b.1);
-        })
-    });
+    let mut ranked: Vec<(&T, f64)> = items.iter().zip(similarities).collect();
+    rank_by_score(&mut ranked);
+    Ok(ranked)
+}
 
-    Ok(paired_results)
+/// Calculate rankings for guesses based on similarity to target image
+///
+/// # Returns
+/// List of tuples (guess, similarity) sorted by similarity (highest to lowest).
+/// Guesses with equal similarity keep their input order, so the ranking is
+/// the same on every run.
+#[deprecated(note = "use `rank_items`, which ranks participants as well as guesses")]
+pub fn calculate_rankings<E: EmbedderTrait, S: ScoringStrategy>(
+    target_image_path: &str,
+    guesses: &[String],
+    validator: &ScoreValidator<E, S>,
+) -> Result<Vec<(String, f64)>> {
+    Ok(rank_items(target_image_path, guesses, validator)?
+        .into_iter()
+        .map(|(guess, sim)| (guess.clone(), sim))
+        .collect())
 }
 
 /// Calculate payouts based on rankings
@@ -295,43 +970,93 @@ pub fn calculate_payouts(ranked_results: &[(String, f64)], prize_pool: f64) -> R
     Ok(payouts)
 }
 
+/// A participant ranked on its adjusted score, with the raw score it started from
+struct RawScored<'a> {
+    participant: &'a Participant,
+    raw_score: f64,
+}
+
+impl Rankable for RawScored<'_> {
+    fn guess_text(&self) -> &str {
+        self.participant.guess_text()
+    }
+}
+
 /// Process participants and calculate their scores and payouts
+///
+/// Payouts follow the linear-by-rank split of `calculate_payouts`.
 pub fn process_participants<E: EmbedderTrait, S: ScoringStrategy>(
     participants: &[Participant],
     target_image_path: &str,
     prize_pool: f64,
     validator: &ScoreValidator<E, S>,
+) -> Result<Vec<ScoringResult>> {
+    score_and_pay(participants, target_image_path, validator, |ranked_results| {
+        calculate_payouts(ranked_results, prize_pool)
+    })
+}
+
+/// Process participants, paying them out with `calculator`
+///
+/// The calculator's curve, fees and rounding decide the split of its prize pool.
+pub fn process_participants_with_calculator<E: EmbedderTrait, S: ScoringStrategy>(
+    participants: &[Participant],
+    target_image_path: &str,
+    calculator: &PayoutCalculator,
+    validator: &ScoreValidator<E, S>,
+) -> Result<Vec<ScoringResult>> {
+    score_and_pay(participants, target_image_path, validator, |ranked_results| {
+        calculator.calculate_payouts(ranked_results)
+    })
+}
+
+/// Rank participants on their adjusted scores and pay them out with `payouts`
+fn score_and_pay<E: EmbedderTrait, S: ScoringStrategy>(
+    participants: &[Participant],
+    target_image_path: &str,
+    validator: &ScoreValidator<E, S>,
+    payouts: impl FnOnce(&[(String, f64)]) -> Result<Vec<f64>>,
 ) -> Result<Vec<ScoringResult>> {
     if participants.is_empty() {
         return Ok(Vec::new());
     }
 
-    // Extract guesses
+    // Rank on the adjusted scores, keeping each participant's raw score
     let guesses: Vec<String> = participants.iter().map(|p| p.guess.text.clone()).collect();
-
-    // Calculate rankings
-    let ranked_results = calculate_rankings(target_image_path, &guesses, validator)?;
+    let scores = validator.calculate_batch_scores(target_image_path, &guesses)?;
+    let mut ranked: Vec<(RawScored, f64)> = participants
+        .iter()
+        .zip(scores)
+        .map(|(participant, (raw_score, adjusted))| (RawScored { participant, raw_score }, adjusted))
+        .collect();
+    rank_by_score(&mut ranked);
 
     // Calculate payouts
-    let payouts = calculate_payouts(&ranked_results, prize_pool)?;
+    let ranked_results: Vec<(String, f64)> = ranked
+        .iter()
+        .map(|(scored, score)| (scored.participant.guess.text.clone(), *score))
+        .collect();
+    let payouts = payouts(&ranked_results)?;
 
     // Create scoring results
-    let mut results = Vec::new();
-    for (i, ((guess, score), payout)) in ranked_results.iter().zip(payouts.iter()).enumerate() {
-        // Find the participant with this guess
-        if let Some(participant) = participants.iter().find(|p| &p.guess.text == guess) {
-            let result = ScoringResult::new(participant.clone(), *score)
-                .with_adjusted_score(*score)
+    let results = ranked
+        .into_iter()
+        .zip(payouts)
+        .enumerate()
+        .map(|(i, ((scored, score), payout))| {
+            ScoringResult::new(scored.participant.clone(), scored.raw_score)
+                .with_adjusted_score(score)
                 .with_rank(i + 1)
-                .with_payout(*payout);
-            results.push(result);
-        }
-    }
+                .with_payout(payout)
+                .with_provenance(validator.embedder_name(), validator.strategy_name())
+        })
+        .collect();
 
     Ok(results)
 }
 
 #[cfg(test)]
+#[allow(deprecated)] // calculate_rankings keeps its coverage
 mod tests {
     use super::*;
     use crate::embedder::MockEmbedder;
@@ -391,6 +1116,373 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_calculate_rankings_ties_keep_input_order() {
+        let guesses: Vec<String> = ["zebra", "apple", "mango", "kiwi"]
+            .iter()
+            .map(|g| g.to_string())
+            .collect();
+        let scores = HashMap::from([
+            ("zebra".to_string(), 0.25),
+            ("apple".to_string(), 0.25),
+            ("mango".to_string(), 0.5),
+            ("kiwi".to_string(), 0.25),
+        ]);
+        let validator = ScoreValidator::new(
+            MockEmbedder::new(128),
+            PrecomputedScoringStrategy::new(scores).unwrap(),
+        );
+
+        for _ in 0..5 {
+            let rankings = calculate_rankings("test.jpg", &guesses, &validator).unwrap();
+            let order: Vec<&str> = rankings.iter().map(|(g, _)| g.as_str()).collect();
+            assert_eq!(order, vec!["mango", "zebra", "apple", "kiwi"]);
+        }
+
+        // Reversing the input reverses the tied guesses too
+        let reversed: Vec<String> = guesses.iter().rev().cloned().collect();
+        let rankings = calculate_rankings("test.jpg", &reversed, &validator).unwrap();
+        let order: Vec<&str> = rankings.iter().map(|(g, _)| g.as_str()).collect();
+        assert_eq!(order, vec!["mango", "kiwi", "apple", "zebra"]);
+    }
+
+    #[test]
+    fn test_validate_with_diagnostics() {
+        let embedder = MockEmbedder::new(128);
+        let validator = ScoreValidator::new(embedder.clone(), ClipBatchStrategy::new());
+
+        let guesses = vec![
+            "a red fox".to_string(),
+            "a blue whale".to_string(),
+            "a red fox".to_string(),
+            "".to_string(),
+        ];
+        let diagnostics = validator.validate_with_diagnostics("test.jpg", &guesses).unwrap();
+
+        assert_eq!(diagnostics.len(), 4);
+        let image = embedder.get_image_embedding("test.jpg").unwrap();
+        for (diagnostic, guess) in diagnostics.iter().zip(&guesses) {
+            assert_eq!(&diagnostic.guess, guess);
+            // The mock has no tokenizer
+            assert_eq!(diagnostic.token_count, None);
+            assert!(!diagnostic.truncated);
+            if !guess.is_empty() {
+                let text = embedder.get_text_embedding(guess).unwrap();
+                let expected = cosine_similarity(&image, &text).unwrap();
+                assert!((diagnostic.raw_similarity - expected).abs() < 1e-12);
+            }
+        }
+
+        // The invalid guess scores nothing and ranks last
+        assert_eq!(diagnostics[3].raw_similarity, 0.0);
+        assert_eq!(diagnostics[3].softmax_score, 0.0);
+        assert_eq!(diagnostics[3].rank, 4);
+
+        // Duplicate guesses tie, and ranks follow scores
+        assert_eq!(diagnostics[0].rank, diagnostics[2].rank);
+        for a in &diagnostics {
+            for b in &diagnostics {
+                if a.softmax_score > b.softmax_score {
+                    assert!(a.rank < b.rank);
+                }
+            }
+        }
+        let rankings = calculate_rankings("test.jpg", &guesses, &validator).unwrap();
+        let top = diagnostics.iter().find(|d| d.rank == 1).unwrap();
+        assert_eq!(top.softmax_score, rankings[0].1);
+    }
+
+    #[test]
+    fn test_similarity_matrix_dimensions() {
+        let embedder = MockEmbedder::new(128);
+        let validator = ScoreValidator::new(embedder.clone(), ClipBatchStrategy::new());
+
+        let guesses = vec![
+            "a red fox".to_string(),
+            "a blue whale".to_string(),
+            "a green, leafy \"tree\"".to_string(),
+        ];
+        let matrix = validator.similarity_matrix("test.jpg", &guesses).unwrap();
+
+        assert_eq!(matrix.references.len(), 4);
+        assert_eq!(matrix.references[0], "test.jpg");
+        assert_eq!(matrix.guesses, guesses);
+        for rows in [&matrix.raw, &matrix.softmax] {
+            assert_eq!(rows.len(), 4);
+            assert!(rows.iter().all(|row| row.len() == 3));
+        }
+
+        // The image row matches scoring; each guess is identical to itself
+        let image = embedder.get_image_embedding("test.jpg").unwrap();
+        let fox = embedder.get_text_embedding("a red fox").unwrap();
+        assert!((matrix.raw[0][0] - cosine_similarity(&image, &fox).unwrap()).abs() < 1e-12);
+        assert_eq!(
+            matrix.softmax[0],
+            validator.calculate_batch_similarities("test.jpg", &guesses).unwrap()
+        );
+        for i in 0..guesses.len() {
+            assert!((matrix.raw[i + 1][i] - 1.0).abs() < 1e-9);
+        }
+
+        // Softmax rows are percentages
+        for row in &matrix.softmax {
+            assert!((row.iter().sum::<f64>() - 100.0).abs() < 1e-6);
+        }
+
+        // One header plus a raw and a softmax line per reference
+        let csv = matrix.to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(
+            lines[0],
+            "matrix,reference,a red fox,a blue whale,\"a green, leafy \"\"tree\"\"\""
+        );
+        assert!(lines[1].starts_with("raw,test.jpg,"));
+        assert!(lines[5].starts_with("softmax,test.jpg,"));
+    }
+
+    /// Image embeddings from a 768-dim model, guess embeddings from a stale 512-dim cache
+    struct StaleCache;
+
+    impl EmbedderTrait for StaleCache {
+        fn get_image_embedding(&self, image_path: &str) -> Result<Array1<f64>> {
+            MockEmbedder::new(768).get_image_embedding(image_path)
+        }
+        fn get_text_embedding(&self, text: &str) -> Result<Array1<f64>> {
+            MockEmbedder::new(512).get_text_embedding(text)
+        }
+        fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+            let image = self.get_image_embedding(image_path)?;
+            texts
+                .iter()
+                .map(|text| cosine_similarity(&image, &self.get_text_embedding(text)?))
+                .collect()
+        }
+        fn embedding_dim(&self) -> usize {
+            768
+        }
+    }
+
+    #[test]
+    fn test_guesses_and_participants_rank_identically() {
+        use crate::types::Guess;
+
+        let validator = ScoreValidator::new(MockEmbedder::clip_like(), ClipBatchStrategy::new());
+        let guesses: Vec<String> = ["a red fox", "a blue whale", "a red fox", "a green tree"]
+            .iter()
+            .map(|g| g.to_string())
+            .collect();
+        let participants: Vec<Participant> = guesses
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                Participant::new(
+                    format!("user{}", i),
+                    format!("user_{}", i),
+                    Guess::new(guess.clone()),
+                    format!("commitment{}", i),
+                )
+            })
+            .collect();
+
+        let by_guess: Vec<(&str, f64)> = rank_items("test.jpg", &guesses, &validator)
+            .unwrap()
+            .into_iter()
+            .map(|(guess, score)| (guess.as_str(), score))
+            .collect();
+        let by_participant: Vec<(&str, f64)> = rank_items("test.jpg", &participants, &validator)
+            .unwrap()
+            .into_iter()
+            .map(|(participant, score)| (participant.guess_text(), score))
+            .collect();
+        assert_eq!(by_guess, by_participant);
+
+        // Each result belongs to its own participant, even for a repeated guess
+        let results = process_participants(&participants, "test.jpg", 100.0, &validator).unwrap();
+        let mut usernames: Vec<&str> = results
+            .iter()
+            .map(|r| r.participant.username.as_str())
+            .collect();
+        usernames.sort();
+        assert_eq!(usernames, vec!["user_0", "user_1", "user_2", "user_3"]);
+        let ranked: Vec<(&str, f64)> = results
+            .iter()
+            .map(|r| (r.participant.guess.text.as_str(), r.raw_score))
+            .collect();
+        assert_eq!(ranked, by_guess);
+    }
+
+    /// Mock embedder counting its batch similarity calls
+    struct CountingEmbedder {
+        inner: MockEmbedder,
+        model: &'static str,
+        batches: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CountingEmbedder {
+        fn new(model: &'static str) -> Self {
+            Self {
+                inner: MockEmbedder::clip_like(),
+                model,
+                batches: std::sync::atomic::AtomicUsize::new(0),
+            }
+        }
+
+        fn batches(&self) -> usize {
+            self.batches.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl EmbedderTrait for CountingEmbedder {
+        fn get_image_embedding(&self, image_path: &str) -> Result<Array1<f64>> {
+            self.inner.get_image_embedding(image_path)
+        }
+        fn get_text_embedding(&self, text: &str) -> Result<Array1<f64>> {
+            self.inner.get_text_embedding(text)
+        }
+        fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+            self.batches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.inner.calculate_batch_similarities(image_path, texts)
+        }
+        fn embedding_dim(&self) -> usize {
+            self.inner.embedding_dim()
+        }
+        fn model_id(&self) -> String {
+            self.model.to_string()
+        }
+    }
+
+    #[test]
+    fn test_second_run_served_from_scoring_cache() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let image_path = temp_dir.path().join("target.jpg");
+        fs::write(&image_path, b"image bytes").unwrap();
+        let image_path = image_path.to_string_lossy();
+        let guesses = vec!["a red fox".to_string(), "a blue whale".to_string()];
+
+        let first = ScoreValidator::new(CountingEmbedder::new("v1"), ClipBatchStrategy::new())
+            .with_scoring_cache(ScoringCache::in_data_dir(temp_dir.path()).unwrap());
+        let scores = first.calculate_batch_similarities(&image_path, &guesses).unwrap();
+        assert_eq!(first.embedder.batches(), 1);
+
+        // A later run loads the saved cache and never calls the embedder
+        let second = ScoreValidator::new(CountingEmbedder::new("v1"), ClipBatchStrategy::new())
+            .with_scoring_cache(ScoringCache::in_data_dir(temp_dir.path()).unwrap());
+        assert_eq!(second.calculate_batch_similarities(&image_path, &guesses).unwrap(), scores);
+        assert_eq!(second.embedder.batches(), 0);
+
+        // Another model id misses the cache
+        let upgraded = ScoreValidator::new(CountingEmbedder::new("v2"), ClipBatchStrategy::new())
+            .with_scoring_cache(ScoringCache::in_data_dir(temp_dir.path()).unwrap());
+        upgraded.calculate_batch_similarities(&image_path, &guesses).unwrap();
+        assert_eq!(upgraded.embedder.batches(), 1);
+
+        // So does a changed image
+        fs::write(temp_dir.path().join("target.jpg"), b"other image").unwrap();
+        second.calculate_batch_similarities(&image_path, &guesses).unwrap();
+        assert_eq!(second.embedder.batches(), 1);
+    }
+
+    #[test]
+    fn test_dimension_mismatch_names_both_sizes() {
+        let validator = ScoreValidator::new(StaleCache, ClipBatchStrategy::new());
+        let guesses = vec!["a red fox".to_string()];
+
+        let error = validator
+            .calculate_batch_similarities("test.jpg", &guesses)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CliptionsError::Scoring(ScoringError::EmbeddingModelMismatch { a: 768, b: 512 })
+        ));
+        let message = error.to_string();
+        assert!(message.contains("768 vs 512"));
+        assert!(message.contains("precomputed embedding cache"));
+
+        assert!(matches!(
+            validator.similarity_matrix("test.jpg", &guesses).unwrap_err(),
+            CliptionsError::Scoring(ScoringError::EmbeddingModelMismatch { a: 768, b: 512 })
+        ));
+
+        // Other errors pass through untouched
+        let other = explain_dimension_mismatch(EmbeddingError::ModelLoadFailed.into());
+        assert!(matches!(other, CliptionsError::Embedding(EmbeddingError::ModelLoadFailed)));
+    }
+
+    /// Mock embedder counting one token per word plus start and end tokens
+    struct WordTokens(MockEmbedder);
+
+    impl EmbedderTrait for WordTokens {
+        fn get_image_embedding(&self, image_path: &str) -> Result<Array1<f64>> {
+            self.0.get_image_embedding(image_path)
+        }
+        fn get_text_embedding(&self, text: &str) -> Result<Array1<f64>> {
+            self.0.get_text_embedding(text)
+        }
+        fn calculate_batch_similarities(&self, image_path: &str, texts: &[String]) -> Result<Vec<f64>> {
+            self.0.calculate_batch_similarities(image_path, texts)
+        }
+        fn embedding_dim(&self) -> usize {
+            self.0.embedding_dim()
+        }
+        fn token_count(&self, text: &str) -> Result<Option<usize>> {
+            Ok(Some(text.split_whitespace().count() + 2))
+        }
+        fn truncation(&self, text: &str) -> Result<Option<Truncation>> {
+            let tokens = text.split_whitespace().count() + 2;
+            Ok((tokens > 77).then_some(Truncation { tokens, max: 77 }))
+        }
+    }
+
+    #[test]
+    fn test_diagnostics_flag_truncated_guesses() {
+        let validator =
+            ScoreValidator::new(WordTokens(MockEmbedder::new(128)), ClipBatchStrategy::new());
+        let guesses = vec!["a red fox".to_string(), "ox ".repeat(75)];
+        let diagnostics = validator.validate_with_diagnostics("test.jpg", &guesses).unwrap();
+
+        assert_eq!(diagnostics[0].token_count, Some(5));
+        assert!(!diagnostics[0].truncated);
+        assert_eq!(diagnostics[1].token_count, Some(77));
+        assert!(!diagnostics[1].truncated);
+
+        let guesses = vec!["ox ".repeat(76)];
+        let diagnostics = validator.validate_with_diagnostics("test.jpg", &guesses).unwrap();
+        assert_eq!(diagnostics[0].token_count, Some(78));
+        assert!(diagnostics[0].truncated);
+    }
+
+    #[test]
+    fn test_reject_overlength_guesses() {
+        let long_guess = "ox ".repeat(76);
+        let guesses = vec!["a red fox".to_string(), long_guess.clone()];
+
+        // Truncation is the default, so the long guess is still scored
+        let validator =
+            ScoreValidator::new(WordTokens(MockEmbedder::new(128)), ClipBatchStrategy::new());
+        assert!(validator.validate_guess(&long_guess));
+        let scores = validator.calculate_batch_similarities("test.jpg", &guesses).unwrap();
+        assert!(scores[1] > 0.0);
+        assert_eq!(validator.truncation("a red fox").unwrap(), None);
+        assert_eq!(
+            validator.truncation(&long_guess).unwrap(),
+            Some(Truncation { tokens: 78, max: 77 })
+        );
+
+        let validator = validator.with_token_limit_policy(TokenLimitPolicy::Reject);
+        assert!(validator.validate_guess("a red fox"));
+        assert!(!validator.validate_guess(&long_guess));
+        assert_eq!(validator.truncation(&long_guess).unwrap(), None);
+        let scores = validator.calculate_batch_similarities("test.jpg", &guesses).unwrap();
+        assert_eq!(scores[1], 0.0);
+        assert!((scores[0] - 100.0).abs() < 1e-9);
+
+        // Without token counts there is nothing to reject on
+        let validator = ScoreValidator::new(MockEmbedder::new(128), ClipBatchStrategy::new())
+            .with_token_limit_policy(TokenLimitPolicy::Reject);
+        assert!(validator.validate_guess(&long_guess));
+    }
+
     #[test]
     fn test_calculate_payouts_no_ties() {
         let ranked_results = vec![
@@ -472,4 +1564,294 @@ mod tests {
             ))
         ));
     }
+
+    #[test]
+    fn test_process_participants_records_provenance() {
+        use crate::types::Guess;
+
+        let embedder = MockEmbedder::new(128);
+        let strategy = ClipBatchStrategy::new();
+        let validator = ScoreValidator::new(embedder, strategy);
+
+        let participants: Vec<Participant> = ["a red car", "a blue boat"]
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                Participant::new(
+                    format!("user{}", i),
+                    format!("user_{}", i),
+                    Guess::new(guess.to_string()),
+                    "commitment".to_string(),
+                )
+            })
+            .collect();
+
+        let before = chrono::Utc::now();
+        let results = process_participants(&participants, "test.jpg", 100.0, &validator).unwrap();
+
+        assert_eq!(results.len(), 2);
+        for result in &results {
+            assert_eq!(result.embedder_name.as_deref(), Some("MockEmbedder"));
+            assert_eq!(result.strategy_name.as_deref(), Some("ClipBatch"));
+            assert!(result.computed_at.unwrap() >= before);
+            assert_eq!(result.effective_score(), result.raw_score);
+        }
+
+        // Provenance survives a round trip through blocks.json serialization
+        let json = serde_json::to_string(&results[0]).unwrap();
+        let restored: ScoringResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, results[0]);
+    }
+
+    #[test]
+    fn test_raw_score_is_the_similarity_before_adjustment() {
+        use crate::types::Guess;
+
+        let participants: Vec<Participant> = ["a red car", "a blue boat"]
+            .iter()
+            .map(|guess| {
+                Participant::new(
+                    guess.to_string(),
+                    guess.to_string(),
+                    Guess::new(guess.to_string()),
+                    "commitment".to_string(),
+                )
+            })
+            .collect();
+        let guesses: Vec<String> = participants.iter().map(|p| p.guess.text.clone()).collect();
+        let clip = ScoreValidator::new(MockEmbedder::new(128), ClipBatchStrategy::new())
+            .calculate_batch_similarities("test.jpg", &guesses)
+            .unwrap();
+
+        let strategy = BlendedStrategy::new(ClipBatchStrategy::new(), 0.0)
+            .unwrap()
+            .with_reference("a blue boat");
+        let validator = ScoreValidator::new(MockEmbedder::new(128), strategy);
+        let results = process_participants(&participants, "test.jpg", 100.0, &validator).unwrap();
+
+        for result in &results {
+            let index = guesses.iter().position(|g| *g == result.participant.guess.text).unwrap();
+            assert!((result.raw_score - clip[index]).abs() < 1e-10);
+        }
+        assert_eq!(results[0].participant.guess.text, "a blue boat");
+        assert!((results[0].effective_score() - 100.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_blended_strategy_alpha_extremes() {
+        let guesses = vec![
+            "a red car on a road".to_string(),
+            "a dog".to_string(),
+            "a red car".to_string(),
+        ];
+        let reference = "a red car parked on a road";
+
+        let clip_only = ScoreValidator::new(MockEmbedder::new(128), ClipBatchStrategy::new())
+            .calculate_batch_similarities("test.jpg", &guesses)
+            .unwrap();
+
+        // alpha = 1 reproduces pure CLIP scores
+        let strategy = BlendedStrategy::new(ClipBatchStrategy::new(), 1.0)
+            .unwrap()
+            .with_reference(reference);
+        let blended = ScoreValidator::new(MockEmbedder::new(128), strategy)
+            .calculate_batch_similarities("test.jpg", &guesses)
+            .unwrap();
+        for (b, c) in blended.iter().zip(clip_only.iter()) {
+            assert!((b - c).abs() < 1e-10);
+        }
+
+        // alpha = 0 reproduces lexical ordering
+        let strategy = BlendedStrategy::new(ClipBatchStrategy::new(), 0.0)
+            .unwrap()
+            .with_reference(reference);
+        let lexical: Vec<f64> = guesses
+            .iter()
+            .map(|g| strategy.lexical_score(g).unwrap())
+            .collect();
+        let validator = ScoreValidator::new(MockEmbedder::new(128), strategy);
+        let rankings = calculate_rankings("test.jpg", &guesses, &validator).unwrap();
+        let ranked_guesses: Vec<&str> = rankings.iter().map(|(g, _)| g.as_str()).collect();
+        assert_eq!(
+            ranked_guesses,
+            vec!["a red car on a road", "a red car", "a dog"]
+        );
+        assert!(lexical[0] > lexical[2] && lexical[2] > lexical[1]);
+        assert_eq!(validator.strategy_name(), "Blended");
+    }
+
+    #[test]
+    fn test_blended_strategy_validation() {
+        assert!(BlendedStrategy::new(ClipBatchStrategy::new(), -0.1).is_err());
+        assert!(BlendedStrategy::new(ClipBatchStrategy::new(), 1.5).is_err());
+
+        // Without a reference caption there is nothing to compare against
+        let strategy = BlendedStrategy::new(ClipBatchStrategy::new(), 0.5).unwrap();
+        assert!(strategy.lexical_score("a red car").is_err());
+
+        // A block's reference caption is used in place of the configured one
+        let for_block = strategy.with_block_reference("a red car").unwrap();
+        let scores = for_block
+            .adjust_batch_scores(&["a red car".to_string()], vec![40.0])
+            .unwrap();
+        assert!((scores[0] - 70.0).abs() < 1e-9);
+        assert!(ClipBatchStrategy::new().with_block_reference("a red car").is_none());
+
+        // Individual scores weight the CLIP similarity by alpha
+        let features = Array1::from_vec(vec![1.0, 0.0]);
+        let score = strategy.calculate_score(&features, &features).unwrap();
+        assert!((score - 50.0).abs() < 1e-9);
+
+        assert_eq!(token_jaccard("A red car!", "a RED car"), 1.0);
+        assert_eq!(token_jaccard("", ""), 0.0);
+    }
+
+    /// Embedder that fails if scoring ever reaches it
+    struct NoEmbedder;
+
+    impl EmbedderTrait for NoEmbedder {
+        fn get_image_embedding(&self, _image_path: &str) -> Result<Array1<f64>> {
+            Err(crate::error::EmbeddingError::ModelLoadFailed.into())
+        }
+        fn get_text_embedding(&self, _text: &str) -> Result<Array1<f64>> {
+            Err(crate::error::EmbeddingError::ModelLoadFailed.into())
+        }
+        fn calculate_batch_similarities(&self, _image_path: &str, _texts: &[String]) -> Result<Vec<f64>> {
+            Err(crate::error::EmbeddingError::ModelLoadFailed.into())
+        }
+        fn embedding_dim(&self) -> usize {
+            0
+        }
+        fn name(&self) -> &str {
+            "NoEmbedder"
+        }
+    }
+
+    #[test]
+    fn test_precomputed_strategy_rankings_and_payouts() {
+        use crate::types::Guess;
+
+        let similarities: HashMap<String, f64> = [
+            ("a red car", 30.0),
+            ("a blue boat", 50.0),
+            ("a green tree", 20.0),
+            ("a yellow sun", 30.0),
+        ]
+        .iter()
+        .map(|(guess, score)| (guess.to_string(), *score))
+        .collect();
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), serde_json::to_string(&similarities).unwrap()).unwrap();
+
+        let strategy = PrecomputedScoringStrategy::from_file(temp_file.path()).unwrap();
+        let validator = ScoreValidator::new(NoEmbedder, strategy);
+        assert_eq!(validator.strategy_name(), "Precomputed");
+
+        let participants: Vec<Participant> = ["a red car", "a blue boat", "a green tree"]
+            .iter()
+            .enumerate()
+            .map(|(i, guess)| {
+                Participant::new(
+                    format!("user{}", i),
+                    format!("user_{}", i),
+                    Guess::new(guess.to_string()),
+                    "commitment".to_string(),
+                )
+            })
+            .collect();
+
+        let results = process_participants(&participants, "unused.jpg", 60.0, &validator).unwrap();
+        let summary: Vec<(&str, f64, usize, f64)> = results
+            .iter()
+            .map(|r| {
+                (
+                    r.participant.guess.text.as_str(),
+                    r.raw_score,
+                    r.rank.unwrap(),
+                    r.payout.unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a blue boat", 50.0, 1, 30.0),
+                ("a red car", 30.0, 2, 20.0),
+                ("a green tree", 20.0, 3, 10.0),
+            ]
+        );
+
+        // Equal similarities tie and split their positions
+        let guesses = vec!["a red car".to_string(), "a yellow sun".to_string()];
+        let rankings = calculate_rankings("unused.jpg", &guesses, &validator).unwrap();
+        assert_eq!(calculate_payouts(&rankings, 30.0).unwrap(), vec![15.0, 15.0]);
+    }
+
+    #[test]
+    fn test_precomputed_strategy_missing_guess() {
+        let similarities = HashMap::from([("a red car".to_string(), 30.0)]);
+        let validator = ScoreValidator::new(
+            NoEmbedder,
+            PrecomputedScoringStrategy::new(similarities).unwrap(),
+        );
+
+        let guesses = vec!["a red car".to_string(), "a purple cow".to_string()];
+        assert!(matches!(
+            calculate_rankings("unused.jpg", &guesses, &validator),
+            Err(CliptionsError::Scoring(ScoringError::MissingSimilarity { guess }))
+                if guess == "a purple cow"
+        ));
+
+        assert!(
+            PrecomputedScoringStrategy::new(HashMap::from([("x".to_string(), f64::NAN)])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_strategy_registry_resolves_by_name() {
+        let mut registry = StrategyRegistry::new();
+        registry.register("clip", |_| Ok(Box::new(ClipBatchStrategy::new())));
+        registry.register("fixed", |params| {
+            let score = params.get("score").and_then(|v| v.as_f64()).unwrap_or(50.0);
+            let similarities = HashMap::from([("a cat".to_string(), score)]);
+            Ok(Box::new(PrecomputedScoringStrategy::new(similarities)?))
+        });
+        assert_eq!(registry.names(), vec!["clip", "fixed"]);
+
+        let clip = registry.create("clip", &StrategyParams::new()).unwrap();
+        assert_eq!(clip.name(), "ClipBatch");
+
+        let params = StrategyParams::from([("score".to_string(), serde_json::json!(75.0))]);
+        let fixed = registry.create("fixed", &params).unwrap();
+        assert_eq!(fixed.name(), "Precomputed");
+        assert_eq!(
+            fixed.precomputed_scores(&["a cat".to_string()]).unwrap(),
+            Some(vec![75.0])
+        );
+
+        let err = registry.create("nope", &StrategyParams::new()).err().unwrap();
+        assert!(err.to_string().contains("available: clip, fixed"), "{}", err);
+    }
+
+    #[test]
+    fn test_builtin_strategy_registry() {
+        let registry = StrategyRegistry::with_builtin();
+        assert_eq!(registry.names(), vec!["blended", "clip-batch", "precomputed"]);
+
+        let default = registry
+            .create(StrategyRegistry::DEFAULT_STRATEGY, &StrategyParams::new())
+            .unwrap();
+        assert_eq!(default.name(), "ClipBatch");
+
+        assert!(registry.create("blended", &StrategyParams::new()).is_err());
+        let params = StrategyParams::from([
+            ("alpha".to_string(), serde_json::json!(0.5)),
+            ("reference".to_string(), serde_json::json!("a red car")),
+        ]);
+        let blended = registry.create("blended", &params).unwrap();
+        let scores = blended
+            .adjust_batch_scores(&["a red car".to_string()], vec![40.0])
+            .unwrap();
+        assert!((scores[0] - 70.0).abs() < 1e-9);
+    }
 }
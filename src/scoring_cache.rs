@@ -0,0 +1,215 @@
+//! Persistent cache of guess similarities, so re-scoring a block skips the embedder
+//!
+//! Entries are keyed on the target image's content hash, the normalized guess
+//! and the embedder's name and model id, so a new image or model never reuses
+//! old scores. Batch similarities are a softmax over every guess scored
+//! together, so each key also carries a digest of the whole batch: a score is
+//! only reused when the same guesses are ranked against the same image again,
+//! as when re-running payouts for a block.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::data_file;
+use crate::error::Result;
+
+/// File name of the cache inside a data directory
+pub const SCORING_CACHE_FILE: &str = "scoring_cache.json";
+
+/// What a cached similarity was computed from
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ScoreKey {
+    /// SHA-256 of the target image file
+    pub image_hash: String,
+    /// Guess text with surrounding whitespace trimmed and inner runs collapsed
+    pub guess: String,
+    /// Embedder name and model id
+    pub embedder: String,
+    /// Digest of every normalized guess in the batch
+    pub batch: String,
+}
+
+/// One stored similarity, as written to the cache file
+///
+/// serde_json's `float_roundtrip` feature makes the similarity read back
+/// bit-for-bit, so cached scores rank exactly like fresh ones.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(flatten)]
+    key: ScoreKey,
+    similarity: f64,
+}
+
+/// Similarities stored in a JSON file
+#[derive(Debug)]
+pub struct ScoringCache {
+    path: PathBuf,
+    entries: HashMap<ScoreKey, f64>,
+}
+
+impl ScoringCache {
+    /// Load the cache at `path`, starting empty if the file doesn't exist yet
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut entries = HashMap::new();
+        if path.exists() {
+            let stored: Vec<CacheEntry> = serde_json::from_str(&data_file::read_to_string(&path)?)?;
+            for entry in stored {
+                entries.insert(entry.key, entry.similarity);
+            }
+        }
+        Ok(Self { path, entries })
+    }
+
+    /// Load the cache kept in `data_dir`
+    pub fn in_data_dir(data_dir: impl AsRef<Path>) -> Result<Self> {
+        Self::open(data_dir.as_ref().join(SCORING_CACHE_FILE))
+    }
+
+    /// Path of the cache file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of cached similarities
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Cached similarities for `guesses`, in order, if every one is cached
+    pub fn lookup(&self, image_hash: &str, embedder: &str, guesses: &[String]) -> Option<Vec<f64>> {
+        let batch = batch_digest(guesses);
+        guesses
+            .iter()
+            .map(|guess| {
+                let key = score_key(image_hash, embedder, &batch, guess);
+                self.entries.get(&key).copied()
+            })
+            .collect()
+    }
+
+    /// Store the similarities computed for `guesses`, in the same order
+    pub fn insert(
+        &mut self,
+        image_hash: &str,
+        embedder: &str,
+        guesses: &[String],
+        similarities: &[f64],
+    ) {
+        let batch = batch_digest(guesses);
+        for (guess, &similarity) in guesses.iter().zip(similarities) {
+            let key = score_key(image_hash, embedder, &batch, guess);
+            self.entries.insert(key, similarity);
+        }
+    }
+
+    /// Write the cache file, creating its directory if needed
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Sorted, so unchanged caches are written byte-for-byte the same
+        let mut stored: Vec<CacheEntry> = self
+            .entries
+            .iter()
+            .map(|(key, &similarity)| CacheEntry {
+                key: key.clone(),
+                similarity,
+            })
+            .collect();
+        stored.sort_by(|a, b| a.key.cmp(&b.key));
+
+        data_file::write(&self.path, serde_json::to_string_pretty(&stored)?)?;
+        Ok(())
+    }
+}
+
+/// Guess text with surrounding whitespace trimmed and inner runs collapsed
+///
+/// CLIP's tokenizer ignores such whitespace differences, so they don't change the score.
+fn collapse_whitespace(guess: &str) -> String {
+    guess.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn score_key(image_hash: &str, embedder: &str, batch: &str, guess: &str) -> ScoreKey {
+    ScoreKey {
+        image_hash: image_hash.to_string(),
+        guess: collapse_whitespace(guess),
+        embedder: embedder.to_string(),
+        batch: batch.to_string(),
+    }
+}
+
+/// Digest of a batch's normalized guesses, independent of their order
+fn batch_digest(guesses: &[String]) -> String {
+    let mut normalized: Vec<String> = guesses.iter().map(|g| collapse_whitespace(g)).collect();
+    normalized.sort();
+
+    let mut hasher = Sha256::new();
+    for guess in &normalized {
+        hasher.update(guess.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn guesses(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_cache_round_trips_through_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = ScoringCache::in_data_dir(temp_dir.path().join("data")).unwrap();
+        assert!(cache.is_empty());
+
+        let batch = guesses(&["a cat", "a dog"]);
+        // Scores that lose their last bit under serde_json's default float parsing
+        cache.insert(
+            "img",
+            "Mock/m1",
+            &batch,
+            &[51.435563223278244, 48.56443677672175],
+        );
+        cache.save().unwrap();
+
+        let cache = ScoringCache::in_data_dir(temp_dir.path().join("data")).unwrap();
+        assert_eq!(cache.len(), 2);
+        // Whitespace and order don't matter
+        assert_eq!(
+            cache.lookup("img", "Mock/m1", &guesses(&["a dog ", "a  cat"])),
+            Some(vec![48.56443677672175, 51.435563223278244])
+        );
+    }
+
+    #[test]
+    fn test_lookup_misses_on_other_image_model_or_batch() {
+        let mut cache = ScoringCache::open("unused.json").unwrap();
+        let batch = guesses(&["a cat", "a dog"]);
+        cache.insert("img", "Mock/m1", &batch, &[70.0, 30.0]);
+
+        assert!(cache.lookup("other", "Mock/m1", &batch).is_none());
+        assert!(cache.lookup("img", "Mock/m2", &batch).is_none());
+        // Softmax scores depend on the other guesses in the batch
+        assert!(cache
+            .lookup("img", "Mock/m1", &guesses(&["a cat", "a bird"]))
+            .is_none());
+        assert!(cache
+            .lookup("img", "Mock/m1", &guesses(&["a cat"]))
+            .is_none());
+    }
+}
@@ -0,0 +1,350 @@
+//! Scoring version registry for Cliptions
+//!
+//! Records which scoring algorithm and parameters produced a block's results,
+//! so payouts can be reproduced after the scoring code changes. The registry
+//! is persisted as `scoring_versions.json` (by default under `~/.cliptions/data/`),
+//! keyed by version id, with the version new payouts use named as `default`:
+//!
+//! ```json
+//! {
+//!     "versions": {
+//!         "v0.2": {
+//!             "description": "Added baseline adjustment",
+//!             "applied_to_blocks": ["block1"],
+//!             "parameters": { "use_baseline_adjustment": true }
+//!         }
+//!     },
+//!     "default": "v0.2"
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CliptionsError, Result};
+use crate::scoring::ScoringStrategy;
+
+/// A named snapshot of a scoring strategy and its parameters
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoringVersion {
+    /// Unique identifier for this version (e.g. "v1"); the key it is stored under
+    #[serde(skip)]
+    pub id: String,
+    /// Name of the scoring strategy, as reported by `ScoringStrategy::name`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub strategy_name: String,
+    /// Strategy parameters that affect scores
+    #[serde(default, rename = "parameters")]
+    pub params: BTreeMap<String, serde_json::Value>,
+    /// What changed in this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Blocks that were scored with this version
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applied_to_blocks: Vec<String>,
+    /// Commit of the scoring code this version describes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_hash: Option<String>,
+    /// When this version was registered; older entries don't record it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl ScoringVersion {
+    /// Create a new scoring version without parameters
+    pub fn new(id: impl Into<String>, strategy_name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            strategy_name: strategy_name.into(),
+            params: BTreeMap::new(),
+            description: None,
+            applied_to_blocks: Vec::new(),
+            commit_hash: None,
+            created_at: Some(Utc::now()),
+        }
+    }
+
+    /// Create a scoring version describing the given strategy
+    pub fn for_strategy<S: ScoringStrategy>(id: impl Into<String>, strategy: &S) -> Self {
+        Self::new(id, strategy.name())
+    }
+
+    /// Add a parameter to this version
+    pub fn with_param(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.params.insert(key.into(), value);
+        self
+    }
+
+    /// Check whether two versions score identically (same strategy and parameters)
+    pub fn same_scoring_as(&self, other: &ScoringVersion) -> bool {
+        self.strategy_name == other.strategy_name && self.params == other.params
+    }
+}
+
+/// Persistent registry of scoring versions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "RegistryFile", into = "RegistryFile")]
+pub struct ScoringVersionRegistry {
+    versions: Vec<ScoringVersion>,
+    default: Option<String>,
+}
+
+/// On-disk form of the registry: versions keyed by id, plus the default id
+#[derive(Serialize, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    versions: BTreeMap<String, ScoringVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+}
+
+impl From<RegistryFile> for ScoringVersionRegistry {
+    fn from(file: RegistryFile) -> Self {
+        let versions = file
+            .versions
+            .into_iter()
+            .map(|(id, version)| ScoringVersion { id, ..version })
+            .collect();
+        Self {
+            versions,
+            default: file.default,
+        }
+    }
+}
+
+impl From<ScoringVersionRegistry> for RegistryFile {
+    fn from(registry: ScoringVersionRegistry) -> Self {
+        Self {
+            versions: registry
+                .versions
+                .into_iter()
+                .map(|version| (version.id.clone(), version))
+                .collect(),
+            default: registry.default,
+        }
+    }
+}
+
+impl ScoringVersionRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default registry location: `~/.cliptions/data/scoring_versions.json`
+    pub fn default_path() -> Result<PathBuf> {
+        let home_dir = dirs::home_dir().ok_or_else(|| {
+            CliptionsError::ConfigError("Could not determine home directory".to_string())
+        })?;
+        Ok(home_dir
+            .join(".cliptions")
+            .join("data")
+            .join("scoring_versions.json"))
+    }
+
+    /// Load a registry from file, returning an empty registry if the file doesn't exist
+    ///
+    /// A `default` that names no registered version is rejected.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() {
+            return Ok(Self::new());
+        }
+
+        let registry: Self = serde_json::from_str(&content)?;
+        if let Some(default) = &registry.default {
+            if registry.get(default).is_none() {
+                return Err(CliptionsError::ValidationError(format!(
+                    "Default scoring version {} is not registered in {}",
+                    default,
+                    path.display()
+                )));
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Save the registry to file, creating parent directories as needed
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Register a version
+    ///
+    /// Re-registering an identical version is a no-op; reusing an id for
+    /// different scoring is rejected, since that would rewrite history.
+    pub fn register(&mut self, version: ScoringVersion) -> Result<()> {
+        if let Some(existing) = self.get(&version.id) {
+            if existing.same_scoring_as(&version) {
+                return Ok(());
+            }
+            return Err(CliptionsError::ValidationError(format!(
+                "Scoring version {} is already registered with different scoring",
+                version.id
+            )));
+        }
+
+        self.versions.push(version);
+        Ok(())
+    }
+
+    /// Look up a version by id
+    pub fn get(&self, id: &str) -> Option<&ScoringVersion> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+
+    /// The most recently registered version (after a reload, the last by id)
+    pub fn latest(&self) -> Option<&ScoringVersion> {
+        self.versions.last()
+    }
+
+    /// All registered versions
+    pub fn versions(&self) -> &[ScoringVersion] {
+        &self.versions
+    }
+
+    /// Make `id` the version new payouts are scored with
+    pub fn set_default(&mut self, id: &str) -> Result<()> {
+        if self.get(id).is_none() {
+            return Err(CliptionsError::ValidationError(format!(
+                "Scoring version {} is not registered",
+                id
+            )));
+        }
+        self.default = Some(id.to_string());
+        Ok(())
+    }
+
+    /// The version new payouts are scored with: `default`, else the latest
+    pub fn default_version(&self) -> Option<&ScoringVersion> {
+        match &self.default {
+            Some(id) => self.get(id),
+            None => self.latest(),
+        }
+    }
+
+    /// Version to score with: the one named by `id`, else the default
+    ///
+    /// Fails when `id` names no registered version.
+    pub fn resolve(&self, id: Option<&str>) -> Result<Option<&ScoringVersion>> {
+        match id {
+            Some(id) => self.get(id).map(Some).ok_or_else(|| {
+                CliptionsError::ValidationError(format!("Scoring version {} is not registered", id))
+            }),
+            None => Ok(self.default_version()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::ClipBatchStrategy;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_registry_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data").join("scoring_versions.json");
+
+        let mut registry = ScoringVersionRegistry::load(&path).unwrap();
+        assert!(registry.versions().is_empty());
+
+        let version = ScoringVersion::for_strategy("v1", &ClipBatchStrategy::new())
+            .with_param("model", serde_json::json!("ViT-B/32"));
+        registry.register(version.clone()).unwrap();
+        registry.save(&path).unwrap();
+
+        let loaded = ScoringVersionRegistry::load(&path).unwrap();
+        assert_eq!(loaded.get("v1"), Some(&version));
+        assert_eq!(loaded.latest().unwrap().strategy_name, "ClipBatch");
+    }
+
+    #[test]
+    fn test_load_keyed_registry_with_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("scoring_versions.json");
+        fs::write(
+            &path,
+            r#"{
+                "versions": {
+                    "v0.1": {
+                        "description": "Original scoring",
+                        "applied_to_blocks": ["block0"],
+                        "parameters": { "use_baseline_adjustment": false },
+                        "commit_hash": "origin/master"
+                    },
+                    "v0.2": {
+                        "parameters": { "use_baseline_adjustment": true }
+                    }
+                },
+                "default": "v0.1"
+            }"#,
+        )
+        .unwrap();
+
+        let mut registry = ScoringVersionRegistry::load(&path).unwrap();
+        let original = registry.get("v0.1").unwrap();
+        assert_eq!(original.id, "v0.1");
+        assert_eq!(original.applied_to_blocks, vec!["block0"]);
+        assert_eq!(original.params["use_baseline_adjustment"], serde_json::json!(false));
+        assert_eq!(original.created_at, None);
+        assert_eq!(registry.default_version().unwrap().id, "v0.1");
+        assert_eq!(registry.resolve(Some("v0.2")).unwrap().unwrap().id, "v0.2");
+        assert!(registry.resolve(Some("v9")).is_err());
+
+        // Saving keeps the keyed format and the default
+        registry.set_default("v0.2").unwrap();
+        registry.save(&path).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["default"], "v0.2");
+        assert_eq!(saved["versions"]["v0.1"]["commit_hash"], "origin/master");
+        assert!(saved["versions"]["v0.1"].get("id").is_none());
+        let reloaded = ScoringVersionRegistry::load(&path).unwrap();
+        assert_eq!(reloaded.resolve(None).unwrap().unwrap().id, "v0.2");
+
+        // A default naming no version is rejected
+        fs::write(&path, r#"{"versions": {}, "default": "v1"}"#).unwrap();
+        assert!(ScoringVersionRegistry::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_repository_registry_loads() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("scoring_versions.json");
+        let registry = ScoringVersionRegistry::load(path).unwrap();
+        assert_eq!(registry.default_version().unwrap().id, "v0.2");
+    }
+
+    #[test]
+    fn test_register_conflicting_id() {
+        let mut registry = ScoringVersionRegistry::new();
+        registry.register(ScoringVersion::new("v1", "ClipBatch")).unwrap();
+
+        // Identical scoring under the same id is accepted
+        registry.register(ScoringVersion::new("v1", "ClipBatch")).unwrap();
+        assert_eq!(registry.versions().len(), 1);
+
+        let changed =
+            ScoringVersion::new("v1", "ClipBatch").with_param("temperature", 0.5.into());
+        assert!(registry.register(changed).is_err());
+    }
+}
@@ -1,12 +1,20 @@
+use crate::clock::{Clock, SystemClock};
+use crate::data_file;
 use crate::error::{CliptionsError, Result};
-use chrono::{DateTime, Duration, Utc};
+use crate::payout::{PayoutInfo, DEFAULT_CURRENCY_LABEL};
+use chrono::{DateTime, SecondsFormat, Utc};
+use chrono_tz::Tz;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use twitter_api::TwitterApi;
 use url::Url;
 
+use crate::twitter_utils::post_tweet_flexible;
+
 /// Tweet ID extracted from URLs
 pub type TweetId = String;
 
@@ -20,6 +28,163 @@ pub struct AnnouncementData {
     pub message: String,
     pub prize_pool: Option<f64>,
     pub livestream_url: Option<String>, // Add this field
+    /// Deadline as an instant; when set, announcements render it with the
+    /// formatter's `DeadlineFormat` instead of using `target_time`
+    #[serde(default)]
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+/// Winners a payouts announcement lists by default
+pub const DEFAULT_ANNOUNCED_WINNERS: usize = 5;
+
+/// Data for announcing a block's payouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutAnnouncementData {
+    pub block_num: u64,
+    /// Paid participants, best first
+    pub winners: Vec<PayoutInfo>,
+    /// Currency label shown next to payout amounts
+    pub currency_label: String,
+    /// Most winners to list; the rest are counted as "and X others"
+    pub top_n: usize,
+    pub hashtags: Vec<String>,
+}
+
+impl PayoutAnnouncementData {
+    /// Announcement of `winners` with the default label and winner count
+    pub fn new(block_num: u64, winners: Vec<PayoutInfo>) -> Self {
+        Self {
+            block_num,
+            winners,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+            top_n: DEFAULT_ANNOUNCED_WINNERS,
+            hashtags: Vec::new(),
+        }
+    }
+}
+
+/// How announcements render deadlines
+///
+/// Deadlines are shown in a local timezone with its abbreviation, followed
+/// by the UTC ISO 8601 timestamp for machine parsing, e.g.
+/// `2025-04-01 | 16:30:00 | EDT (2025-04-01T20:30:00Z)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadlineFormat {
+    pub timezone: Tz,
+}
+
+impl DeadlineFormat {
+    /// Render deadlines in `timezone`
+    pub fn new(timezone: Tz) -> Self {
+        Self { timezone }
+    }
+
+    /// Render a deadline
+    pub fn format(&self, deadline: DateTime<Utc>) -> String {
+        let local = deadline.with_timezone(&self.timezone);
+        format!(
+            "{} ({})",
+            local.format("%Y-%m-%d | %H:%M:%S | %Z"),
+            deadline.to_rfc3339_opts(SecondsFormat::Secs, true)
+        )
+    }
+}
+
+impl Default for DeadlineFormat {
+    fn default() -> Self {
+        Self::new(chrono_tz::US::Eastern)
+    }
+}
+
+/// Prefix of the metadata trailer line
+const METADATA_PREFIX: &str = "cliptions:v";
+
+/// Current metadata trailer version
+const METADATA_VERSION: u32 = 1;
+
+/// Machine-readable metadata appended to announcement tweets
+///
+/// Rendered as a single trailer line such as
+/// `cliptions:v1 block=42 state=revealsopen deadline=2024-01-01T18:00:00Z`,
+/// which `parse_announcement_metadata` reads back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnouncementMetadata {
+    pub version: u32,
+    pub block_num: u64,
+    /// Lowercase state name, as used in the state hashtag
+    pub state: String,
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+impl AnnouncementMetadata {
+    /// Metadata for an announcement
+    pub fn from_announcement(data: &AnnouncementData) -> Self {
+        Self {
+            version: METADATA_VERSION,
+            block_num: data.block_num,
+            state: data.state_name.to_lowercase(),
+            deadline: data.deadline,
+        }
+    }
+
+    /// Render the trailer line
+    pub fn to_line(&self) -> String {
+        let mut line = format!(
+            "{}{} block={} state={}",
+            METADATA_PREFIX, self.version, self.block_num, self.state
+        );
+        if let Some(deadline) = self.deadline {
+            line.push_str(&format!(
+                " deadline={}",
+                deadline.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ));
+        }
+        line
+    }
+}
+
+/// Read the metadata trailer from an announcement's text
+///
+/// Returns `None` if the text has no trailer, the trailer is from an
+/// unsupported version, or it lacks a block number or state. Unknown keys
+/// are ignored.
+pub fn parse_announcement_metadata(text: &str) -> Option<AnnouncementMetadata> {
+    let line = text
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| line.starts_with(METADATA_PREFIX))?;
+
+    let mut fields = line.split_whitespace();
+    let version: u32 = fields.next()?.strip_prefix(METADATA_PREFIX)?.parse().ok()?;
+    if version != METADATA_VERSION {
+        return None;
+    }
+
+    let mut block_num = None;
+    let mut state = None;
+    let mut deadline = None;
+    for field in fields {
+        match field.split_once('=') {
+            Some(("block", value)) => block_num = Some(value.parse().ok()?),
+            Some(("state", value)) => state = Some(value.to_lowercase()),
+            Some(("deadline", value)) => {
+                deadline = Some(
+                    DateTime::parse_from_rfc3339(value)
+                        .ok()?
+                        .with_timezone(&Utc),
+                )
+            }
+            _ => {}
+        }
+    }
+
+    Some(AnnouncementMetadata {
+        version,
+        block_num: block_num?,
+        state: state?,
+        deadline,
+    })
 }
 
 /// Represents a social media task execution context
@@ -71,6 +236,19 @@ impl UrlParser {
         )))
     }
 
+    /// Extract the IDs of every Twitter/X status URL in `text`, in order of
+    /// first appearance and without duplicates
+    pub fn extract_all_tweet_ids(&self, text: &str) -> Vec<TweetId> {
+        let mut tweet_ids: Vec<TweetId> = Vec::new();
+        for captures in self.twitter_regex.captures_iter(text) {
+            let tweet_id = captures[1].to_string();
+            if !tweet_ids.contains(&tweet_id) {
+                tweet_ids.push(tweet_id);
+            }
+        }
+        tweet_ids
+    }
+
     /// Validate URL format
     pub fn validate_url(&self, url: &str) -> Result<()> {
         Url::parse(url)
@@ -93,6 +271,95 @@ impl Default for UrlParser {
     }
 }
 
+/// Root of the conversation a tweet belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversationRoot {
+    /// ID of the tweet that started the conversation
+    pub root_id: TweetId,
+    /// Whether the tweet is the root itself rather than a reply in the thread
+    pub is_root: bool,
+}
+
+/// Find the conversation root of `tweet`
+///
+/// Returns `None` when the tweet was fetched without a `conversation_id`.
+pub fn extract_conversation_root(tweet: &twitter_api::Tweet) -> Option<ConversationRoot> {
+    let root_id = tweet.conversation_id.clone()?;
+    Some(ConversationRoot {
+        is_root: root_id == tweet.id,
+        root_id,
+    })
+}
+
+/// Parent/child structure of a reply thread, rebuilt from a flat list of tweets
+#[derive(Debug, Clone, Default)]
+pub struct ReplyTree {
+    parents: HashMap<TweetId, TweetId>,
+    children: HashMap<TweetId, Vec<TweetId>>,
+}
+
+impl ReplyTree {
+    /// ID of the tweet `tweet_id` replies to, if known
+    pub fn parent(&self, tweet_id: &str) -> Option<&str> {
+        self.parents.get(tweet_id).map(String::as_str)
+    }
+
+    /// IDs of the tweets replying directly to `tweet_id`, in input order
+    pub fn direct_replies(&self, tweet_id: &str) -> &[TweetId] {
+        self.children.get(tweet_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether `tweet_id` replies directly to `parent_id`
+    pub fn is_direct_reply(&self, tweet_id: &str, parent_id: &str) -> bool {
+        self.parent(tweet_id) == Some(parent_id)
+    }
+
+    /// Number of replies between `tweet_id` and `ancestor_id`, 1 for a direct reply
+    ///
+    /// Returns `None` if `ancestor_id` isn't reachable through known parents.
+    pub fn depth_below(&self, tweet_id: &str, ancestor_id: &str) -> Option<usize> {
+        let mut depth = 0;
+        let mut current = tweet_id;
+        while let Some(parent) = self.parent(current) {
+            depth += 1;
+            if parent == ancestor_id {
+                return Some(depth);
+            }
+            // Guard against a cycle in malformed data
+            if depth > self.parents.len() {
+                return None;
+            }
+            current = parent;
+        }
+        None
+    }
+}
+
+/// Reconstruct who replied to whom from `tweets`
+///
+/// A tweet's parent is the tweet in its `replied_to` reference. Tweets
+/// fetched without references fall back to their conversation root, which
+/// treats them as direct replies to it.
+pub fn build_reply_tree(tweets: &[twitter_api::Tweet]) -> ReplyTree {
+    let mut tree = ReplyTree::default();
+    for tweet in tweets {
+        let parent = tweet.replied_to_id().map(str::to_string).or_else(|| {
+            tweet
+                .conversation_id
+                .clone()
+                .filter(|root| *root != tweet.id)
+        });
+        if let Some(parent) = parent {
+            tree.children
+                .entry(parent.clone())
+                .or_default()
+                .push(tweet.id.clone());
+            tree.parents.insert(tweet.id.clone(), parent);
+        }
+    }
+    tree
+}
+
 /// Hashtag manager for social media posts
 pub struct HashtagManager {
     standard_hashtags: Vec<String>,
@@ -187,22 +454,98 @@ impl Default for HashtagManager {
     }
 }
 
+/// An announcement fitted to the tweet limit, with the custom hashtags left out
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedAnnouncement {
+    pub text: String,
+    /// Custom hashtags dropped to fit, lowest priority (last given) first
+    pub dropped_hashtags: Vec<String>,
+}
+
 /// Announcement formatter for different types of block announcements
 pub struct AnnouncementFormatter {
     hashtag_manager: HashtagManager,
+    deadline_format: DeadlineFormat,
+    include_metadata: bool,
+    tweet_limit: usize,
+    currency_label: String,
 }
 
 impl AnnouncementFormatter {
     /// Create a new announcement formatter
     pub fn new() -> Self {
-        Self {
-            hashtag_manager: HashtagManager::new(),
-        }
+        Self::with_hashtag_manager(HashtagManager::new())
     }
 
     /// Create announcement formatter with custom hashtag manager
     pub fn with_hashtag_manager(hashtag_manager: HashtagManager) -> Self {
-        Self { hashtag_manager }
+        Self {
+            hashtag_manager,
+            deadline_format: DeadlineFormat::default(),
+            include_metadata: true,
+            tweet_limit: twitter_api::MAX_TWEET_LENGTH,
+            currency_label: DEFAULT_CURRENCY_LABEL.to_string(),
+        }
+    }
+
+    /// Weighted length that fitted announcements are kept within
+    /// (default: `MAX_TWEET_LENGTH`)
+    pub fn with_tweet_limit(mut self, tweet_limit: usize) -> Self {
+        self.tweet_limit = tweet_limit;
+        self
+    }
+
+    /// Currency label shown next to prize pools (default: `DEFAULT_CURRENCY_LABEL`)
+    pub fn with_currency_label(mut self, currency_label: impl Into<String>) -> Self {
+        self.currency_label = currency_label.into();
+        self
+    }
+
+    /// Whether to append the `AnnouncementMetadata` trailer (on by default)
+    pub fn with_metadata(mut self, include_metadata: bool) -> Self {
+        self.include_metadata = include_metadata;
+        self
+    }
+
+    /// Render deadlines with `deadline_format`
+    pub fn with_deadline_format(mut self, deadline_format: DeadlineFormat) -> Self {
+        self.deadline_format = deadline_format;
+        self
+    }
+
+    /// Render a deadline with this formatter's `DeadlineFormat`
+    pub fn format_deadline(&self, deadline: DateTime<Utc>) -> String {
+        self.deadline_format.format(deadline)
+    }
+
+    /// The deadline as shown in an announcement
+    fn deadline_text(&self, data: &AnnouncementData) -> String {
+        match data.deadline {
+            Some(deadline) => self.format_deadline(deadline),
+            None => data.target_time.clone(),
+        }
+    }
+
+    /// Append the metadata trailer to an announcement, if enabled
+    ///
+    /// An announcement with no room left under the tweet limit goes out
+    /// without it; `TweetCache` then recognises it by its hashtags.
+    fn with_trailer(&self, data: &AnnouncementData, text: String) -> String {
+        let with_trailer =
+            self.with_metadata_line(AnnouncementMetadata::from_announcement(data), text.clone());
+        if twitter_api::weighted_tweet_length(&with_trailer) <= self.tweet_limit {
+            with_trailer
+        } else {
+            text
+        }
+    }
+
+    fn with_metadata_line(&self, metadata: AnnouncementMetadata, text: String) -> String {
+        if self.include_metadata {
+            format!("{}\n{}", text.trim_end(), metadata.to_line())
+        } else {
+            text
+        }
     }
 
     /// Create a standard block announcement
@@ -215,18 +558,19 @@ impl AnnouncementFormatter {
         let hashtag_string = self.hashtag_manager.format_hashtags(&hashtags);
 
         let prize_info = if let Some(prize) = data.prize_pool {
-            format!(" Prize pool: {} TAO.", prize)
+            format!(" Prize pool: {} {}.", prize, self.currency_label)
         } else {
             String::new()
         };
 
-        format!(
+        let announcement = format!(
             "🎯 Block {} is now live! Target frame reveal at {}.{} Submit your predictions below! {}",
             data.block_num,
-            data.target_time,
+            self.deadline_text(data),
             prize_info,
             hashtag_string
-        )
+        );
+        self.with_trailer(data, announcement)
     }
 
     /// Create a custom announcement with provided message
@@ -238,7 +582,7 @@ impl AnnouncementFormatter {
         );
         let hashtag_string = self.hashtag_manager.format_hashtags(&hashtags);
 
-        format!("{} {}", data.message, hashtag_string)
+        self.with_trailer(data, format!("{} {}", data.message, hashtag_string))
     }
 
     /// Format announcement based on type
@@ -251,21 +595,23 @@ impl AnnouncementFormatter {
     }
 
     /// Create a commitment phase announcement
+    ///
+    /// Custom hashtags that would push it over the tweet limit are left out;
+    /// see `create_commitment_announcement_fitted`.
     pub fn create_commitment_announcement(&self, data: &AnnouncementData) -> String {
-        // Generate hashtags with commitment-specific format
-        let mut hashtags = vec![
-            "#cliptions".to_string(),
-            "#ai".to_string(),
-            "#CLIP".to_string(),
-            format!("#block{}", data.block_num),
-            format!("#{}", data.state_name.to_lowercase()),
-        ];
-
-        // Add any custom hashtags
-        hashtags.extend(data.hashtags.clone());
-
-        let hashtag_string = self.hashtag_manager.format_hashtags(&hashtags);
+        self.create_commitment_announcement_fitted(data).text
+    }
 
+    /// Create a commitment phase announcement, reporting dropped custom hashtags
+    ///
+    /// Custom hashtags are dropped from the end of `data.hashtags` until the
+    /// announcement fits the tweet limit. The standard hashtags and the
+    /// instructions are always kept, so the result can still be too long if
+    /// they alone exceed the limit.
+    pub fn create_commitment_announcement_fitted(
+        &self,
+        data: &AnnouncementData,
+    ) -> FittedAnnouncement {
         let instructions = format!(
             "BLOCK {} - Commitment Phase\n\
             livestream: {}\n\n\
@@ -275,10 +621,45 @@ impl AnnouncementFormatter {
             Reply format ->\nCommit: [hash]\nWallet: [address]",
             data.block_num,
             data.livestream_url.as_deref().unwrap_or(""),
-            data.target_time
+            self.deadline_text(data)
         );
 
-        format!("{}\n\n{}", hashtag_string, instructions)
+        self.fit_custom_hashtags(data, |hashtag_string| {
+            self.with_trailer(data, format!("{}\n\n{}", hashtag_string, instructions))
+        })
+    }
+
+    /// Render with as many of `data`'s custom hashtags as fit the tweet limit
+    fn fit_custom_hashtags(
+        &self,
+        data: &AnnouncementData,
+        render: impl Fn(&str) -> String,
+    ) -> FittedAnnouncement {
+        let mut custom = data.hashtags.clone();
+        let mut dropped_hashtags = Vec::new();
+        loop {
+            let hashtag_string = self.hashtag_manager.format_hashtags(&state_hashtags(
+                data.block_num,
+                &data.state_name,
+                &custom,
+            ));
+            let text = render(&hashtag_string);
+            if twitter_api::weighted_tweet_length(&text) <= self.tweet_limit {
+                return FittedAnnouncement {
+                    text,
+                    dropped_hashtags,
+                };
+            }
+            match custom.pop() {
+                Some(hashtag) => dropped_hashtags.push(hashtag),
+                None => {
+                    return FittedAnnouncement {
+                        text,
+                        dropped_hashtags,
+                    }
+                }
+            }
+        }
     }
 
     /// Create a reveals phase announcement
@@ -307,13 +688,133 @@ impl AnnouncementFormatter {
             ",
             data.block_num,
             data.block_num,
-            data.target_time
+            self.deadline_text(data)
+        );
+
+        self.with_trailer(data, format!("{}\n\n{}", hashtag_string, instructions))
+    }
+
+    /// Create a reveals closed announcement
+    pub fn create_closed_announcement(&self, data: &AnnouncementData) -> String {
+        let hashtag_string = self
+            .hashtag_manager
+            .format_hashtags(&self.phase_hashtags(data));
+
+        let instructions = format!(
+            "BLOCK {} - Reveals are now closed\n\n\
+            Verifying commitments and scoring guesses against the target frame.\n\
+            Results will be posted shortly.",
+            data.block_num
         );
 
-        format!("{}\n\n{}", hashtag_string, instructions)
+        self.with_trailer(data, format!("{}\n\n{}", hashtag_string, instructions))
+    }
+
+    /// Create a results announcement
+    pub fn create_results_announcement(&self, data: &AnnouncementData) -> String {
+        let hashtag_string = self
+            .hashtag_manager
+            .format_hashtags(&self.phase_hashtags(data));
+
+        let prize_info = if let Some(prize) = data.prize_pool {
+            format!("Prize pool: {} {}\n", prize, self.currency_label)
+        } else {
+            String::new()
+        };
+
+        let instructions = format!(
+            "BLOCK {} - RESULTS\n\n\
+            {}Payouts have been sent to the top guesses. Thanks for playing!",
+            data.block_num, prize_info
+        );
+
+        self.with_trailer(data, format!("{}\n\n{}", hashtag_string, instructions))
+    }
+
+    /// Create a payouts announcement listing the top winners
+    ///
+    /// Lists up to `top_n` winners with their rank and payout and counts the
+    /// rest as "and X others". If the tweet would still be too long, fewer
+    /// winners are listed.
+    pub fn create_payouts_announcement(&self, data: &PayoutAnnouncementData) -> String {
+        let hashtag_string = self.hashtag_manager.format_hashtags(&state_hashtags(
+            data.block_num,
+            "payouts",
+            &data.hashtags,
+        ));
+        let metadata = AnnouncementMetadata {
+            version: METADATA_VERSION,
+            block_num: data.block_num,
+            state: "payouts".to_string(),
+            deadline: None,
+        };
+
+        let listed = data.top_n.min(data.winners.len());
+        let mut announcement = String::new();
+        for shown in (0..=listed).rev() {
+            let mut lines: Vec<String> = data.winners[..shown]
+                .iter()
+                .map(|winner| {
+                    format!(
+                        "#{} @{} {} {}",
+                        winner.rank,
+                        winner.username,
+                        format_payout_amount(winner.payout),
+                        data.currency_label
+                    )
+                })
+                .collect();
+            let others = data.winners.len() - shown;
+            if others > 0 && shown > 0 {
+                lines.push(format!("and {} others", others));
+            } else if others > 0 {
+                lines.push(format!("{} winners paid", others));
+            }
+
+            announcement = self.with_metadata_line(
+                metadata.clone(),
+                format!(
+                    "{}\n\nBLOCK {} - PAYOUTS\n\n{}",
+                    hashtag_string,
+                    data.block_num,
+                    lines.join("\n")
+                ),
+            );
+            if twitter_api::weighted_tweet_length(&announcement) <= twitter_api::MAX_TWEET_LENGTH {
+                break;
+            }
+        }
+        announcement
+    }
+
+    /// Standard hashtags for a lifecycle announcement, plus any custom ones
+    fn phase_hashtags(&self, data: &AnnouncementData) -> Vec<String> {
+        state_hashtags(data.block_num, &data.state_name, &data.hashtags)
     }
 }
 
+/// Standard hashtags for a block in `state_name`, followed by `custom`
+fn state_hashtags(block_num: u64, state_name: &str, custom: &[String]) -> Vec<String> {
+    let mut hashtags = vec![
+        "#cliptions".to_string(),
+        "#ai".to_string(),
+        "#CLIP".to_string(),
+        format!("#block{}", block_num),
+        format!("#{}", state_name.to_lowercase()),
+    ];
+    hashtags.extend_from_slice(custom);
+    hashtags
+}
+
+/// Payout amount without trailing zeros, e.g. `12.5` rather than `12.500000000`
+fn format_payout_amount(amount: f64) -> String {
+    let formatted = format!("{:.9}", amount);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
 impl Default for AnnouncementFormatter {
     fn default() -> Self {
         Self::new()
@@ -368,11 +869,116 @@ impl SocialTask for MockSocialTask {
     }
 }
 
+/// Social media task that posts a tweet through a `TwitterApi` client
+///
+/// The tweet text is rendered from `template` by replacing `{name}` placeholders
+/// with the matching context parameters. The optional `reply_to` and
+/// `image_path` parameters turn the post into a reply and/or attach an image.
+/// Returns the URL of the posted tweet.
+pub struct TweetTask<C: TwitterApi> {
+    name: String,
+    client: Arc<C>,
+    template: String,
+    placeholder_regex: Regex,
+}
+
+impl<C: TwitterApi + Send + Sync> TweetTask<C> {
+    /// Create a new tweet task
+    pub fn new(name: String, client: Arc<C>, template: String) -> Result<Self> {
+        let placeholder_regex = Regex::new(r"\{(\w+)\}")
+            .map_err(|e| CliptionsError::ValidationError(format!("Invalid regex: {}", e)))?;
+
+        Ok(Self {
+            name,
+            client,
+            template,
+            placeholder_regex,
+        })
+    }
+
+    /// Render the tweet text from the template and context parameters
+    pub fn render_text(&self, params: &HashMap<String, String>) -> String {
+        self.placeholder_regex
+            .replace_all(&self.template, |caps: &regex::Captures| {
+                params
+                    .get(&caps[1])
+                    .cloned()
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// Post the tweet described by `context`, returning the tweet URL
+    pub async fn execute_async(&self, context: &TaskContext) -> Result<String> {
+        let text = self.render_text(&context.parameters);
+        let reply_to = context.parameters.get("reply_to").map(|s| s.as_str());
+        let image_path = context.parameters.get("image_path").map(PathBuf::from);
+
+        let result = post_tweet_flexible(self.client.as_ref(), &text, reply_to, image_path)
+            .await
+            .map_err(|e| CliptionsError::ApiError(format!("Failed to post tweet: {}", e)))?;
+
+        Ok(result.tweet.url)
+    }
+}
+
+impl<C: TwitterApi + Send + Sync> SocialTask for TweetTask<C> {
+    /// Blocks on `execute_async`, using the current tokio runtime if there is one.
+    ///
+    /// Inside a runtime this relies on `block_in_place`, so it must run on a
+    /// multi-threaded runtime; outside one a temporary runtime is created.
+    fn execute(&self, context: &TaskContext) -> Result<String> {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                tokio::task::block_in_place(|| handle.block_on(self.execute_async(context)))
+            }
+            Err(_) => tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(self.execute_async(context)),
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn validate_parameters(&self, params: &HashMap<String, String>) -> Result<()> {
+        let missing: Vec<&str> = self
+            .placeholder_regex
+            .captures_iter(&self.template)
+            .filter_map(|caps| caps.get(1))
+            .map(|m| m.as_str())
+            .filter(|name| !params.contains_key(*name))
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(CliptionsError::ValidationError(format!(
+                "Missing parameters for task '{}': {}",
+                self.name,
+                missing.join(", ")
+            )));
+        }
+
+        if let Some(image_path) = params.get("image_path") {
+            if !Path::new(image_path).exists() {
+                return Err(CliptionsError::ValidationError(format!(
+                    "Image file does not exist: {}",
+                    image_path
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Social media workflow manager
 pub struct SocialWorkflow {
     tasks: Vec<Box<dyn SocialTask>>,
     url_parser: UrlParser,
     announcement_formatter: AnnouncementFormatter,
+    continue_on_error: bool,
 }
 
 impl SocialWorkflow {
@@ -382,6 +988,7 @@ impl SocialWorkflow {
             tasks: Vec::new(),
             url_parser: UrlParser::new()?,
             announcement_formatter: AnnouncementFormatter::new(),
+            continue_on_error: false,
         })
     }
 
@@ -390,20 +997,52 @@ impl SocialWorkflow {
         self.tasks.push(task);
     }
 
+    /// Keep running the remaining tasks after one fails instead of aborting
+    pub fn set_continue_on_error(&mut self, continue_on_error: bool) {
+        self.continue_on_error = continue_on_error;
+    }
+
     /// Execute all tasks in the workflow
     pub fn execute_workflow(&self, contexts: &[TaskContext]) -> Result<Vec<String>> {
-        let mut results = Vec::new();
+        self.execute_workflow_outcomes(contexts)?
+            .into_iter()
+            .collect()
+    }
 
-        for (task, context) in self.tasks.iter().zip(contexts.iter()) {
-            // Validate parameters first
-            task.validate_parameters(&context.parameters)?;
+    /// Execute all tasks, returning one outcome per task that was run
+    ///
+    /// Each task is paired with the context at the same index, so the counts
+    /// must match. When a task fails its error is recorded; the workflow then
+    /// stops unless `continue_on_error` is set, in which case the remaining
+    /// tasks still run.
+    pub fn execute_workflow_outcomes(
+        &self,
+        contexts: &[TaskContext],
+    ) -> Result<Vec<Result<String>>> {
+        if contexts.len() != self.tasks.len() {
+            return Err(CliptionsError::ValidationError(format!(
+                "Workflow has {} tasks but {} contexts were provided",
+                self.tasks.len(),
+                contexts.len()
+            )));
+        }
 
-            // Execute the task
-            let result = task.execute(context)?;
-            results.push(result);
+        let mut outcomes = Vec::with_capacity(self.tasks.len());
+
+        for (task, context) in self.tasks.iter().zip(contexts.iter()) {
+            // Validate parameters first, then execute the task
+            let outcome = task
+                .validate_parameters(&context.parameters)
+                .and_then(|_| task.execute(context));
+            let failed = outcome.is_err();
+            outcomes.push(outcome);
+
+            if failed && !self.continue_on_error {
+                break;
+            }
         }
 
-        Ok(results)
+        Ok(outcomes)
     }
 
     /// Get URL parser
@@ -417,6 +1056,16 @@ impl SocialWorkflow {
     }
 }
 
+/// Hashtags of the block states a validator announces
+const STATE_HASHTAGS: [&str; 6] = [
+    "#commitmentsopen",
+    "#commitmentsclosed",
+    "#revealsopen",
+    "#revealsclosed",
+    "#payouts",
+    "#finished",
+];
+
 /// Simple tweet cache for reducing Twitter API calls
 /// Stores the last validator tweet and only queries Twitter when needed
 ///
@@ -440,15 +1089,21 @@ impl TweetCache {
         }
     }
 
-    /// Check if the cached tweet is still fresh (less than 15 minutes old)
-    pub fn is_fresh(&self) -> bool {
-        let now = chrono::Utc::now();
-        let cache_age = now - self.cached_at;
+    /// Check if the cached tweet is still fresh (less than 15 minutes old by `clock`)
+    pub fn is_fresh(&self, clock: &dyn Clock) -> bool {
+        let cache_age = clock.now() - self.cached_at;
         cache_age < chrono::Duration::minutes(15)
     }
 
     /// Check if the tweet contains state hashtags that indicate it's a block state tweet
+    ///
+    /// A metadata trailer is authoritative when present; older tweets
+    /// without one fall back to their hashtags.
     pub fn has_state_hashtags(&self) -> bool {
+        if let Some(metadata) = parse_announcement_metadata(&self.tweet_text) {
+            return STATE_HASHTAGS.contains(&format!("#{}", metadata.state).as_str());
+        }
+
         let hashtag_manager = HashtagManager::new();
         let hashtags = hashtag_manager.extract_hashtags(&self.tweet_text);
 
@@ -457,15 +1112,9 @@ impl TweetCache {
         let has_block = hashtags
             .iter()
             .any(|h| h.to_lowercase().starts_with("#block"));
-        let has_state = hashtags.iter().any(|h| {
-            let h_lower = h.to_lowercase();
-            h_lower == "#commitmentsopen"
-                || h_lower == "#commitmentsclosed"
-                || h_lower == "#revealsopen"
-                || h_lower == "#revealsclosed"
-                || h_lower == "#payouts"
-                || h_lower == "#finished"
-        });
+        let has_state = hashtags
+            .iter()
+            .any(|h| STATE_HASHTAGS.contains(&h.to_lowercase().as_str()));
 
         has_cliptions && has_block && has_state
     }
@@ -473,12 +1122,22 @@ impl TweetCache {
 
 pub struct TweetCacheManager {
     cache_file: String,
+    clock: Arc<dyn Clock>,
 }
 
 impl TweetCacheManager {
     /// Create a new cache manager with the specified cache file
     pub fn new(cache_file: String) -> Self {
-        Self { cache_file }
+        Self {
+            cache_file,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Use `clock` for cache timestamps and freshness checks
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
     }
 
     /// Create a cache manager with default cache file location
@@ -492,7 +1151,7 @@ impl TweetCacheManager {
             return Ok(None);
         }
 
-        let content = std::fs::read_to_string(&self.cache_file).map_err(|e| {
+        let content = data_file::read_to_string(&self.cache_file).map_err(|e| {
             CliptionsError::ValidationError(format!("Failed to read cache file: {}", e))
         })?;
 
@@ -520,7 +1179,7 @@ impl TweetCacheManager {
             CliptionsError::ValidationError(format!("Failed to serialize cache: {}", e))
         })?;
 
-        std::fs::write(&self.cache_file, content).map_err(|e| {
+        data_file::write(&self.cache_file, content).map_err(|e| {
             CliptionsError::ValidationError(format!("Failed to write cache file: {}", e))
         })?;
 
@@ -540,21 +1199,22 @@ impl TweetCacheManager {
     /// Get cached tweet if it's fresh and has state hashtags
     pub fn get_fresh_state_tweet(&self) -> Result<Option<TweetCache>> {
         if let Some(cache) = self.load_cache()? {
-            if cache.is_fresh() && cache.has_state_hashtags() {
+            if cache.is_fresh(self.clock.as_ref()) && cache.has_state_hashtags() {
                 return Ok(Some(cache));
             }
         }
         Ok(None)
     }
 
-    /// Update cache with new tweet data (always uses Utc::now for cached_at)
+    /// Update cache with new tweet data, stamped with the manager's clock
     pub fn update_cache(
         &self,
         tweet_id: String,
         tweet_text: String,
         validator_username: String,
     ) -> Result<()> {
-        let cache = TweetCache::new(tweet_id, tweet_text, validator_username);
+        let mut cache = TweetCache::new(tweet_id, tweet_text, validator_username);
+        cache.cached_at = self.clock.now();
         self.save_cache(&cache)
     }
 }
@@ -568,6 +1228,8 @@ impl Default for TweetCacheManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+    use twitter_api::testing::InMemoryTwitterApi;
 
     #[test]
     fn test_extract_tweet_id_from_url() {
@@ -588,6 +1250,38 @@ mod tests {
         assert!(parser.extract_tweet_id(invalid_url).is_err());
     }
 
+    #[test]
+    fn test_extract_all_tweet_ids() {
+        let parser = UrlParser::new().unwrap();
+
+        let text = "Reveal thread: https://twitter.com/alice/status/111 (see also \
+                    https://x.com/bob/status/222?s=20), not https://example.com/status/333 \
+                    and again https://www.twitter.com/alice/status/111";
+        assert_eq!(parser.extract_all_tweet_ids(text), vec!["111", "222"]);
+
+        assert!(parser.extract_all_tweet_ids("no links here").is_empty());
+    }
+
+    #[test]
+    fn test_extract_conversation_root() {
+        let mut tweet = twitter_api::Tweet {
+            id: "111".to_string(),
+            conversation_id: Some("111".to_string()),
+            ..Default::default()
+        };
+        let root = extract_conversation_root(&tweet).unwrap();
+        assert_eq!(root.root_id, "111");
+        assert!(root.is_root);
+
+        tweet.id = "112".to_string();
+        let reply = extract_conversation_root(&tweet).unwrap();
+        assert_eq!(reply.root_id, "111");
+        assert!(!reply.is_root);
+
+        tweet.conversation_id = None;
+        assert_eq!(extract_conversation_root(&tweet), None);
+    }
+
     #[test]
     fn test_validate_url() {
         let parser = UrlParser::new().unwrap();
@@ -662,6 +1356,7 @@ mod tests {
             message: "Commitments are now open!".to_string(),
             prize_pool: None,
             livestream_url: Some("https://example.com/livestream".to_string()),
+            deadline: None,
         };
 
         let tweet = formatter.format_announcement(&data, true);
@@ -698,6 +1393,7 @@ mod tests {
             message: "Time to reveal!".to_string(),
             prize_pool: Some(100.0),
             livestream_url: Some("https://example.com/livestream2".to_string()),
+            deadline: None,
         };
 
         let tweet2 = formatter.format_announcement(&data2, true);
@@ -721,6 +1417,373 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deadline_format_timezones() {
+        let deadline = Utc.with_ymd_and_hms(2025, 1, 15, 17, 0, 0).unwrap();
+
+        let eastern = DeadlineFormat::default().format(deadline);
+        assert_eq!(
+            eastern,
+            "2025-01-15 | 12:00:00 | EST (2025-01-15T17:00:00Z)"
+        );
+
+        let tokyo = DeadlineFormat::new(chrono_tz::Asia::Tokyo).format(deadline);
+        assert_eq!(tokyo, "2025-01-16 | 02:00:00 | JST (2025-01-15T17:00:00Z)");
+
+        let data = AnnouncementData {
+            block_num: 3,
+            state_name: "revealsopen".to_string(),
+            target_time: "ignored when a deadline is set".to_string(),
+            hashtags: vec![],
+            message: String::new(),
+            prize_pool: None,
+            livestream_url: None,
+            deadline: Some(deadline),
+        };
+        for (timezone, local) in [
+            (chrono_tz::US::Eastern, "2025-01-15 | 12:00:00 | EST"),
+            (chrono_tz::Asia::Tokyo, "2025-01-16 | 02:00:00 | JST"),
+        ] {
+            let formatter =
+                AnnouncementFormatter::new().with_deadline_format(DeadlineFormat::new(timezone));
+            let tweet = formatter.create_reveals_announcement(&data);
+            assert!(tweet.contains(local), "{}", tweet);
+            assert!(tweet.contains("2025-01-15T17:00:00Z"));
+            assert!(!tweet.contains("ignored"));
+        }
+    }
+
+    /// Tweet limit of accounts allowed long posts
+    const LONG_POST_LIMIT: usize = 25_000;
+
+    #[test]
+    fn test_announcement_metadata_round_trip() {
+        // An account allowed long posts has room for the trailer on every announcement
+        let formatter = AnnouncementFormatter::new().with_tweet_limit(LONG_POST_LIMIT);
+        let deadline = Utc.with_ymd_and_hms(2024, 1, 1, 18, 0, 0).unwrap();
+        let data = |state_name: &str, deadline: Option<DateTime<Utc>>| AnnouncementData {
+            block_num: 42,
+            state_name: state_name.to_string(),
+            target_time: "2024-01-01 13:00:00".to_string(),
+            hashtags: vec![],
+            message: "Commitments are now closed".to_string(),
+            prize_pool: Some(100.0),
+            livestream_url: Some("https://example.com/livestream".to_string()),
+            deadline,
+        };
+
+        let cases = [
+            (
+                "commitmentsopen",
+                formatter.create_commitment_announcement(&data("CommitmentsOpen", Some(deadline))),
+            ),
+            (
+                "commitmentsclosed",
+                formatter.create_custom_announcement(&data("CommitmentsClosed", None)),
+            ),
+            (
+                "revealsopen",
+                formatter.create_reveals_announcement(&data("RevealsOpen", Some(deadline))),
+            ),
+            (
+                "revealsclosed",
+                formatter.create_closed_announcement(&data("RevealsClosed", None)),
+            ),
+            (
+                "finished",
+                formatter.create_results_announcement(&data("Finished", None)),
+            ),
+            (
+                "payouts",
+                formatter.create_standard_announcement(&data("Payouts", Some(deadline))),
+            ),
+        ];
+
+        for (state, tweet) in cases {
+            let metadata = parse_announcement_metadata(&tweet).unwrap();
+            let expected_deadline =
+                matches!(state, "commitmentsopen" | "revealsopen" | "payouts").then_some(deadline);
+            assert_eq!(
+                metadata,
+                AnnouncementMetadata {
+                    version: 1,
+                    block_num: 42,
+                    state: state.to_string(),
+                    deadline: expected_deadline,
+                }
+            );
+            // The trailer is the last line, after the human-readable text
+            assert!(tweet.lines().last().unwrap().starts_with("cliptions:v1 "));
+            assert!(tweet.contains(&format!("#{}", state)));
+        }
+
+        // The trailer is only appended; the text before it is unchanged
+        let commitment =
+            formatter.create_commitment_announcement(&data("CommitmentsOpen", Some(deadline)));
+        let plain = AnnouncementFormatter::new()
+            .with_metadata(false)
+            .create_commitment_announcement(&data("CommitmentsOpen", Some(deadline)));
+        assert!(parse_announcement_metadata(&plain).is_none());
+        assert!(plain.contains("2. Reply BEFORE: 2024-01-01 | 13:00:00 | EST (2024-01-01T18:00:00Z)"));
+        assert_eq!(
+            commitment,
+            format!(
+                "{}\ncliptions:v1 block=42 state=commitmentsopen deadline=2024-01-01T18:00:00Z",
+                plain
+            )
+        );
+
+        // Within the standard limit, announcements without room go out without it
+        let standard = AnnouncementFormatter::new();
+        let closed = standard.create_closed_announcement(&data("RevealsClosed", None));
+        assert!(twitter_api::weighted_tweet_length(&closed) <= twitter_api::MAX_TWEET_LENGTH);
+        assert!(parse_announcement_metadata(&closed).is_some());
+        assert_eq!(
+            standard.create_commitment_announcement(&data("CommitmentsOpen", Some(deadline))),
+            plain
+        );
+    }
+
+    #[test]
+    fn test_commitment_announcement_drops_lowest_priority_hashtags() {
+        let custom: Vec<String> = (1..=40).map(|i| format!("#sponsor{:02}", i)).collect();
+        let data = AnnouncementData {
+            block_num: 42,
+            state_name: "CommitmentsOpen".to_string(),
+            target_time: "2024-01-01 13:00:00".to_string(),
+            hashtags: custom.clone(),
+            message: String::new(),
+            prize_pool: None,
+            livestream_url: Some("https://example.com/livestream".to_string()),
+            deadline: None,
+        };
+        let formatter = AnnouncementFormatter::new();
+
+        let fitted = formatter.create_commitment_announcement_fitted(&data);
+        assert!(twitter_api::weighted_tweet_length(&fitted.text) <= twitter_api::MAX_TWEET_LENGTH);
+        assert_eq!(formatter.create_commitment_announcement(&data), fitted.text);
+
+        // The last-given tags go first, so the kept ones are a prefix of the list
+        let kept = custom.len() - fitted.dropped_hashtags.len();
+        assert!(kept > 0 && kept < custom.len());
+        let mut expected_dropped = custom[kept..].to_vec();
+        expected_dropped.reverse();
+        assert_eq!(fitted.dropped_hashtags, expected_dropped);
+        assert!(fitted.text.contains(&custom[..kept].join(" ")));
+        assert!(!fitted.text.contains(&custom[kept]));
+        for standard in ["#cliptions", "#ai", "#CLIP", "#block42", "#commitmentsopen"] {
+            assert!(fitted.text.contains(standard));
+        }
+
+        // A tighter budget drops more, but never the standard tags
+        let tight = AnnouncementFormatter::new()
+            .with_tweet_limit(100)
+            .create_commitment_announcement_fitted(&data);
+        assert_eq!(tight.dropped_hashtags.len(), custom.len());
+        assert!(tight
+            .text
+            .starts_with("#cliptions #ai #CLIP #block42 #commitmentsopen\n"));
+
+        // Nothing is dropped when everything fits
+        let few = AnnouncementData {
+            hashtags: custom[..2].to_vec(),
+            ..data
+        };
+        let fitted = formatter.create_commitment_announcement_fitted(&few);
+        assert!(fitted.dropped_hashtags.is_empty());
+        assert!(fitted.text.contains("#sponsor01 #sponsor02"));
+    }
+
+    fn payout_winners(count: usize) -> Vec<PayoutInfo> {
+        (1..=count)
+            .map(|rank| PayoutInfo {
+                username: format!("player_number_{}", rank),
+                guess: format!("guess {}", rank),
+                score: 1.0 / rank as f64,
+                rank,
+                payout: 100.0 / count as f64,
+                scoring_version: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_payouts_announcement_lists_winners() {
+        let formatter = AnnouncementFormatter::new();
+        let mut data = PayoutAnnouncementData::new(7, payout_winners(2));
+        data.winners[0].payout = 62.5;
+        data.winners[1].payout = 37.5;
+        data.currency_label = "USDC".to_string();
+
+        let tweet = formatter.create_payouts_announcement(&data);
+        assert!(tweet.contains("#block7 #payouts"));
+        assert!(tweet.contains("BLOCK 7 - PAYOUTS"));
+        assert!(tweet.contains("#1 @player_number_1 62.5 USDC"));
+        assert!(tweet.contains("#2 @player_number_2 37.5 USDC"));
+        assert!(!tweet.contains("others"));
+        assert_eq!(
+            parse_announcement_metadata(&tweet).unwrap().state,
+            "payouts".to_string()
+        );
+    }
+
+    #[test]
+    fn test_payouts_announcement_truncates_large_winner_sets() {
+        let formatter = AnnouncementFormatter::new();
+
+        // Only the top N are listed
+        let data = PayoutAnnouncementData::new(3, payout_winners(50));
+        let tweet = formatter.create_payouts_announcement(&data);
+        assert!(twitter_api::weighted_tweet_length(&tweet) <= twitter_api::MAX_TWEET_LENGTH);
+        assert!(tweet.contains("#1 @player_number_1 "));
+        assert!(!tweet.contains("@player_number_6 "));
+        assert!(tweet.contains("and 45 others"));
+
+        // A top N too long for one tweet is cut further
+        let mut data = PayoutAnnouncementData::new(3, payout_winners(50));
+        data.top_n = 50;
+        let tweet = formatter.create_payouts_announcement(&data);
+        assert!(twitter_api::weighted_tweet_length(&tweet) <= twitter_api::MAX_TWEET_LENGTH);
+        let listed = tweet
+            .lines()
+            .filter(|line| line.starts_with('#') && line.contains('@'))
+            .count();
+        assert!(listed > 0 && listed < 50);
+        assert!(tweet.contains(&format!("and {} others", 50 - listed)));
+    }
+
+    #[test]
+    fn test_parse_announcement_metadata() {
+        let parsed = parse_announcement_metadata(
+            "#cliptions #block9\nBLOCK 9\n  cliptions:v1 state=RevealsClosed block=9 extra=1  ",
+        )
+        .unwrap();
+        assert_eq!(parsed.block_num, 9);
+        assert_eq!(parsed.state, "revealsclosed");
+        assert_eq!(parsed.deadline, None);
+
+        for text in [
+            "#cliptions #block9 #revealsopen",
+            "cliptions:v2 block=9 state=revealsopen",
+            "cliptions:v1 state=revealsopen",
+            "cliptions:v1 block=nine state=revealsopen",
+            "cliptions:v1 block=9 state=revealsopen deadline=tomorrow",
+        ] {
+            assert!(parse_announcement_metadata(text).is_none(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_has_state_hashtags_prefers_metadata() {
+        let cache = |text: &str| {
+            TweetCache::new("1".to_string(), text.to_string(), "validator".to_string())
+        };
+
+        assert!(cache("#cliptions #block3 #revealsopen").has_state_hashtags());
+        assert!(!cache("#cliptions #block3 gm").has_state_hashtags());
+        assert!(cache("Block 3 update\ncliptions:v1 block=3 state=payouts").has_state_hashtags());
+        // A trailer with an unknown state wins over hashtags
+        assert!(
+            !cache("#cliptions #block3 #revealsopen\ncliptions:v1 block=3 state=paused")
+                .has_state_hashtags()
+        );
+    }
+
+    #[test]
+    fn test_build_reply_tree() {
+        let reply = |id: &str, replied_to: Option<&str>| twitter_api::Tweet {
+            id: id.to_string(),
+            conversation_id: Some("100".to_string()),
+            referenced_tweets: replied_to
+                .map(|parent| twitter_api::ReferencedTweet {
+                    kind: "replied_to".to_string(),
+                    id: parent.to_string(),
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        // 100 <- 200 (reveal tweet) <- 201, 202; 201 <- 301 <- 401; 203 has no references
+        let tweets = vec![
+            reply("200", Some("100")),
+            reply("201", Some("200")),
+            reply("301", Some("201")),
+            reply("202", Some("200")),
+            reply("401", Some("301")),
+            reply("203", None),
+        ];
+
+        let tree = build_reply_tree(&tweets);
+
+        assert_eq!(tree.direct_replies("200"), ["201", "202"]);
+        assert_eq!(tree.direct_replies("100"), ["200", "203"]);
+        assert!(tree.direct_replies("202").is_empty());
+        assert!(tree.is_direct_reply("201", "200"));
+        assert!(!tree.is_direct_reply("301", "200"));
+        assert_eq!(tree.parent("203"), Some("100"));
+        assert_eq!(tree.parent("100"), None);
+
+        assert_eq!(tree.depth_below("201", "200"), Some(1));
+        assert_eq!(tree.depth_below("401", "200"), Some(3));
+        assert_eq!(tree.depth_below("203", "200"), None);
+    }
+
+    #[test]
+    fn test_tweet_cache_freshness_boundary() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let clock = Arc::new(crate::clock::MockClock::new(
+            Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap(),
+        ));
+        let manager = TweetCacheManager::new(
+            temp_dir
+                .path()
+                .join("cache.json")
+                .to_string_lossy()
+                .into_owned(),
+        )
+        .with_clock(clock.clone());
+
+        manager
+            .update_cache(
+                "1".to_string(),
+                "#cliptions #block3 #revealsopen".to_string(),
+                "validator".to_string(),
+            )
+            .unwrap();
+        let cache = manager.load_cache().unwrap().unwrap();
+        assert_eq!(cache.cached_at, clock.now());
+
+        // Fresh up to, but not including, 15 minutes
+        clock.advance(chrono::Duration::minutes(15) - chrono::Duration::seconds(1));
+        assert!(cache.is_fresh(clock.as_ref()));
+        assert!(manager.get_fresh_state_tweet().unwrap().is_some());
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(!cache.is_fresh(clock.as_ref()));
+        assert!(manager.get_fresh_state_tweet().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_tweet_cache_compressed_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache.json.gz");
+        let manager = TweetCacheManager::new(path.to_string_lossy().into_owned());
+
+        manager
+            .update_cache(
+                "1".to_string(),
+                "#cliptions #block3 #revealsopen".to_string(),
+                "validator".to_string(),
+            )
+            .unwrap();
+
+        // Stored gzip-compressed, loaded back transparently
+        assert_eq!(&fs::read(&path).unwrap()[..2], &[0x1f, 0x8b]);
+        let cache = manager.load_cache().unwrap().unwrap();
+        assert_eq!(cache.tweet_id, "1");
+        assert_eq!(cache.validator_username, "validator");
+    }
+
     #[test]
     fn test_format_hashtags() {
         let hashtag_manager = HashtagManager::new();
@@ -760,6 +1823,7 @@ mod tests {
             message: "".to_string(),
             prize_pool: Some(100.0),
             livestream_url: None,
+            deadline: None,
         };
 
         let announcement = formatter.create_standard_announcement(&data);
@@ -769,6 +1833,14 @@ mod tests {
         assert!(announcement.contains("#cliptions"));
         assert!(announcement.contains("#block1"));
         assert!(announcement.contains("#commitmentsopen"));
+
+        let usdc = AnnouncementFormatter::new().with_currency_label("USDC");
+        assert!(usdc
+            .create_standard_announcement(&data)
+            .contains("Prize pool: 100 USDC."));
+        assert!(usdc
+            .create_results_announcement(&data)
+            .contains("Prize pool: 100 USDC\n"));
     }
 
     #[test]
@@ -782,6 +1854,7 @@ mod tests {
             message: "Custom announcement message".to_string(),
             prize_pool: None,
             livestream_url: None,
+            deadline: None,
         };
 
         let announcement = formatter.create_custom_announcement(&data);
@@ -802,6 +1875,7 @@ mod tests {
             message: "".to_string(),
             prize_pool: Some(50.0),
             livestream_url: None,
+            deadline: None,
         };
 
         // Test standard announcement
@@ -895,6 +1969,7 @@ mod tests {
             message: "Test message".to_string(),
             prize_pool: Some(100.0),
             livestream_url: None,
+            deadline: None,
         };
 
         assert_eq!(data.block_num, 1);
@@ -903,4 +1978,124 @@ mod tests {
         assert_eq!(data.message, "Test message");
         assert_eq!(data.prize_pool, Some(100.0));
     }
+
+    #[test]
+    fn test_tweet_task_posts_rendered_text() {
+        let client = Arc::new(InMemoryTwitterApi::new());
+        client.seed(twitter_api::Tweet {
+            id: "12345".to_string(),
+            text: "Block 7".to_string(),
+            author_id: "2".to_string(),
+            ..twitter_api::Tweet::default()
+        });
+
+        let task = TweetTask::new(
+            "announce".to_string(),
+            Arc::clone(&client),
+            "Block {block_num} is live! #cliptions".to_string(),
+        )
+        .unwrap();
+
+        let mut parameters = HashMap::new();
+        parameters.insert("block_num".to_string(), "7".to_string());
+        parameters.insert("reply_to".to_string(), "12345".to_string());
+        let context = TaskContext {
+            task_name: "announce".to_string(),
+            parameters,
+            timeout_seconds: 30,
+        };
+
+        assert!(task.validate_parameters(&context.parameters).is_ok());
+        let url = task.execute(&context).unwrap();
+
+        let reply = client.tweets().pop().unwrap();
+        assert_eq!(url, reply.url);
+        assert_eq!(reply.text, "Block 7 is live! #cliptions");
+        assert_eq!(reply.conversation_id.as_deref(), Some("12345"));
+    }
+
+    #[test]
+    fn test_tweet_task_missing_parameters() {
+        let task = TweetTask::new(
+            "announce".to_string(),
+            Arc::new(InMemoryTwitterApi::new()),
+            "Block {block_num} is live!".to_string(),
+        )
+        .unwrap();
+
+        let result = task.validate_parameters(&HashMap::new());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("block_num"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tweet_task_execute_inside_runtime() {
+        let client = Arc::new(InMemoryTwitterApi::new());
+        let task =
+            TweetTask::new("hello".to_string(), Arc::clone(&client), "{text}".to_string()).unwrap();
+        let mut parameters = HashMap::new();
+        parameters.insert("text".to_string(), "Hello".to_string());
+        let context = TaskContext {
+            task_name: "hello".to_string(),
+            parameters,
+            timeout_seconds: 30,
+        };
+
+        let url = task.execute(&context).unwrap();
+        let tweets = client.tweets();
+        assert_eq!(tweets.len(), 1);
+        assert_eq!(tweets[0].text, "Hello");
+        assert_eq!(url, tweets[0].url);
+    }
+
+    fn workflow_with_failing_middle_task() -> (SocialWorkflow, Vec<TaskContext>) {
+        let mut workflow = SocialWorkflow::new().unwrap();
+        workflow.add_task(Box::new(MockSocialTask::new("task1".to_string())));
+        workflow.add_task(Box::new(MockSocialTask::new_failing("task2".to_string())));
+        workflow.add_task(Box::new(MockSocialTask::new("task3".to_string())));
+
+        let contexts = ["task1", "task2", "task3"]
+            .iter()
+            .map(|name| TaskContext {
+                task_name: name.to_string(),
+                parameters: HashMap::new(),
+                timeout_seconds: 30,
+            })
+            .collect();
+
+        (workflow, contexts)
+    }
+
+    #[test]
+    fn test_social_workflow_aborts_on_error() {
+        let (workflow, contexts) = workflow_with_failing_middle_task();
+
+        let outcomes = workflow.execute_workflow_outcomes(&contexts).unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes[0].as_ref().unwrap().contains("task1"));
+        assert!(outcomes[1].is_err());
+
+        assert!(workflow.execute_workflow(&contexts).is_err());
+    }
+
+    #[test]
+    fn test_social_workflow_continue_on_error() {
+        let (mut workflow, contexts) = workflow_with_failing_middle_task();
+        workflow.set_continue_on_error(true);
+
+        let outcomes = workflow.execute_workflow_outcomes(&contexts).unwrap();
+        assert_eq!(outcomes.len(), 3);
+        assert!(outcomes[0].as_ref().unwrap().contains("task1"));
+        assert!(outcomes[1].is_err());
+        assert!(outcomes[2].as_ref().unwrap().contains("task3"));
+    }
+
+    #[test]
+    fn test_social_workflow_context_count_mismatch() {
+        let (workflow, mut contexts) = workflow_with_failing_middle_task();
+        contexts.pop();
+
+        assert!(workflow.execute_workflow_outcomes(&contexts).is_err());
+        assert!(workflow.execute_workflow(&contexts).is_err());
+    }
 }
@@ -0,0 +1,229 @@
+//! Read-only HTTP server exposing block state to dashboards
+//!
+//! Built with the `status-server` feature. Routes:
+//!
+//! - `GET /blocks`: every block's `BlockSummary`, most recently created first
+//! - `GET /blocks/:num`: the block's full `RoundReport`
+//!
+//! The blocks file is re-read on every request, so responses show the latest
+//! saved state. No route changes any block.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path as UrlPath, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+use tokio::net::TcpListener;
+
+use crate::block_processor::{BlockListQuery, BlockProcessor};
+use crate::embedder::EmbedderTrait;
+use crate::error::{BlockError, CliptionsError, Result};
+use crate::scoring::ScoringStrategy;
+
+type SharedProcessor<E, S> = Arc<Mutex<BlockProcessor<E, S>>>;
+
+/// Body of an error response
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// The status routes, reading blocks through `processor`
+pub fn router<E, S>(processor: BlockProcessor<E, S>) -> Router
+where
+    E: EmbedderTrait + Send + 'static,
+    S: ScoringStrategy + Send + 'static,
+{
+    Router::new()
+        .route("/blocks", get(list_blocks::<E, S>))
+        .route("/blocks/:num", get(block_report::<E, S>))
+        .with_state(Arc::new(Mutex::new(processor)))
+}
+
+/// Serve the status routes on `listener` until the server fails
+pub async fn serve<E, S>(listener: TcpListener, processor: BlockProcessor<E, S>) -> Result<()>
+where
+    E: EmbedderTrait + Send + 'static,
+    S: ScoringStrategy + Send + 'static,
+{
+    axum::serve(listener, router(processor)).await?;
+    Ok(())
+}
+
+async fn list_blocks<E, S>(State(processor): State<SharedProcessor<E, S>>) -> Response
+where
+    E: EmbedderTrait,
+    S: ScoringStrategy,
+{
+    let mut processor = processor.lock().unwrap();
+    // A missing blocks file means no blocks yet; don't create it
+    if !Path::new(processor.blocks_file()).exists() {
+        return Json(Vec::<()>::new()).into_response();
+    }
+    respond(
+        processor
+            .reload_blocks()
+            .and_then(|_| processor.list_blocks(&BlockListQuery::default())),
+    )
+}
+
+async fn block_report<E, S>(
+    State(processor): State<SharedProcessor<E, S>>,
+    UrlPath(block_num): UrlPath<String>,
+) -> Response
+where
+    E: EmbedderTrait,
+    S: ScoringStrategy,
+{
+    let mut processor = processor.lock().unwrap();
+    if !Path::new(processor.blocks_file()).exists() {
+        return respond::<()>(Err(BlockError::BlockNotFound { block_num }.into()));
+    }
+    respond(
+        processor
+            .reload_blocks()
+            .and_then(|_| processor.generate_report(&block_num)),
+    )
+}
+
+/// JSON for `result`; unknown blocks are 404, any other failure 500
+fn respond<T: Serialize>(result: Result<T>) -> Response {
+    match result {
+        Ok(body) => Json(body).into_response(),
+        Err(e) => {
+            let status = match e {
+                CliptionsError::Block(BlockError::BlockNotFound { .. }) => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            let body = ErrorBody {
+                error: e.to_string(),
+            };
+            (status, Json(body)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedder::MockEmbedder;
+    use crate::scoring::ClipBatchStrategy;
+    use crate::types::{BlockData, Guess, Participant};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    /// Serve `blocks_file` on a free local port, returning the base URL
+    async fn start_server(blocks_file: &Path) -> String {
+        let processor = BlockProcessor::new(
+            blocks_file.to_string_lossy().to_string(),
+            MockEmbedder::clip_like(),
+            ClipBatchStrategy::new(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, processor));
+        format!("http://{}", address)
+    }
+
+    fn write_blocks(blocks_file: &Path, block_nums: &[&str]) {
+        let blocks: HashMap<String, BlockData> = block_nums
+            .iter()
+            .map(|block_num| {
+                let mut block = BlockData::new(
+                    block_num.to_string(),
+                    "target.jpg".to_string(),
+                    format!("social_{}", block_num),
+                    100.0,
+                );
+                block.add_participant(Participant::new(
+                    "1".to_string(),
+                    "alice".to_string(),
+                    Guess::new("a red fox".to_string()),
+                    "commitment".to_string(),
+                ));
+                (block_num.to_string(), block)
+            })
+            .collect();
+        std::fs::write(blocks_file, serde_json::to_string(&blocks).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_blocks_routes_serve_summaries_and_reports() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks_file = temp_dir.path().join("blocks.json");
+        write_blocks(&blocks_file, &["1"]);
+        let base_url = start_server(&blocks_file).await;
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("{}/blocks", base_url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let summaries: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(summaries.as_array().unwrap().len(), 1);
+        assert_eq!(summaries[0]["block_num"], "1");
+        assert_eq!(summaries[0]["participant_count"], 1);
+
+        let response = client
+            .get(format!("{}/blocks/1", base_url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let report: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(report["summary"]["block_num"], "1");
+        assert_eq!(report["target_image_path"], "target.jpg");
+        assert_eq!(report["participants"][0]["username"], "alice");
+
+        // Blocks saved after the server started are served too
+        write_blocks(&blocks_file, &["1", "2"]);
+        let summaries: serde_json::Value = client
+            .get(format!("{}/blocks", base_url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(summaries.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_block_is_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let blocks_file = temp_dir.path().join("blocks.json");
+        let base_url = start_server(&blocks_file).await;
+        let client = reqwest::Client::new();
+
+        // No blocks file yet: an empty list, and the file isn't created
+        let summaries: serde_json::Value = client
+            .get(format!("{}/blocks", base_url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(summaries, serde_json::json!([]));
+        assert!(!blocks_file.exists());
+
+        write_blocks(&blocks_file, &["1"]);
+        let response = client
+            .get(format!("{}/blocks/9", base_url))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .contains("Block 9 not found"));
+    }
+}
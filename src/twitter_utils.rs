@@ -1,10 +1,10 @@
 use std::path::PathBuf;
-use twitter_api::{PostTweetResult, TwitterApi, TwitterClient, TwitterError};
+use twitter_api::{PostTweetResult, TwitterApi, TwitterError};
 
 /// Post a tweet, reply, or tweet with image using the provided TwitterClient.
 /// If both reply_to and image_path are Some, prints a warning and posts image tweet only.
-pub async fn post_tweet_flexible(
-    client: &TwitterClient,
+pub async fn post_tweet_flexible<C: TwitterApi + Sync>(
+    client: &C,
     tweet_text: &str,
     reply_to: Option<&str>,
     image_path: Option<PathBuf>,
@@ -3,16 +3,33 @@
 //! This module defines the fundamental data structures used throughout the Cliptions system,
 //! including participants, guesses, scoring results, and block data.
 
+use crate::commitment::{normalize_guess, CommitmentVerification, CommitmentVerifier, HashAlgo};
+use crate::embedder::EmbedderSpec;
+use crate::error::{CliptionsError, Result};
+use crate::payout::DEFAULT_CURRENCY_LABEL;
 use chrono::{DateTime, Utc};
 use ndarray::Array1;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Fewest non-whitespace characters a checked guess may have
+pub const MIN_GUESS_LENGTH: usize = 1;
+
+/// Most characters a checked guess may have, roughly CLIP's 77-token limit
+pub const MAX_GUESS_LENGTH: usize = 300;
+
+/// Seconds after a deadline that submissions are still accepted, to absorb
+/// clock differences between participants and Twitter's timestamps
+pub const DEFAULT_DEADLINE_GRACE_SECS: i64 = 60;
+
 /// A participant's guess in the prediction market
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Guess {
-    /// The text content of the guess
+    /// The text content of the guess, normalized with `normalize_guess`
     pub text: String,
+    /// The guess as originally submitted, when it differs from `text`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_text: Option<String>,
     /// Embedding vector for the guess (if computed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub embedding: Option<Vec<f64>>,
@@ -25,25 +42,63 @@ pub struct Guess {
 
 impl Guess {
     /// Create a new guess with the current timestamp
+    ///
+    /// The text is normalized with `normalize_guess`; the original is kept for display.
     pub fn new(text: String) -> Self {
-        Self {
-            text,
-            embedding: None,
-            timestamp: Utc::now(),
-            metadata: HashMap::new(),
+        Self::with_timestamp(text, Utc::now())
+    }
+
+    /// Create a new guess, rejecting text that can't be a meaningful guess
+    ///
+    /// The text must have at least `MIN_GUESS_LENGTH` non-whitespace characters,
+    /// at most `MAX_GUESS_LENGTH` characters and no control characters. Use
+    /// `new` where the text was already accepted, e.g. when loading saved data.
+    pub fn new_checked(text: String) -> Result<Self> {
+        let visible = text.chars().filter(|c| !c.is_whitespace()).count();
+        if visible < MIN_GUESS_LENGTH {
+            return Err(CliptionsError::ValidationError(format!(
+                "Guess must have at least {} non-whitespace characters, got {}",
+                MIN_GUESS_LENGTH, visible
+            )));
         }
+
+        let length = text.chars().count();
+        if length > MAX_GUESS_LENGTH {
+            return Err(CliptionsError::ValidationError(format!(
+                "Guess must be at most {} characters, got {}",
+                MAX_GUESS_LENGTH, length
+            )));
+        }
+
+        if let Some(c) = text.chars().find(|c| c.is_control()) {
+            return Err(CliptionsError::ValidationError(format!(
+                "Guess contains control character {:?}",
+                c
+            )));
+        }
+
+        Ok(Self::new(text))
     }
 
     /// Create a guess with a specific timestamp
     pub fn with_timestamp(text: String, timestamp: DateTime<Utc>) -> Self {
+        let normalized = normalize_guess(&text);
+        let raw_text = (normalized != text).then_some(text);
+
         Self {
-            text,
+            text: normalized,
+            raw_text,
             embedding: None,
             timestamp,
             metadata: HashMap::new(),
         }
     }
 
+    /// The guess as the participant wrote it, for display
+    pub fn raw_text(&self) -> &str {
+        self.raw_text.as_deref().unwrap_or(&self.text)
+    }
+
     /// Set the embedding for this guess
     pub fn with_embedding(mut self, embedding: Vec<f64>) -> Self {
         self.embedding = Some(embedding);
@@ -89,6 +144,9 @@ pub struct Participant {
     /// Whether the commitment has been verified
     #[serde(default)]
     pub verified: bool,
+    /// Algorithm the commitment was hashed with; older blocks predate it and are SHA-256
+    #[serde(default, skip_serializing_if = "HashAlgo::is_default")]
+    pub hash_algo: HashAlgo,
 }
 
 impl Participant {
@@ -105,11 +163,12 @@ impl Participant {
             score: 0.0, // Will be calculated later
             payout: Payout {
                 amount: 0.0,
-                currency: "TAO".to_string(),
+                currency: DEFAULT_CURRENCY_LABEL.to_string(),
                 url: String::new(),
             },
             salt: None,
             verified: false,
+            hash_algo: HashAlgo::default(),
         }
     }
 
@@ -119,12 +178,56 @@ impl Participant {
         self
     }
 
+    /// Record that the commitment was hashed with `hash_algo`
+    pub fn with_hash_algo(mut self, hash_algo: HashAlgo) -> Self {
+        self.hash_algo = hash_algo;
+        self
+    }
+
     /// Mark the participant as verified
     pub fn mark_verified(mut self) -> Self {
         self.verified = true;
         self
     }
 
+    /// Check the revealed guess and salt against the participant's commitment
+    ///
+    /// The guess is hashed as submitted (`Guess::raw_text`), not in its
+    /// normalized form. Returns false when no salt has been revealed.
+    pub fn verify_commitment(&self) -> bool {
+        self.verify_commitment_with(&CommitmentVerifier::new())
+    }
+
+    /// Check the reveal against the commitment using a specific verifier
+    ///
+    /// The participant's `hash_algo` overrides the verifier's.
+    pub fn verify_commitment_with(&self, verifier: &CommitmentVerifier) -> bool {
+        let verifier = verifier.clone().with_hash_algo(self.hash_algo);
+        match &self.salt {
+            Some(salt) => verifier.verify(self.guess.raw_text(), salt, &self.commitment),
+            None => false,
+        }
+    }
+
+    /// Verify the commitment, explaining why it failed if it did
+    ///
+    /// The participant's `hash_algo` overrides the verifier's.
+    pub fn explain_commitment_with(&self, verifier: &CommitmentVerifier) -> CommitmentVerification {
+        verifier
+            .clone()
+            .with_hash_algo(self.hash_algo)
+            .explain(self.guess.raw_text(), self.salt.as_deref(), &self.commitment)
+    }
+
+    /// Verify the commitment and mark the participant as verified on success
+    pub fn verify_and_mark(&mut self) -> bool {
+        let is_valid = self.verify_commitment();
+        if is_valid {
+            self.verified = true;
+        }
+        is_valid
+    }
+
     /// Set the guess URL
     pub fn with_guess_url(mut self, guess_url: String) -> Self {
         self.guess_url = guess_url;
@@ -169,7 +272,7 @@ pub struct Payout {
 pub struct ScoringResult {
     /// Reference to the participant
     pub participant: Participant,
-    /// Raw similarity score
+    /// Raw similarity score, before any adjustment
     pub raw_score: f64,
     /// Adjusted similarity score (if applicable)
     pub adjusted_score: Option<f64>,
@@ -177,6 +280,18 @@ pub struct ScoringResult {
     pub rank: Option<usize>,
     /// Calculated payout amount
     pub payout: Option<f64>,
+    /// Id of the scoring version that produced this result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring_version: Option<String>,
+    /// Name of the embedder that produced the similarity score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedder_name: Option<String>,
+    /// Name of the scoring strategy that produced the similarity score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_name: Option<String>,
+    /// When the score was computed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed_at: Option<DateTime<Utc>>,
 }
 
 impl ScoringResult {
@@ -188,9 +303,31 @@ impl ScoringResult {
             adjusted_score: None,
             rank: None,
             payout: None,
+            scoring_version: None,
+            embedder_name: None,
+            strategy_name: None,
+            computed_at: None,
         }
     }
 
+    /// Record which embedder and strategy produced the score, stamped with the current time
+    pub fn with_provenance(
+        mut self,
+        embedder_name: impl Into<String>,
+        strategy_name: impl Into<String>,
+    ) -> Self {
+        self.embedder_name = Some(embedder_name.into());
+        self.strategy_name = Some(strategy_name.into());
+        self.computed_at = Some(Utc::now());
+        self
+    }
+
+    /// Stamp the result with the scoring version that produced it
+    pub fn with_scoring_version(mut self, scoring_version: impl Into<String>) -> Self {
+        self.scoring_version = Some(scoring_version.into());
+        self
+    }
+
     /// Set the adjusted score
     pub fn with_adjusted_score(mut self, adjusted_score: f64) -> Self {
         self.adjusted_score = Some(adjusted_score);
@@ -249,6 +386,33 @@ pub enum BlockStatus {
     Cancelled,
 }
 
+/// Window a submission belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionPhase {
+    /// Commitments, accepted until the commitment deadline
+    Commitment,
+    /// Reveals, accepted between the commitment and reveal deadlines
+    Reveal,
+}
+
+/// When a submission arrived relative to its window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionTiming {
+    /// Inside the window
+    OnTime,
+    /// After the deadline, but inside the grace period that follows it
+    WithinGrace,
+    /// Outside the window and its grace period
+    Outside,
+}
+
+impl SubmissionTiming {
+    /// Whether the submission should be accepted
+    pub fn is_accepted(&self) -> bool {
+        !matches!(self, SubmissionTiming::Outside)
+    }
+}
+
 /// Complete data for a prediction block
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockData {
@@ -275,6 +439,21 @@ pub struct BlockData {
     /// Scoring results (if processed)
     #[serde(default)]
     pub results: Vec<ScoringResult>,
+    /// Id of the scoring version used to process the block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring_version: Option<String>,
+    /// Embedder to score this block with, overriding the processor's default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedder_spec: Option<EmbedderSpec>,
+    /// Caption of the target frame that reference-based strategies compare guesses against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_caption: Option<String>,
+    /// Name of the embedder the block was last scored with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedder_name: Option<String>,
+    /// Name of the scoring strategy the block was last scored with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_name: Option<String>,
     /// Timestamp when the block was created
     pub created_at: DateTime<Utc>,
     /// Timestamp when the block was last updated
@@ -302,6 +481,11 @@ impl BlockData {
             total_payout: 0.0, // Will be calculated later
             participants: Vec::new(),
             results: Vec::new(),
+            scoring_version: None,
+            embedder_spec: None,
+            reference_caption: None,
+            embedder_name: None,
+            strategy_name: None,
             created_at: now,
             updated_at: now,
         }
@@ -329,11 +513,39 @@ impl BlockData {
             total_payout: 0.0, // Will be calculated later
             participants: Vec::new(),
             results: Vec::new(),
+            scoring_version: None,
+            embedder_spec: None,
+            reference_caption: None,
+            embedder_name: None,
+            strategy_name: None,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Score this block with a specific embedder instead of the processor's default
+    pub fn with_embedder_spec(mut self, embedder_spec: EmbedderSpec) -> Self {
+        self.embedder_spec = Some(embedder_spec);
+        self
+    }
+
+    /// Compare guesses against `reference_caption` when the strategy uses a reference
+    pub fn with_reference_caption(mut self, reference_caption: impl Into<String>) -> Self {
+        self.reference_caption = Some(reference_caption.into());
+        self
+    }
+
+    /// Record which embedder and strategy the block was scored with
+    pub fn stamp_provenance(
+        &mut self,
+        embedder_name: impl Into<String>,
+        strategy_name: impl Into<String>,
+    ) {
+        self.embedder_name = Some(embedder_name.into());
+        self.strategy_name = Some(strategy_name.into());
+        self.updated_at = Utc::now();
+    }
+
     /// Add a participant to the block
     pub fn add_participant(&mut self, participant: Participant) {
         self.participants.push(participant);
@@ -353,11 +565,42 @@ impl BlockData {
         self.updated_at = Utc::now();
     }
 
+    /// Record the scoring version used to process the block
+    pub fn stamp_scoring_version(&mut self, scoring_version: impl Into<String>) {
+        self.scoring_version = Some(scoring_version.into());
+        self.updated_at = Utc::now();
+    }
+
+    /// Check whether the block was processed with a different scoring version
+    ///
+    /// Returns the recorded version id when it differs from `current`; blocks
+    /// that were never stamped don't count as a mismatch.
+    pub fn scoring_version_mismatch(&self, current: &str) -> Option<&str> {
+        self.scoring_version
+            .as_deref()
+            .filter(|recorded| *recorded != current)
+    }
+
     /// Get participants with verified commitments
     pub fn verified_participants(&self) -> Vec<&Participant> {
         self.participants.iter().filter(|p| p.verified).collect()
     }
 
+    /// Compact, serializable status report without participant detail
+    pub fn summary(&self) -> BlockSummary {
+        BlockSummary {
+            block_num: self.block_num.clone(),
+            status: self.status.clone(),
+            participant_count: self.participants.len(),
+            verified_count: self.verified_participants().len(),
+            prize_pool: self.prize_pool,
+            total_payout: self.total_payout,
+            commitment_deadline: self.commitment_deadline,
+            reveal_deadline: self.reveal_deadline,
+            created_at: self.created_at,
+        }
+    }
+
     /// Check if the block is open for submissions
     pub fn is_open(&self) -> bool {
         matches!(self.status, BlockStatus::Open)
@@ -367,6 +610,73 @@ impl BlockData {
     pub fn is_complete(&self) -> bool {
         matches!(self.status, BlockStatus::Complete)
     }
+
+    /// Derive the status the block should have at `now` from its deadlines
+    ///
+    /// An `Open` block becomes `Processing` once the reveal deadline has passed;
+    /// any other stored status is returned unchanged.
+    pub fn computed_status(&self, now: DateTime<Utc>) -> BlockStatus {
+        match self.status {
+            BlockStatus::Open if now > self.reveal_deadline => BlockStatus::Processing,
+            ref status => status.clone(),
+        }
+    }
+
+    /// Check if a commitment made at `now` is on time (up to and including the commitment deadline)
+    pub fn is_accepting_commitments(&self, now: DateTime<Utc>) -> bool {
+        self.computed_status(now) == BlockStatus::Open && now <= self.commitment_deadline
+    }
+
+    /// Check if a reveal made at `now` is on time (after the commitment deadline, up to and including the reveal deadline)
+    pub fn is_accepting_reveals(&self, now: DateTime<Utc>) -> bool {
+        self.computed_status(now) == BlockStatus::Open
+            && now > self.commitment_deadline
+            && now <= self.reveal_deadline
+    }
+
+    /// Timing of a submission made at `at` in the given phase, allowing `grace` after its deadline
+    ///
+    /// Reveals before the commitment deadline get no grace: they are outside the window.
+    pub fn submission_timing(
+        &self,
+        phase: SubmissionPhase,
+        at: DateTime<Utc>,
+        grace: chrono::Duration,
+    ) -> SubmissionTiming {
+        let (on_time, deadline) = match phase {
+            SubmissionPhase::Commitment => (self.is_accepting_commitments(at), self.commitment_deadline),
+            SubmissionPhase::Reveal => (self.is_accepting_reveals(at), self.reveal_deadline),
+        };
+        if on_time {
+            SubmissionTiming::OnTime
+        } else if self.is_open() && within_grace(at, deadline, grace) {
+            SubmissionTiming::WithinGrace
+        } else {
+            SubmissionTiming::Outside
+        }
+    }
+}
+
+/// Whether `at` is after `deadline` but no more than `grace` after it
+fn within_grace(at: DateTime<Utc>, deadline: DateTime<Utc>, grace: chrono::Duration) -> bool {
+    at > deadline && at <= deadline + grace
+}
+
+/// Summary of a block for dashboards and status APIs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BlockSummary {
+    pub block_num: String,
+    pub status: BlockStatus,
+    /// All participants, verified or not
+    pub participant_count: usize,
+    /// Participants whose commitments have been verified
+    pub verified_count: usize,
+    pub prize_pool: f64,
+    pub total_payout: f64,
+    pub commitment_deadline: DateTime<Utc>,
+    pub reveal_deadline: DateTime<Utc>,
+    #[serde(default)]
+    pub created_at: DateTime<Utc>,
 }
 
 /// Payout result for a participant
@@ -7,9 +7,10 @@
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use cliptions_core::block_engine::state_machine::*;
+use cliptions_core::clock::SystemClock;
 use mockall::mock;
 use std::path::Path;
-use twitter_api::{PostTweetResult, Tweet, TwitterApi, TwitterError};
+use twitter_api::{PostTweetResult, SearchResult, SearchScope, Tweet, TwitterApi, TwitterError};
 
 mock! {
     pub TwitterApiClient {
@@ -36,7 +37,13 @@ mock! {
             username: &str,
             exclude_retweets_replies: bool,
         ) -> Result<Option<Tweet>, TwitterError>;
-        async fn search_replies(&self, tweet_id: &str, max_results: u32) -> Result<Vec<Tweet>, TwitterError>;
+        async fn get_user_tweets(&self, username: &str, max_results: u32) -> Result<Vec<Tweet>, TwitterError>;
+        async fn search_replies(&self, tweet_id: &str, max_results: u32, scope: SearchScope, since_id: Option<String>) -> Result<Vec<Tweet>, TwitterError>;
+        async fn search_replies_paged(&self, tweet_id: &str, max_results: u32, scope: SearchScope, since_id: Option<String>, max_pages: Option<u32>) -> Result<SearchResult, TwitterError>;
+        async fn quote_tweet(&self, text: &str, quoted_tweet_url: &str) -> Result<PostTweetResult, TwitterError>;
+        async fn delete_tweet(&self, tweet_id: &str) -> Result<bool, TwitterError>;
+        async fn get_authenticated_user(&self) -> Result<twitter_api::TwitterUser, TwitterError>;
+        async fn get_users_by_username(&self, usernames: &[String]) -> Result<Vec<twitter_api::TwitterUser>, TwitterError>;
     }
 }
 
@@ -49,6 +56,8 @@ fn create_mock_tweet(id: &str, text: &str) -> Tweet {
         conversation_id: None,
         public_metrics: None,
         url: format!("https://twitter.com/i/status/{}", id),
+        in_reply_to_user_id: None,
+        referenced_tweets: Vec::new(),
     }
 }
 
@@ -161,7 +170,7 @@ async fn test_full_block_lifecycle_with_mocks() {
     let mut commitments_closed_block = commitments_closed_block;
     commitments_closed_block.target_timestamp = Utc::now() - Duration::seconds(1);
     let frame_captured_block = commitments_closed_block
-        .capture_frame(target_frame_path.clone())
+        .capture_frame(target_frame_path.clone(), &SystemClock)
         .unwrap();
     assert_eq!(frame_captured_block.state_name(), "FrameCaptured");
     assert_eq!(
@@ -295,7 +304,7 @@ async fn test_block_lifecycle_with_machine_readable_tweets() {
     commitments_closed_block.target_timestamp = now - Duration::seconds(1);
     let test_frame_path: std::path::PathBuf = "test_frame.jpg".into();
     let frame_captured_block = commitments_closed_block
-        .capture_frame(test_frame_path.clone())
+        .capture_frame(test_frame_path.clone(), &SystemClock)
         .unwrap();
     let reveals_block = frame_captured_block
         .open_reveals(
@@ -5,13 +5,26 @@
 
 use tempfile::NamedTempFile;
 
-use cliptions_core::commitment::{CommitmentGenerator, CommitmentVerifier};
+use cliptions_core::commitment::{CommitmentGenerator, CommitmentVerifier, HashAlgo};
 use cliptions_core::embedder::{EmbedderTrait, MockEmbedder};
 use cliptions_core::block_processor::BlockProcessor;
 use cliptions_core::scoring::{
-    calculate_payouts, calculate_rankings, ClipBatchStrategy, ScoreValidator,
+    calculate_payouts, rank_items, ClipBatchStrategy, ScoreValidator,
 };
-use cliptions_core::types::{Guess, Participant, BlockConfig, BlockData, BlockStatus};
+use cliptions_core::error::CliptionsError;
+use cliptions_core::types::{Guess, Participant, BlockConfig, BlockData, BlockStatus, MAX_GUESS_LENGTH};
+
+/// Rank guesses as owned (guess, score) pairs, ready for `calculate_payouts`
+fn rank_guesses(
+    guesses: &[String],
+    validator: &ScoreValidator<MockEmbedder, ClipBatchStrategy>,
+) -> Vec<(String, f64)> {
+    rank_items("test.jpg", guesses, validator)
+        .unwrap()
+        .into_iter()
+        .map(|(guess, score)| (guess.clone(), score))
+        .collect()
+}
 
 #[test]
 fn test_complete_block_lifecycle() {
@@ -157,7 +170,7 @@ fn test_ranking_and_payout_calculation() {
     ];
 
     // Calculate rankings
-    let rankings = calculate_rankings("test.jpg", &guesses, &validator).unwrap();
+    let rankings = rank_guesses(&guesses, &validator);
     assert_eq!(rankings.len(), 4);
 
     // Should be sorted by score (highest first)
@@ -317,7 +330,7 @@ fn test_error_handling() {
     let embedder = MockEmbedder::new(128);
     let strategy = ClipBatchStrategy::new();
     let validator = ScoreValidator::new(embedder, strategy);
-    assert!(calculate_rankings("test.jpg", &[], &validator).is_err());
+    assert!(rank_items::<String, _, _>("test.jpg", &[], &validator).is_err());
 
     // 3. Empty salt for commitment
     let generator = CommitmentGenerator::new();
@@ -349,7 +362,7 @@ fn test_performance_characteristics() {
             .collect();
 
         let start = Instant::now();
-        let rankings = calculate_rankings("test.jpg", &guesses, &validator).unwrap();
+        let rankings = rank_guesses(&guesses, &validator);
         let ranking_time = start.elapsed();
 
         let start = Instant::now();
@@ -422,3 +435,190 @@ fn test_deterministic_behavior() {
 
     assert_eq!(commitment1, commitment2);
 }
+
+#[test]
+fn test_block_deadline_status() {
+    use chrono::{Duration, Utc};
+
+    let commitment_deadline = Utc::now();
+    let reveal_deadline = commitment_deadline + Duration::hours(24);
+    let block = BlockData::with_deadlines(
+        "1".to_string(),
+        "target.jpg".to_string(),
+        "123".to_string(),
+        100.0,
+        commitment_deadline,
+        reveal_deadline,
+    );
+    let second = Duration::seconds(1);
+
+    // Before the commitment deadline
+    assert_eq!(block.computed_status(commitment_deadline - second), BlockStatus::Open);
+    assert!(block.is_accepting_commitments(commitment_deadline - second));
+    assert!(!block.is_accepting_reveals(commitment_deadline - second));
+
+    // At the commitment deadline
+    assert!(block.is_accepting_commitments(commitment_deadline));
+    assert!(!block.is_accepting_reveals(commitment_deadline));
+
+    // Between the deadlines
+    assert_eq!(block.computed_status(commitment_deadline + second), BlockStatus::Open);
+    assert!(!block.is_accepting_commitments(commitment_deadline + second));
+    assert!(block.is_accepting_reveals(commitment_deadline + second));
+
+    // At the reveal deadline
+    assert_eq!(block.computed_status(reveal_deadline), BlockStatus::Open);
+    assert!(block.is_accepting_reveals(reveal_deadline));
+
+    // After the reveal deadline
+    assert_eq!(block.computed_status(reveal_deadline + second), BlockStatus::Processing);
+    assert!(!block.is_accepting_commitments(reveal_deadline + second));
+    assert!(!block.is_accepting_reveals(reveal_deadline + second));
+
+    // Terminal statuses are never overridden by the clock
+    let mut cancelled = block.clone();
+    cancelled.set_status(BlockStatus::Cancelled);
+    assert_eq!(cancelled.computed_status(commitment_deadline - second), BlockStatus::Cancelled);
+    assert!(!cancelled.is_accepting_commitments(commitment_deadline - second));
+}
+
+#[test]
+fn test_block_deadline_grace() {
+    use chrono::{Duration, Utc};
+    use cliptions_core::types::SubmissionPhase::{Commitment, Reveal};
+    use cliptions_core::types::SubmissionTiming;
+
+    let commitment_deadline = Utc::now();
+    let reveal_deadline = commitment_deadline + Duration::hours(24);
+    let block = BlockData::with_deadlines(
+        "1".to_string(),
+        "target.jpg".to_string(),
+        "123".to_string(),
+        100.0,
+        commitment_deadline,
+        reveal_deadline,
+    );
+    let grace = Duration::seconds(60);
+    let second = Duration::seconds(1);
+
+    // Exactly at the deadline is on time; just past it falls in the grace period
+    assert_eq!(
+        block.submission_timing(Commitment, commitment_deadline, grace),
+        SubmissionTiming::OnTime
+    );
+    assert_eq!(
+        block.submission_timing(Commitment, commitment_deadline + second, grace),
+        SubmissionTiming::WithinGrace
+    );
+    assert_eq!(
+        block.submission_timing(Commitment, commitment_deadline + grace, grace),
+        SubmissionTiming::WithinGrace
+    );
+    assert_eq!(
+        block.submission_timing(Commitment, commitment_deadline + grace + second, grace),
+        SubmissionTiming::Outside
+    );
+
+    assert_eq!(block.submission_timing(Reveal, reveal_deadline, grace), SubmissionTiming::OnTime);
+    assert_eq!(
+        block.submission_timing(Reveal, reveal_deadline + second, grace),
+        SubmissionTiming::WithinGrace
+    );
+    assert_eq!(
+        block.submission_timing(Reveal, reveal_deadline + grace + second, grace),
+        SubmissionTiming::Outside
+    );
+
+    // Cancelled blocks accept nothing, grace or not
+    let mut cancelled = block.clone();
+    cancelled.set_status(BlockStatus::Cancelled);
+    assert!(!cancelled
+        .submission_timing(Reveal, reveal_deadline + second, grace)
+        .is_accepted());
+}
+
+#[test]
+fn test_participant_verify_commitment() {
+    let generator = CommitmentGenerator::new();
+    let guess = "a cat sitting on a fence";
+    let commitment = generator.generate(guess, "right_salt").unwrap();
+    let participant = |salt: Option<&str>| {
+        let participant = Participant::new(
+            "user1".to_string(),
+            "alice".to_string(),
+            Guess::new(guess.to_string()),
+            commitment.clone(),
+        );
+        match salt {
+            Some(salt) => participant.with_salt(salt.to_string()),
+            None => participant,
+        }
+    };
+
+    // Valid reveal
+    let mut valid = participant(Some("right_salt"));
+    assert!(valid.verify_commitment());
+    assert!(!valid.verified);
+    assert!(valid.verify_and_mark());
+    assert!(valid.verified);
+
+    // Wrong salt
+    let mut wrong_salt = participant(Some("wrong_salt"));
+    assert!(!wrong_salt.verify_commitment());
+    assert!(!wrong_salt.verify_and_mark());
+    assert!(!wrong_salt.verified);
+
+    // Missing salt
+    let mut missing_salt = participant(None);
+    assert!(!missing_salt.verify_commitment());
+    assert!(!missing_salt.verify_and_mark());
+    assert!(!missing_salt.verified);
+
+    // A BLAKE3 commitment only verifies once the participant records its algorithm
+    let blake3_commitment = CommitmentGenerator::new()
+        .with_hash_algo(HashAlgo::Blake3)
+        .generate(guess, "right_salt")
+        .unwrap();
+    let blake3 = Participant::new(
+        "user1".to_string(),
+        "alice".to_string(),
+        Guess::new(guess.to_string()),
+        blake3_commitment,
+    )
+    .with_salt("right_salt".to_string());
+    assert!(!blake3.verify_commitment());
+    let blake3 = blake3.with_hash_algo(HashAlgo::Blake3);
+    assert!(blake3.verify_commitment());
+    assert!(blake3.explain_commitment_with(&CommitmentVerifier::new()).valid);
+}
+
+#[test]
+fn test_guess_new_checked() {
+    // Valid guesses are normalized like `Guess::new`
+    let guess = Guess::new_checked("  A Red   Fox ".to_string()).unwrap();
+    let unchecked = Guess::new("  A Red   Fox ".to_string());
+    assert_eq!(guess.text, unchecked.text);
+    assert_eq!(guess.raw_text(), "  A Red   Fox ");
+    assert!(Guess::new_checked("x".repeat(MAX_GUESS_LENGTH)).is_ok());
+
+    // Empty and whitespace-only
+    for text in ["", "   ", "\t \n"] {
+        let error = Guess::new_checked(text.to_string()).unwrap_err();
+        assert!(matches!(error, CliptionsError::ValidationError(_)));
+    }
+    assert!(Guess::new_checked(" ".to_string())
+        .unwrap_err()
+        .to_string()
+        .contains("at least 1 non-whitespace characters, got 0"));
+
+    // Over-length
+    let error = Guess::new_checked("x".repeat(MAX_GUESS_LENGTH + 1)).unwrap_err();
+    assert!(error.to_string().contains("at most 300 characters, got 301"));
+
+    // Control characters
+    let error = Guess::new_checked("a red\u{0}fox".to_string()).unwrap_err();
+    assert!(error.to_string().contains("control character"));
+
+    // The infallible constructor still accepts anything, e.g. for stored data
+    assert_eq!(Guess::new(String::new()).text, "");
+}
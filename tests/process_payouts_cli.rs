@@ -0,0 +1,76 @@
+//! End-to-end tests for the process_payouts binary
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use cliptions_core::commitment::CommitmentGenerator;
+use cliptions_core::types::{BlockData, Guess, Participant};
+
+/// A single verified two-player block, serialized as a blocks file
+fn blocks_json() -> String {
+    let target_image = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/City street with vintage cars and neon signs at night.png"
+    );
+    let mut block = BlockData::new(
+        "test_block".to_string(),
+        target_image.to_string(),
+        "test_social_id".to_string(),
+        30.0,
+    );
+    let commitment_gen = CommitmentGenerator::new();
+    for (i, guess) in ["a red car", "a blue boat"].iter().enumerate() {
+        let salt = format!("salt{}", i);
+        let commitment = commitment_gen.generate(guess, &salt).unwrap();
+        block.add_participant(
+            Participant::new(
+                format!("user{}", i),
+                format!("user_user{}", i),
+                Guess::new(guess.to_string()),
+                commitment,
+            )
+            .with_salt(salt)
+            .mark_verified(),
+        );
+    }
+    serde_json::to_string(&HashMap::from([("test_block".to_string(), block)])).unwrap()
+}
+
+#[test]
+fn test_verbose_stdio_output_keeps_stdout_json_only() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_process_payouts"))
+        .args([
+            "--block", "test_block",
+            "--blocks-file", "-",
+            "--output", "json",
+            "--output-file", "-",
+            "--use-mock",
+            "--verbose",
+            "--no-color",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(blocks_json().as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(output.status.success(), "stderr: {}", stderr);
+    let results: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout is not JSON ({}): {}", e, stdout));
+    assert_eq!(results["summary"]["total_blocks_processed"], 1);
+
+    // The verbose lines still show up, just not on stdout
+    assert!(stderr.contains("Using MockEmbedder for testing"));
+    assert!(stderr.contains("Processing block: test_block"));
+    assert!(stderr.contains("Payout processing completed successfully"));
+}